@@ -0,0 +1,283 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use derive_more::AsRef;
+use eyre::Result;
+use futures_util::future::try_join_all;
+use prometheus::{CounterVec, IntCounterVec};
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::{debug, error, info, info_span, instrument::Instrumented, warn, Instrument};
+
+use hyperlane_base::{
+    metrics::AgentMetrics, settings::ChainConf, spawn_named, AgentMetadata, BaseAgent,
+    ChainMetrics, CoreMetrics, HyperlaneAgentCore,
+};
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, HyperlaneDomain, HyperlaneProvider, NativeAmount,
+    NativeTokenTransfer, TxOutcome,
+};
+
+use crate::{
+    limits::FundingLimiter,
+    settings::{FundedKeyConf, FunderSettings},
+};
+
+/// Metrics of the funder.
+#[derive(Debug, Clone)]
+struct FunderMetrics {
+    /// Funds sent to keys, scaled to the native token's decimals.
+    funds_sent: CounterVec,
+    /// Top up attempts by outcome.
+    top_ups: IntCounterVec,
+}
+
+impl FunderMetrics {
+    fn new(metrics: &CoreMetrics) -> Result<Self> {
+        Ok(Self {
+            funds_sent: metrics.new_counter(
+                "funder_funds_sent",
+                "Native tokens sent by the funder to a key",
+                &["chain", "key_name", "key_address"],
+            )?,
+            top_ups: metrics.new_int_counter(
+                "funder_top_ups",
+                "Number of top ups attempted by the funder, by outcome",
+                &["chain", "key_name", "outcome"],
+            )?,
+        })
+    }
+}
+
+/// A funder agent
+#[derive(Debug, AsRef)]
+pub struct Funder {
+    #[as_ref]
+    core: HyperlaneAgentCore,
+    chain_funders: Vec<ChainFunder>,
+    core_metrics: Arc<CoreMetrics>,
+}
+
+#[async_trait]
+impl BaseAgent for Funder {
+    const AGENT_NAME: &'static str = "funder";
+
+    type Settings = FunderSettings;
+
+    async fn from_settings(
+        _agent_metadata: AgentMetadata,
+        settings: Self::Settings,
+        metrics: Arc<CoreMetrics>,
+        _agent_metrics: AgentMetrics,
+        _chain_metrics: ChainMetrics,
+    ) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        let core = settings.build_hyperlane_core(metrics.clone());
+        let funder_metrics = FunderMetrics::new(&metrics)?;
+
+        let mut chain_funders = Vec::with_capacity(settings.chains_to_fund.len());
+        for domain in &settings.chains_to_fund {
+            let chain_conf = settings.chain_setup(domain)?;
+            let keys = settings
+                .funded_keys
+                .iter()
+                .filter(|key| key.chain == domain.name())
+                .cloned()
+                .collect();
+            let limits = settings
+                .chain_limits
+                .get(domain.name())
+                .cloned()
+                .unwrap_or_default();
+            chain_funders.push(
+                ChainFunder::new(
+                    chain_conf,
+                    &metrics,
+                    keys,
+                    FundingLimiter::new(limits),
+                    settings.interval,
+                    funder_metrics.clone(),
+                )
+                .await?,
+            );
+        }
+
+        Ok(Self {
+            core,
+            chain_funders,
+            core_metrics: metrics,
+        })
+    }
+
+    #[allow(clippy::async_yields_async)]
+    async fn run(self) {
+        let mut tasks = vec![];
+
+        let server = self
+            .core
+            .settings
+            .server(self.core_metrics.clone())
+            .expect("Failed to create server");
+        tasks.push(server.run().instrument(info_span!("Funder server")));
+
+        for chain_funder in self.chain_funders {
+            tasks.push(chain_funder.spawn());
+        }
+
+        // Note that this only returns an error if one of the tasks panics
+        if let Err(err) = try_join_all(tasks).await {
+            error!(?err, "One of the funder tasks returned an error");
+        }
+    }
+}
+
+/// Keeps the configured keys on one chain funded from the chain's signer.
+#[derive(Debug)]
+struct ChainFunder {
+    domain: HyperlaneDomain,
+    provider: Box<dyn HyperlaneProvider>,
+    transfer: Box<dyn NativeTokenTransfer>,
+    keys: Vec<FundedKeyConf>,
+    limiter: FundingLimiter,
+    interval: Duration,
+    metrics: FunderMetrics,
+}
+
+/// Whether a top up may have sent funds. Top ups whose result is unknown, e.g.
+/// because their receipt timed out, count against the funding limits.
+fn may_have_sent_funds(result: &ChainResult<TxOutcome>) -> bool {
+    match result {
+        // Reverted transfers don't move funds
+        Ok(outcome) => outcome.executed,
+        // Nothing is sent without a signer
+        Err(ChainCommunicationError::SignerUnavailable) => false,
+        Err(_) => true,
+    }
+}
+
+impl ChainFunder {
+    async fn new(
+        chain_conf: &ChainConf,
+        core_metrics: &CoreMetrics,
+        keys: Vec<FundedKeyConf>,
+        limiter: FundingLimiter,
+        interval: Duration,
+        metrics: FunderMetrics,
+    ) -> Result<Self> {
+        Ok(Self {
+            domain: chain_conf.domain.clone(),
+            provider: chain_conf.build_provider(core_metrics).await?,
+            transfer: chain_conf.build_native_token_transfer(core_metrics).await?,
+            keys,
+            limiter,
+            interval,
+            metrics,
+        })
+    }
+
+    fn record_outcome(&self, key: &FundedKeyConf, outcome: &str) {
+        self.metrics
+            .top_ups
+            .with_label_values(&[self.domain.name(), &key.name, outcome])
+            .inc();
+    }
+
+    async fn fund_key(&mut self, key: &FundedKeyConf) {
+        let balance = match self.provider.get_balance(key.address.clone()).await {
+            Ok(balance) => balance,
+            Err(err) => {
+                warn!(key=%key.name, address=%key.address, ?err, "Failed to fetch key balance");
+                return;
+            }
+        };
+        if balance >= key.threshold {
+            debug!(key=%key.name, address=%key.address, %balance, "Key is funded");
+            return;
+        }
+
+        let desired = key.target.saturating_sub(balance);
+        let amount = self.limiter.allowance(Instant::now(), desired);
+        if amount.is_zero() {
+            warn!(key=%key.name, address=%key.address, %balance, %desired, "Funding limit reached, not topping up key");
+            self.record_outcome(key, "limited");
+            return;
+        }
+
+        info!(key=%key.name, address=%key.address, %balance, %amount, treasury=%self.transfer.sender_address(), "Topping up key");
+        let result = self
+            .transfer
+            .transfer_native(key.address.clone(), amount)
+            .await;
+        if may_have_sent_funds(&result) {
+            self.limiter.record(Instant::now(), amount);
+        }
+        match result {
+            Ok(outcome) if outcome.executed => {
+                self.metrics
+                    .funds_sent
+                    .with_label_values(&[self.domain.name(), &key.name, &key.address])
+                    .inc_by(
+                        NativeAmount::of_protocol(amount, self.domain.domain_protocol())
+                            .to_whole_f64(),
+                    );
+                self.record_outcome(key, "success");
+                info!(key=%key.name, address=%key.address, %amount, tx_outcome=?outcome, "Topped up key");
+            }
+            Ok(outcome) => {
+                self.record_outcome(key, "reverted");
+                error!(key=%key.name, address=%key.address, %amount, tx_outcome=?outcome, "Top up transaction reverted");
+            }
+            Err(err) => {
+                self.record_outcome(key, "failed");
+                error!(key=%key.name, address=%key.address, %amount, ?err, "Failed to top up key");
+            }
+        }
+    }
+
+    fn spawn(mut self) -> Instrumented<JoinHandle<()>> {
+        let span = info_span!("ChainFunder", domain=%self.domain);
+        let domain = self.domain.clone();
+        spawn_named("chain_funder", Some(&domain), async move {
+            let mut interval = tokio::time::interval(self.interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            let keys = std::mem::take(&mut self.keys);
+            loop {
+                interval.tick().await;
+                for key in &keys {
+                    self.fund_key(key).await;
+                }
+            }
+        })
+        .instrument(span)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::{FixedPointNumber, H512, U256};
+
+    use super::*;
+
+    #[test]
+    fn test_may_have_sent_funds() {
+        let outcome = |executed| TxOutcome {
+            transaction_id: H512::zero(),
+            executed,
+            gas_used: U256::zero(),
+            gas_price: FixedPointNumber::zero(),
+        };
+
+        assert!(may_have_sent_funds(&Ok(outcome(true))));
+        assert!(!may_have_sent_funds(&Ok(outcome(false))));
+        assert!(!may_have_sent_funds(&Err(
+            ChainCommunicationError::SignerUnavailable
+        )));
+        assert!(may_have_sent_funds(&Err(
+            ChainCommunicationError::from_other_str("receipt timed out")
+        )));
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use hyperlane_core::U256;
+
+use crate::settings::ChainFundingLimitsConf;
+
+/// The window `maxPerDay` applies to.
+const DAILY_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Tracks the funds sent on a chain and caps top ups to the configured
+/// per-top-up and rolling daily limits.
+#[derive(Debug)]
+pub struct FundingLimiter {
+    limits: ChainFundingLimitsConf,
+    /// Amounts sent within the last `DAILY_WINDOW`, oldest first.
+    sent: VecDeque<(Instant, U256)>,
+}
+
+impl FundingLimiter {
+    pub fn new(limits: ChainFundingLimitsConf) -> Self {
+        Self {
+            limits,
+            sent: VecDeque::new(),
+        }
+    }
+
+    fn prune(&mut self, now: Instant) {
+        while let Some((sent_at, _)) = self.sent.front() {
+            if now.saturating_duration_since(*sent_at) < DAILY_WINDOW {
+                break;
+            }
+            self.sent.pop_front();
+        }
+    }
+
+    /// Total amount sent within the last 24 hours.
+    pub fn sent_in_window(&mut self, now: Instant) -> U256 {
+        self.prune(now);
+        self.sent
+            .iter()
+            .fold(U256::zero(), |acc, (_, amount)| acc.saturating_add(*amount))
+    }
+
+    /// The largest amount, up to `desired`, that can be sent right now without
+    /// exceeding any limit.
+    pub fn allowance(&mut self, now: Instant, desired: U256) -> U256 {
+        let mut allowed = desired;
+        if let Some(max_per_top_up) = self.limits.max_per_top_up {
+            allowed = allowed.min(max_per_top_up);
+        }
+        if let Some(max_per_day) = self.limits.max_per_day {
+            let remaining = max_per_day.saturating_sub(self.sent_in_window(now));
+            allowed = allowed.min(remaining);
+        }
+        allowed
+    }
+
+    /// Record that `amount` was sent at `now`.
+    pub fn record(&mut self, now: Instant, amount: U256) {
+        self.prune(now);
+        self.sent.push_back((now, amount));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn limiter(max_per_top_up: Option<u64>, max_per_day: Option<u64>) -> FundingLimiter {
+        FundingLimiter::new(ChainFundingLimitsConf {
+            max_per_top_up: max_per_top_up.map(Into::into),
+            max_per_day: max_per_day.map(Into::into),
+        })
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let mut limiter = limiter(None, None);
+        let now = Instant::now();
+        limiter.record(now, 1_000.into());
+        assert_eq!(limiter.allowance(now, 5_000.into()), 5_000.into());
+    }
+
+    #[test]
+    fn test_max_per_top_up() {
+        let mut limiter = limiter(Some(100), None);
+        let now = Instant::now();
+        assert_eq!(limiter.allowance(now, 500.into()), 100.into());
+        assert_eq!(limiter.allowance(now, 50.into()), 50.into());
+    }
+
+    #[test]
+    fn test_max_per_day_is_rolling() {
+        let mut limiter = limiter(None, Some(1_000));
+        let start = Instant::now();
+        limiter.record(start, 600.into());
+        limiter.record(start + Duration::from_secs(60 * 60), 300.into());
+
+        let later = start + Duration::from_secs(2 * 60 * 60);
+        assert_eq!(limiter.sent_in_window(later), 900.into());
+        assert_eq!(limiter.allowance(later, 500.into()), 100.into());
+
+        // The first top up falls out of the window after 24 hours
+        let next_day = start + DAILY_WINDOW;
+        assert_eq!(limiter.sent_in_window(next_day), 300.into());
+        assert_eq!(limiter.allowance(next_day, 500.into()), 500.into());
+    }
+
+    #[test]
+    fn test_daily_limit_exhausted() {
+        let mut limiter = limiter(Some(500), Some(1_000));
+        let now = Instant::now();
+        limiter.record(now, 1_000.into());
+        assert_eq!(limiter.allowance(now, 500.into()), U256::zero());
+    }
+}
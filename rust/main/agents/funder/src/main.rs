@@ -0,0 +1,26 @@
+//! The funder tops up the balances of agent keys from a treasury account,
+//! within per-chain spending limits.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use std::process::ExitCode;
+
+use eyre::Result;
+
+use hyperlane_base::{agent_main, AgentOutcome};
+
+use crate::funder::Funder;
+
+mod funder;
+mod limits;
+mod settings;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<ExitCode> {
+    let outcome = agent_main::<Funder>().await?;
+    if let AgentOutcome::ConfigValidated(report) = &outcome {
+        println!("{}", report.to_json()?);
+    }
+    Ok(outcome.exit_code())
+}
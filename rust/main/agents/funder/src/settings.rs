@@ -0,0 +1,195 @@
+//! Funder configuration.
+//!
+//! The correct settings shape is defined in the TypeScript SDK metadata. While the exact shape
+//! and validations it defines are not applied here, we should mirror them.
+//! ANY CHANGES HERE NEED TO BE REFLECTED IN THE TYPESCRIPT SDK.
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use eyre::Context;
+use hyperlane_base::{
+    impl_loadable_from_settings,
+    settings::{
+        parser::{RawAgentConf, ValueParser},
+        Settings,
+    },
+};
+use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain, U256};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Settings for `Funder`
+#[derive(Debug, AsRef, AsMut, Deref, DerefMut)]
+pub struct FunderSettings {
+    #[as_ref]
+    #[as_mut]
+    #[deref]
+    #[deref_mut]
+    base: Settings,
+
+    /// Chains keys are funded on. The signer configured for each of these
+    /// chains is the treasury funds are sent from.
+    pub chains_to_fund: Vec<HyperlaneDomain>,
+    /// Keys to keep funded
+    pub funded_keys: Vec<FundedKeyConf>,
+    /// Spending limits by chain name
+    pub chain_limits: HashMap<String, ChainFundingLimitsConf>,
+    /// How frequently to check key balances
+    pub interval: Duration,
+}
+
+/// A key kept funded by the funder.
+#[derive(Debug, Clone)]
+pub struct FundedKeyConf {
+    /// Name of the chain the key is funded on
+    pub chain: String,
+    /// Human readable name of the key, e.g. `relayer`
+    pub name: String,
+    /// Address of the key
+    pub address: String,
+    /// The key is topped up once its balance drops below this amount
+    pub threshold: U256,
+    /// The balance the key is topped up to
+    pub target: U256,
+}
+
+/// Limits on how much the funder sends on a chain.
+#[derive(Debug, Clone, Default)]
+pub struct ChainFundingLimitsConf {
+    /// Maximum amount sent in a single top up
+    pub max_per_top_up: Option<U256>,
+    /// Maximum amount sent in any rolling 24 hour window
+    pub max_per_day: Option<U256>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+struct RawFunderSettings(Value);
+
+impl_loadable_from_settings!(Funder, RawFunderSettings -> FunderSettings);
+
+impl FromRawConf<RawFunderSettings> for FunderSettings {
+    fn from_config_filtered(
+        raw: RawFunderSettings,
+        cwp: &ConfigPath,
+        _filter: (),
+    ) -> ConfigResult<Self> {
+        let mut err = ConfigParsingError::default();
+
+        let p = ValueParser::new(cwp.clone(), &raw.0);
+
+        let funded_keys: Vec<FundedKeyConf> = p
+            .chain(&mut err)
+            .get_key("fundedKeys")
+            .into_array_iter()
+            .map(|itr| {
+                itr.filter_map(|key| {
+                    let chain = key.chain(&mut err).get_key("chain").parse_string().end();
+                    let name = key.chain(&mut err).get_key("name").parse_string().end();
+                    let address = key.chain(&mut err).get_key("address").parse_string().end();
+                    let threshold = key.chain(&mut err).get_key("threshold").parse_u256().end();
+                    let target = key.chain(&mut err).get_key("target").parse_u256().end();
+
+                    let (Some(chain), Some(name), Some(address), Some(threshold), Some(target)) =
+                        (chain, name, address, threshold, target)
+                    else {
+                        return None;
+                    };
+                    if target < threshold {
+                        err.push(
+                            &key.cwp + "target",
+                            eyre::eyre!("Funding target must not be below the threshold"),
+                        );
+                        return None;
+                    }
+
+                    Some(FundedKeyConf {
+                        chain: chain.to_owned(),
+                        name: name.to_owned(),
+                        address: address.to_owned(),
+                        threshold,
+                        target,
+                    })
+                })
+                .collect()
+            })
+            .unwrap_or_default();
+
+        let chain_names_to_fund: HashSet<&str> =
+            funded_keys.iter().map(|key| key.chain.as_str()).collect();
+
+        let base = p
+            .parse_from_raw_config::<Settings, RawAgentConf, Option<&HashSet<&str>>>(
+                Some(&chain_names_to_fund),
+                "Parsing base config",
+            )
+            .take_config_err(&mut err);
+
+        let chains_to_fund = if let Some(base) = &base {
+            chain_names_to_fund
+                .iter()
+                .filter_map(|chain| {
+                    base.lookup_domain(chain)
+                        .context("Missing configuration for a chain in `fundedKeys`")
+                        .into_config_result(|| cwp + "funded_keys")
+                        .take_config_err(&mut err)
+                })
+                .collect()
+        } else {
+            Default::default()
+        };
+
+        let chain_limits = p
+            .chain(&mut err)
+            .get_opt_key("chainLimits")
+            .into_obj_iter()
+            .map(|itr| {
+                itr.map(|(chain, limits)| {
+                    let max_per_top_up = limits
+                        .chain(&mut err)
+                        .get_opt_key("maxPerTopUp")
+                        .parse_u256()
+                        .end();
+                    let max_per_day = limits
+                        .chain(&mut err)
+                        .get_opt_key("maxPerDay")
+                        .parse_u256()
+                        .end();
+                    (
+                        chain,
+                        ChainFundingLimitsConf {
+                            max_per_top_up,
+                            max_per_day,
+                        },
+                    )
+                })
+                .collect()
+            })
+            .unwrap_or_default();
+
+        let interval = p.chain(&mut err).get_opt_key("interval").parse_u64().end();
+        if interval == Some(0) {
+            err.push(
+                cwp + "interval",
+                eyre::eyre!("Funding interval must be greater than 0"),
+            );
+        }
+        let interval = interval
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(60));
+
+        cfg_unwrap_all!(&p.cwp, err: [base]);
+
+        err.into_result(Self {
+            base,
+            chains_to_fund,
+            funded_keys,
+            chain_limits,
+            interval,
+        })
+    }
+}
@@ -0,0 +1,303 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use derive_more::AsRef;
+use eyre::Result;
+use futures_util::future::try_join_all;
+use prometheus::IntCounterVec;
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::{error, info, info_span, instrument::Instrumented, warn, Instrument};
+
+use hyperlane_base::{
+    metrics::AgentMetrics, settings::ChainConf, spawn_named, AgentMetadata, BaseAgent,
+    ChainMetrics, CoreMetrics, HyperlaneAgentCore,
+};
+use hyperlane_core::{HyperlaneDomain, MessageDispatcher, H256, U256};
+
+use crate::settings::KathySettings;
+
+/// Metrics of kathy.
+#[derive(Debug, Clone)]
+struct KathyMetrics {
+    /// Dispatch attempts by outcome.
+    dispatches: IntCounterVec,
+    /// Gas payment attempts by outcome.
+    gas_payments: IntCounterVec,
+}
+
+impl KathyMetrics {
+    fn new(metrics: &CoreMetrics) -> Result<Self> {
+        Ok(Self {
+            dispatches: metrics.new_int_counter(
+                "kathy_dispatches",
+                "Number of messages kathy attempted to dispatch, by outcome",
+                &["origin", "destination", "outcome"],
+            )?,
+            gas_payments: metrics.new_int_counter(
+                "kathy_gas_payments",
+                "Number of gas payments kathy attempted, by outcome",
+                &["origin", "destination", "outcome"],
+            )?,
+        })
+    }
+}
+
+/// Settings shared by the dispatchers of every origin chain
+#[derive(Debug, Clone)]
+struct LoadConf {
+    messages_per_round: u32,
+    interval: Duration,
+    body_size: usize,
+    destination_gas_amount: Option<U256>,
+    max_messages_per_pair: Option<u64>,
+}
+
+/// A load generator agent
+#[derive(Debug, AsRef)]
+pub struct Kathy {
+    #[as_ref]
+    core: HyperlaneAgentCore,
+    origin_dispatchers: Vec<OriginDispatcher>,
+    core_metrics: Arc<CoreMetrics>,
+}
+
+#[async_trait]
+impl BaseAgent for Kathy {
+    const AGENT_NAME: &'static str = "kathy";
+
+    type Settings = KathySettings;
+
+    async fn from_settings(
+        _agent_metadata: AgentMetadata,
+        settings: Self::Settings,
+        metrics: Arc<CoreMetrics>,
+        _agent_metrics: AgentMetrics,
+        _chain_metrics: ChainMetrics,
+    ) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        let core = settings.build_hyperlane_core(metrics.clone());
+        let kathy_metrics = KathyMetrics::new(&metrics)?;
+        let load = LoadConf {
+            messages_per_round: settings.messages_per_round,
+            interval: settings.interval,
+            body_size: settings.body_size,
+            destination_gas_amount: settings.destination_gas_amount,
+            max_messages_per_pair: settings.max_messages_per_pair,
+        };
+
+        let mut origin_dispatchers = Vec::with_capacity(settings.origin_chains.len());
+        for origin in &settings.origin_chains {
+            let chain_conf = settings.chain_setup(origin)?;
+            let mut routes = vec![];
+            for pair in settings
+                .chain_pairs
+                .iter()
+                .filter(|pair| pair.origin == origin.name())
+            {
+                routes.push(Route {
+                    destination: settings.lookup_domain(&pair.destination)?,
+                    recipient: pair.recipient,
+                    dispatched: 0,
+                });
+            }
+            origin_dispatchers.push(
+                OriginDispatcher::new(
+                    chain_conf,
+                    &metrics,
+                    routes,
+                    load.clone(),
+                    kathy_metrics.clone(),
+                )
+                .await?,
+            );
+        }
+
+        Ok(Self {
+            core,
+            origin_dispatchers,
+            core_metrics: metrics,
+        })
+    }
+
+    #[allow(clippy::async_yields_async)]
+    async fn run(self) {
+        let mut tasks = vec![];
+
+        let server = self
+            .core
+            .settings
+            .server(self.core_metrics.clone())
+            .expect("Failed to create server");
+        tasks.push(server.run().instrument(info_span!("Kathy server")));
+
+        for origin_dispatcher in self.origin_dispatchers {
+            tasks.push(origin_dispatcher.spawn());
+        }
+
+        // Note that this only returns an error if one of the tasks panics
+        if let Err(err) = try_join_all(tasks).await {
+            error!(?err, "One of the kathy tasks returned an error");
+        }
+    }
+}
+
+/// A destination messages are dispatched to from an origin chain
+#[derive(Debug)]
+struct Route {
+    destination: HyperlaneDomain,
+    recipient: H256,
+    /// Number of messages dispatched on this route so far
+    dispatched: u64,
+}
+
+/// Dispatches messages from one origin chain to each of its routes. Messages
+/// are dispatched one at a time, since they are all sent by the same signer.
+#[derive(Debug)]
+struct OriginDispatcher {
+    origin: HyperlaneDomain,
+    dispatcher: Box<dyn MessageDispatcher>,
+    routes: Vec<Route>,
+    load: LoadConf,
+    metrics: KathyMetrics,
+}
+
+impl OriginDispatcher {
+    async fn new(
+        chain_conf: &ChainConf,
+        core_metrics: &CoreMetrics,
+        routes: Vec<Route>,
+        load: LoadConf,
+        metrics: KathyMetrics,
+    ) -> Result<Self> {
+        Ok(Self {
+            origin: chain_conf.domain.clone(),
+            dispatcher: chain_conf.build_message_dispatcher(core_metrics).await?,
+            routes,
+            load,
+            metrics,
+        })
+    }
+
+    fn is_done(&self, route: &Route) -> bool {
+        self.load
+            .max_messages_per_pair
+            .map_or(false, |max| route.dispatched >= max)
+    }
+
+    /// Dispatches a message on the route, and pays for its gas if configured
+    /// to. Returns whether the message was dispatched.
+    async fn dispatch(&self, route: &Route) -> bool {
+        let labels =
+            |outcome: &'static str| [self.origin.name(), route.destination.name(), outcome];
+        let body = message_body(route.dispatched, self.load.body_size);
+        let outcome = match self
+            .dispatcher
+            .dispatch(route.destination.id(), route.recipient, body)
+            .await
+        {
+            Ok(outcome) if outcome.tx_outcome.executed => outcome,
+            Ok(outcome) => {
+                self.metrics
+                    .dispatches
+                    .with_label_values(&labels("reverted"))
+                    .inc();
+                error!(destination=%route.destination, tx_outcome=?outcome.tx_outcome, "Dispatch transaction reverted");
+                return false;
+            }
+            Err(err) => {
+                self.metrics
+                    .dispatches
+                    .with_label_values(&labels("failed"))
+                    .inc();
+                error!(destination=%route.destination, ?err, "Failed to dispatch message");
+                return false;
+            }
+        };
+        self.metrics
+            .dispatches
+            .with_label_values(&labels("success"))
+            .inc();
+        info!(destination=%route.destination, message_id=?outcome.message_id, "Dispatched message");
+
+        let Some(gas_amount) = self.load.destination_gas_amount else {
+            return true;
+        };
+        let Some(message_id) = outcome.message_id else {
+            warn!(destination=%route.destination, tx_outcome=?outcome.tx_outcome, "Dispatched message id not found, not paying for gas");
+            return true;
+        };
+        let payment_outcome = match self
+            .dispatcher
+            .pay_for_gas(message_id, route.destination.id(), gas_amount)
+            .await
+        {
+            Ok(tx_outcome) if tx_outcome.executed => "success",
+            Ok(tx_outcome) => {
+                error!(?message_id, ?tx_outcome, "Gas payment transaction reverted");
+                "reverted"
+            }
+            Err(err) => {
+                error!(?message_id, ?err, "Failed to pay for gas");
+                "failed"
+            }
+        };
+        self.metrics
+            .gas_payments
+            .with_label_values(&labels(payment_outcome))
+            .inc();
+        true
+    }
+
+    fn spawn(mut self) -> Instrumented<JoinHandle<()>> {
+        let span = info_span!("OriginDispatcher", origin=%self.origin);
+        let origin = self.origin.clone();
+        spawn_named("origin_dispatcher", Some(&origin), async move {
+            let mut interval = tokio::time::interval(self.load.interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            let mut routes = std::mem::take(&mut self.routes);
+            loop {
+                interval.tick().await;
+                for route in routes.iter_mut() {
+                    for _ in 0..self.load.messages_per_round {
+                        if self.is_done(route) {
+                            break;
+                        }
+                        if self.dispatch(route).await {
+                            route.dispatched += 1;
+                        }
+                    }
+                }
+                if routes.iter().all(|route| self.is_done(route)) {
+                    info!("Dispatched the configured number of messages on every route");
+                    break;
+                }
+            }
+        })
+        .instrument(span)
+    }
+}
+
+/// Body of the `sequence`th message of a route, of `size` bytes. Bodies
+/// differ between messages so they are easy to tell apart.
+fn message_body(sequence: u64, size: usize) -> Vec<u8> {
+    sequence
+        .to_be_bytes()
+        .into_iter()
+        .cycle()
+        .take(size)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_message_body_has_configured_size() {
+        assert_eq!(message_body(1, 0), Vec::<u8>::new());
+        assert_eq!(message_body(0x0102, 4), vec![0, 0, 0, 0]);
+        assert_eq!(message_body(0x0102, 10), vec![0, 0, 0, 0, 0, 0, 1, 2, 0, 0]);
+    }
+}
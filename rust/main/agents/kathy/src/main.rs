@@ -0,0 +1,25 @@
+//! Kathy dispatches a steady volume of messages between configured chain
+//! pairs, optionally paying for their delivery, to soak test relayers.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use std::process::ExitCode;
+
+use eyre::Result;
+
+use hyperlane_base::{agent_main, AgentOutcome};
+
+use crate::kathy::Kathy;
+
+mod kathy;
+mod settings;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<ExitCode> {
+    let outcome = agent_main::<Kathy>().await?;
+    if let AgentOutcome::ConfigValidated(report) = &outcome {
+        println!("{}", report.to_json()?);
+    }
+    Ok(outcome.exit_code())
+}
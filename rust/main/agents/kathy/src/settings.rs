@@ -0,0 +1,192 @@
+//! Kathy configuration.
+//!
+//! The correct settings shape is defined in the TypeScript SDK metadata. While the exact shape
+//! and validations it defines are not applied here, we should mirror them.
+//! ANY CHANGES HERE NEED TO BE REFLECTED IN THE TYPESCRIPT SDK.
+
+use std::{collections::HashSet, time::Duration};
+
+use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use eyre::Context;
+use hyperlane_base::{
+    impl_loadable_from_settings,
+    settings::{
+        parser::{RawAgentConf, ValueParser},
+        Settings,
+    },
+};
+use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain, H256, U256};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Settings for `Kathy`
+#[derive(Debug, AsRef, AsMut, Deref, DerefMut)]
+pub struct KathySettings {
+    #[as_ref]
+    #[as_mut]
+    #[deref]
+    #[deref_mut]
+    base: Settings,
+
+    /// Chains messages are dispatched from. The signer configured for each
+    /// of these chains is the sender of the messages.
+    pub origin_chains: Vec<HyperlaneDomain>,
+    /// Chain pairs messages are dispatched between
+    pub chain_pairs: Vec<ChainPairConf>,
+    /// Number of messages dispatched to each chain pair per round
+    pub messages_per_round: u32,
+    /// How frequently to start a round of dispatches
+    pub interval: Duration,
+    /// Size of the body of dispatched messages, in bytes
+    pub body_size: usize,
+    /// Destination gas to pay the interchain gas paymaster for, for each
+    /// message. No separate gas payment is made if unset.
+    pub destination_gas_amount: Option<U256>,
+    /// Stop dispatching once this many messages were dispatched for each
+    /// chain pair. Dispatches forever if unset.
+    pub max_messages_per_pair: Option<u64>,
+}
+
+/// A chain pair messages are dispatched between.
+#[derive(Debug, Clone)]
+pub struct ChainPairConf {
+    /// Name of the chain messages are dispatched from
+    pub origin: String,
+    /// Name of the chain messages are dispatched to
+    pub destination: String,
+    /// Recipient of the messages on the destination chain
+    pub recipient: H256,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+struct RawKathySettings(Value);
+
+impl_loadable_from_settings!(Kathy, RawKathySettings -> KathySettings);
+
+impl FromRawConf<RawKathySettings> for KathySettings {
+    fn from_config_filtered(
+        raw: RawKathySettings,
+        cwp: &ConfigPath,
+        _filter: (),
+    ) -> ConfigResult<Self> {
+        let mut err = ConfigParsingError::default();
+
+        let p = ValueParser::new(cwp.clone(), &raw.0);
+
+        let chain_pairs: Vec<ChainPairConf> = p
+            .chain(&mut err)
+            .get_key("chainPairs")
+            .into_array_iter()
+            .map(|itr| {
+                itr.filter_map(|pair| {
+                    let origin = pair.chain(&mut err).get_key("origin").parse_string().end();
+                    let destination = pair
+                        .chain(&mut err)
+                        .get_key("destination")
+                        .parse_string()
+                        .end();
+                    let recipient = pair
+                        .chain(&mut err)
+                        .get_key("recipient")
+                        .parse_address_hash()
+                        .end();
+
+                    let (Some(origin), Some(destination), Some(recipient)) =
+                        (origin, destination, recipient)
+                    else {
+                        return None;
+                    };
+                    Some(ChainPairConf {
+                        origin: origin.to_owned(),
+                        destination: destination.to_owned(),
+                        recipient,
+                    })
+                })
+                .collect()
+            })
+            .unwrap_or_default();
+
+        let chain_names: HashSet<&str> = chain_pairs
+            .iter()
+            .flat_map(|pair| [pair.origin.as_str(), pair.destination.as_str()])
+            .collect();
+
+        let base = p
+            .parse_from_raw_config::<Settings, RawAgentConf, Option<&HashSet<&str>>>(
+                Some(&chain_names),
+                "Parsing base config",
+            )
+            .take_config_err(&mut err);
+
+        let origin_chains = if let Some(base) = &base {
+            let origin_names: HashSet<&str> = chain_pairs
+                .iter()
+                .map(|pair| pair.origin.as_str())
+                .collect();
+            // Destinations must be configured too, for their domain id
+            for destination in chain_pairs.iter().map(|pair| pair.destination.as_str()) {
+                base.lookup_domain(destination)
+                    .context("Missing configuration for a destination in `chainPairs`")
+                    .into_config_result(|| cwp + "chain_pairs")
+                    .take_config_err(&mut err);
+            }
+            origin_names
+                .iter()
+                .filter_map(|chain| {
+                    base.lookup_domain(chain)
+                        .context("Missing configuration for an origin in `chainPairs`")
+                        .into_config_result(|| cwp + "chain_pairs")
+                        .take_config_err(&mut err)
+                })
+                .collect()
+        } else {
+            Default::default()
+        };
+
+        let messages_per_round = p
+            .chain(&mut err)
+            .get_opt_key("messagesPerRound")
+            .parse_u32()
+            .unwrap_or(1);
+
+        let interval = p
+            .chain(&mut err)
+            .get_opt_key("interval")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(60));
+
+        let body_size = p
+            .chain(&mut err)
+            .get_opt_key("bodySize")
+            .parse_u64()
+            .map(|size| size as usize)
+            .unwrap_or(32);
+
+        let destination_gas_amount = p
+            .chain(&mut err)
+            .get_opt_key("destinationGasAmount")
+            .parse_u256()
+            .end();
+
+        let max_messages_per_pair = p
+            .chain(&mut err)
+            .get_opt_key("maxMessagesPerPair")
+            .parse_u64()
+            .end();
+
+        cfg_unwrap_all!(&p.cwp, err: [base]);
+
+        err.into_result(Self {
+            base,
+            origin_chains,
+            chain_pairs,
+            messages_per_round,
+            interval,
+            body_size,
+            destination_gas_amount,
+            max_messages_per_pair,
+        })
+    }
+}
@@ -0,0 +1,13 @@
+//! Reports on the latest checkpoints of a validator set, verifying their
+//! signatures against the on-chain merkle tree hook. See
+//! `relayer::checkpoint_inspector` for the available options.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use eyre::Result;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    relayer::checkpoint_inspector_main().await
+}
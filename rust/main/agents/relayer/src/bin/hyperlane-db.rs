@@ -0,0 +1,12 @@
+//! Lists, dumps and deletes entries of an agent database. See
+//! `relayer::db_explorer` for the available commands and options.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use eyre::Result;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    relayer::db_explorer_main().await
+}
@@ -0,0 +1,13 @@
+//! Delivers a single message with a one-off process transaction, building its
+//! metadata the same way the relayer does. See `relayer::manual_process` for
+//! the available options.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use eyre::Result;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    relayer::manual_process_main().await
+}
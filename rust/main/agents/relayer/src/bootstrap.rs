@@ -0,0 +1,112 @@
+//! Bootstraps the database of a fresh relayer from snapshots of the messages
+//! and gas payments of its origin chains, so that years of history aren't
+//! indexed from RPCs. See `hyperlane_base::db::IndexSnapshot`.
+
+use std::{fs::File, io::BufReader};
+
+use eyre::{bail, Context, Result};
+use hyperlane_base::{
+    db::{
+        complete_snapshot_bootstrap, HyperlaneDb, HyperlaneRocksDB, IndexSnapshot, RestoredSnapshot,
+    },
+    utils::reqwest_client,
+};
+use hyperlane_core::{config::ProxyConf, H256};
+use tracing::{debug, info};
+
+use crate::settings::SnapshotSource;
+
+/// Number of messages requested from the scraper at once
+const SCRAPER_PAGE_SIZE: usize = 10_000;
+
+/// Restores the snapshot of the messages dispatched from `mailbox` if `db`
+/// has none yet, or resumes restoring it if that was interrupted. Messages
+/// dispatched after the snapshot are indexed as usual.
+pub(crate) async fn bootstrap_db(
+    source: &SnapshotSource,
+    db: &HyperlaneRocksDB,
+    mailbox: H256,
+    proxy: Option<&ProxyConf>,
+) -> Result<()> {
+    let domain = db.domain().clone();
+    if db.retrieve_snapshot_bootstrap_complete()? {
+        debug!(%domain, "Database already bootstrapped");
+        return Ok(());
+    }
+    let resume_from = db.retrieve_snapshot_bootstrap_next_nonce()?;
+    if resume_from.is_none() && db.retrieve_highest_seen_message_nonce()?.is_some() {
+        debug!(%domain, "Database already has messages, not bootstrapping it");
+        return Ok(());
+    }
+
+    let mut total = RestoredSnapshot::default();
+    let mut add = |restored: RestoredSnapshot| {
+        total.messages += restored.messages;
+        total.gas_payments += restored.gas_payments;
+        total.last_nonce = total.last_nonce.max(restored.last_nonce);
+        total.last_block = total.last_block.max(restored.last_block);
+    };
+    match source {
+        SnapshotSource::Scraper(url) => {
+            let client = reqwest_client(proxy);
+            let url = format!("{}/snapshot", url.as_str().trim_end_matches('/'));
+            let mut from_nonce = resume_from.unwrap_or_default();
+            loop {
+                let snapshot: IndexSnapshot = client
+                    .get(&url)
+                    .query(&[
+                        ("origin", domain.id().to_string()),
+                        ("mailbox", format!("{mailbox:?}")),
+                        ("from_nonce", from_nonce.to_string()),
+                        ("limit", SCRAPER_PAGE_SIZE.to_string()),
+                    ])
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await
+                    .context("Decoding snapshot from scraper")?;
+                add(snapshot.restore(db, mailbox).await?);
+                match snapshot.next_nonce {
+                    Some(next_nonce) if next_nonce > from_nonce => {
+                        from_nonce = next_nonce;
+                        db.store_snapshot_bootstrap_next_nonce(from_nonce)?;
+                    }
+                    Some(next_nonce) => {
+                        bail!("Scraper snapshot page at nonce {from_nonce} points back to nonce {next_nonce}")
+                    }
+                    None => break,
+                }
+            }
+        }
+        SnapshotSource::Dir(dir) => {
+            let path = dir.join(format!("{}.json", domain.name()));
+            if !path.exists() {
+                info!(%domain, ?path, "No snapshot to bootstrap the database from");
+                return Ok(());
+            }
+            // Restoring is idempotent, so an interrupted bootstrap restores
+            // the whole file again
+            db.store_snapshot_bootstrap_next_nonce(0)?;
+            let file = File::open(&path).with_context(|| format!("Opening {path:?}"))?;
+            // Snapshot pages may be concatenated in a single file
+            for snapshot in serde_json::Deserializer::from_reader(BufReader::new(file))
+                .into_iter::<IndexSnapshot>()
+            {
+                let snapshot = snapshot.with_context(|| format!("Decoding {path:?}"))?;
+                if snapshot.domain == domain.id() {
+                    add(snapshot.restore(db, mailbox).await?);
+                }
+            }
+        }
+    }
+    complete_snapshot_bootstrap(db, total.last_block).await?;
+    info!(
+        %domain,
+        messages = total.messages,
+        gas_payments = total.gas_payments,
+        last_nonce = ?total.last_nonce,
+        "Bootstrapped database from snapshot"
+    );
+    Ok(())
+}
@@ -0,0 +1,376 @@
+//! Inspects the checkpoints a validator set published for an origin chain, for
+//! debugging stalled deliveries.
+//!
+//! For each validator, the most recently announced storage location is read,
+//! the latest signed checkpoint is downloaded and its signature verified, and
+//! the checkpoint is compared against the latest checkpoint of the origin
+//! merkle tree hook.
+//!
+//! Configuration is loaded the same way as for the agents, so every option can
+//! be passed as an argument, e.g.
+//! `hyperlane-checkpoints --originChainName ethereum --validators 0x..,0x.. --threshold 2`.
+
+use std::{collections::HashSet, fmt::Display, num::NonZeroU64, str::FromStr, sync::Arc};
+
+use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use eyre::{Context, Result};
+use hyperlane_base::{
+    impl_loadable_from_settings,
+    settings::{
+        parser::{RawAgentConf, ValueParser},
+        CheckpointSyncerConf, Settings,
+    },
+    CoreMetrics, LoadableFromSettings,
+};
+use hyperlane_core::{
+    cfg_unwrap_all, config::*, utils::hex_or_base58_to_h256, Checkpoint, HyperlaneDomain,
+    SignedCheckpointWithMessageId, H160, H256,
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Name the tool reports metrics and logs under.
+const TOOL_NAME: &str = "hyperlane-checkpoints";
+
+/// Settings for the checkpoint inspector
+#[derive(Debug, AsRef, AsMut, Deref, DerefMut)]
+pub struct CheckpointInspectorSettings {
+    #[as_ref]
+    #[as_mut]
+    #[deref]
+    #[deref_mut]
+    base: Settings,
+
+    /// Chain the validators attest to
+    pub origin_chain: HyperlaneDomain,
+    /// The validators to inspect
+    pub validators: Vec<H256>,
+    /// Number of up to date validators needed for delivery, if known
+    pub threshold: Option<u32>,
+    /// Whether to read checkpoints from local storage locations
+    pub allow_local_checkpoint_syncers: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+struct RawCheckpointInspectorSettings(Value);
+
+impl_loadable_from_settings!(CheckpointInspector, RawCheckpointInspectorSettings -> CheckpointInspectorSettings);
+
+impl FromRawConf<RawCheckpointInspectorSettings> for CheckpointInspectorSettings {
+    fn from_config_filtered(
+        raw: RawCheckpointInspectorSettings,
+        cwp: &ConfigPath,
+        _filter: (),
+    ) -> ConfigResult<Self> {
+        let mut err = ConfigParsingError::default();
+
+        let p = ValueParser::new(cwp.clone(), &raw.0);
+
+        let origin_chain_name = p
+            .chain(&mut err)
+            .get_key("originChainName")
+            .parse_string()
+            .end();
+
+        let origin_chain_name_set = origin_chain_name.map(|s| HashSet::from([s]));
+
+        let base = p
+            .parse_from_raw_config::<Settings, RawAgentConf, Option<&HashSet<&str>>>(
+                origin_chain_name_set.as_ref(),
+                "Expected valid base agent configuration",
+            )
+            .take_config_err(&mut err);
+
+        let origin_chain = if let (Some(base), Some(name)) = (&base, origin_chain_name) {
+            base.lookup_domain(name)
+                .context("Missing configuration for the origin chain")
+                .take_err(&mut err, || cwp + "origin_chain_name")
+        } else {
+            None
+        };
+
+        let validators = p
+            .chain(&mut err)
+            .get_key("validators")
+            .parse_string()
+            .end()
+            .and_then(|validators| {
+                validators
+                    .split(',')
+                    .map(|validator| hex_or_base58_to_h256(validator.trim()))
+                    .collect::<Result<Vec<_>>>()
+                    .context("Expected a comma separated list of validator addresses")
+                    .take_err(&mut err, || cwp + "validators")
+            });
+
+        let threshold = p.chain(&mut err).get_opt_key("threshold").parse_u32().end();
+
+        let allow_local_checkpoint_syncers = p
+            .chain(&mut err)
+            .get_opt_key("allowLocalCheckpointSyncers")
+            .parse_bool()
+            .unwrap_or(false);
+
+        cfg_unwrap_all!(cwp, err: [base, origin_chain, validators]);
+
+        err.into_result(Self {
+            base,
+            origin_chain,
+            validators,
+            threshold,
+            allow_local_checkpoint_syncers,
+        })
+    }
+}
+
+/// What the latest checkpoint of a validator says about its health.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckpointStatus {
+    /// The validator has not announced a usable storage location
+    NotAnnounced,
+    /// The storage location could not be read
+    Unreachable(String),
+    /// The storage location holds no checkpoints
+    NoCheckpoints,
+    /// The checkpoint was not signed by the validator
+    InvalidSignature(Option<H160>),
+    /// The checkpoint is for a different merkle tree hook or domain
+    WrongMerkleTreeHook,
+    /// The checkpoint is at the on-chain index, but with a different root
+    RootMismatch,
+    /// The checkpoint is behind the on-chain index
+    Lagging(u32),
+    /// The checkpoint is at or ahead of the on-chain index
+    UpToDate,
+}
+
+impl CheckpointStatus {
+    /// Classify the latest checkpoint of `validator`, signed by `signer`,
+    /// against the on-chain checkpoint.
+    pub fn classify(
+        validator: H160,
+        signed: &SignedCheckpointWithMessageId,
+        signer: Option<H160>,
+        onchain: &Checkpoint,
+    ) -> Self {
+        let checkpoint = &signed.value.checkpoint;
+        if signer != Some(validator) {
+            Self::InvalidSignature(signer)
+        } else if checkpoint.merkle_tree_hook_address != onchain.merkle_tree_hook_address
+            || checkpoint.mailbox_domain != onchain.mailbox_domain
+        {
+            Self::WrongMerkleTreeHook
+        } else if checkpoint.index < onchain.index {
+            Self::Lagging(onchain.index - checkpoint.index)
+        } else if checkpoint.index == onchain.index && checkpoint.root != onchain.root {
+            Self::RootMismatch
+        } else {
+            Self::UpToDate
+        }
+    }
+}
+
+impl Display for CheckpointStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAnnounced => write!(f, "not announced"),
+            Self::Unreachable(err) => write!(f, "unreachable: {err}"),
+            Self::NoCheckpoints => write!(f, "no checkpoints"),
+            Self::InvalidSignature(Some(signer)) => {
+                write!(f, "invalid signature, signed by {signer:?}")
+            }
+            Self::InvalidSignature(None) => write!(f, "invalid signature"),
+            Self::WrongMerkleTreeHook => write!(f, "wrong merkle tree hook or domain"),
+            Self::RootMismatch => write!(f, "root mismatch at the on-chain index"),
+            Self::Lagging(behind) => write!(f, "lagging by {behind}"),
+            Self::UpToDate => write!(f, "up to date"),
+        }
+    }
+}
+
+/// Load the settings from the config locations and inspect the configured
+/// validators.
+pub async fn checkpoint_inspector_main() -> Result<()> {
+    let settings = CheckpointInspectorSettings::load()?;
+    let metrics = settings.as_ref().metrics(TOOL_NAME)?;
+    let _console_server = settings.tracing.start_tracing(&metrics)?;
+    inspect_checkpoints(settings, metrics).await
+}
+
+async fn inspect_checkpoints(
+    settings: CheckpointInspectorSettings,
+    metrics: Arc<CoreMetrics>,
+) -> Result<()> {
+    let origin = &settings.origin_chain;
+    let reorg_period = settings.chain_setup(origin)?.reorg_period;
+    let merkle_tree_hook = settings.build_merkle_tree_hook(origin, &metrics).await?;
+    let onchain = merkle_tree_hook
+        .latest_checkpoint(NonZeroU64::new(reorg_period as u64))
+        .await?;
+    println!(
+        "{origin} merkle tree hook {:?}: index {}, root {:?}",
+        onchain.merkle_tree_hook_address, onchain.index, onchain.root
+    );
+
+    let validator_announce = settings.build_validator_announce(origin, &metrics).await?;
+    let storage_locations = validator_announce
+        .get_announced_storage_locations(&settings.validators)
+        .await?;
+
+    let mut up_to_date = 0;
+    for (validator, locations) in settings.validators.iter().zip(storage_locations) {
+        let validator = H160::from(*validator);
+        let (location, index, status) = inspect_validator(
+            validator,
+            &locations,
+            &onchain,
+            settings.allow_local_checkpoint_syncers,
+            settings.proxy.as_ref(),
+        )
+        .await;
+        if status == CheckpointStatus::UpToDate {
+            up_to_date += 1;
+        }
+        println!(
+            "{validator:?}  index {}  {status}  ({})",
+            index.map_or_else(|| "-".to_owned(), |index| index.to_string()),
+            location.unwrap_or("-")
+        );
+    }
+
+    match settings.threshold {
+        Some(threshold) if up_to_date < threshold => println!(
+            "{up_to_date}/{} validators up to date, below the threshold of {threshold}",
+            settings.validators.len()
+        ),
+        _ => println!(
+            "{up_to_date}/{} validators up to date",
+            settings.validators.len()
+        ),
+    }
+    Ok(())
+}
+
+/// Fetch and classify the latest checkpoint of a validator. Returns the storage
+/// location used and the index of the latest checkpoint, if any.
+async fn inspect_validator<'a>(
+    validator: H160,
+    locations: &'a [String],
+    onchain: &Checkpoint,
+    allow_local_checkpoint_syncers: bool,
+    proxy: Option<&ProxyConf>,
+) -> (Option<&'a str>, Option<u32>, CheckpointStatus) {
+    // Only use the most recently announced usable location, like the relayer
+    let Some((location, conf)) = locations.iter().rev().find_map(|location| {
+        let conf = CheckpointSyncerConf::from_str(location).ok()?;
+        let allowed = allow_local_checkpoint_syncers
+            || !matches!(conf, CheckpointSyncerConf::LocalStorage { .. });
+        allowed.then_some((location.as_str(), conf))
+    }) else {
+        return (None, None, CheckpointStatus::NotAnnounced);
+    };
+
+    let syncer = match conf.build_and_validate(None, proxy).await {
+        Ok(syncer) => syncer,
+        Err(err) => {
+            return (
+                Some(location),
+                None,
+                CheckpointStatus::Unreachable(err.to_string()),
+            )
+        }
+    };
+    let index = match syncer.latest_index().await {
+        Ok(Some(index)) => index,
+        Ok(None) => return (Some(location), None, CheckpointStatus::NoCheckpoints),
+        Err(err) => {
+            return (
+                Some(location),
+                None,
+                CheckpointStatus::Unreachable(err.to_string()),
+            )
+        }
+    };
+    let signed = match syncer.fetch_checkpoint(index).await {
+        Ok(Some(signed)) => signed,
+        Ok(None) => return (Some(location), Some(index), CheckpointStatus::NoCheckpoints),
+        Err(err) => {
+            return (
+                Some(location),
+                Some(index),
+                CheckpointStatus::Unreachable(err.to_string()),
+            )
+        }
+    };
+    let signer = signed.recover().ok();
+    let status = CheckpointStatus::classify(validator, &signed, signer, onchain);
+    (Some(location), Some(index), status)
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::{CheckpointWithMessageId, Signature, U256};
+
+    use super::*;
+
+    fn checkpoint(index: u32, root: u64) -> Checkpoint {
+        Checkpoint {
+            merkle_tree_hook_address: H256::repeat_byte(1),
+            mailbox_domain: 1,
+            root: H256::from_low_u64_be(root),
+            index,
+        }
+    }
+
+    fn signed(checkpoint: Checkpoint) -> SignedCheckpointWithMessageId {
+        SignedCheckpointWithMessageId {
+            value: CheckpointWithMessageId {
+                checkpoint,
+                message_id: H256::zero(),
+            },
+            signature: Signature {
+                r: U256::zero(),
+                s: U256::zero(),
+                v: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_classify_checkpoints() {
+        let validator = H160::repeat_byte(2);
+        let onchain = checkpoint(10, 10);
+        let classify = |checkpoint, signer| {
+            CheckpointStatus::classify(validator, &signed(checkpoint), signer, &onchain)
+        };
+
+        assert_eq!(
+            classify(checkpoint(10, 10), Some(validator)),
+            CheckpointStatus::UpToDate
+        );
+        assert_eq!(
+            classify(checkpoint(11, 11), Some(validator)),
+            CheckpointStatus::UpToDate
+        );
+        assert_eq!(
+            classify(checkpoint(7, 7), Some(validator)),
+            CheckpointStatus::Lagging(3)
+        );
+        assert_eq!(
+            classify(checkpoint(10, 9), Some(validator)),
+            CheckpointStatus::RootMismatch
+        );
+        assert_eq!(
+            classify(checkpoint(10, 10), Some(H160::zero())),
+            CheckpointStatus::InvalidSignature(Some(H160::zero()))
+        );
+
+        let mut other_domain = checkpoint(10, 10);
+        other_domain.mailbox_domain = 2;
+        assert_eq!(
+            classify(other_domain, Some(validator)),
+            CheckpointStatus::WrongMerkleTreeHook
+        );
+    }
+}
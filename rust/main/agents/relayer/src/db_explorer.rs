@@ -0,0 +1,331 @@
+//! Read and repair an agent database, for operators debugging a relayer.
+//!
+//! Supported commands:
+//! - `statuses`: list every indexed message with its processed flag, operation
+//!   status and retry count
+//! - `gasPayments`: dump the gas payments and expenditure of `messageId`
+//! - `cursors`: show how far each kind of log has been indexed
+//! - `delete`: delete the raw, hex encoded `key`. Keys are laid out as
+//!   `<origin chain name>_<prefix><encoded key>`.
+//!
+//! RocksDB only allows a single writer, so stop the agent before running this.
+//!
+//! Configuration is loaded the same way as for the agents, so every option can
+//! be passed as an argument, e.g.
+//! `hyperlane-db --originChainName ethereum --db ./hyperlane_db --command statuses`.
+
+use std::{collections::HashSet, path::PathBuf};
+
+use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use ethers::types::Bytes;
+use eyre::{bail, eyre, Context, Result};
+use hyperlane_base::{
+    db::{HyperlaneDb, HyperlaneRocksDB, DB},
+    impl_loadable_from_settings,
+    settings::{
+        parser::{RawAgentConf, ValueParser},
+        Settings,
+    },
+    LoadableFromSettings,
+};
+use hyperlane_core::{
+    cfg_unwrap_all, config::*, utils::bytes_to_hex, GasPaymentKey, HyperlaneDomain,
+    HyperlaneWatermarkedLogStore, InterchainGasPayment, H256,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use strum::{Display, EnumString};
+
+/// Name the tool reports metrics and logs under.
+const TOOL_NAME: &str = "hyperlane-db";
+
+/// What to do with the database.
+#[derive(Debug, Display, EnumString, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "camelCase")]
+pub enum DbCommand {
+    /// List the status of every indexed message
+    Statuses,
+    /// Dump the gas payments of a message
+    GasPayments,
+    /// Show indexing progress
+    Cursors,
+    /// Delete a raw key
+    Delete,
+}
+
+/// Settings for the DB explorer
+#[derive(Debug, AsRef, AsMut, Deref, DerefMut)]
+pub struct DbExplorerSettings {
+    #[as_ref]
+    #[as_mut]
+    #[deref]
+    #[deref_mut]
+    base: Settings,
+
+    /// Path to the agent database
+    pub db: PathBuf,
+    /// Chain the database entries are scoped to
+    pub origin_chain: HyperlaneDomain,
+    /// What to do with the database
+    pub command: DbCommand,
+    /// Message to dump the gas payments of
+    pub message_id: Option<H256>,
+    /// Raw key to delete
+    pub key: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+struct RawDbExplorerSettings(Value);
+
+impl_loadable_from_settings!(DbExplorer, RawDbExplorerSettings -> DbExplorerSettings);
+
+impl FromRawConf<RawDbExplorerSettings> for DbExplorerSettings {
+    fn from_config_filtered(
+        raw: RawDbExplorerSettings,
+        cwp: &ConfigPath,
+        _filter: (),
+    ) -> ConfigResult<Self> {
+        let mut err = ConfigParsingError::default();
+
+        let p = ValueParser::new(cwp.clone(), &raw.0);
+
+        let origin_chain_name = p
+            .chain(&mut err)
+            .get_key("originChainName")
+            .parse_string()
+            .end();
+
+        let origin_chain_name_set = origin_chain_name.map(|s| HashSet::from([s]));
+
+        let base = p
+            .parse_from_raw_config::<Settings, RawAgentConf, Option<&HashSet<&str>>>(
+                origin_chain_name_set.as_ref(),
+                "Expected valid base agent configuration",
+            )
+            .take_config_err(&mut err);
+
+        let origin_chain = if let (Some(base), Some(name)) = (&base, origin_chain_name) {
+            base.lookup_domain(name)
+                .context("Missing configuration for the origin chain")
+                .take_err(&mut err, || cwp + "origin_chain_name")
+        } else {
+            None
+        };
+
+        let command = p
+            .chain(&mut err)
+            .get_key("command")
+            .parse_string()
+            .end()
+            .and_then(|command| {
+                command
+                    .parse::<DbCommand>()
+                    .map_err(|_| eyre!("Unknown command `{command}`"))
+                    .take_err(&mut err, || cwp + "command")
+            });
+
+        let message_id = p
+            .chain(&mut err)
+            .get_opt_key("messageId")
+            .parse_address_hash()
+            .end();
+        let key = p
+            .chain(&mut err)
+            .get_opt_key("key")
+            .parse_from_str::<Bytes>("Expected hex encoded key")
+            .end()
+            .map(|key| key.to_vec());
+
+        match command {
+            Some(DbCommand::GasPayments) if message_id.is_none() => err.push(
+                cwp + "message_id",
+                eyre!("`messageId` is required for the `gasPayments` command"),
+            ),
+            Some(DbCommand::Delete) if key.is_none() => err.push(
+                cwp + "key",
+                eyre!("`key` is required for the `delete` command"),
+            ),
+            _ => {}
+        }
+
+        let db = p
+            .chain(&mut err)
+            .get_opt_key("db")
+            .parse_from_str("Expected database path")
+            .unwrap_or_else(|| std::env::current_dir().unwrap().join("hyperlane_db"));
+
+        cfg_unwrap_all!(cwp, err: [base, origin_chain, command]);
+
+        err.into_result(Self {
+            base,
+            db,
+            origin_chain,
+            command,
+            message_id,
+            key,
+        })
+    }
+}
+
+/// Load the settings from the config locations and run the configured
+/// command against the database.
+pub async fn db_explorer_main() -> Result<()> {
+    let settings = DbExplorerSettings::load()?;
+    let metrics = settings.as_ref().metrics(TOOL_NAME)?;
+    let _console_server = settings.tracing.start_tracing(&metrics)?;
+
+    let raw_db = DB::from_path(&settings.db)?;
+    let db = HyperlaneRocksDB::new(&settings.origin_chain, raw_db.clone());
+    match settings.command {
+        DbCommand::Statuses => print_statuses(&db),
+        DbCommand::GasPayments => {
+            // Checked when parsing the settings
+            let message_id = settings.message_id.expect("Missing message id");
+            print_gas_payments(&db, message_id)
+        }
+        DbCommand::Cursors => print_cursors(&db).await,
+        DbCommand::Delete => {
+            let key = settings.key.as_deref().expect("Missing key");
+            delete_key(&raw_db, key)
+        }
+    }
+}
+
+fn print_statuses(db: &HyperlaneRocksDB) -> Result<()> {
+    let Some(highest_nonce) = db.retrieve_highest_seen_message_nonce()? else {
+        println!("No messages indexed for {}", db.domain());
+        return Ok(());
+    };
+    for nonce in 0..=highest_nonce {
+        let Some(message) = db.retrieve_message_by_nonce(nonce)? else {
+            println!("{nonce}  missing");
+            continue;
+        };
+        let id = message.id();
+        let processed = db.retrieve_processed_by_nonce(&nonce)?.unwrap_or(false);
+        let status = db
+            .retrieve_status_by_message_id(&id)?
+            .map_or_else(|| "-".to_owned(), |status| status.to_string());
+        let retries = db
+            .retrieve_pending_message_retry_count_by_message_id(&id)?
+            .unwrap_or(0);
+        println!(
+            "{nonce}  {id:?}  destination {}  processed {processed}  status {status}  retries {retries}",
+            message.destination
+        );
+    }
+    Ok(())
+}
+
+fn print_gas_payments(db: &HyperlaneRocksDB, message_id: H256) -> Result<()> {
+    let Some(message) = db.retrieve_message_by_id(&message_id)? else {
+        bail!("Message {message_id:?} not found in the database");
+    };
+
+    // Individual payments are only kept for sequence indexed chains
+    let mut sequence = 0;
+    while let Some(payment) = db.retrieve_gas_payment_by_sequence(&sequence)? {
+        if payment.message_id == message_id {
+            let block = db.retrieve_gas_payment_block_by_sequence(&sequence)?;
+            println!(
+                "payment #{sequence} at block {}: destination {}, payment {}, gas amount {}",
+                display_opt(block),
+                payment.destination,
+                payment.payment,
+                payment.gas_amount
+            );
+        }
+        sequence += 1;
+    }
+
+    let total = db
+        .retrieve_gas_payment_by_gas_payment_key(GasPaymentKey {
+            message_id,
+            destination: message.destination,
+        })?
+        .unwrap_or_else(|| InterchainGasPayment {
+            message_id,
+            destination: message.destination,
+            payment: 0.into(),
+            gas_amount: 0.into(),
+        });
+    println!(
+        "total paid: payment {}, gas amount {}",
+        total.payment, total.gas_amount
+    );
+
+    let expenditure = db.retrieve_gas_expenditure_by_message_id(message_id)?;
+    println!(
+        "total spent: tokens used {}, gas used {}",
+        expenditure.tokens_used, expenditure.gas_used
+    );
+    Ok(())
+}
+
+async fn print_cursors(db: &HyperlaneRocksDB) -> Result<()> {
+    println!(
+        "highest seen message nonce: {}",
+        display_opt(db.retrieve_highest_seen_message_nonce()?)
+    );
+
+    let mut leaf_count = 0;
+    while db
+        .retrieve_merkle_tree_insertion_by_leaf_index(&leaf_count)?
+        .is_some()
+    {
+        leaf_count += 1;
+    }
+    println!("merkle tree insertions: {leaf_count}");
+
+    let mut gas_payment_count = 0;
+    while db
+        .retrieve_gas_payment_by_sequence(&gas_payment_count)?
+        .is_some()
+    {
+        gas_payment_count += 1;
+    }
+    println!("sequenced gas payments: {gas_payment_count}");
+
+    let gas_payment_block =
+        HyperlaneWatermarkedLogStore::<InterchainGasPayment>::retrieve_high_watermark(db).await?;
+    println!(
+        "gas payment block watermark: {}",
+        display_opt(gas_payment_block)
+    );
+    Ok(())
+}
+
+fn delete_key(db: &DB, key: &[u8]) -> Result<()> {
+    let Some(value) = db.retrieve(key)? else {
+        bail!("Key {} not found in the database", bytes_to_hex(key));
+    };
+    db.delete(key)?;
+    println!(
+        "Deleted key {} with value {}",
+        bytes_to_hex(key),
+        bytes_to_hex(&value)
+    );
+    Ok(())
+}
+
+fn display_opt(value: Option<impl ToString>) -> String {
+    value.map_or_else(|| "-".to_owned(), |value| value.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!("statuses".parse::<DbCommand>(), Ok(DbCommand::Statuses));
+        assert_eq!(
+            "gasPayments".parse::<DbCommand>(),
+            Ok(DbCommand::GasPayments)
+        );
+        assert_eq!("cursors".parse::<DbCommand>(), Ok(DbCommand::Cursors));
+        assert_eq!("delete".parse::<DbCommand>(), Ok(DbCommand::Delete));
+        assert!("gas_payments".parse::<DbCommand>().is_err());
+    }
+}
@@ -0,0 +1,257 @@
+use std::{
+    fmt::{Debug, Formatter},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use eyre::Result;
+use hyperlane_base::{settings::ChainConf, spawn_named, CoreMetrics, NotificationEvent, Notifier};
+use hyperlane_core::{HyperlaneDomain, HyperlaneProvider, U256};
+use prometheus::IntGauge;
+use strum::Display;
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::{debug, error, info, info_span, instrument::Instrumented, warn, Instrument};
+
+use crate::settings::FundingThresholdsConf;
+
+/// Gas assumed to be used by a single delivery when estimating its cost, if the
+/// destination has no `transactionGasLimit` configured.
+pub const DEFAULT_DELIVERY_GAS_ESTIMATE: u64 = 300_000;
+
+/// How often the signer balance is checked.
+const FUNDING_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Funding status of the relayer signer on a destination chain, ordered by
+/// severity.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FundingStatus {
+    /// The balance is above all configured thresholds.
+    Healthy,
+    /// The balance dropped below the warning threshold.
+    Warning,
+    /// The balance dropped below the critical threshold.
+    Critical,
+    /// The balance can't cover the estimated cost of a single delivery.
+    Insufficient,
+}
+
+impl FundingStatus {
+    /// Classify a signer balance against the thresholds and the estimated cost
+    /// of one delivery, if known.
+    pub fn classify(
+        balance: U256,
+        thresholds: &FundingThresholdsConf,
+        delivery_cost: Option<U256>,
+    ) -> Self {
+        let below = |threshold: Option<U256>| threshold.map(|t| balance < t).unwrap_or(false);
+        if below(delivery_cost) {
+            Self::Insufficient
+        } else if below(thresholds.critical) {
+            Self::Critical
+        } else if below(thresholds.warning) {
+            Self::Warning
+        } else {
+            Self::Healthy
+        }
+    }
+
+    fn as_metric_value(&self) -> i64 {
+        *self as i64
+    }
+}
+
+/// Periodically checks the balance of the relayer signer on a destination
+/// chain, exports its funding status and optionally pauses submission to that
+/// chain while the balance can't cover one delivery. Notifies when the balance
+/// becomes critically low.
+pub struct FundingMonitor {
+    domain: HyperlaneDomain,
+    provider: Box<dyn HyperlaneProvider>,
+    signer_address: String,
+    thresholds: FundingThresholdsConf,
+    delivery_gas_estimate: U256,
+    pause_on_insufficient_balance: bool,
+    submission_paused: Arc<AtomicBool>,
+    funding_status: IntGauge,
+    notifier: Notifier,
+    last_status: FundingStatus,
+}
+
+impl Debug for FundingMonitor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "FundingMonitor {{ domain: {}, signer_address: {}, thresholds: {:?}, delivery_gas_estimate: {}, pause_on_insufficient_balance: {} }}",
+            self.domain,
+            self.signer_address,
+            self.thresholds,
+            self.delivery_gas_estimate,
+            self.pause_on_insufficient_balance
+        )
+    }
+}
+
+impl FundingMonitor {
+    /// Creates a funding monitor for the destination described by `chain_conf`.
+    /// Returns `None` if there is no signer configured for the chain, since
+    /// there is then no balance to monitor.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new(
+        chain_conf: &ChainConf,
+        core_metrics: &CoreMetrics,
+        thresholds: FundingThresholdsConf,
+        transaction_gas_limit: Option<U256>,
+        pause_on_insufficient_balance: bool,
+        submission_paused: Arc<AtomicBool>,
+        funding_status: IntGauge,
+        notifier: Notifier,
+    ) -> Result<Option<Self>> {
+        let Some(signer) = chain_conf.chain_signer().await? else {
+            return Ok(None);
+        };
+        let provider = chain_conf.build_provider(core_metrics).await?;
+        Ok(Some(Self {
+            domain: chain_conf.domain.clone(),
+            provider,
+            signer_address: signer.address_string(),
+            thresholds,
+            delivery_gas_estimate: transaction_gas_limit
+                .unwrap_or_else(|| DEFAULT_DELIVERY_GAS_ESTIMATE.into()),
+            pause_on_insufficient_balance,
+            submission_paused,
+            funding_status,
+            notifier,
+            last_status: FundingStatus::Healthy,
+        }))
+    }
+
+    /// Estimate the cost of a single delivery from the current gas price.
+    async fn estimated_delivery_cost(&self) -> Option<U256> {
+        match self.provider.get_chain_metrics().await {
+            Ok(Some(chain_info)) => chain_info
+                .min_gas_price
+                .map(|gas_price| gas_price.saturating_mul(self.delivery_gas_estimate)),
+            Ok(None) => None,
+            Err(err) => {
+                debug!(domain=%self.domain, ?err, "Failed to fetch gas price for delivery cost estimate");
+                None
+            }
+        }
+    }
+
+    async fn check(&mut self) {
+        let balance = match self.provider.get_balance(self.signer_address.clone()).await {
+            Ok(balance) => balance,
+            Err(err) => {
+                warn!(domain=%self.domain, signer=%self.signer_address, ?err, "Failed to fetch relayer signer balance");
+                return;
+            }
+        };
+        let delivery_cost = self.estimated_delivery_cost().await;
+        let status = FundingStatus::classify(balance, &self.thresholds, delivery_cost);
+        self.funding_status.set(status.as_metric_value());
+
+        match status {
+            FundingStatus::Healthy => {
+                debug!(domain=%self.domain, signer=%self.signer_address, %balance, "Relayer signer is funded")
+            }
+            FundingStatus::Warning => {
+                warn!(domain=%self.domain, signer=%self.signer_address, %balance, thresholds=?self.thresholds, "Relayer signer balance is below the warning threshold")
+            }
+            FundingStatus::Critical | FundingStatus::Insufficient => {
+                error!(domain=%self.domain, signer=%self.signer_address, %balance, ?delivery_cost, thresholds=?self.thresholds, %status, "Relayer signer balance is critically low")
+            }
+        }
+
+        // Only notify when the balance becomes critical, not on every check
+        if status >= FundingStatus::Critical && self.last_status < FundingStatus::Critical {
+            let event = NotificationEvent::BalanceCritical {
+                chain: self.domain.name().to_owned(),
+                address: self.signer_address.clone(),
+                balance: balance.to_string(),
+            };
+            self.notifier.notify(&event);
+        }
+        self.last_status = status;
+
+        let should_pause =
+            self.pause_on_insufficient_balance && status == FundingStatus::Insufficient;
+        let was_paused = self.submission_paused.swap(should_pause, Ordering::Relaxed);
+        match (was_paused, should_pause) {
+            (false, true) => {
+                error!(domain=%self.domain, %balance, ?delivery_cost, "Pausing submission, signer balance can't cover a delivery")
+            }
+            (true, false) => info!(domain=%self.domain, %balance, "Resuming submission"),
+            _ => {}
+        }
+    }
+
+    /// Spawns a tokio task that checks the signer balance on an interval.
+    pub fn spawn(mut self) -> Instrumented<JoinHandle<()>> {
+        let span = info_span!("FundingMonitor", domain=%self.domain);
+        let domain = self.domain.clone();
+        spawn_named("funding_monitor", Some(&domain), async move {
+            let mut interval = tokio::time::interval(FUNDING_CHECK_INTERVAL);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                self.check().await;
+            }
+        })
+        .instrument(span)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn thresholds(warning: u64, critical: u64) -> FundingThresholdsConf {
+        FundingThresholdsConf {
+            warning: Some(warning.into()),
+            critical: Some(critical.into()),
+        }
+    }
+
+    #[test]
+    fn test_classify_against_thresholds() {
+        let thresholds = thresholds(100, 50);
+        assert_eq!(
+            FundingStatus::classify(150.into(), &thresholds, None),
+            FundingStatus::Healthy
+        );
+        assert_eq!(
+            FundingStatus::classify(99.into(), &thresholds, None),
+            FundingStatus::Warning
+        );
+        assert_eq!(
+            FundingStatus::classify(49.into(), &thresholds, None),
+            FundingStatus::Critical
+        );
+    }
+
+    #[test]
+    fn test_classify_insufficient_for_delivery() {
+        let thresholds = thresholds(100, 50);
+        assert_eq!(
+            FundingStatus::classify(150.into(), &thresholds, Some(200.into())),
+            FundingStatus::Insufficient
+        );
+        assert_eq!(
+            FundingStatus::classify(49.into(), &thresholds, Some(10.into())),
+            FundingStatus::Critical
+        );
+    }
+
+    #[test]
+    fn test_classify_without_thresholds() {
+        let thresholds = FundingThresholdsConf::default();
+        assert_eq!(
+            FundingStatus::classify(U256::zero(), &thresholds, None),
+            FundingStatus::Healthy
+        );
+    }
+}
@@ -0,0 +1,287 @@
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Formatter},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use eyre::Result;
+use hyperlane_base::{
+    db::{GasReconciliationData, HyperlaneRocksDB},
+    spawn_named, CoreMetrics,
+};
+use hyperlane_core::HyperlaneDomain;
+use prometheus::{Gauge, GaugeVec, IntGauge, IntGaugeVec};
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::{debug, info_span, instrument::Instrumented, warn, Instrument};
+
+/// How often the gas recorded in the ledger is added to the epoch totals.
+const RECONCILIATION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The gas paid for and spent on the messages of an origin delivered to each
+/// destination, since the reconciler last took it.
+#[derive(Debug, Default)]
+pub struct GasLedger {
+    entries: Mutex<HashMap<u32, GasReconciliationData>>,
+}
+
+impl GasLedger {
+    /// Record gas paid for or spent on messages to `destination`
+    pub fn record(&self, destination: u32, data: GasReconciliationData) {
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        let entry = entries.entry(destination).or_default();
+        *entry = *entry + data;
+    }
+
+    fn take(&self) -> HashMap<u32, GasReconciliationData> {
+        std::mem::take(&mut *self.entries.lock().unwrap_or_else(|err| err.into_inner()))
+    }
+}
+
+/// The epoch a timestamp falls in, counted from the unix epoch.
+fn epoch_at(timestamp: Duration, epoch: Duration) -> u64 {
+    timestamp.as_secs() / epoch.as_secs().max(1)
+}
+
+/// Profitability of the routes from an origin over the current epoch.
+#[derive(Debug, Clone)]
+pub struct GasReconciliationMetrics {
+    messages: IntGaugeVec,
+    payment: GaugeVec,
+    tokens_used: GaugeVec,
+    gas_amount: GaugeVec,
+    gas_used: GaugeVec,
+    gas_coverage: GaugeVec,
+}
+
+impl GasReconciliationMetrics {
+    pub fn new(metrics: &CoreMetrics) -> Result<Self> {
+        let labels = &["origin", "remote"];
+        Ok(Self {
+            messages: metrics.new_int_gauge(
+                "relayer_gas_reconciliation_messages",
+                "Messages delivered over the current gas reconciliation epoch",
+                labels,
+            )?,
+            payment: metrics.new_gauge(
+                "relayer_gas_reconciliation_payment",
+                "Gas payments claimed on the origin for the messages delivered over the current epoch, in the smallest unit of the origin token",
+                labels,
+            )?,
+            tokens_used: metrics.new_gauge(
+                "relayer_gas_reconciliation_tokens_used",
+                "Tokens spent delivering messages over the current epoch, in the smallest unit of the destination token",
+                labels,
+            )?,
+            gas_amount: metrics.new_gauge(
+                "relayer_gas_reconciliation_gas_amount",
+                "Destination gas paid for by the messages delivered over the current epoch",
+                labels,
+            )?,
+            gas_used: metrics.new_gauge(
+                "relayer_gas_reconciliation_gas_used",
+                "Destination gas spent delivering messages over the current epoch",
+                labels,
+            )?,
+            gas_coverage: metrics.new_gauge(
+                "relayer_gas_reconciliation_gas_coverage",
+                "Destination gas paid for per unit of gas spent over the current epoch, below 1 if deliveries were underpaid",
+                labels,
+            )?,
+        })
+    }
+}
+
+struct RouteMetrics {
+    messages: IntGauge,
+    payment: Gauge,
+    tokens_used: Gauge,
+    gas_amount: Gauge,
+    gas_used: Gauge,
+    gas_coverage: Gauge,
+}
+
+impl RouteMetrics {
+    fn new(metrics: &GasReconciliationMetrics, origin: &str, destination: &str) -> Self {
+        let labels = &[origin, destination];
+        Self {
+            messages: metrics.messages.with_label_values(labels),
+            payment: metrics.payment.with_label_values(labels),
+            tokens_used: metrics.tokens_used.with_label_values(labels),
+            gas_amount: metrics.gas_amount.with_label_values(labels),
+            gas_used: metrics.gas_used.with_label_values(labels),
+            gas_coverage: metrics.gas_coverage.with_label_values(labels),
+        }
+    }
+
+    fn set(&self, data: &GasReconciliationData) {
+        self.messages.set(data.messages as i64);
+        self.payment.set(data.payment.to_f64_lossy());
+        self.tokens_used.set(data.tokens_used.to_f64_lossy());
+        self.gas_amount.set(data.gas_amount.to_f64_lossy());
+        self.gas_used.set(data.gas_used.to_f64_lossy());
+        let gas_coverage = if data.gas_used.is_zero() {
+            0.
+        } else {
+            data.gas_amount.to_f64_lossy() / data.gas_used.to_f64_lossy()
+        };
+        self.gas_coverage.set(gas_coverage);
+    }
+}
+
+/// Periodically adds the gas paid for and spent on the messages of an origin
+/// to the totals of the current epoch in the database, and exports them per
+/// route so that gas payment enforcement can be tuned to the actual cost of
+/// deliveries. Payments are counted on the first delivery attempt of a
+/// message, spend on every attempt.
+pub struct GasReconciler {
+    origin: HyperlaneDomain,
+    db: HyperlaneRocksDB,
+    ledger: Arc<GasLedger>,
+    epoch: Duration,
+    routes: HashMap<u32, RouteMetrics>,
+}
+
+impl Debug for GasReconciler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "GasReconciler {{ origin: {}, epoch: {:?} }}",
+            self.origin, self.epoch
+        )
+    }
+}
+
+impl GasReconciler {
+    pub fn new<'a>(
+        origin: HyperlaneDomain,
+        destinations: impl IntoIterator<Item = &'a HyperlaneDomain>,
+        db: HyperlaneRocksDB,
+        ledger: Arc<GasLedger>,
+        epoch: Duration,
+        metrics: &GasReconciliationMetrics,
+    ) -> Self {
+        let routes = destinations
+            .into_iter()
+            .map(|destination| {
+                let route = RouteMetrics::new(metrics, origin.name(), destination.name());
+                (destination.id(), route)
+            })
+            .collect();
+        Self {
+            origin,
+            db,
+            ledger,
+            epoch,
+            routes,
+        }
+    }
+
+    fn reconcile(&self, now: Duration) {
+        let epoch = epoch_at(now, self.epoch);
+        let mut recorded = self.ledger.take();
+        for (&destination, route) in &self.routes {
+            let data = recorded.remove(&destination).unwrap_or_default();
+            let stored = match self.db.retrieve_gas_reconciliation(destination, epoch) {
+                Ok(stored) => stored.unwrap_or_default(),
+                Err(err) => {
+                    warn!(origin=%self.origin, destination, epoch, ?err, "Failed to retrieve gas reconciliation");
+                    self.ledger.record(destination, data);
+                    continue;
+                }
+            };
+            let total = stored + data;
+            if data != GasReconciliationData::default() {
+                if let Err(err) = self.db.store_gas_reconciliation(destination, epoch, &total) {
+                    warn!(origin=%self.origin, destination, epoch, ?err, "Failed to store gas reconciliation");
+                    self.ledger.record(destination, data);
+                    continue;
+                }
+                debug!(origin=%self.origin, destination, epoch, ?total, "Reconciled gas payments with delivery spend");
+            }
+            route.set(&total);
+        }
+    }
+
+    /// Spawns a tokio task that reconciles the recorded gas on an interval.
+    pub fn spawn(self) -> Instrumented<JoinHandle<()>> {
+        let span = info_span!("GasReconciler", origin=%self.origin);
+        let origin = self.origin.clone();
+        spawn_named("gas_reconciler", Some(&origin), async move {
+            let mut interval = tokio::time::interval(RECONCILIATION_INTERVAL);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                self.reconcile(now);
+            }
+        })
+        .instrument(span)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_base::db::test_utils;
+    use prometheus::Registry;
+
+    use super::*;
+
+    fn spend(messages: u64, payment: u64, gas_used: u64) -> GasReconciliationData {
+        GasReconciliationData {
+            messages,
+            payment: payment.into(),
+            gas_amount: payment.into(),
+            gas_used: gas_used.into(),
+            tokens_used: gas_used.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_per_epoch() {
+        test_utils::run_test_db(|db| async move {
+            let origin = HyperlaneDomain::new_test_domain("test_reconcile_per_epoch");
+            let destination = HyperlaneDomain::new_test_domain("test_destination");
+            let db = HyperlaneRocksDB::new(&origin, db);
+            let core_metrics = CoreMetrics::new("dummy_relayer", 37582, Registry::new()).unwrap();
+            let metrics = GasReconciliationMetrics::new(&core_metrics).unwrap();
+            let ledger = Arc::new(GasLedger::default());
+            let epoch = Duration::from_secs(100);
+            let reconciler = GasReconciler::new(
+                origin,
+                [&destination],
+                db.clone(),
+                ledger.clone(),
+                epoch,
+                &metrics,
+            );
+            let id = destination.id();
+
+            ledger.record(id, spend(1, 100, 0));
+            ledger.record(id, spend(0, 0, 80));
+            reconciler.reconcile(Duration::from_secs(250));
+            ledger.record(id, spend(1, 50, 60));
+            reconciler.reconcile(Duration::from_secs(299));
+            assert_eq!(
+                db.retrieve_gas_reconciliation(id, 2).unwrap(),
+                Some(spend(2, 150, 140))
+            );
+
+            // Gas recorded in the next epoch is reconciled separately
+            ledger.record(id, spend(1, 10, 20));
+            reconciler.reconcile(Duration::from_secs(300));
+            assert_eq!(
+                db.retrieve_gas_reconciliation(id, 3).unwrap(),
+                Some(spend(1, 10, 20))
+            );
+            assert_eq!(
+                db.retrieve_gas_reconciliation(id, 2).unwrap(),
+                Some(spend(2, 150, 140))
+            );
+            assert_eq!(reconciler.routes[&id].gas_coverage.get(), 0.5);
+        })
+        .await;
+    }
+}
@@ -0,0 +1,583 @@
+//! Leader election between relayer replicas. All replicas index and prepare
+//! operations, but only the elected leader submits them, so a standby replica
+//! can take over as soon as the leader goes away without both of them
+//! spending gas on the same deliveries.
+
+use std::{
+    fmt::Debug,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use eyre::{Context, Result};
+use hyperlane_base::spawn_named;
+use prometheus::IntGauge;
+use reqwest::{Certificate, Client, StatusCode};
+use sea_orm::{
+    ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbBackend, Statement,
+};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::Mutex,
+    task::JoinHandle,
+    time::{sleep, timeout, Instant},
+};
+use tracing::{error, info, info_span, instrument::Instrumented, warn, Instrument};
+
+use crate::settings::{LeaderElectionBackend, LeaderElectionConf};
+
+/// Where the service account of a pod is mounted in Kubernetes
+const SERVICE_ACCOUNT_DIR: &str = "/var/run/secrets/kubernetes.io/serviceaccount";
+
+/// How long confirming the leadership before a submission may take
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Whether this relayer instance is the leader, and may submit operations.
+/// Instances without leader election are always the leader.
+#[derive(Debug, Clone)]
+pub struct Leadership {
+    leader: Arc<AtomicBool>,
+    election: Option<Arc<dyn LeaderElection>>,
+}
+
+impl Default for Leadership {
+    fn default() -> Self {
+        Self {
+            leader: Arc::new(AtomicBool::new(true)),
+            election: None,
+        }
+    }
+}
+
+impl Leadership {
+    /// Whether this instance was the leader when the elector last took or
+    /// renewed the leadership
+    pub fn is_leader(&self) -> bool {
+        self.leader.load(Ordering::Relaxed)
+    }
+
+    /// Whether this instance is still the leader, checked with the election
+    /// right before submitting. The elector only renews the leadership
+    /// periodically, so a leader whose lock was taken over in between would
+    /// otherwise keep submitting until its next renewal.
+    pub async fn confirm(&self) -> bool {
+        if !self.is_leader() {
+            return false;
+        }
+        let Some(election) = &self.election else {
+            return true;
+        };
+        match timeout(CONFIRM_TIMEOUT, election.still_held()).await {
+            Ok(Ok(held)) => held,
+            Ok(Err(err)) => {
+                warn!(?err, "Failed to confirm the leadership, not submitting");
+                false
+            }
+            Err(_) => {
+                warn!("Timed out confirming the leadership, not submitting");
+                false
+            }
+        }
+    }
+}
+
+/// A lock held by at most one relayer instance at a time
+#[async_trait]
+pub trait LeaderElection: Debug + Send + Sync {
+    /// Take the lock, or renew it if this instance already holds it.
+    /// Returns false if another instance holds the lock.
+    async fn try_acquire(&self) -> Result<bool>;
+
+    /// Whether this instance still holds the lock it last took or renewed,
+    /// without taking it. By default the lock is trusted to be held until
+    /// the next renewal, which suits locks that can only be taken over once
+    /// their holder stopped renewing them for a whole lease duration.
+    async fn still_held(&self) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+/// Elects a leader with a Postgres advisory lock. The lock is held for as
+/// long as the database session of the leader lives, so it is released as
+/// soon as the leader exits or loses its connection, and another instance
+/// can take it right away. Submitters therefore check that the lock is
+/// still held before each submission.
+#[derive(Debug)]
+pub struct PostgresLeaderElection {
+    db: DatabaseConnection,
+    lock_id: u32,
+    held: AtomicBool,
+}
+
+const TRY_ADVISORY_LOCK: &str = "SELECT pg_try_advisory_lock($1) AS leader";
+
+/// Whether the current session still holds the lock. Taking an advisory lock
+/// again would succeed but stack another hold on it.
+const HOLDS_ADVISORY_LOCK: &str = "SELECT EXISTS (
+    SELECT 1 FROM pg_locks
+    WHERE locktype = 'advisory' AND classid = 0 AND objid::bigint = $1 AND objsubid = 1
+        AND pid = pg_backend_pid() AND granted
+) AS leader";
+
+impl PostgresLeaderElection {
+    /// Connect to the database at `url`. A single connection is used, since
+    /// advisory locks belong to the session that took them.
+    pub async fn connect(url: &str, lock_id: u32) -> Result<Self> {
+        let mut options = ConnectOptions::new(url.to_owned());
+        options.max_connections(1).min_connections(1);
+        Ok(Self {
+            db: Database::connect(options).await?,
+            lock_id,
+            held: AtomicBool::new(false),
+        })
+    }
+
+    /// Run `query` for whether this session holds the lock
+    async fn query_lock(&self, query: &str) -> Result<bool> {
+        let leader = self
+            .db
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                query,
+                [i64::from(self.lock_id).into()],
+            ))
+            .await
+            .and_then(|row| row.map(|row| row.try_get::<bool>("", "leader")).transpose());
+        // If the session was lost, so was the lock
+        let held = matches!(leader, Ok(Some(true)));
+        self.held.store(held, Ordering::Relaxed);
+        Ok(leader?.unwrap_or(false))
+    }
+}
+
+#[async_trait]
+impl LeaderElection for PostgresLeaderElection {
+    async fn try_acquire(&self) -> Result<bool> {
+        let query = if self.held.load(Ordering::Relaxed) {
+            HOLDS_ADVISORY_LOCK
+        } else {
+            TRY_ADVISORY_LOCK
+        };
+        self.query_lock(query).await
+    }
+
+    async fn still_held(&self) -> Result<bool> {
+        if !self.held.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
+        self.query_lock(HOLDS_ADVISORY_LOCK).await
+    }
+}
+
+/// A `coordination.k8s.io/v1` Lease
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Lease {
+    api_version: String,
+    kind: String,
+    metadata: LeaseMetadata,
+    #[serde(default)]
+    spec: LeaseSpec,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LeaseMetadata {
+    name: String,
+    namespace: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resource_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LeaseSpec {
+    holder_identity: Option<String>,
+    lease_duration_seconds: Option<u64>,
+    acquire_time: Option<String>,
+    renew_time: Option<String>,
+    lease_transitions: Option<u32>,
+}
+
+/// What to do with a lease that was read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LeaseAction {
+    /// This instance holds the lease
+    Renew,
+    /// The lease is free, or its holder stopped renewing it
+    TakeOver,
+    /// Another instance holds the lease
+    Wait,
+}
+
+impl LeaseSpec {
+    /// The expiry of the lease is judged by how long it was seen unchanged
+    /// rather than by its renew time, so that clocks don't need to agree.
+    fn action(
+        &self,
+        holder: &str,
+        unchanged_for: Duration,
+        default_duration: Duration,
+    ) -> LeaseAction {
+        let duration = self
+            .lease_duration_seconds
+            .map(Duration::from_secs)
+            .unwrap_or(default_duration);
+        match self.holder_identity.as_deref() {
+            Some(current) if current == holder => LeaseAction::Renew,
+            Some(current) if !current.is_empty() && unchanged_for < duration => LeaseAction::Wait,
+            _ => LeaseAction::TakeOver,
+        }
+    }
+}
+
+/// Elects a leader with a Kubernetes Lease, like the controllers of
+/// Kubernetes itself. Requires running in a pod whose service account may
+/// get, create and update leases in its namespace.
+#[derive(Debug)]
+pub struct KubernetesLeaderElection {
+    client: Client,
+    api_url: String,
+    namespace: String,
+    lease_name: String,
+    holder: String,
+    lease_duration: Duration,
+    /// Resource version of the lease when it was last seen held by another
+    /// instance, and when that version was first seen
+    observed: Mutex<Option<(String, Instant)>>,
+}
+
+impl KubernetesLeaderElection {
+    /// Use the lease `lease_name` of `namespace`, or of the namespace of the
+    /// pod if none is given
+    pub fn in_cluster(
+        lease_name: String,
+        namespace: Option<String>,
+        lease_duration: Duration,
+    ) -> Result<Self> {
+        let dir = Path::new(SERVICE_ACCOUNT_DIR);
+        let host = std::env::var("KUBERNETES_SERVICE_HOST")
+            .context("Leader election with a Kubernetes lease requires running in a pod")?;
+        let port = std::env::var("KUBERNETES_SERVICE_PORT").unwrap_or_else(|_| "443".into());
+        let ca = std::fs::read(dir.join("ca.crt")).context("Reading service account CA")?;
+        let namespace = match namespace {
+            Some(namespace) => namespace,
+            None => std::fs::read_to_string(dir.join("namespace"))
+                .context("Reading service account namespace")?
+                .trim()
+                .to_owned(),
+        };
+        let client = Client::builder()
+            .add_root_certificate(Certificate::from_pem(&ca)?)
+            .build()?;
+        // The pod name identifies the instance in the lease
+        let holder =
+            std::env::var("HOSTNAME").unwrap_or_else(|_| format!("{:016x}", rand::random::<u64>()));
+        Ok(Self {
+            client,
+            api_url: format!("https://{host}:{port}"),
+            namespace,
+            lease_name,
+            holder,
+            lease_duration,
+            observed: Mutex::new(None),
+        })
+    }
+
+    fn leases_url(&self) -> String {
+        format!(
+            "{}/apis/coordination.k8s.io/v1/namespaces/{}/leases",
+            self.api_url, self.namespace
+        )
+    }
+
+    fn request(&self, method: reqwest::Method, url: String) -> Result<reqwest::RequestBuilder> {
+        // Service account tokens are rotated, so the token is read every time
+        let token = std::fs::read_to_string(Path::new(SERVICE_ACCOUNT_DIR).join("token"))
+            .context("Reading service account token")?;
+        Ok(self.client.request(method, url).bearer_auth(token.trim()))
+    }
+
+    /// Create or update `lease` as held by this instance. Returns false if
+    /// another instance changed the lease in the meantime.
+    async fn write(&self, mut lease: Lease, take_over: bool) -> Result<bool> {
+        let now = Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true);
+        if take_over {
+            lease.spec.acquire_time = Some(now.clone());
+            lease.spec.lease_transitions = Some(lease.spec.lease_transitions.map_or(0, |t| t + 1));
+        }
+        lease.spec.holder_identity = Some(self.holder.clone());
+        lease.spec.lease_duration_seconds = Some(self.lease_duration.as_secs());
+        lease.spec.renew_time = Some(now);
+        let request = match lease.metadata.resource_version {
+            Some(_) => self.request(
+                reqwest::Method::PUT,
+                format!("{}/{}", self.leases_url(), self.lease_name),
+            )?,
+            None => self.request(reqwest::Method::POST, self.leases_url())?,
+        };
+        let response = request.json(&lease).send().await?;
+        match response.status() {
+            // Another instance created or updated the lease first
+            StatusCode::CONFLICT => Ok(false),
+            _ => {
+                response.error_for_status()?;
+                Ok(true)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl LeaderElection for KubernetesLeaderElection {
+    async fn try_acquire(&self) -> Result<bool> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                format!("{}/{}", self.leases_url(), self.lease_name),
+            )?
+            .send()
+            .await?;
+        let lease = match response.status() {
+            StatusCode::NOT_FOUND => Lease {
+                api_version: "coordination.k8s.io/v1".to_owned(),
+                kind: "Lease".to_owned(),
+                metadata: LeaseMetadata {
+                    name: self.lease_name.clone(),
+                    namespace: self.namespace.clone(),
+                    resource_version: None,
+                },
+                spec: LeaseSpec::default(),
+            },
+            _ => response.error_for_status()?.json::<Lease>().await?,
+        };
+
+        let unchanged_for = {
+            let mut observed = self.observed.lock().await;
+            let version = lease.metadata.resource_version.clone().unwrap_or_default();
+            match &*observed {
+                Some((observed_version, since)) if *observed_version == version => since.elapsed(),
+                _ => {
+                    *observed = Some((version, Instant::now()));
+                    Duration::ZERO
+                }
+            }
+        };
+        match lease
+            .spec
+            .action(&self.holder, unchanged_for, self.lease_duration)
+        {
+            LeaseAction::Renew => self.write(lease, false).await,
+            LeaseAction::TakeOver => self.write(lease, true).await,
+            LeaseAction::Wait => Ok(false),
+        }
+    }
+}
+
+/// Build the leader election described by `conf`
+pub async fn build_leader_election(conf: &LeaderElectionConf) -> Result<Arc<dyn LeaderElection>> {
+    Ok(match &conf.backend {
+        LeaderElectionBackend::Postgres { url, lock_id } => {
+            Arc::new(PostgresLeaderElection::connect(url, *lock_id).await?)
+        }
+        LeaderElectionBackend::Kubernetes {
+            lease_name,
+            namespace,
+        } => Arc::new(KubernetesLeaderElection::in_cluster(
+            lease_name.clone(),
+            namespace.clone(),
+            conf.lease_duration,
+        )?),
+    })
+}
+
+/// Periodically takes or renews the leadership of this instance. A leader
+/// that fails to renew its leadership steps down immediately, well before
+/// its lease can expire. Since a lock can also be lost between renewals,
+/// submitters confirm the leadership before each submission with
+/// [`Leadership::confirm`]. A submission already underway when the lock is
+/// lost still completes, so two instances may briefly overlap.
+#[derive(Debug, Clone)]
+pub struct LeaderElector {
+    election: Arc<dyn LeaderElection>,
+    leadership: Leadership,
+    lease_duration: Duration,
+    is_leader: IntGauge,
+}
+
+impl LeaderElector {
+    /// Create an elector starting out as a standby
+    pub fn new(
+        election: Arc<dyn LeaderElection>,
+        lease_duration: Duration,
+        is_leader: IntGauge,
+    ) -> Self {
+        Self {
+            leadership: Leadership {
+                leader: Arc::new(AtomicBool::new(false)),
+                election: Some(election.clone()),
+            },
+            election,
+            lease_duration,
+            is_leader,
+        }
+    }
+
+    /// The leadership of this instance, as decided by the elector
+    pub fn leadership(&self) -> Leadership {
+        self.leadership.clone()
+    }
+
+    fn set_leader(&self, leader: bool) {
+        let was_leader = self.leadership.leader.swap(leader, Ordering::Relaxed);
+        self.is_leader.set(leader as i64);
+        match (was_leader, leader) {
+            (false, true) => info!("Became the leader, submitting operations"),
+            (true, false) => warn!("Lost the leadership, standing by"),
+            _ => {}
+        }
+    }
+
+    /// Take or renew the leadership once. Any failure to do so in time
+    /// steps down right away, since this instance can't tell whether
+    /// another one took over in the meantime.
+    async fn elect(&self, timeout_after: Duration) {
+        match timeout(timeout_after, self.election.try_acquire()).await {
+            Ok(Ok(leader)) => self.set_leader(leader),
+            Ok(Err(err)) => {
+                error!(?err, "Failed to take or renew the leadership");
+                self.set_leader(false);
+            }
+            Err(_) => {
+                error!(
+                    ?timeout_after,
+                    "Timed out taking or renewing the leadership"
+                );
+                self.set_leader(false);
+            }
+        }
+    }
+
+    async fn run(self) {
+        // Renew well within the lease duration
+        let retry_period = self.lease_duration / 3;
+        loop {
+            self.elect(retry_period).await;
+            sleep(retry_period).await;
+        }
+    }
+
+    /// Spawns a tokio task running the election
+    pub fn spawn(self) -> Instrumented<JoinHandle<()>> {
+        let span = info_span!("LeaderElector");
+        spawn_named("leader_election", None, self.run()).instrument(span)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DURATION: Duration = Duration::from_secs(15);
+
+    fn held_by(holder: &str) -> LeaseSpec {
+        LeaseSpec {
+            holder_identity: Some(holder.to_owned()),
+            lease_duration_seconds: Some(DURATION.as_secs()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_lease_action() {
+        let free = LeaseSpec::default();
+        assert_eq!(
+            free.action("a", Duration::ZERO, DURATION),
+            LeaseAction::TakeOver
+        );
+        assert_eq!(
+            held_by("").action("a", Duration::ZERO, DURATION),
+            LeaseAction::TakeOver
+        );
+        assert_eq!(
+            held_by("a").action("a", DURATION * 2, DURATION),
+            LeaseAction::Renew
+        );
+        assert_eq!(
+            held_by("b").action("a", Duration::from_secs(5), DURATION),
+            LeaseAction::Wait
+        );
+        assert_eq!(
+            held_by("b").action("a", DURATION, DURATION),
+            LeaseAction::TakeOver
+        );
+    }
+
+    /// Acquires the lock until told to fail
+    #[derive(Debug, Default)]
+    struct FlakyElection {
+        failing: AtomicBool,
+    }
+
+    #[async_trait]
+    impl LeaderElection for FlakyElection {
+        async fn try_acquire(&self) -> Result<bool> {
+            if self.failing.load(Ordering::Relaxed) {
+                eyre::bail!("connection lost");
+            }
+            Ok(true)
+        }
+
+        async fn still_held(&self) -> Result<bool> {
+            self.try_acquire().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_steps_down_on_failure() {
+        let election = Arc::new(FlakyElection::default());
+        let elector = LeaderElector::new(
+            election.clone(),
+            DURATION,
+            IntGauge::new("is_leader", "is_leader").unwrap(),
+        );
+        let leadership = elector.leadership();
+        assert!(!leadership.is_leader());
+
+        elector.elect(DURATION).await;
+        assert!(leadership.is_leader());
+
+        election.failing.store(true, Ordering::Relaxed);
+        elector.elect(DURATION).await;
+        assert!(!leadership.is_leader());
+        assert_eq!(elector.is_leader.get(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_confirms_leadership_before_submitting() {
+        let election = Arc::new(FlakyElection::default());
+        let elector = LeaderElector::new(
+            election.clone(),
+            DURATION,
+            IntGauge::new("is_leader", "is_leader").unwrap(),
+        );
+        let leadership = elector.leadership();
+        assert!(!leadership.confirm().await);
+
+        elector.elect(DURATION).await;
+        assert!(leadership.confirm().await);
+
+        // The lock is lost before the elector renews it
+        election.failing.store(true, Ordering::Relaxed);
+        assert!(leadership.is_leader());
+        assert!(!leadership.confirm().await);
+
+        assert!(Leadership::default().confirm().await);
+    }
+}
@@ -1,4 +1,12 @@
+mod bootstrap;
+mod checkpoint_inspector;
+mod db_explorer;
+mod funding_monitor;
+mod gas_reconciliation;
+mod leader_election;
+mod manual_process;
 mod merkle_tree;
+mod message_backlog;
 mod msg;
 mod processor;
 mod prover;
@@ -6,5 +14,10 @@ mod relayer;
 mod server;
 mod settings;
 
+pub use checkpoint_inspector::{
+    checkpoint_inspector_main, CheckpointInspectorSettings, CheckpointStatus,
+};
+pub use db_explorer::{db_explorer_main, DbCommand, DbExplorerSettings};
+pub use manual_process::{manual_process_main, ManualProcessSettings, MessageSource};
 pub use msg::GAS_EXPENDITURE_LOG_MESSAGE;
 pub use relayer::*;
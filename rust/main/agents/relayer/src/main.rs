@@ -7,9 +7,11 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+use std::process::ExitCode;
+
 use eyre::Result;
 
-use hyperlane_base::agent_main;
+use hyperlane_base::{agent_main, AgentOutcome};
 
 use relayer::Relayer;
 
@@ -17,14 +19,17 @@ use relayer::Relayer;
 mod memory_profiler;
 
 #[tokio::main(flavor = "multi_thread", worker_threads = 20)]
-async fn main() -> Result<()> {
+async fn main() -> Result<ExitCode> {
     let agent_main_fut = agent_main::<Relayer>();
 
     #[cfg(feature = "memory-profiling")]
-    memory_profiler::run_future(agent_main_fut).await?;
+    let outcome = memory_profiler::run_future(agent_main_fut).await?;
 
     #[cfg(not(feature = "memory-profiling"))]
-    agent_main_fut.await?;
+    let outcome = agent_main_fut.await?;
 
-    Ok(())
+    if let AgentOutcome::ConfigValidated(report) = &outcome {
+        println!("{}", report.to_json()?);
+    }
+    Ok(outcome.exit_code())
 }
@@ -0,0 +1,274 @@
+//! One-off delivery of a single message, for unsticking messages the relayer
+//! failed to deliver.
+//!
+//! The message is looked up by id in the relayer database, or passed in raw.
+//! Metadata is built with the same builders the relayer uses, which needs the
+//! origin's merkle tree insertions from the relayer database. RocksDB only
+//! allows a single writer, so point `db` at a copy of the database or stop the
+//! relayer first.
+//!
+//! Configuration is loaded the same way as for the agents, so every option can
+//! be passed as an argument, e.g.
+//! `hyperlane-process --originChainName ethereum --destinationChainName arbitrum --messageId 0x..`.
+
+use std::{collections::HashSet, path::PathBuf, sync::Arc};
+
+use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use ethers::types::Bytes;
+use eyre::{bail, eyre, Context, Result};
+use hyperlane_base::{
+    db::{HyperlaneDb, HyperlaneRocksDB, DB},
+    impl_loadable_from_settings,
+    settings::{
+        parser::{RawAgentConf, ValueParser},
+        Settings,
+    },
+    CoreMetrics, LoadableFromSettings,
+};
+use hyperlane_core::{
+    cfg_unwrap_all, config::*, utils::bytes_to_hex, Decode, HyperlaneDomain, HyperlaneMessage,
+    Mailbox, H256,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::{
+    merkle_tree::builder::MerkleTreeBuilder,
+    msg::metadata::{
+        BaseMetadataBuilder, IsmAwareAppContextClassifier, MessageMetadataBuilder, MetadataBuilder,
+    },
+};
+
+/// Name the tool reports metrics and logs under.
+const TOOL_NAME: &str = "hyperlane-process";
+
+/// Where to get the message to deliver from.
+#[derive(Debug, Clone)]
+pub enum MessageSource {
+    /// Look the message up in the relayer database
+    Id(H256),
+    /// Decode the message from its raw bytes
+    Raw(Vec<u8>),
+}
+
+/// Settings for the manual process tool
+#[derive(Debug, AsRef, AsMut, Deref, DerefMut)]
+pub struct ManualProcessSettings {
+    #[as_ref]
+    #[as_mut]
+    #[deref]
+    #[deref_mut]
+    base: Settings,
+
+    /// Path to the relayer database
+    pub db: PathBuf,
+    /// Chain the message was dispatched on
+    pub origin_chain: HyperlaneDomain,
+    /// Chain to deliver the message on
+    pub destination_chain: HyperlaneDomain,
+    /// The message to deliver
+    pub message: MessageSource,
+    /// Whether to allow validators announcing local checkpoint syncers
+    pub allow_local_checkpoint_syncers: bool,
+    /// Only build metadata and estimate the cost of delivery, without
+    /// submitting a transaction
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+struct RawManualProcessSettings(Value);
+
+impl_loadable_from_settings!(ManualProcess, RawManualProcessSettings -> ManualProcessSettings);
+
+impl FromRawConf<RawManualProcessSettings> for ManualProcessSettings {
+    fn from_config_filtered(
+        raw: RawManualProcessSettings,
+        cwp: &ConfigPath,
+        _filter: (),
+    ) -> ConfigResult<Self> {
+        let mut err = ConfigParsingError::default();
+
+        let p = ValueParser::new(cwp.clone(), &raw.0);
+
+        let origin_chain_name = p
+            .chain(&mut err)
+            .get_key("originChainName")
+            .parse_string()
+            .end();
+        let destination_chain_name = p
+            .chain(&mut err)
+            .get_key("destinationChainName")
+            .parse_string()
+            .end();
+
+        let chain_names: HashSet<&str> = origin_chain_name
+            .into_iter()
+            .chain(destination_chain_name)
+            .collect();
+
+        let base = p
+            .parse_from_raw_config::<Settings, RawAgentConf, Option<&HashSet<&str>>>(
+                Some(&chain_names),
+                "Expected valid base agent configuration",
+            )
+            .take_config_err(&mut err);
+
+        let origin_chain = if let (Some(base), Some(name)) = (&base, origin_chain_name) {
+            base.lookup_domain(name)
+                .context("Missing configuration for the origin chain")
+                .take_err(&mut err, || cwp + "origin_chain_name")
+        } else {
+            None
+        };
+        let destination_chain = if let (Some(base), Some(name)) = (&base, destination_chain_name) {
+            base.lookup_domain(name)
+                .context("Missing configuration for the destination chain")
+                .take_err(&mut err, || cwp + "destination_chain_name")
+        } else {
+            None
+        };
+
+        let message_id = p
+            .chain(&mut err)
+            .get_opt_key("messageId")
+            .parse_address_hash()
+            .end();
+        let raw_message = p
+            .chain(&mut err)
+            .get_opt_key("message")
+            .parse_from_str::<Bytes>("Expected hex encoded message")
+            .end();
+        let message = match (message_id, raw_message) {
+            (Some(id), None) => Some(MessageSource::Id(id)),
+            (None, Some(raw)) => Some(MessageSource::Raw(raw.to_vec())),
+            _ => {
+                err.push(
+                    cwp + "message_id",
+                    eyre!("Expected exactly one of `messageId` or `message`"),
+                );
+                None
+            }
+        };
+
+        let db = p
+            .chain(&mut err)
+            .get_opt_key("db")
+            .parse_from_str("Expected database path")
+            .unwrap_or_else(|| std::env::current_dir().unwrap().join("hyperlane_db"));
+
+        let allow_local_checkpoint_syncers = p
+            .chain(&mut err)
+            .get_opt_key("allowLocalCheckpointSyncers")
+            .parse_bool()
+            .unwrap_or(false);
+
+        let dry_run = p
+            .chain(&mut err)
+            .get_opt_key("dryRun")
+            .parse_bool()
+            .unwrap_or(false);
+
+        cfg_unwrap_all!(cwp, err: [base, origin_chain, destination_chain, message]);
+
+        err.into_result(Self {
+            base,
+            db,
+            origin_chain,
+            destination_chain,
+            message,
+            allow_local_checkpoint_syncers,
+            dry_run,
+        })
+    }
+}
+
+/// Load the settings from the config locations and deliver the configured
+/// message.
+pub async fn manual_process_main() -> Result<()> {
+    let settings = ManualProcessSettings::load()?;
+    let metrics = settings.as_ref().metrics(TOOL_NAME)?;
+    let _console_server = settings.tracing.start_tracing(&metrics)?;
+    manual_process(settings, metrics).await
+}
+
+async fn manual_process(settings: ManualProcessSettings, metrics: Arc<CoreMetrics>) -> Result<()> {
+    let origin = &settings.origin_chain;
+    let destination = &settings.destination_chain;
+    let db = HyperlaneRocksDB::new(origin, DB::from_path(&settings.db)?);
+
+    let message = match &settings.message {
+        MessageSource::Id(id) => db.retrieve_message_by_id(id)?.ok_or_else(|| {
+            eyre!("Message {id:?} not found in the relayer database, pass the raw message instead")
+        })?,
+        MessageSource::Raw(bytes) => HyperlaneMessage::read_from(&mut bytes.as_slice())
+            .context("Failed to decode raw message")?,
+    };
+    if message.origin != origin.id() || message.destination != destination.id() {
+        bail!(
+            "Message {:?} is from domain {} to domain {}, not from {origin} to {destination}",
+            message.id(),
+            message.origin,
+            message.destination
+        );
+    }
+    info!(id=?message.id(), ?message, "Delivering message");
+
+    let mailbox = settings.build_mailbox(destination, &metrics).await?;
+    if mailbox.delivered(message.id()).await? {
+        info!(id=?message.id(), "Message was already delivered");
+        return Ok(());
+    }
+
+    // Rebuild the origin merkle tree from the insertions the relayer indexed
+    let mut prover = MerkleTreeBuilder::new();
+    let mut leaf_index = 0;
+    while let Some(insertion) = db.retrieve_merkle_tree_insertion_by_leaf_index(&leaf_index)? {
+        prover.ingest_message_id(insertion.message_id()).await?;
+        leaf_index += 1;
+    }
+    info!(leaf_count = prover.count(), "Rebuilt origin merkle tree");
+
+    let destination_mailbox: Arc<dyn Mailbox> = mailbox.into();
+    let base_builder = Arc::new(BaseMetadataBuilder::new(
+        origin.clone(),
+        settings.chain_setup(destination)?.clone(),
+        Arc::new(RwLock::new(prover)),
+        settings
+            .build_validator_announce(origin, &metrics)
+            .await?
+            .into(),
+        settings.allow_local_checkpoint_syncers,
+        metrics.clone(),
+        db,
+        IsmAwareAppContextClassifier::new(destination_mailbox.clone(), vec![]),
+        settings.proxy.clone(),
+    ));
+
+    let ism_address = destination_mailbox.recipient_ism(message.recipient).await?;
+    let metadata = MessageMetadataBuilder::new(ism_address, &message, base_builder)
+        .await?
+        .build(ism_address, &message)
+        .await?
+        .ok_or_else(|| eyre!("Could not build metadata for message {:?}", message.id()))?;
+    info!(metadata=%bytes_to_hex(&metadata), "Built metadata");
+
+    let cost_estimate = destination_mailbox
+        .process_estimate_costs(&message, &metadata)
+        .await?;
+    info!(?cost_estimate, "Estimated delivery cost");
+    if settings.dry_run {
+        return Ok(());
+    }
+
+    let outcome = destination_mailbox
+        .process(&message, &metadata, None)
+        .await?;
+    if !outcome.executed {
+        bail!("Process transaction reverted: {outcome:?}");
+    }
+    info!(tx_outcome=?outcome, "Delivered message");
+    Ok(())
+}
@@ -39,6 +39,10 @@ impl Debug for MerkleTreeProcessor {
 
 #[async_trait]
 impl ProcessorExt for MerkleTreeProcessor {
+    fn name(&self) -> &'static str {
+        "merkle_tree_processor"
+    }
+
     /// The domain this processor is getting merkle tree hook insertions from.
     fn domain(&self) -> &HyperlaneDomain {
         self.db.domain()
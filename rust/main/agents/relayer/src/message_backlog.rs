@@ -0,0 +1,397 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{Debug, Formatter},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use eyre::Result;
+use hyperlane_base::{
+    db::{HyperlaneDb, HyperlaneRocksDB},
+    spawn_named, CoreMetrics,
+};
+use hyperlane_core::{HyperlaneDomain, HyperlaneMessageRef, MessageVersions, H256};
+use prometheus::{IntGauge, IntGaugeVec};
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::{debug, info, info_span, instrument::Instrumented, warn, Instrument};
+
+use crate::msg::filter::MessageFilter;
+
+/// How often the backlog is refreshed from the database.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How many nonces are scanned before yielding to the runtime, so that the
+/// initial scan of a long message history doesn't starve other futures.
+const NONCES_PER_YIELD: u32 = 1000;
+
+/// The backlog of undelivered messages from an origin, per destination.
+#[derive(Debug, Clone)]
+pub struct MessageBacklogMetrics {
+    unprocessed: IntGaugeVec,
+    retried: IntGaugeVec,
+    oldest_age: IntGaugeVec,
+}
+
+impl MessageBacklogMetrics {
+    pub fn new(metrics: &CoreMetrics) -> Result<Self> {
+        let labels = &["origin", "remote"];
+        Ok(Self {
+            unprocessed: metrics.new_int_gauge(
+                "relayer_backlog_unprocessed_messages",
+                "Indexed messages which haven't been delivered yet",
+                labels,
+            )?,
+            retried: metrics.new_int_gauge(
+                "relayer_backlog_retried_messages",
+                "Undelivered messages whose delivery failed at least once",
+                labels,
+            )?,
+            oldest_age: metrics.new_int_gauge(
+                "relayer_backlog_oldest_message_age_seconds",
+                "Seconds since the oldest undelivered message was indexed",
+                labels,
+            )?,
+        })
+    }
+}
+
+struct RouteMetrics {
+    unprocessed: IntGauge,
+    retried: IntGauge,
+    oldest_age: IntGauge,
+}
+
+impl RouteMetrics {
+    fn new(metrics: &MessageBacklogMetrics, origin: &str, destination: &str) -> Self {
+        let labels = &[origin, destination];
+        Self {
+            unprocessed: metrics.unprocessed.with_label_values(labels),
+            retried: metrics.retried.with_label_values(labels),
+            oldest_age: metrics.oldest_age.with_label_values(labels),
+        }
+    }
+}
+
+/// The backlog of a route, as derived from the database
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct RouteBacklog {
+    unprocessed: i64,
+    retried: i64,
+    oldest_indexed_at: Option<u64>,
+}
+
+/// An undelivered message, as tracked between refreshes
+#[derive(Debug)]
+struct UnprocessedMessage {
+    id: H256,
+    destination: u32,
+    indexed_at: Option<u64>,
+}
+
+/// Exports the backlog of undelivered messages from an origin, derived from
+/// the database rather than from the submission queues. The queues only fill
+/// up as the processor gets to the messages after a restart, so gauges driven
+/// by them read zero right after deploys, whereas the database already knows
+/// of every undelivered message and of its failed attempts.
+///
+/// The message history is scanned once on startup, after which only new
+/// messages and the ones still undelivered are read. Messages indexed before
+/// their indexing time was stored don't count towards the oldest age, and
+/// messages the processor skips, because they're filtered out or their
+/// destination doesn't support their version, don't count at all.
+pub struct MessageBacklog {
+    origin: HyperlaneDomain,
+    db: HyperlaneRocksDB,
+    message_filter: MessageFilter,
+    routes: HashMap<u32, RouteMetrics>,
+    /// The message versions each destination supports
+    destination_versions: HashMap<u32, MessageVersions>,
+    unprocessed: BTreeMap<u32, UnprocessedMessage>,
+    /// The lowest nonce which hasn't been scanned yet
+    next_nonce: u32,
+}
+
+impl Debug for MessageBacklog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "MessageBacklog {{ origin: {}, next_nonce: {} }}",
+            self.origin, self.next_nonce
+        )
+    }
+}
+
+impl MessageBacklog {
+    pub fn new<'a>(
+        origin: HyperlaneDomain,
+        destinations: impl IntoIterator<Item = (&'a HyperlaneDomain, &'a MessageVersions)>,
+        db: HyperlaneRocksDB,
+        message_filter: MessageFilter,
+        metrics: &MessageBacklogMetrics,
+    ) -> Self {
+        let mut routes = HashMap::new();
+        let mut destination_versions = HashMap::new();
+        for (destination, versions) in destinations {
+            if *destination == origin {
+                continue;
+            }
+            let route = RouteMetrics::new(metrics, origin.name(), destination.name());
+            routes.insert(destination.id(), route);
+            destination_versions.insert(destination.id(), versions.clone());
+        }
+        Self {
+            origin,
+            db,
+            message_filter,
+            routes,
+            destination_versions,
+            unprocessed: BTreeMap::new(),
+            next_nonce: 0,
+        }
+    }
+
+    /// Scans the messages indexed since the last refresh, and forgets the
+    /// ones delivered since.
+    async fn scan(&mut self) -> Result<()> {
+        let Some(highest_nonce) = self.db.retrieve_highest_seen_message_nonce()? else {
+            return Ok(());
+        };
+        while self.next_nonce <= highest_nonce {
+            let nonce = self.next_nonce;
+            // Stop at gaps, which are filled as indexing catches up
+            let Some(raw) = self.db.retrieve_raw_message_by_nonce(nonce)? else {
+                break;
+            };
+            // Messages are inspected in place, since most of them were
+            // delivered long ago and are never decoded
+            let message = HyperlaneMessageRef::new(&raw)?;
+            if self.is_relayed(&message) && !self.is_processed(nonce)? {
+                self.unprocessed.insert(
+                    nonce,
+                    UnprocessedMessage {
+                        id: message.id(),
+                        destination: message.destination(),
+                        indexed_at: self.db.retrieve_message_indexed_at_by_nonce(nonce)?,
+                    },
+                );
+            }
+            self.next_nonce = nonce.saturating_add(1);
+            if nonce % NONCES_PER_YIELD == 0 {
+                tokio::task::yield_now().await;
+            }
+            if nonce == u32::MAX {
+                break;
+            }
+        }
+
+        let mut delivered = vec![];
+        for &nonce in self.unprocessed.keys() {
+            if self.is_processed(nonce)? {
+                delivered.push(nonce);
+            }
+        }
+        for nonce in delivered {
+            self.unprocessed.remove(&nonce);
+        }
+        Ok(())
+    }
+
+    /// Whether the processor relays the message rather than skipping it
+    fn is_relayed(&self, message: &HyperlaneMessageRef) -> bool {
+        let Some(versions) = self.destination_versions.get(&message.destination()) else {
+            return false;
+        };
+        versions.supports(message.version()) && self.message_filter.skip_reason(message).is_none()
+    }
+
+    fn is_processed(&self, nonce: u32) -> Result<bool> {
+        Ok(self
+            .db
+            .retrieve_processed_by_nonce(&nonce)?
+            .unwrap_or(false))
+    }
+
+    fn backlog(&self) -> Result<HashMap<u32, RouteBacklog>> {
+        let mut backlog: HashMap<u32, RouteBacklog> = HashMap::new();
+        for message in self.unprocessed.values() {
+            let route = backlog.entry(message.destination).or_default();
+            route.unprocessed += 1;
+            let retries = self
+                .db
+                .retrieve_pending_message_retry_count_by_message_id(&message.id)?
+                .unwrap_or_default();
+            if retries > 0 {
+                route.retried += 1;
+            }
+            route.oldest_indexed_at = match (route.oldest_indexed_at, message.indexed_at) {
+                (Some(oldest), Some(indexed_at)) => Some(oldest.min(indexed_at)),
+                (oldest, indexed_at) => oldest.or(indexed_at),
+            };
+        }
+        Ok(backlog)
+    }
+
+    async fn refresh(&mut self, now: Duration) -> Result<()> {
+        self.scan().await?;
+        let backlog = self.backlog()?;
+        for (destination, route) in &self.routes {
+            let data = backlog.get(destination).copied().unwrap_or_default();
+            let oldest_age = data
+                .oldest_indexed_at
+                .map_or(0, |indexed_at| now.as_secs().saturating_sub(indexed_at));
+            route.unprocessed.set(data.unprocessed);
+            route.retried.set(data.retried);
+            route.oldest_age.set(oldest_age as i64);
+        }
+        debug!(origin=%self.origin, ?backlog, "Refreshed message backlog");
+        Ok(())
+    }
+
+    /// Spawns a tokio task that refreshes the backlog on an interval,
+    /// starting with a scan of the whole message history.
+    pub fn spawn(mut self) -> Instrumented<JoinHandle<()>> {
+        let span = info_span!("MessageBacklog", origin=%self.origin);
+        let origin = self.origin.clone();
+        spawn_named("message_backlog", Some(&origin), async move {
+            let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            let mut scanned_history = false;
+            loop {
+                interval.tick().await;
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                match self.refresh(now).await {
+                    Ok(()) if !scanned_history => {
+                        scanned_history = true;
+                        info!(
+                            unprocessed = self.unprocessed.len(),
+                            "Derived message backlog from the database"
+                        );
+                    }
+                    Ok(()) => {}
+                    Err(err) => warn!(?err, "Failed to refresh message backlog"),
+                }
+            }
+        })
+        .instrument(span)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::{HyperlaneMessage, MessageVersion};
+    use prometheus::Registry;
+
+    use super::*;
+    use crate::settings::matching_list::MatchingList;
+
+    #[tokio::test]
+    async fn test_backlog_derived_from_db() {
+        test_utils::run_test_db(|db| async move {
+            let origin = HyperlaneDomain::new_test_domain("test_backlog_derived_from_db");
+            let destination = HyperlaneDomain::new_test_domain("test_destination");
+            let db = HyperlaneRocksDB::new(&origin, db);
+            let core_metrics = CoreMetrics::new("dummy_relayer", 37582, Registry::new()).unwrap();
+            let metrics = MessageBacklogMetrics::new(&core_metrics).unwrap();
+            let versions = MessageVersions::default();
+            let mut backlog = MessageBacklog::new(
+                origin.clone(),
+                [(&destination, &versions)],
+                db.clone(),
+                Default::default(),
+                &metrics,
+            );
+
+            let messages: Vec<_> = (0..4)
+                .map(|nonce| HyperlaneMessage {
+                    version: MessageVersion::CURRENT.into(),
+                    nonce,
+                    origin: origin.id(),
+                    destination: destination.id(),
+                    ..Default::default()
+                })
+                .collect();
+            for message in &messages {
+                db.store_message(message, 1).unwrap();
+            }
+            db.store_processed_by_nonce(&0, &true).unwrap();
+            db.store_pending_message_retry_count_by_message_id(&messages[1].id(), &3)
+                .unwrap();
+
+            let indexed_at = db.retrieve_message_indexed_at_by_nonce(1).unwrap().unwrap();
+            let now = Duration::from_secs(indexed_at + 60);
+            backlog.refresh(now).await.unwrap();
+            let route = &backlog.routes[&destination.id()];
+            assert_eq!(route.unprocessed.get(), 3);
+            assert_eq!(route.retried.get(), 1);
+            assert!(route.oldest_age.get() >= 60);
+
+            // Delivered messages leave the backlog
+            for nonce in 1..4 {
+                db.store_processed_by_nonce(&nonce, &true).unwrap();
+            }
+            backlog.refresh(now).await.unwrap();
+            let route = &backlog.routes[&destination.id()];
+            assert_eq!(route.unprocessed.get(), 0);
+            assert_eq!(route.retried.get(), 0);
+            assert_eq!(route.oldest_age.get(), 0);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_backlog_skips_filtered_messages() {
+        test_utils::run_test_db(|db| async move {
+            let origin = HyperlaneDomain::new_test_domain("test_backlog_skips_filtered_messages");
+            let destination = HyperlaneDomain::new_test_domain("test_destination");
+            let db = HyperlaneRocksDB::new(&origin, db);
+            let core_metrics = CoreMetrics::new("dummy_relayer", 37583, Registry::new()).unwrap();
+            let metrics = MessageBacklogMetrics::new(&core_metrics).unwrap();
+            let blacklisted_sender = H256::repeat_byte(1);
+            let blacklist: MatchingList = serde_json::from_value(
+                serde_json::json!([{ "senderaddress": format!("{blacklisted_sender:?}") }]),
+            )
+            .unwrap();
+            let message_filter = MessageFilter {
+                message_blacklist: Arc::new(blacklist),
+                ..Default::default()
+            };
+            let versions = MessageVersions::default();
+            let mut backlog = MessageBacklog::new(
+                origin.clone(),
+                [(&destination, &versions)],
+                db.clone(),
+                message_filter,
+                &metrics,
+            );
+
+            let message = |nonce, version: u8, sender| HyperlaneMessage {
+                version,
+                nonce,
+                origin: origin.id(),
+                sender,
+                destination: destination.id(),
+                ..Default::default()
+            };
+            let current = MessageVersion::CURRENT.into();
+            let messages = [
+                message(0, current, H256::zero()),
+                // Blacklisted
+                message(1, current, blacklisted_sender),
+                // Unsupported by the destination
+                message(2, u8::MAX, H256::zero()),
+            ];
+            for message in &messages {
+                db.store_message(message, 1).unwrap();
+            }
+
+            backlog.refresh(Duration::ZERO).await.unwrap();
+            let route = &backlog.routes[&destination.id()];
+            assert_eq!(route.unprocessed.get(), 1);
+        })
+        .await;
+    }
+}
@@ -1,33 +1,57 @@
-use hyperlane_core::HyperlaneMessage;
+use std::sync::{Arc, RwLock};
+
+use super::filter::FilterableMessage;
 
 #[derive(Debug, Clone, Default)]
 pub struct AddressBlacklist {
     // A list of addresses that are blocked from being relayed.
     // Addresses are any length to support different address types.
     pub blacklist: Vec<Vec<u8>>,
+    // Addresses blocked by the denylist fetched from a screening service,
+    // replaced whenever it is fetched again.
+    denylist: Arc<RwLock<Vec<Vec<u8>>>>,
 }
 
 impl AddressBlacklist {
     pub fn new(blacklist: Vec<Vec<u8>>) -> Self {
-        Self { blacklist }
+        Self {
+            blacklist,
+            denylist: Default::default(),
+        }
+    }
+
+    /// Replaces the addresses blocked by the fetched denylist.
+    pub fn set_denylist(&self, denylist: Vec<Vec<u8>>) {
+        *self.denylist.write().unwrap() = denylist;
     }
 
     /// Returns true if the message is blocked by the blacklist.
     /// At the moment, this only checks if the sender, recipient, or body of the
     /// message contains any of the blocked addresses.
-    pub fn find_blacklisted_address(&self, message: &HyperlaneMessage) -> Option<Vec<u8>> {
-        self.blacklist.iter().find_map(|address| {
-            if is_subsequence(message.sender.as_bytes(), address)
-                || is_subsequence(message.recipient.as_bytes(), address)
-                || is_subsequence(&message.body, address)
-            {
-                // Return the blocked address that was found.
-                Some(address.clone())
-            } else {
-                None
-            }
-        })
+    pub fn find_blacklisted_address(&self, message: &impl FilterableMessage) -> Option<Vec<u8>> {
+        find_address(&self.blacklist, message)
     }
+
+    /// Returns the address of the fetched denylist the message involves, if
+    /// any, the same way as `find_blacklisted_address`.
+    pub fn find_denylisted_address(&self, message: &impl FilterableMessage) -> Option<Vec<u8>> {
+        find_address(&self.denylist.read().unwrap(), message)
+    }
+}
+
+fn find_address(addresses: &[Vec<u8>], message: &impl FilterableMessage) -> Option<Vec<u8>> {
+    let (sender, recipient) = (message.sender(), message.recipient());
+    addresses.iter().find_map(|address| {
+        if is_subsequence(sender.as_bytes(), address)
+            || is_subsequence(recipient.as_bytes(), address)
+            || is_subsequence(message.body(), address)
+        {
+            // Return the blocked address that was found.
+            Some(address.clone())
+        } else {
+            None
+        }
+    })
 }
 
 /// Returns true if `needle` is a subsequence of `haystack`.
@@ -50,7 +74,7 @@ fn is_subsequence<T: PartialEq>(mut haystack: &[T], needle: &[T]) -> bool {
 
 #[cfg(test)]
 mod test {
-    use hyperlane_core::H256;
+    use hyperlane_core::{HyperlaneMessage, H256};
 
     use super::*;
 
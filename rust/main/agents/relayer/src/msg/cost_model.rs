@@ -0,0 +1,330 @@
+//! Cost models estimate what delivering messages costs on a destination, so
+//! that messages are only batched if the batch is cheaper than delivering them
+//! one by one.
+//!
+//! A batch saves the base cost of all but one transaction, but pays for the
+//! multicall dispatching its deliveries. On rollups posting their data to an
+//! L1, the data of a batch also compresses better than the data of each
+//! delivery alone, since deliveries to the same recipients share most of their
+//! calldata.
+
+use std::time::{Duration, Instant};
+
+use hyperlane_core::{config::DataCostModel, HyperlaneProvider, L1DataFee, U256};
+use tracing::warn;
+
+/// Gas every transaction pays before its calldata
+const TX_BASE_GAS: u64 = 21_000;
+/// Execution gas a multicall spends dispatching each of its calls
+const MULTICALL_GAS_PER_CALL: u64 = 3_000;
+/// Size of the ABI encoding of the target, failure flag and calldata offsets
+/// wrapping each call of a multicall, which is mostly zero padding
+const MULTICALL_BYTES_PER_CALL: usize = 128;
+/// Gas of a zero and a non-zero byte of calldata, per EIP-2028
+const ZERO_BYTE_GAS: u64 = 4;
+const NON_ZERO_BYTE_GAS: u64 = 16;
+/// Bytes a rollup posts to its L1 for each transaction on top of its
+/// calldata, i.e. its signature, nonce, gas fields and recipient
+const L1_BYTES_PER_TX: u64 = 68;
+/// How long the L1 data fee read from a destination is priced with
+const L1_DATA_FEE_TTL: Duration = Duration::from_secs(60);
+
+/// Prices the data of a transaction in gas of the destination chain
+pub trait DataGas {
+    /// The gas paid for posting `data` as the calldata of a transaction
+    fn data_gas(&self, data: &[u8]) -> U256;
+}
+
+/// How the data of transactions is priced on a destination, at the fees it
+/// currently charges
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataPricing {
+    /// Calldata priced per EIP-2028
+    Calldata,
+    /// Calldata posted to an L1 compressed, for the given gas of the
+    /// destination per compressed byte
+    L1Data { gas_per_compressed_byte: f64 },
+}
+
+impl DataGas for DataPricing {
+    fn data_gas(&self, data: &[u8]) -> U256 {
+        let l1_gas = match self {
+            DataPricing::Calldata => U256::zero(),
+            DataPricing::L1Data {
+                gas_per_compressed_byte,
+            } => ((compressed_len(data) as f64 * gas_per_compressed_byte) as u64).into(),
+        };
+        l1_gas + U256::from(calldata_gas(data))
+    }
+}
+
+/// Prices the data of transactions on a destination per its cost model,
+/// reading the L1 data fee of rollups from the destination at most once
+/// per [`L1_DATA_FEE_TTL`]
+#[derive(Debug)]
+pub struct DataPricer {
+    model: DataCostModel,
+    /// The last pricing read from the destination, and when it was read
+    l1_data_pricing: Option<(DataPricing, Instant)>,
+}
+
+impl DataPricer {
+    pub fn new(model: DataCostModel) -> Self {
+        Self {
+            model,
+            l1_data_pricing: None,
+        }
+    }
+
+    /// The current pricing of data on the destination of `provider`, or None
+    /// if the L1 data fee of the destination can't be read
+    pub async fn pricing(&mut self, provider: &dyn HyperlaneProvider) -> Option<DataPricing> {
+        if self.model == DataCostModel::Calldata {
+            return Some(DataPricing::Calldata);
+        }
+        if let Some((pricing, read_at)) = self.l1_data_pricing {
+            if read_at.elapsed() < L1_DATA_FEE_TTL {
+                return Some(pricing);
+            }
+        }
+        let pricing = match provider.l1_data_fee().await {
+            Ok(Some(fee)) => l1_data_pricing(&fee)?,
+            Ok(None) => {
+                warn!("Destination doesn't expose an L1 data fee to price batches with");
+                return None;
+            }
+            Err(err) => {
+                warn!(?err, "Failed to read the L1 data fee of the destination");
+                return None;
+            }
+        };
+        self.l1_data_pricing = Some((pricing, Instant::now()));
+        Some(pricing)
+    }
+}
+
+fn l1_data_pricing(fee: &L1DataFee) -> Option<DataPricing> {
+    if fee.gas_price.is_zero() {
+        return None;
+    }
+    let gas_per_compressed_byte =
+        u256_to_f64(fee.fee_per_compressed_byte) / u256_to_f64(fee.gas_price);
+    Some(DataPricing::L1Data {
+        gas_per_compressed_byte,
+    })
+}
+
+fn u256_to_f64(value: U256) -> f64 {
+    // Fees are far below 2^128 wei
+    value.low_u128() as f64
+}
+
+/// A delivery to price, as its estimated gas limit and calldata
+#[derive(Debug, Clone)]
+pub struct DeliveryCost {
+    pub gas_limit: U256,
+    pub calldata: Vec<u8>,
+}
+
+/// The estimated cost of sending each of `deliveries` in its own transaction
+pub fn serial_cost(model: &impl DataGas, deliveries: &[DeliveryCost]) -> U256 {
+    deliveries
+        .iter()
+        .map(|d| {
+            d.gas_limit.saturating_sub(calldata_gas(&d.calldata).into())
+                + model.data_gas(&d.calldata)
+        })
+        .fold(U256::zero(), |acc, cost| acc.saturating_add(cost))
+}
+
+/// The estimated cost of sending `deliveries` in a single multicall
+pub fn batch_cost(model: &impl DataGas, deliveries: &[DeliveryCost]) -> U256 {
+    let execution_gas = deliveries
+        .iter()
+        .map(|d| {
+            d.gas_limit
+                .saturating_sub((TX_BASE_GAS + calldata_gas(&d.calldata)).into())
+        })
+        .fold(U256::zero(), |acc, gas| acc.saturating_add(gas));
+    let batch_calldata = deliveries
+        .iter()
+        .flat_map(|d| {
+            std::iter::repeat(0)
+                .take(MULTICALL_BYTES_PER_CALL)
+                .chain(d.calldata.iter().copied())
+        })
+        .collect::<Vec<_>>();
+    U256::from(TX_BASE_GAS + MULTICALL_GAS_PER_CALL * deliveries.len() as u64)
+        + execution_gas
+        + model.data_gas(&batch_calldata)
+}
+
+/// Gas of `data` as calldata, per EIP-2028
+fn calldata_gas(data: &[u8]) -> u64 {
+    data.iter()
+        .map(|b| match b {
+            0 => ZERO_BYTE_GAS,
+            _ => NON_ZERO_BYTE_GAS,
+        })
+        .sum()
+}
+
+/// The bytes a rollup posts to its L1 for a transaction with `data`
+fn compressed_len(data: &[u8]) -> u64 {
+    flz_compress_len(data) + L1_BYTES_PER_TX
+}
+
+/// The length of `data` compressed with FastLZ, which the OP stack uses to
+/// estimate the compressed size of transactions since Fjord. Port of
+/// `FlzCompressLen` of op-geth.
+fn flz_compress_len(data: &[u8]) -> u64 {
+    let u24 = |i: usize| {
+        u32::from(data[i]) | (u32::from(data[i + 1]) << 8) | (u32::from(data[i + 2]) << 16)
+    };
+    let hash = |v: u32| (v.wrapping_mul(2654435769) >> 19) as usize & 0x1fff;
+    let literals = |r: usize| {
+        let r = r as u64;
+        0x21 * (r / 0x20) + if r % 0x20 != 0 { r % 0x20 + 1 } else { 0 }
+    };
+    let match_len = |l: usize| {
+        let l = l as u64 - 1;
+        3 * (l / 262) + if l % 262 >= 6 { 3 } else { 2 }
+    };
+
+    let mut len = 0;
+    let mut table = vec![0usize; 8192];
+    let mut anchor = 0;
+    let ip_limit = data.len().saturating_sub(13);
+    let mut ip = anchor + 2;
+    while ip < ip_limit {
+        let mut reference;
+        loop {
+            let seq = u24(ip);
+            let h = hash(seq);
+            reference = table[h];
+            table[h] = ip;
+            let distance = ip - reference;
+            if ip >= ip_limit {
+                break;
+            }
+            ip += 1;
+            if distance <= 0x1fff && seq == u24(reference) {
+                break;
+            }
+        }
+        if ip >= ip_limit {
+            break;
+        }
+        ip -= 1;
+        if ip > anchor {
+            len += literals(ip - anchor);
+        }
+        // Length of the match, which counts the first mismatching byte
+        let end = ip_limit + 9 - (ip + 3);
+        let mut l = 0;
+        while l < end {
+            let mismatch = data[reference + 3 + l] != data[ip + 3 + l];
+            l += 1;
+            if mismatch {
+                break;
+            }
+        }
+        len += match_len(l);
+        ip += l;
+        table[hash(u24(ip))] = ip;
+        table[hash(u24(ip + 1))] = ip + 1;
+        ip += 2;
+        anchor = ip;
+    }
+    len + literals(data.len() - anchor)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn deliveries(count: usize) -> Vec<DeliveryCost> {
+        let calldata = [0xd8, 0x12, 0x81, 0xd5]
+            .into_iter()
+            .chain(0..=255)
+            .collect::<Vec<u8>>();
+        vec![
+            DeliveryCost {
+                gas_limit: 150_000.into(),
+                calldata,
+            };
+            count
+        ]
+    }
+
+    #[test]
+    fn test_compressed_len() {
+        assert_eq!(flz_compress_len(&[0; 1000]), 21);
+        assert_eq!(
+            flz_compress_len(&(0..=255).collect::<Vec<u8>>().repeat(4)),
+            279
+        );
+        assert_eq!(flz_compress_len(&[]), 0);
+    }
+
+    #[test]
+    fn test_batch_cost_per_model() {
+        let deliveries = deliveries(3);
+        let calldata = DataPricing::Calldata;
+        assert_eq!(serial_cost(&calldata, &deliveries), 450_000.into());
+        assert_eq!(batch_cost(&calldata, &deliveries), 418_536.into());
+
+        let l1_calldata = DataPricing::L1Data {
+            gas_per_compressed_byte: 160.,
+        };
+        assert_eq!(serial_cost(&l1_calldata, &deliveries), 611_760.into());
+        assert_eq!(batch_cost(&l1_calldata, &deliveries), 476_616.into());
+
+        let l1_blobs = DataPricing::L1Data {
+            gas_per_compressed_byte: 10.,
+        };
+        assert_eq!(serial_cost(&l1_blobs, &deliveries), 460_110.into());
+        assert_eq!(batch_cost(&l1_blobs, &deliveries), 422_166.into());
+    }
+
+    #[test]
+    fn test_l1_data_pricing() {
+        let fee = L1DataFee {
+            fee_per_compressed_byte: 1_600_000_000u64.into(),
+            gas_price: 10_000_000u64.into(),
+        };
+        assert_eq!(
+            l1_data_pricing(&fee),
+            Some(DataPricing::L1Data {
+                gas_per_compressed_byte: 160.
+            })
+        );
+        let free = L1DataFee {
+            gas_price: U256::zero(),
+            ..fee
+        };
+        assert_eq!(l1_data_pricing(&free), None);
+    }
+
+    fn is_batch_cheaper(model: &DataPricing, count: usize) -> bool {
+        let deliveries = deliveries(count);
+        batch_cost(model, &deliveries) < serial_cost(model, &deliveries)
+    }
+
+    #[test]
+    fn test_single_delivery_is_not_batched() {
+        let models = [
+            DataPricing::Calldata,
+            DataPricing::L1Data {
+                gas_per_compressed_byte: 160.,
+            },
+            DataPricing::L1Data {
+                gas_per_compressed_byte: 10.,
+            },
+        ];
+        for model in models {
+            assert!(!is_batch_cheaper(&model, 1));
+            assert!(is_batch_cheaper(&model, 2));
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Delivery decorators adjust the delivery of messages to recipients that
+//! need more than the ISM metadata, e.g. extra calldata appended to the
+//! metadata or a gas buffer for calls the recipient makes.
+
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use ethers::core::utils::hex::{decode as hex_decode, encode as hex_encode};
+use eyre::{Context, Result};
+use hyperlane_base::utils::reqwest_client;
+use hyperlane_core::{config::ProxyConf, HyperlaneMessage, RawHyperlaneMessage, U256};
+use reqwest::{Client, Url};
+use serde::Deserialize;
+use serde_json::json;
+use tracing::{debug, instrument};
+
+use crate::settings::{matching_list::MatchingList, DeliveryDecoratorConf, DeliveryDecoratorType};
+
+/// Parameters of the transaction delivering a message
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeliveryParams {
+    /// The metadata passed to the mailbox
+    pub metadata: Vec<u8>,
+    /// Gas added to the gas limit of the delivery
+    pub extra_gas: U256,
+}
+
+/// A hook adjusting the delivery of messages to some recipients
+#[async_trait]
+pub trait DeliveryDecorator: Debug + Send + Sync {
+    /// Adjust the delivery of `message`. Errors delay the delivery until the
+    /// message is prepared again.
+    async fn decorate(&self, message: &HyperlaneMessage, params: &mut DeliveryParams)
+        -> Result<()>;
+}
+
+/// Appends fixed bytes to the metadata
+#[derive(Debug)]
+struct AppendMetadata(Vec<u8>);
+
+#[async_trait]
+impl DeliveryDecorator for AppendMetadata {
+    async fn decorate(
+        &self,
+        _message: &HyperlaneMessage,
+        params: &mut DeliveryParams,
+    ) -> Result<()> {
+        params.metadata.extend_from_slice(&self.0);
+        Ok(())
+    }
+}
+
+/// Adds fixed gas to the gas limit
+#[derive(Debug)]
+struct ExtraGas(U256);
+
+#[async_trait]
+impl DeliveryDecorator for ExtraGas {
+    async fn decorate(
+        &self,
+        _message: &HyperlaneMessage,
+        params: &mut DeliveryParams,
+    ) -> Result<()> {
+        params.extra_gas = params.extra_gas.saturating_add(self.0);
+        Ok(())
+    }
+}
+
+/// Asks an application server for the adjustments of each delivery. The
+/// server receives the message and metadata, and can answer with bytes to
+/// append to the metadata and extra gas.
+#[derive(Debug)]
+struct PreflightCall {
+    url: Url,
+    client: Client,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PreflightResponse {
+    metadata: Option<String>,
+    extra_gas: Option<u64>,
+}
+
+#[async_trait]
+impl DeliveryDecorator for PreflightCall {
+    #[instrument(err, skip_all, fields(url = %self.url))]
+    async fn decorate(
+        &self,
+        message: &HyperlaneMessage,
+        params: &mut DeliveryParams,
+    ) -> Result<()> {
+        let body = json!({
+            "message": format!("0x{}", hex_encode(RawHyperlaneMessage::from(message))),
+            "metadata": format!("0x{}", hex_encode(&params.metadata)),
+        });
+        let response: PreflightResponse = self
+            .client
+            .post(self.url.clone())
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Invalid preflight response")?;
+        if let Some(metadata) = response.metadata {
+            let metadata = hex_decode(metadata.trim_start_matches("0x"))
+                .context("Invalid preflight metadata")?;
+            params.metadata.extend_from_slice(&metadata);
+        }
+        if let Some(extra_gas) = response.extra_gas {
+            params.extra_gas = params.extra_gas.saturating_add(extra_gas.into());
+        }
+        Ok(())
+    }
+}
+
+/// The delivery decorators of a destination chain, each applying to the
+/// messages matching its list
+#[derive(Debug, Default)]
+pub struct DeliveryDecorators {
+    decorators: Vec<(MatchingList, Box<dyn DeliveryDecorator>)>,
+}
+
+impl DeliveryDecorators {
+    /// Build the decorators configured for a destination chain, whose
+    /// preflight calls are routed through `proxy`
+    pub fn new(confs: &[DeliveryDecoratorConf], proxy: Option<&ProxyConf>) -> Self {
+        let decorators = confs
+            .iter()
+            .map(|conf| {
+                let decorator: Box<dyn DeliveryDecorator> = match &conf.decorator {
+                    DeliveryDecoratorType::AppendMetadata { metadata } => {
+                        Box::new(AppendMetadata(metadata.clone()))
+                    }
+                    DeliveryDecoratorType::ExtraGas { gas } => Box::new(ExtraGas(*gas)),
+                    DeliveryDecoratorType::Preflight { url } => Box::new(PreflightCall {
+                        url: url.clone(),
+                        client: reqwest_client(proxy),
+                    }),
+                };
+                (conf.matching_list.clone(), decorator)
+            })
+            .collect();
+        Self { decorators }
+    }
+
+    /// Apply the decorators matching `message`, in the configured order
+    pub async fn decorate(
+        &self,
+        message: &HyperlaneMessage,
+        params: &mut DeliveryParams,
+    ) -> Result<()> {
+        for (matching_list, decorator) in &self.decorators {
+            if matching_list.msg_matches(message, true) {
+                debug!(?decorator, "Decorating message delivery");
+                decorator.decorate(message, params).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::H256;
+
+    use super::*;
+
+    fn matching_recipient(recipient: H256) -> MatchingList {
+        serde_json::from_value(json!([{ "recipientaddress": format!("{recipient:?}") }])).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_decorators_apply_to_matching_messages() {
+        let recipient = H256::repeat_byte(0xaa);
+        let decorators = DeliveryDecorators::new(
+            &[
+                DeliveryDecoratorConf {
+                    matching_list: matching_recipient(recipient),
+                    decorator: DeliveryDecoratorType::AppendMetadata {
+                        metadata: vec![0xde, 0xad],
+                    },
+                },
+                DeliveryDecoratorConf {
+                    matching_list: MatchingList::default(),
+                    decorator: DeliveryDecoratorType::ExtraGas { gas: 50_000.into() },
+                },
+            ],
+            None,
+        );
+
+        let message = HyperlaneMessage {
+            recipient,
+            ..Default::default()
+        };
+        let mut params = DeliveryParams {
+            metadata: vec![0x01],
+            ..Default::default()
+        };
+        decorators.decorate(&message, &mut params).await.unwrap();
+        assert_eq!(params.metadata, vec![0x01, 0xde, 0xad]);
+        assert_eq!(params.extra_gas, 50_000.into());
+
+        let other = HyperlaneMessage::default();
+        let mut params = DeliveryParams::default();
+        decorators.decorate(&other, &mut params).await.unwrap();
+        assert!(params.metadata.is_empty());
+        assert_eq!(params.extra_gas, 50_000.into());
+    }
+}
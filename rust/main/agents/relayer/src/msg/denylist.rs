@@ -0,0 +1,152 @@
+//! Denylists of addresses published by screening services change more often
+//! than the relayer config, so they are fetched periodically and matched
+//! alongside the configured address blacklist.
+
+use std::{sync::Arc, time::Duration};
+
+use ethers::utils::hex;
+use eyre::{eyre, Result};
+use hyperlane_base::{spawn_named, utils::reqwest_client_builder};
+use hyperlane_core::config::ProxyConf;
+use prometheus::IntGauge;
+use reqwest::Client;
+use serde_json::Value;
+use tokio::{task::JoinHandle, time::sleep};
+use tracing::{info, info_span, instrument::Instrumented, warn, Instrument};
+
+use crate::{msg::blacklist::AddressBlacklist, settings::AddressDenylistConf};
+
+/// How long fetching the denylist may take
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Fetches the address denylist and blocks its addresses in the blacklist
+#[derive(Debug)]
+pub struct DenylistFetcher {
+    conf: AddressDenylistConf,
+    client: Client,
+    blacklist: Arc<AddressBlacklist>,
+    /// Number of addresses in the last fetched denylist
+    denylist_size: IntGauge,
+}
+
+impl DenylistFetcher {
+    pub fn new(
+        conf: AddressDenylistConf,
+        blacklist: Arc<AddressBlacklist>,
+        denylist_size: IntGauge,
+        proxy: Option<&ProxyConf>,
+    ) -> Result<Self> {
+        Ok(Self {
+            conf,
+            client: reqwest_client_builder(proxy)
+                .timeout(FETCH_TIMEOUT)
+                .build()?,
+            blacklist,
+            denylist_size,
+        })
+    }
+
+    /// Fetch the denylist and replace the addresses it blocks
+    pub async fn refresh(&self) -> Result<()> {
+        let mut request = self.client.get(self.conf.url.clone());
+        if let Some(api_key) = &self.conf.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let body = request.send().await?.error_for_status()?.text().await?;
+        let addresses = parse_denylist(&body)?;
+        info!(addresses = addresses.len(), "Fetched address denylist");
+        self.denylist_size.set(addresses.len() as i64);
+        self.blacklist.set_denylist(addresses);
+        Ok(())
+    }
+
+    async fn run(self) {
+        loop {
+            sleep(self.conf.refresh_interval).await;
+            if let Err(err) = self.refresh().await {
+                // Keep blocking the addresses of the last fetched denylist
+                warn!(?err, "Failed to fetch address denylist");
+            }
+        }
+    }
+
+    /// Spawns a tokio task fetching the denylist again every refresh
+    /// interval
+    pub fn spawn(self) -> Instrumented<JoinHandle<()>> {
+        let span = info_span!("DenylistFetcher");
+        spawn_named("address_denylist", None, self.run()).instrument(span)
+    }
+}
+
+/// Parse the hex addresses of a denylist, either a JSON array of addresses or
+/// of objects with an `address`, optionally under an `addresses` key, or
+/// plain text with an address per line.
+fn parse_denylist(body: &str) -> Result<Vec<Vec<u8>>> {
+    let addresses: Vec<String> = match serde_json::from_str::<Value>(body) {
+        Ok(json) => {
+            let entries = match json {
+                Value::Object(mut obj) => obj.remove("addresses").unwrap_or_default(),
+                json => json,
+            };
+            let Value::Array(entries) = entries else {
+                return Err(eyre!("Expected an array of denylisted addresses"));
+            };
+            entries
+                .into_iter()
+                .map(|entry| match entry {
+                    Value::String(address) => Ok(address),
+                    Value::Object(mut obj) => match obj.remove("address") {
+                        Some(Value::String(address)) => Ok(address),
+                        _ => Err(eyre!("Denylist entry without an address")),
+                    },
+                    entry => Err(eyre!("Unexpected denylist entry {entry}")),
+                })
+                .collect::<Result<_>>()?
+        }
+        Err(_) => body
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_owned)
+            .collect(),
+    };
+    addresses
+        .iter()
+        .map(|address| {
+            let address = address.trim();
+            let hex_address = address.strip_prefix("0x").unwrap_or(address);
+            // An empty address would be found in every message
+            if hex_address.is_empty() {
+                return Err(eyre!("Empty denylisted address {address:?}"));
+            }
+            hex::decode(hex_address)
+                .map_err(|err| eyre!("Invalid denylisted address {address}: {err}"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_denylist() {
+        let expected = vec![vec![0xab; 20], vec![0xcd; 32]];
+        let first = format!("0x{}", hex::encode(&expected[0]));
+        let second = hex::encode(&expected[1]);
+
+        let json = format!(r#"["{first}", "{second}"]"#);
+        assert_eq!(parse_denylist(&json).unwrap(), expected);
+
+        let json = format!(r#"{{"addresses": [{{"address": "{first}"}}, "{second}"]}}"#);
+        assert_eq!(parse_denylist(&json).unwrap(), expected);
+
+        let text = format!("# sanctioned addresses\n{first}\n\n  {second}\n");
+        assert_eq!(parse_denylist(&text).unwrap(), expected);
+
+        assert!(parse_denylist(r#"["0xnot an address"]"#).is_err());
+        assert!(parse_denylist(r#"{"addresses": "0xab"}"#).is_err());
+        assert!(parse_denylist(r#"["0xab", "0x"]"#).is_err());
+        assert!(parse_denylist(r#"[""]"#).is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use hyperlane_core::ReprepareReason;
+use strum::{EnumIter, IntoStaticStr};
+
+/// Class of a failed attempt to deliver a message, used to label the
+/// delivery failure metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum DeliveryFailureReason {
+    /// The gas payment enforcement policy rejected the message
+    GasEnforcement,
+    /// The ISM metadata is not available yet or could not be built
+    Metadata,
+    /// The ISM rejected the metadata
+    IsmVerification,
+    /// Simulating the delivery reverted
+    RevertedSimulation,
+    /// The delivery transaction reverted or was reorged out
+    Reverted,
+    /// The delivery transaction was priced too low
+    Underpriced,
+    /// The nonce of the delivery transaction was invalid
+    Nonce,
+    /// The delivery would exceed a spend limit of the relayer
+    SpendLimit,
+    /// A call to the chain failed
+    Rpc,
+    /// Any other failure
+    Other,
+}
+
+impl DeliveryFailureReason {
+    /// Classify the failure behind `reason`, using the error it came with to
+    /// tell apart failures of the same step. Returns None for reasons that
+    /// aren't failures of the delivery, like leaving it to another relayer.
+    pub fn classify(reason: &ReprepareReason, err: Option<&str>) -> Option<Self> {
+        let err = err.map(str::to_lowercase).unwrap_or_default();
+        let failure = match reason {
+            ReprepareReason::GasPaymentNotFound
+            | ReprepareReason::GasPaymentRequirementNotMet
+            | ReprepareReason::ExceedsMaxGasLimit => Self::GasEnforcement,
+            ReprepareReason::ErrorBuildingMetadata | ReprepareReason::CouldNotFetchMetadata => {
+                Self::Metadata
+            }
+            ReprepareReason::IsmRejectedMetadata => Self::IsmVerification,
+            ReprepareReason::ErrorEstimatingGas if is_ism_verification_error(&err) => {
+                Self::IsmVerification
+            }
+            ReprepareReason::ErrorEstimatingGas if is_revert_error(&err) => {
+                Self::RevertedSimulation
+            }
+            ReprepareReason::RevertedOrReorged => Self::Reverted,
+            ReprepareReason::ExceedsMaxDeliveryFee => Self::SpendLimit,
+            ReprepareReason::ErrorSubmitting if is_underpriced_error(&err) => Self::Underpriced,
+            ReprepareReason::ErrorSubmitting if err.contains("nonce") => Self::Nonce,
+            ReprepareReason::ErrorSubmitting
+            | ReprepareReason::ErrorCheckingDeliveryStatus
+            | ReprepareReason::ErrorCheckingIfRecipientIsContract
+            | ReprepareReason::ErrorFetchingIsmAddress
+            | ReprepareReason::ErrorGettingMetadataBuilder
+            | ReprepareReason::ErrorVerifyingMetadata
+            | ReprepareReason::ErrorCheckingGasRequirement
+            | ReprepareReason::ErrorEstimatingGas => Self::Rpc,
+            ReprepareReason::ErrorDecoratingDelivery => Self::Other,
+            ReprepareReason::LeasedByOtherRelayer | ReprepareReason::ErrorTakingSubmissionLease => {
+                return None
+            }
+        };
+        Some(failure)
+    }
+
+    /// The metric label of the reason
+    pub fn as_str(&self) -> &'static str {
+        self.into()
+    }
+}
+
+fn is_ism_verification_error(err: &str) -> bool {
+    err.contains("ism verification failed") || err.contains("!verify")
+}
+
+/// Whether estimating gas failed because the delivery reverted, rather than
+/// because the call to the chain failed
+fn is_revert_error(err: &str) -> bool {
+    err.contains("revert")
+}
+
+fn is_underpriced_error(err: &str) -> bool {
+    ["underpriced", "fee too low", "less than block base fee"]
+        .iter()
+        .any(|pattern| err.contains(pattern))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_classify_uses_error_of_step() {
+        use DeliveryFailureReason as R;
+
+        let cases = [
+            (
+                ReprepareReason::GasPaymentNotFound,
+                None,
+                Some(R::GasEnforcement),
+            ),
+            (
+                ReprepareReason::ErrorEstimatingGas,
+                Some("execution reverted: Mailbox: ISM verification failed"),
+                Some(R::IsmVerification),
+            ),
+            (
+                ReprepareReason::ErrorEstimatingGas,
+                Some("execution reverted: !recipient"),
+                Some(R::RevertedSimulation),
+            ),
+            (
+                ReprepareReason::ErrorSubmitting,
+                Some("replacement transaction underpriced"),
+                Some(R::Underpriced),
+            ),
+            (
+                ReprepareReason::ErrorSubmitting,
+                Some("nonce too low"),
+                Some(R::Nonce),
+            ),
+            (
+                ReprepareReason::ErrorSubmitting,
+                Some("connection refused"),
+                Some(R::Rpc),
+            ),
+            (
+                ReprepareReason::IsmRejectedMetadata,
+                None,
+                Some(R::IsmVerification),
+            ),
+            (ReprepareReason::RevertedOrReorged, None, Some(R::Reverted)),
+            (
+                ReprepareReason::ErrorEstimatingGas,
+                Some("error sending request for url"),
+                Some(R::Rpc),
+            ),
+            (ReprepareReason::LeasedByOtherRelayer, None, None),
+            (ReprepareReason::ErrorTakingSubmissionLease, None, None),
+        ];
+        for (reason, err, expected) in cases {
+            assert_eq!(DeliveryFailureReason::classify(&reason, err), expected);
+        }
+    }
+
+    #[test]
+    fn test_labels() {
+        assert_eq!(
+            DeliveryFailureReason::GasEnforcement.as_str(),
+            "gas_enforcement"
+        );
+        assert_eq!(
+            DeliveryFailureReason::RevertedSimulation.as_str(),
+            "reverted_simulation"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use hyperlane_core::{HyperlaneMessage, HyperlaneMessageRef, H256};
+
+use super::{blacklist::AddressBlacklist, shard::MessageShard};
+use crate::settings::matching_list::MatchingList;
+
+/// The fields of a message the filters inspect, so that messages can be
+/// filtered while borrowed from their encoding, without copying their body
+pub trait FilterableMessage {
+    fn id(&self) -> H256;
+    fn origin(&self) -> u32;
+    fn sender(&self) -> H256;
+    fn destination(&self) -> u32;
+    fn recipient(&self) -> H256;
+    fn body(&self) -> &[u8];
+}
+
+impl FilterableMessage for HyperlaneMessage {
+    fn id(&self) -> H256 {
+        self.id()
+    }
+
+    fn origin(&self) -> u32 {
+        self.origin
+    }
+
+    fn sender(&self) -> H256 {
+        self.sender
+    }
+
+    fn destination(&self) -> u32 {
+        self.destination
+    }
+
+    fn recipient(&self) -> H256 {
+        self.recipient
+    }
+
+    fn body(&self) -> &[u8] {
+        &self.body
+    }
+}
+
+impl FilterableMessage for HyperlaneMessageRef<'_> {
+    fn id(&self) -> H256 {
+        self.id()
+    }
+
+    fn origin(&self) -> u32 {
+        self.origin()
+    }
+
+    fn sender(&self) -> H256 {
+        self.sender()
+    }
+
+    fn destination(&self) -> u32 {
+        self.destination()
+    }
+
+    fn recipient(&self) -> H256 {
+        self.recipient()
+    }
+
+    fn body(&self) -> &[u8] {
+        self.body()
+    }
+}
+
+/// Why a message is left to other relayers, or not relayed at all
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    NotWhitelisted,
+    Blacklisted,
+    /// The message involves an address of the configured blacklist
+    BlacklistedAddress(Vec<u8>),
+    /// The message involves an address denylisted by the screening service
+    DenylistedAddress(Vec<u8>),
+    /// Another relayer processes the message's shard
+    OtherShard,
+}
+
+/// The configured filters deciding which messages the relayer relays, shared
+/// by the processor and by everything reporting on the messages it relays.
+#[derive(Debug, Clone, Default)]
+pub struct MessageFilter {
+    /// A matching list of messages that should be whitelisted.
+    pub message_whitelist: Arc<MatchingList>,
+    /// A matching list of messages that should be blacklisted.
+    pub message_blacklist: Arc<MatchingList>,
+    /// Addresses that messages may not interact with.
+    pub address_blacklist: Arc<AddressBlacklist>,
+    /// The shard of messages this relayer processes
+    pub message_shard: MessageShard,
+}
+
+impl MessageFilter {
+    /// Why the message isn't relayed, if it's filtered out
+    pub fn skip_reason(&self, message: &impl FilterableMessage) -> Option<SkipReason> {
+        if !self.message_whitelist.msg_matches(message, true) {
+            return Some(SkipReason::NotWhitelisted);
+        }
+        if self.message_blacklist.msg_matches(message, false) {
+            return Some(SkipReason::Blacklisted);
+        }
+        if let Some(address) = self.address_blacklist.find_blacklisted_address(message) {
+            return Some(SkipReason::BlacklistedAddress(address));
+        }
+        if let Some(address) = self.address_blacklist.find_denylisted_address(message) {
+            return Some(SkipReason::DenylistedAddress(address));
+        }
+        if !self.message_shard.contains(&message.id()) {
+            return Some(SkipReason::OtherShard);
+        }
+        None
+    }
+}
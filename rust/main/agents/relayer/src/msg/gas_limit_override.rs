@@ -0,0 +1,77 @@
+//! Gas limit overrides raise the estimated gas limit of deliveries to
+//! recipients which need more gas than `eth_estimateGas` predicts, e.g.
+//! because their gas use depends on state changing before the delivery lands.
+
+use std::collections::HashMap;
+
+use hyperlane_core::{H256, U256};
+
+use crate::settings::GasLimitOverrideConf;
+
+/// Precision of the multipliers applied to gas estimates
+const MULTIPLIER_PRECISION: u64 = 10_000;
+
+/// The gas limit overrides of a destination chain, keyed by recipient
+#[derive(Debug, Default)]
+pub struct GasLimitOverrides {
+    overrides: HashMap<H256, GasLimitOverrideConf>,
+}
+
+impl GasLimitOverrides {
+    /// Build the overrides configured for a destination chain
+    pub fn new(overrides: HashMap<H256, GasLimitOverrideConf>) -> Self {
+        Self { overrides }
+    }
+
+    /// The gas limit of a delivery to `recipient` estimated at `estimate`,
+    /// or `None` if no override raises it
+    pub fn apply(&self, recipient: &H256, estimate: U256) -> Option<U256> {
+        let conf = self.overrides.get(recipient)?;
+        let mut gas_limit = estimate;
+        if let Some(multiplier) = conf.multiplier {
+            let scaled = (multiplier * MULTIPLIER_PRECISION as f64) as u64;
+            gas_limit = estimate.saturating_mul(scaled.into()) / MULTIPLIER_PRECISION;
+        }
+        if let Some(minimum) = conf.minimum {
+            gas_limit = gas_limit.max(minimum);
+        }
+        (gas_limit > estimate).then_some(gas_limit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn overrides(multiplier: Option<f64>, minimum: Option<u64>) -> (H256, GasLimitOverrides) {
+        let recipient = H256::repeat_byte(0xaa);
+        let conf = GasLimitOverrideConf {
+            multiplier,
+            minimum: minimum.map(Into::into),
+        };
+        (
+            recipient,
+            GasLimitOverrides::new(HashMap::from([(recipient, conf)])),
+        )
+    }
+
+    #[test]
+    fn test_multiplier_and_minimum_raise_estimate() {
+        let (recipient, overrides) = overrides(Some(1.5), Some(200_000));
+        assert_eq!(
+            overrides.apply(&recipient, 100_000.into()),
+            Some(200_000.into())
+        );
+        assert_eq!(
+            overrides.apply(&recipient, 200_000.into()),
+            Some(300_000.into())
+        );
+        assert_eq!(overrides.apply(&H256::zero(), 100_000.into()), None);
+    }
+
+    #[test]
+    fn test_overrides_never_lower_estimate() {
+        let (recipient, overrides) = overrides(Some(0.5), Some(50_000));
+        assert_eq!(overrides.apply(&recipient, 100_000.into()), None);
+    }
+}
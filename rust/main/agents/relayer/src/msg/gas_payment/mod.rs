@@ -1,16 +1,17 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
 use eyre::Result;
-use hyperlane_base::db::HyperlaneRocksDB;
+use hyperlane_base::db::{GasReconciliationData, HyperlaneRocksDB};
 use hyperlane_core::{
     FixedPointNumber, GasPaymentKey, HyperlaneMessage, InterchainGasExpenditure,
     InterchainGasPayment, TxCostEstimate, TxOutcome, U256,
 };
 use tracing::{debug, error, trace};
 
-use self::policies::{GasPaymentPolicyMinimum, GasPaymentPolicyNone};
+use self::policies::{GasPaymentPolicyIgpQuote, GasPaymentPolicyMinimum, GasPaymentPolicyNone};
 use crate::{
+    gas_reconciliation::GasLedger,
     msg::gas_payment::policies::GasPaymentPolicyOnChainFeeQuoting,
     settings::{
         matching_list::MatchingList, GasPaymentEnforcementConf, GasPaymentEnforcementPolicy,
@@ -50,6 +51,8 @@ pub struct GasPaymentEnforcer {
     /// whitelists, then whichever is first in the list will be used.
     policies: Vec<(Box<dyn GasPaymentPolicy>, MatchingList)>,
     db: HyperlaneRocksDB,
+    /// Gas paid for and spent on delivered messages, to be reconciled
+    gas_ledger: Arc<GasLedger>,
 }
 
 impl GasPaymentEnforcer {
@@ -71,16 +74,51 @@ impl GasPaymentEnforcer {
                         gas_fraction_numerator: n,
                         gas_fraction_denominator: d,
                     } => Box::new(GasPaymentPolicyOnChainFeeQuoting::new(n, d)),
+                    GasPaymentEnforcementPolicy::IgpQuote {
+                        gas_fraction_numerator: n,
+                        gas_fraction_denominator: d,
+                    } => Box::new(GasPaymentPolicyIgpQuote::new(db.clone(), n, d)),
                 };
                 (p, cfg.matching_list)
             })
             .collect();
 
-        Self { policies, db }
+        Self {
+            policies,
+            db,
+            gas_ledger: Default::default(),
+        }
+    }
+
+    /// The gas paid for and spent on the messages delivered so far
+    pub fn gas_ledger(&self) -> Arc<GasLedger> {
+        self.gas_ledger.clone()
     }
 }
 
 impl GasPaymentEnforcer {
+    fn gas_payment_key(message: &HyperlaneMessage) -> GasPaymentKey {
+        GasPaymentKey {
+            message_id: message.id(),
+            destination: message.destination,
+        }
+    }
+
+    /// The total gas payment made for the delivery of the message so far, if
+    /// any payment was made
+    fn stored_payment(&self, message: &HyperlaneMessage) -> Result<Option<InterchainGasPayment>> {
+        Ok(self
+            .db
+            .retrieve_gas_payment_by_gas_payment_key(Self::gas_payment_key(message))?)
+    }
+
+    /// The total gas payment made for the delivery of the message so far
+    pub fn current_payment(&self, message: &HyperlaneMessage) -> Result<InterchainGasPayment> {
+        Ok(self.stored_payment(message)?.unwrap_or_else(|| {
+            InterchainGasPayment::from_gas_payment_key(Self::gas_payment_key(message))
+        }))
+    }
+
     /// Returns Some(gas_limit) if the enforcer has approved the transaction or
     /// None if the transaction is not approved.
     pub async fn message_meets_gas_payment_requirement(
@@ -89,17 +127,10 @@ impl GasPaymentEnforcer {
         tx_cost_estimate: &TxCostEstimate,
     ) -> Result<GasPolicyStatus> {
         let msg_id = message.id();
-        let gas_payment_key = GasPaymentKey {
-            message_id: msg_id,
-            destination: message.destination,
-        };
-        let current_payment_option = self
-            .db
-            .retrieve_gas_payment_by_gas_payment_key(gas_payment_key)?;
-        let current_payment = match current_payment_option {
-            Some(payment) => payment,
-            None => InterchainGasPayment::from_gas_payment_key(gas_payment_key),
-        };
+        let current_payment_option = self.stored_payment(message)?;
+        let current_payment = current_payment_option.unwrap_or_else(|| {
+            InterchainGasPayment::from_gas_payment_key(Self::gas_payment_key(message))
+        });
         let current_expenditure = self.db.retrieve_gas_expenditure_by_message_id(msg_id)?;
 
         for (policy, whitelist) in &self.policies {
@@ -164,16 +195,45 @@ impl GasPaymentEnforcer {
             "{}",
             GAS_EXPENDITURE_LOG_MESSAGE,
         );
-        self.db.process_gas_expenditure(InterchainGasExpenditure {
+        let expenditure = InterchainGasExpenditure {
             message_id: message.id(),
             gas_used: outcome.gas_used,
             tokens_used: (FixedPointNumber::try_from(outcome.gas_used)? * outcome.gas_price)
                 .try_into()?,
-        })?;
+        };
+        // The payment of a message is only counted on its first delivery attempt
+        let previous = self
+            .db
+            .retrieve_gas_expenditure_by_message_id(message.id())?;
+        let mut spend = GasReconciliationData {
+            gas_used: expenditure.gas_used,
+            tokens_used: expenditure.tokens_used,
+            ..Default::default()
+        };
+        if previous.gas_used.is_zero() {
+            let payment = self.current_payment(message)?;
+            spend.messages = 1;
+            spend.payment = payment.payment;
+            spend.gas_amount = payment.gas_amount;
+        }
+        self.db.process_gas_expenditure(expenditure)?;
+        self.gas_ledger.record(message.destination, spend);
         Ok(())
     }
 }
 
+/// The priority of delivering a message by the gas payment made for it per
+/// unit of gas its delivery is estimated to use. The payment is in the native
+/// token of the origin, so it is multiplied by a `weight` per origin to make
+/// the payments of different origins comparable.
+pub fn payment_priority(payment: U256, gas_limit: U256, weight: f64) -> u64 {
+    if gas_limit.is_zero() {
+        return 0;
+    }
+    // Casting saturates, so payments too large for a u64 get the highest priority
+    (payment.to_f64_lossy() / gas_limit.to_f64_lossy() * weight) as u64
+}
+
 #[cfg(test)]
 mod test {
     use std::str::FromStr;
@@ -184,7 +244,7 @@ mod test {
         H256, U256,
     };
 
-    use super::GasPaymentEnforcer;
+    use super::{payment_priority, GasPaymentEnforcer};
     use crate::{
         msg::gas_payment::GasPolicyStatus,
         settings::{
@@ -445,4 +505,17 @@ mod test {
         })
         .await;
     }
+
+    #[test]
+    fn test_payment_priority() {
+        let gas_limit = U256::from(100_000);
+        // 1 gwei per gas
+        let payment = U256::exp10(14);
+        assert_eq!(payment_priority(payment, gas_limit, 1.), 1_000_000_000);
+        // Weights make payments of different origins comparable
+        assert_eq!(payment_priority(payment, gas_limit, 0.5), 500_000_000);
+        assert_eq!(payment_priority(payment * 2, gas_limit, 1.), 2_000_000_000);
+        assert_eq!(payment_priority(U256::MAX, gas_limit, 1.), u64::MAX);
+        assert_eq!(payment_priority(payment, U256::zero(), 1.), 0);
+    }
 }
@@ -0,0 +1,158 @@
+use async_trait::async_trait;
+use eyre::{eyre, Result};
+use hyperlane_base::db::HyperlaneRocksDB;
+use hyperlane_core::{
+    HyperlaneMessage, InterchainGasExpenditure, InterchainGasPayment, TxCostEstimate, U256,
+};
+use tracing::warn;
+
+use crate::msg::gas_payment::GasPaymentPolicy;
+
+/// Requires the payment of a message to cover what the IGP quotes for a
+/// fraction of its estimated gas. The quote is computed from the gas oracle
+/// config of the destination as indexed from the events of the IGP and its
+/// gas oracles, rather than queried from the origin for every message.
+#[derive(Debug)]
+pub struct GasPaymentPolicyIgpQuote {
+    /// The origin database the gas oracle configs are indexed to
+    db: HyperlaneRocksDB,
+    /// Numerator value to modify the estimated gas by. The estimated gas value
+    /// is multiplied by this value.
+    fractional_numerator: u64,
+    /// Denominator value to modify the estimated gas by. The estimated gas
+    /// value is divided by this value.
+    fractional_denominator: u64,
+}
+
+impl GasPaymentPolicyIgpQuote {
+    pub fn new(
+        db: HyperlaneRocksDB,
+        fractional_numerator: u64,
+        fractional_denominator: u64,
+    ) -> Self {
+        Self {
+            db,
+            fractional_numerator,
+            fractional_denominator,
+        }
+    }
+}
+
+#[async_trait]
+impl GasPaymentPolicy for GasPaymentPolicyIgpQuote {
+    async fn message_meets_gas_payment_requirement(
+        &self,
+        message: &HyperlaneMessage,
+        current_payment: &InterchainGasPayment,
+        current_expenditure: &InterchainGasExpenditure,
+        tx_cost_estimate: &TxCostEstimate,
+    ) -> Result<Option<U256>> {
+        let Some(config) = self.db.retrieve_gas_oracle_config(message.destination)? else {
+            warn!(
+                destination = message.destination,
+                "No gas oracle config indexed for the destination, the IGP quote can't be enforced"
+            );
+            return Ok(None);
+        };
+        let fractional_gas_estimate = tx_cost_estimate
+            .enforceable_gas_limit()
+            .checked_mul(self.fractional_numerator.into())
+            .and_then(|gas| gas.checked_div(self.fractional_denominator.into()))
+            .ok_or_else(|| eyre!("Overflow or zero denominator computing the gas to enforce"))?;
+        // Gas already spent on the message used up the payment quoted for it
+        let payment = current_payment
+            .payment
+            .saturating_sub(config.quote_gas_payment(current_expenditure.gas_used));
+        if payment >= config.quote_gas_payment(fractional_gas_estimate) {
+            Ok(Some(tx_cost_estimate.gas_limit))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::{
+        GasOracleConfigUpdate, HyperlaneDomain, HyperlaneLogStore, Indexed, LogMeta, H256,
+        TOKEN_EXCHANGE_RATE_SCALE,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_payment_covers_igp_quote() {
+        test_utils::run_test_db(|db| async move {
+            let origin = HyperlaneDomain::new_test_domain("test_payment_covers_igp_quote");
+            let db = HyperlaneRocksDB::new(&origin, db);
+            let policy = GasPaymentPolicyIgpQuote::new(db.clone(), 1, 2);
+            let message = HyperlaneMessage {
+                destination: 12,
+                ..Default::default()
+            };
+            let payment = |payment: u64| InterchainGasPayment {
+                message_id: message.id(),
+                destination: message.destination,
+                payment: payment.into(),
+                gas_amount: U256::zero(),
+            };
+            let expenditure = |gas_used: u64| InterchainGasExpenditure {
+                message_id: message.id(),
+                gas_used: gas_used.into(),
+                tokens_used: U256::zero(),
+            };
+            let estimate = TxCostEstimate {
+                gas_limit: 2000.into(),
+                ..Default::default()
+            };
+            let meets = |payment, expenditure| {
+                let policy = &policy;
+                let message = &message;
+                let estimate = &estimate;
+                async move {
+                    policy
+                        .message_meets_gas_payment_requirement(
+                            message,
+                            &payment,
+                            &expenditure,
+                            estimate,
+                        )
+                        .await
+                        .unwrap()
+                }
+            };
+
+            // Nothing is delivered until the gas oracle config is indexed
+            assert_eq!(meets(payment(u64::MAX), expenditure(0)).await, None);
+
+            let gas_oracle = H256::from_low_u64_be(7);
+            let updates = [
+                GasOracleConfigUpdate::DestinationGasConfig {
+                    remote_domain: 12,
+                    gas_oracle,
+                    gas_overhead: U256::zero(),
+                },
+                GasOracleConfigUpdate::RemoteGasData {
+                    gas_oracle,
+                    remote_domain: 12,
+                    // Remote tokens are worth as much as local ones
+                    token_exchange_rate: TOKEN_EXCHANGE_RATE_SCALE.into(),
+                    gas_price: 10.into(),
+                },
+            ]
+            .map(|update| (Indexed::new(update), LogMeta::random()));
+            db.store_logs(&updates).await.unwrap();
+
+            // Half the estimated gas at a gas price of 10
+            assert_eq!(meets(payment(9_999), expenditure(0)).await, None);
+            assert_eq!(
+                meets(payment(10_000), expenditure(0)).await,
+                Some(estimate.gas_limit)
+            );
+            // Gas already spent uses up its share of the payment
+            assert_eq!(meets(payment(10_000), expenditure(1)).await, None);
+        })
+        .await;
+    }
+}
@@ -61,6 +61,7 @@ async fn test_gas_payment_policy_minimum() {
                     gas_limit: U256::from(100000u32),
                     gas_price: U256::from(100000u32).try_into().unwrap(),
                     l2_gas_limit: None,
+                    l1_data_fee: None,
                 },
             )
             .await
@@ -85,6 +86,7 @@ async fn test_gas_payment_policy_minimum() {
                     gas_limit: U256::from(100000u32),
                     gas_price: U256::from(100001u32).try_into().unwrap(),
                     l2_gas_limit: None,
+                    l1_data_fee: None,
                 },
             )
             .await
@@ -103,6 +105,7 @@ async fn test_gas_payment_policy_minimum() {
                     gas_limit: U256::from(100000u32),
                     gas_price: U256::from(100001u32).try_into().unwrap(),
                     l2_gas_limit: Some(U256::from(22222u32)),
+                    l1_data_fee: None,
                 },
             )
             .await
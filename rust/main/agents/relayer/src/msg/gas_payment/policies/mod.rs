@@ -1,7 +1,9 @@
+mod igp_quote;
 mod minimum;
 mod none;
 mod on_chain_fee_quoting;
 
+pub(crate) use igp_quote::GasPaymentPolicyIgpQuote;
 pub(crate) use minimum::GasPaymentPolicyMinimum;
 pub(crate) use none::GasPaymentPolicyNone;
 pub(crate) use on_chain_fee_quoting::GasPaymentPolicyOnChainFeeQuoting;
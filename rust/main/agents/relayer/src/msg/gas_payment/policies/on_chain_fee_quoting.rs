@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use eyre::Result;
+use eyre::{eyre, Result};
 
 use hyperlane_core::{
     HyperlaneMessage, InterchainGasExpenditure, InterchainGasPayment, TxCostEstimate, U256,
@@ -45,9 +45,11 @@ impl GasPaymentPolicy for GasPaymentPolicyOnChainFeeQuoting {
         current_expenditure: &InterchainGasExpenditure,
         tx_cost_estimate: &TxCostEstimate,
     ) -> Result<Option<U256>> {
-        let fractional_gas_estimate = (tx_cost_estimate.enforceable_gas_limit()
-            * self.fractional_numerator)
-            / self.fractional_denominator;
+        let fractional_gas_estimate = tx_cost_estimate
+            .enforceable_gas_limit()
+            .checked_mul(self.fractional_numerator.into())
+            .and_then(|gas| gas.checked_div(self.fractional_denominator.into()))
+            .ok_or_else(|| eyre!("Overflow or zero denominator computing the gas to enforce"))?;
         let gas_amount = current_payment
             .gas_amount
             .saturating_sub(current_expenditure.gas_used);
@@ -90,6 +92,7 @@ mod test {
         gas_limit: U256([2000, 0, 0, 0]), // MIN * 2
         gas_price: U256([100001, 0, 0, 0]).try_into().unwrap(),
         l2_gas_limit: None,
+        l1_data_fee: None,
     });
 
     #[test]
@@ -206,6 +209,7 @@ mod test {
             gas_limit: MIN * 100, // Large gas limit
             gas_price: COST_ESTIMATE.gas_price.clone(),
             l2_gas_limit: Some(MIN * 2),
+            l1_data_fee: None,
         };
 
         // First ensure that if l2_gas_limit is None, because of the high gas limit,
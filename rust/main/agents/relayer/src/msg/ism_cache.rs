@@ -0,0 +1,268 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use hyperlane_base::CoreMetrics;
+use hyperlane_core::{ChainResult, HyperlaneDomain, Mailbox, H256};
+use prometheus::IntCounter;
+use tokio::sync::RwLock;
+
+#[derive(Debug, Clone, Copy)]
+struct CachedIsm {
+    ism: H256,
+    /// Hash of the recipient's code when its ISM was fetched, if the chain
+    /// supports fetching it
+    code_hash: Option<H256>,
+    /// When the ISM is next checked, a TTL after it was fetched or last kept
+    /// because the recipient's code didn't change
+    expires_at: Instant,
+}
+
+/// Caches the ISM of each recipient on a destination, so that it isn't
+/// fetched for every message. A cached ISM is served without any RPC for a
+/// TTL, after which it's kept if the code of its recipient didn't change, and
+/// refetched otherwise or on chains that can't cheaply tell when the code
+/// changes. Recipients changing their ISM without changing their code are
+/// caught by invalidating their cached ISM when delivering to them fails.
+#[derive(Debug)]
+pub struct RecipientIsmCache {
+    mailbox: Arc<dyn Mailbox>,
+    entries: RwLock<HashMap<H256, CachedIsm>>,
+    hits: IntCounter,
+    misses: IntCounter,
+}
+
+impl RecipientIsmCache {
+    /// Time to live for a cached ISM. 10 mins.
+    const TTL: Duration = Duration::from_secs(60 * 10);
+
+    pub fn new(
+        mailbox: Arc<dyn Mailbox>,
+        destination: &HyperlaneDomain,
+        metrics: &CoreMetrics,
+    ) -> Self {
+        let lookups = metrics.recipient_ism_cache_lookups_count();
+        Self {
+            hits: lookups.with_label_values(&[destination.name(), "hit"]),
+            misses: lookups.with_label_values(&[destination.name(), "miss"]),
+            mailbox,
+            entries: Default::default(),
+        }
+    }
+
+    /// Gets the ISM of `recipient`, fetching it from onchain if the cached
+    /// value is stale.
+    pub async fn get(&self, recipient: H256) -> ChainResult<H256> {
+        let cached = self.entries.read().await.get(&recipient).copied();
+        if let Some(cached) = cached.filter(|cached| Instant::now() < cached.expires_at) {
+            self.hits.inc();
+            return Ok(cached.ism);
+        }
+
+        // Only checked once the cached ISM expires, to not make every lookup
+        // an RPC
+        let code_hash = self.mailbox.provider().code_hash(&recipient).await?;
+        let ism = match cached {
+            Some(cached) if code_hash.is_some() && cached.code_hash == code_hash => {
+                self.hits.inc();
+                cached.ism
+            }
+            _ => {
+                self.misses.inc();
+                self.mailbox.recipient_ism(recipient).await?
+            }
+        };
+        self.entries.write().await.insert(
+            recipient,
+            CachedIsm {
+                ism,
+                code_hash,
+                expires_at: Instant::now() + Self::TTL,
+            },
+        );
+        Ok(ism)
+    }
+
+    /// Drops the cached ISM of `recipient`, e.g. because delivering to it
+    /// failed and its ISM may have changed.
+    pub async fn invalidate(&self, recipient: &H256) {
+        self.entries.write().await.remove(recipient);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    };
+
+    use async_trait::async_trait;
+    use hyperlane_core::{
+        test_utils::dummy_domain, BlockInfo, ChainInfo, HyperlaneChain, HyperlaneProvider, TxnInfo,
+        U256,
+    };
+    use hyperlane_test::mocks::MockMailboxContract;
+    use prometheus::Registry;
+
+    use super::*;
+
+    mockall::mock! {
+        pub Provider {}
+
+        impl Debug for Provider {
+            fn fmt<'a>(&self, f: &mut std::fmt::Formatter<'a>) -> std::fmt::Result;
+        }
+
+        impl HyperlaneChain for Provider {
+            fn domain(&self) -> &HyperlaneDomain;
+            fn provider(&self) -> Box<dyn HyperlaneProvider>;
+        }
+
+        #[async_trait]
+        impl HyperlaneProvider for Provider {
+            async fn get_block_by_hash(&self, hash: &H256) -> ChainResult<BlockInfo>;
+            async fn get_txn_by_hash(&self, hash: &H256) -> ChainResult<TxnInfo>;
+            async fn is_contract(&self, address: &H256) -> ChainResult<bool>;
+            async fn code_hash(&self, address: &H256) -> ChainResult<Option<H256>>;
+            async fn get_balance(&self, address: String) -> ChainResult<U256>;
+            async fn get_chain_metrics(&self) -> ChainResult<Option<ChainInfo>>;
+        }
+    }
+
+    /// The onchain state the cache is tested against, counting the RPCs the
+    /// cache makes
+    #[derive(Default)]
+    struct Onchain {
+        ism: Mutex<H256>,
+        code_hash: Mutex<Option<H256>>,
+        ism_fetches: AtomicUsize,
+        code_hash_fetches: AtomicUsize,
+    }
+
+    fn cache(onchain: &Arc<Onchain>) -> RecipientIsmCache {
+        let mut mailbox = MockMailboxContract::new();
+        let state = onchain.clone();
+        mailbox.expect__recipient_ism().returning(move |_| {
+            state.ism_fetches.fetch_add(1, Ordering::SeqCst);
+            Ok(*state.ism.lock().unwrap())
+        });
+        let state = onchain.clone();
+        mailbox.expect__provider().returning(move || {
+            let state = state.clone();
+            let mut provider = MockProvider::new();
+            provider.expect_code_hash().returning(move |_| {
+                state.code_hash_fetches.fetch_add(1, Ordering::SeqCst);
+                Ok(*state.code_hash.lock().unwrap())
+            });
+            Box::new(provider)
+        });
+        let metrics = CoreMetrics::new("dummy_relayer", 37582, Registry::new()).unwrap();
+        RecipientIsmCache::new(
+            Arc::new(mailbox),
+            &dummy_domain(1, "dummy_destination"),
+            &metrics,
+        )
+    }
+
+    /// Makes the cached ISM of `recipient` expire
+    async fn expire(cache: &RecipientIsmCache, recipient: &H256) {
+        let mut entries = cache.entries.write().await;
+        let entry = entries.get_mut(recipient).unwrap();
+        entry.expires_at = Instant::now();
+    }
+
+    #[tokio::test]
+    async fn test_cached_ism_is_served_without_rpcs() {
+        let onchain = Arc::new(Onchain::default());
+        *onchain.ism.lock().unwrap() = H256::from_low_u64_be(1);
+        *onchain.code_hash.lock().unwrap() = Some(H256::from_low_u64_be(2));
+        let cache = cache(&onchain);
+        let recipient = H256::from_low_u64_be(3);
+
+        assert_eq!(
+            cache.get(recipient).await.unwrap(),
+            H256::from_low_u64_be(1)
+        );
+        assert_eq!(
+            cache.get(recipient).await.unwrap(),
+            H256::from_low_u64_be(1)
+        );
+
+        assert_eq!(onchain.ism_fetches.load(Ordering::SeqCst), 1);
+        assert_eq!(onchain.code_hash_fetches.load(Ordering::SeqCst), 1);
+        assert_eq!(cache.misses.get(), 1);
+        assert_eq!(cache.hits.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expired_ism_is_kept_while_the_code_is_unchanged() {
+        let onchain = Arc::new(Onchain::default());
+        *onchain.code_hash.lock().unwrap() = Some(H256::from_low_u64_be(2));
+        let cache = cache(&onchain);
+        let recipient = H256::from_low_u64_be(3);
+        cache.get(recipient).await.unwrap();
+
+        expire(&cache, &recipient).await;
+        cache.get(recipient).await.unwrap();
+        assert_eq!(onchain.ism_fetches.load(Ordering::SeqCst), 1);
+        assert_eq!(onchain.code_hash_fetches.load(Ordering::SeqCst), 2);
+
+        // Kept for another TTL
+        cache.get(recipient).await.unwrap();
+        assert_eq!(onchain.code_hash_fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expired_ism_is_refetched_once_the_code_changes() {
+        let onchain = Arc::new(Onchain::default());
+        *onchain.code_hash.lock().unwrap() = Some(H256::from_low_u64_be(2));
+        let cache = cache(&onchain);
+        let recipient = H256::from_low_u64_be(3);
+        cache.get(recipient).await.unwrap();
+
+        *onchain.ism.lock().unwrap() = H256::from_low_u64_be(4);
+        *onchain.code_hash.lock().unwrap() = Some(H256::from_low_u64_be(5));
+        expire(&cache, &recipient).await;
+
+        assert_eq!(
+            cache.get(recipient).await.unwrap(),
+            H256::from_low_u64_be(4)
+        );
+        assert_eq!(onchain.ism_fetches.load(Ordering::SeqCst), 2);
+        assert_eq!(cache.misses.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expired_ism_is_refetched_without_code_hashes() {
+        let onchain = Arc::new(Onchain::default());
+        let cache = cache(&onchain);
+        let recipient = H256::from_low_u64_be(3);
+        cache.get(recipient).await.unwrap();
+
+        expire(&cache, &recipient).await;
+        cache.get(recipient).await.unwrap();
+        assert_eq!(onchain.ism_fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalidated_ism_is_refetched() {
+        let onchain = Arc::new(Onchain::default());
+        *onchain.code_hash.lock().unwrap() = Some(H256::from_low_u64_be(2));
+        let cache = cache(&onchain);
+        let recipient = H256::from_low_u64_be(3);
+        cache.get(recipient).await.unwrap();
+
+        *onchain.ism.lock().unwrap() = H256::from_low_u64_be(4);
+        cache.invalidate(&recipient).await;
+
+        assert_eq!(
+            cache.get(recipient).await.unwrap(),
+            H256::from_low_u64_be(4)
+        );
+        assert_eq!(onchain.ism_fetches.load(Ordering::SeqCst), 2);
+    }
+}
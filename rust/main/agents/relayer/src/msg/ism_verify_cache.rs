@@ -0,0 +1,78 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    time::{Duration, Instant},
+};
+
+use hyperlane_core::H256;
+use tokio::sync::RwLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct VerifyKey {
+    ism: H256,
+    message_id: H256,
+    metadata_hash: u64,
+}
+
+impl VerifyKey {
+    fn new(ism: H256, message_id: H256, metadata: &[u8]) -> Self {
+        let mut hasher = DefaultHasher::new();
+        metadata.hash(&mut hasher);
+        Self {
+            ism,
+            message_id,
+            metadata_hash: hasher.finish(),
+        }
+    }
+}
+
+/// Remembers the metadata an ISM rejected when dry running its verification,
+/// so that retries of a message with the same metadata don't dry run it
+/// again. Rejections are only kept briefly, because the ISM's configuration
+/// may change.
+#[derive(Debug, Default)]
+pub struct IsmVerifyCache {
+    rejections: RwLock<HashMap<VerifyKey, Instant>>,
+}
+
+impl IsmVerifyCache {
+    /// Time to live for a cached rejection. 2 mins.
+    const TTL: Duration = Duration::from_secs(60 * 2);
+
+    /// Whether `ism` recently rejected `metadata` for the message
+    pub async fn is_rejected(&self, ism: H256, message_id: H256, metadata: &[u8]) -> bool {
+        let key = VerifyKey::new(ism, message_id, metadata);
+        self.rejections
+            .read()
+            .await
+            .get(&key)
+            .map_or(false, |rejected_at| rejected_at.elapsed() < Self::TTL)
+    }
+
+    /// Remember that `ism` rejected `metadata` for the message
+    pub async fn record_rejection(&self, ism: H256, message_id: H256, metadata: &[u8]) {
+        let key = VerifyKey::new(ism, message_id, metadata);
+        let mut rejections = self.rejections.write().await;
+        rejections.retain(|_, rejected_at| rejected_at.elapsed() < Self::TTL);
+        rejections.insert(key, Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rejections_are_keyed_by_metadata() {
+        let cache = IsmVerifyCache::default();
+        let ism = H256::repeat_byte(1);
+        let message_id = H256::repeat_byte(2);
+        let other_ism = H256::repeat_byte(3);
+
+        assert!(!cache.is_rejected(ism, message_id, &[1, 2, 3]).await);
+        cache.record_rejection(ism, message_id, &[1, 2, 3]).await;
+        assert!(cache.is_rejected(ism, message_id, &[1, 2, 3]).await);
+        assert!(!cache.is_rejected(ism, message_id, &[1, 2, 4]).await);
+        assert!(!cache.is_rejected(other_ism, message_id, &[1, 2, 3]).await);
+    }
+}
@@ -0,0 +1,210 @@
+//! Sanity limits dropping obviously abusive messages before their metadata
+//! is built or their delivery estimated.
+
+use std::sync::Arc;
+
+use eyre::Result;
+use hyperlane_base::db::HyperlaneRocksDB;
+use hyperlane_core::{
+    Decode, HyperlaneMessage, HyperlaneProvider, StandardHookMetadata, H256, U256,
+};
+use tracing::debug;
+
+use crate::settings::MessageLimitsConf;
+
+/// The limits on the messages of an origin
+#[derive(Debug, Default)]
+pub struct MessageLimits {
+    conf: MessageLimitsConf,
+    /// The provider and mailbox of the origin, to read the hook metadata of
+    /// messages from the transactions dispatching them. Only needed to limit
+    /// the requested gas limit.
+    origin: Option<(Arc<dyn HyperlaneProvider>, H256)>,
+}
+
+impl MessageLimits {
+    pub fn new(
+        conf: MessageLimitsConf,
+        origin: Option<(Arc<dyn HyperlaneProvider>, H256)>,
+    ) -> Self {
+        Self { conf, origin }
+    }
+
+    /// The limit `message` exceeds, if any
+    pub async fn exceeded_limit(
+        &self,
+        message: &HyperlaneMessage,
+        origin_db: &HyperlaneRocksDB,
+    ) -> Result<Option<String>> {
+        if let Some(max_body_bytes) = self.conf.max_body_bytes {
+            if message.body.len() > max_body_bytes {
+                return Ok(Some(format!(
+                    "body of {} bytes exceeds {max_body_bytes} bytes",
+                    message.body.len()
+                )));
+            }
+        }
+        if let Some(max_gas_limit) = self.conf.max_gas_limit {
+            if let Some(gas_limit) = self.requested_gas_limit(message, origin_db).await? {
+                if gas_limit > max_gas_limit {
+                    return Ok(Some(format!(
+                        "requested gas limit of {gas_limit} exceeds {max_gas_limit}"
+                    )));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// The gas limit the hook metadata of `message` requests for its
+    /// delivery, or None if it requests none or can't be read, e.g. because
+    /// the message was dispatched through another contract
+    async fn requested_gas_limit(
+        &self,
+        message: &HyperlaneMessage,
+        origin_db: &HyperlaneRocksDB,
+    ) -> Result<Option<U256>> {
+        let Some((provider, mailbox)) = &self.origin else {
+            return Ok(None);
+        };
+        let Some(transaction) =
+            origin_db.retrieve_dispatch_transaction_by_message_id(&message.id())?
+        else {
+            debug!(id = ?message.id(), "Dispatch transaction of message unknown, not limiting its gas");
+            return Ok(None);
+        };
+        let Some(hook_metadata) = provider
+            .get_dispatch_hook_metadata(&transaction, mailbox, message)
+            .await?
+        else {
+            debug!(id = ?message.id(), ?transaction, "Hook metadata of message unknown, not limiting its gas");
+            return Ok(None);
+        };
+        Ok(requested_gas_limit(&hook_metadata))
+    }
+}
+
+/// The gas limit requested by standard hook metadata. Hooks given empty
+/// metadata use their default gas limit instead.
+fn requested_gas_limit(hook_metadata: &[u8]) -> Option<U256> {
+    StandardHookMetadata::read_from(&mut &hook_metadata[..])
+        .ok()
+        .map(|metadata| metadata.gas_limit)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::{
+        BlockInfo, ChainInfo, ChainResult, Encode, HyperlaneChain, HyperlaneDomain,
+        HyperlaneLogStore, Indexed, LogMeta, TxnInfo, H512,
+    };
+    use mockall::mock;
+
+    use super::*;
+
+    mock! {
+        pub Provider {}
+
+        impl Debug for Provider {
+            fn fmt<'a>(&self, f: &mut std::fmt::Formatter<'a>) -> std::fmt::Result;
+        }
+
+        impl HyperlaneChain for Provider {
+            fn domain(&self) -> &HyperlaneDomain;
+            fn provider(&self) -> Box<dyn HyperlaneProvider>;
+        }
+
+        #[async_trait]
+        impl HyperlaneProvider for Provider {
+            async fn get_block_by_hash(&self, hash: &H256) -> ChainResult<BlockInfo>;
+            async fn get_txn_by_hash(&self, hash: &H256) -> ChainResult<TxnInfo>;
+            async fn is_contract(&self, address: &H256) -> ChainResult<bool>;
+            async fn get_balance(&self, address: String) -> ChainResult<U256>;
+            async fn get_chain_metrics(&self) -> ChainResult<Option<ChainInfo>>;
+            async fn get_dispatch_hook_metadata(
+                &self,
+                hash: &H512,
+                mailbox: &H256,
+                message: &HyperlaneMessage,
+            ) -> ChainResult<Option<Vec<u8>>>;
+        }
+    }
+
+    #[test]
+    fn test_requested_gas_limit() {
+        let metadata = StandardHookMetadata::default().with_gas_limit(300_000.into());
+        assert_eq!(
+            requested_gas_limit(&metadata.to_vec()),
+            Some(300_000.into())
+        );
+        assert_eq!(requested_gas_limit(&[]), None);
+        assert_eq!(requested_gas_limit(&[0, 2]), None);
+    }
+
+    #[tokio::test]
+    async fn test_limits_body_size_and_hook_metadata_gas_limit() {
+        test_utils::run_test_db(|db| async move {
+            let origin = HyperlaneDomain::new_test_domain("test_message_limits");
+            let db = HyperlaneRocksDB::new(&origin, db);
+            let mailbox = H256::repeat_byte(0xaa);
+            let transaction = H512::repeat_byte(0xbb);
+            let dispatched_with = |gas_limit: u64| HyperlaneMessage {
+                nonce: gas_limit as u32,
+                body: StandardHookMetadata::default()
+                    .with_gas_limit(gas_limit.into())
+                    .to_vec(),
+                ..Default::default()
+            };
+            let (within, beyond, unknown) = (
+                dispatched_with(100_000),
+                dispatched_with(200_000),
+                dispatched_with(300_000),
+            );
+            let indexed = [&within, &beyond].map(|message| {
+                let meta = LogMeta {
+                    transaction_id: transaction,
+                    ..Default::default()
+                };
+                (Indexed::new(message.clone()), meta)
+            });
+            db.store_logs(&indexed).await.unwrap();
+
+            // The mocked dispatch transaction requests the gas limit the
+            // test messages carry in their body
+            let mut provider = MockProvider::new();
+            provider
+                .expect_get_dispatch_hook_metadata()
+                .withf(move |hash, address, _| *hash == transaction && *address == mailbox)
+                .returning(|_, _, message| Ok(Some(message.body.clone())));
+            let limits = MessageLimits::new(
+                MessageLimitsConf {
+                    max_body_bytes: Some(200),
+                    max_gas_limit: Some(150_000.into()),
+                },
+                Some((Arc::new(provider), mailbox)),
+            );
+
+            assert_eq!(limits.exceeded_limit(&within, &db).await.unwrap(), None);
+            assert_eq!(
+                limits.exceeded_limit(&beyond, &db).await.unwrap(),
+                Some("requested gas limit of 200000 exceeds 150000".to_owned())
+            );
+            // Messages whose dispatch transaction isn't known aren't limited
+            assert_eq!(limits.exceeded_limit(&unknown, &db).await.unwrap(), None);
+
+            let large = HyperlaneMessage {
+                body: vec![0; 201],
+                ..Default::default()
+            };
+            assert_eq!(
+                limits.exceeded_limit(&large, &db).await.unwrap(),
+                Some("body of 201 bytes exceeds 200 bytes".to_owned())
+            );
+        })
+        .await;
+    }
+}
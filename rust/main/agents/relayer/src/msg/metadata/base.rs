@@ -6,14 +6,19 @@ use std::{
     fmt::Debug,
     ops::Deref,
     str::FromStr,
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 
+#[cfg(feature = "legacy")]
+use crate::msg::metadata::multisig::LegacyMultisigMetadataBuilder;
 use crate::{
     merkle_tree::builder::MerkleTreeBuilder,
     msg::metadata::{
-        multisig::{MerkleRootMultisigMetadataBuilder, MessageIdMultisigMetadataBuilder},
+        multisig::{
+            MerkleRootMultisigMetadataBuilder, MessageIdMultisigMetadataBuilder,
+            MultisigMetadataCache,
+        },
         AggregationIsmMetadataBuilder, CcipReadIsmMetadataBuilder, NullMetadataBuilder,
         RoutingIsmMetadataBuilder,
     },
@@ -25,12 +30,12 @@ use eyre::{Context, Result};
 use hyperlane_base::db::{HyperlaneDb, HyperlaneRocksDB};
 use hyperlane_base::{
     settings::{ChainConf, CheckpointSyncerConf},
-    CheckpointSyncer, CoreMetrics, MultisigCheckpointSyncer,
+    CheckpointSyncer, CoreMetrics, LatestCheckpointEndpoint, MultisigCheckpointSyncer,
 };
 use hyperlane_core::{
-    accumulator::merkle::Proof, AggregationIsm, CcipReadIsm, Checkpoint, HyperlaneDomain,
-    HyperlaneMessage, InterchainSecurityModule, Mailbox, ModuleType, MultisigIsm, RoutingIsm,
-    ValidatorAnnounce, H160, H256,
+    accumulator::merkle::Proof, config::ProxyConf, AggregationIsm, CcipReadIsm, Checkpoint,
+    HyperlaneDomain, HyperlaneMessage, InterchainSecurityModule, Mailbox, MessageVersions,
+    ModuleType, MultisigIsm, RoutingIsm, ValidatorAnnounce, H160, H256,
 };
 
 use tokio::sync::RwLock;
@@ -256,6 +261,8 @@ impl MessageMetadataBuilder {
             ModuleType::Aggregation => Box::new(AggregationIsmMetadataBuilder::new(cloned)),
             ModuleType::Null => Box::new(NullMetadataBuilder::new()),
             ModuleType::CcipRead => Box::new(CcipReadIsmMetadataBuilder::new(cloned)),
+            #[cfg(feature = "legacy")]
+            ModuleType::LegacyMultisig => Box::new(LegacyMultisigMetadataBuilder::new(cloned)),
             _ => return Err(MetadataBuilderError::UnsupportedModuleType(module_type).into()),
         };
         let meta = metadata_builder
@@ -282,8 +289,17 @@ pub struct BaseMetadataBuilder {
     metrics: Arc<CoreMetrics>,
     db: HyperlaneRocksDB,
     app_context_classifier: IsmAwareAppContextClassifier,
+    /// The proxy requests to validators' storage and to offchain lookup
+    /// gateways are routed through
+    proxy: Option<ProxyConf>,
     #[new(value = "7")]
     max_depth: u32,
+    #[new(default)]
+    multisig_metadata_cache: MultisigMetadataCache,
+    /// The latest checkpoint endpoints of validators by their announced
+    /// location, kept across messages so that they keep long polling
+    #[new(default)]
+    latest_checkpoint_endpoints: Mutex<HashMap<String, LatestCheckpointEndpoint>>,
 }
 
 impl Debug for BaseMetadataBuilder {
@@ -305,6 +321,14 @@ impl BaseMetadataBuilder {
         &self.destination_chain_setup.domain
     }
 
+    pub fn destination_message_versions(&self) -> &MessageVersions {
+        &self.destination_chain_setup.message_versions
+    }
+
+    pub fn proxy(&self) -> Option<&ProxyConf> {
+        self.proxy.as_ref()
+    }
+
     pub async fn get_proof(&self, leaf_index: u32, checkpoint: Checkpoint) -> Result<Proof> {
         const CTX: &str = "When fetching message proof";
         let proof = self
@@ -324,6 +348,15 @@ impl BaseMetadataBuilder {
         Ok(proof)
     }
 
+    pub fn multisig_metadata_cache(&self) -> &MultisigMetadataCache {
+        &self.multisig_metadata_cache
+    }
+
+    /// Drops the metadata cached for a message, so that it is built again
+    pub async fn invalidate_cached_metadata(&self, message_id: &H256) {
+        self.multisig_metadata_cache.invalidate(message_id).await;
+    }
+
     pub async fn highest_known_leaf_index(&self) -> Option<u32> {
         self.origin_prover_sync.read().await.count().checked_sub(1)
     }
@@ -365,6 +398,19 @@ impl BaseMetadataBuilder {
             .await
     }
 
+    /// The endpoint of the most recently announced http location, if any
+    fn latest_checkpoint_endpoint(&self, locations: &[String]) -> Option<LatestCheckpointEndpoint> {
+        let mut endpoints = self.latest_checkpoint_endpoints.lock().unwrap();
+        locations.iter().rev().find_map(|location| {
+            if let Some(endpoint) = endpoints.get(location) {
+                return Some(endpoint.clone());
+            }
+            let endpoint = LatestCheckpointEndpoint::from_location(location, self.proxy.as_ref())?;
+            endpoints.insert(location.clone(), endpoint.clone());
+            Some(endpoint)
+        })
+    }
+
     pub async fn build_checkpoint_syncer(
         &self,
         validators: &[H256],
@@ -377,7 +423,14 @@ impl BaseMetadataBuilder {
 
         // Only use the most recently announced location for now.
         let mut checkpoint_syncers: HashMap<H160, Arc<dyn CheckpointSyncer>> = HashMap::new();
+        // Validators may also announce the http endpoint serving their latest
+        // checkpoint index, which is polled instead of their storage
+        let mut latest_checkpoint_endpoints: HashMap<H160, LatestCheckpointEndpoint> =
+            HashMap::new();
         for (&validator, validator_storage_locations) in validators.iter().zip(storage_locations) {
+            if let Some(endpoint) = self.latest_checkpoint_endpoint(&validator_storage_locations) {
+                latest_checkpoint_endpoints.insert(validator.into(), endpoint);
+            }
             for storage_location in validator_storage_locations.iter().rev() {
                 let Ok(config) = CheckpointSyncerConf::from_str(storage_location) else {
                     debug!(
@@ -400,7 +453,7 @@ impl BaseMetadataBuilder {
                     continue;
                 }
 
-                match config.build_and_validate(None).await {
+                match config.build_and_validate(None, self.proxy.as_ref()).await {
                     Ok(checkpoint_syncer) => {
                         // found the syncer for this validator
                         checkpoint_syncers.insert(validator.into(), checkpoint_syncer.into());
@@ -428,10 +481,9 @@ impl BaseMetadataBuilder {
                 }
             }
         }
-        Ok(MultisigCheckpointSyncer::new(
-            checkpoint_syncers,
-            self.metrics.clone(),
-            app_context,
-        ))
+        Ok(
+            MultisigCheckpointSyncer::new(checkpoint_syncers, self.metrics.clone(), app_context)
+                .with_latest_checkpoint_endpoints(latest_checkpoint_endpoints),
+        )
     }
 }
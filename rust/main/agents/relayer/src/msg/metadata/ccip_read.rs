@@ -5,10 +5,10 @@ use derive_more::Deref;
 use derive_new::new;
 use ethers::{abi::AbiDecode, core::utils::hex::decode as hex_decode};
 use eyre::Context;
+use hyperlane_base::utils::reqwest_client;
 use hyperlane_core::{utils::bytes_to_hex, HyperlaneMessage, RawHyperlaneMessage, H256};
 use hyperlane_ethereum::OffchainLookup;
 use regex::Regex;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use tracing::{info, instrument};
@@ -69,14 +69,17 @@ impl MetadataBuilder for CcipReadIsmMetadataBuilder {
                     "sender": sender_as_bytes,
                     "data": data_as_bytes
                 });
-                Client::new()
+                reqwest_client(self.proxy())
                     .post(interpolated_url)
                     .header("Content-Type", "application/json")
                     .json(&body)
                     .send()
                     .await?
             } else {
-                reqwest::get(interpolated_url).await?
+                reqwest_client(self.proxy())
+                    .get(interpolated_url)
+                    .send()
+                    .await?
             };
 
             let json: Result<OffchainResponse, reqwest::Error> = res.json().await;
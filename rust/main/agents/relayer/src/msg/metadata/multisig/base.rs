@@ -16,6 +16,8 @@ use crate::msg::metadata::base::MessageMetadataBuilder;
 
 use crate::msg::metadata::MetadataBuilder;
 
+use super::MultisigMetadataCacheKey;
+
 #[derive(new, AsRef, Deref)]
 pub struct MultisigMetadata {
     #[deref]
@@ -48,6 +50,11 @@ pub trait MultisigIsmMetadataBuilder: AsRef<MessageMetadataBuilder> + Send + Syn
 
     fn token_layout(&self) -> Vec<MetadataToken>;
 
+    /// Index of the newest checkpoint the metadata of the message can be
+    /// built from, as far as is known without fetching checkpoints. Cached
+    /// metadata is rebuilt once this index changes.
+    async fn latest_checkpoint_index(&self, message: &HyperlaneMessage) -> Result<Option<u32>>;
+
     fn format_metadata(&self, metadata: MultisigMetadata) -> Result<Vec<u8>> {
         let build_token = |token: &MetadataToken| -> Result<Vec<u8>> {
             match token {
@@ -115,6 +122,23 @@ impl<T: MultisigIsmMetadataBuilder> MetadataBuilder for T {
             return Ok(None);
         }
 
+        let cache_key = self
+            .latest_checkpoint_index(message)
+            .await
+            .context(CTX)?
+            .map(|checkpoint_index| MultisigMetadataCacheKey {
+                ism: ism_address,
+                message_id: message.id(),
+                checkpoint_index,
+            });
+        let cache = self.as_ref().multisig_metadata_cache();
+        if let Some(cache_key) = &cache_key {
+            if let Some(metadata) = cache.get(cache_key, &validators, threshold).await {
+                debug!(?cache_key, "Using cached metadata");
+                return Ok(Some(metadata));
+            }
+        }
+
         let checkpoint_syncer = self
             .as_ref()
             .build_checkpoint_syncer(&validators, self.as_ref().app_context.clone())
@@ -127,7 +151,13 @@ impl<T: MultisigIsmMetadataBuilder> MetadataBuilder for T {
             .context(CTX)?
         {
             debug!(?message, ?metadata.checkpoint, "Found checkpoint with quorum");
-            Ok(Some(self.format_metadata(metadata)?))
+            let metadata = self.format_metadata(metadata)?;
+            if let Some(cache_key) = cache_key {
+                cache
+                    .insert(cache_key, validators, threshold, metadata.clone())
+                    .await;
+            }
+            Ok(Some(metadata))
         } else {
             info!(
                 ?message, ?validators, threshold, ism=%multisig_ism.address(),
@@ -0,0 +1,120 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use hyperlane_core::H256;
+use tokio::sync::RwLock;
+
+/// Identifies the metadata built for a message, from the newest checkpoint
+/// that was known when it was built
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MultisigMetadataCacheKey {
+    pub ism: H256,
+    pub message_id: H256,
+    pub checkpoint_index: u32,
+}
+
+#[derive(Debug, Clone)]
+struct CachedMultisigMetadata {
+    validators: Vec<H256>,
+    threshold: u8,
+    metadata: Vec<u8>,
+    cached_at: Instant,
+}
+
+/// Caches the multisig metadata built for messages, so that retrying a
+/// message doesn't fetch its checkpoints from the validators' storage again.
+/// Cached metadata is rebuilt once a newer checkpoint is known, or once the
+/// ISM's validator set or threshold changes.
+#[derive(Debug, Default)]
+pub struct MultisigMetadataCache {
+    entries: RwLock<HashMap<MultisigMetadataCacheKey, CachedMultisigMetadata>>,
+}
+
+impl MultisigMetadataCache {
+    /// Time to live for cached metadata. 10 mins.
+    const TTL: Duration = Duration::from_secs(60 * 10);
+
+    /// Gets the metadata cached for `key`, if it was built for the same
+    /// validator set and threshold
+    pub async fn get(
+        &self,
+        key: &MultisigMetadataCacheKey,
+        validators: &[H256],
+        threshold: u8,
+    ) -> Option<Vec<u8>> {
+        self.entries
+            .read()
+            .await
+            .get(key)
+            .filter(|cached| {
+                cached.validators == validators
+                    && cached.threshold == threshold
+                    && cached.cached_at.elapsed() < Self::TTL
+            })
+            .map(|cached| cached.metadata.clone())
+    }
+
+    pub async fn insert(
+        &self,
+        key: MultisigMetadataCacheKey,
+        validators: Vec<H256>,
+        threshold: u8,
+        metadata: Vec<u8>,
+    ) {
+        let mut entries = self.entries.write().await;
+        entries.retain(|_, cached| cached.cached_at.elapsed() < Self::TTL);
+        entries.insert(
+            key,
+            CachedMultisigMetadata {
+                validators,
+                threshold,
+                metadata,
+                cached_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Drops the metadata cached for a message, e.g. because the ISM
+    /// rejected it.
+    pub async fn invalidate(&self, message_id: &H256) {
+        self.entries
+            .write()
+            .await
+            .retain(|key, _| key.message_id != *message_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cached_metadata_is_keyed_by_checkpoint_and_validators() {
+        let cache = MultisigMetadataCache::default();
+        let validators = vec![H256::repeat_byte(1), H256::repeat_byte(2)];
+        let key = MultisigMetadataCacheKey {
+            ism: H256::repeat_byte(3),
+            message_id: H256::repeat_byte(4),
+            checkpoint_index: 10,
+        };
+        cache
+            .insert(key, validators.clone(), 2, vec![1, 2, 3])
+            .await;
+
+        assert_eq!(cache.get(&key, &validators, 2).await, Some(vec![1, 2, 3]));
+        // A newer checkpoint is known
+        let newer_key = MultisigMetadataCacheKey {
+            checkpoint_index: 11,
+            ..key
+        };
+        assert_eq!(cache.get(&newer_key, &validators, 2).await, None);
+        // The validator set changed
+        assert_eq!(cache.get(&key, &validators, 1).await, None);
+        assert_eq!(cache.get(&key, &validators[..1], 2).await, None);
+
+        cache.invalidate(&key.message_id).await;
+        assert_eq!(cache.get(&key, &validators, 2).await, None);
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use derive_more::{AsRef, Deref};
+use derive_new::new;
+
+use eyre::{Context, Result};
+use hyperlane_core::accumulator::merkle::Proof;
+use hyperlane_core::{
+    unwrap_or_none_result, HyperlaneMessage, MultisigSignedLegacyCheckpoint, H256,
+};
+use tracing::{debug, info};
+
+use crate::msg::metadata::{MessageMetadataBuilder, MetadataBuilder};
+
+/// Builds the metadata of the legacy multisig ISMs of legacy (v2)
+/// deployments, from the legacy checkpoints of their validators. Its layout
+/// is:
+///
+/// - the checkpoint root (32 bytes) and index (4 bytes)
+/// - the origin mailbox (32 bytes)
+/// - the merkle proof of the message against the root (32 * 32 bytes)
+/// - the threshold (1 byte)
+/// - the signatures, ordered by validator index (65 * threshold bytes)
+/// - the whole validator set (32 bytes each)
+#[derive(Debug, Clone, Deref, new, AsRef)]
+pub struct LegacyMultisigMetadataBuilder(MessageMetadataBuilder);
+
+#[async_trait]
+impl MetadataBuilder for LegacyMultisigMetadataBuilder {
+    async fn build(
+        &self,
+        ism_address: H256,
+        message: &HyperlaneMessage,
+    ) -> Result<Option<Vec<u8>>> {
+        const CTX: &str = "When fetching LegacyMultisig metadata";
+        let multisig_ism = self.build_multisig_ism(ism_address).await.context(CTX)?;
+        let (validators, threshold) = multisig_ism
+            .validators_and_threshold(message)
+            .await
+            .context(CTX)?;
+        if validators.is_empty() {
+            info!("Could not fetch metadata: No validator set found for ISM");
+            return Ok(None);
+        }
+
+        let highest_leaf_index = unwrap_or_none_result!(
+            self.highest_known_leaf_index().await,
+            debug!("Couldn't get highest known leaf index")
+        );
+        let leaf_index = unwrap_or_none_result!(
+            self.get_merkle_leaf_id_by_message_id(message.id())
+                .await
+                .context(CTX)?,
+            debug!(
+                ?message,
+                "No merkle leaf found for message id, must have not been enqueued in the tree"
+            )
+        );
+        let checkpoint_syncer = self
+            .build_checkpoint_syncer(&validators, self.app_context.clone())
+            .await
+            .context(CTX)?;
+        let quorum_checkpoint = unwrap_or_none_result!(
+            checkpoint_syncer
+                .fetch_legacy_checkpoint_in_range(
+                    &validators,
+                    threshold as usize,
+                    leaf_index,
+                    highest_leaf_index,
+                    self.origin_domain(),
+                    self.destination_domain(),
+                )
+                .await
+                .context(CTX)?,
+            info!(
+                ?message, ?validators, threshold, ism=%multisig_ism.address(),
+                "Could not fetch metadata: Unable to reach quorum"
+            )
+        );
+        let proof = self
+            .get_proof(leaf_index, quorum_checkpoint.checkpoint.into())
+            .await
+            .context(CTX)?;
+        Ok(Some(format_metadata(
+            &quorum_checkpoint,
+            &proof,
+            threshold,
+            &validators,
+        )))
+    }
+}
+
+fn format_metadata(
+    quorum_checkpoint: &MultisigSignedLegacyCheckpoint,
+    proof: &Proof,
+    threshold: u8,
+    validators: &[H256],
+) -> Vec<u8> {
+    let checkpoint = &quorum_checkpoint.checkpoint;
+    let mut metadata = Vec::new();
+    metadata.extend_from_slice(checkpoint.root.as_bytes());
+    metadata.extend_from_slice(&checkpoint.index.to_be_bytes());
+    metadata.extend_from_slice(checkpoint.mailbox_address.as_bytes());
+    for node in proof.path.iter() {
+        metadata.extend_from_slice(node.as_bytes());
+    }
+    metadata.push(threshold);
+    for signature in quorum_checkpoint.signatures.iter() {
+        metadata.extend(signature.to_vec());
+    }
+    for validator in validators {
+        metadata.extend_from_slice(validator.as_bytes());
+    }
+    metadata
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::{accumulator::TREE_DEPTH, LegacyCheckpoint, Signature, U256};
+
+    use super::*;
+
+    #[test]
+    fn test_format_metadata() {
+        let checkpoint = LegacyCheckpoint {
+            mailbox_address: H256::repeat_byte(1),
+            mailbox_domain: 1,
+            root: H256::repeat_byte(2),
+            index: 7,
+        };
+        let signature = Signature {
+            r: U256::one(),
+            s: U256::one(),
+            v: 27,
+        };
+        let quorum_checkpoint = MultisigSignedLegacyCheckpoint {
+            checkpoint,
+            signatures: vec![signature; 2],
+        };
+        let proof = Proof {
+            leaf: H256::zero(),
+            index: 5,
+            path: [H256::repeat_byte(3); TREE_DEPTH],
+        };
+        let validators = [
+            H256::repeat_byte(4),
+            H256::repeat_byte(5),
+            H256::repeat_byte(6),
+        ];
+
+        let metadata = format_metadata(&quorum_checkpoint, &proof, 2, &validators);
+
+        assert_eq!(metadata.len(), 1093 + 2 * 65 + 3 * 32);
+        assert_eq!(&metadata[0..32], checkpoint.root.as_bytes());
+        assert_eq!(&metadata[32..36], &7u32.to_be_bytes());
+        assert_eq!(&metadata[36..68], checkpoint.mailbox_address.as_bytes());
+        assert_eq!(&metadata[68..100], H256::repeat_byte(3).as_bytes());
+        assert_eq!(metadata[1092], 2);
+        assert_eq!(&metadata[1093..1158], signature.to_vec().as_slice());
+        assert_eq!(&metadata[1223..1255], validators[0].as_bytes());
+    }
+}
@@ -28,6 +28,10 @@ impl MultisigIsmMetadataBuilder for MerkleRootMultisigMetadataBuilder {
         ]
     }
 
+    async fn latest_checkpoint_index(&self, _message: &HyperlaneMessage) -> Result<Option<u32>> {
+        Ok(self.highest_known_leaf_index().await)
+    }
+
     async fn fetch_metadata(
         &self,
         validators: &[H256],
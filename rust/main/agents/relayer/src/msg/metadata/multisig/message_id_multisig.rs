@@ -27,6 +27,11 @@ impl MultisigIsmMetadataBuilder for MessageIdMultisigMetadataBuilder {
         ]
     }
 
+    async fn latest_checkpoint_index(&self, message: &HyperlaneMessage) -> Result<Option<u32>> {
+        // Only the checkpoint of the message's own leaf is used
+        self.get_merkle_leaf_id_by_message_id(message.id()).await
+    }
+
     async fn fetch_metadata(
         &self,
         validators: &[H256],
@@ -1,9 +1,15 @@
 mod base;
+mod cache;
+#[cfg(feature = "legacy")]
+mod legacy_multisig;
 mod merkle_root_multisig;
 mod message_id_multisig;
 
 #[allow(unused_imports)] // TODO: `rustc` 1.80.1 clippy issue
 pub use base::{MetadataToken, MultisigIsmMetadataBuilder, MultisigMetadata};
 
+pub use cache::{MultisigMetadataCache, MultisigMetadataCacheKey};
+#[cfg(feature = "legacy")]
+pub use legacy_multisig::LegacyMultisigMetadataBuilder;
 pub use merkle_root_multisig::MerkleRootMultisigMetadataBuilder;
 pub use message_id_multisig::MessageIdMultisigMetadataBuilder;
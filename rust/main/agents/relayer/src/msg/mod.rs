@@ -28,11 +28,25 @@
 //!   switch everyone to new one)
 
 pub(crate) mod blacklist;
+pub(crate) mod cost_model;
+pub(crate) mod decorator;
+pub(crate) mod denylist;
+pub(crate) mod failure;
+pub(crate) mod filter;
+pub(crate) mod gas_limit_override;
 pub(crate) mod gas_payment;
+pub(crate) mod ism_cache;
+pub(crate) mod ism_verify_cache;
+pub(crate) mod message_limits;
 pub(crate) mod metadata;
 pub(crate) mod op_queue;
 pub(crate) mod op_submitter;
 pub(crate) mod pending_message;
 pub(crate) mod processor;
+pub(crate) mod self_relay;
+pub(crate) mod shard;
+pub(crate) mod spend_limit;
+pub(crate) mod submission_lease;
+pub(crate) mod trace;
 
 pub use gas_payment::GAS_EXPENDITURE_LOG_MESSAGE;
@@ -1,7 +1,7 @@
 use std::{cmp::Reverse, collections::BinaryHeap, sync::Arc};
 
 use derive_new::new;
-use hyperlane_core::{PendingOperation, PendingOperationStatus, QueueOperation};
+use hyperlane_core::{PendingOperation, PendingOperationStatus, QueueOperation, H256};
 use prometheus::{IntGauge, IntGaugeVec};
 use tokio::sync::{broadcast::Receiver, Mutex};
 use tracing::{debug, info, instrument};
@@ -100,6 +100,21 @@ impl OpQueue {
         queue.append(&mut reprioritized_queue);
     }
 
+    /// Ids and statuses of the operations in the queue
+    pub async fn pending_operations(&self) -> Vec<(H256, PendingOperationStatus)> {
+        self.queue
+            .lock()
+            .await
+            .iter()
+            .map(|Reverse(op)| (op.id(), op.status()))
+            .collect()
+    }
+
+    /// The label of the queue in metrics
+    pub fn label(&self) -> &str {
+        &self.queue_metrics_label
+    }
+
     /// Get the metric associated with this operation
     fn get_operation_metric(&self, operation: &dyn PendingOperation) -> IntGauge {
         let (destination, app_context) = operation.get_operation_labels();
@@ -131,6 +146,16 @@ pub mod test {
         id: H256,
         seconds_to_next_attempt: u64,
         destination_domain: HyperlaneDomain,
+        /// Whether the operation is ready to be attempted, without a
+        /// `next_attempt_after`
+        #[serde(skip)]
+        ready: bool,
+        #[serde(skip)]
+        origin_domain_id: u32,
+        #[serde(skip)]
+        nonce: u32,
+        #[serde(skip)]
+        payment_priority: u64,
     }
 
     impl MockPendingOperation {
@@ -139,12 +164,33 @@ pub mod test {
                 id: H256::random(),
                 seconds_to_next_attempt,
                 destination_domain,
+                ready: false,
+                origin_domain_id: 0,
+                nonce: 0,
+                payment_priority: 0,
             }
         }
 
         pub fn with_id(self, id: H256) -> Self {
             Self { id, ..self }
         }
+
+        /// Make the operation ready to be attempted, ordered by the given
+        /// priorities
+        pub fn ready_with_priorities(
+            self,
+            origin_domain_id: u32,
+            nonce: u32,
+            payment_priority: u64,
+        ) -> Self {
+            Self {
+                ready: true,
+                origin_domain_id,
+                nonce,
+                payment_priority,
+                ..self
+            }
+        }
     }
 
     impl TryBatchAs<HyperlaneMessage> for MockPendingOperation {}
@@ -167,7 +213,11 @@ pub mod test {
         }
 
         fn priority(&self) -> u32 {
-            todo!()
+            self.nonce
+        }
+
+        fn payment_priority(&self) -> u64 {
+            self.payment_priority
         }
 
         fn retrieve_status_from_db(&self) -> Option<PendingOperationStatus> {
@@ -179,7 +229,7 @@ pub mod test {
         }
 
         fn origin_domain_id(&self) -> u32 {
-            todo!()
+            self.origin_domain_id
         }
 
         fn destination_domain(&self) -> &HyperlaneDomain {
@@ -212,7 +262,10 @@ pub mod test {
         /// responsible for checking if the operation has reached a point at
         /// which we consider it safe from reorgs.
         async fn confirm(&mut self) -> PendingOperationResult {
-            todo!()
+            if self.seconds_to_next_attempt > 0 {
+                return PendingOperationResult::NotReady;
+            }
+            PendingOperationResult::Success
         }
 
         fn set_operation_outcome(
@@ -224,6 +277,9 @@ pub mod test {
         }
 
         fn next_attempt_after(&self) -> Option<Instant> {
+            if self.ready {
+                return None;
+            }
             Some(
                 Instant::now()
                     .checked_add(Duration::from_secs(self.seconds_to_next_attempt))
@@ -231,8 +287,8 @@ pub mod test {
             )
         }
 
-        fn set_next_attempt_after(&mut self, _delay: Duration) {
-            todo!()
+        fn set_next_attempt_after(&mut self, delay: Duration) {
+            self.seconds_to_next_attempt = delay.as_secs();
         }
 
         fn set_retries(&mut self, _retries: u32) {
@@ -393,4 +449,51 @@ pub mod test {
         assert_eq!(popped[3], op_ids[0]);
         assert_eq!(popped[4], op_ids[1]);
     }
+
+    #[tokio::test]
+    async fn test_ready_operations_order() {
+        let (metrics, queue_metrics_label) = dummy_metrics_and_label();
+        let broadcaster = sync::broadcast::Sender::new(100);
+        let mut op_queue = OpQueue::new(
+            metrics,
+            queue_metrics_label,
+            Arc::new(Mutex::new(broadcaster.subscribe())),
+        );
+
+        let destination_domain: HyperlaneDomain = KnownHyperlaneDomain::Ethereum.into();
+        let op = |origin, nonce, payment_priority| {
+            Box::new(
+                MockPendingOperation::new(0, destination_domain.clone()).ready_with_priorities(
+                    origin,
+                    nonce,
+                    payment_priority,
+                ),
+            ) as QueueOperation
+        };
+        // Ordering the first three by nonce within their origin and by
+        // payment across origins would be cyclic
+        let ops = vec![
+            op(1, 1, 10),
+            op(1, 2, 100),
+            op(2, 1, 50),
+            op(1, 3, 10),
+            op(2, 0, 10),
+            Box::new(MockPendingOperation::new(1, destination_domain.clone())) as QueueOperation,
+        ];
+        let op_ids: Vec<_> = ops.iter().map(|op| op.id()).collect();
+        for op in ops.into_iter().rev() {
+            op_queue
+                .push(op, Some(PendingOperationStatus::FirstPrepareAttempt))
+                .await;
+        }
+
+        let mut popped = vec![];
+        while let Some(op) = op_queue.pop().await {
+            popped.push(op.id());
+        }
+        // Best paying first, then by origin and nonce, and operations not
+        // ready yet last
+        let expected: Vec<_> = [1, 2, 0, 3, 4, 5].iter().map(|&i| op_ids[i]).collect();
+        assert_eq!(popped, expected);
+    }
 }
@@ -1,12 +1,14 @@
 #![allow(clippy::doc_markdown)] // TODO: `rustc` 1.80.1 clippy issue
 #![allow(clippy::doc_lazy_continuation)] // TODO: `rustc` 1.80.1 clippy issue
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
 use derive_new::new;
 use futures::future::join_all;
 use futures_util::future::try_join_all;
+use hyperlane_core::config::DataCostModel;
 use hyperlane_core::total_estimated_cost;
 use hyperlane_core::BatchResult;
 use hyperlane_core::ConfirmReason::*;
@@ -20,23 +22,32 @@ use tokio::sync::broadcast::Sender;
 use tokio::sync::mpsc;
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
-use tokio::time::sleep;
+use tokio::time::{sleep, timeout_at, Instant};
 use tokio_metrics::TaskMonitor;
 use tracing::{debug, info_span, instrument, instrument::Instrumented, trace, Instrument};
 use tracing::{info, warn};
 
-use hyperlane_base::CoreMetrics;
+use hyperlane_base::{spawn_named, ChainHalts, CoreMetrics, ShutdownSignal, CHAIN_HALT_PAUSE};
 use hyperlane_core::{
     ChainCommunicationError, ChainResult, HyperlaneDomain, HyperlaneDomainProtocol,
     PendingOperationResult, QueueOperation, TxOutcome,
 };
 
+use crate::leader_election::Leadership;
+use crate::msg::cost_model::{batch_cost, serial_cost, DataPricer, DeliveryCost};
 use crate::msg::pending_message::CONFIRM_DELAY;
+use crate::msg::spend_limit::SpendLimiter;
+use crate::msg::submission_lease::SubmissionLease;
 use crate::server::MessageRetryRequest;
 
 use super::op_queue::OpQueue;
 use super::op_queue::OperationPriorityQueue;
 
+/// How long to wait before retrying an operation leased by another relayer
+const LEASED_RETRY_DELAY: Duration = Duration::from_secs(60);
+/// How long to wait before retrying an operation whose lease couldn't be taken
+const LEASE_ERROR_RETRY_DELAY: Duration = Duration::from_secs(10);
+
 /// SerialSubmitter accepts operations over a channel. It is responsible for
 /// executing the right strategy to deliver those messages to the destination
 /// chain. It is designed to be used in a scenario allowing only one
@@ -94,20 +105,48 @@ pub struct SerialSubmitter {
     metrics: SerialSubmitterMetrics,
     /// Max batch size for submitting messages
     max_batch_size: u32,
+    /// Pricing of transaction data on the destination. If set, operations are
+    /// only batched if the batch is estimated to be cheaper.
+    data_cost_model: Option<DataCostModel>,
+    /// Leases shared with redundant relayers. If set, operations are only
+    /// submitted once their lease is taken.
+    submission_lease: Option<Arc<dyn SubmissionLease>>,
+    /// Set while submission to this domain is paused, e.g. because the
+    /// signer can't afford a delivery.
+    submission_paused: Arc<AtomicBool>,
+    /// Whether this relayer is the leader among its replicas. Standbys
+    /// prepare operations but don't submit them.
+    leadership: Leadership,
+    /// Pauses submission while the hourly spend cap of the domain is
+    /// exceeded.
+    spend_limiter: Arc<SpendLimiter>,
+    /// Pauses preparing and submitting operations while the domain is
+    /// halted.
+    chain_halts: ChainHalts,
     /// tokio task monitor
     task_monitor: TaskMonitor,
+    /// Signals the relayer is shutting down, after which no new operations
+    /// are prepared or submitted.
+    shutdown: ShutdownSignal,
+    /// How long to wait for in-flight submissions to be confirmed once
+    /// shutting down.
+    shutdown_timeout: Duration,
     prepare_queue: OpQueue,
     submit_queue: OpQueue,
     confirm_queue: OpQueue,
 }
 
 impl SerialSubmitter {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         domain: HyperlaneDomain,
         rx: mpsc::UnboundedReceiver<QueueOperation>,
         retry_op_transmitter: Sender<MessageRetryRequest>,
         metrics: SerialSubmitterMetrics,
         max_batch_size: u32,
+        data_cost_model: Option<DataCostModel>,
+        submission_lease: Option<Arc<dyn SubmissionLease>>,
+        submission_paused: Arc<AtomicBool>,
         task_monitor: TaskMonitor,
     ) -> Self {
         let prepare_queue = OpQueue::new(
@@ -131,13 +170,49 @@ impl SerialSubmitter {
             rx,
             metrics,
             max_batch_size,
+            data_cost_model,
+            submission_lease,
+            submission_paused,
+            leadership: Leadership::default(),
+            spend_limiter: Default::default(),
+            chain_halts: Default::default(),
             task_monitor,
+            shutdown: ShutdownSignal::default(),
+            shutdown_timeout: Duration::ZERO,
             prepare_queue,
             submit_queue,
             confirm_queue,
         }
     }
 
+    /// Stop taking new operations once `shutdown` is triggered, and wait up
+    /// to `timeout` for the operations already submitted to be confirmed.
+    pub fn with_shutdown(mut self, shutdown: ShutdownSignal, timeout: Duration) -> Self {
+        self.shutdown = shutdown;
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// Only submit operations while this relayer is the leader
+    pub fn with_leadership(mut self, leadership: Leadership) -> Self {
+        self.leadership = leadership;
+        self
+    }
+
+    /// Pause submission while the hourly spend cap of `spend_limiter` is
+    /// exceeded
+    pub fn with_spend_limiter(mut self, spend_limiter: Arc<SpendLimiter>) -> Self {
+        self.spend_limiter = spend_limiter;
+        self
+    }
+
+    /// Pause preparing and submitting operations while the domain is halted
+    /// in `chain_halts`
+    pub fn with_chain_halts(mut self, chain_halts: ChainHalts) -> Self {
+        self.chain_halts = chain_halts;
+        self
+    }
+
     pub async fn prepare_queue(&self) -> OperationPriorityQueue {
         self.prepare_queue.queue.clone()
     }
@@ -145,9 +220,12 @@ impl SerialSubmitter {
     pub fn spawn(self) -> Instrumented<JoinHandle<()>> {
         let span = info_span!("SerialSubmitter", destination=%self.domain);
         let task_monitor = self.task_monitor.clone();
-        tokio::spawn(TaskMonitor::instrument(&task_monitor, async move {
-            self.run().await
-        }))
+        let domain = self.domain.clone();
+        spawn_named(
+            "serial_submitter",
+            Some(&domain),
+            TaskMonitor::instrument(&task_monitor, async move { self.run().await }),
+        )
         .instrument(span)
     }
 
@@ -157,58 +235,169 @@ impl SerialSubmitter {
             metrics,
             rx: rx_prepare,
             max_batch_size,
+            data_cost_model,
+            submission_lease,
+            submission_paused,
+            leadership,
+            spend_limiter,
+            chain_halts,
             task_monitor,
+            shutdown,
+            shutdown_timeout,
             prepare_queue,
             submit_queue,
             confirm_queue,
         } = self;
+        let queues = [
+            prepare_queue.clone(),
+            submit_queue.clone(),
+            confirm_queue.clone(),
+        ];
 
         let tasks = [
-            tokio::spawn(TaskMonitor::instrument(
-                &task_monitor,
-                receive_task(domain.clone(), rx_prepare, prepare_queue.clone()),
-            )),
-            tokio::spawn(TaskMonitor::instrument(
-                &task_monitor,
-                prepare_task(
-                    domain.clone(),
-                    prepare_queue.clone(),
-                    submit_queue.clone(),
-                    confirm_queue.clone(),
-                    max_batch_size,
-                    metrics.clone(),
+            spawn_named(
+                "receive_operations",
+                Some(&domain),
+                TaskMonitor::instrument(
+                    &task_monitor,
+                    receive_task(
+                        domain.clone(),
+                        rx_prepare,
+                        prepare_queue.clone(),
+                        shutdown.clone(),
+                    ),
                 ),
-            )),
-            tokio::spawn(TaskMonitor::instrument(
-                &task_monitor,
-                submit_task(
-                    domain.clone(),
-                    prepare_queue.clone(),
-                    submit_queue,
-                    confirm_queue.clone(),
-                    max_batch_size,
-                    metrics.clone(),
+            ),
+            spawn_named(
+                "prepare_queue",
+                Some(&domain),
+                TaskMonitor::instrument(
+                    &task_monitor,
+                    prepare_task(
+                        domain.clone(),
+                        prepare_queue.clone(),
+                        submit_queue.clone(),
+                        confirm_queue.clone(),
+                        max_batch_size,
+                        chain_halts.clone(),
+                        metrics.clone(),
+                        shutdown.clone(),
+                    ),
                 ),
-            )),
-            tokio::spawn(TaskMonitor::instrument(
-                &task_monitor,
-                confirm_task(
-                    domain.clone(),
-                    prepare_queue,
-                    confirm_queue,
-                    max_batch_size,
-                    metrics,
+            ),
+            spawn_named(
+                "submit_queue",
+                Some(&domain),
+                TaskMonitor::instrument(
+                    &task_monitor,
+                    submit_task(
+                        domain.clone(),
+                        prepare_queue.clone(),
+                        submit_queue,
+                        confirm_queue.clone(),
+                        max_batch_size,
+                        data_cost_model,
+                        submission_lease.clone(),
+                        submission_paused,
+                        leadership,
+                        spend_limiter,
+                        chain_halts,
+                        metrics.clone(),
+                        shutdown.clone(),
+                    ),
+                ),
+            ),
+            spawn_named(
+                "confirm_queue",
+                Some(&domain),
+                TaskMonitor::instrument(
+                    &task_monitor,
+                    confirm_task(
+                        domain.clone(),
+                        prepare_queue.clone(),
+                        confirm_queue.clone(),
+                        max_batch_size,
+                        submission_lease.clone(),
+                        metrics.clone(),
+                        shutdown.clone(),
+                    ),
                 ),
-            )),
+            ),
         ];
 
-        if let Err(err) = try_join_all(tasks).await {
-            tracing::error!(
-                error=?err,
-                ?domain,
-                "SerialSubmitter task panicked for domain"
-            );
+        let tasks = try_join_all(tasks);
+        tokio::pin!(tasks);
+        tokio::select! {
+            result = &mut tasks => {
+                if let Err(err) = result {
+                    tracing::error!(
+                        error=?err,
+                        ?domain,
+                        "SerialSubmitter task panicked for domain"
+                    );
+                }
+                return;
+            }
+            _ = shutdown.triggered() => {}
+        }
+
+        info!(
+            %domain,
+            ?shutdown_timeout,
+            "Shutting down, waiting for in-flight submissions to be confirmed"
+        );
+        let deadline = Instant::now() + shutdown_timeout;
+        match timeout_at(deadline, &mut tasks).await {
+            Ok(Ok(_)) => {
+                let drained = timeout_at(
+                    deadline,
+                    drain_confirm_queue(
+                        &domain,
+                        &prepare_queue,
+                        confirm_queue,
+                        max_batch_size,
+                        submission_lease.as_deref(),
+                        &metrics,
+                    ),
+                )
+                .await;
+                if drained.is_err() {
+                    warn!(%domain, "Timed out confirming submitted operations");
+                }
+            }
+            Ok(Err(err)) => {
+                tracing::error!(
+                    error=?err,
+                    ?domain,
+                    "SerialSubmitter task panicked for domain while shutting down"
+                );
+            }
+            Err(_) => warn!(%domain, "Timed out waiting for in-flight submissions"),
         }
+        report_pending_operations(&domain, &queues).await;
+    }
+}
+
+/// Log the operations left in the queues of `domain` once shut down. Their
+/// status is persisted, so they are picked up again when the relayer restarts.
+async fn report_pending_operations(domain: &HyperlaneDomain, queues: &[OpQueue]) {
+    let mut pending_count = 0;
+    for queue in queues {
+        let pending = queue.pending_operations().await;
+        if pending.is_empty() {
+            continue;
+        }
+        pending_count += pending.len();
+        warn!(
+            %domain,
+            queue = queue.label(),
+            count = pending.len(),
+            operations = ?pending,
+            "Operations left pending at shutdown"
+        );
+    }
+    if pending_count == 0 {
+        info!(%domain, "No operations left pending at shutdown");
     }
 }
 
@@ -217,9 +406,17 @@ async fn receive_task(
     domain: HyperlaneDomain,
     mut rx: mpsc::UnboundedReceiver<QueueOperation>,
     prepare_queue: OpQueue,
+    shutdown: ShutdownSignal,
 ) {
-    // Pull any messages sent to this submitter
-    while let Some(op) = rx.recv().await {
+    // Pull any messages sent to this submitter, until shutting down
+    loop {
+        let op = tokio::select! {
+            op = rx.recv() => op,
+            _ = shutdown.triggered() => break,
+        };
+        let Some(op) = op else {
+            break;
+        };
         trace!(?op, "Received new operation");
         // make sure things are getting wired up correctly; if this works in testing it
         // should also be valid in production.
@@ -235,6 +432,7 @@ async fn receive_task(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 #[instrument(skip_all, fields(%domain))]
 async fn prepare_task(
     domain: HyperlaneDomain,
@@ -242,11 +440,18 @@ async fn prepare_task(
     submit_queue: OpQueue,
     confirm_queue: OpQueue,
     max_batch_size: u32,
+    chain_halts: ChainHalts,
     metrics: SerialSubmitterMetrics,
+    shutdown: ShutdownSignal,
 ) {
     // Prepare at most `max_batch_size` ops at a time to avoid getting rate-limited
     let ops_to_prepare = max_batch_size as usize;
-    loop {
+    while !shutdown.is_triggered() {
+        if chain_halts.is_halted(&domain) {
+            // Preparing would only fail until the destination resumes
+            sleep(CHAIN_HALT_PAUSE).await;
+            continue;
+        }
         // Pop messages here according to the configured batch.
         let mut batch = prepare_queue.pop_many(ops_to_prepare).await;
         if batch.is_empty() {
@@ -310,6 +515,7 @@ async fn prepare_task(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 #[instrument(skip_all, fields(%domain))]
 async fn submit_task(
     domain: HyperlaneDomain,
@@ -317,11 +523,42 @@ async fn submit_task(
     mut submit_queue: OpQueue,
     mut confirm_queue: OpQueue,
     max_batch_size: u32,
+    data_cost_model: Option<DataCostModel>,
+    submission_lease: Option<Arc<dyn SubmissionLease>>,
+    submission_paused: Arc<AtomicBool>,
+    leadership: Leadership,
+    spend_limiter: Arc<SpendLimiter>,
+    chain_halts: ChainHalts,
     metrics: SerialSubmitterMetrics,
+    shutdown: ShutdownSignal,
 ) {
     let recv_limit = max_batch_size as usize;
-    loop {
+    let mut data_pricer = data_cost_model.map(DataPricer::new);
+    // An operation popped from the queue is always submitted, so that a
+    // submission is never interrupted by shutting down
+    while !shutdown.is_triggered() {
+        if submission_paused.load(Ordering::Relaxed)
+            || chain_halts.is_halted(&domain)
+            || !leadership.is_leader()
+            || spend_limiter.is_capped()
+        {
+            // Leave operations in the submit queue until submission is resumed,
+            // or until this relayer becomes the leader
+            sleep(Duration::from_secs(1)).await;
+            continue;
+        }
         let mut batch = submit_queue.pop_many(recv_limit).await;
+        if !batch.is_empty() && !leadership.confirm().await {
+            // The leadership was lost since the elector last renewed it
+            for op in batch {
+                submit_queue.push(op, None).await;
+            }
+            sleep(Duration::from_secs(1)).await;
+            continue;
+        }
+        if let Some(lease) = &submission_lease {
+            batch = take_leases(lease.as_ref(), batch, &mut prepare_queue).await;
+        }
 
         match batch.len().cmp(&1) {
             std::cmp::Ordering::Less => {
@@ -334,12 +571,69 @@ async fn submit_task(
                 submit_single_operation(op, &mut prepare_queue, &mut confirm_queue, &metrics).await;
             }
             std::cmp::Ordering::Greater => {
-                OperationBatch::new(batch, domain.clone())
-                    .submit(&mut prepare_queue, &mut confirm_queue, &metrics)
+                let batch = OperationBatch::new(batch, domain.clone());
+                let is_cheaper = match &mut data_pricer {
+                    Some(pricer) => batch.is_cheaper_than_serial(pricer).await,
+                    None => true,
+                };
+                if is_cheaper {
+                    batch
+                        .submit(&mut prepare_queue, &mut confirm_queue, &metrics)
+                        .await;
+                } else {
+                    debug!(batch=?batch.operations, "Batch is estimated to cost more than serial submission, submitting serially");
+                    batch
+                        .submit_serially(&mut prepare_queue, &mut confirm_queue, &metrics)
+                        .await;
+                }
+            }
+        }
+    }
+}
+
+/// Take the submission leases of `batch`, returning the operations that may be
+/// submitted. Operations leased by another relayer are sent back to the prepare
+/// queue, and so are the ones whose lease couldn't be taken because of an
+/// error, since another relayer may be submitting them.
+async fn take_leases(
+    lease: &dyn SubmissionLease,
+    batch: Vec<QueueOperation>,
+    prepare_queue: &mut OpQueue,
+) -> Vec<QueueOperation> {
+    let mut leased = Vec::with_capacity(batch.len());
+    for mut op in batch {
+        match lease.try_acquire(&op.id()).await {
+            Ok(true) => leased.push(op),
+            Ok(false) => {
+                debug!(
+                    ?op,
+                    "Operation is leased by another relayer, retrying later"
+                );
+                op.set_next_attempt_after(LEASED_RETRY_DELAY);
+                prepare_queue
+                    .push(
+                        op,
+                        Some(PendingOperationStatus::Retry(
+                            ReprepareReason::LeasedByOtherRelayer,
+                        )),
+                    )
+                    .await;
+            }
+            Err(err) => {
+                warn!(?op, ?err, "Failed to take submission lease, retrying later");
+                op.set_next_attempt_after(LEASE_ERROR_RETRY_DELAY);
+                prepare_queue
+                    .push(
+                        op,
+                        Some(PendingOperationStatus::Retry(
+                            ReprepareReason::ErrorTakingSubmissionLease,
+                        )),
+                    )
                     .await;
             }
         }
     }
+    leased
 }
 
 #[instrument(skip(prepare_queue, confirm_queue, metrics), ret, level = "debug")]
@@ -408,40 +702,104 @@ async fn confirm_task(
     prepare_queue: OpQueue,
     mut confirm_queue: OpQueue,
     max_batch_size: u32,
+    submission_lease: Option<Arc<dyn SubmissionLease>>,
     metrics: SerialSubmitterMetrics,
+    shutdown: ShutdownSignal,
 ) {
-    let recv_limit = max_batch_size as usize;
-    loop {
-        // Pick the next message to try confirming.
-        let batch = confirm_queue.pop_many(recv_limit).await;
-
-        if batch.is_empty() {
+    while !shutdown.is_triggered() {
+        if !confirm_batch(
+            &domain,
+            &prepare_queue,
+            &mut confirm_queue,
+            max_batch_size,
+            submission_lease.as_deref(),
+            &metrics,
+        )
+        .await
+        {
             // queue is empty so give some time before checking again to prevent burning CPU
             sleep(Duration::from_millis(200)).await;
-            continue;
         }
+    }
+}
 
-        let futures = batch.into_iter().map(|op| {
-            confirm_operation(
-                op,
-                domain.clone(),
-                prepare_queue.clone(),
-                confirm_queue.clone(),
-                metrics.clone(),
-            )
-        });
-        let op_results = join_all(futures).await;
-        if op_results.iter().all(|op| {
-            matches!(
-                op,
-                PendingOperationResult::NotReady | PendingOperationResult::Confirm(_)
-            )
-        }) {
-            // None of the operations are ready, so wait for a little bit
-            // before checking again to prevent burning CPU
-            sleep(Duration::from_millis(500)).await;
+/// Confirm the operations submitted before shutting down, until none is left
+/// in the confirm queue
+async fn drain_confirm_queue(
+    domain: &HyperlaneDomain,
+    prepare_queue: &OpQueue,
+    mut confirm_queue: OpQueue,
+    max_batch_size: u32,
+    submission_lease: Option<&dyn SubmissionLease>,
+    metrics: &SerialSubmitterMetrics,
+) {
+    loop {
+        let mut ops = confirm_queue.pop_many(usize::MAX).await;
+        if ops.is_empty() {
+            return;
         }
+        while !ops.is_empty() {
+            let batch_size = ops.len().min(max_batch_size.max(1) as usize);
+            let futures = ops.drain(..batch_size).map(|mut op| {
+                // Operations are only confirmed `CONFIRM_DELAY` after their
+                // submission, which outlasts the shutdown, so check their
+                // delivery right away
+                op.set_next_attempt_after(Duration::ZERO);
+                confirm_operation(
+                    op,
+                    domain.clone(),
+                    prepare_queue.clone(),
+                    confirm_queue.clone(),
+                    submission_lease,
+                    metrics.clone(),
+                )
+            });
+            join_all(futures).await;
+        }
+        // Give the operations left unconfirmed some time before checking
+        // them again
+        sleep(Duration::from_millis(500)).await;
+    }
+}
+
+/// Try confirming the next batch of operations. Returns false if the confirm
+/// queue was empty.
+async fn confirm_batch(
+    domain: &HyperlaneDomain,
+    prepare_queue: &OpQueue,
+    confirm_queue: &mut OpQueue,
+    max_batch_size: u32,
+    submission_lease: Option<&dyn SubmissionLease>,
+    metrics: &SerialSubmitterMetrics,
+) -> bool {
+    // Pick the next message to try confirming.
+    let batch = confirm_queue.pop_many(max_batch_size as usize).await;
+    if batch.is_empty() {
+        return false;
     }
+
+    let futures = batch.into_iter().map(|op| {
+        confirm_operation(
+            op,
+            domain.clone(),
+            prepare_queue.clone(),
+            confirm_queue.clone(),
+            submission_lease,
+            metrics.clone(),
+        )
+    });
+    let op_results = join_all(futures).await;
+    if op_results.iter().all(|op| {
+        matches!(
+            op,
+            PendingOperationResult::NotReady | PendingOperationResult::Confirm(_)
+        )
+    }) {
+        // None of the operations are ready, so wait for a little bit
+        // before checking again to prevent burning CPU
+        sleep(Duration::from_millis(500)).await;
+    }
+    true
 }
 
 async fn confirm_operation(
@@ -449,11 +807,13 @@ async fn confirm_operation(
     domain: HyperlaneDomain,
     prepare_queue: OpQueue,
     confirm_queue: OpQueue,
+    submission_lease: Option<&dyn SubmissionLease>,
     metrics: SerialSubmitterMetrics,
 ) -> PendingOperationResult {
     trace!(?op, "Confirming operation");
     debug_assert_eq!(*op.destination_domain(), domain);
 
+    let id = op.id();
     let operation_result = op.confirm().await;
     match &operation_result {
         PendingOperationResult::Success => {
@@ -481,6 +841,17 @@ async fn confirm_operation(
             op.decrement_metric_if_exists();
         }
     }
+    if matches!(
+        operation_result,
+        PendingOperationResult::Success | PendingOperationResult::Drop
+    ) {
+        // No relayer needs to submit the operation anymore
+        if let Some(lease) = submission_lease {
+            if let Err(err) = lease.release(&id).await {
+                warn!(?id, ?err, "Failed to release submission lease");
+            }
+        }
+    }
     operation_result
 }
 
@@ -550,6 +921,40 @@ impl OperationBatch {
         }
     }
 
+    /// Whether submitting the operations as a batch is estimated to be cheaper
+    /// than submitting them one by one. Batches which can't be priced are
+    /// assumed to be cheaper, leaving it to batch submission to fall back.
+    async fn is_cheaper_than_serial(&self, pricer: &mut DataPricer) -> bool {
+        let Some(first) = self.operations.first().and_then(|op| op.try_batch().ok()) else {
+            return true;
+        };
+        let Some(pricing) = pricer.pricing(first.mailbox.provider().as_ref()).await else {
+            return true;
+        };
+        let deliveries = self
+            .operations
+            .iter()
+            .map(|op| {
+                let item = op.try_batch()?;
+                Ok(DeliveryCost {
+                    gas_limit: item.submission_data.gas_limit,
+                    calldata: item
+                        .mailbox
+                        .process_calldata(&item.data, &item.submission_data.metadata),
+                })
+            })
+            .collect::<ChainResult<Vec<_>>>();
+        match deliveries {
+            Ok(deliveries) => {
+                let batch_cost = batch_cost(&pricing, &deliveries);
+                let serial_cost = serial_cost(&pricing, &deliveries);
+                debug!(?batch_cost, ?serial_cost, "Estimated batch and serial cost");
+                batch_cost < serial_cost
+            }
+            Err(_) => true,
+        }
+    }
+
     #[instrument(skip(metrics), ret, level = "debug")]
     async fn try_submit_as_batch(
         &self,
@@ -621,3 +1026,49 @@ impl OperationBatch {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::KnownHyperlaneDomain;
+    use prometheus::Registry;
+
+    use super::*;
+    use crate::msg::op_queue::test::{dummy_metrics_and_label, MockPendingOperation};
+
+    #[tokio::test]
+    async fn test_drain_confirms_just_submitted_operations() {
+        let (queue_metrics, queue_metrics_label) = dummy_metrics_and_label();
+        let broadcaster = tokio::sync::broadcast::Sender::new(100);
+        let new_queue = || {
+            OpQueue::new(
+                queue_metrics.clone(),
+                queue_metrics_label.clone(),
+                Arc::new(Mutex::new(broadcaster.subscribe())),
+            )
+        };
+        let (prepare_queue, mut confirm_queue) = (new_queue(), new_queue());
+        let domain: HyperlaneDomain = KnownHyperlaneDomain::Arbitrum.into();
+        let core_metrics = CoreMetrics::new("dummy_relayer", 37584, Registry::new()).unwrap();
+        let metrics = SerialSubmitterMetrics::new(&core_metrics, &domain);
+
+        let op: QueueOperation = Box::new(MockPendingOperation::new(0, domain.clone()));
+        confirm_op(op, &mut confirm_queue, &metrics).await;
+
+        let drained = tokio::time::timeout(
+            Duration::from_secs(5),
+            drain_confirm_queue(
+                &domain,
+                &prepare_queue,
+                confirm_queue.clone(),
+                1,
+                None,
+                &metrics,
+            ),
+        )
+        .await;
+        assert!(drained.is_ok());
+        assert!(confirm_queue.pending_operations().await.is_empty());
+        assert!(prepare_queue.pending_operations().await.is_empty());
+        assert_eq!(metrics.ops_confirmed.get(), 1);
+    }
+}
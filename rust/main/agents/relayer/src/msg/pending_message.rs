@@ -1,9 +1,10 @@
 #![allow(clippy::clone_on_ref_ptr)] // TODO: `rustc` 1.80.1 clippy issue
 
 use std::{
+    collections::HashMap,
     fmt::{Debug, Formatter},
     sync::Arc,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use async_trait::async_trait;
@@ -15,17 +16,27 @@ use hyperlane_base::{
 };
 use hyperlane_core::{
     gas_used_by_operation, BatchItem, ChainCommunicationError, ChainResult, ConfirmReason,
-    HyperlaneChain, HyperlaneDomain, HyperlaneMessage, Mailbox, MessageSubmissionData,
-    PendingOperation, PendingOperationResult, PendingOperationStatus, ReprepareReason, TryBatchAs,
-    TxOutcome, H256, U256,
+    DeliveryStatus, HyperlaneChain, HyperlaneDomain, HyperlaneMessage, Mailbox,
+    MessageSubmissionData, PendingOperation, PendingOperationResult, PendingOperationStatus,
+    ReprepareReason, TryBatchAs, TxOutcome, H256, U256,
 };
 use prometheus::{IntCounter, IntGauge};
 use serde::Serialize;
+use strum::IntoEnumIterator;
 use tracing::{debug, error, info, info_span, instrument, trace, warn, Instrument};
 
 use super::{
-    gas_payment::{GasPaymentEnforcer, GasPolicyStatus},
+    decorator::{DeliveryDecorators, DeliveryParams},
+    failure::DeliveryFailureReason,
+    gas_limit_override::GasLimitOverrides,
+    gas_payment::{payment_priority, GasPaymentEnforcer, GasPolicyStatus},
+    ism_cache::RecipientIsmCache,
+    ism_verify_cache::IsmVerifyCache,
+    message_limits::MessageLimits,
     metadata::{BaseMetadataBuilder, MessageMetadataBuilder, MetadataBuilder},
+    self_relay::SelfRelayGracePeriods,
+    spend_limit::SpendLimiter,
+    trace::{MessageTraces, TraceEvent},
 };
 
 pub const CONFIRM_DELAY: Duration = if cfg!(any(test, feature = "test-utils")) {
@@ -36,11 +47,24 @@ pub const CONFIRM_DELAY: Duration = if cfg!(any(test, feature = "test-utils")) {
     Duration::from_secs(60 * 10)
 };
 
+/// How long to wait before checking again whether a delivery seen before it
+/// was finalized has been finalized
+pub const FINALITY_CHECK_DELAY: Duration = if cfg!(any(test, feature = "test-utils")) {
+    Duration::from_secs(1)
+} else {
+    Duration::from_secs(30)
+};
+
 /// The message context contains the links needed to submit a message. Each
 /// instance is for a unique origin -> destination pairing.
 pub struct MessageContext {
     /// Mailbox on the destination chain.
     pub destination_mailbox: Arc<dyn Mailbox>,
+    /// Caches the ISMs of recipients on the destination chain.
+    pub recipient_ism_cache: Arc<RecipientIsmCache>,
+    /// Caches the metadata ISMs on the destination chain rejected when dry
+    /// running their verification.
+    pub ism_verify_cache: Arc<IsmVerifyCache>,
     /// Origin chain database to verify gas payments.
     pub origin_db: HyperlaneRocksDB,
     /// Used to construct the ISM metadata needed to verify a message from the
@@ -52,6 +76,28 @@ pub struct MessageContext {
     /// Hard limit on transaction gas when submitting a transaction to the
     /// destination.
     pub transaction_gas_limit: Option<U256>,
+    /// Deliver with empty metadata instead of building it for the ISM of the
+    /// recipient. Only meant for destinations whose ISMs accept anything,
+    /// like the TestIsm of local and staging deployments.
+    pub skip_ism: bool,
+    /// Limits beyond which messages are dropped as abusive.
+    pub message_limits: Arc<MessageLimits>,
+    /// Adjusts the delivery of messages to recipients needing more than the
+    /// ISM metadata.
+    pub delivery_decorators: Arc<DeliveryDecorators>,
+    /// Raises the estimated gas limit of deliveries to recipients needing
+    /// more gas than estimated.
+    pub gas_limit_overrides: Arc<GasLimitOverrides>,
+    /// Limits on the fees spent delivering to the destination.
+    pub spend_limiter: Arc<SpendLimiter>,
+    /// Weight of the gas payments of messages from the origin when ordering
+    /// deliveries by payment, or None to not prioritize them by payment.
+    pub payment_priority_weight: Option<f64>,
+    /// Grace periods during which new messages are left to the apps relaying
+    /// their own messages.
+    pub self_relay_grace_periods: Arc<SelfRelayGracePeriods>,
+    /// Records what happens to messages, for the message status API.
+    pub traces: Arc<MessageTraces>,
     pub metrics: MessageSubmissionMetrics,
 }
 
@@ -85,6 +131,22 @@ pub struct PendingMessage {
     #[new(default)]
     #[serde(skip_serializing)]
     metric: Option<Arc<IntGauge>>,
+    #[new(default)]
+    #[serde(skip_serializing)]
+    payment_priority: u64,
+    /// Whether the message was already found within the message limits
+    #[new(default)]
+    #[serde(skip_serializing)]
+    within_message_limits: bool,
+    /// Whether the message was already checked to be past the grace period
+    /// of its app, if the app relays its own messages
+    #[new(default)]
+    #[serde(skip_serializing)]
+    self_relay_deferral_checked: bool,
+    /// Whether the delivery was already seen before being finalized
+    #[new(default)]
+    #[serde(skip_serializing)]
+    awaiting_finality: bool,
 }
 
 impl Debug for PendingMessage {
@@ -148,9 +210,9 @@ impl PendingOperation for PendingMessage {
         if let Err(e) = self
             .ctx
             .origin_db
-            .store_status_by_message_id(&self.message.id(), &self.status)
+            .store_status_by_message_id(&self.message.id(), &status)
         {
-            warn!(message_id = ?self.message.id(), err = %e, status = %self.status, "Persisting `status` failed for message");
+            warn!(message_id = ?self.message.id(), err = %e, %status, "Persisting `status` failed for message");
         }
         self.status = status;
     }
@@ -159,6 +221,10 @@ impl PendingOperation for PendingMessage {
         self.message.nonce
     }
 
+    fn payment_priority(&self) -> u64 {
+        self.payment_priority
+    }
+
     fn origin_domain_id(&self) -> u32 {
         self.message.origin
     }
@@ -188,6 +254,30 @@ impl PendingOperation for PendingMessage {
             return PendingOperationResult::NotReady;
         }
 
+        if self.is_orphaned() {
+            info!("Dropping message whose dispatch was orphaned by a reorg");
+            return PendingOperationResult::Drop;
+        }
+
+        if self.defer_self_relayed().await {
+            return PendingOperationResult::NotReady;
+        }
+
+        // Drop obviously abusive messages before doing any expensive work
+        match self.exceeded_message_limit().await {
+            Ok(None) => {}
+            Ok(Some(limit)) => {
+                info!(
+                    %limit,
+                    "Dropping message because it exceeds the message limits"
+                );
+                return PendingOperationResult::Drop;
+            }
+            Err(err) => {
+                return self.on_reprepare(Some(err), ReprepareReason::ErrorCheckingGasRequirement);
+            }
+        }
+
         // If the message has already been processed, e.g. due to another relayer having
         // already processed, then mark it as already-processed, and move on to
         // the next tick.
@@ -229,51 +319,39 @@ impl PendingOperation for PendingMessage {
             return PendingOperationResult::Drop;
         }
 
-        let ism_address = match self
-            .ctx
-            .destination_mailbox
-            .recipient_ism(self.message.recipient)
-            .await
-        {
-            Ok(ism_address) => ism_address,
-            Err(err) => {
-                return self.on_reprepare(Some(err), ReprepareReason::ErrorFetchingIsmAddress);
+        let metadata = if self.ctx.skip_ism {
+            trace!("Skipping the ISM of the destination, delivering with empty metadata");
+            vec![]
+        } else {
+            match self.build_ism_metadata().await {
+                Ok(metadata) => metadata,
+                Err(result) => return result,
             }
         };
 
-        let message_metadata_builder = match MessageMetadataBuilder::new(
-            ism_address,
-            &self.message,
-            self.ctx.metadata_builder.clone(),
-        )
-        .await
-        {
-            Ok(message_metadata_builder) => message_metadata_builder,
-            Err(err) => {
-                return self.on_reprepare(Some(err), ReprepareReason::ErrorGettingMetadataBuilder);
-            }
+        let mut delivery_params = DeliveryParams {
+            metadata,
+            extra_gas: U256::zero(),
         };
-
-        let metadata = match message_metadata_builder
-            .build(ism_address, &self.message)
+        if let Err(err) = self
+            .ctx
+            .delivery_decorators
+            .decorate(&self.message, &mut delivery_params)
             .await
         {
-            Ok(metadata) => metadata,
-            Err(err) => {
-                return self.on_reprepare(Some(err), ReprepareReason::ErrorBuildingMetadata);
-            }
-        };
-        self.metadata = metadata.clone();
-
-        let Some(metadata) = metadata else {
-            return self.on_reprepare::<String>(None, ReprepareReason::CouldNotFetchMetadata);
-        };
+            return self.on_reprepare(Some(err), ReprepareReason::ErrorDecoratingDelivery);
+        }
+        let DeliveryParams {
+            metadata,
+            extra_gas,
+        } = delivery_params;
+        self.metadata = Some(metadata.clone());
 
         // Estimate transaction costs for the process call. If there are issues, it's
         // likely that gas estimation has failed because the message is
         // reverting. This is defined behavior, so we just log the error and
         // move onto the next tick.
-        let tx_cost_estimate = match self
+        let mut tx_cost_estimate = match self
             .ctx
             .destination_mailbox
             .process_estimate_costs(&self.message, &metadata)
@@ -281,9 +359,27 @@ impl PendingOperation for PendingMessage {
         {
             Ok(tx_cost_estimate) => tx_cost_estimate,
             Err(err) => {
+                // The recipient may have changed its ISM since it was cached
+                self.ctx
+                    .recipient_ism_cache
+                    .invalidate(&self.message.recipient)
+                    .await;
                 return self.on_reprepare(Some(err), ReprepareReason::ErrorEstimatingGas);
             }
         };
+        if let Some(gas_limit) = self
+            .ctx
+            .gas_limit_overrides
+            .apply(&self.message.recipient, tx_cost_estimate.gas_limit)
+        {
+            debug!(
+                estimated_gas_limit=?tx_cost_estimate.gas_limit,
+                ?gas_limit,
+                "Overriding estimated gas limit for recipient"
+            );
+            self.ctx.metrics.gas_limit_overrides.inc();
+            tx_cost_estimate.gas_limit = gas_limit;
+        }
 
         // If the gas payment requirement hasn't been met, move to the next tick.
         let gas_limit = match self
@@ -306,13 +402,14 @@ impl PendingOperation for PendingMessage {
                 return self
                     .on_reprepare::<String>(None, ReprepareReason::GasPaymentRequirementNotMet)
             }
-            GasPolicyStatus::PolicyMet(gas_limit) => gas_limit,
+            GasPolicyStatus::PolicyMet(gas_limit) => gas_limit.saturating_add(extra_gas),
         };
 
         // Go ahead and attempt processing of message to destination chain.
         debug!(
             ?gas_limit,
             ?tx_cost_estimate,
+            estimated_total_cost=?tx_cost_estimate.total_cost(),
             "Gas payment requirement met, ready to process message"
         );
 
@@ -323,6 +420,38 @@ impl PendingOperation for PendingMessage {
             }
         }
 
+        let estimated_fee = tx_cost_estimate.total_cost();
+        if self
+            .ctx
+            .spend_limiter
+            .exceeds_max_delivery_fee(&estimated_fee)
+        {
+            warn!(
+                ?estimated_fee,
+                "Delivery estimated fee exceeds max fee, not submitting"
+            );
+            return self.on_reprepare::<String>(None, ReprepareReason::ExceedsMaxDeliveryFee);
+        }
+
+        if let Some(weight) = self.ctx.payment_priority_weight {
+            match self
+                .ctx
+                .origin_gas_payment_enforcer
+                .current_payment(&self.message)
+            {
+                Ok(current_payment) => {
+                    self.payment_priority =
+                        payment_priority(current_payment.payment, gas_limit, weight);
+                }
+                Err(err) => {
+                    warn!(?err, "Error retrieving gas payment to prioritize message");
+                }
+            }
+        }
+
+        self.ctx
+            .traces
+            .record(self.id(), TraceEvent::Prepared { gas_limit });
         self.submission_data = Some(Box::new(MessageSubmissionData {
             metadata,
             gas_limit,
@@ -337,6 +466,11 @@ impl PendingOperation for PendingMessage {
             return PendingOperationResult::Success;
         }
 
+        if self.is_orphaned() {
+            info!("Dropping message whose dispatch was orphaned by a reorg");
+            return PendingOperationResult::Drop;
+        }
+
         let state = self
             .submission_data
             .clone()
@@ -344,14 +478,13 @@ impl PendingOperation for PendingMessage {
 
         // To avoid spending gas on a tx that will revert, dry-run just before submitting.
         if let Some(metadata) = self.metadata.as_ref() {
-            if self
+            if let Err(err) = self
                 .ctx
                 .destination_mailbox
                 .process_estimate_costs(&self.message, metadata)
                 .await
-                .is_err()
             {
-                return self.on_reprepare::<String>(None, ReprepareReason::ErrorEstimatingGas);
+                return self.on_reprepare(Some(err), ReprepareReason::ErrorEstimatingGas);
             }
         }
 
@@ -369,6 +502,7 @@ impl PendingOperation for PendingMessage {
             }
             Err(e) => {
                 error!(error=?e, "Error when processing message");
+                self.record_failure(&ReprepareReason::ErrorSubmitting, Some(&e));
                 return PendingOperationResult::Reprepare(ReprepareReason::ErrorSubmitting);
             }
         }
@@ -387,37 +521,53 @@ impl PendingOperation for PendingMessage {
             return PendingOperationResult::NotReady;
         }
 
-        let is_delivered = match self
+        let delivery_status = match self
             .ctx
             .destination_mailbox
-            .delivered(self.message.id())
+            .delivery_status(self.message.id())
             .await
         {
-            Ok(is_delivered) => is_delivered,
+            Ok(delivery_status) => delivery_status,
             Err(err) => {
                 return self.on_reconfirm(Some(err), "Error confirming message delivery");
             }
         };
 
-        if is_delivered {
-            if let Err(err) = self.record_message_process_success() {
-                return self
-                    .on_reconfirm(Some(err), "Error when recording message process success");
+        match delivery_status {
+            DeliveryStatus::Finalized => {
+                if let Err(err) = self.record_message_process_success() {
+                    return self
+                        .on_reconfirm(Some(err), "Error when recording message process success");
+                }
+                info!(
+                    submission=?self.submission_outcome,
+                    "Message successfully processed"
+                );
+                PendingOperationResult::Success
+            }
+            DeliveryStatus::Unfinalized => {
+                // Only commit and count the delivery once finalized, so that it
+                // is retried if the transaction gets dropped
+                if !self.awaiting_finality {
+                    info!(
+                        submission=?self.submission_outcome,
+                        "Message processed, awaiting finality"
+                    );
+                    self.awaiting_finality = true;
+                }
+                self.set_next_attempt_after(FINALITY_CHECK_DELAY);
+                PendingOperationResult::Confirm(ConfirmReason::AwaitingFinality)
+            }
+            DeliveryStatus::NotDelivered => {
+                let span = info_span!(
+                    "Error: Transaction attempting to process message either reverted or was reorged",
+                    tx_outcome=?self.submission_outcome,
+                    message_id=?self.message.id()
+                );
+                self.on_reprepare::<String>(None, ReprepareReason::RevertedOrReorged)
+                    .instrument(span)
+                    .into_inner()
             }
-            info!(
-                submission=?self.submission_outcome,
-                "Message successfully processed"
-            );
-            PendingOperationResult::Success
-        } else {
-            let span = info_span!(
-                "Error: Transaction attempting to process message either reverted or was reorged",
-                tx_outcome=?self.submission_outcome,
-                message_id=?self.message.id()
-            );
-            self.on_reprepare::<String>(None, ReprepareReason::RevertedOrReorged)
-                .instrument(span)
-                .into_inner()
         }
     }
 
@@ -454,6 +604,15 @@ impl PendingOperation for PendingMessage {
         {
             error!(error=?e, "Error when recording tx outcome");
         }
+        self.ctx.spend_limiter.record_spend(&operation_outcome);
+        self.ctx.traces.record(
+            self.id(),
+            TraceEvent::Submitted {
+                tx_hash: operation_outcome.transaction_id,
+                executed: operation_outcome.executed,
+                gas_used: operation_outcome.gas_used,
+            },
+        );
         // set the outcome in `Self` as well, for later logging
         self.set_submission_outcome(operation_outcome);
         debug!(
@@ -495,7 +654,8 @@ impl PendingOperation for PendingMessage {
 }
 
 impl PendingMessage {
-    /// Constructor that tries reading the retry count from the HyperlaneDB in order to recompute the `next_attempt_after`.
+    /// Constructor that tries reading the retry count and next attempt time from the HyperlaneDB in order to restore
+    /// the `next_attempt_after`, recomputing it from the retry count if the time wasn't persisted.
     /// In case of failure, behaves like `Self::new(...)`.
     pub fn from_persisted_retries(
         message: HyperlaneMessage,
@@ -515,18 +675,67 @@ impl PendingMessage {
             .retrieve_pending_message_retry_count_by_message_id(&pm.message.id())
         {
             Ok(Some(num_retries)) => {
-                let next_attempt_after = PendingMessage::calculate_msg_backoff(num_retries)
-                    .map(|dur| Instant::now() + dur);
+                let next_attempt_after = match pm
+                    .ctx
+                    .origin_db
+                    .retrieve_pending_message_next_attempt_by_message_id(&pm.message.id())
+                {
+                    Ok(Some(timestamp)) => Some(instant_from_unix_timestamp(timestamp)),
+                    _ => PendingMessage::calculate_msg_backoff(num_retries)
+                        .map(|dur| Instant::now() + dur),
+                };
                 pm.num_retries = num_retries;
                 pm.next_attempt_after = next_attempt_after;
+                // Messages were only persisted after any deferral
+                pm.self_relay_deferral_checked = true;
             }
+            // New messages are deferred on their first preparation
+            Ok(None) => {}
             r => {
+                pm.self_relay_deferral_checked = true;
                 trace!(message_id = ?pm.message.id(), result = ?r, "Failed to read retry count from HyperlaneDB for message.")
             }
         }
+        pm.ctx.traces.record(
+            pm.message.id(),
+            TraceEvent::Queued {
+                num_retries: pm.num_retries,
+            },
+        );
         pm
     }
 
+    /// Leave a new message to its app until the grace period since its
+    /// dispatch passes, if the app relays its own messages. Returns whether
+    /// the message was deferred. The deferral is persisted, so that restarts
+    /// don't extend it.
+    async fn defer_self_relayed(&mut self) -> bool {
+        if self.self_relay_deferral_checked {
+            return false;
+        }
+        self.self_relay_deferral_checked = true;
+        let Some(grace_period_end) = self
+            .ctx
+            .self_relay_grace_periods
+            .grace_period_end(&self.message, &self.ctx.origin_db)
+            .await
+        else {
+            return false;
+        };
+        let next_attempt_after = instant_from_unix_timestamp(grace_period_end);
+        if next_attempt_after <= Instant::now() {
+            return false;
+        }
+        debug!(
+            grace_period_end,
+            "Deferring message of an app relaying its own messages"
+        );
+        self.next_attempt_after = Some(next_attempt_after);
+        self.persist_retries();
+        self.persist_next_attempt();
+        true
+    }
+
     fn on_reprepare<E: Debug>(
         &mut self,
         err: Option<E>,
@@ -534,6 +743,7 @@ impl PendingMessage {
     ) -> PendingOperationResult {
         self.inc_attempts();
         self.submitted = false;
+        self.record_failure(&reason, err.as_ref());
         if let Some(e) = err {
             warn!(error = ?e, "Repreparing message: {}", reason.clone());
         } else {
@@ -542,6 +752,21 @@ impl PendingMessage {
         PendingOperationResult::Reprepare(reason)
     }
 
+    fn record_failure<E: Debug>(&self, reason: &ReprepareReason, err: Option<&E>) {
+        let err = err.map(|e| format!("{e:?}"));
+        if let Some(failure) = DeliveryFailureReason::classify(reason, err.as_deref()) {
+            self.ctx.metrics.inc_delivery_failure(failure);
+        }
+        self.ctx.traces.record(
+            self.id(),
+            TraceEvent::Failed {
+                reason: reason.to_string(),
+                error: err,
+                num_retries: self.num_retries,
+            },
+        );
+    }
+
     fn on_reconfirm<E: Debug>(&mut self, err: Option<E>, reason: &str) -> PendingOperationResult {
         self.inc_attempts();
         if let Some(e) = err {
@@ -558,6 +783,22 @@ impl PendingMessage {
             .unwrap_or(true)
     }
 
+    /// Whether the dispatch of the message was orphaned by a reorg, in which
+    /// case it's no longer the message stored for its nonce
+    fn is_orphaned(&self) -> bool {
+        match self
+            .ctx
+            .origin_db
+            .retrieve_message_id_by_nonce(&self.message.nonce)
+        {
+            Ok(id) => id != Some(self.message.id()),
+            Err(err) => {
+                warn!(?err, "Failed to check whether the message was orphaned");
+                false
+            }
+        }
+    }
+
     /// Record in HyperlaneDB and various metrics that this process has observed
     /// the successful processing of a message. An `Ok(())` value returned by
     /// this function is the 'commit' point in a message's lifetime for
@@ -569,14 +810,118 @@ impl PendingMessage {
         self.ctx
             .origin_db
             .store_processed_by_nonce(&self.message.nonce, &true)?;
+        self.ctx.traces.record(self.id(), TraceEvent::Delivered);
         self.ctx.metrics.update_nonce(&self.message);
         self.ctx.metrics.messages_processed.inc();
         Ok(())
     }
 
+    /// The limit the message exceeds, if any. Checked until the message is
+    /// found within the limits, which need an RPC to read its hook metadata.
+    async fn exceeded_message_limit(&mut self) -> Result<Option<String>> {
+        if self.within_message_limits {
+            return Ok(None);
+        }
+        let exceeded = self
+            .ctx
+            .message_limits
+            .exceeded_limit(&self.message, &self.ctx.origin_db)
+            .await?;
+        self.within_message_limits = exceeded.is_none();
+        Ok(exceeded)
+    }
+
+    /// Builds the metadata the ISM of the recipient verifies the message
+    /// with, and dry runs its verification. Errors with the result of the
+    /// preparation if the metadata can't be built or is rejected.
+    async fn build_ism_metadata(&mut self) -> Result<Vec<u8>, PendingOperationResult> {
+        let ism_address = match self
+            .ctx
+            .recipient_ism_cache
+            .get(self.message.recipient)
+            .await
+        {
+            Ok(ism_address) => ism_address,
+            Err(err) => {
+                return Err(self.on_reprepare(Some(err), ReprepareReason::ErrorFetchingIsmAddress));
+            }
+        };
+
+        let message_metadata_builder = match MessageMetadataBuilder::new(
+            ism_address,
+            &self.message,
+            self.ctx.metadata_builder.clone(),
+        )
+        .await
+        {
+            Ok(message_metadata_builder) => message_metadata_builder,
+            Err(err) => {
+                return Err(
+                    self.on_reprepare(Some(err), ReprepareReason::ErrorGettingMetadataBuilder)
+                );
+            }
+        };
+
+        let metadata = match message_metadata_builder
+            .build(ism_address, &self.message)
+            .await
+        {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                return Err(self.on_reprepare(Some(err), ReprepareReason::ErrorBuildingMetadata));
+            }
+        };
+        let Some(metadata) = metadata else {
+            self.metadata = None;
+            return Err(self.on_reprepare::<String>(None, ReprepareReason::CouldNotFetchMetadata));
+        };
+
+        // Dry run the ISM's verification before estimating the delivery, so
+        // that metadata the ISM rejects is told apart from the mailbox or
+        // recipient reverting
+        match self.dry_run_ism_verify(ism_address, &metadata).await {
+            Ok(true) => {}
+            Ok(false) => {
+                self.ctx
+                    .metadata_builder
+                    .invalidate_cached_metadata(&self.message.id())
+                    .await;
+                return Err(self.on_reprepare::<String>(None, ReprepareReason::IsmRejectedMetadata));
+            }
+            Err(err) => {
+                return Err(self.on_reprepare(Some(err), ReprepareReason::ErrorVerifyingMetadata));
+            }
+        }
+
+        Ok(metadata)
+    }
+
+    /// Whether the ISM verifies the metadata when dry running its
+    /// verification. Rejections are cached briefly, so that retrying with the
+    /// same metadata doesn't dry run it again.
+    async fn dry_run_ism_verify(&self, ism_address: H256, metadata: &[u8]) -> Result<bool> {
+        let message_id = self.message.id();
+        let cache = &self.ctx.ism_verify_cache;
+        if cache.is_rejected(ism_address, message_id, metadata).await {
+            debug!(?ism_address, "ISM recently rejected the same metadata");
+            return Ok(false);
+        }
+        let ism = self.ctx.metadata_builder.build_ism(ism_address).await?;
+        let verified = ism.dry_run_verify(&self.message, metadata).await?.is_some();
+        if !verified {
+            cache
+                .record_rejection(ism_address, message_id, metadata)
+                .await;
+        }
+        Ok(verified)
+    }
+
     fn reset_attempts(&mut self) {
+        // A retry request starts the backoff over, also after a restart
+        self.set_retries(0);
         self.next_attempt_after = None;
         self.last_attempted_at = Instant::now();
+        self.persist_next_attempt();
     }
 
     fn inc_attempts(&mut self) {
@@ -584,6 +929,7 @@ impl PendingMessage {
         self.last_attempted_at = Instant::now();
         self.next_attempt_after = PendingMessage::calculate_msg_backoff(self.num_retries)
             .map(|dur| self.last_attempted_at + dur);
+        self.persist_next_attempt();
     }
 
     fn set_retries(&mut self, retries: u32) {
@@ -601,6 +947,21 @@ impl PendingMessage {
         }
     }
 
+    /// Persist when the message is next retried, so that its backoff survives
+    /// relayer restarts
+    fn persist_next_attempt(&self) {
+        let timestamp = self
+            .next_attempt_after
+            .map_or(0, unix_timestamp_from_instant);
+        if let Err(e) = self
+            .ctx
+            .origin_db
+            .store_pending_message_next_attempt_by_message_id(&self.message.id(), &timestamp)
+        {
+            warn!(message_id = ?self.message.id(), err = %e, "Persisting the next attempt time failed for message");
+        }
+    }
+
     /// Get duration we should wait before re-attempting to deliver a message
     /// given the number of retries.
     /// `pub(crate)` for testing purposes
@@ -630,11 +991,31 @@ impl PendingMessage {
     }
 }
 
+/// The unix timestamp, in seconds, of `instant`
+fn unix_timestamp_from_instant(instant: Instant) -> u64 {
+    let now = SystemTime::now();
+    let at = match instant.checked_duration_since(Instant::now()) {
+        Some(until) => now + until,
+        None => now - Instant::now().duration_since(instant),
+    };
+    at.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// The instant of the unix `timestamp` in seconds, or now if it has passed
+fn instant_from_unix_timestamp(timestamp: u64) -> Instant {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    Instant::now() + Duration::from_secs(timestamp.saturating_sub(now))
+}
+
 #[derive(Debug)]
 pub struct MessageSubmissionMetrics {
     // Fields are public for testing purposes
     pub last_known_nonce: IntGauge,
     pub messages_processed: IntCounter,
+    pub delivery_failures: HashMap<DeliveryFailureReason, IntCounter>,
+    pub gas_limit_overrides: IntCounter,
 }
 
 impl MessageSubmissionMetrics {
@@ -654,6 +1035,25 @@ impl MessageSubmissionMetrics {
             messages_processed: metrics
                 .messages_processed_count()
                 .with_label_values(&[origin, destination]),
+            delivery_failures: DeliveryFailureReason::iter()
+                .map(|reason| {
+                    let counter = metrics.message_delivery_failures_count().with_label_values(&[
+                        origin,
+                        destination,
+                        reason.as_str(),
+                    ]);
+                    (reason, counter)
+                })
+                .collect(),
+            gas_limit_overrides: metrics
+                .gas_limit_overrides_count()
+                .with_label_values(&[origin, destination]),
+        }
+    }
+
+    fn inc_delivery_failure(&self, reason: DeliveryFailureReason) {
+        if let Some(counter) = self.delivery_failures.get(&reason) {
+            counter.inc();
         }
     }
 
@@ -17,21 +17,21 @@ use hyperlane_base::{
 use hyperlane_core::{HyperlaneDomain, HyperlaneMessage, QueueOperation};
 use prometheus::IntGauge;
 use tokio::sync::mpsc::UnboundedSender;
-use tracing::{debug, instrument, trace};
+use tracing::{debug, instrument, trace, warn};
 
-use super::{blacklist::AddressBlacklist, metadata::AppContextClassifier, pending_message::*};
+use super::{
+    filter::{MessageFilter, SkipReason},
+    metadata::AppContextClassifier,
+    pending_message::*,
+};
 use crate::{processor::ProcessorExt, settings::matching_list::MatchingList};
 
 /// Finds unprocessed messages from an origin and submits then through a channel
 /// for to the appropriate destination.
 #[allow(clippy::too_many_arguments)]
 pub struct MessageProcessor {
-    /// A matching list of messages that should be whitelisted.
-    message_whitelist: Arc<MatchingList>,
-    /// A matching list of messages that should be blacklisted.
-    message_blacklist: Arc<MatchingList>,
-    /// Addresses that messages may not interact with.
-    address_blacklist: Arc<AddressBlacklist>,
+    /// The filters deciding which messages are relayed
+    message_filter: MessageFilter,
     metrics: MessageProcessorMetrics,
     /// channel for each destination chain to send operations (i.e. message
     /// submissions) to
@@ -79,6 +79,16 @@ impl ForwardBackwardIterator {
         }
     }
 
+    /// Moves the forward iterator back to `nonce`, so that the messages indexed
+    /// after a reorg invalidated the ones previously stored from `nonce` on are
+    /// processed. The backward iterator goes through lower nonces anyway.
+    fn rewind_to(&mut self, nonce: u32) {
+        if self.high_nonce_iter.nonce.is_some_and(|high| nonce < high) {
+            debug!(nonce, iterator=?self, "Rewinding to the lowest invalidated nonce");
+            self.high_nonce_iter.nonce = Some(nonce);
+        }
+    }
+
     async fn try_get_next_message(
         &mut self,
         metrics: &MessageProcessorMetrics,
@@ -225,14 +235,18 @@ impl Debug for MessageProcessor {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "MessageProcessor {{ message_whitelist: {:?}, message_blacklist: {:?}, address_blacklist: {:?}, nonce_iterator: {:?}}}",
-            self.message_whitelist, self.message_blacklist, self.address_blacklist, self.nonce_iterator
+            "MessageProcessor {{ message_filter: {:?}, nonce_iterator: {:?}}}",
+            self.message_filter, self.nonce_iterator
         )
     }
 }
 
 #[async_trait]
 impl ProcessorExt for MessageProcessor {
+    fn name(&self) -> &'static str {
+        "message_processor"
+    }
+
     /// The domain this processor is getting messages from.
     fn domain(&self) -> &HyperlaneDomain {
         self.nonce_iterator.high_nonce_iter.db.domain()
@@ -254,27 +268,44 @@ impl ProcessorExt for MessageProcessor {
             );
             let destination = msg.destination;
 
-            // Skip if not whitelisted.
-            if !self.message_whitelist.msg_matches(&msg, true) {
-                debug!(?msg, whitelist=?self.message_whitelist, "Message not whitelisted, skipping");
-                return Ok(());
-            }
-
-            // Skip if the message is blacklisted
-            if self.message_blacklist.msg_matches(&msg, false) {
-                debug!(?msg, blacklist=?self.message_blacklist, "Message blacklisted, skipping");
-                return Ok(());
-            }
-
-            // Skip if the message involves a blacklisted address
-            if let Some(blacklisted_address) = self.address_blacklist.find_blacklisted_address(&msg)
-            {
-                debug!(
-                    ?msg,
-                    blacklisted_address = hex::encode(blacklisted_address),
-                    "Message involves blacklisted address, skipping"
-                );
-                return Ok(());
+            // Skip if the message is filtered out
+            match self.message_filter.skip_reason(&msg) {
+                None => {}
+                Some(SkipReason::NotWhitelisted) => {
+                    debug!(?msg, whitelist=?self.message_filter.message_whitelist, "Message not whitelisted, skipping");
+                    return Ok(());
+                }
+                Some(SkipReason::Blacklisted) => {
+                    debug!(?msg, blacklist=?self.message_filter.message_blacklist, "Message blacklisted, skipping");
+                    return Ok(());
+                }
+                Some(SkipReason::BlacklistedAddress(blacklisted_address)) => {
+                    debug!(
+                        ?msg,
+                        blacklisted_address = hex::encode(blacklisted_address),
+                        "Message involves blacklisted address, skipping"
+                    );
+                    return Ok(());
+                }
+                // Keep a record of messages involving an address denylisted by
+                // the screening service for audits
+                Some(SkipReason::DenylistedAddress(denylisted_address)) => {
+                    warn!(
+                        id = ?msg.id(),
+                        nonce = msg.nonce,
+                        origin = msg.origin,
+                        destination,
+                        sender = ?msg.sender,
+                        recipient = ?msg.recipient,
+                        denylisted_address = hex::encode(denylisted_address),
+                        "Refusing to relay message involving denylisted address"
+                    );
+                    return Ok(());
+                }
+                Some(SkipReason::OtherShard) => {
+                    debug!(?msg, shard=?self.message_filter.message_shard, "Message not in this relayer's shard, skipping");
+                    return Ok(());
+                }
             }
 
             // Skip if the message is intended for this origin
@@ -289,6 +320,21 @@ impl ProcessorExt for MessageProcessor {
                 return Ok(());
             }
 
+            // Skip if the destination mailbox can't process the message's
+            // version, e.g. a legacy message once the destination upgraded
+            let destination_ctx = self.destination_ctxs[&destination].clone();
+            let destination_versions = destination_ctx
+                .metadata_builder
+                .destination_message_versions();
+            if !destination_versions.supports(msg.version) {
+                warn!(
+                    ?msg,
+                    ?destination_versions,
+                    "Message version not supported by the destination, skipping"
+                );
+                return Ok(());
+            }
+
             debug!(%msg, "Sending message to submitter");
 
             let app_context_classifier =
@@ -296,11 +342,8 @@ impl ProcessorExt for MessageProcessor {
 
             let app_context = app_context_classifier.get_app_context(&msg).await?;
             // Finally, build the submit arg and dispatch it to the submitter.
-            let pending_msg = PendingMessage::from_persisted_retries(
-                msg,
-                self.destination_ctxs[&destination].clone(),
-                app_context,
-            );
+            let pending_msg =
+                PendingMessage::from_persisted_retries(msg, destination_ctx, app_context);
             self.send_channels[&destination].send(Box::new(pending_msg) as QueueOperation)?;
         } else {
             tokio::time::sleep(Duration::from_secs(1)).await;
@@ -310,21 +353,16 @@ impl ProcessorExt for MessageProcessor {
 }
 
 impl MessageProcessor {
-    #[allow(clippy::too_many_arguments)]
     pub fn new(
         db: HyperlaneRocksDB,
-        message_whitelist: Arc<MatchingList>,
-        message_blacklist: Arc<MatchingList>,
-        address_blacklist: Arc<AddressBlacklist>,
+        message_filter: MessageFilter,
         metrics: MessageProcessorMetrics,
         send_channels: HashMap<u32, UnboundedSender<QueueOperation>>,
         destination_ctxs: HashMap<u32, Arc<MessageContext>>,
         metric_app_contexts: Vec<(MatchingList, String)>,
     ) -> Self {
         Self {
-            message_whitelist,
-            message_blacklist,
-            address_blacklist,
+            message_filter,
             metrics,
             send_channels,
             destination_ctxs,
@@ -335,6 +373,14 @@ impl MessageProcessor {
 
     async fn try_get_unprocessed_message(&mut self) -> Result<Option<HyperlaneMessage>> {
         trace!(nonce_iterator=?self.nonce_iterator, "Trying to get the next processor message");
+        if let Some(nonce) = self
+            .nonce_iterator
+            .high_nonce_iter
+            .db
+            .take_lowest_invalidated_message_nonce()?
+        {
+            self.nonce_iterator.rewind_to(nonce);
+        }
         let next_message = self
             .nonce_iterator
             .try_get_next_message(&self.metrics)
@@ -390,6 +436,7 @@ mod test {
         merkle_tree::builder::MerkleTreeBuilder,
         msg::{
             gas_payment::GasPaymentEnforcer,
+            ism_cache::RecipientIsmCache,
             metadata::{BaseMetadataBuilder, IsmAwareAppContextClassifier},
         },
         processor::Processor,
@@ -405,7 +452,7 @@ mod test {
     };
     use hyperlane_core::{
         test_utils::dummy_domain, GasPaymentKey, InterchainGasPayment, InterchainGasPaymentMeta,
-        MerkleTreeInsertion, PendingOperationStatus, H256,
+        MerkleTreeInsertion, MessageVersion, PendingOperationStatus, H256,
     };
     use hyperlane_test::mocks::{MockMailboxContract, MockValidatorAnnounceContract};
     use prometheus::{IntCounter, Registry};
@@ -436,6 +483,8 @@ mod test {
         MessageSubmissionMetrics {
             last_known_nonce: IntGauge::new("last_known_nonce_gauge", "help string").unwrap(),
             messages_processed: IntCounter::new("message_processed_gauge", "help string").unwrap(),
+            delivery_failures: Default::default(),
+            gas_limit_overrides: IntCounter::new("gas_limit_overrides", "help string").unwrap(),
         }
     }
 
@@ -451,9 +500,21 @@ mod test {
                 },
                 transaction_overrides: Default::default(),
                 operation_batch: Default::default(),
+                revert_tracing: None,
+                finality_source: Default::default(),
+                requests_per_second: Default::default(),
+                broadcast_transactions: false,
+                processor: None,
+                rpc_headers: Default::default(),
+                proxy: None,
+                rpc_tls: None,
+                legacy_mailbox: false,
+                rpc_trace: None,
             }),
             metrics_conf: Default::default(),
             index: Default::default(),
+            code_hashes: Default::default(),
+            message_versions: Default::default(),
         }
     }
 
@@ -482,6 +543,7 @@ mod test {
             Arc::new(core_metrics),
             db.clone(),
             IsmAwareAppContextClassifier::new(Arc::new(MockMailboxContract::default()), vec![]),
+            None,
         )
     }
 
@@ -491,12 +553,28 @@ mod test {
         db: &HyperlaneRocksDB,
     ) -> (MessageProcessor, UnboundedReceiver<QueueOperation>) {
         let base_metadata_builder = dummy_metadata_builder(origin_domain, destination_domain, db);
+        let core_metrics = CoreMetrics::new("dummy_relayer", 37582, Registry::new()).unwrap();
+        let destination_mailbox = Arc::new(MockMailboxContract::default());
         let message_context = Arc::new(MessageContext {
-            destination_mailbox: Arc::new(MockMailboxContract::default()),
+            recipient_ism_cache: Arc::new(RecipientIsmCache::new(
+                destination_mailbox.clone(),
+                destination_domain,
+                &core_metrics,
+            )),
+            destination_mailbox,
             origin_db: db.clone(),
+            ism_verify_cache: Default::default(),
             metadata_builder: Arc::new(base_metadata_builder),
             origin_gas_payment_enforcer: Arc::new(GasPaymentEnforcer::new([], db.clone())),
             transaction_gas_limit: Default::default(),
+            skip_ism: false,
+            message_limits: Default::default(),
+            delivery_decorators: Default::default(),
+            gas_limit_overrides: Default::default(),
+            spend_limiter: Default::default(),
+            payment_priority_weight: None,
+            self_relay_grace_periods: Default::default(),
+            traces: Default::default(),
             metrics: dummy_submission_metrics(),
         });
 
@@ -505,8 +583,6 @@ mod test {
             MessageProcessor::new(
                 db.clone(),
                 Default::default(),
-                Default::default(),
-                Default::default(),
                 dummy_processor_metrics(origin_domain.id()),
                 HashMap::from([(destination_domain.id(), send_channel)]),
                 HashMap::from([(destination_domain.id(), message_context)]),
@@ -518,7 +594,7 @@ mod test {
 
     fn dummy_hyperlane_message(destination: &HyperlaneDomain, nonce: u32) -> HyperlaneMessage {
         HyperlaneMessage {
-            version: Default::default(),
+            version: MessageVersion::CURRENT.into(),
             nonce,
             // Origin must be different from the destination
             origin: destination.id() + 1,
@@ -700,6 +776,21 @@ mod test {
                 message_id: &H256,
             ) -> DbResult<Option<u32>>;
 
+            /// Store when a pending message is next retried, as a unix timestamp in
+            /// seconds, by its message id
+            fn store_pending_message_next_attempt_by_message_id(
+                &self,
+                message_id: &H256,
+                timestamp: &u64,
+            ) -> DbResult<()>;
+
+            /// Retrieve when a pending message is next retried, as a unix timestamp in
+            /// seconds, by its message id
+            fn retrieve_pending_message_next_attempt_by_message_id(
+                &self,
+                message_id: &H256,
+            ) -> DbResult<Option<u64>>;
+
             fn store_merkle_tree_insertion_by_leaf_index(
                 &self,
                 leaf_index: &u32,
@@ -737,6 +828,8 @@ mod test {
             /// Retrieve the nonce of the highest processed message we're aware of
             fn retrieve_highest_seen_message_nonce_number(&self) -> DbResult<Option<u32>>;
 
+            fn take_lowest_invalidated_message_nonce(&self) -> DbResult<Option<u32>>;
+
         }
     }
 
@@ -795,6 +888,85 @@ mod test {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_persisted_next_attempt_is_restored() {
+        test_utils::run_test_db(|db| async move {
+            let origin_domain = dummy_domain(0, "dummy_origin_domain");
+            let destination_domain = dummy_domain(1, "dummy_destination_domain");
+            let db = HyperlaneRocksDB::new(&origin_domain, db);
+
+            // A message that was retried 10 times, and is next retried in a minute
+            let message = dummy_hyperlane_message(&destination_domain, 0);
+            add_db_entry(&db, &message, 10);
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            db.store_pending_message_next_attempt_by_message_id(&message.id(), &(now + 60))
+                .unwrap();
+
+            let pending_messages =
+                get_first_n_operations_from_processor(&origin_domain, &destination_domain, &db, 1)
+                    .await;
+
+            // The persisted time takes precedence over the backoff of the retry count
+            let backoff = pending_messages[0]
+                .next_attempt_after()
+                .map(|instant| instant.duration_since(Instant::now()).as_secs());
+            assert!(matches!(backoff, Some(58..=60)), "{backoff:?}");
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_rewinds_to_invalidated_messages() {
+        test_utils::run_test_db(|db| async move {
+            let origin_domain = dummy_domain(0, "dummy_origin_domain");
+            let destination_domain = dummy_domain(1, "dummy_destination_domain");
+            let db = HyperlaneRocksDB::new(&origin_domain, db);
+            let messages = (0..3)
+                .map(|nonce| dummy_hyperlane_message(&destination_domain, nonce))
+                .collect::<Vec<_>>();
+            for message in &messages {
+                add_db_entry(&db, message, 0);
+            }
+            let (mut processor, _receive_channel) =
+                dummy_message_processor(&origin_domain, &destination_domain, &db);
+            let mut nonces = vec![];
+            while let Some(message) = processor.try_get_unprocessed_message().await.unwrap() {
+                nonces.push(message.nonce);
+            }
+            assert_eq!(nonces, vec![2, 1, 0]);
+
+            // A reorg replaces the messages dispatched from nonce 1 on
+            for message in &messages[1..] {
+                assert!(db.invalidate_message(message).unwrap());
+            }
+            let canonical_messages = (1..3)
+                .map(|nonce| HyperlaneMessage {
+                    body: vec![1],
+                    ..dummy_hyperlane_message(&destination_domain, nonce)
+                })
+                .collect::<Vec<_>>();
+            for message in &canonical_messages {
+                add_db_entry(&db, message, 0);
+            }
+
+            let mut ids = vec![];
+            while let Some(message) = processor.try_get_unprocessed_message().await.unwrap() {
+                ids.push(message.id());
+            }
+            assert_eq!(
+                ids,
+                canonical_messages
+                    .iter()
+                    .map(|m| m.id())
+                    .collect::<Vec<_>>()
+            );
+        })
+        .await;
+    }
+
     #[tokio::test]
     async fn test_forward_backward_iterator() {
         let mut mock_db = MockDb::new();
@@ -0,0 +1,215 @@
+//! Many apps relay their own messages. Rather than racing them and wasting
+//! the work of preparing deliveries they make anyway, the first attempt to
+//! deliver their messages is deferred until a grace period has passed since
+//! their dispatch, after which only the messages they left undelivered are
+//! delivered.
+
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use hyperlane_base::db::HyperlaneRocksDB;
+use hyperlane_core::{HyperlaneMessage, HyperlaneProvider};
+use tracing::warn;
+
+use crate::settings::SelfRelayConf;
+
+/// The grace periods of the apps relaying their own messages
+#[derive(Debug, Default)]
+pub struct SelfRelayGracePeriods {
+    rules: Vec<SelfRelayConf>,
+    /// The provider of the origin, to read the time messages were dispatched
+    /// at from their blocks
+    origin_provider: Option<Arc<dyn HyperlaneProvider>>,
+}
+
+impl SelfRelayGracePeriods {
+    pub fn new(
+        rules: Vec<SelfRelayConf>,
+        origin_provider: Option<Arc<dyn HyperlaneProvider>>,
+    ) -> Self {
+        Self {
+            rules,
+            origin_provider,
+        }
+    }
+
+    /// How long a message is left to its app after its dispatch, from the
+    /// first rule it matches, or `None` if its app doesn't relay its own
+    /// messages
+    pub fn grace_period(&self, message: &HyperlaneMessage) -> Option<Duration> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matching_list.msg_matches(message, false))
+            .map(|rule| rule.grace_period)
+    }
+
+    /// The unix timestamp in seconds the grace period of `message` ends at,
+    /// or `None` if its app doesn't relay its own messages
+    pub async fn grace_period_end(
+        &self,
+        message: &HyperlaneMessage,
+        origin_db: &HyperlaneRocksDB,
+    ) -> Option<u64> {
+        let grace_period = self.grace_period(message)?;
+        Some(self.dispatched_at(message, origin_db).await + grace_period.as_secs())
+    }
+
+    /// The timestamp of the block that dispatched `message`, falling back to
+    /// when it was indexed, or to now, if that block can't be read
+    async fn dispatched_at(&self, message: &HyperlaneMessage, origin_db: &HyperlaneRocksDB) -> u64 {
+        if let Some(provider) = &self.origin_provider {
+            match origin_db.retrieve_dispatch_block_hash_by_message_id(&message.id()) {
+                Ok(Some(block_hash)) => match provider.get_block_by_hash(&block_hash).await {
+                    Ok(block) => return block.timestamp,
+                    Err(err) => {
+                        warn!(
+                            ?err,
+                            ?block_hash,
+                            "Failed to read the dispatch block of message"
+                        )
+                    }
+                },
+                Ok(None) => {}
+                Err(err) => warn!(?err, "Failed to read the dispatch block hash of message"),
+            }
+        }
+        match origin_db.retrieve_message_indexed_at_by_nonce(message.nonce) {
+            Ok(Some(indexed_at)) => indexed_at,
+            _ => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::{
+        BlockInfo, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain, HyperlaneLogStore,
+        Indexed, LogMeta, TxnInfo, H256, U256,
+    };
+    use mockall::mock;
+
+    use super::*;
+
+    mock! {
+        pub Provider {}
+
+        impl Debug for Provider {
+            fn fmt<'a>(&self, f: &mut std::fmt::Formatter<'a>) -> std::fmt::Result;
+        }
+
+        impl HyperlaneChain for Provider {
+            fn domain(&self) -> &HyperlaneDomain;
+            fn provider(&self) -> Box<dyn HyperlaneProvider>;
+        }
+
+        #[async_trait]
+        impl HyperlaneProvider for Provider {
+            async fn get_block_by_hash(&self, hash: &H256) -> ChainResult<BlockInfo>;
+            async fn get_txn_by_hash(&self, hash: &H256) -> ChainResult<TxnInfo>;
+            async fn is_contract(&self, address: &H256) -> ChainResult<bool>;
+            async fn get_balance(&self, address: String) -> ChainResult<U256>;
+            async fn get_chain_metrics(&self) -> ChainResult<Option<ChainInfo>>;
+        }
+    }
+
+    #[test]
+    fn test_first_matching_rule_applies() {
+        let sender = H256::repeat_byte(0xaa);
+        let rule = |matching_list: &str, secs| SelfRelayConf {
+            matching_list: serde_json::from_str(matching_list).unwrap(),
+            grace_period: Duration::from_secs(secs),
+        };
+        let grace_periods = SelfRelayGracePeriods::new(
+            vec![
+                rule(&format!(r#"[{{"senderaddress": "{sender:?}"}}]"#), 60),
+                rule(r#"[{"origindomain": 1}]"#, 300),
+            ],
+            None,
+        );
+
+        let from_sender = HyperlaneMessage {
+            origin: 1,
+            sender,
+            ..Default::default()
+        };
+        let from_origin = HyperlaneMessage {
+            origin: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            grace_periods.grace_period(&from_sender),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            grace_periods.grace_period(&from_origin),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            grace_periods.grace_period(&HyperlaneMessage::default()),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_grace_period_ends_after_dispatch_block() {
+        test_utils::run_test_db(|db| async move {
+            let origin = HyperlaneDomain::new_test_domain("test_self_relay");
+            let db = HyperlaneRocksDB::new(&origin, db);
+            let block_hash = H256::repeat_byte(0xbb);
+            let message = HyperlaneMessage {
+                origin: 1,
+                ..Default::default()
+            };
+            let meta = LogMeta {
+                block_hash,
+                ..Default::default()
+            };
+            db.store_logs(&[(Indexed::new(message.clone()), meta)])
+                .await
+                .unwrap();
+
+            // The message was dispatched long before it was indexed, e.g.
+            // while backfilling, so its grace period already ended
+            let mut provider = MockProvider::new();
+            provider
+                .expect_get_block_by_hash()
+                .withf(move |hash| *hash == block_hash)
+                .returning(move |_| {
+                    Ok(BlockInfo {
+                        hash: block_hash,
+                        timestamp: 1_000,
+                        number: 1,
+                    })
+                });
+            let grace_periods = SelfRelayGracePeriods::new(
+                vec![SelfRelayConf {
+                    matching_list: serde_json::from_str(r#"[{"origindomain": 1}]"#).unwrap(),
+                    grace_period: Duration::from_secs(60),
+                }],
+                Some(Arc::new(provider)),
+            );
+
+            assert_eq!(
+                grace_periods.grace_period_end(&message, &db).await,
+                Some(1_060)
+            );
+            let other_origin = HyperlaneMessage {
+                origin: 2,
+                ..Default::default()
+            };
+            assert_eq!(
+                grace_periods.grace_period_end(&other_origin, &db).await,
+                None
+            );
+        })
+        .await;
+    }
+}
@@ -0,0 +1,59 @@
+use hyperlane_core::H256;
+
+/// The shard of messages a relayer instance processes. Messages are assigned
+/// to one of `count` shards by their id, so that relayers running the other
+/// shards of a route don't deliver the same messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageShard {
+    index: u32,
+    count: u32,
+}
+
+impl Default for MessageShard {
+    /// A single shard containing all messages
+    fn default() -> Self {
+        Self { index: 0, count: 1 }
+    }
+}
+
+impl MessageShard {
+    /// The shard at `index` out of `count`, which must be less than `count`
+    pub fn new(index: u32, count: u32) -> Self {
+        debug_assert!(index < count, "shard index {index} out of {count} shards");
+        Self { index, count }
+    }
+
+    /// Whether the message with `id` belongs to this shard
+    pub fn contains(&self, id: &H256) -> bool {
+        if self.count <= 1 {
+            return true;
+        }
+        // Message ids are keccak hashes, so their low bytes are evenly distributed
+        let low_bytes = u64::from_be_bytes(id.as_bytes()[24..].try_into().unwrap());
+        low_bytes % u64::from(self.count) == u64::from(self.index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_each_message_is_in_one_shard() {
+        let shards = (0..3).map(|i| MessageShard::new(i, 3)).collect::<Vec<_>>();
+        for byte in 0..=u8::MAX {
+            let id = H256::repeat_byte(byte);
+            let containing = shards.iter().filter(|s| s.contains(&id)).count();
+            assert_eq!(containing, 1);
+            assert!(MessageShard::default().contains(&id));
+        }
+    }
+
+    #[test]
+    fn test_shard_of_message_id() {
+        let id = H256::from_low_u64_be(7);
+        assert!(MessageShard::new(1, 3).contains(&id));
+        assert!(!MessageShard::new(0, 3).contains(&id));
+        assert!(!MessageShard::new(2, 3).contains(&id));
+    }
+}
@@ -0,0 +1,165 @@
+//! Spend limits keep a misbehaving gas oracle, a spike in gas prices or a
+//! flood of expensive messages from draining the relayer signer. Deliveries
+//! estimated to cost more than a ceiling aren't submitted, and submission to
+//! a destination is paused while the fees spent there over the last hour
+//! exceed a cap. Both limits can be lifted for a while through the API.
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use eyre::{eyre, Result};
+use hyperlane_core::{FixedPointNumber, TxOutcome, U256};
+use tracing::{info, warn};
+
+use crate::settings::SpendLimitsConf;
+
+/// The window over which spend is capped
+const SPEND_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// The spend limits of a destination chain
+#[derive(Debug, Default)]
+pub struct SpendLimiter {
+    max_delivery_fee: Option<FixedPointNumber>,
+    hourly_cap: Option<FixedPointNumber>,
+    /// Fees paid over the last hour, oldest first
+    spent: Mutex<VecDeque<(Instant, FixedPointNumber)>>,
+    /// Until when the limits are lifted
+    lifted_until: Mutex<Option<Instant>>,
+    capped: AtomicBool,
+}
+
+impl SpendLimiter {
+    /// Build the limits configured for a destination chain
+    pub fn new(conf: &SpendLimitsConf) -> Self {
+        let to_fixed = |amount: Option<U256>| amount.and_then(|a| a.try_into().ok());
+        Self {
+            max_delivery_fee: to_fixed(conf.max_delivery_fee),
+            hourly_cap: to_fixed(conf.hourly_cap),
+            ..Default::default()
+        }
+    }
+
+    fn is_lifted(&self) -> bool {
+        self.lifted_until
+            .lock()
+            .unwrap()
+            .map_or(false, |until| Instant::now() < until)
+    }
+
+    /// Whether a delivery estimated to cost `fee` may not be submitted
+    pub fn exceeds_max_delivery_fee(&self, fee: &FixedPointNumber) -> bool {
+        self.max_delivery_fee
+            .as_ref()
+            .map_or(false, |max| fee > max && !self.is_lifted())
+    }
+
+    /// Count the fee paid by a delivery towards the hourly cap
+    pub fn record_spend(&self, outcome: &TxOutcome) {
+        if self.hourly_cap.is_none() {
+            return;
+        }
+        let Ok(gas_used) = FixedPointNumber::try_from(outcome.gas_used) else {
+            return;
+        };
+        let fee = gas_used * outcome.gas_price.clone();
+        self.spent.lock().unwrap().push_back((Instant::now(), fee));
+    }
+
+    /// The fees paid over the last hour
+    pub fn spent_last_hour(&self) -> FixedPointNumber {
+        let mut spent = self.spent.lock().unwrap();
+        while let Some((at, _)) = spent.front() {
+            if at.elapsed() < SPEND_WINDOW {
+                break;
+            }
+            spent.pop_front();
+        }
+        spent
+            .iter()
+            .fold(FixedPointNumber::zero(), |acc, (_, fee)| acc + fee.clone())
+    }
+
+    /// Whether submission is paused because the hourly cap is exceeded
+    pub fn is_capped(&self) -> bool {
+        let Some(cap) = &self.hourly_cap else {
+            return false;
+        };
+        let spent = self.spent_last_hour();
+        let capped = &spent >= cap && !self.is_lifted();
+        match (self.capped.swap(capped, Ordering::Relaxed), capped) {
+            (false, true) => {
+                warn!(
+                    ?spent,
+                    ?cap,
+                    "Pausing submission, hourly spend cap exceeded"
+                )
+            }
+            (true, false) => info!(?spent, ?cap, "Resuming submission under hourly spend cap"),
+            _ => {}
+        }
+        capped
+    }
+
+    /// Lift the limits for `duration`, or restore them if it is zero.
+    /// Fails, leaving the limits as they are, if `duration` is too long to
+    /// represent its end.
+    pub fn lift_for(&self, duration: Duration) -> Result<()> {
+        let lifted_until = if duration.is_zero() {
+            None
+        } else {
+            let until = Instant::now()
+                .checked_add(duration)
+                .ok_or_else(|| eyre!("Duration of {}s is too long", duration.as_secs()))?;
+            Some(until)
+        };
+        *self.lifted_until.lock().unwrap() = lifted_until;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::H512;
+
+    use super::*;
+
+    fn outcome(gas_used: u64, gas_price: u64) -> TxOutcome {
+        TxOutcome {
+            transaction_id: H512::zero(),
+            executed: true,
+            gas_used: gas_used.into(),
+            gas_price: U256::from(gas_price).try_into().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_hourly_cap_pauses_until_lifted() {
+        let limiter = SpendLimiter::new(&SpendLimitsConf {
+            max_delivery_fee: Some(1_000.into()),
+            hourly_cap: Some(2_000.into()),
+        });
+        let fee = |fee: u64| FixedPointNumber::try_from(U256::from(fee)).unwrap();
+        assert!(!limiter.exceeds_max_delivery_fee(&fee(1_000)));
+        assert!(limiter.exceeds_max_delivery_fee(&fee(1_001)));
+
+        limiter.record_spend(&outcome(100, 10));
+        assert!(!limiter.is_capped());
+        limiter.record_spend(&outcome(100, 10));
+        assert!(limiter.is_capped());
+
+        limiter.lift_for(Duration::from_secs(60)).unwrap();
+        assert!(!limiter.is_capped());
+        assert!(!limiter.exceeds_max_delivery_fee(&fee(1_001)));
+        limiter.lift_for(Duration::ZERO).unwrap();
+        assert!(limiter.is_capped());
+
+        assert!(limiter.lift_for(Duration::from_secs(u64::MAX)).is_err());
+        assert!(limiter.is_capped());
+    }
+}
@@ -0,0 +1,100 @@
+//! Submission leases keep redundant relayers from delivering the same message
+//! at once. Before submitting an operation, a relayer takes a lease on its id
+//! in a store shared by all instances. Operations leased by another instance
+//! are retried later, once that instance has released the lease or it has
+//! expired.
+
+use std::fmt::Debug;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use eyre::Result;
+use hyperlane_core::H256;
+use sea_orm::{ConnectionTrait, Database, DatabaseConnection, DbBackend, Statement};
+
+/// A store of leases on operation ids, shared by relayer instances
+#[async_trait]
+pub trait SubmissionLease: Debug + Send + Sync {
+    /// Take the lease on `id`, or renew it if this instance already holds it.
+    /// Returns false if another instance holds the lease.
+    async fn try_acquire(&self, id: &H256) -> Result<bool>;
+
+    /// Release the lease on `id` if this instance holds it
+    async fn release(&self, id: &H256) -> Result<()>;
+}
+
+const CREATE_LEASE_TABLE: &str = "CREATE TABLE IF NOT EXISTS relayer_submission_lease (
+    message_id BYTEA PRIMARY KEY,
+    holder TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL
+)";
+
+/// Inserts the lease, or takes it over if it is held by this instance or has
+/// expired. Returns no row if another instance holds the lease.
+const ACQUIRE_LEASE: &str = "INSERT INTO relayer_submission_lease (message_id, holder, expires_at)
+    VALUES ($1, $2, now() + $3 * interval '1 second')
+    ON CONFLICT (message_id) DO UPDATE
+        SET holder = EXCLUDED.holder, expires_at = EXCLUDED.expires_at
+        WHERE relayer_submission_lease.holder = EXCLUDED.holder
+            OR relayer_submission_lease.expires_at < now()
+    RETURNING holder";
+
+/// Deletes the lease if it is held by this instance
+const RELEASE_LEASE: &str =
+    "DELETE FROM relayer_submission_lease WHERE message_id = $1 AND holder = $2";
+
+/// Leases stored in a Postgres table. A lease taken before submitting is held
+/// until the delivery is confirmed or the operation dropped, and expires if
+/// the instance holding it stops.
+#[derive(Debug)]
+pub struct PostgresSubmissionLease {
+    db: DatabaseConnection,
+    /// Identifies this relayer instance. It is stable across restarts, so that
+    /// a restarted instance holds the leases it took before.
+    holder: String,
+    ttl: Duration,
+}
+
+impl PostgresSubmissionLease {
+    /// Connect to the database at `url`, creating the lease table if needed.
+    /// Leases are taken as `holder`.
+    pub async fn connect(url: &str, holder: String, ttl: Duration) -> Result<Self> {
+        let db = Database::connect(url).await?;
+        db.execute(Statement::from_string(
+            DbBackend::Postgres,
+            CREATE_LEASE_TABLE.to_owned(),
+        ))
+        .await?;
+        Ok(Self { db, holder, ttl })
+    }
+}
+
+#[async_trait]
+impl SubmissionLease for PostgresSubmissionLease {
+    async fn try_acquire(&self, id: &H256) -> Result<bool> {
+        let lease = self
+            .db
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                ACQUIRE_LEASE,
+                [
+                    id.as_bytes().to_vec().into(),
+                    self.holder.clone().into(),
+                    self.ttl.as_secs_f64().into(),
+                ],
+            ))
+            .await?;
+        Ok(lease.is_some())
+    }
+
+    async fn release(&self, id: &H256) -> Result<()> {
+        self.db
+            .execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                RELEASE_LEASE,
+                [id.as_bytes().to_vec().into(), self.holder.clone().into()],
+            ))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+//! Delivery traces record what happened to the messages the relayer handled
+//! since it started: when they were queued, each failed attempt and its
+//! reason, and the transactions they were submitted in. They are served with
+//! the persisted state of a message by the message status API, to answer
+//! "where is my message" without digging through logs.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use hyperlane_core::{H256, H512, U256};
+use serde::Serialize;
+
+/// How many messages are traced, those first traced longest ago being
+/// dropped
+const MAX_TRACED_MESSAGES: usize = 10_000;
+/// How many events are kept per message, the oldest being dropped
+const MAX_EVENTS_PER_MESSAGE: usize = 50;
+
+/// Something that happened to a message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TraceEvent {
+    /// The message was read from the database and queued for delivery
+    Queued { num_retries: u32 },
+    /// The metadata was built and the delivery is ready to be submitted
+    Prepared { gas_limit: U256 },
+    /// An attempt failed, and the message will be retried
+    Failed {
+        reason: String,
+        error: Option<String>,
+        num_retries: u32,
+    },
+    /// The delivery was included in a transaction, which may have reverted
+    Submitted {
+        tx_hash: H512,
+        executed: bool,
+        gas_used: U256,
+    },
+    /// The delivery was confirmed, either by us or by someone else
+    Delivered,
+}
+
+/// An event and when it happened
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceEntry {
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub event: TraceEvent,
+}
+
+#[derive(Debug, Default)]
+struct Traces {
+    by_message: HashMap<H256, VecDeque<TraceEntry>>,
+    /// Traced message ids, in the order they were first traced
+    order: VecDeque<H256>,
+}
+
+/// The delivery traces of recently handled messages, shared by all the
+/// pending messages and the API
+#[derive(Debug, Default)]
+pub struct MessageTraces {
+    traces: Mutex<Traces>,
+}
+
+impl MessageTraces {
+    /// Record `event` for the message with id `message_id`
+    pub fn record(&self, message_id: H256, event: TraceEvent) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        let mut traces = self.traces.lock().unwrap();
+        let Traces { by_message, order } = &mut *traces;
+
+        if !by_message.contains_key(&message_id) {
+            if order.len() >= MAX_TRACED_MESSAGES {
+                if let Some(dropped) = order.pop_front() {
+                    by_message.remove(&dropped);
+                }
+            }
+            order.push_back(message_id);
+        }
+
+        let entries = by_message.entry(message_id).or_default();
+        if entries.len() >= MAX_EVENTS_PER_MESSAGE {
+            entries.pop_front();
+        }
+        entries.push_back(TraceEntry { timestamp, event });
+    }
+
+    /// The recorded events of the message with id `message_id`, oldest first
+    pub fn get(&self, message_id: &H256) -> Vec<TraceEntry> {
+        self.traces
+            .lock()
+            .unwrap()
+            .by_message
+            .get(message_id)
+            .map(|entries| entries.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_traces_are_bounded() {
+        let traces = MessageTraces::default();
+        let first = H256::from_low_u64_be(0);
+        for _ in 0..MAX_EVENTS_PER_MESSAGE + 1 {
+            traces.record(first, TraceEvent::Queued { num_retries: 0 });
+        }
+        traces.record(first, TraceEvent::Delivered);
+        let events = traces.get(&first);
+        assert_eq!(events.len(), MAX_EVENTS_PER_MESSAGE);
+        assert_eq!(events.last().unwrap().event, TraceEvent::Delivered);
+
+        for i in 1..=MAX_TRACED_MESSAGES as u64 {
+            traces.record(H256::from_low_u64_be(i), TraceEvent::Delivered);
+        }
+        assert!(traces.get(&first).is_empty());
+        assert_eq!(traces.get(&H256::from_low_u64_be(1)).len(), 1);
+    }
+}
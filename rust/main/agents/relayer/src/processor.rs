@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use async_trait::async_trait;
 use derive_new::new;
 use eyre::Result;
+use hyperlane_base::spawn_named;
 use hyperlane_core::HyperlaneDomain;
 use tokio::task::JoinHandle;
 use tokio_metrics::TaskMonitor;
@@ -10,6 +11,9 @@ use tracing::{instrument, warn};
 
 #[async_trait]
 pub trait ProcessorExt: Send + Debug {
+    /// Name of the processor, used to name its task.
+    fn name(&self) -> &'static str;
+
     /// The domain this processor is getting messages from.
     fn domain(&self) -> &HyperlaneDomain;
 
@@ -27,9 +31,13 @@ pub struct Processor {
 impl Processor {
     pub fn spawn(self) -> JoinHandle<()> {
         let task_monitor = self.task_monitor.clone();
-        tokio::spawn(TaskMonitor::instrument(&task_monitor, async move {
-            self.main_loop().await
-        }))
+        let name = self.ticker.name();
+        let domain = self.ticker.domain().clone();
+        spawn_named(
+            name,
+            Some(&domain),
+            TaskMonitor::instrument(&task_monitor, async move { self.main_loop().await }),
+        )
     }
 
     #[instrument(ret, skip(self), level = "info", fields(domain=%self.ticker.domain()))]
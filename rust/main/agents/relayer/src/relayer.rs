@@ -1,29 +1,31 @@
 use std::{
     collections::{HashMap, HashSet},
     fmt::{Debug, Formatter},
-    sync::Arc,
+    sync::{atomic::AtomicBool, Arc},
+    time::Duration,
 };
 
 use async_trait::async_trait;
 use derive_more::AsRef;
-use eyre::Result;
+use eyre::{eyre, Result};
 use futures_util::future::try_join_all;
 use hyperlane_base::{
     broadcast::BroadcastMpscSender,
     db::{HyperlaneRocksDB, DB},
     metrics::{AgentMetrics, MetricsUpdater},
     settings::ChainConf,
-    AgentMetadata, BaseAgent, ChainMetrics, ContractSyncMetrics, ContractSyncer, CoreMetrics,
-    HyperlaneAgentCore, SyncOptions,
+    spawn_named, AgentMetadata, BaseAgent, ChainHaltMonitor, ChainHalts, ChainMetrics,
+    ContractSyncMetrics, ContractSyncer, CoreMetrics, HyperlaneAgentCore, ShutdownSignal,
+    SyncOptions, TaskSupervisor,
 };
 use hyperlane_core::{
-    HyperlaneDomain, HyperlaneMessage, InterchainGasPayment, MerkleTreeInsertion, QueueOperation,
-    H512, U256,
+    GasOracleConfigUpdate, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider,
+    InterchainGasPayment, MerkleTreeInsertion, QueueOperation, H512, U256,
 };
 use tokio::{
     sync::{
         broadcast::Sender as BroadcastSender,
-        mpsc::{self, Receiver as MpscReceiver, UnboundedSender},
+        mpsc::{self, UnboundedSender},
         RwLock,
     },
     task::JoinHandle,
@@ -32,17 +34,34 @@ use tokio_metrics::TaskMonitor;
 use tracing::{error, info, info_span, instrument::Instrumented, warn, Instrument};
 
 use crate::{
+    bootstrap::bootstrap_db,
+    funding_monitor::FundingMonitor,
+    gas_reconciliation::{GasLedger, GasReconciler, GasReconciliationMetrics},
+    leader_election::{build_leader_election, LeaderElector, Leadership},
     merkle_tree::builder::MerkleTreeBuilder,
+    message_backlog::{MessageBacklog, MessageBacklogMetrics},
     msg::{
         blacklist::AddressBlacklist,
+        decorator::DeliveryDecorators,
+        denylist::DenylistFetcher,
+        filter::MessageFilter,
+        gas_limit_override::GasLimitOverrides,
         gas_payment::GasPaymentEnforcer,
+        ism_cache::RecipientIsmCache,
+        ism_verify_cache::IsmVerifyCache,
+        message_limits::MessageLimits,
         metadata::{BaseMetadataBuilder, IsmAwareAppContextClassifier},
         op_submitter::{SerialSubmitter, SerialSubmitterMetrics},
         pending_message::{MessageContext, MessageSubmissionMetrics},
         processor::{MessageProcessor, MessageProcessorMetrics},
+        self_relay::SelfRelayGracePeriods,
+        shard::MessageShard,
+        spend_limit::SpendLimiter,
+        submission_lease::{PostgresSubmissionLease, SubmissionLease},
+        trace::MessageTraces,
     },
     server::{self as relayer_server, MessageRetryRequest},
-    settings::{matching_list::MatchingList, RelayerSettings},
+    settings::{matching_list::MatchingList, FundingThresholdsConf, RelayerSettings},
 };
 use crate::{
     merkle_tree::processor::{MerkleTreeProcessor, MerkleTreeProcessorMetrics},
@@ -66,26 +85,50 @@ pub struct Relayer {
     message_syncs: HashMap<HyperlaneDomain, Arc<dyn ContractSyncer<HyperlaneMessage>>>,
     interchain_gas_payment_syncs:
         HashMap<HyperlaneDomain, Arc<dyn ContractSyncer<InterchainGasPayment>>>,
+    /// Only built for the origins indexing gas payments, if a gas payment
+    /// enforcement policy quotes payments from the indexed IGP configs
+    gas_oracle_config_syncs:
+        HashMap<HyperlaneDomain, Arc<dyn ContractSyncer<GasOracleConfigUpdate>>>,
     /// Context data for each (origin, destination) chain pair a message can be
     /// sent between
     msg_ctxs: HashMap<ContextKey, Arc<MessageContext>>,
     prover_syncs: HashMap<HyperlaneDomain, Arc<RwLock<MerkleTreeBuilder>>>,
     merkle_tree_hook_syncs: HashMap<HyperlaneDomain, Arc<dyn ContractSyncer<MerkleTreeInsertion>>>,
+    db: DB,
     dbs: HashMap<HyperlaneDomain, HyperlaneRocksDB>,
     message_whitelist: Arc<MatchingList>,
     message_blacklist: Arc<MatchingList>,
     address_blacklist: Arc<AddressBlacklist>,
+    /// Fetches the address denylist periodically, if one is configured
+    denylist_fetcher: Option<DenylistFetcher>,
+    message_shard: MessageShard,
     transaction_gas_limit: Option<U256>,
     skip_transaction_gas_limit_for: HashSet<u32>,
     allow_local_checkpoint_syncers: bool,
     metric_app_contexts: Vec<(MatchingList, String)>,
+    funding_thresholds: HashMap<String, FundingThresholdsConf>,
+    pause_submission_on_low_balance: bool,
+    chain_halt_threshold: Option<Duration>,
+    /// The chains halted, whose indexing and submission are paused
+    chain_halts: ChainHalts,
+    submission_lease: Option<Arc<dyn SubmissionLease>>,
+    shutdown_timeout: Duration,
+    /// The gas paid for and spent on the messages of each origin chain
+    gas_ledgers: HashMap<HyperlaneDomain, Arc<GasLedger>>,
+    gas_reconciliation_epoch: Duration,
+    /// Limits on the fees spent delivering to each destination
+    spend_limiters: HashMap<HyperlaneDomain, Arc<SpendLimiter>>,
+    /// What happened to the messages handled since the relayer started
+    message_traces: Arc<MessageTraces>,
+    /// Elects the replica submitting operations, if there are standbys
+    leader_elector: Option<LeaderElector>,
+    /// Restarts the indexing tasks of a chain if they fail
+    supervisor: TaskSupervisor,
     core_metrics: Arc<CoreMetrics>,
     // TODO: decide whether to consolidate `agent_metrics` and `chain_metrics` into a single struct
     // or move them in `core_metrics`, like the validator metrics
     agent_metrics: AgentMetrics,
     chain_metrics: ChainMetrics,
-    /// Tokio console server
-    pub tokio_console_server: Option<console_subscriber::Server>,
 }
 
 impl Debug for Relayer {
@@ -118,22 +161,100 @@ impl BaseAgent for Relayer {
         core_metrics: Arc<CoreMetrics>,
         agent_metrics: AgentMetrics,
         chain_metrics: ChainMetrics,
-        tokio_console_server: console_subscriber::Server,
     ) -> Result<Self>
     where
         Self: Sized,
     {
         let core = settings.build_hyperlane_core(core_metrics.clone());
         let db = DB::from_path(&settings.db)?;
+        let submission_lease: Option<Arc<dyn SubmissionLease>> = match &settings.submission_lease {
+            Some(conf) => {
+                let holder = conf
+                    .holder
+                    .clone()
+                    .or_else(|| std::env::var("HOSTNAME").ok())
+                    .ok_or_else(|| {
+                        eyre!("Set `submissionLeaseHolder` to identify this relayer in the leases")
+                    })?;
+                Some(Arc::new(
+                    PostgresSubmissionLease::connect(&conf.url, holder, conf.ttl).await?,
+                ))
+            }
+            None => None,
+        };
+        let leader_elector = match &settings.leader_election {
+            Some(conf) => Some(LeaderElector::new(
+                build_leader_election(conf).await?,
+                conf.lease_duration,
+                core_metrics
+                    .new_int_gauge(
+                        "relayer_is_leader",
+                        "Whether this relayer replica is the leader submitting operations",
+                        &[],
+                    )?
+                    .with_label_values(&[]),
+            )),
+            None => None,
+        };
         let dbs = settings
             .origin_chains
             .iter()
             .map(|origin| (origin.clone(), HyperlaneRocksDB::new(origin, db.clone())))
             .collect::<HashMap<_, _>>();
+        if let Some(source) = &settings.bootstrap_snapshot {
+            for (origin, db) in &dbs {
+                let mailbox = settings.chain_setup(origin)?.addresses.mailbox;
+                if let Err(err) =
+                    bootstrap_db(source, db, mailbox, core.settings.proxy.as_ref()).await
+                {
+                    warn!(
+                        ?err,
+                        %origin,
+                        "Failed to bootstrap database from snapshot, indexing it instead"
+                    );
+                }
+            }
+        }
 
         let mailboxes = settings
             .build_mailboxes(settings.destination_chains.iter(), &core_metrics)
             .await?;
+        // Reading the hook metadata and dispatch time of messages needs the
+        // providers of their origins
+        let mut origin_providers: HashMap<_, Arc<dyn HyperlaneProvider>> = HashMap::new();
+        if settings.message_limits.max_gas_limit.is_some()
+            || !settings.self_relay_grace_periods.is_empty()
+        {
+            for origin in &settings.origin_chains {
+                let provider = settings.build_provider(origin, &core_metrics).await?;
+                origin_providers.insert(origin.clone(), provider.into());
+            }
+        }
+        let mut message_limits = HashMap::new();
+        let mut self_relay_grace_periods = HashMap::new();
+        for origin in &settings.origin_chains {
+            let origin_provider = origin_providers.get(origin).cloned();
+            let mailbox = settings.chain_setup(origin)?.addresses.mailbox;
+            let hook_metadata_source = settings
+                .message_limits
+                .max_gas_limit
+                .and(origin_provider.clone())
+                .map(|provider| (provider, mailbox));
+            message_limits.insert(
+                origin.clone(),
+                Arc::new(MessageLimits::new(
+                    settings.message_limits,
+                    hook_metadata_source,
+                )),
+            );
+            self_relay_grace_periods.insert(
+                origin.clone(),
+                Arc::new(SelfRelayGracePeriods::new(
+                    settings.self_relay_grace_periods.clone(),
+                    origin_provider,
+                )),
+            );
+        }
         let validator_announces = settings
             .build_validator_announces(settings.origin_chains.iter(), &core_metrics)
             .await?;
@@ -156,7 +277,10 @@ impl BaseAgent for Relayer {
 
         let interchain_gas_payment_syncs = settings
             .contract_syncs::<InterchainGasPayment, _>(
-                settings.origin_chains.iter(),
+                settings
+                    .origin_chains
+                    .iter()
+                    .filter(|origin| settings.chain_roles(origin).index_gas_payments),
                 &core_metrics,
                 &contract_sync_metrics,
                 dbs.iter()
@@ -168,6 +292,45 @@ impl BaseAgent for Relayer {
             .map(|(k, v)| (k, v as _))
             .collect();
 
+        let mut gas_oracle_config_syncs = HashMap::new();
+        if settings
+            .gas_payment_enforcement
+            .iter()
+            .any(|conf| conf.policy.uses_gas_oracle_configs())
+        {
+            for origin in settings
+                .origin_chains
+                .iter()
+                .filter(|origin| settings.chain_roles(origin).index_gas_payments)
+            {
+                // The gas oracles configured before the sync resumes
+                let gas_oracles = settings
+                    .destination_chains
+                    .iter()
+                    .filter_map(|destination| {
+                        dbs[origin].retrieve_gas_oracle(destination.id()).ok()?
+                    })
+                    .collect();
+                match settings
+                    .gas_oracle_config_sync(
+                        origin,
+                        gas_oracles,
+                        &core_metrics,
+                        &contract_sync_metrics,
+                        Arc::new(dbs[origin].clone()),
+                    )
+                    .await
+                {
+                    Ok(sync) => {
+                        gas_oracle_config_syncs.insert(origin.clone(), sync as _);
+                    }
+                    Err(err) => {
+                        warn!(%origin, ?err, "Failed to build gas oracle config sync");
+                    }
+                }
+            }
+        }
+
         let merkle_tree_hook_syncs = settings
             .contract_syncs::<MerkleTreeInsertion, _>(
                 settings.origin_chains.iter(),
@@ -185,6 +348,26 @@ impl BaseAgent for Relayer {
         let message_whitelist = Arc::new(settings.whitelist);
         let message_blacklist = Arc::new(settings.blacklist);
         let address_blacklist = Arc::new(AddressBlacklist::new(settings.address_blacklist));
+        let denylist_fetcher = match settings.address_denylist {
+            Some(conf) => {
+                let fetcher = DenylistFetcher::new(
+                    conf,
+                    address_blacklist.clone(),
+                    core_metrics
+                        .new_int_gauge(
+                            "relayer_denylisted_addresses",
+                            "Number of addresses in the last fetched address denylist",
+                            &[],
+                        )?
+                        .with_label_values(&[]),
+                    core.settings.proxy.as_ref(),
+                )?;
+                // Don't relay anything before the denylist is known
+                fetcher.refresh().await?;
+                Some(fetcher)
+            }
+            None => None,
+        };
         let skip_transaction_gas_limit_for = settings.skip_transaction_gas_limit_for;
         let transaction_gas_limit = settings.transaction_gas_limit;
 
@@ -226,8 +409,14 @@ impl BaseAgent for Relayer {
                 )
             })
             .collect();
+        let gas_ledgers = gas_payment_enforcers
+            .iter()
+            .map(|(origin, enforcer)| (origin.clone(), enforcer.gas_ledger()))
+            .collect();
 
         let mut msg_ctxs = HashMap::new();
+        let mut spend_limiters = HashMap::new();
+        let message_traces = Arc::new(MessageTraces::default());
         let mut destination_chains = HashMap::new();
         for destination in &settings.destination_chains {
             let destination_chain_setup = core.settings.chain_setup(destination).unwrap().clone();
@@ -238,6 +427,35 @@ impl BaseAgent for Relayer {
                 } else {
                     transaction_gas_limit
                 };
+            let delivery_decorators = Arc::new(DeliveryDecorators::new(
+                settings
+                    .delivery_decorators
+                    .get(destination.name())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default(),
+                core.settings.proxy.as_ref(),
+            ));
+            let recipient_ism_cache = Arc::new(RecipientIsmCache::new(
+                mailboxes[destination].clone(),
+                destination,
+                &core_metrics,
+            ));
+            let ism_verify_cache = Arc::new(IsmVerifyCache::default());
+            let gas_limit_overrides = Arc::new(GasLimitOverrides::new(
+                settings
+                    .gas_limit_overrides
+                    .get(destination.name())
+                    .cloned()
+                    .unwrap_or_default(),
+            ));
+            let spend_limiter = Arc::new(SpendLimiter::new(
+                &settings
+                    .spend_limits
+                    .get(destination.name())
+                    .cloned()
+                    .unwrap_or_default(),
+            ));
+            spend_limiters.insert(destination.clone(), spend_limiter.clone());
 
             for origin in &settings.origin_chains {
                 let db = dbs.get(origin).unwrap().clone();
@@ -253,6 +471,7 @@ impl BaseAgent for Relayer {
                         mailboxes[destination].clone(),
                         settings.metric_app_contexts.clone(),
                     ),
+                    core.settings.proxy.clone(),
                 );
 
                 msg_ctxs.insert(
@@ -262,10 +481,23 @@ impl BaseAgent for Relayer {
                     },
                     Arc::new(MessageContext {
                         destination_mailbox: mailboxes[destination].clone(),
+                        recipient_ism_cache: recipient_ism_cache.clone(),
+                        ism_verify_cache: ism_verify_cache.clone(),
                         origin_db: dbs.get(origin).unwrap().clone(),
                         metadata_builder: Arc::new(metadata_builder),
                         origin_gas_payment_enforcer: gas_payment_enforcers[origin].clone(),
                         transaction_gas_limit,
+                        skip_ism: settings.skip_ism_for.contains(&destination.id()),
+                        message_limits: message_limits[origin].clone(),
+                        delivery_decorators: delivery_decorators.clone(),
+                        gas_limit_overrides: gas_limit_overrides.clone(),
+                        spend_limiter: spend_limiter.clone(),
+                        payment_priority_weight: settings
+                            .payment_priority_weights
+                            .get(origin.name())
+                            .copied(),
+                        self_relay_grace_periods: self_relay_grace_periods[origin].clone(),
+                        traces: message_traces.clone(),
                         metrics: MessageSubmissionMetrics::new(&core_metrics, origin, destination),
                     }),
                 );
@@ -273,6 +505,7 @@ impl BaseAgent for Relayer {
         }
 
         Ok(Self {
+            db,
             dbs,
             origin_chains: settings.origin_chains,
             destination_chains,
@@ -280,44 +513,76 @@ impl BaseAgent for Relayer {
             core,
             message_syncs,
             interchain_gas_payment_syncs,
+            gas_oracle_config_syncs,
             prover_syncs,
             merkle_tree_hook_syncs,
             message_whitelist,
             message_blacklist,
             address_blacklist,
+            denylist_fetcher,
+            message_shard: MessageShard::new(settings.shard_index, settings.shard_count),
             transaction_gas_limit,
             skip_transaction_gas_limit_for,
             allow_local_checkpoint_syncers: settings.allow_local_checkpoint_syncers,
             metric_app_contexts: settings.metric_app_contexts,
+            funding_thresholds: settings.funding_thresholds,
+            pause_submission_on_low_balance: settings.pause_submission_on_low_balance,
+            chain_halt_threshold: settings.chain_halt_threshold,
+            chain_halts: ChainHalts::default(),
+            submission_lease,
+            shutdown_timeout: settings.shutdown_timeout,
+            gas_ledgers,
+            gas_reconciliation_epoch: settings.gas_reconciliation_epoch,
+            spend_limiters,
+            message_traces,
+            leader_elector,
+            supervisor: TaskSupervisor::new(&core_metrics),
             core_metrics,
             agent_metrics,
             chain_metrics,
-            tokio_console_server: Some(tokio_console_server),
         })
     }
 
     #[allow(clippy::async_yields_async)]
-    async fn run(mut self) {
+    async fn run(self) {
+        self.run_until_shutdown(ShutdownSignal::default()).await
+    }
+
+    /// On shutdown, the submitters stop taking new operations and wait for
+    /// the operations they submitted to be confirmed, then the database is
+    /// flushed. Indexing and processing stop when the agent exits.
+    async fn run_until_shutdown(mut self, shutdown: ShutdownSignal) {
         let mut tasks = vec![];
+        let mut submitter_tasks = vec![];
 
         let task_monitor = tokio_metrics::TaskMonitor::new();
-        if let Some(tokio_console_server) = self.tokio_console_server.take() {
-            let console_server =
-                tokio::spawn(TaskMonitor::instrument(&task_monitor.clone(), async move {
-                    info!("Starting tokio console server");
-                    if let Err(e) = tokio_console_server.serve().await {
-                        error!(error=?e, "Tokio console server failed to start");
-                    }
-                }));
-            tasks.push(console_server.instrument(info_span!("Tokio console server")));
-        }
         let sender = BroadcastSender::<MessageRetryRequest>::new(ENDPOINT_MESSAGES_QUEUE_SIZE);
         // send channels by destination chain
         let mut send_channels = HashMap::with_capacity(self.destination_chains.len());
         let mut prep_queues = HashMap::with_capacity(self.destination_chains.len());
+        let funding_status = self
+            .core_metrics
+            .new_int_gauge(
+                "relayer_funding_status",
+                "Funding status of the relayer signer: 0 = healthy, 1 = warning, 2 = critical, 3 = insufficient for a delivery",
+                &["chain"],
+            )
+            .expect("Failed to register funding status metric");
+        let notifier = self.core.settings.notifier();
+        let leadership = match &self.leader_elector {
+            Some(elector) => {
+                tasks.push(elector.clone().spawn());
+                elector.leadership()
+            }
+            None => Leadership::default(),
+        };
+        if let Some(fetcher) = self.denylist_fetcher.take() {
+            tasks.push(fetcher.spawn());
+        }
         for (dest_domain, dest_conf) in &self.destination_chains {
             let (send_channel, receive_channel) = mpsc::unbounded_channel::<QueueOperation>();
             send_channels.insert(dest_domain.id(), send_channel);
+            let submission_paused = Arc::new(AtomicBool::new(false));
             let serial_submitter = SerialSubmitter::new(
                 dest_domain.clone(),
                 receive_channel,
@@ -329,11 +594,21 @@ impl BaseAgent for Relayer {
                     .operation_batch_config()
                     .map(|c| c.max_batch_size)
                     .unwrap_or(1),
+                self.core.settings.chains[dest_domain.name()]
+                    .connection
+                    .operation_batch_config()
+                    .and_then(|c| c.data_cost_model),
+                self.submission_lease.clone(),
+                submission_paused.clone(),
                 task_monitor.clone(),
-            );
+            )
+            .with_shutdown(shutdown.clone(), self.shutdown_timeout)
+            .with_leadership(leadership.clone())
+            .with_spend_limiter(self.spend_limiters[dest_domain].clone())
+            .with_chain_halts(self.chain_halts.clone());
             prep_queues.insert(dest_domain.id(), serial_submitter.prepare_queue().await);
 
-            tasks.push(self.run_destination_submitter(
+            submitter_tasks.push(self.run_destination_submitter(
                 dest_domain,
                 serial_submitter,
                 task_monitor.clone(),
@@ -351,6 +626,108 @@ impl BaseAgent for Relayer {
                 panic!("Error creating metrics updater for destination {dest_domain}")
             });
             tasks.push(metrics_updater.spawn());
+
+            let transaction_gas_limit = if self
+                .skip_transaction_gas_limit_for
+                .contains(&dest_domain.id())
+            {
+                None
+            } else {
+                self.transaction_gas_limit
+            };
+            match FundingMonitor::new(
+                dest_conf,
+                &self.core_metrics,
+                self.funding_thresholds
+                    .get(dest_domain.name())
+                    .cloned()
+                    .unwrap_or_default(),
+                transaction_gas_limit,
+                self.pause_submission_on_low_balance,
+                submission_paused,
+                funding_status.with_label_values(&[dest_domain.name()]),
+                notifier.clone(),
+            )
+            .await
+            {
+                Ok(Some(funding_monitor)) => tasks.push(funding_monitor.spawn()),
+                Ok(None) => {
+                    warn!(destination=%dest_domain, "No signer configured, not monitoring funding")
+                }
+                Err(err) => {
+                    error!(destination=%dest_domain, ?err, "Failed to create funding monitor")
+                }
+            }
+        }
+
+        if let Some(threshold) = self.chain_halt_threshold {
+            let chain_halted = self
+                .core_metrics
+                .new_int_gauge(
+                    "relayer_chain_halted",
+                    "Whether the chain is halted, pausing its indexing and submission: 1 = halted",
+                    &["chain"],
+                )
+                .expect("Failed to register chain halted metric");
+            let chains: HashSet<_> = self
+                .origin_chains
+                .iter()
+                .chain(self.destination_chains.keys())
+                .collect();
+            for chain in chains {
+                let chain_conf = &self.core.settings.chains[chain.name()];
+                match chain_conf.build_delivery_indexer(&self.core_metrics).await {
+                    Ok(indexer) => {
+                        let monitor = ChainHaltMonitor::new(
+                            chain.clone(),
+                            indexer.into(),
+                            threshold,
+                            self.chain_halts.clone(),
+                            chain_halted.with_label_values(&[chain.name()]),
+                            notifier.clone(),
+                        );
+                        tasks.push(monitor.spawn());
+                    }
+                    Err(err) => error!(%chain, ?err, "Failed to create chain halt monitor"),
+                }
+            }
+        }
+
+        match GasReconciliationMetrics::new(&self.core_metrics) {
+            Ok(metrics) => {
+                for (origin, ledger) in &self.gas_ledgers {
+                    let reconciler = GasReconciler::new(
+                        origin.clone(),
+                        self.destination_chains.keys(),
+                        self.dbs[origin].clone(),
+                        ledger.clone(),
+                        self.gas_reconciliation_epoch,
+                        &metrics,
+                    );
+                    tasks.push(reconciler.spawn());
+                }
+            }
+            Err(err) => error!(?err, "Failed to register gas reconciliation metrics"),
+        }
+
+        match MessageBacklogMetrics::new(&self.core_metrics) {
+            Ok(metrics) => {
+                for origin in &self.origin_chains {
+                    let destinations = self
+                        .destination_chains
+                        .iter()
+                        .map(|(domain, chain)| (domain, &chain.message_versions));
+                    let backlog = MessageBacklog::new(
+                        origin.clone(),
+                        destinations,
+                        self.dbs[origin].clone(),
+                        self.message_filter(),
+                        &metrics,
+                    );
+                    tasks.push(backlog.spawn());
+                }
+            }
+            Err(err) => error!(?err, "Failed to register message backlog metrics"),
         }
 
         for origin in &self.origin_chains {
@@ -358,28 +735,52 @@ impl BaseAgent for Relayer {
                 .message_syncs
                 .get(origin)
                 .and_then(|sync| sync.get_broadcaster());
-            tasks.push(self.run_message_sync(origin, task_monitor.clone()).await);
-            tasks.push(
-                self.run_interchain_gas_payment_sync(
+            tasks.push(self.run_message_sync(origin, task_monitor.clone()));
+            if self.interchain_gas_payment_syncs.contains_key(origin) {
+                tasks.push(self.run_interchain_gas_payment_sync(
                     origin,
-                    BroadcastMpscSender::map_get_receiver(maybe_broadcaster.as_ref()).await,
+                    maybe_broadcaster.clone(),
                     task_monitor.clone(),
-                )
-                .await,
-            );
-            tasks.push(
-                self.run_merkle_tree_hook_syncs(
+                ));
+            } else {
+                info!(%origin, "Gas payment indexing is disabled for origin");
+            }
+            if self.gas_oracle_config_syncs.contains_key(origin) {
+                tasks.push(self.run_gas_oracle_config_sync(
                     origin,
-                    BroadcastMpscSender::map_get_receiver(maybe_broadcaster.as_ref()).await,
+                    maybe_broadcaster.clone(),
                     task_monitor.clone(),
-                )
-                .await,
-            );
+                ));
+            }
+            tasks.push(self.run_merkle_tree_hook_syncs(
+                origin,
+                maybe_broadcaster.clone(),
+                task_monitor.clone(),
+            ));
         }
         // run server
         let custom_routes = relayer_server::Server::new()
             .with_op_retry(sender.clone())
             .with_message_queue(prep_queues)
+            .with_merkle_proofs(
+                self.prover_syncs
+                    .iter()
+                    .map(|(origin, prover_sync)| (origin.id(), prover_sync.clone()))
+                    .collect(),
+            )
+            .with_spend_limits(
+                self.spend_limiters
+                    .iter()
+                    .map(|(destination, limiter)| (destination.id(), limiter.clone()))
+                    .collect(),
+            )
+            .with_message_status(
+                self.dbs
+                    .iter()
+                    .map(|(origin, db)| (origin.id(), db.clone()))
+                    .collect(),
+                self.message_traces.clone(),
+            )
             .routes();
 
         let server = self
@@ -392,7 +793,9 @@ impl BaseAgent for Relayer {
             .instrument(info_span!("Relayer server"));
         tasks.push(server_task);
 
-        // each message process attempts to send messages from a chain
+        // each message process attempts to send messages from a chain. They
+        // aren't restarted, which would queue their pending messages again,
+        // so a panic ends the relayer like its other unsupervised tasks.
         for origin in &self.origin_chains {
             tasks.push(self.run_message_processor(
                 origin,
@@ -402,40 +805,85 @@ impl BaseAgent for Relayer {
             tasks.push(self.run_merkle_tree_processor(origin, task_monitor.clone()));
         }
 
-        if let Err(err) = try_join_all(tasks).await {
-            tracing::error!(
-                error=?err,
-                "Relayer task panicked"
-            );
+        // Submitters own the queues and channels of their destination, so
+        // they aren't restarted if they panic. The relayer exits instead,
+        // rather than silently no longer delivering to the destination.
+        let submitters = async {
+            let result = try_join_all(submitter_tasks).await;
+            // Submitters only all return once shutting down, unless one panicked
+            if result.is_ok() {
+                shutdown.triggered().await;
+            }
+            result
+        };
+        tokio::select! {
+            result = try_join_all(tasks) => {
+                if let Err(err) = result {
+                    tracing::error!(
+                        error=?err,
+                        "Relayer task panicked"
+                    );
+                }
+            }
+            result = submitters => {
+                if let Err(err) = result {
+                    tracing::error!(
+                        error=?err,
+                        "Relayer submitter panicked"
+                    );
+                }
+                match self.db.flush() {
+                    Ok(()) => info!("Flushed database"),
+                    Err(err) => error!(?err, "Failed to flush database"),
+                }
+            }
         }
     }
 }
 
 impl Relayer {
-    async fn run_message_sync(
+    fn run_message_sync(
         &self,
         origin: &HyperlaneDomain,
         task_monitor: TaskMonitor,
     ) -> Instrumented<JoinHandle<()>> {
         let index_settings = self.as_ref().settings.chains[origin.name()].index_settings();
         let contract_sync = self.message_syncs.get(origin).unwrap().clone();
-        let cursor = contract_sync
-            .cursor(index_settings)
-            .await
-            .unwrap_or_else(|err| panic!("Error getting cursor for origin {origin}: {err}"));
-        tokio::spawn(TaskMonitor::instrument(&task_monitor, async move {
-            contract_sync
-                .clone()
-                .sync("dispatched_messages", cursor.into())
-                .await
-        }))
-        .instrument(info_span!("MessageSync"))
+        let db = Arc::new(self.dbs.get(origin).unwrap().clone());
+        let domain = origin.clone();
+        let chain_halts = self.chain_halts.clone();
+        self.supervisor
+            .spawn("message_sync", Some(origin), move || {
+                let index_settings = index_settings.clone();
+                let contract_sync = contract_sync.clone();
+                let db = db.clone();
+                let origin = domain.clone();
+                let chain_halts = chain_halts.clone();
+                TaskMonitor::instrument(&task_monitor, async move {
+                    let backfill = index_settings.backfill.clone();
+                    let cursor = contract_sync
+                        .cursor(index_settings)
+                        .await
+                        .unwrap_or_else(|err| {
+                            panic!("Error getting cursor for origin {origin}: {err}")
+                        });
+                    contract_sync
+                        .sync_with_backfill(
+                            "dispatched_messages",
+                            SyncOptions::from(cursor).with_chain_halts(chain_halts),
+                            backfill,
+                            db,
+                        )
+                        .await
+                })
+            })
+            .instrument(info_span!("MessageSync"))
     }
 
-    async fn run_interchain_gas_payment_sync(
+    fn run_interchain_gas_payment_sync(
         &self,
         origin: &HyperlaneDomain,
-        tx_id_receiver: Option<MpscReceiver<H512>>,
+        tx_id_broadcaster: Option<BroadcastMpscSender<H512>>,
         task_monitor: TaskMonitor,
     ) -> Instrumented<JoinHandle<()>> {
         let index_settings = self.as_ref().settings.chains[origin.name()].index_settings();
@@ -444,44 +892,134 @@ impl Relayer {
             .get(origin)
             .unwrap()
             .clone();
-        let cursor = contract_sync
-            .cursor(index_settings)
-            .await
-            .unwrap_or_else(|err| panic!("Error getting cursor for origin {origin}: {err}"));
-        tokio::spawn(TaskMonitor::instrument(&task_monitor, async move {
-            contract_sync
-                .clone()
-                .sync(
-                    "gas_payments",
-                    SyncOptions::new(Some(cursor), tx_id_receiver),
-                )
-                .await
-        }))
-        .instrument(info_span!("IgpSync"))
+        let db = Arc::new(self.dbs.get(origin).unwrap().clone());
+        let domain = origin.clone();
+        let chain_halts = self.chain_halts.clone();
+        self.supervisor
+            .spawn("gas_payment_sync", Some(origin), move || {
+                let index_settings = index_settings.clone();
+                let contract_sync = contract_sync.clone();
+                let db = db.clone();
+                let origin = domain.clone();
+                let chain_halts = chain_halts.clone();
+                let tx_id_broadcaster = tx_id_broadcaster.clone();
+                TaskMonitor::instrument(&task_monitor, async move {
+                    let backfill = index_settings.backfill.clone();
+                    let cursor = contract_sync
+                        .cursor(index_settings)
+                        .await
+                        .unwrap_or_else(|err| {
+                            panic!("Error getting cursor for origin {origin}: {err}")
+                        });
+                    let tx_id_receiver =
+                        BroadcastMpscSender::map_get_receiver(tx_id_broadcaster.as_ref()).await;
+                    contract_sync
+                        .sync_with_backfill(
+                            "gas_payments",
+                            SyncOptions::new(Some(cursor), tx_id_receiver)
+                                .with_chain_halts(chain_halts),
+                            backfill,
+                            db,
+                        )
+                        .await
+                })
+            })
+            .instrument(info_span!("IgpSync"))
+    }
+
+    fn run_gas_oracle_config_sync(
+        &self,
+        origin: &HyperlaneDomain,
+        tx_id_broadcaster: Option<BroadcastMpscSender<H512>>,
+        task_monitor: TaskMonitor,
+    ) -> Instrumented<JoinHandle<()>> {
+        let index_settings = self.as_ref().settings.chains[origin.name()].index_settings();
+        let contract_sync = self.gas_oracle_config_syncs.get(origin).unwrap().clone();
+        let db = Arc::new(self.dbs.get(origin).unwrap().clone());
+        let domain = origin.clone();
+        let chain_halts = self.chain_halts.clone();
+        self.supervisor
+            .spawn("gas_oracle_config_sync", Some(origin), move || {
+                let index_settings = index_settings.clone();
+                let contract_sync = contract_sync.clone();
+                let db = db.clone();
+                let origin = domain.clone();
+                let chain_halts = chain_halts.clone();
+                let tx_id_broadcaster = tx_id_broadcaster.clone();
+                TaskMonitor::instrument(&task_monitor, async move {
+                    let backfill = index_settings.backfill.clone();
+                    let cursor = contract_sync
+                        .cursor(index_settings)
+                        .await
+                        .unwrap_or_else(|err| {
+                            panic!("Error getting cursor for origin {origin}: {err}")
+                        });
+                    let tx_id_receiver =
+                        BroadcastMpscSender::map_get_receiver(tx_id_broadcaster.as_ref()).await;
+                    contract_sync
+                        .sync_with_backfill(
+                            "gas_oracle_configs",
+                            SyncOptions::new(Some(cursor), tx_id_receiver)
+                                .with_chain_halts(chain_halts),
+                            backfill,
+                            db,
+                        )
+                        .await
+                })
+            })
+            .instrument(info_span!("GasOracleConfigSync"))
     }
 
-    async fn run_merkle_tree_hook_syncs(
+    fn run_merkle_tree_hook_syncs(
         &self,
         origin: &HyperlaneDomain,
-        tx_id_receiver: Option<MpscReceiver<H512>>,
+        tx_id_broadcaster: Option<BroadcastMpscSender<H512>>,
         task_monitor: TaskMonitor,
     ) -> Instrumented<JoinHandle<()>> {
         let index_settings = self.as_ref().settings.chains[origin.name()].index.clone();
         let contract_sync = self.merkle_tree_hook_syncs.get(origin).unwrap().clone();
-        let cursor = contract_sync
-            .cursor(index_settings)
-            .await
-            .unwrap_or_else(|err| panic!("Error getting cursor for origin {origin}: {err}"));
-        tokio::spawn(TaskMonitor::instrument(&task_monitor, async move {
-            contract_sync
-                .clone()
-                .sync(
-                    "merkle_tree_hook",
-                    SyncOptions::new(Some(cursor), tx_id_receiver),
-                )
-                .await
-        }))
-        .instrument(info_span!("MerkleTreeHookSync"))
+        let db = Arc::new(self.dbs.get(origin).unwrap().clone());
+        let domain = origin.clone();
+        let chain_halts = self.chain_halts.clone();
+        self.supervisor
+            .spawn("merkle_tree_hook_sync", Some(origin), move || {
+                let index_settings = index_settings.clone();
+                let contract_sync = contract_sync.clone();
+                let db = db.clone();
+                let origin = domain.clone();
+                let chain_halts = chain_halts.clone();
+                let tx_id_broadcaster = tx_id_broadcaster.clone();
+                TaskMonitor::instrument(&task_monitor, async move {
+                    let backfill = index_settings.backfill.clone();
+                    let cursor = contract_sync
+                        .cursor(index_settings)
+                        .await
+                        .unwrap_or_else(|err| {
+                            panic!("Error getting cursor for origin {origin}: {err}")
+                        });
+                    let tx_id_receiver =
+                        BroadcastMpscSender::map_get_receiver(tx_id_broadcaster.as_ref()).await;
+                    contract_sync
+                        .sync_with_backfill(
+                            "merkle_tree_hook",
+                            SyncOptions::new(Some(cursor), tx_id_receiver)
+                                .with_chain_halts(chain_halts),
+                            backfill,
+                            db,
+                        )
+                        .await
+                })
+            })
+            .instrument(info_span!("MerkleTreeHookSync"))
+    }
+
+    fn message_filter(&self) -> MessageFilter {
+        MessageFilter {
+            message_whitelist: self.message_whitelist.clone(),
+            message_blacklist: self.message_blacklist.clone(),
+            address_blacklist: self.address_blacklist.clone(),
+            message_shard: self.message_shard,
+        }
     }
 
     fn run_message_processor(
@@ -513,9 +1051,7 @@ impl Relayer {
 
         let message_processor = MessageProcessor::new(
             self.dbs.get(origin).unwrap().clone(),
-            self.message_whitelist.clone(),
-            self.message_blacklist.clone(),
-            self.address_blacklist.clone(),
+            self.message_filter(),
             metrics,
             send_channels,
             destination_ctxs,
@@ -554,17 +1090,17 @@ impl Relayer {
         task_monitor: TaskMonitor,
     ) -> Instrumented<JoinHandle<()>> {
         let span = info_span!("SerialSubmitter", destination=%destination);
-        let destination = destination.clone();
-        tokio::spawn(TaskMonitor::instrument(&task_monitor, async move {
+        let domain = destination.clone();
+        let submitter = TaskMonitor::instrument(&task_monitor, async move {
             // Propagate task panics
             serial_submitter.spawn().await.unwrap_or_else(|err| {
                 panic!(
                     "destination submitter panicked for destination {}: {:?}",
-                    destination, err
+                    domain, err
                 )
             });
-        }))
-        .instrument(span)
+        });
+        spawn_named("destination_submitter", Some(destination), submitter).instrument(span)
     }
 }
 
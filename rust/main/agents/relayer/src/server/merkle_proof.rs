@@ -0,0 +1,178 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    routing, Router,
+};
+use derive_new::new;
+use hyperlane_core::{accumulator::merkle::Proof, H256};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::merkle_tree::builder::MerkleTreeBuilder;
+
+const MERKLE_PROOF_API_BASE: &str = "/merkle_proof";
+
+type MerkleTrees = HashMap<u32, Arc<RwLock<MerkleTreeBuilder>>>;
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct MerkleProofRequest {
+    origin_domain: u32,
+    leaf_index: u32,
+    /// Index of the last leaf of the tree to prove against. Defaults to the
+    /// latest leaf the relayer has ingested.
+    root_index: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+struct MerkleProofResponse {
+    root: H256,
+    root_index: u32,
+    proof: Proof,
+}
+
+/// Generates proofs of messages against current or historical roots of the
+/// merkle tree of an origin, as rebuilt by the relayer from the tree
+/// insertions it has stored. Useful for ISMs that need proofs and for
+/// debugging.
+#[derive(new, Clone)]
+pub struct MerkleProofApi {
+    merkle_trees: MerkleTrees,
+}
+
+async fn merkle_proof(
+    State(merkle_trees): State<MerkleTrees>,
+    Query(request): Query<MerkleProofRequest>,
+) -> (StatusCode, String) {
+    let domain = request.origin_domain;
+    let Some(merkle_tree) = merkle_trees.get(&domain) else {
+        return (
+            StatusCode::NOT_FOUND,
+            format!("No merkle tree found for domain {}", domain),
+        );
+    };
+    let merkle_tree = merkle_tree.read().await;
+    let Some(latest_index) = merkle_tree.count().checked_sub(1) else {
+        return (
+            StatusCode::NOT_FOUND,
+            format!("Merkle tree of domain {} is empty", domain),
+        );
+    };
+    let root_index = request.root_index.unwrap_or(latest_index);
+    if request.leaf_index > root_index || root_index > latest_index {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Leaf index {} and root index {} must not exceed the latest index {}",
+                request.leaf_index, root_index, latest_index
+            ),
+        );
+    }
+
+    let response = merkle_tree
+        .get_proof(request.leaf_index, root_index)
+        .map_err(|err| err.to_string())
+        .and_then(|proof| {
+            serde_json::to_string_pretty(&MerkleProofResponse {
+                root: proof.root(),
+                root_index,
+                proof,
+            })
+            .map_err(|err| err.to_string())
+        });
+    match response {
+        Ok(response) => (StatusCode::OK, response),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to generate proof: {}", err),
+        ),
+    }
+}
+
+impl MerkleProofApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/", routing::get(merkle_proof))
+            .with_state(self.merkle_trees.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (MERKLE_PROOF_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use hyperlane_core::accumulator::incremental::IncrementalMerkle;
+
+    use super::*;
+
+    const DUMMY_DOMAIN: u32 = 42161;
+
+    async fn setup_test_server(leaves: &[H256]) -> SocketAddr {
+        let mut merkle_tree = MerkleTreeBuilder::new();
+        for leaf in leaves {
+            merkle_tree.ingest_message_id(*leaf).await.unwrap();
+        }
+        let mut merkle_trees = HashMap::new();
+        merkle_trees.insert(DUMMY_DOMAIN, Arc::new(RwLock::new(merkle_tree)));
+
+        let (path, router) = MerkleProofApi::new(merkle_trees).get_route();
+        let app = Router::new().nest(path, router);
+
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    async fn get_proof(addr: SocketAddr, query: &str) -> (StatusCode, String) {
+        let response = reqwest::get(format!(
+            "http://{}{}?{}",
+            addr, MERKLE_PROOF_API_BASE, query
+        ))
+        .await
+        .unwrap();
+        (response.status(), response.text().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_proof_against_historical_root() {
+        let leaves = (1..=5).map(H256::from_low_u64_be).collect::<Vec<_>>();
+        let addr = setup_test_server(&leaves).await;
+
+        let mut historical_tree = IncrementalMerkle::default();
+        leaves[..3]
+            .iter()
+            .for_each(|leaf| historical_tree.ingest(*leaf));
+
+        let (status, body) = get_proof(
+            addr,
+            &format!("origin_domain={}&leaf_index=1&root_index=2", DUMMY_DOMAIN),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let response: serde_json::Value = serde_json::from_str(&body).unwrap();
+        let proof: Proof = serde_json::from_value(response["proof"].clone()).unwrap();
+        assert_eq!(proof.leaf, leaves[1]);
+        assert_eq!(proof.root(), historical_tree.root());
+        assert_eq!(
+            response["root"],
+            serde_json::to_value(historical_tree.root()).unwrap()
+        );
+
+        let (status, _) = get_proof(
+            addr,
+            &format!("origin_domain={}&leaf_index=5", DUMMY_DOMAIN),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = get_proof(addr, "origin_domain=1&leaf_index=0").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,213 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    routing, Router,
+};
+use derive_new::new;
+use hyperlane_base::db::{DbResult, HyperlaneDb, HyperlaneRocksDB};
+use hyperlane_core::{GasPaymentKey, HyperlaneMessage, PendingOperationStatus, H256, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::msg::trace::{MessageTraces, TraceEntry};
+
+const MESSAGE_STATUS_API_BASE: &str = "/message_status";
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct MessageStatusRequest {
+    message_id: H256,
+}
+
+#[derive(Debug, Serialize)]
+struct GasPaymentStatus {
+    /// Native tokens paid on the origin
+    paid: U256,
+    /// Destination gas paid for
+    gas_amount: U256,
+    /// Destination tokens spent delivering the message
+    tokens_used: U256,
+    /// Destination gas spent delivering the message
+    gas_used: U256,
+}
+
+#[derive(Debug, Serialize)]
+struct MessageStatusResponse {
+    message: HyperlaneMessage,
+    /// Block of the origin chain the message was indexed from
+    dispatched_block_number: Option<u64>,
+    processed: bool,
+    status: Option<PendingOperationStatus>,
+    gas_payment: GasPaymentStatus,
+    num_retries: u32,
+    /// Unix timestamp in seconds of the next attempt, while backing off
+    next_attempt_at: Option<u64>,
+    /// What happened to the message since the relayer started, oldest first
+    events: Vec<TraceEntry>,
+}
+
+/// Reports everything the relayer knows about a message: its persisted
+/// indexing, gas payment and retry state, and the attempts to deliver it
+/// since the relayer started. Meant to diagnose undelivered messages.
+#[derive(new, Clone)]
+pub struct MessageStatusApi {
+    origin_dbs: HashMap<u32, HyperlaneRocksDB>,
+    traces: Arc<MessageTraces>,
+}
+
+type MessageStatusState = (HashMap<u32, HyperlaneRocksDB>, Arc<MessageTraces>);
+
+fn message_status_from_db(
+    db: &HyperlaneRocksDB,
+    message: HyperlaneMessage,
+    events: Vec<TraceEntry>,
+) -> DbResult<MessageStatusResponse> {
+    let id = message.id();
+    let payment = db.retrieve_gas_payment_by_gas_payment_key(GasPaymentKey {
+        message_id: id,
+        destination: message.destination,
+    })?;
+    let expenditure = db.retrieve_gas_expenditure_by_message_id(id)?;
+    Ok(MessageStatusResponse {
+        dispatched_block_number: db.retrieve_dispatched_block_number_by_nonce(&message.nonce)?,
+        processed: db
+            .retrieve_processed_by_nonce(&message.nonce)?
+            .unwrap_or(false),
+        status: db.retrieve_status_by_message_id(&id)?,
+        gas_payment: GasPaymentStatus {
+            paid: payment.map(|payment| payment.payment).unwrap_or_default(),
+            gas_amount: payment
+                .map(|payment| payment.gas_amount)
+                .unwrap_or_default(),
+            tokens_used: expenditure.tokens_used,
+            gas_used: expenditure.gas_used,
+        },
+        num_retries: db
+            .retrieve_pending_message_retry_count_by_message_id(&id)?
+            .unwrap_or(0),
+        next_attempt_at: db.retrieve_pending_message_next_attempt_by_message_id(&id)?,
+        events,
+        message,
+    })
+}
+
+async fn message_status(
+    State((origin_dbs, traces)): State<MessageStatusState>,
+    Query(request): Query<MessageStatusRequest>,
+) -> (StatusCode, String) {
+    let id = request.message_id;
+    let found = origin_dbs.values().find_map(|db| {
+        db.retrieve_message_by_id(&id)
+            .transpose()
+            .map(|message| (db, message))
+    });
+    let Some((db, message)) = found else {
+        return (
+            StatusCode::NOT_FOUND,
+            format!("Message {:?} not found in any origin database", id),
+        );
+    };
+
+    let response = message
+        .and_then(|message| message_status_from_db(db, message, traces.get(&id)))
+        .map_err(|err| err.to_string())
+        .and_then(|response| {
+            serde_json::to_string_pretty(&response).map_err(|err| err.to_string())
+        });
+    match response {
+        Ok(response) => (StatusCode::OK, response),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to read the status of message {:?}: {}", id, err),
+        ),
+    }
+}
+
+impl MessageStatusApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/", routing::get(message_status))
+            .with_state((self.origin_dbs.clone(), self.traces.clone()))
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (MESSAGE_STATUS_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::{HyperlaneDomain, H512};
+    use serde_json::Value;
+
+    use crate::msg::trace::TraceEvent;
+
+    use super::*;
+
+    fn setup_test_server(db: HyperlaneRocksDB, traces: Arc<MessageTraces>) -> SocketAddr {
+        let origin_dbs = HashMap::from([(db.domain().id(), db)]);
+        let (path, router) = MessageStatusApi::new(origin_dbs, traces).get_route();
+        let app = Router::new().nest(path, router);
+
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_message_status() {
+        test_utils::run_test_db(|db| async move {
+            let origin = HyperlaneDomain::new_test_domain("test_message_status");
+            let db = HyperlaneRocksDB::new(&origin, db);
+            let message = HyperlaneMessage {
+                origin: origin.id(),
+                nonce: 3,
+                ..Default::default()
+            };
+            let id = message.id();
+            db.store_message(&message, 100).unwrap();
+            db.store_pending_message_retry_count_by_message_id(&id, &2)
+                .unwrap();
+            let traces = Arc::new(MessageTraces::default());
+            traces.record(
+                id,
+                TraceEvent::Submitted {
+                    tx_hash: H512::from_low_u64_be(7),
+                    executed: false,
+                    gas_used: 21_000.into(),
+                },
+            );
+            let addr = setup_test_server(db, traces);
+
+            let response = reqwest::get(format!(
+                "http://{}{}?message_id={:?}",
+                addr, MESSAGE_STATUS_API_BASE, id
+            ))
+            .await
+            .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let status: Value = response.json().await.unwrap();
+            assert_eq!(status["dispatched_block_number"], 100);
+            assert_eq!(status["processed"], false);
+            assert_eq!(status["num_retries"], 2);
+            assert_eq!(status["events"][0]["event"], "submitted");
+            assert_eq!(status["events"][0]["executed"], false);
+
+            let response = reqwest::get(format!(
+                "http://{}{}?message_id={:?}",
+                addr,
+                MESSAGE_STATUS_API_BASE,
+                H256::zero()
+            ))
+            .await
+            .unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        })
+        .await;
+    }
+}
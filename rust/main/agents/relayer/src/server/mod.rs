@@ -1,17 +1,27 @@
 use axum::Router;
 use derive_new::new;
-use std::collections::HashMap;
-use tokio::sync::broadcast::Sender;
+use hyperlane_base::db::HyperlaneRocksDB;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::{broadcast::Sender, RwLock};
 
-use crate::msg::op_queue::OperationPriorityQueue;
+use crate::{
+    merkle_tree::builder::MerkleTreeBuilder,
+    msg::{op_queue::OperationPriorityQueue, spend_limit::SpendLimiter, trace::MessageTraces},
+};
 
 pub const ENDPOINT_MESSAGES_QUEUE_SIZE: usize = 100;
 
 pub use list_messages::*;
+pub use merkle_proof::*;
 pub use message_retry::*;
+pub use message_status::*;
+pub use spend_limit::*;
 
 mod list_messages;
+mod merkle_proof;
 mod message_retry;
+mod message_status;
+mod spend_limit;
 
 #[derive(new)]
 pub struct Server {
@@ -19,6 +29,12 @@ pub struct Server {
     retry_transmitter: Option<Sender<MessageRetryRequest>>,
     #[new(default)]
     op_queues: Option<HashMap<u32, OperationPriorityQueue>>,
+    #[new(default)]
+    merkle_trees: Option<HashMap<u32, Arc<RwLock<MerkleTreeBuilder>>>>,
+    #[new(default)]
+    spend_limiters: Option<HashMap<u32, Arc<SpendLimiter>>>,
+    #[new(default)]
+    message_status: Option<(HashMap<u32, HyperlaneRocksDB>, Arc<MessageTraces>)>,
 }
 
 impl Server {
@@ -32,6 +48,28 @@ impl Server {
         self
     }
 
+    pub fn with_merkle_proofs(
+        mut self,
+        merkle_trees: HashMap<u32, Arc<RwLock<MerkleTreeBuilder>>>,
+    ) -> Self {
+        self.merkle_trees = Some(merkle_trees);
+        self
+    }
+
+    pub fn with_spend_limits(mut self, spend_limiters: HashMap<u32, Arc<SpendLimiter>>) -> Self {
+        self.spend_limiters = Some(spend_limiters);
+        self
+    }
+
+    pub fn with_message_status(
+        mut self,
+        origin_dbs: HashMap<u32, HyperlaneRocksDB>,
+        traces: Arc<MessageTraces>,
+    ) -> Self {
+        self.message_status = Some((origin_dbs, traces));
+        self
+    }
+
     /// Returns a vector of agent-specific endpoint routes to be served.
     /// Can be extended with additional routes and feature flags to enable/disable individually.
     pub fn routes(self) -> Vec<(&'static str, Router)> {
@@ -42,6 +80,15 @@ impl Server {
         if let Some(op_queues) = self.op_queues {
             routes.push(ListOperationsApi::new(op_queues).get_route());
         }
+        if let Some(merkle_trees) = self.merkle_trees {
+            routes.push(MerkleProofApi::new(merkle_trees).get_route());
+        }
+        if let Some(spend_limiters) = self.spend_limiters {
+            routes.push(SpendLimitApi::new(spend_limiters).get_route());
+        }
+        if let Some((origin_dbs, traces)) = self.message_status {
+            routes.push(MessageStatusApi::new(origin_dbs, traces).get_route());
+        }
 
         routes
     }
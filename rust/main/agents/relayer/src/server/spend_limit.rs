@@ -0,0 +1,129 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    routing, Router,
+};
+use derive_new::new;
+use serde::Deserialize;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use crate::msg::spend_limit::SpendLimiter;
+
+const SPEND_LIMIT_API_BASE: &str = "/spend_limit_override";
+
+/// Lifts the spend limits of a destination for `duration_secs`, or restores
+/// them if it is zero
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct SpendLimitOverrideRequest {
+    destination_domain: u32,
+    duration_secs: u64,
+}
+
+#[derive(new, Clone)]
+pub struct SpendLimitApi {
+    spend_limiters: HashMap<u32, Arc<SpendLimiter>>,
+}
+
+async fn override_spend_limit(
+    State(limiters): State<HashMap<u32, Arc<SpendLimiter>>>,
+    Query(request): Query<SpendLimitOverrideRequest>,
+) -> (StatusCode, String) {
+    let domain = request.destination_domain;
+    let Some(limiter) = limiters.get(&domain) else {
+        return (
+            StatusCode::NOT_FOUND,
+            format!("No spend limits found for domain {}", domain),
+        );
+    };
+    if let Err(err) = limiter.lift_for(Duration::from_secs(request.duration_secs)) {
+        return (StatusCode::BAD_REQUEST, err.to_string());
+    }
+    let response = if request.duration_secs == 0 {
+        format!("Restored spend limits for domain {}", domain)
+    } else {
+        format!(
+            "Lifted spend limits for domain {} for {}s",
+            domain, request.duration_secs
+        )
+    };
+    (StatusCode::OK, response)
+}
+
+impl SpendLimitApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/", routing::get(override_spend_limit))
+            .with_state(self.spend_limiters.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (SPEND_LIMIT_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::SpendLimitsConf;
+    use hyperlane_core::{FixedPointNumber, KnownHyperlaneDomain, U256};
+    use std::net::SocketAddr;
+
+    const DUMMY_DOMAIN: KnownHyperlaneDomain = KnownHyperlaneDomain::Arbitrum;
+
+    fn setup_test_server() -> (SocketAddr, Arc<SpendLimiter>) {
+        let limiter = Arc::new(SpendLimiter::new(&SpendLimitsConf {
+            max_delivery_fee: Some(1_000.into()),
+            hourly_cap: None,
+        }));
+        let mut limiters = HashMap::new();
+        limiters.insert(DUMMY_DOMAIN as u32, limiter.clone());
+
+        let (path, router) = SpendLimitApi::new(limiters).get_route();
+        let app = Router::new().nest(path, router);
+
+        // Running the app in the background using a test server
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        (addr, limiter)
+    }
+
+    #[tokio::test]
+    async fn test_spend_limit_override() {
+        let (addr, limiter) = setup_test_server();
+        let fee = FixedPointNumber::try_from(U256::from(2_000)).unwrap();
+        assert!(limiter.exceeds_max_delivery_fee(&fee));
+
+        let response = reqwest::get(format!(
+            "http://{}{}?destination_domain={}&duration_secs=60",
+            addr, SPEND_LIMIT_API_BASE, DUMMY_DOMAIN as u32
+        ))
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!limiter.exceeds_max_delivery_fee(&fee));
+
+        let response = reqwest::get(format!(
+            "http://{}{}?destination_domain={}&duration_secs=0",
+            addr, SPEND_LIMIT_API_BASE, DUMMY_DOMAIN as u32
+        ))
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(limiter.exceeds_max_delivery_fee(&fee));
+
+        let response = reqwest::get(format!(
+            "http://{}{}?destination_domain={}&duration_secs={}",
+            addr,
+            SPEND_LIMIT_API_BASE,
+            DUMMY_DOMAIN as u32,
+            u64::MAX
+        ))
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(limiter.exceeds_max_delivery_fee(&fee));
+    }
+}
@@ -8,12 +8,14 @@ use std::{
     marker::PhantomData,
 };
 
-use hyperlane_core::{config::StrOrInt, utils::hex_or_base58_to_h256, HyperlaneMessage, H256};
+use hyperlane_core::{config::StrOrInt, utils::hex_or_base58_to_h256, H256};
 use serde::{
     de::{Error, SeqAccess, Visitor},
     Deserialize, Deserializer,
 };
 
+use crate::msg::filter::FilterableMessage;
+
 /// Defines a set of patterns for determining if a message should or should not
 /// be relayed. This is useful for determine if a message matches a given set or
 /// rules.
@@ -254,22 +256,18 @@ struct MatchInfo<'a> {
     dst_addr: &'a H256,
 }
 
-impl<'a> From<&'a HyperlaneMessage> for MatchInfo<'a> {
-    fn from(msg: &'a HyperlaneMessage) -> Self {
-        Self {
-            src_domain: msg.origin,
-            src_addr: &msg.sender,
-            dst_domain: msg.destination,
-            dst_addr: &msg.recipient,
-        }
-    }
-}
-
 impl MatchingList {
     /// Check if a message matches any of the rules.
     /// - `default`: What to return if the matching list is empty.
-    pub fn msg_matches(&self, msg: &HyperlaneMessage, default: bool) -> bool {
-        self.matches(msg.into(), default)
+    pub fn msg_matches(&self, msg: &impl FilterableMessage, default: bool) -> bool {
+        let (sender, recipient) = (msg.sender(), msg.recipient());
+        let info = MatchInfo {
+            src_domain: msg.origin(),
+            src_addr: &sender,
+            dst_domain: msg.destination(),
+            dst_addr: &recipient,
+        };
+        self.matches(info, default)
     }
 
     /// Check if a message matches any of the rules.
@@ -4,7 +4,11 @@
 //! and validations it defines are not applied here, we should mirror them.
 //! ANY CHANGES HERE NEED TO BE REFLECTED IN THE TYPESCRIPT SDK.
 
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    time::Duration,
+};
 
 use convert_case::Case;
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
@@ -17,8 +21,9 @@ use hyperlane_base::{
         Settings,
     },
 };
-use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain, U256};
+use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain, H256, U256};
 use itertools::Itertools;
+use reqwest::Url;
 use serde::Deserialize;
 use serde_json::Value;
 
@@ -26,6 +31,26 @@ use crate::settings::matching_list::MatchingList;
 
 pub mod matching_list;
 
+/// How long a submission lease is held by default, covering the submission
+/// and confirmation of a delivery
+const DEFAULT_SUBMISSION_LEASE_TTL: Duration = Duration::from_secs(600);
+
+/// How long in-flight submissions are waited on by default when shutting down
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long a leader holds its lease by default without renewing it
+const DEFAULT_LEADER_LEASE_DURATION: Duration = Duration::from_secs(15);
+
+/// The Postgres advisory lock taken by leaders by default
+const DEFAULT_LEADER_LOCK_ID: u32 = 0x6879_706c;
+
+/// How often the address denylist is fetched by default
+const DEFAULT_DENYLIST_REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// How long the epochs gas payments are reconciled with delivery spend over
+/// are by default
+const DEFAULT_GAS_RECONCILIATION_EPOCH: Duration = Duration::from_secs(60 * 60);
+
 /// Settings for `Relayer`
 #[derive(Debug, AsRef, AsMut, Deref, DerefMut)]
 pub struct RelayerSettings {
@@ -51,16 +76,223 @@ pub struct RelayerSettings {
     /// This is intentionally not an H256 to allow for addresses of any length without
     /// adding any padding.
     pub address_blacklist: Vec<Vec<u8>>,
+    /// Denylist of addresses to block interactions with, periodically fetched
+    /// from a screening service.
+    pub address_denylist: Option<AddressDenylistConf>,
     /// This is optional. If not specified, any amount of gas will be valid, otherwise this
     /// is the max allowed gas in wei to relay a transaction.
     pub transaction_gas_limit: Option<U256>,
     /// List of domain ids to skip transaction gas for.
     pub skip_transaction_gas_limit_for: HashSet<u32>,
+    /// List of domain ids to deliver to with empty metadata, without building
+    /// it for the ISM. Only for destinations using the TestIsm, which accepts
+    /// any metadata. Not intended for production use.
+    pub skip_ism_for: HashSet<u32>,
+    /// Limits beyond which messages are dropped as abusive, before their
+    /// metadata is built or their delivery estimated.
+    pub message_limits: MessageLimitsConf,
     /// If true, allows local storage based checkpoint syncers.
     /// Not intended for production use.
     pub allow_local_checkpoint_syncers: bool,
     /// App contexts used for metrics.
     pub metric_app_contexts: Vec<(MatchingList, String)>,
+    /// Grace periods during which new messages of apps relaying their own
+    /// messages are left to them. The first matching rule applies.
+    pub self_relay_grace_periods: Vec<SelfRelayConf>,
+    /// Signer balance thresholds for each destination chain, keyed by chain name.
+    pub funding_thresholds: HashMap<String, FundingThresholdsConf>,
+    /// If true, submission to a destination is paused while the signer balance
+    /// can't cover the estimated cost of a single delivery.
+    pub pause_submission_on_low_balance: bool,
+    /// If set, indexing and submission on a chain are paused while it doesn't
+    /// produce a new block for this long, until it produces one again.
+    pub chain_halt_threshold: Option<Duration>,
+    /// Limits on the fees spent delivering to each destination chain, keyed
+    /// by chain name.
+    pub spend_limits: HashMap<String, SpendLimitsConf>,
+    /// Delivery decorators for each destination chain, keyed by chain name.
+    pub delivery_decorators: HashMap<String, Vec<DeliveryDecoratorConf>>,
+    /// Gas limit overrides for each destination chain, keyed by chain name
+    /// and recipient.
+    pub gas_limit_overrides: HashMap<String, HashMap<H256, GasLimitOverrideConf>>,
+    /// Shared store of submission leases, which keeps redundant relayers from
+    /// delivering the same message at once.
+    pub submission_lease: Option<SubmissionLeaseConf>,
+    /// The shard of messages this relayer processes, out of `shard_count`
+    pub shard_index: u32,
+    /// How many shards messages are split into between relayer instances
+    pub shard_count: u32,
+    /// Weights of the gas payments of messages from each origin chain, keyed
+    /// by chain name. Messages from these origins are delivered in order of
+    /// their weighted gas payment per unit of estimated gas.
+    pub payment_priority_weights: HashMap<String, f64>,
+    /// What the relayer does on each chain, keyed by chain name. Chains
+    /// without an entry take every role.
+    pub chain_roles: HashMap<String, ChainRolesConf>,
+    /// Where the messages and gas payments of origin chains are restored
+    /// from when the database has none yet, instead of indexing them
+    pub bootstrap_snapshot: Option<SnapshotSource>,
+    /// How long to wait for in-flight submissions to be confirmed when
+    /// shutting down, before reporting the operations left pending
+    pub shutdown_timeout: Duration,
+    /// If set, only the elected leader among the relayer replicas submits
+    /// operations, while the others stand by
+    pub leader_election: Option<LeaderElectionConf>,
+    /// How long the epochs are over which the gas paid for messages is
+    /// reconciled with the gas spent delivering them
+    pub gas_reconciliation_epoch: Duration,
+}
+
+/// Config for fetching an address denylist from a screening service
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressDenylistConf {
+    /// Where the denylist is fetched from
+    pub url: Url,
+    /// Sent as a bearer token, if the service requires one
+    pub api_key: Option<String>,
+    /// How often the denylist is fetched again
+    pub refresh_interval: Duration,
+}
+
+/// Config for electing the replica that submits operations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderElectionConf {
+    /// Where the leadership is held
+    pub backend: LeaderElectionBackend,
+    /// How long the leadership lasts without being renewed
+    pub lease_duration: Duration,
+}
+
+/// Where the leadership of relayer replicas is held
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeaderElectionBackend {
+    /// A Lease of the Kubernetes cluster the relayer runs in
+    Kubernetes {
+        lease_name: String,
+        /// Defaults to the namespace of the relayer pod
+        namespace: Option<String>,
+    },
+    /// An advisory lock of a Postgres database
+    Postgres { url: String, lock_id: u32 },
+}
+
+/// Where snapshots of the messages and gas payments of origin chains are
+/// loaded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotSource {
+    /// The query API of a scraper, e.g. `http://scraper:9090/query`
+    Scraper(Url),
+    /// A directory of snapshots exported from a scraper, named
+    /// `<chain name>.json`
+    Dir(PathBuf),
+}
+
+/// The roles the relayer takes on a chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainRolesConf {
+    /// Whether messages dispatched on the chain are indexed and relayed
+    pub index_messages: bool,
+    /// Whether messages are delivered to the chain
+    pub submit: bool,
+    /// Whether gas payments made on the chain are indexed. Messages from a
+    /// chain whose gas payments aren't indexed only pass a `none` gas payment
+    /// enforcement policy.
+    pub index_gas_payments: bool,
+}
+
+impl Default for ChainRolesConf {
+    fn default() -> Self {
+        Self {
+            index_messages: true,
+            submit: true,
+            index_gas_payments: true,
+        }
+    }
+}
+
+/// Sanity limits on the messages the relayer delivers.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MessageLimitsConf {
+    /// Messages with a larger body are not delivered.
+    pub max_body_bytes: Option<usize>,
+    /// Messages whose hook metadata requests a larger gas limit for their
+    /// delivery are not delivered.
+    pub max_gas_limit: Option<U256>,
+}
+
+/// Signer balance thresholds for a destination chain, in the lowest
+/// denomination of the chain's native token.
+#[derive(Debug, Clone, Default)]
+pub struct FundingThresholdsConf {
+    /// Balance under which a warning is raised.
+    pub warning: Option<U256>,
+    /// Balance under which the funding status is considered critical.
+    pub critical: Option<U256>,
+}
+
+/// Limits on the fees spent delivering to a destination chain, in the lowest
+/// denomination of the chain's native token.
+#[derive(Debug, Clone, Default)]
+pub struct SpendLimitsConf {
+    /// Deliveries estimated to cost more are not submitted.
+    pub max_delivery_fee: Option<U256>,
+    /// Submission is paused while more was spent over the last hour.
+    pub hourly_cap: Option<U256>,
+}
+
+/// Config for a delivery decorator, which adjusts the delivery of messages to
+/// recipients needing more than the ISM metadata.
+#[derive(Debug, Clone)]
+pub struct DeliveryDecoratorConf {
+    /// The messages the decorator applies to. By default all messages match.
+    pub matching_list: MatchingList,
+    /// The adjustment made by the decorator
+    pub decorator: DeliveryDecoratorType,
+}
+
+/// Config for the adjustment made by a delivery decorator
+#[derive(Debug, Clone)]
+pub enum DeliveryDecoratorType {
+    /// Append fixed bytes to the metadata
+    AppendMetadata { metadata: Vec<u8> },
+    /// Add fixed gas to the gas limit of the delivery
+    ExtraGas { gas: U256 },
+    /// Ask an application server for the bytes to append to the metadata and
+    /// the extra gas of each delivery
+    Preflight { url: Url },
+}
+
+/// Config for deferring the messages of an app relaying its own messages,
+/// which are only delivered if still undelivered after the grace period
+#[derive(Debug, Clone)]
+pub struct SelfRelayConf {
+    /// The messages of the app
+    pub matching_list: MatchingList,
+    /// How long messages are left to the app after their dispatch
+    pub grace_period: Duration,
+}
+
+/// Config for raising the estimated gas limit of deliveries to a recipient.
+/// The greater of the multiplied estimate and the minimum is used, and the
+/// estimate is never lowered.
+#[derive(Debug, Clone, Default)]
+pub struct GasLimitOverrideConf {
+    /// Multiplier applied to the estimated gas limit
+    pub multiplier: Option<f64>,
+    /// Minimum gas limit
+    pub minimum: Option<U256>,
+}
+
+/// Config for the submission leases shared by redundant relayers
+#[derive(Debug, Clone)]
+pub struct SubmissionLeaseConf {
+    /// Url of the Postgres database storing the leases
+    pub url: String,
+    /// Identifies this relayer instance in the leases. Defaults to the
+    /// hostname, which is the pod name on Kubernetes.
+    pub holder: Option<String>,
+    /// How long a lease is held after it is taken
+    pub ttl: Duration,
 }
 
 /// Config for gas payment enforcement
@@ -87,6 +319,19 @@ pub enum GasPaymentEnforcementPolicy {
         gas_fraction_numerator: u64,
         gas_fraction_denominator: u64,
     },
+    /// The payment covers what the IGP quotes for a fraction of the estimated
+    /// gas, according to the gas oracle configs indexed from the origin.
+    IgpQuote {
+        gas_fraction_numerator: u64,
+        gas_fraction_denominator: u64,
+    },
+}
+
+impl GasPaymentEnforcementPolicy {
+    /// Whether the policy needs the gas oracle configs of the origin IGPs
+    pub fn uses_gas_oracle_configs(&self) -> bool {
+        matches!(self, Self::IgpQuote { .. })
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -95,6 +340,16 @@ struct RawRelayerSettings(Value);
 
 impl_loadable_from_settings!(Relayer, RawRelayerSettings -> RelayerSettings);
 
+impl RelayerSettings {
+    /// The roles the relayer takes on a chain
+    pub fn chain_roles(&self, domain: &HyperlaneDomain) -> ChainRolesConf {
+        self.chain_roles
+            .get(domain.name())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
 impl FromRawConf<RawRelayerSettings> for RelayerSettings {
     fn from_config_filtered(
         raw: RawRelayerSettings,
@@ -131,59 +386,12 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             .and_then(parse_json_array)
             .unwrap_or_else(|| (&p.cwp + "gas_payment_enforcement", Value::Array(vec![])));
 
-        let gas_payment_enforcement_parser = ValueParser::new(
+        let gas_payment_enforcement = parse_gas_payment_enforcement(ValueParser::new(
             raw_gas_payment_enforcement_path,
             &raw_gas_payment_enforcement,
-        );
-        let mut gas_payment_enforcement = gas_payment_enforcement_parser.into_array_iter().map(|itr| {
-            itr.filter_map(|policy| {
-                let policy_type = policy.chain(&mut err).get_opt_key("type").parse_string().end();
-                let minimum_is_defined = matches!(policy.get_opt_key("minimum"), Ok(Some(_)));
-
-                let matching_list = policy.chain(&mut err).get_opt_key("matchingList").and_then(parse_matching_list).unwrap_or_default();
-
-                let parse_minimum = |p| GasPaymentEnforcementPolicy::Minimum { payment: p };
-                match policy_type {
-                    Some("minimum") => policy.chain(&mut err).get_opt_key("payment").parse_u256().end().map(parse_minimum),
-                    None if minimum_is_defined => policy.chain(&mut err).get_opt_key("payment").parse_u256().end().map(parse_minimum),
-                    Some("none") | None => Some(GasPaymentEnforcementPolicy::None),
-                    Some("onChainFeeQuoting") => {
-                        let gas_fraction = policy.chain(&mut err)
-                            .get_opt_key("gasFraction")
-                            .parse_string()
-                            .map(|v| v.replace(' ', ""))
-                            .unwrap_or_else(|| "1/2".to_owned());
-                        let (numerator, denominator) = gas_fraction
-                            .split_once('/')
-                            .ok_or_else(|| eyre!("Invalid `gas_fraction` for OnChainFeeQuoting gas payment enforcement policy; expected `numerator / denominator`"))
-                            .take_err(&mut err, || &policy.cwp + "gas_fraction")
-                            .unwrap_or(("1", "1"));
-
-                        Some(GasPaymentEnforcementPolicy::OnChainFeeQuoting {
-                            gas_fraction_numerator: numerator
-                                .parse()
-                                .context("Error parsing gas fraction numerator")
-                                .take_err(&mut err, || &policy.cwp + "gas_fraction")
-                                .unwrap_or(1),
-                            gas_fraction_denominator: denominator
-                                .parse()
-                                .context("Error parsing gas fraction denominator")
-                                .take_err(&mut err, || &policy.cwp + "gas_fraction")
-                                .unwrap_or(1),
-                        })
-                    }
-                    Some(pt) => Err(eyre!("Unknown gas payment enforcement policy type `{pt}`"))
-                        .take_err(&mut err, || cwp + "type"),
-                }.map(|policy| GasPaymentEnforcementConf {
-                    policy,
-                    matching_list,
-                })
-            }).collect_vec()
-        }).unwrap_or_default();
-
-        if gas_payment_enforcement.is_empty() {
-            gas_payment_enforcement.push(GasPaymentEnforcementConf::default());
-        }
+        ))
+        .take_config_err(&mut err)
+        .unwrap_or_default();
 
         let whitelist = p
             .chain(&mut err)
@@ -204,6 +412,12 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             .map(|str| parse_address_list(str, &mut err, || &p.cwp + "address_blacklist"))
             .unwrap_or_default();
 
+        let address_denylist = p
+            .chain(&mut err)
+            .get_opt_key("addressDenylist")
+            .and_then(parse_address_denylist)
+            .end();
+
         let transaction_gas_limit = p
             .chain(&mut err)
             .get_opt_key("transactionGasLimit")
@@ -217,12 +431,262 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             .map(|v| v.split(',').collect())
             .unwrap_or_default();
 
+        let skip_ism_for_names: HashSet<&str> = p
+            .chain(&mut err)
+            .get_opt_key("skipIsmFor")
+            .parse_string()
+            .map(|v| v.split(',').collect())
+            .unwrap_or_default();
+
+        let message_limits = MessageLimitsConf {
+            max_body_bytes: p
+                .chain(&mut err)
+                .get_opt_key("messageLimits")
+                .get_opt_key("maxBodyBytes")
+                .parse_u64()
+                .map(|bytes| bytes as usize)
+                .end(),
+            max_gas_limit: p
+                .chain(&mut err)
+                .get_opt_key("messageLimits")
+                .get_opt_key("maxGasLimit")
+                .parse_u256()
+                .end(),
+        };
+
         let allow_local_checkpoint_syncers = p
             .chain(&mut err)
             .get_opt_key("allowLocalCheckpointSyncers")
             .parse_bool()
             .unwrap_or(false);
 
+        let funding_thresholds = p
+            .chain(&mut err)
+            .get_opt_key("fundingThresholds")
+            .into_obj_iter()
+            .map(|itr| {
+                itr.map(|(chain, thresholds)| {
+                    let warning = thresholds
+                        .chain(&mut err)
+                        .get_opt_key("warning")
+                        .parse_u256()
+                        .end();
+                    let critical = thresholds
+                        .chain(&mut err)
+                        .get_opt_key("critical")
+                        .parse_u256()
+                        .end();
+                    (chain, FundingThresholdsConf { warning, critical })
+                })
+                .collect()
+            })
+            .unwrap_or_default();
+
+        let pause_submission_on_low_balance = p
+            .chain(&mut err)
+            .get_opt_key("pauseSubmissionOnLowBalance")
+            .parse_bool()
+            .unwrap_or(false);
+
+        let chain_halt_threshold = p
+            .chain(&mut err)
+            .get_opt_key("chainHaltThreshold")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .end();
+
+        let spend_limits = p
+            .chain(&mut err)
+            .get_opt_key("spendLimits")
+            .into_obj_iter()
+            .map(|itr| {
+                itr.map(|(chain, limits)| {
+                    let max_delivery_fee = limits
+                        .chain(&mut err)
+                        .get_opt_key("maxDeliveryFee")
+                        .parse_u256()
+                        .end();
+                    let hourly_cap = limits
+                        .chain(&mut err)
+                        .get_opt_key("hourlyCap")
+                        .parse_u256()
+                        .end();
+                    (
+                        chain,
+                        SpendLimitsConf {
+                            max_delivery_fee,
+                            hourly_cap,
+                        },
+                    )
+                })
+                .collect()
+            })
+            .unwrap_or_default();
+
+        let delivery_decorators = p
+            .chain(&mut err)
+            .get_opt_key("deliveryDecorators")
+            .into_obj_iter()
+            .map(|itr| {
+                itr.map(|(chain, decorators)| {
+                    let decorators = parse_json_array(decorators)
+                        .map(|(path, raw)| {
+                            ValueParser::new(path, &raw)
+                                .into_array_iter()
+                                .map(|itr| {
+                                    itr.filter_map(|decorator| {
+                                        parse_delivery_decorator(decorator)
+                                            .take_config_err(&mut err)
+                                    })
+                                    .collect_vec()
+                                })
+                                .take_config_err(&mut err)
+                                .unwrap_or_default()
+                        })
+                        .unwrap_or_default();
+                    (chain, decorators)
+                })
+                .collect()
+            })
+            .unwrap_or_default();
+
+        let gas_limit_overrides = p
+            .chain(&mut err)
+            .get_opt_key("gasLimitOverrides")
+            .into_obj_iter()
+            .map(|itr| {
+                itr.map(|(chain, overrides)| {
+                    let overrides = parse_json_array(overrides)
+                        .map(|(path, raw)| {
+                            ValueParser::new(path, &raw)
+                                .into_array_iter()
+                                .map(|itr| {
+                                    itr.filter_map(|conf| {
+                                        parse_gas_limit_override(conf).take_config_err(&mut err)
+                                    })
+                                    .collect::<HashMap<_, _>>()
+                                })
+                                .take_config_err(&mut err)
+                                .unwrap_or_default()
+                        })
+                        .unwrap_or_default();
+                    (chain, overrides)
+                })
+                .collect()
+            })
+            .unwrap_or_default();
+
+        let submission_lease_ttl = p
+            .chain(&mut err)
+            .get_opt_key("submissionLeaseTtl")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_SUBMISSION_LEASE_TTL);
+        let submission_lease_holder = p
+            .chain(&mut err)
+            .get_opt_key("submissionLeaseHolder")
+            .parse_string()
+            .map(str::to_owned)
+            .end();
+        let submission_lease = p
+            .chain(&mut err)
+            .get_opt_key("submissionLeaseDb")
+            .parse_string()
+            .map(|url| SubmissionLeaseConf {
+                url: url.to_owned(),
+                holder: submission_lease_holder,
+                ttl: submission_lease_ttl,
+            })
+            .end();
+
+        let shard_count = p
+            .chain(&mut err)
+            .get_opt_key("shardCount")
+            .parse_u32()
+            .unwrap_or(1);
+        let shard_index = p
+            .chain(&mut err)
+            .get_opt_key("shardIndex")
+            .parse_u32()
+            .unwrap_or(0);
+        if shard_count == 0 {
+            err.push(&p.cwp + "shard_count", eyre!("Shard count must be at least 1"));
+        } else if shard_index >= shard_count {
+            err.push(
+                &p.cwp + "shard_index",
+                eyre!("Shard index {shard_index} must be less than the shard count {shard_count}"),
+            );
+        }
+
+        let bootstrap_snapshot = p
+            .chain(&mut err)
+            .get_opt_key("bootstrapSnapshot")
+            .parse_string()
+            .end()
+            .map(|source| match Url::parse(source) {
+                Ok(url) if matches!(url.scheme(), "http" | "https") => SnapshotSource::Scraper(url),
+                _ => SnapshotSource::Dir(source.into()),
+            });
+
+        let shutdown_timeout = p
+            .chain(&mut err)
+            .get_opt_key("shutdownTimeout")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT);
+
+        let gas_reconciliation_epoch = p
+            .chain(&mut err)
+            .get_opt_key("gasReconciliationEpoch")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_GAS_RECONCILIATION_EPOCH);
+
+        let leader_election = p
+            .chain(&mut err)
+            .get_opt_key("leaderElection")
+            .and_then(parse_leader_election)
+            .end();
+
+        let payment_priority_weights = p
+            .chain(&mut err)
+            .get_opt_key("paymentPriorityWeights")
+            .into_obj_iter()
+            .map(|itr| {
+                itr.filter_map(|(chain, weight)| {
+                    weight
+                        .parse_f64()
+                        .take_config_err(&mut err)
+                        .map(|weight| (chain, weight))
+                })
+                .collect()
+            })
+            .unwrap_or_default();
+
+        let chain_roles: HashMap<String, ChainRolesConf> = p
+            .chain(&mut err)
+            .get_opt_key("chainRoles")
+            .into_obj_iter()
+            .map(|itr| {
+                itr.map(|(chain, roles)| {
+                    let mut flag = |key: &str| {
+                        roles
+                            .chain(&mut err)
+                            .get_opt_key(key)
+                            .parse_bool()
+                            .unwrap_or(true)
+                    };
+                    let roles = ChainRolesConf {
+                        index_messages: flag("indexMessages"),
+                        submit: flag("submit"),
+                        index_gas_payments: flag("indexGasPayments"),
+                    };
+                    (chain, roles)
+                })
+                .collect()
+            })
+            .unwrap_or_default();
+
         cfg_unwrap_all!(cwp, err: [base]);
 
         let skip_transaction_gas_limit_for = skip_transaction_gas_limit_for_names
@@ -236,6 +700,17 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             .map(|d| d.id())
             .collect();
 
+        let skip_ism_for = skip_ism_for_names
+            .into_iter()
+            .filter_map(|chain| {
+                base.lookup_domain(chain)
+                    .context("Missing configuration for a chain in `skipIsmFor`")
+                    .into_config_result(|| cwp + "skip_ism_for")
+                    .take_config_err(&mut err)
+            })
+            .map(|d| d.id())
+            .collect();
+
         let relay_chains: HashSet<HyperlaneDomain> = relay_chain_names
             .unwrap_or_default()
             .into_iter()
@@ -273,19 +748,81 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             })
             .unwrap_or_default();
 
+        let (raw_self_relay_path, raw_self_relay) = p
+            .get_opt_key("selfRelayGracePeriods")
+            .take_config_err_flat(&mut err)
+            .and_then(parse_json_array)
+            .unwrap_or_else(|| (&p.cwp + "self_relay_grace_periods", Value::Array(vec![])));
+
+        let self_relay_grace_periods = ValueParser::new(raw_self_relay_path, &raw_self_relay)
+            .into_array_iter()
+            .map(|itr| {
+                itr.filter_map(|rule| {
+                    // Required, as an empty matching list would match all messages
+                    let matching_list = rule
+                        .chain(&mut err)
+                        .get_key("matchingList")
+                        .and_then(parse_matching_list)
+                        .end()?;
+                    let grace_period = rule
+                        .chain(&mut err)
+                        .get_key("gracePeriod")
+                        .parse_u64()
+                        .end()?;
+                    Some(SelfRelayConf {
+                        matching_list,
+                        grace_period: Duration::from_secs(grace_period),
+                    })
+                })
+                .collect_vec()
+            })
+            .unwrap_or_default();
+
+        let roles =
+            |domain: &HyperlaneDomain| chain_roles.get(domain.name()).copied().unwrap_or_default();
+        let origin_chains = relay_chains
+            .iter()
+            .filter(|domain| roles(domain).index_messages)
+            .cloned()
+            .collect();
+        let destination_chains = relay_chains
+            .iter()
+            .filter(|domain| roles(domain).submit)
+            .cloned()
+            .collect();
+
         err.into_result(RelayerSettings {
             base,
             db,
-            origin_chains: relay_chains.clone(),
-            destination_chains: relay_chains,
+            origin_chains,
+            destination_chains,
             gas_payment_enforcement,
             whitelist,
             blacklist,
             address_blacklist,
+            address_denylist,
             transaction_gas_limit,
             skip_transaction_gas_limit_for,
+            skip_ism_for,
+            message_limits,
             allow_local_checkpoint_syncers,
             metric_app_contexts,
+            self_relay_grace_periods,
+            funding_thresholds,
+            pause_submission_on_low_balance,
+            chain_halt_threshold,
+            spend_limits,
+            delivery_decorators,
+            gas_limit_overrides,
+            submission_lease,
+            shard_index,
+            shard_count,
+            payment_priority_weights,
+            chain_roles,
+            bootstrap_snapshot,
+            shutdown_timeout,
+            leader_election,
+            gas_reconciliation_epoch,
         })
     }
 }
@@ -310,6 +847,74 @@ fn parse_json_array(p: ValueParser) -> Option<(ConfigPath, Value)> {
     }
 }
 
+/// Parse the gas payment enforcement policies, in the order in which they
+/// are evaluated. Messages use the policy of the first matching list they
+/// match, and everything is enforced with the default policy if none is given.
+fn parse_gas_payment_enforcement(p: ValueParser) -> ConfigResult<Vec<GasPaymentEnforcementConf>> {
+    let mut err = ConfigParsingError::default();
+
+    let mut gas_payment_enforcement = p.into_array_iter().map(|itr| {
+        itr.filter_map(|policy| {
+            let policy_type = policy.chain(&mut err).get_opt_key("type").parse_string().end();
+            let minimum_is_defined = matches!(policy.get_opt_key("minimum"), Ok(Some(_)));
+
+            let matching_list = policy.chain(&mut err).get_opt_key("matchingList").and_then(parse_matching_list).unwrap_or_default();
+
+            let parse_minimum = |p| GasPaymentEnforcementPolicy::Minimum { payment: p };
+            match policy_type {
+                Some("minimum") => policy.chain(&mut err).get_opt_key("payment").parse_u256().end().map(parse_minimum),
+                None if minimum_is_defined => policy.chain(&mut err).get_opt_key("payment").parse_u256().end().map(parse_minimum),
+                Some("none") | None => Some(GasPaymentEnforcementPolicy::None),
+                Some(policy_type @ ("onChainFeeQuoting" | "igpQuote")) => {
+                    let gas_fraction = policy.chain(&mut err)
+                        .get_opt_key("gasFraction")
+                        .parse_string()
+                        .map(|v| v.replace(' ', ""))
+                        .unwrap_or_else(|| "1/2".to_owned());
+                    let (numerator, denominator) = gas_fraction
+                        .split_once('/')
+                        .ok_or_else(|| eyre!("Invalid `gas_fraction` for {policy_type} gas payment enforcement policy; expected `numerator / denominator`"))
+                        .take_err(&mut err, || &policy.cwp + "gas_fraction")
+                        .unwrap_or(("1", "1"));
+                    let gas_fraction_numerator = numerator
+                        .parse()
+                        .context("Error parsing gas fraction numerator")
+                        .take_err(&mut err, || &policy.cwp + "gas_fraction")
+                        .unwrap_or(1);
+                    let gas_fraction_denominator = denominator
+                        .parse()
+                        .context("Error parsing gas fraction denominator")
+                        .take_err(&mut err, || &policy.cwp + "gas_fraction")
+                        .unwrap_or(1);
+
+                    if policy_type == "igpQuote" {
+                        Some(GasPaymentEnforcementPolicy::IgpQuote {
+                            gas_fraction_numerator,
+                            gas_fraction_denominator,
+                        })
+                    } else {
+                        Some(GasPaymentEnforcementPolicy::OnChainFeeQuoting {
+                            gas_fraction_numerator,
+                            gas_fraction_denominator,
+                        })
+                    }
+                }
+                Some(pt) => Err(eyre!("Unknown gas payment enforcement policy type `{pt}`"))
+                    .take_err(&mut err, || &policy.cwp + "type"),
+            }.map(|policy| GasPaymentEnforcementConf {
+                policy,
+                matching_list,
+            })
+        }).collect_vec()
+    }).unwrap_or_default();
+
+    if gas_payment_enforcement.is_empty() {
+        gas_payment_enforcement.push(GasPaymentEnforcementConf::default());
+    }
+
+    err.into_result(gas_payment_enforcement)
+}
+
 fn parse_matching_list(p: ValueParser) -> ConfigResult<MatchingList> {
     let mut err = ConfigParsingError::default();
 
@@ -326,17 +931,180 @@ fn parse_matching_list(p: ValueParser) -> ConfigResult<MatchingList> {
     err.into_result(ml)
 }
 
+fn parse_delivery_decorator(p: ValueParser) -> ConfigResult<DeliveryDecoratorConf> {
+    let mut err = ConfigParsingError::default();
+
+    let matching_list = p
+        .chain(&mut err)
+        .get_opt_key("matchingList")
+        .and_then(parse_matching_list)
+        .unwrap_or_default();
+
+    let decorator = match p.chain(&mut err).get_key("type").parse_string().end() {
+        Some("appendMetadata") => p
+            .chain(&mut err)
+            .get_key("metadata")
+            .parse_string()
+            .end()
+            .and_then(|metadata| {
+                hex::decode(metadata.trim_start_matches("0x"))
+                    .take_err(&mut err, || &p.cwp + "metadata")
+            })
+            .map(|metadata| DeliveryDecoratorType::AppendMetadata { metadata }),
+        Some("extraGas") => p
+            .chain(&mut err)
+            .get_key("gas")
+            .parse_u256()
+            .end()
+            .map(|gas| DeliveryDecoratorType::ExtraGas { gas }),
+        Some("preflight") => p
+            .chain(&mut err)
+            .get_key("url")
+            .parse_from_str("Expected preflight url")
+            .end()
+            .map(|url| DeliveryDecoratorType::Preflight { url }),
+        Some(t) => Err(eyre!("Unknown delivery decorator type `{t}`"))
+            .take_err(&mut err, || &p.cwp + "type"),
+        None => None,
+    };
+
+    cfg_unwrap_all!(&p.cwp, err: [decorator]);
+    err.into_result(DeliveryDecoratorConf {
+        matching_list,
+        decorator,
+    })
+}
+
+fn parse_address_denylist(p: ValueParser) -> ConfigResult<AddressDenylistConf> {
+    let mut err = ConfigParsingError::default();
+
+    let url = p
+        .chain(&mut err)
+        .get_key("url")
+        .parse_from_str("Expected denylist url")
+        .end();
+    let api_key = p
+        .chain(&mut err)
+        .get_opt_key("apiKey")
+        .parse_string()
+        .map(str::to_owned)
+        .end();
+    let refresh_interval = p
+        .chain(&mut err)
+        .get_opt_key("refreshInterval")
+        .parse_u64()
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_DENYLIST_REFRESH_INTERVAL);
+
+    cfg_unwrap_all!(&p.cwp, err: [url]);
+    err.into_result(AddressDenylistConf {
+        url,
+        api_key,
+        refresh_interval,
+    })
+}
+
+fn parse_leader_election(p: ValueParser) -> ConfigResult<LeaderElectionConf> {
+    let mut err = ConfigParsingError::default();
+
+    let lease_duration = p
+        .chain(&mut err)
+        .get_opt_key("leaseDuration")
+        .parse_u64()
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_LEADER_LEASE_DURATION);
+
+    let backend = match p.chain(&mut err).get_key("type").parse_string().end() {
+        Some("kubernetes") => p
+            .chain(&mut err)
+            .get_key("leaseName")
+            .parse_string()
+            .end()
+            .map(|lease_name| LeaderElectionBackend::Kubernetes {
+                lease_name: lease_name.to_owned(),
+                namespace: p
+                    .chain(&mut err)
+                    .get_opt_key("namespace")
+                    .parse_string()
+                    .map(str::to_owned)
+                    .end(),
+            }),
+        Some("postgres") => p
+            .chain(&mut err)
+            .get_key("url")
+            .parse_string()
+            .end()
+            .map(|url| LeaderElectionBackend::Postgres {
+                url: url.to_owned(),
+                lock_id: p
+                    .chain(&mut err)
+                    .get_opt_key("lockId")
+                    .parse_u32()
+                    .unwrap_or(DEFAULT_LEADER_LOCK_ID),
+            }),
+        Some(t) => {
+            Err(eyre!("Unknown leader election type `{t}`")).take_err(&mut err, || &p.cwp + "type")
+        }
+        None => None,
+    };
+
+    cfg_unwrap_all!(&p.cwp, err: [backend]);
+    err.into_result(LeaderElectionConf {
+        backend,
+        lease_duration,
+    })
+}
+
+fn parse_gas_limit_override(p: ValueParser) -> ConfigResult<(H256, GasLimitOverrideConf)> {
+    let mut err = ConfigParsingError::default();
+
+    let recipient = p
+        .chain(&mut err)
+        .get_key("recipientAddress")
+        .parse_address_hash()
+        .end();
+    let multiplier = p
+        .chain(&mut err)
+        .get_opt_key("multiplier")
+        .parse_f64()
+        .end();
+    if multiplier.is_some_and(|multiplier| multiplier <= 0.) {
+        err.push(
+            &p.cwp + "multiplier",
+            eyre!("Gas limit multiplier must be greater than zero"),
+        );
+    }
+    let minimum = p.chain(&mut err).get_opt_key("minimum").parse_u256().end();
+
+    cfg_unwrap_all!(&p.cwp, err: [recipient]);
+    err.into_result((
+        recipient,
+        GasLimitOverrideConf {
+            multiplier,
+            minimum,
+        },
+    ))
+}
+
 fn parse_address_list(
     str: &str,
     err: &mut ConfigParsingError,
     err_path: impl Fn() -> ConfigPath,
 ) -> Vec<Vec<u8>> {
+    if str.trim().is_empty() {
+        return vec![];
+    }
     str.split(',')
         .filter_map(|s| {
             let mut s = s.trim().to_owned();
             if let Some(stripped) = s.strip_prefix("0x") {
                 s = stripped.to_owned();
             }
+            // An empty address would be found in every message
+            if s.is_empty() {
+                err.push(err_path(), eyre!("Empty address in address list"));
+                return None;
+            }
             hex::decode(s).take_err(err, &err_path)
         })
         .collect_vec()
@@ -345,7 +1113,7 @@ fn parse_address_list(
 #[cfg(test)]
 mod test {
     use super::*;
-    use hyperlane_core::H160;
+    use hyperlane_core::{HyperlaneMessage, H160};
 
     #[test]
     fn test_parse_address_blacklist() {
@@ -371,7 +1139,74 @@ mod test {
         );
         let mut err = ConfigParsingError::default();
         let res = parse_address_list(&input, &mut err, ConfigPath::default);
-        assert_eq!(res, vec![valid_address1, valid_address2]);
+        assert_eq!(res, vec![valid_address1.clone(), valid_address2]);
         assert!(!err.is_ok());
+
+        // Empty addresses would block every message
+        let input = format!("0x{}, 0x", hex::encode(&valid_address1));
+        let mut err = ConfigParsingError::default();
+        let res = parse_address_list(&input, &mut err, ConfigPath::default);
+        assert_eq!(res, vec![valid_address1]);
+        assert!(!err.is_ok());
+
+        let mut err = ConfigParsingError::default();
+        assert!(parse_address_list("", &mut err, ConfigPath::default).is_empty());
+        assert!(err.is_ok());
+    }
+
+    #[test]
+    fn test_parse_gas_payment_enforcement_in_order() {
+        let own_route = H256::from_low_u64_be(1);
+        let raw = serde_json::json!([
+            { "type": "none", "matchinglist": [{ "senderaddress": own_route }] },
+            { "type": "none", "matchinglist": [{ "recipientaddress": own_route }] },
+            { "type": "onChainFeeQuoting", "gasfraction": "1 / 3" },
+        ]);
+        let policies =
+            parse_gas_payment_enforcement(ValueParser::new(ConfigPath::default(), &raw)).unwrap();
+        assert_eq!(policies.len(), 3);
+
+        let message = |sender, recipient| HyperlaneMessage {
+            sender,
+            recipient,
+            ..Default::default()
+        };
+        let first_match = |message: &HyperlaneMessage| {
+            policies
+                .iter()
+                .position(|p| p.matching_list.msg_matches(message, true))
+        };
+        assert_eq!(first_match(&message(own_route, H256::zero())), Some(0));
+        assert_eq!(first_match(&message(H256::zero(), own_route)), Some(1));
+        assert_eq!(first_match(&message(own_route, own_route)), Some(0));
+        assert_eq!(first_match(&message(H256::zero(), H256::zero())), Some(2));
+
+        assert!(matches!(
+            policies[0].policy,
+            GasPaymentEnforcementPolicy::None
+        ));
+        assert!(matches!(
+            policies[2].policy,
+            GasPaymentEnforcementPolicy::OnChainFeeQuoting {
+                gas_fraction_numerator: 1,
+                gas_fraction_denominator: 3,
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_gas_payment_enforcement_defaults() {
+        let raw = serde_json::json!([]);
+        let policies =
+            parse_gas_payment_enforcement(ValueParser::new(ConfigPath::default(), &raw)).unwrap();
+        assert_eq!(policies.len(), 1);
+        assert!(policies[0]
+            .matching_list
+            .msg_matches(&HyperlaneMessage::default(), true));
+
+        let raw = serde_json::json!([{ "type": "unknown" }]);
+        assert!(
+            parse_gas_payment_enforcement(ValueParser::new(ConfigPath::default(), &raw)).is_err()
+        );
     }
 }
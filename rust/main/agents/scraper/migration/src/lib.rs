@@ -13,6 +13,12 @@ mod m20230309_000003_create_table_transaction;
 mod m20230309_000004_create_table_delivered_message;
 mod m20230309_000004_create_table_gas_payment;
 mod m20230309_000005_create_table_message;
+mod m20241016_000006_create_table_ica_call;
+mod m20241101_000007_create_table_route_backlog;
+mod m20241101_000007_create_table_route_hourly_stats;
+mod m20241101_000007_create_table_route_latency_bucket;
+mod m20241110_000008_create_table_backfill_chunk;
+mod m20241120_000009_create_table_validator_announcement;
 
 pub struct Migrator;
 
@@ -30,6 +36,12 @@ impl MigratorTrait for Migrator {
             Box::new(m20230309_000004_create_table_gas_payment::Migration),
             Box::new(m20230309_000004_create_table_delivered_message::Migration),
             Box::new(m20230309_000005_create_table_message::Migration),
+            Box::new(m20241016_000006_create_table_ica_call::Migration),
+            Box::new(m20241101_000007_create_table_route_hourly_stats::Migration),
+            Box::new(m20241101_000007_create_table_route_latency_bucket::Migration),
+            Box::new(m20241101_000007_create_table_route_backlog::Migration),
+            Box::new(m20241110_000008_create_table_backfill_chunk::Migration),
+            Box::new(m20241120_000009_create_table_validator_announcement::Migration),
         ]
     }
 }
@@ -0,0 +1,97 @@
+use sea_orm_migration::prelude::*;
+
+use crate::l20230309_types::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(IcaCall::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(IcaCall::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(IcaCall::TimeCreated)
+                            .timestamp()
+                            .not_null()
+                            .default("NOW()"),
+                    )
+                    .col(ColumnDef::new_with_type(IcaCall::MsgId, Hash).not_null())
+                    .col(ColumnDef::new(IcaCall::CallIndex).unsigned().not_null())
+                    .col(ColumnDef::new_with_type(IcaCall::Owner, Address).not_null())
+                    .col(ColumnDef::new_with_type(IcaCall::Ism, Address).not_null())
+                    .col(ColumnDef::new_with_type(IcaCall::Target, Address).not_null())
+                    .col(ColumnDef::new_with_type(IcaCall::Value, Wei).not_null())
+                    .col(ColumnDef::new(IcaCall::Calldata).binary())
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(IcaCall::MsgId)
+                            .col(IcaCall::CallIndex),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table(IcaCall::Table)
+                    .name("ica_call_msg_id_idx")
+                    .col(IcaCall::MsgId)
+                    .index_type(IndexType::Hash)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table(IcaCall::Table)
+                    .name("ica_call_target_idx")
+                    .col(IcaCall::Target)
+                    .index_type(IndexType::Hash)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(IcaCall::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum IcaCall {
+    Table,
+    /// Unique database ID
+    Id,
+    /// Time of record creation
+    TimeCreated,
+    /// Unique id of the interchain account message the call is part of
+    MsgId,
+    /// Position of the call in the message
+    CallIndex,
+    /// Owner of the interchain account making the call
+    Owner,
+    /// ISM of the interchain account making the call
+    Ism,
+    /// Address called on the destination
+    Target,
+    /// Value sent with the call
+    Value,
+    /// Calldata of the call
+    Calldata,
+}
@@ -0,0 +1,83 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230309_000001_create_table_domain::Domain;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RouteBacklog::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RouteBacklog::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(RouteBacklog::TimeUpdated)
+                            .timestamp()
+                            .not_null()
+                            .default("NOW()"),
+                    )
+                    .col(ColumnDef::new(RouteBacklog::Origin).unsigned().not_null())
+                    .col(
+                        ColumnDef::new(RouteBacklog::Destination)
+                            .unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RouteBacklog::UndeliveredCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(RouteBacklog::Origin)
+                            .to(Domain::Table, Domain::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(RouteBacklog::Destination)
+                            .to(Domain::Table, Domain::Id),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(RouteBacklog::Origin)
+                            .col(RouteBacklog::Destination),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RouteBacklog::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum RouteBacklog {
+    Table,
+    /// Unique database ID
+    Id,
+    /// Time the record was last updated
+    TimeUpdated,
+    /// Domain ID of the origin chain of the route
+    Origin,
+    /// Domain ID of the destination chain of the route
+    Destination,
+    /// Messages dispatched on the route that haven't been delivered yet
+    UndeliveredCount,
+}
@@ -0,0 +1,125 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230309_000001_create_table_domain::Domain;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RouteHourlyStats::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RouteHourlyStats::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(RouteHourlyStats::TimeUpdated)
+                            .timestamp()
+                            .not_null()
+                            .default("NOW()"),
+                    )
+                    .col(
+                        ColumnDef::new(RouteHourlyStats::Origin)
+                            .unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RouteHourlyStats::Destination)
+                            .unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RouteHourlyStats::Hour)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RouteHourlyStats::DispatchedCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(RouteHourlyStats::DeliveredCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(RouteHourlyStats::LatencyP50Secs).big_integer())
+                    .col(ColumnDef::new(RouteHourlyStats::LatencyP95Secs).big_integer())
+                    .col(ColumnDef::new(RouteHourlyStats::MaxLatencySecs).big_integer())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(RouteHourlyStats::Origin)
+                            .to(Domain::Table, Domain::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(RouteHourlyStats::Destination)
+                            .to(Domain::Table, Domain::Id),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(RouteHourlyStats::Origin)
+                            .col(RouteHourlyStats::Destination)
+                            .col(RouteHourlyStats::Hour),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table(RouteHourlyStats::Table)
+                    .name("route_hourly_stats_hour_idx")
+                    .col(RouteHourlyStats::Hour)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RouteHourlyStats::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum RouteHourlyStats {
+    Table,
+    /// Unique database ID
+    Id,
+    /// Time the record was last updated
+    TimeUpdated,
+    /// Domain ID of the origin chain of the route
+    Origin,
+    /// Domain ID of the destination chain of the route
+    Destination,
+    /// Start of the hour the stats are for
+    Hour,
+    /// Messages dispatched on the route during the hour
+    DispatchedCount,
+    /// Messages delivered on the route during the hour
+    DeliveredCount,
+    /// Median time between dispatch and delivery of the messages delivered
+    /// during the hour, in seconds
+    LatencyP50Secs,
+    /// 95th percentile of the time between dispatch and delivery of the
+    /// messages delivered during the hour, in seconds
+    LatencyP95Secs,
+    /// Longest time between dispatch and delivery of the messages delivered
+    /// during the hour, in seconds
+    MaxLatencySecs,
+}
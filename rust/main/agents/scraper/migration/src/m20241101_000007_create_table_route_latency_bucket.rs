@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230309_000001_create_table_domain::Domain;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RouteLatencyBucket::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RouteLatencyBucket::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(RouteLatencyBucket::Origin)
+                            .unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RouteLatencyBucket::Destination)
+                            .unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RouteLatencyBucket::Hour)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RouteLatencyBucket::Bucket)
+                            .unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RouteLatencyBucket::Count)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(RouteLatencyBucket::Origin)
+                            .to(Domain::Table, Domain::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(RouteLatencyBucket::Destination)
+                            .to(Domain::Table, Domain::Id),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(RouteLatencyBucket::Origin)
+                            .col(RouteLatencyBucket::Destination)
+                            .col(RouteLatencyBucket::Hour)
+                            .col(RouteLatencyBucket::Bucket),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RouteLatencyBucket::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum RouteLatencyBucket {
+    Table,
+    /// Unique database ID
+    Id,
+    /// Domain ID of the origin chain of the route
+    Origin,
+    /// Domain ID of the destination chain of the route
+    Destination,
+    /// Start of the hour the messages were delivered in
+    Hour,
+    /// Index of the latency bucket, see the scraper for the bucket bounds
+    Bucket,
+    /// Messages delivered with a latency in the bucket
+    Count,
+}
@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230309_000001_create_table_domain::Domain;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(BackfillChunk::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(BackfillChunk::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(BackfillChunk::TimeCreated)
+                            .timestamp()
+                            .not_null()
+                            .default("NOW()"),
+                    )
+                    .col(ColumnDef::new(BackfillChunk::Domain).unsigned().not_null())
+                    .col(ColumnDef::new(BackfillChunk::Label).text().not_null())
+                    .col(
+                        ColumnDef::new(BackfillChunk::FromBlock)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BackfillChunk::ToBlock)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(BackfillChunk::Domain)
+                            .to(Domain::Table, Domain::Id),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(BackfillChunk::Domain)
+                            .col(BackfillChunk::Label)
+                            .col(BackfillChunk::FromBlock)
+                            .col(BackfillChunk::ToBlock),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BackfillChunk::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum BackfillChunk {
+    Table,
+    /// Unique database ID
+    Id,
+    /// Time when the record was created
+    TimeCreated,
+    /// Hyperlane domain ID the backfill is indexing
+    Domain,
+    /// Name of the data the backfill is indexing, e.g. `message_dispatch`
+    Label,
+    /// First block of the chunk
+    FromBlock,
+    /// Last block of the chunk, inclusive
+    ToBlock,
+}
@@ -4,15 +4,19 @@ use async_trait::async_trait;
 use derive_more::AsRef;
 use futures::future::try_join_all;
 use hyperlane_base::{
-    broadcast::BroadcastMpscSender, metrics::AgentMetrics, settings::IndexSettings, AgentMetadata,
-    BaseAgent, ChainMetrics, ContractSyncMetrics, ContractSyncer, CoreMetrics, HyperlaneAgentCore,
-    MetricsUpdater, SyncOptions,
+    broadcast::BroadcastMpscSender, metrics::AgentMetrics, settings::IndexSettings, spawn_named,
+    AgentMetadata, BaseAgent, ChainMetrics, ContractSyncMetrics, ContractSyncer, CoreMetrics,
+    HyperlaneAgentCore, MetricsUpdater, SyncOptions, TaskSupervisor,
 };
-use hyperlane_core::{Delivery, HyperlaneDomain, HyperlaneMessage, InterchainGasPayment, H512};
-use tokio::{sync::mpsc::Receiver as MpscReceiver, task::JoinHandle};
-use tracing::{info_span, instrument::Instrumented, trace, Instrument};
+use hyperlane_core::{
+    Delivery, HyperlaneDomain, HyperlaneMessage, InterchainGasPayment, ValidatorAnnouncement, H512,
+};
+use tokio::task::JoinHandle;
+use tracing::{info_span, instrument::Instrumented, trace, warn, Instrument};
 
-use crate::{chain_scraper::HyperlaneSqlDb, db::ScraperDb, settings::ScraperSettings};
+use crate::{
+    chain_scraper::HyperlaneSqlDb, db::ScraperDb, query_api::QueryApi, settings::ScraperSettings,
+};
 
 /// A message explorer scraper agent
 #[derive(Debug, AsRef)]
@@ -22,7 +26,10 @@ pub struct Scraper {
     core: HyperlaneAgentCore,
     contract_sync_metrics: Arc<ContractSyncMetrics>,
     scrapers: HashMap<u32, ChainScraper>,
+    db: ScraperDb,
     settings: ScraperSettings,
+    /// Restarts the indexing tasks of a chain if they fail
+    supervisor: TaskSupervisor,
     core_metrics: Arc<CoreMetrics>,
     agent_metrics: AgentMetrics,
     chain_metrics: ChainMetrics,
@@ -46,7 +53,6 @@ impl BaseAgent for Scraper {
         metrics: Arc<CoreMetrics>,
         agent_metrics: AgentMetrics,
         chain_metrics: ChainMetrics,
-        _tokio_console_server: console_subscriber::Server,
     ) -> eyre::Result<Self>
     where
         Self: Sized,
@@ -56,6 +62,7 @@ impl BaseAgent for Scraper {
 
         let contract_sync_metrics = Arc::new(ContractSyncMetrics::new(&metrics));
         let mut scrapers: HashMap<u32, ChainScraper> = HashMap::new();
+        let ica_routers = Arc::new(settings.ica_routers.clone());
 
         for domain in settings.chains_to_scrape.iter() {
             let chain_setup = settings.chain_setup(domain).expect("Missing chain config");
@@ -68,6 +75,7 @@ impl BaseAgent for Scraper {
                     .await?
                     .into(),
                 &chain_setup.index.clone(),
+                ica_routers.clone(),
             )
             .await?;
             scrapers.insert(
@@ -86,7 +94,9 @@ impl BaseAgent for Scraper {
             core,
             contract_sync_metrics,
             scrapers,
+            db,
             settings,
+            supervisor: TaskSupervisor::new(&metrics),
             core_metrics: metrics,
             agent_metrics,
             chain_metrics,
@@ -103,7 +113,19 @@ impl BaseAgent for Scraper {
             .settings
             .server(self.core_metrics.clone())
             .expect("Failed to create server");
-        let server_task = server.run().instrument(info_span!("Relayer server"));
+        let mut custom_routes = vec![];
+        if self.settings.query_api {
+            let formatters = self
+                .settings
+                .chains
+                .values()
+                .map(|chain| (chain.domain.id(), chain.connection.address_formatter()))
+                .collect();
+            custom_routes.push(QueryApi::new(self.db.clone(), Arc::new(formatters)).get_route());
+        }
+        let server_task = server
+            .run_with_custom_routes(custom_routes)
+            .instrument(info_span!("Relayer server"));
         tasks.push(server_task);
 
         for (domain, scraper) in self.scrapers.iter() {
@@ -136,7 +158,7 @@ impl Scraper {
         let index_settings = scraper.index_settings.clone();
         let domain = scraper.domain.clone();
 
-        let mut tasks = Vec::with_capacity(2);
+        let mut tasks = Vec::with_capacity(4);
         let (message_indexer, maybe_broadcaster) = self
             .build_message_indexer(
                 domain.clone(),
@@ -159,17 +181,27 @@ impl Scraper {
         );
         tasks.push(
             self.build_interchain_gas_payment_indexer(
-                domain,
+                domain.clone(),
+                self.core_metrics.clone(),
+                self.contract_sync_metrics.clone(),
+                db.clone(),
+                index_settings.clone(),
+                maybe_broadcaster,
+            )
+            .await,
+        );
+        tasks.extend(
+            self.build_validator_announcement_indexer(
+                domain.clone(),
                 self.core_metrics.clone(),
                 self.contract_sync_metrics.clone(),
                 db,
                 index_settings.clone(),
-                BroadcastMpscSender::<H512>::map_get_receiver(maybe_broadcaster.as_ref()).await,
             )
             .await,
         );
 
-        tokio::spawn(async move {
+        spawn_named("scraper", Some(&domain), async move {
             // If any of the tasks panic, we want to propagate it, so we unwrap
             try_join_all(tasks).await.unwrap();
         })
@@ -196,16 +228,29 @@ impl Scraper {
                 &domain,
                 &metrics.clone(),
                 &contract_sync_metrics.clone(),
-                db.into(),
+                db.clone().into(),
             )
             .await
             .unwrap();
-        let cursor = sync
-            .cursor(index_settings.clone())
-            .await
-            .unwrap_or_else(|err| panic!("Error getting cursor for domain {domain}: {err}"));
         let maybe_broadcaser = sync.get_broadcaster();
-        let task = tokio::spawn(async move { sync.sync("message_dispatch", cursor.into()).await })
+        let progress = Arc::new(db);
+        let sync_domain = domain.clone();
+        let task = self
+            .supervisor
+            .spawn("message_sync", Some(&domain), move || {
+                let sync = sync.clone();
+                let index_settings = index_settings.clone();
+                let progress = progress.clone();
+                let domain = sync_domain.clone();
+                async move {
+                    let backfill = index_settings.backfill.clone();
+                    let cursor = sync.cursor(index_settings).await.unwrap_or_else(|err| {
+                        panic!("Error getting cursor for domain {domain}: {err}")
+                    });
+                    sync.sync_with_backfill("message_dispatch", cursor.into(), backfill, progress)
+                        .await
+                }
+            })
             .instrument(
                 info_span!("ChainContractSync", chain=%domain.name(), event="message_dispatch"),
             );
@@ -233,13 +278,30 @@ impl Scraper {
             .unwrap();
 
         let label = "message_delivery";
-        let cursor = sync
-            .cursor(index_settings.clone())
-            .await
-            .unwrap_or_else(|err| panic!("Error getting cursor for domain {domain}: {err}"));
-        // there is no txid receiver for delivery indexing, since delivery txs aren't batched with
-        // other types of indexed txs / events
-        tokio::spawn(async move { sync.sync(label, SyncOptions::new(Some(cursor), None)).await })
+        let db = Arc::new(db);
+        let sync_domain = domain.clone();
+        self.supervisor
+            .spawn("delivery_sync", Some(&domain), move || {
+                let sync = sync.clone();
+                let index_settings = index_settings.clone();
+                let db = db.clone();
+                let domain = sync_domain.clone();
+                async move {
+                    let backfill = index_settings.backfill.clone();
+                    let cursor = sync.cursor(index_settings).await.unwrap_or_else(|err| {
+                        panic!("Error getting cursor for domain {domain}: {err}")
+                    });
+                    // there is no txid receiver for delivery indexing, since delivery txs aren't
+                    // batched with other types of indexed txs / events
+                    sync.sync_with_backfill(
+                        label,
+                        SyncOptions::new(Some(cursor), None),
+                        backfill,
+                        db,
+                    )
+                    .await
+                }
+            })
             .instrument(info_span!("ChainContractSync", chain=%domain.name(), event=label))
     }
 
@@ -250,7 +312,7 @@ impl Scraper {
         contract_sync_metrics: Arc<ContractSyncMetrics>,
         db: HyperlaneSqlDb,
         index_settings: IndexSettings,
-        tx_id_receiver: Option<MpscReceiver<H512>>,
+        tx_id_broadcaster: Option<BroadcastMpscSender<H512>>,
     ) -> Instrumented<JoinHandle<()>> {
         let sync = self
             .as_ref()
@@ -265,14 +327,87 @@ impl Scraper {
             .unwrap();
 
         let label = "gas_payment";
-        let cursor = sync
-            .cursor(index_settings.clone())
+        let db = Arc::new(db);
+        let sync_domain = domain.clone();
+        self.supervisor
+            .spawn("gas_payment_sync", Some(&domain), move || {
+                let sync = sync.clone();
+                let index_settings = index_settings.clone();
+                let db = db.clone();
+                let domain = sync_domain.clone();
+                let tx_id_broadcaster = tx_id_broadcaster.clone();
+                async move {
+                    let backfill = index_settings.backfill.clone();
+                    let cursor = sync.cursor(index_settings).await.unwrap_or_else(|err| {
+                        panic!("Error getting cursor for domain {domain}: {err}")
+                    });
+                    let tx_id_receiver =
+                        BroadcastMpscSender::map_get_receiver(tx_id_broadcaster.as_ref()).await;
+                    sync.sync_with_backfill(
+                        label,
+                        SyncOptions::new(Some(cursor), tx_id_receiver),
+                        backfill,
+                        db,
+                    )
+                    .await
+                }
+            })
+            .instrument(info_span!("ChainContractSync", chain=%domain.name(), event=label))
+    }
+
+    /// Validator announcements are indexed on the chains which support it,
+    /// without holding up the indexing of the other chains.
+    async fn build_validator_announcement_indexer(
+        &self,
+        domain: HyperlaneDomain,
+        metrics: Arc<CoreMetrics>,
+        contract_sync_metrics: Arc<ContractSyncMetrics>,
+        db: HyperlaneSqlDb,
+        index_settings: IndexSettings,
+    ) -> Option<Instrumented<JoinHandle<()>>> {
+        let sync = match self
+            .as_ref()
+            .settings
+            .watermark_contract_sync::<ValidatorAnnouncement, _>(
+                &domain,
+                &metrics.clone(),
+                &contract_sync_metrics.clone(),
+                Arc::new(db.clone()),
+            )
             .await
-            .unwrap_or_else(|err| panic!("Error getting cursor for domain {domain}: {err}"));
-        tokio::spawn(async move {
-            sync.sync(label, SyncOptions::new(Some(cursor), tx_id_receiver))
-                .await
-        })
-        .instrument(info_span!("ChainContractSync", chain=%domain.name(), event=label))
+        {
+            Ok(sync) => sync,
+            Err(err) => {
+                warn!(domain = %domain.name(), ?err, "Not indexing validator announcements");
+                return None;
+            }
+        };
+
+        let label = "validator_announcement";
+        let db = Arc::new(db);
+        let sync_domain = domain.clone();
+        let task = self
+            .supervisor
+            .spawn("validator_announcement_sync", Some(&domain), move || {
+                let sync = sync.clone();
+                let index_settings = index_settings.clone();
+                let db = db.clone();
+                let domain = sync_domain.clone();
+                async move {
+                    let backfill = index_settings.backfill.clone();
+                    let cursor = sync.cursor(index_settings).await.unwrap_or_else(|err| {
+                        panic!("Error getting cursor for domain {domain}: {err}")
+                    });
+                    sync.sync_with_backfill(
+                        label,
+                        SyncOptions::new(Some(cursor), None),
+                        backfill,
+                        db,
+                    )
+                    .await
+                }
+            })
+            .instrument(info_span!("ChainContractSync", chain=%domain.name(), event=label));
+        Some(task)
     }
 }
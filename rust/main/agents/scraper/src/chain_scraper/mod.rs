@@ -3,22 +3,30 @@
 //! This module (and children) are responsible for scraping blockchain data and
 //! keeping things updated.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+    sync::Arc,
+};
 
 use async_trait::async_trait;
 use eyre::Result;
 use hyperlane_base::settings::IndexSettings;
 use hyperlane_core::{
-    unwrap_or_none_result, BlockInfo, Delivery, HyperlaneDomain, HyperlaneLogStore,
-    HyperlaneMessage, HyperlaneProvider, HyperlaneSequenceAwareIndexerStoreReader,
-    HyperlaneWatermarkedLogStore, Indexed, InterchainGasPayment, LogMeta, H256,
+    unwrap_or_none_result, BlockInfo, Delivery, HyperlaneBackfillStore, HyperlaneDomain,
+    HyperlaneLogStore, HyperlaneMessage, HyperlaneProvider,
+    HyperlaneSequenceAwareIndexerStoreReader, HyperlaneWatermarkedLogStore, Indexed,
+    InterchainGasPayment, LogMeta, ValidatorAnnouncement, H256,
 };
 use itertools::Itertools;
 use tracing::{trace, warn};
 
-use crate::db::{
-    BasicBlock, BlockCursor, ScraperDb, StorableDelivery, StorableMessage, StorablePayment,
-    StorableTxn,
+use crate::{
+    db::{
+        BasicBlock, BlockCursor, ScraperDb, StorableDelivery, StorableIcaMessage, StorableMessage,
+        StorablePayment, StorableTxn, StorableValidatorAnnouncement,
+    },
+    ica::IcaMessage,
 };
 
 /// Maximum number of records to query at a time. This came about because when a
@@ -36,6 +44,8 @@ pub struct HyperlaneSqlDb {
     db: ScraperDb,
     provider: Arc<dyn HyperlaneProvider>,
     cursor: Arc<BlockCursor>,
+    /// Interchain account routers, whose messages are decoded
+    ica_routers: Arc<HashSet<H256>>,
 }
 
 #[allow(unused)]
@@ -46,6 +56,7 @@ impl HyperlaneSqlDb {
         domain: HyperlaneDomain,
         provider: Arc<dyn HyperlaneProvider>,
         index_settings: &IndexSettings,
+        ica_routers: Arc<HashSet<H256>>,
     ) -> Result<Self> {
         let cursor = Arc::new(
             db.block_cursor(domain.id(), index_settings.from as u64)
@@ -57,6 +68,7 @@ impl HyperlaneSqlDb {
             provider,
             mailbox_address,
             cursor,
+            ica_routers,
         })
     }
 
@@ -108,6 +120,47 @@ impl HyperlaneSqlDb {
         Ok(txns_with_ids.map(move |TxnWithId { hash, id: txn_id }| TxnWithId { hash, id: txn_id }))
     }
 
+    /// The database ids of the stored transactions the logs were emitted in
+    async fn stored_txn_ids(&self, log_meta: impl Iterator<Item = &LogMeta>) -> Result<Vec<i64>> {
+        let hashes: HashSet<H256> = log_meta
+            .map(|meta| {
+                meta.transaction_id
+                    .try_into()
+                    .expect("256-bit transaction ids are the maximum supported at this time")
+            })
+            .collect();
+        if hashes.is_empty() {
+            return Ok(vec![]);
+        }
+        Ok(self
+            .db
+            .get_txn_ids(hashes.iter())
+            .await?
+            .into_values()
+            .collect())
+    }
+
+    /// Decode the messages sent by interchain account routers into the calls
+    /// they execute on their destination. Messages which cannot be decoded
+    /// are skipped.
+    fn ica_messages<'a>(
+        &'a self,
+        messages: impl Iterator<Item = &'a HyperlaneMessage> + 'a,
+    ) -> impl Iterator<Item = StorableIcaMessage> + 'a {
+        messages
+            .filter(|message| self.ica_routers.contains(&message.sender))
+            .filter_map(|message| match IcaMessage::decode(&message.body) {
+                Ok(ica_message) => Some(StorableIcaMessage {
+                    message_id: message.id(),
+                    ica_message,
+                }),
+                Err(err) => {
+                    warn!(message_id = ?message.id(), ?err, "error decoding interchain account message");
+                    None
+                }
+            })
+    }
+
     /// Takes a list of transaction hashes and the block id the transaction is
     /// in. if it is in the database already:
     ///     Fetches its associated database id
@@ -308,13 +361,44 @@ impl HyperlaneLogStore<HyperlaneMessage> for HyperlaneSqlDb {
                 )
                 .map(|t| (message.inner().clone(), meta, t.id))
             })
-            .map(|(msg, meta, txn_id)| StorableMessage { msg, meta, txn_id });
+            .map(|(msg, meta, txn_id)| StorableMessage { msg, meta, txn_id })
+            .collect_vec();
+        let ica_messages = self
+            .ica_messages(storable.iter().map(|storable| &storable.msg))
+            .collect_vec();
         let stored = self
             .db
-            .store_dispatched_messages(self.domain().id(), &self.mailbox_address, storable)
+            .store_dispatched_messages(
+                self.domain().id(),
+                &self.mailbox_address,
+                storable.into_iter(),
+            )
             .await?;
+        self.db.store_ica_calls(ica_messages.into_iter()).await?;
         Ok(stored as u32)
     }
+
+    /// Delete dispatched messages whose blocks were orphaned by a reorg, along
+    /// with the interchain account calls decoded from them.
+    async fn invalidate_logs(
+        &self,
+        messages: &[(Indexed<HyperlaneMessage>, LogMeta)],
+    ) -> Result<u32> {
+        let txn_ids = self.stored_txn_ids(messages.iter().map(|r| &r.1)).await?;
+        if txn_ids.is_empty() {
+            return Ok(0);
+        }
+        let message_ids = messages
+            .iter()
+            .map(|(message, _)| message.inner().id())
+            .collect_vec();
+        self.db.delete_ica_calls(message_ids.iter()).await?;
+        let deleted = self
+            .db
+            .delete_dispatched_messages(self.domain().id(), &self.mailbox_address, &txn_ids)
+            .await?;
+        Ok(deleted as u32)
+    }
 }
 
 #[async_trait]
@@ -354,6 +438,19 @@ impl HyperlaneLogStore<Delivery> for HyperlaneSqlDb {
             .await?;
         Ok(stored as u32)
     }
+
+    /// Delete deliveries whose blocks were orphaned by a reorg.
+    async fn invalidate_logs(&self, deliveries: &[(Indexed<Delivery>, LogMeta)]) -> Result<u32> {
+        let txn_ids = self.stored_txn_ids(deliveries.iter().map(|r| &r.1)).await?;
+        if txn_ids.is_empty() {
+            return Ok(0);
+        }
+        let deleted = self
+            .db
+            .delete_deliveries(self.domain().id(), self.mailbox_address, &txn_ids)
+            .await?;
+        Ok(deleted as u32)
+    }
 }
 
 #[async_trait]
@@ -393,6 +490,66 @@ impl HyperlaneLogStore<InterchainGasPayment> for HyperlaneSqlDb {
         let stored = self.db.store_payments(self.domain().id(), storable).await?;
         Ok(stored as u32)
     }
+
+    /// Delete interchain gas payments whose blocks were orphaned by a reorg.
+    async fn invalidate_logs(
+        &self,
+        payments: &[(Indexed<InterchainGasPayment>, LogMeta)],
+    ) -> Result<u32> {
+        let txn_ids = self.stored_txn_ids(payments.iter().map(|r| &r.1)).await?;
+        if txn_ids.is_empty() {
+            return Ok(0);
+        }
+        let deleted = self
+            .db
+            .delete_payments(self.domain().id(), &txn_ids)
+            .await?;
+        Ok(deleted as u32)
+    }
+}
+
+#[async_trait]
+impl HyperlaneLogStore<ValidatorAnnouncement> for HyperlaneSqlDb {
+    /// Store validator announcements into the database.
+    /// We store only announcements from blocks and transaction which we could
+    /// successfully insert into database.
+    async fn store_logs(
+        &self,
+        announcements: &[(Indexed<ValidatorAnnouncement>, LogMeta)],
+    ) -> Result<u32> {
+        if announcements.is_empty() {
+            return Ok(0);
+        }
+        let txns: HashMap<H256, TxnWithId> = self
+            .ensure_blocks_and_txns(announcements.iter().map(|r| &r.1))
+            .await?
+            .map(|t| (t.hash, t))
+            .collect();
+        let storable = announcements
+            .iter()
+            .filter_map(|(announcement, meta)| {
+                txns.get(
+                    &meta
+                        .transaction_id
+                        .try_into()
+                        .expect("256-bit transaction ids are the maximum supported at this time"),
+                )
+                .map(|txn| (announcement.inner(), meta, txn.id))
+            })
+            .map(
+                |(announcement, meta, txn_id)| StorableValidatorAnnouncement {
+                    announcement,
+                    meta,
+                    txn_id,
+                },
+            );
+
+        let stored = self
+            .db
+            .store_validator_announcements(self.domain().id(), storable)
+            .await?;
+        Ok(stored as u32)
+    }
 }
 
 #[async_trait]
@@ -434,6 +591,29 @@ where
     }
 }
 
+#[async_trait]
+impl HyperlaneBackfillStore for HyperlaneSqlDb {
+    async fn is_backfill_range_complete(
+        &self,
+        label: &str,
+        range: &RangeInclusive<u32>,
+    ) -> Result<bool> {
+        self.db
+            .is_backfill_chunk_complete(self.domain.id(), label, range)
+            .await
+    }
+
+    async fn store_backfill_range_complete(
+        &self,
+        label: &str,
+        range: &RangeInclusive<u32>,
+    ) -> Result<()> {
+        self.db
+            .store_backfill_chunk_complete(self.domain.id(), label, range)
+            .await
+    }
+}
+
 #[derive(Debug, Clone)]
 struct TxnWithId {
     hash: H256,
@@ -37,3 +37,25 @@ pub fn u256_to_decimal(v: U256) -> BigDecimal {
     v.to_little_endian(&mut buf);
     BigDecimal::from(BigInt::from_bytes_le(Sign::Plus, &buf as &[u8]))
 }
+
+pub fn decimal_to_u256(v: &BigDecimal) -> eyre::Result<U256> {
+    let (int, _) = v.with_scale(0).into_bigint_and_exponent();
+    let (sign, bytes) = int.to_bytes_le();
+    if sign == Sign::Minus || bytes.len() > 32 {
+        return Err(eyre::eyre!("Decimal out of the range of U256: {v}"));
+    }
+    Ok(U256::from_little_endian(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_u256_round_trip() {
+        for value in [U256::zero(), U256::from(12345), U256::MAX] {
+            assert_eq!(decimal_to_u256(&u256_to_decimal(value)).unwrap(), value);
+        }
+        assert!(decimal_to_u256(&BigDecimal::from(-1)).is_err());
+    }
+}
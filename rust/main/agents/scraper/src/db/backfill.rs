@@ -0,0 +1,68 @@
+use std::ops::RangeInclusive;
+
+use eyre::{Context, Result};
+use migration::OnConflict;
+use sea_orm::{prelude::*, ActiveValue::*, Insert};
+use tracing::{debug, instrument};
+
+use crate::date_time;
+use crate::db::ScraperDb;
+
+use super::generated::backfill_chunk;
+
+impl ScraperDb {
+    /// Whether the backfill named `label` on `domain` has indexed `range`
+    #[instrument(skip(self))]
+    pub async fn is_backfill_chunk_complete(
+        &self,
+        domain: u32,
+        label: &str,
+        range: &RangeInclusive<u32>,
+    ) -> Result<bool> {
+        let count = backfill_chunk::Entity::find()
+            .filter(backfill_chunk::Column::Domain.eq(domain))
+            .filter(backfill_chunk::Column::Label.eq(label))
+            .filter(backfill_chunk::Column::FromBlock.eq(*range.start() as i64))
+            .filter(backfill_chunk::Column::ToBlock.eq(*range.end() as i64))
+            .count(&self.0)
+            .await?;
+        Ok(count > 0)
+    }
+
+    /// Record that the backfill named `label` on `domain` has indexed `range`
+    #[instrument(skip(self))]
+    pub async fn store_backfill_chunk_complete(
+        &self,
+        domain: u32,
+        label: &str,
+        range: &RangeInclusive<u32>,
+    ) -> Result<()> {
+        let model = backfill_chunk::ActiveModel {
+            id: NotSet,
+            time_created: Set(date_time::now()),
+            domain: Unchanged(domain as i32),
+            label: Unchanged(label.to_owned()),
+            from_block: Unchanged(*range.start() as i64),
+            to_block: Unchanged(*range.end() as i64),
+        };
+        debug!(?model, "Writing backfill chunk to database");
+        match Insert::one(model)
+            .on_conflict(
+                OnConflict::columns([
+                    backfill_chunk::Column::Domain,
+                    backfill_chunk::Column::Label,
+                    backfill_chunk::Column::FromBlock,
+                    backfill_chunk::Column::ToBlock,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec(&self.0)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(DbErr::RecordNotInserted) => Ok(()),
+            Err(e) => Err(e).context("When inserting backfill chunk"),
+        }
+    }
+}
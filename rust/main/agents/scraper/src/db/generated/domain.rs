@@ -54,6 +54,7 @@ pub enum Relation {
     DeliveredMessage,
     GasPayment,
     Message,
+    ValidatorAnnouncement,
 }
 
 impl ColumnTrait for Column {
@@ -80,6 +81,9 @@ impl RelationTrait for Relation {
             Self::DeliveredMessage => Entity::has_many(super::delivered_message::Entity).into(),
             Self::GasPayment => Entity::has_many(super::gas_payment::Entity).into(),
             Self::Message => Entity::has_many(super::message::Entity).into(),
+            Self::ValidatorAnnouncement => {
+                Entity::has_many(super::validator_announcement::Entity).into()
+            }
         }
     }
 }
@@ -114,4 +118,10 @@ impl Related<super::message::Entity> for Entity {
     }
 }
 
+impl Related<super::validator_announcement::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ValidatorAnnouncement.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -2,10 +2,16 @@
 
 pub mod prelude;
 
+pub mod backfill_chunk;
 pub mod block;
 pub mod cursor;
 pub mod delivered_message;
 pub mod domain;
 pub mod gas_payment;
+pub mod ica_call;
 pub mod message;
+pub mod route_backlog;
+pub mod route_hourly_stats;
+pub mod route_latency_bucket;
 pub mod transaction;
+pub mod validator_announcement;
@@ -1,8 +1,10 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.12.3
 #[allow(unused_imports)]
 pub use super::{
-    block::Entity as Block, cursor::Entity as Cursor,
+    backfill_chunk::Entity as BackfillChunk, block::Entity as Block, cursor::Entity as Cursor,
     delivered_message::Entity as DeliveredMessage, domain::Entity as Domain,
-    gas_payment::Entity as GasPayment, message::Entity as Message,
-    transaction::Entity as Transaction,
+    gas_payment::Entity as GasPayment, ica_call::Entity as IcaCall, message::Entity as Message,
+    route_backlog::Entity as RouteBacklog, route_hourly_stats::Entity as RouteHourlyStats,
+    route_latency_bucket::Entity as RouteLatencyBucket, transaction::Entity as Transaction,
+    validator_announcement::Entity as ValidatorAnnouncement,
 };
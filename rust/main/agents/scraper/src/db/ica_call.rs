@@ -0,0 +1,104 @@
+use eyre::Result;
+use itertools::Itertools;
+use sea_orm::{prelude::*, ActiveValue::*, Insert};
+use tracing::{debug, instrument, trace};
+
+use hyperlane_core::H256;
+use migration::OnConflict;
+
+use crate::conversions::{address_to_bytes, h256_to_bytes, u256_to_decimal};
+use crate::date_time;
+use crate::db::ScraperDb;
+use crate::ica::IcaMessage;
+
+use super::generated::ica_call;
+
+pub struct StorableIcaMessage {
+    /// The id of the message sent by the interchain account router
+    pub message_id: H256,
+    pub ica_message: IcaMessage,
+}
+
+impl ScraperDb {
+    /// Store the calls of interchain account messages into the database (or
+    /// update existing ones).
+    #[instrument(skip_all)]
+    pub async fn store_ica_calls(
+        &self,
+        messages: impl Iterator<Item = StorableIcaMessage>,
+    ) -> Result<u64> {
+        let models = messages
+            .flat_map(|storable| {
+                let msg_id = h256_to_bytes(&storable.message_id);
+                let owner = address_to_bytes(&storable.ica_message.owner);
+                let ism = address_to_bytes(&storable.ica_message.ism);
+                storable
+                    .ica_message
+                    .calls
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(index, call)| ica_call::ActiveModel {
+                        id: NotSet,
+                        time_created: Set(date_time::now()),
+                        msg_id: Unchanged(msg_id.clone()),
+                        call_index: Unchanged(index as i32),
+                        owner: Set(owner.clone()),
+                        ism: Set(ism.clone()),
+                        target: Set(address_to_bytes(&call.target)),
+                        value: Set(u256_to_decimal(call.value)),
+                        calldata: Set(if call.data.is_empty() {
+                            None
+                        } else {
+                            Some(call.data)
+                        }),
+                    })
+            })
+            .collect_vec();
+
+        trace!(?models, "Writing interchain account calls to database");
+
+        if models.is_empty() {
+            debug!("Wrote zero new interchain account calls to database");
+            return Ok(0);
+        }
+
+        let calls_count = models.len() as u64;
+        Insert::many(models)
+            .on_conflict(
+                OnConflict::columns([ica_call::Column::MsgId, ica_call::Column::CallIndex])
+                    .update_columns([
+                        ica_call::Column::TimeCreated,
+                        ica_call::Column::Owner,
+                        ica_call::Column::Ism,
+                        ica_call::Column::Target,
+                        ica_call::Column::Value,
+                        ica_call::Column::Calldata,
+                    ])
+                    .to_owned(),
+            )
+            .exec(&self.0)
+            .await?;
+
+        debug!(
+            calls = calls_count,
+            "Wrote interchain account calls to database"
+        );
+        Ok(calls_count)
+    }
+
+    /// Delete the calls of interchain account messages, e.g. because the
+    /// dispatches of the messages were orphaned by a reorg.
+    #[instrument(skip_all)]
+    pub async fn delete_ica_calls(&self, message_ids: impl Iterator<Item = &H256>) -> Result<u64> {
+        let deleted = ica_call::Entity::delete_many()
+            .filter(ica_call::Column::MsgId.is_in(message_ids.map(h256_to_bytes)))
+            .exec(&self.0)
+            .await?
+            .rows_affected;
+        debug!(
+            calls = deleted,
+            "Deleted interchain account calls from database"
+        );
+        Ok(deleted)
+    }
+}
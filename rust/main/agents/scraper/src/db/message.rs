@@ -2,7 +2,10 @@
 
 use eyre::Result;
 use itertools::Itertools;
-use sea_orm::{prelude::*, ActiveValue::*, DeriveColumn, EnumIter, Insert, QuerySelect};
+use sea_orm::{
+    prelude::*, ActiveValue::*, ConnectionTrait, DeriveColumn, EnumIter, Insert, QuerySelect,
+    TransactionTrait,
+};
 use tracing::{debug, instrument, trace};
 
 use hyperlane_core::{HyperlaneMessage, LogMeta, H256};
@@ -12,7 +15,10 @@ use crate::conversions::{address_to_bytes, bytes_to_address, h256_to_bytes};
 use crate::date_time;
 use crate::db::ScraperDb;
 
-use super::generated::{delivered_message, message};
+use super::{
+    generated::{delivered_message, message},
+    route_stats::{record_deliveries, record_dispatched_messages},
+};
 
 #[derive(Debug, Clone)]
 pub struct StorableDelivery<'a> {
@@ -120,14 +126,18 @@ impl ScraperDb {
         Ok(tx_id)
     }
 
-    async fn latest_deliveries_id(&self, domain: u32, destination_mailbox: Vec<u8>) -> Result<i64> {
+    async fn latest_deliveries_id(
+        db: &impl ConnectionTrait,
+        domain: u32,
+        destination_mailbox: Vec<u8>,
+    ) -> Result<i64> {
         let result = delivered_message::Entity::find()
             .select_only()
             .column_as(delivered_message::Column::Id.max(), "max_id")
             .filter(delivered_message::Column::Domain.eq(domain))
             .filter(delivered_message::Column::DestinationMailbox.eq(destination_mailbox))
             .into_tuple::<Option<i64>>()
-            .one(&self.0)
+            .one(db)
             .await?;
 
         Ok(result
@@ -139,7 +149,7 @@ impl ScraperDb {
     }
 
     async fn deliveries_count_since_id(
-        &self,
+        db: &impl ConnectionTrait,
         domain: u32,
         destination_mailbox: Vec<u8>,
         prev_id: i64,
@@ -148,7 +158,7 @@ impl ScraperDb {
             .filter(delivered_message::Column::Domain.eq(domain))
             .filter(delivered_message::Column::DestinationMailbox.eq(destination_mailbox))
             .filter(delivered_message::Column::Id.gt(prev_id))
-            .count(&self.0)
+            .count(db)
             .await?)
     }
 
@@ -162,9 +172,11 @@ impl ScraperDb {
         deliveries: impl Iterator<Item = StorableDelivery<'_>>,
     ) -> Result<u64> {
         let destination_mailbox = address_to_bytes(&destination_mailbox);
-        let latest_id_before = self
-            .latest_deliveries_id(domain, destination_mailbox.clone())
-            .await?;
+        // The route stats are updated with the deliveries in the same
+        // transaction, so that they're never counted twice or missed
+        let txn = self.0.begin().await?;
+        let latest_id_before =
+            Self::latest_deliveries_id(&txn, domain, destination_mailbox.clone()).await?;
         // we have a race condition where a message may not have been scraped yet even
         // though we have received news of delivery on this chain, so the
         // message IDs are looked up in a separate "thread".
@@ -195,12 +207,20 @@ impl ScraperDb {
                     ])
                     .to_owned(),
             )
-            .exec(&self.0)
+            .exec(&txn)
             .await?;
 
-        let new_deliveries_count = self
-            .deliveries_count_since_id(domain, destination_mailbox, latest_id_before)
-            .await?;
+        let new_deliveries_count = Self::deliveries_count_since_id(
+            &txn,
+            domain,
+            destination_mailbox.clone(),
+            latest_id_before,
+        )
+        .await?;
+        if new_deliveries_count > 0 {
+            record_deliveries(&txn, domain, destination_mailbox, latest_id_before).await?;
+        }
+        txn.commit().await?;
 
         debug!(
             messages = new_deliveries_count,
@@ -209,14 +229,44 @@ impl ScraperDb {
         Ok(new_deliveries_count)
     }
 
-    async fn latest_dispatched_id(&self, domain: u32, origin_mailbox: Vec<u8>) -> Result<i64> {
+    /// Delete the deliveries to a mailbox made in the given transactions,
+    /// e.g. because their blocks were orphaned by a reorg.
+    #[instrument(skip(self))]
+    pub async fn delete_deliveries(
+        &self,
+        domain: u32,
+        destination_mailbox: H256,
+        txn_ids: &[i64],
+    ) -> Result<u64> {
+        let deleted = delivered_message::Entity::delete_many()
+            .filter(delivered_message::Column::Domain.eq(domain))
+            .filter(
+                delivered_message::Column::DestinationMailbox
+                    .eq(address_to_bytes(&destination_mailbox)),
+            )
+            .filter(delivered_message::Column::DestinationTxId.is_in(txn_ids.iter().copied()))
+            .exec(&self.0)
+            .await?
+            .rows_affected;
+        debug!(
+            deliveries = deleted,
+            "Deleted delivered messages from database"
+        );
+        Ok(deleted)
+    }
+
+    async fn latest_dispatched_id(
+        db: &impl ConnectionTrait,
+        domain: u32,
+        origin_mailbox: Vec<u8>,
+    ) -> Result<i64> {
         let result = message::Entity::find()
             .select_only()
             .column_as(message::Column::Id.max(), "max_id")
             .filter(message::Column::Origin.eq(domain))
             .filter(message::Column::OriginMailbox.eq(origin_mailbox))
             .into_tuple::<Option<i64>>()
-            .one(&self.0)
+            .one(db)
             .await?;
 
         Ok(result
@@ -228,7 +278,7 @@ impl ScraperDb {
     }
 
     async fn dispatch_count_since_id(
-        &self,
+        db: &impl ConnectionTrait,
         domain: u32,
         origin_mailbox: Vec<u8>,
         prev_id: i64,
@@ -237,7 +287,7 @@ impl ScraperDb {
             .filter(message::Column::Origin.eq(domain))
             .filter(message::Column::OriginMailbox.eq(origin_mailbox))
             .filter(message::Column::Id.gt(prev_id))
-            .count(&self.0)
+            .count(db)
             .await?)
     }
 
@@ -251,9 +301,11 @@ impl ScraperDb {
         messages: impl Iterator<Item = StorableMessage<'_>>,
     ) -> Result<u64> {
         let origin_mailbox = address_to_bytes(origin_mailbox);
-        let latest_id_before = self
-            .latest_dispatched_id(domain, origin_mailbox.clone())
-            .await?;
+        // The route stats are updated with the messages in the same
+        // transaction, so that they're never counted twice or missed
+        let txn = self.0.begin().await?;
+        let latest_id_before =
+            Self::latest_dispatched_id(&txn, domain, origin_mailbox.clone()).await?;
         // we have a race condition where a message may not have been scraped yet even
         let models = messages
             .map(|storable| message::ActiveModel {
@@ -299,12 +351,16 @@ impl ScraperDb {
                 ])
                 .to_owned(),
             )
-            .exec(&self.0)
+            .exec(&txn)
             .await?;
 
-        let new_dispatch_count = self
-            .dispatch_count_since_id(domain, origin_mailbox, latest_id_before)
-            .await?;
+        let new_dispatch_count =
+            Self::dispatch_count_since_id(&txn, domain, origin_mailbox.clone(), latest_id_before)
+                .await?;
+        if new_dispatch_count > 0 {
+            record_dispatched_messages(&txn, domain, origin_mailbox, latest_id_before).await?;
+        }
+        txn.commit().await?;
 
         debug!(
             messages = new_dispatch_count,
@@ -312,4 +368,24 @@ impl ScraperDb {
         );
         Ok(new_dispatch_count)
     }
+
+    /// Delete the messages dispatched from a mailbox in the given transactions,
+    /// e.g. because their blocks were orphaned by a reorg.
+    #[instrument(skip(self))]
+    pub async fn delete_dispatched_messages(
+        &self,
+        domain: u32,
+        origin_mailbox: &H256,
+        txn_ids: &[i64],
+    ) -> Result<u64> {
+        let deleted = message::Entity::delete_many()
+            .filter(message::Column::Origin.eq(domain))
+            .filter(message::Column::OriginMailbox.eq(address_to_bytes(origin_mailbox)))
+            .filter(message::Column::OriginTxId.is_in(txn_ids.iter().copied()))
+            .exec(&self.0)
+            .await?
+            .rows_affected;
+        debug!(messages = deleted, "Deleted messages from database");
+        Ok(deleted)
+    }
 }
@@ -1,24 +1,33 @@
 pub use block::*;
 pub use block_cursor::BlockCursor;
 use eyre::Result;
+pub use ica_call::*;
 pub use message::*;
 pub use payment::*;
+pub use query::*;
 use sea_orm::{Database, DbConn};
 use tracing::instrument;
 pub use txn::*;
+pub use validator_announcement::*;
 
 #[allow(clippy::all)]
 mod generated;
 
 // These modules implement additional functionality for the ScraperDb
+mod backfill;
 mod block;
 mod block_cursor;
+mod ica_call;
 mod message;
 mod payment;
+mod query;
+mod route_stats;
 mod txn;
+mod validator_announcement;
 
 /// Database interface to the message explorer database for the scraper. This is
-/// focused on writing data to the database.
+/// focused on writing data to the database, with read queries serving the
+/// query api.
 #[derive(Clone, Debug)]
 pub struct ScraperDb(DbConn);
 
@@ -102,4 +102,18 @@ impl ScraperDb {
             .count(&self.0)
             .await?)
     }
+
+    /// Delete the gas payments made in the given transactions, e.g. because
+    /// their blocks were orphaned by a reorg.
+    #[instrument(skip(self))]
+    pub async fn delete_payments(&self, domain: u32, txn_ids: &[i64]) -> Result<u64> {
+        let deleted = gas_payment::Entity::delete_many()
+            .filter(gas_payment::Column::Domain.eq(domain))
+            .filter(gas_payment::Column::TxId.is_in(txn_ids.iter().copied()))
+            .exec(&self.0)
+            .await?
+            .rows_affected;
+        debug!(payments = deleted, "Deleted gas payments from database");
+        Ok(deleted)
+    }
 }
@@ -0,0 +1,387 @@
+use std::collections::HashMap;
+
+use ethers::types::Bytes;
+use eyre::Result;
+use itertools::Itertools;
+use sea_orm::{prelude::*, sea_query::Query, QueryOrder, QuerySelect};
+use serde::{Deserialize, Serialize};
+use tracing::{instrument, warn};
+
+use hyperlane_base::db::{IndexSnapshot, SnapshotGasPayment, SnapshotMessage};
+use hyperlane_core::{HyperlaneMessage, RawHyperlaneMessage, H256, H512};
+
+use crate::conversions::{address_to_bytes, bytes_to_address, decimal_to_u256, h256_to_bytes};
+use crate::db::ScraperDb;
+
+use super::generated::{
+    block, delivered_message, gas_payment, message, transaction, validator_announcement,
+};
+
+/// Whether a message has been delivered to its destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+}
+
+/// Criteria messages are searched by. Unset criteria match any message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageFilter {
+    pub msg_id: Option<H256>,
+    pub origin: Option<u32>,
+    pub destination: Option<u32>,
+    pub sender: Option<H256>,
+    pub recipient: Option<H256>,
+    pub status: Option<DeliveryStatus>,
+    /// Maximum number of messages returned, most recent first
+    pub limit: u64,
+}
+
+/// A dispatched message, joined with its delivery
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageRecord {
+    pub msg_id: H256,
+    pub origin: u32,
+    pub destination: u32,
+    pub nonce: u32,
+    pub sender: H256,
+    pub recipient: H256,
+    pub body: Bytes,
+    pub origin_mailbox: H256,
+    pub origin_tx_hash: Option<H256>,
+    pub status: DeliveryStatus,
+    pub destination_tx_hash: Option<H256>,
+}
+
+/// A gas payment made for a message
+#[derive(Debug, Clone, Serialize)]
+pub struct GasPaymentRecord {
+    pub domain: u32,
+    pub msg_id: H256,
+    pub payment: String,
+    pub gas_amount: String,
+    pub tx_hash: Option<H256>,
+    pub log_index: i64,
+}
+
+/// A checkpoint storage location announced by a validator
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidatorAnnouncementRecord {
+    pub domain: u32,
+    pub validator: H256,
+    pub storage_location: String,
+    pub tx_hash: Option<H256>,
+    pub log_index: i64,
+}
+
+/// The transaction a log was emitted by, and its block
+#[derive(Debug, Clone, Copy)]
+struct TxBlock {
+    transaction_id: H512,
+    block_hash: H256,
+    block_number: u64,
+}
+
+impl ScraperDb {
+    /// Search dispatched messages, joined with their delivery if they have
+    /// been delivered.
+    #[instrument(skip(self))]
+    pub async fn query_messages(&self, filter: &MessageFilter) -> Result<Vec<MessageRecord>> {
+        let mut query = message::Entity::find();
+        if let Some(msg_id) = &filter.msg_id {
+            query = query.filter(message::Column::MsgId.eq(h256_to_bytes(msg_id)));
+        }
+        if let Some(origin) = filter.origin {
+            query = query.filter(message::Column::Origin.eq(origin));
+        }
+        if let Some(destination) = filter.destination {
+            query = query.filter(message::Column::Destination.eq(destination));
+        }
+        if let Some(sender) = &filter.sender {
+            query = query.filter(message::Column::Sender.eq(address_to_bytes(sender)));
+        }
+        if let Some(recipient) = &filter.recipient {
+            query = query.filter(message::Column::Recipient.eq(address_to_bytes(recipient)));
+        }
+        if let Some(status) = filter.status {
+            let delivered = Query::select()
+                .column(delivered_message::Column::MsgId)
+                .from(delivered_message::Entity)
+                .to_owned();
+            query = query.filter(match status {
+                DeliveryStatus::Delivered => message::Column::MsgId.in_subquery(delivered),
+                DeliveryStatus::Pending => message::Column::MsgId.not_in_subquery(delivered),
+            });
+        }
+        let messages = query
+            .order_by_desc(message::Column::Id)
+            .limit(filter.limit)
+            .all(&self.0)
+            .await?;
+
+        let deliveries: HashMap<Vec<u8>, delivered_message::Model> =
+            delivered_message::Entity::find()
+                .filter(
+                    delivered_message::Column::MsgId
+                        .is_in(messages.iter().map(|message| message.msg_id.clone())),
+                )
+                .all(&self.0)
+                .await?
+                .into_iter()
+                .map(|delivery| (delivery.msg_id.clone(), delivery))
+                .collect();
+        let tx_hashes = self
+            .tx_hashes(
+                messages.iter().map(|message| message.origin_tx_id).chain(
+                    deliveries
+                        .values()
+                        .map(|delivery| delivery.destination_tx_id),
+                ),
+            )
+            .await?;
+
+        messages
+            .into_iter()
+            .map(|message| {
+                let delivery = deliveries.get(&message.msg_id);
+                Ok(MessageRecord {
+                    msg_id: H256::from_slice(&message.msg_id),
+                    origin: message.origin as u32,
+                    destination: message.destination as u32,
+                    nonce: message.nonce as u32,
+                    sender: bytes_to_address(message.sender)?,
+                    recipient: bytes_to_address(message.recipient)?,
+                    body: message.msg_body.unwrap_or_default().into(),
+                    origin_mailbox: bytes_to_address(message.origin_mailbox)?,
+                    origin_tx_hash: tx_hashes.get(&message.origin_tx_id).copied(),
+                    status: if delivery.is_some() {
+                        DeliveryStatus::Delivered
+                    } else {
+                        DeliveryStatus::Pending
+                    },
+                    destination_tx_hash: delivery
+                        .and_then(|delivery| tx_hashes.get(&delivery.destination_tx_id).copied()),
+                })
+            })
+            .collect()
+    }
+
+    /// Get the gas payments made for a message, in the order they were stored.
+    #[instrument(skip(self))]
+    pub async fn query_gas_payments(&self, msg_id: &H256) -> Result<Vec<GasPaymentRecord>> {
+        let payments = gas_payment::Entity::find()
+            .filter(gas_payment::Column::MsgId.eq(h256_to_bytes(msg_id)))
+            .order_by_asc(gas_payment::Column::Id)
+            .all(&self.0)
+            .await?;
+        let tx_hashes = self
+            .tx_hashes(payments.iter().map(|payment| payment.tx_id))
+            .await?;
+
+        Ok(payments
+            .into_iter()
+            .map(|payment| GasPaymentRecord {
+                domain: payment.domain as u32,
+                msg_id: *msg_id,
+                payment: payment.payment.to_string(),
+                gas_amount: payment.gas_amount.to_string(),
+                tx_hash: tx_hashes.get(&payment.tx_id).copied(),
+                log_index: payment.log_index,
+            })
+            .collect())
+    }
+
+    /// Get the announcements of a validator, most recent first, so that the
+    /// first announcement of each domain is its current storage location.
+    #[instrument(skip(self))]
+    pub async fn query_validator_announcements(
+        &self,
+        validator: &H256,
+        domain: Option<u32>,
+        limit: u64,
+    ) -> Result<Vec<ValidatorAnnouncementRecord>> {
+        let mut query = validator_announcement::Entity::find()
+            .filter(validator_announcement::Column::Validator.eq(address_to_bytes(validator)));
+        if let Some(domain) = domain {
+            query = query.filter(validator_announcement::Column::Domain.eq(domain));
+        }
+        let announcements = query
+            .order_by_desc(validator_announcement::Column::Id)
+            .limit(limit)
+            .all(&self.0)
+            .await?;
+        let tx_hashes = self
+            .tx_hashes(announcements.iter().map(|announcement| announcement.tx_id))
+            .await?;
+
+        Ok(announcements
+            .into_iter()
+            .map(|announcement| ValidatorAnnouncementRecord {
+                domain: announcement.domain as u32,
+                validator: *validator,
+                storage_location: announcement.storage_location,
+                tx_hash: tx_hashes.get(&announcement.tx_id).copied(),
+                log_index: announcement.log_index,
+            })
+            .collect())
+    }
+
+    /// Snapshot of at most `limit` messages dispatched from `origin`'s
+    /// `mailbox`, starting at `from_nonce`, and of the gas payments made for
+    /// them.
+    #[instrument(skip(self))]
+    pub async fn query_snapshot(
+        &self,
+        origin: u32,
+        mailbox: &H256,
+        from_nonce: u32,
+        limit: u64,
+    ) -> Result<IndexSnapshot> {
+        let messages = message::Entity::find()
+            .filter(message::Column::Origin.eq(origin))
+            .filter(message::Column::OriginMailbox.eq(address_to_bytes(mailbox)))
+            .filter(message::Column::Nonce.gte(from_nonce))
+            .order_by_asc(message::Column::Nonce)
+            .limit(limit)
+            .all(&self.0)
+            .await?;
+        // Messages may be left out of the page below, so the next page starts
+        // after the last message read rather than the last one returned
+        let next_nonce = match messages.last() {
+            Some(last) if messages.len() as u64 == limit => Some(last.nonce as u32 + 1),
+            _ => None,
+        };
+        let payments = gas_payment::Entity::find()
+            .filter(gas_payment::Column::Domain.eq(origin))
+            .filter(
+                gas_payment::Column::MsgId
+                    .is_in(messages.iter().map(|message| message.msg_id.clone())),
+            )
+            .order_by_asc(gas_payment::Column::Id)
+            .all(&self.0)
+            .await?;
+        let tx_blocks = self
+            .tx_blocks(
+                messages
+                    .iter()
+                    .map(|message| message.origin_tx_id)
+                    .chain(payments.iter().map(|payment| payment.tx_id)),
+            )
+            .await?;
+        let destinations: HashMap<&[u8], u32> = messages
+            .iter()
+            .map(|message| (message.msg_id.as_slice(), message.destination as u32))
+            .collect();
+
+        // Logs are only snapshotted along with the transaction they were emitted by
+        let mut gas_payments = vec![];
+        for payment in &payments {
+            let Some(tx_block) = tx_blocks.get(&payment.tx_id) else {
+                continue;
+            };
+            gas_payments.push(SnapshotGasPayment {
+                message_id: H256::from_slice(&payment.msg_id),
+                destination: destinations[payment.msg_id.as_slice()],
+                payment: decimal_to_u256(&payment.payment)?,
+                gas_amount: decimal_to_u256(&payment.gas_amount)?,
+                block_number: tx_block.block_number,
+                block_hash: tx_block.block_hash,
+                transaction_id: tx_block.transaction_id,
+                log_index: payment.log_index as u64,
+            });
+        }
+        let mut snapshot_messages = vec![];
+        for message in messages {
+            let Some(tx_block) = tx_blocks.get(&message.origin_tx_id) else {
+                continue;
+            };
+            let msg_id = H256::from_slice(&message.msg_id);
+            // The version isn't stored, so the message is assumed to be of the
+            // current one, which its id confirms
+            let decoded = HyperlaneMessage {
+                nonce: message.nonce as u32,
+                origin: message.origin as u32,
+                sender: bytes_to_address(message.sender)?,
+                destination: message.destination as u32,
+                recipient: bytes_to_address(message.recipient)?,
+                body: message.msg_body.unwrap_or_default(),
+                ..Default::default()
+            };
+            if decoded.id() != msg_id {
+                warn!(
+                    ?msg_id,
+                    "Message doesn't match its id, leaving it out of the snapshot"
+                );
+                continue;
+            }
+            snapshot_messages.push(SnapshotMessage {
+                message: RawHyperlaneMessage::from(&decoded).into(),
+                mailbox: *mailbox,
+                block_number: tx_block.block_number,
+                block_hash: tx_block.block_hash,
+                transaction_id: tx_block.transaction_id,
+            });
+        }
+
+        Ok(IndexSnapshot {
+            domain: origin,
+            messages: snapshot_messages,
+            gas_payments,
+            next_nonce,
+        })
+    }
+
+    /// The hash and block of the transactions with the given database ids
+    async fn tx_blocks(&self, ids: impl Iterator<Item = i64>) -> Result<HashMap<i64, TxBlock>> {
+        let txs = transaction::Entity::find()
+            .filter(transaction::Column::Id.is_in(ids.unique()))
+            .select_only()
+            .column(transaction::Column::Id)
+            .column(transaction::Column::Hash)
+            .column(transaction::Column::BlockId)
+            .into_tuple::<(i64, Vec<u8>, i64)>()
+            .all(&self.0)
+            .await?;
+        let blocks: HashMap<i64, (H256, u64)> = block::Entity::find()
+            .filter(block::Column::Id.is_in(txs.iter().map(|(_, _, block_id)| *block_id).unique()))
+            .select_only()
+            .column(block::Column::Id)
+            .column(block::Column::Hash)
+            .column(block::Column::Height)
+            .into_tuple::<(i64, Vec<u8>, i64)>()
+            .all(&self.0)
+            .await?
+            .into_iter()
+            .map(|(id, hash, height)| (id, (H256::from_slice(&hash), height as u64)))
+            .collect();
+        Ok(txs
+            .into_iter()
+            .filter_map(|(id, hash, block_id)| {
+                let (block_hash, block_number) = *blocks.get(&block_id)?;
+                let tx_block = TxBlock {
+                    transaction_id: H512::from(H256::from_slice(&hash)),
+                    block_hash,
+                    block_number,
+                };
+                Some((id, tx_block))
+            })
+            .collect())
+    }
+
+    /// Hashes of the transactions with the given database ids
+    async fn tx_hashes(&self, ids: impl Iterator<Item = i64>) -> Result<HashMap<i64, H256>> {
+        let hashes = transaction::Entity::find()
+            .filter(transaction::Column::Id.is_in(ids.unique()))
+            .select_only()
+            .column(transaction::Column::Id)
+            .column(transaction::Column::Hash)
+            .into_tuple::<(i64, Vec<u8>)>()
+            .all(&self.0)
+            .await?
+            .into_iter()
+            .map(|(id, hash)| (id, H256::from_slice(&hash)))
+            .collect();
+        Ok(hashes)
+    }
+}
@@ -0,0 +1,394 @@
+//! Aggregates maintained as messages and deliveries are stored:
+//! - `route_hourly_stats`: messages dispatched and delivered on each route
+//!   every hour, with percentiles of their delivery latency
+//! - `route_latency_bucket`: counts of the delivery latencies the percentiles
+//!   are estimated from
+//! - `route_backlog`: messages dispatched on each route that haven't been
+//!   delivered yet
+//!
+//! A delivery is counted once both it and its message are stored, which
+//! depends on the order the origin and destination are scraped in.
+
+use std::collections::{HashMap, HashSet};
+
+use eyre::Result;
+use itertools::Itertools;
+use sea_orm::{prelude::*, ActiveValue::Set, ConnectionTrait, DbBackend, QuerySelect, Statement};
+use time::Time;
+use tracing::{debug, instrument};
+
+use super::generated::{
+    block, delivered_message, message, route_hourly_stats, route_latency_bucket, transaction,
+};
+
+/// Upper bounds, in seconds, of the buckets delivery latencies are counted
+/// in. Latencies above the last bound are counted in an extra overflow bucket.
+pub const LATENCY_BUCKET_BOUNDS_SECS: [i64; 13] = [
+    15, 30, 60, 120, 300, 600, 1200, 1800, 3600, 7200, 21600, 43200, 86400,
+];
+
+/// The bucket a delivery latency is counted in
+fn latency_bucket(latency_secs: i64) -> usize {
+    LATENCY_BUCKET_BOUNDS_SECS
+        .iter()
+        .position(|bound| latency_secs <= *bound)
+        .unwrap_or(LATENCY_BUCKET_BOUNDS_SECS.len())
+}
+
+/// Estimates the `quantile` of the latencies counted in `bucket_counts` as the
+/// upper bound of the bucket the quantile falls in, capped at the longest
+/// latency.
+fn latency_percentile(bucket_counts: &[i64], quantile: f64, max_latency_secs: i64) -> Option<i64> {
+    let total: i64 = bucket_counts.iter().sum();
+    if total == 0 {
+        return None;
+    }
+    let rank = ((quantile * total as f64).ceil() as i64).max(1);
+    let mut counted = 0;
+    for (bucket, count) in bucket_counts.iter().enumerate() {
+        counted += count;
+        if counted >= rank {
+            let bound = LATENCY_BUCKET_BOUNDS_SECS
+                .get(bucket)
+                .copied()
+                .unwrap_or(max_latency_secs);
+            return Some(bound.min(max_latency_secs));
+        }
+    }
+    Some(max_latency_secs)
+}
+
+/// Start of the hour `time` is in
+fn hour_of(time: TimeDateTime) -> TimeDateTime {
+    time.replace_time(Time::from_hms(time.hour(), 0, 0).expect("hour is valid"))
+}
+
+/// A route during an hour
+type RouteHour = (i32, i32, TimeDateTime);
+
+/// Update the route stats with the messages stored after `after_id`.
+#[instrument(skip(db, origin_mailbox))]
+pub(super) async fn record_dispatched_messages(
+    db: &impl ConnectionTrait,
+    origin: u32,
+    origin_mailbox: Vec<u8>,
+    after_id: i64,
+) -> Result<()> {
+    let messages = message::Entity::find()
+        .filter(message::Column::Origin.eq(origin))
+        .filter(message::Column::OriginMailbox.eq(origin_mailbox))
+        .filter(message::Column::Id.gt(after_id))
+        .all(db)
+        .await?;
+    if messages.is_empty() {
+        return Ok(());
+    }
+
+    let timestamps =
+        tx_block_timestamps(db, messages.iter().map(|message| message.origin_tx_id)).await?;
+    let mut dispatched: HashMap<RouteHour, i64> = HashMap::new();
+    let mut backlog: HashMap<(i32, i32), i64> = HashMap::new();
+    for message in &messages {
+        if let Some(timestamp) = timestamps.get(&message.origin_tx_id) {
+            *dispatched
+                .entry((message.origin, message.destination, hour_of(*timestamp)))
+                .or_default() += 1;
+        }
+        *backlog
+            .entry((message.origin, message.destination))
+            .or_default() += 1;
+    }
+    for (route_hour, count) in dispatched {
+        add_to_hourly_stats(db, route_hour, count, 0, None).await?;
+    }
+    for (route, count) in backlog {
+        add_to_backlog(db, route, count).await?;
+    }
+
+    // Deliveries of these messages stored before them
+    let deliveries = delivered_message::Entity::find()
+        .filter(
+            delivered_message::Column::MsgId
+                .is_in(messages.iter().map(|message| message.msg_id.clone())),
+        )
+        .all(db)
+        .await?;
+    let messages: HashMap<Vec<u8>, message::Model> = messages
+        .into_iter()
+        .map(|message| (message.msg_id.clone(), message))
+        .collect();
+    record_completed_deliveries(
+        db,
+        deliveries
+            .into_iter()
+            .filter_map(|delivery| Some((messages.get(&delivery.msg_id)?.clone(), delivery)))
+            .collect(),
+    )
+    .await
+}
+
+/// Update the route stats with the deliveries stored after `after_id`.
+#[instrument(skip(db, destination_mailbox))]
+pub(super) async fn record_deliveries(
+    db: &impl ConnectionTrait,
+    destination: u32,
+    destination_mailbox: Vec<u8>,
+    after_id: i64,
+) -> Result<()> {
+    let deliveries = delivered_message::Entity::find()
+        .filter(delivered_message::Column::Domain.eq(destination))
+        .filter(delivered_message::Column::DestinationMailbox.eq(destination_mailbox))
+        .filter(delivered_message::Column::Id.gt(after_id))
+        .all(db)
+        .await?;
+    if deliveries.is_empty() {
+        return Ok(());
+    }
+
+    // Deliveries of messages not stored yet are counted with their message
+    let messages: HashMap<Vec<u8>, message::Model> = message::Entity::find()
+        .filter(
+            message::Column::MsgId.is_in(deliveries.iter().map(|delivery| delivery.msg_id.clone())),
+        )
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|message| (message.msg_id.clone(), message))
+        .collect();
+    record_completed_deliveries(
+        db,
+        deliveries
+            .into_iter()
+            .filter_map(|delivery| Some((messages.get(&delivery.msg_id)?.clone(), delivery)))
+            .collect(),
+    )
+    .await
+}
+
+async fn record_completed_deliveries(
+    db: &impl ConnectionTrait,
+    deliveries: Vec<(message::Model, delivered_message::Model)>,
+) -> Result<()> {
+    if deliveries.is_empty() {
+        return Ok(());
+    }
+    let timestamps = tx_block_timestamps(
+        db,
+        deliveries
+            .iter()
+            .flat_map(|(message, delivery)| [message.origin_tx_id, delivery.destination_tx_id]),
+    )
+    .await?;
+
+    let mut delivered: HashMap<RouteHour, (i64, i64)> = HashMap::new();
+    let mut buckets: HashMap<(RouteHour, usize), i64> = HashMap::new();
+    let mut backlog: HashMap<(i32, i32), i64> = HashMap::new();
+    for (message, delivery) in &deliveries {
+        *backlog
+            .entry((message.origin, message.destination))
+            .or_default() -= 1;
+        let (Some(dispatched_at), Some(delivered_at)) = (
+            timestamps.get(&message.origin_tx_id),
+            timestamps.get(&delivery.destination_tx_id),
+        ) else {
+            continue;
+        };
+        let latency_secs = (*delivered_at - *dispatched_at).whole_seconds().max(0);
+        let route_hour = (message.origin, message.destination, hour_of(*delivered_at));
+        let (count, max_latency_secs) = delivered.entry(route_hour).or_default();
+        *count += 1;
+        *max_latency_secs = (*max_latency_secs).max(latency_secs);
+        *buckets
+            .entry((route_hour, latency_bucket(latency_secs)))
+            .or_default() += 1;
+    }
+
+    for (route, count) in backlog {
+        add_to_backlog(db, route, count).await?;
+    }
+    for ((route_hour, bucket), count) in buckets {
+        add_to_latency_bucket(db, route_hour, bucket, count).await?;
+    }
+    let route_hours: HashSet<RouteHour> = delivered.keys().copied().collect();
+    for (route_hour, (count, max_latency_secs)) in delivered {
+        add_to_hourly_stats(db, route_hour, 0, count, Some(max_latency_secs)).await?;
+    }
+    for route_hour in route_hours {
+        update_latency_percentiles(db, route_hour).await?;
+    }
+    debug!(
+        deliveries = deliveries.len(),
+        "Recorded deliveries in route stats"
+    );
+    Ok(())
+}
+
+async fn add_to_hourly_stats(
+    db: &impl ConnectionTrait,
+    (origin, destination, hour): RouteHour,
+    dispatched: i64,
+    delivered: i64,
+    max_latency_secs: Option<i64>,
+) -> Result<()> {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"INSERT INTO route_hourly_stats (origin, destination, hour, dispatched_count, delivered_count, max_latency_secs)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (origin, destination, hour) DO UPDATE SET
+            time_updated = NOW(),
+            dispatched_count = route_hourly_stats.dispatched_count + EXCLUDED.dispatched_count,
+            delivered_count = route_hourly_stats.delivered_count + EXCLUDED.delivered_count,
+            max_latency_secs = GREATEST(route_hourly_stats.max_latency_secs, EXCLUDED.max_latency_secs)"#,
+        [
+            origin.into(),
+            destination.into(),
+            hour.into(),
+            dispatched.into(),
+            delivered.into(),
+            max_latency_secs.into(),
+        ],
+    ))
+    .await?;
+    Ok(())
+}
+
+async fn add_to_latency_bucket(
+    db: &impl ConnectionTrait,
+    (origin, destination, hour): RouteHour,
+    bucket: usize,
+    count: i64,
+) -> Result<()> {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"INSERT INTO route_latency_bucket (origin, destination, hour, bucket, count)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (origin, destination, hour, bucket) DO UPDATE SET
+            count = route_latency_bucket.count + EXCLUDED.count"#,
+        [
+            origin.into(),
+            destination.into(),
+            hour.into(),
+            (bucket as i32).into(),
+            count.into(),
+        ],
+    ))
+    .await?;
+    Ok(())
+}
+
+async fn add_to_backlog(
+    db: &impl ConnectionTrait,
+    (origin, destination): (i32, i32),
+    count: i64,
+) -> Result<()> {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"INSERT INTO route_backlog (origin, destination, undelivered_count)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (origin, destination) DO UPDATE SET
+            time_updated = NOW(),
+            undelivered_count = route_backlog.undelivered_count + EXCLUDED.undelivered_count"#,
+        [origin.into(), destination.into(), count.into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Re-estimate the latency percentiles of a route hour from its buckets
+async fn update_latency_percentiles(
+    db: &impl ConnectionTrait,
+    (origin, destination, hour): RouteHour,
+) -> Result<()> {
+    let Some(stats) = route_hourly_stats::Entity::find()
+        .filter(route_hourly_stats::Column::Origin.eq(origin))
+        .filter(route_hourly_stats::Column::Destination.eq(destination))
+        .filter(route_hourly_stats::Column::Hour.eq(hour))
+        .one(db)
+        .await?
+    else {
+        return Ok(());
+    };
+    let mut bucket_counts = vec![0; LATENCY_BUCKET_BOUNDS_SECS.len() + 1];
+    for bucket in route_latency_bucket::Entity::find()
+        .filter(route_latency_bucket::Column::Origin.eq(origin))
+        .filter(route_latency_bucket::Column::Destination.eq(destination))
+        .filter(route_latency_bucket::Column::Hour.eq(hour))
+        .all(db)
+        .await?
+    {
+        if let Some(count) = bucket_counts.get_mut(bucket.bucket as usize) {
+            *count += bucket.count;
+        }
+    }
+    let max_latency_secs = stats.max_latency_secs.unwrap_or_default();
+
+    let mut stats: route_hourly_stats::ActiveModel = stats.into();
+    stats.latency_p50_secs = Set(latency_percentile(&bucket_counts, 0.5, max_latency_secs));
+    stats.latency_p95_secs = Set(latency_percentile(&bucket_counts, 0.95, max_latency_secs));
+    stats.update(db).await?;
+    Ok(())
+}
+
+/// Timestamps of the blocks of the transactions with the given database
+/// ids
+async fn tx_block_timestamps(
+    db: &impl ConnectionTrait,
+    tx_ids: impl Iterator<Item = i64>,
+) -> Result<HashMap<i64, TimeDateTime>> {
+    let tx_blocks = transaction::Entity::find()
+        .filter(transaction::Column::Id.is_in(tx_ids.unique()))
+        .select_only()
+        .column(transaction::Column::Id)
+        .column(transaction::Column::BlockId)
+        .into_tuple::<(i64, i64)>()
+        .all(db)
+        .await?;
+    let block_timestamps: HashMap<i64, TimeDateTime> = block::Entity::find()
+        .filter(block::Column::Id.is_in(tx_blocks.iter().map(|(_, block_id)| *block_id).unique()))
+        .select_only()
+        .column(block::Column::Id)
+        .column(block::Column::Timestamp)
+        .into_tuple::<(i64, TimeDateTime)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+    Ok(tx_blocks
+        .into_iter()
+        .filter_map(|(tx_id, block_id)| Some((tx_id, *block_timestamps.get(&block_id)?)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_bucket() {
+        assert_eq!(latency_bucket(0), 0);
+        assert_eq!(latency_bucket(15), 0);
+        assert_eq!(latency_bucket(16), 1);
+        assert_eq!(latency_bucket(86400), LATENCY_BUCKET_BOUNDS_SECS.len() - 1);
+        assert_eq!(latency_bucket(86401), LATENCY_BUCKET_BOUNDS_SECS.len());
+    }
+
+    #[test]
+    fn test_latency_percentile() {
+        let mut bucket_counts = vec![0; LATENCY_BUCKET_BOUNDS_SECS.len() + 1];
+        assert_eq!(latency_percentile(&bucket_counts, 0.5, 0), None);
+
+        // 10 deliveries within 15s, 9 within 60s and 1 after two days
+        bucket_counts[0] = 10;
+        bucket_counts[2] = 9;
+        bucket_counts[LATENCY_BUCKET_BOUNDS_SECS.len()] = 1;
+        assert_eq!(latency_percentile(&bucket_counts, 0.5, 172800), Some(15));
+        assert_eq!(latency_percentile(&bucket_counts, 0.95, 172800), Some(60));
+        assert_eq!(
+            latency_percentile(&bucket_counts, 1.0, 172800),
+            Some(172800)
+        );
+
+        // Estimates never exceed the longest latency
+        assert_eq!(latency_percentile(&bucket_counts[..3], 0.95, 40), Some(40));
+    }
+}
@@ -0,0 +1,74 @@
+use eyre::Result;
+use itertools::Itertools;
+use sea_orm::{prelude::*, ActiveValue::*, Insert};
+use tracing::{debug, instrument, trace};
+
+use hyperlane_core::{LogMeta, ValidatorAnnouncement};
+use migration::OnConflict;
+
+use crate::conversions::address_to_bytes;
+use crate::date_time;
+use crate::db::ScraperDb;
+
+use super::generated::validator_announcement;
+
+pub struct StorableValidatorAnnouncement<'a> {
+    pub announcement: &'a ValidatorAnnouncement,
+    pub meta: &'a LogMeta,
+    /// The database id of the transaction the announcement was made in
+    pub txn_id: i64,
+}
+
+impl ScraperDb {
+    /// Store validator announcements into the database (or update existing
+    /// ones).
+    #[instrument(skip_all)]
+    pub async fn store_validator_announcements(
+        &self,
+        domain: u32,
+        announcements: impl Iterator<Item = StorableValidatorAnnouncement<'_>>,
+    ) -> Result<u64> {
+        let models = announcements
+            .map(|storable| validator_announcement::ActiveModel {
+                id: NotSet,
+                time_created: Set(date_time::now()),
+                domain: Unchanged(domain as i32),
+                validator: Set(address_to_bytes(&storable.announcement.validator)),
+                storage_location: Set(storable.announcement.storage_location.clone()),
+                tx_id: Unchanged(storable.txn_id),
+                log_index: Unchanged(storable.meta.log_index.as_u64() as i64),
+            })
+            .collect_vec();
+
+        trace!(?models, "Writing validator announcements to database");
+
+        if models.is_empty() {
+            debug!("Wrote zero new validator announcements to database");
+            return Ok(0);
+        }
+
+        let announcements_count = models.len() as u64;
+        Insert::many(models)
+            .on_conflict(
+                OnConflict::columns([
+                    // don't need domain because TxId includes it
+                    validator_announcement::Column::TxId,
+                    validator_announcement::Column::LogIndex,
+                ])
+                .update_columns([
+                    validator_announcement::Column::TimeCreated,
+                    validator_announcement::Column::Validator,
+                    validator_announcement::Column::StorageLocation,
+                ])
+                .to_owned(),
+            )
+            .exec(&self.0)
+            .await?;
+
+        debug!(
+            announcements = announcements_count,
+            "Wrote validator announcements to database"
+        );
+        Ok(announcements_count)
+    }
+}
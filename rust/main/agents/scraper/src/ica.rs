@@ -0,0 +1,130 @@
+//! Decoding of the messages of interchain account routers, so the explorer can
+//! show the calls an interchain account message executes on its destination.
+
+use ethers::abi::{decode, ParamType, Token};
+use eyre::{eyre, Result};
+use hyperlane_core::{H256, U256};
+
+/// A call made by an interchain account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcaCall {
+    /// The address called
+    pub target: H256,
+    /// The value sent with the call
+    pub value: U256,
+    /// The calldata
+    pub data: Vec<u8>,
+}
+
+/// The body of a message sent by an interchain account router, formatted as
+/// `abi.encode(bytes32 owner, bytes32 ism, CallLib.Call[] calls)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcaMessage {
+    /// The owner of the interchain account on the origin
+    pub owner: H256,
+    /// The ISM of the interchain account, zero for the default ISM
+    pub ism: H256,
+    /// The calls the interchain account makes, in order
+    pub calls: Vec<IcaCall>,
+}
+
+impl IcaMessage {
+    /// Decode the body of a message sent by an interchain account router
+    pub fn decode(body: &[u8]) -> Result<Self> {
+        let call = ParamType::Tuple(vec![
+            ParamType::FixedBytes(32),
+            ParamType::Uint(256),
+            ParamType::Bytes,
+        ]);
+        let tokens = decode(
+            &[
+                ParamType::FixedBytes(32),
+                ParamType::FixedBytes(32),
+                ParamType::Array(Box::new(call)),
+            ],
+            body,
+        )?;
+        let [owner, ism, Token::Array(calls)] = tokens.as_slice() else {
+            return Err(eyre!("Unexpected interchain account message tokens"));
+        };
+        let calls = calls
+            .iter()
+            .map(|call| match call {
+                Token::Tuple(fields) => match fields.as_slice() {
+                    [target, Token::Uint(value), Token::Bytes(data)] => Ok(IcaCall {
+                        target: bytes32(target)?,
+                        value: (*value).into(),
+                        data: data.clone(),
+                    }),
+                    _ => Err(eyre!("Unexpected interchain account call fields")),
+                },
+                _ => Err(eyre!("Unexpected interchain account call token")),
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            owner: bytes32(owner)?,
+            ism: bytes32(ism)?,
+            calls,
+        })
+    }
+}
+
+fn bytes32(token: &Token) -> Result<H256> {
+    match token {
+        Token::FixedBytes(bytes) if bytes.len() == 32 => Ok(H256::from_slice(bytes)),
+        _ => Err(eyre!("Expected a bytes32 token")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethers::abi::encode;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_ica_message() {
+        let owner = H256::repeat_byte(0x11);
+        let target = H256::from_low_u64_be(0xbeef);
+        let body = encode(&[
+            Token::FixedBytes(owner.as_bytes().to_vec()),
+            Token::FixedBytes(H256::zero().as_bytes().to_vec()),
+            Token::Array(vec![
+                Token::Tuple(vec![
+                    Token::FixedBytes(target.as_bytes().to_vec()),
+                    Token::Uint(7.into()),
+                    Token::Bytes(vec![0xde, 0xad, 0xbe, 0xef]),
+                ]),
+                Token::Tuple(vec![
+                    Token::FixedBytes(target.as_bytes().to_vec()),
+                    Token::Uint(0.into()),
+                    Token::Bytes(vec![]),
+                ]),
+            ]),
+        ]);
+
+        let message = IcaMessage::decode(&body).unwrap();
+        assert_eq!(message.owner, owner);
+        assert_eq!(message.ism, H256::zero());
+        assert_eq!(
+            message.calls,
+            vec![
+                IcaCall {
+                    target,
+                    value: 7.into(),
+                    data: vec![0xde, 0xad, 0xbe, 0xef],
+                },
+                IcaCall {
+                    target,
+                    value: 0.into(),
+                    data: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_body() {
+        assert!(IcaMessage::decode(&[0x01, 0x02]).is_err());
+    }
+}
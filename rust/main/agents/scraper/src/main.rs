@@ -13,9 +13,11 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+use std::process::ExitCode;
+
 use agent::Scraper;
 use eyre::Result;
-use hyperlane_base::agent_main;
+use hyperlane_base::{agent_main, AgentOutcome};
 
 mod db;
 
@@ -23,9 +25,15 @@ mod agent;
 mod chain_scraper;
 mod conversions;
 mod date_time;
+mod ica;
+mod query_api;
 mod settings;
 
 #[tokio::main(flavor = "current_thread")]
-async fn main() -> Result<()> {
-    agent_main::<Scraper>().await
+async fn main() -> Result<ExitCode> {
+    let outcome = agent_main::<Scraper>().await?;
+    if let AgentOutcome::ConfigValidated(report) = &outcome {
+        println!("{}", report.to_json()?);
+    }
+    Ok(outcome.exit_code())
 }
@@ -0,0 +1,249 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    routing, Router,
+};
+use std::{collections::HashMap, sync::Arc};
+
+use derive_new::new;
+use hyperlane_core::{utils::hex_or_base58_to_h256, AddressFormatter, H256};
+use serde::{Deserialize, Serialize};
+
+use crate::db::{DeliveryStatus, MessageFilter, MessageRecord, ScraperDb};
+
+const QUERY_API_BASE: &str = "/query";
+
+/// Number of messages returned when the request doesn't set a limit
+const DEFAULT_MESSAGES_LIMIT: u64 = 100;
+/// Maximum number of messages a request can ask for
+const MAX_MESSAGES_LIMIT: u64 = 1000;
+/// Maximum number of announcements returned for a validator
+const MAX_ANNOUNCEMENTS_LIMIT: u64 = 1000;
+/// Maximum number of messages a snapshot request can ask for, which is also
+/// the default
+const MAX_SNAPSHOT_LIMIT: u64 = 10_000;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct MessagesRequest {
+    msg_id: Option<String>,
+    origin: Option<u32>,
+    destination: Option<u32>,
+    sender: Option<String>,
+    recipient: Option<String>,
+    status: Option<DeliveryStatus>,
+    limit: Option<u64>,
+}
+
+impl MessagesRequest {
+    fn into_filter(self) -> Result<MessageFilter, String> {
+        let parse = |name: &str, value: Option<String>| {
+            value
+                .map(|value| {
+                    hex_or_base58_to_h256(&value).map_err(|err| format!("Invalid {name}: {err}"))
+                })
+                .transpose()
+        };
+        Ok(MessageFilter {
+            msg_id: parse("msg_id", self.msg_id)?,
+            origin: self.origin,
+            destination: self.destination,
+            sender: parse("sender", self.sender)?,
+            recipient: parse("recipient", self.recipient)?,
+            status: self.status,
+            limit: self
+                .limit
+                .unwrap_or(DEFAULT_MESSAGES_LIMIT)
+                .min(MAX_MESSAGES_LIMIT),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct GasPaymentsRequest {
+    msg_id: String,
+}
+
+/// The announcements of a validator, on every domain unless one is set
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct ValidatorAnnouncementsRequest {
+    validator: String,
+    domain: Option<u32>,
+    limit: Option<u64>,
+}
+
+/// A page of the snapshot of the messages dispatched from a mailbox, which
+/// agents bootstrap their database from
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct SnapshotRequest {
+    origin: u32,
+    mailbox: String,
+    from_nonce: Option<u32>,
+    limit: Option<u64>,
+}
+
+/// A message, with its sender and recipient formatted the way their chains
+/// display addresses
+#[derive(Serialize)]
+struct MessageResponse {
+    #[serde(flatten)]
+    record: MessageRecord,
+    sender_address: String,
+    recipient_address: String,
+}
+
+/// Serves searches over the messages, deliveries, gas payments and validator
+/// announcements in the scraper database, so that explorers and support
+/// tooling don't need direct access to it.
+#[derive(new, Clone)]
+pub struct QueryApi {
+    db: ScraperDb,
+    /// How the addresses of each domain are displayed
+    formatters: Arc<HashMap<u32, AddressFormatter>>,
+}
+
+fn json_response(result: eyre::Result<impl Serialize>) -> (StatusCode, String) {
+    match result.and_then(|records| Ok(serde_json::to_string_pretty(&records)?)) {
+        Ok(body) => (StatusCode::OK, body),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to query database: {}", err),
+        ),
+    }
+}
+
+async fn messages(
+    State(api): State<QueryApi>,
+    Query(request): Query<MessagesRequest>,
+) -> (StatusCode, String) {
+    let filter = match request.into_filter() {
+        Ok(filter) => filter,
+        Err(err) => return (StatusCode::BAD_REQUEST, err),
+    };
+    let messages = api.db.query_messages(&filter).await.map(|records| {
+        records
+            .into_iter()
+            .map(|record| api.message_response(record))
+            .collect::<Vec<_>>()
+    });
+    json_response(messages)
+}
+
+async fn gas_payments(
+    State(QueryApi { db, .. }): State<QueryApi>,
+    Query(request): Query<GasPaymentsRequest>,
+) -> (StatusCode, String) {
+    let msg_id: H256 = match hex_or_base58_to_h256(&request.msg_id) {
+        Ok(msg_id) => msg_id,
+        Err(err) => return (StatusCode::BAD_REQUEST, format!("Invalid msg_id: {}", err)),
+    };
+    json_response(db.query_gas_payments(&msg_id).await)
+}
+
+async fn validator_announcements(
+    State(QueryApi { db, .. }): State<QueryApi>,
+    Query(request): Query<ValidatorAnnouncementsRequest>,
+) -> (StatusCode, String) {
+    let validator: H256 = match hex_or_base58_to_h256(&request.validator) {
+        Ok(validator) => validator,
+        Err(err) => return (StatusCode::BAD_REQUEST, format!("Invalid validator: {err}")),
+    };
+    let limit = request
+        .limit
+        .unwrap_or(MAX_ANNOUNCEMENTS_LIMIT)
+        .min(MAX_ANNOUNCEMENTS_LIMIT);
+    json_response(
+        db.query_validator_announcements(&validator, request.domain, limit)
+            .await,
+    )
+}
+
+async fn snapshot(
+    State(QueryApi { db, .. }): State<QueryApi>,
+    Query(request): Query<SnapshotRequest>,
+) -> (StatusCode, String) {
+    let mailbox: H256 = match hex_or_base58_to_h256(&request.mailbox) {
+        Ok(mailbox) => mailbox,
+        Err(err) => return (StatusCode::BAD_REQUEST, format!("Invalid mailbox: {}", err)),
+    };
+    let limit = request
+        .limit
+        .unwrap_or(MAX_SNAPSHOT_LIMIT)
+        .min(MAX_SNAPSHOT_LIMIT);
+    json_response(
+        db.query_snapshot(
+            request.origin,
+            &mailbox,
+            request.from_nonce.unwrap_or_default(),
+            limit,
+        )
+        .await,
+    )
+}
+
+impl QueryApi {
+    fn format_address(&self, domain: u32, address: H256) -> String {
+        match self.formatters.get(&domain) {
+            Some(formatter) => formatter.format(address),
+            None => format!("{address:?}"),
+        }
+    }
+
+    fn message_response(&self, record: MessageRecord) -> MessageResponse {
+        MessageResponse {
+            sender_address: self.format_address(record.origin, record.sender),
+            recipient_address: self.format_address(record.destination, record.recipient),
+            record,
+        }
+    }
+
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/messages", routing::get(messages))
+            .route("/gas_payments", routing::get(gas_payments))
+            .route(
+                "/validator_announcements",
+                routing::get(validator_announcements),
+            )
+            .route("/snapshot", routing::get(snapshot))
+            .with_state(self.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (QUERY_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages_request_into_filter() {
+        let request = MessagesRequest {
+            sender: Some("0x000000000000000000000000000000000000beef".to_owned()),
+            status: Some(DeliveryStatus::Pending),
+            limit: Some(MAX_MESSAGES_LIMIT + 1),
+            ..Default::default()
+        };
+        assert_eq!(
+            request.into_filter(),
+            Ok(MessageFilter {
+                sender: Some(H256::from_low_u64_be(0xbeef)),
+                status: Some(DeliveryStatus::Pending),
+                limit: MAX_MESSAGES_LIMIT,
+                ..Default::default()
+            })
+        );
+
+        let request = MessagesRequest {
+            msg_id: Some("0x1234".to_owned()),
+            ..Default::default()
+        };
+        assert!(request.into_filter().is_err());
+
+        assert_eq!(
+            MessagesRequest::default().into_filter().map(|f| f.limit),
+            Ok(DEFAULT_MESSAGES_LIMIT)
+        );
+    }
+}
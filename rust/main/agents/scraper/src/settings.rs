@@ -15,7 +15,9 @@ use hyperlane_base::{
         Settings,
     },
 };
-use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain};
+use hyperlane_core::{
+    cfg_unwrap_all, config::*, utils::hex_or_base58_to_h256, HyperlaneDomain, H256,
+};
 use serde::Deserialize;
 use serde_json::Value;
 
@@ -30,6 +32,12 @@ pub struct ScraperSettings {
 
     pub db: String,
     pub chains_to_scrape: Vec<HyperlaneDomain>,
+    /// Interchain account routers, whose messages are decoded into the calls
+    /// they execute on their destination.
+    pub ica_routers: HashSet<H256>,
+    /// Whether to serve the query api, searching the scraped messages, on the
+    /// metrics port
+    pub query_api: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -69,6 +77,29 @@ impl FromRawConf<RawScraperSettings> for ScraperSettings {
             .end()
             .map(|v| v.to_owned());
 
+        let ica_routers = p
+            .chain(&mut err)
+            .get_opt_key("icaRouters")
+            .parse_string()
+            .end()
+            .map(|routers| {
+                routers
+                    .split(',')
+                    .filter_map(|router| {
+                        hex_or_base58_to_h256(router.trim())
+                            .context("Invalid interchain account router address")
+                            .take_err(&mut err, || cwp + "ica_routers")
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let query_api = p
+            .chain(&mut err)
+            .get_opt_key("queryApi")
+            .parse_bool()
+            .unwrap_or(false);
+
         let chains_to_scrape = if let (Some(base), Some(chains)) = (&base, chains_names_to_scrape) {
             chains
                 .into_iter()
@@ -89,6 +120,8 @@ impl FromRawConf<RawScraperSettings> for ScraperSettings {
             base,
             db,
             chains_to_scrape,
+            ica_routers,
+            query_api,
         })
     }
 }
@@ -3,9 +3,11 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+use std::process::ExitCode;
+
 use eyre::Result;
 
-use hyperlane_base::agent_main;
+use hyperlane_base::{agent_main, AgentOutcome};
 
 use crate::validator::Validator;
 
@@ -15,6 +17,10 @@ mod submit;
 mod validator;
 
 #[tokio::main(flavor = "current_thread")]
-async fn main() -> Result<()> {
-    agent_main::<Validator>().await
+async fn main() -> Result<ExitCode> {
+    let outcome = agent_main::<Validator>().await?;
+    if let AgentOutcome::ConfigValidated(report) = &outcome {
+        println!("{}", report.to_json()?);
+    }
+    Ok(outcome.exit_code())
 }
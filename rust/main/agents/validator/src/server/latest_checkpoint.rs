@@ -0,0 +1,114 @@
+//! A server that serves the index of the latest checkpoint the validator
+//! signed, so that relayers learn about new checkpoints without polling the
+//! checkpoint storage. The validator announces its url as one of its storage
+//! locations when `checkpointEndpointUrl` is configured, and relayers then
+//! long poll it with the last index they know of.
+//!
+//! Base URL /checkpoints
+//! Routes
+//! - /latest - Index of the latest signed checkpoint
+//!   eg. response {"index":42}
+//!   With `?after=<index>`, the response is held until a checkpoint newer than
+//!   `index` is signed, for up to 30 seconds (long polling).
+
+use std::time::Duration;
+
+use axum::{extract::Query, routing::get, Json, Router};
+use derive_new::new;
+use hyperlane_base::LatestCheckpointResponse;
+use serde::Deserialize;
+use tokio::{sync::watch, time::timeout};
+
+const LATEST_CHECKPOINT_API_BASE: &str = "/checkpoints";
+
+/// How long a long polling request is held waiting for a new checkpoint
+const LONG_POLL_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Deserialize, Debug, Default)]
+struct LatestCheckpointQuery {
+    /// Hold the response until a checkpoint newer than this index is signed
+    after: Option<u32>,
+}
+
+#[derive(new)]
+pub struct LatestCheckpointApi {
+    /// Index of the latest checkpoint written to the checkpoint storage
+    latest_index: watch::Receiver<Option<u32>>,
+}
+
+impl LatestCheckpointApi {
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (LATEST_CHECKPOINT_API_BASE, self.router())
+    }
+
+    pub fn router(&self) -> Router {
+        let latest_index = self.latest_index.clone();
+        Router::new().route(
+            "/latest",
+            get(move |Query(query): Query<LatestCheckpointQuery>| {
+                Self::latest_checkpoint_handler(latest_index.clone(), query)
+            }),
+        )
+    }
+
+    async fn latest_checkpoint_handler(
+        mut latest_index: watch::Receiver<Option<u32>>,
+        query: LatestCheckpointQuery,
+    ) -> Json<LatestCheckpointResponse> {
+        if let Some(after) = query.after {
+            // Answering with the current index on timeout or shutdown is fine,
+            // the relayer polls again
+            let _ = timeout(
+                LONG_POLL_TIMEOUT,
+                latest_index.wait_for(|index| index.map_or(false, |index| index > after)),
+            )
+            .await;
+        }
+        let index = *latest_index.borrow();
+        Json(LatestCheckpointResponse { index })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::*;
+
+    fn setup_test_server(latest_index: watch::Receiver<Option<u32>>) -> SocketAddr {
+        let app = LatestCheckpointApi::new(latest_index).router();
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_latest_checkpoint_long_poll() {
+        let (tx, rx) = watch::channel(Some(41));
+        let addr = setup_test_server(rx);
+        let client = reqwest::Client::new();
+
+        let res: LatestCheckpointResponse = client
+            .get(format!("http://{}/latest", addr))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(res.index, Some(41));
+
+        // Held until a newer checkpoint is signed
+        let long_poll = client
+            .get(format!("http://{}/latest?after=41", addr))
+            .send();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            tx.send_replace(Some(42));
+        });
+        let res: LatestCheckpointResponse = long_poll.await.unwrap().json().await.unwrap();
+        assert_eq!(res.index, Some(42));
+    }
+}
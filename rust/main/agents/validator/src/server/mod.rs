@@ -1,19 +1,27 @@
 pub mod eigen_node;
+pub mod latest_checkpoint;
 use std::{sync::Arc, vec};
 
 use axum::Router;
 pub use eigen_node::EigenNodeApi;
+pub use latest_checkpoint::LatestCheckpointApi;
 
 use hyperlane_base::CoreMetrics;
 use hyperlane_core::HyperlaneDomain;
+use tokio::sync::watch;
 
 /// Returns a vector of validator-specific endpoint routes to be served.
 /// Can be extended with additional routes and feature flags to enable/disable individually.
 pub fn routes(
     origin_chain: HyperlaneDomain,
     metrics: Arc<CoreMetrics>,
+    latest_index: watch::Receiver<Option<u32>>,
 ) -> Vec<(&'static str, Router)> {
     let eigen_node_api = EigenNodeApi::new(origin_chain, metrics);
+    let latest_checkpoint_api = LatestCheckpointApi::new(latest_index);
 
-    vec![eigen_node_api.get_route()]
+    vec![
+        eigen_node_api.get_route(),
+        latest_checkpoint_api.get_route(),
+    ]
 }
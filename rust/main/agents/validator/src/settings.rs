@@ -14,6 +14,7 @@ use hyperlane_base::{
         parser::{RawAgentConf, RawAgentSignerConf, ValueParser},
         CheckpointSyncerConf, Settings, SignerConf,
     },
+    LatestCheckpointEndpoint,
 };
 use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain, HyperlaneDomainProtocol};
 use serde::Deserialize;
@@ -40,6 +41,10 @@ pub struct ValidatorSettings {
     pub reorg_period: u64,
     /// How frequently to check for new checkpoints
     pub interval: Duration,
+    /// The public base url of the validator's server, announced alongside
+    /// its checkpoint storage so that relayers long poll its latest
+    /// checkpoint index
+    pub checkpoint_endpoint_url: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -114,6 +119,21 @@ impl FromRawConf<RawValidatorSettings> for ValidatorSettings {
             .map(Duration::from_secs)
             .unwrap_or(Duration::from_secs(5));
 
+        let checkpoint_endpoint_url = p
+            .chain(&mut err)
+            .get_opt_key("checkpointEndpointUrl")
+            .parse_string()
+            .end()
+            .map(str::to_owned);
+        if let Some(url) = &checkpoint_endpoint_url {
+            if LatestCheckpointEndpoint::from_location(url, None).is_none() {
+                err.push(
+                    cwp + "checkpoint_endpoint_url",
+                    eyre!("Expected an http(s) url, got {url}"),
+                );
+            }
+        }
+
         cfg_unwrap_all!(cwp, err: [origin_chain_name]);
 
         let reorg_period = p
@@ -128,6 +148,7 @@ impl FromRawConf<RawValidatorSettings> for ValidatorSettings {
         cfg_unwrap_all!(cwp, err: [base, origin_chain, validator, checkpoint_syncer]);
 
         let mut base: Settings = base;
+        let validator = validator.with_proxy(base.proxy.as_ref());
         // If the origin chain is an EVM chain, then we can use the validator as the signer if needed.
         if origin_chain.domain_protocol() == HyperlaneDomainProtocol::Ethereum {
             if let Some(origin) = base.chains.get_mut(origin_chain.name()) {
@@ -143,6 +164,7 @@ impl FromRawConf<RawValidatorSettings> for ValidatorSettings {
             checkpoint_syncer,
             reorg_period,
             interval,
+            checkpoint_endpoint_url,
         })
     }
 }
@@ -3,8 +3,9 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::vec;
 
+use futures_util::stream::{self, StreamExt};
 use prometheus::IntGauge;
-use tokio::time::sleep;
+use tokio::{sync::watch, time::sleep};
 use tracing::{debug, error, info};
 
 use hyperlane_base::db::HyperlaneDb;
@@ -17,6 +18,10 @@ use hyperlane_core::{
 use hyperlane_core::{ChainResult, MerkleTreeHook, ReorgEvent};
 use hyperlane_ethereum::SingletonSignerHandle;
 
+/// Maximum number of checkpoints signed and submitted at the same time when
+/// catching up on a backlog
+const MAX_CONCURRENT_SUBMISSIONS: usize = 10;
+
 #[derive(Clone)]
 pub(crate) struct ValidatorSubmitter {
     interval: Duration,
@@ -24,6 +29,7 @@ pub(crate) struct ValidatorSubmitter {
     signer: SingletonSignerHandle,
     merkle_tree_hook: Arc<dyn MerkleTreeHook>,
     checkpoint_syncer: Arc<dyn CheckpointSyncer>,
+    latest_index: Arc<watch::Sender<Option<u32>>>,
     db: Arc<dyn HyperlaneDb>,
     metrics: ValidatorSubmitterMetrics,
 }
@@ -35,6 +41,7 @@ impl ValidatorSubmitter {
         merkle_tree_hook: Arc<dyn MerkleTreeHook>,
         signer: SingletonSignerHandle,
         checkpoint_syncer: Arc<dyn CheckpointSyncer>,
+        latest_index: Arc<watch::Sender<Option<u32>>>,
         db: Arc<dyn HyperlaneDb>,
         metrics: ValidatorSubmitterMetrics,
     ) -> Self {
@@ -44,6 +51,7 @@ impl ValidatorSubmitter {
             merkle_tree_hook,
             signer,
             checkpoint_syncer,
+            latest_index,
             db,
             metrics,
         }
@@ -273,25 +281,35 @@ impl ValidatorSubmitter {
         Ok(())
     }
 
-    /// Signs and submits any previously unsubmitted checkpoints.
+    /// Signs and submits any previously unsubmitted checkpoints, up to
+    /// `MAX_CONCURRENT_SUBMISSIONS` at a time.
+    ///
+    /// The latest index is only updated once every checkpoint has been
+    /// submitted, so that it never points past a checkpoint that is missing
+    /// from the store.
     async fn sign_and_submit_checkpoints(&self, checkpoints: Vec<CheckpointWithMessageId>) {
-        let last_checkpoint = checkpoints.as_slice()[checkpoints.len() - 1];
+        let Some(last_checkpoint) = checkpoints.last().copied() else {
+            return;
+        };
         // Submits checkpoints to the store in reverse order. This speeds up processing historic checkpoints (those before the validator is spun up),
         // since those are the most likely to make messages become processable.
         // A side effect is that new checkpoints will also be submitted in reverse order.
-        for queued_checkpoint in checkpoints.into_iter().rev() {
-            // certain checkpoint stores rate limit very aggressively, so we retry indefinitely
-            call_and_retry_indefinitely(|| {
-                let self_clone = self.clone();
-                Box::pin(async move {
-                    self_clone
-                        .sign_and_submit_checkpoint(queued_checkpoint)
-                        .await?;
-                    Ok(())
+        stream::iter(checkpoints.into_iter().rev())
+            .map(|queued_checkpoint| {
+                // certain checkpoint stores rate limit very aggressively, so we retry indefinitely
+                call_and_retry_indefinitely(move || {
+                    let self_clone = self.clone();
+                    Box::pin(async move {
+                        self_clone
+                            .sign_and_submit_checkpoint(queued_checkpoint)
+                            .await?;
+                        Ok(())
+                    })
                 })
             })
+            .buffer_unordered(MAX_CONCURRENT_SUBMISSIONS)
+            .collect::<Vec<()>>()
             .await;
-        }
 
         call_and_retry_indefinitely(|| {
             let self_clone = self.clone();
@@ -304,6 +322,17 @@ impl ValidatorSubmitter {
             })
         })
         .await;
+
+        // Wakes up relayers long polling for a new checkpoint. The backfill and
+        // tip submitters share the index, which must not go backwards.
+        self.latest_index.send_if_modified(|latest_index| {
+            if latest_index.map_or(true, |index| index < last_checkpoint.index) {
+                *latest_index = Some(last_checkpoint.index);
+                true
+            } else {
+                false
+            }
+        });
     }
 }
 
@@ -338,6 +367,7 @@ impl ValidatorSubmitterMetrics {
 mod test {
     use super::*;
     use async_trait::async_trait;
+    use ethers::signers::LocalWallet;
     use eyre::Result;
     use hyperlane_base::{
         db::{DbResult, HyperlaneDb, InterchainGasExpenditureData, InterchainGasPaymentData},
@@ -349,8 +379,13 @@ mod test {
         InterchainGasPaymentMeta, MerkleTreeHook, MerkleTreeInsertion, PendingOperationStatus,
         ReorgEvent, SignedAnnouncement, SignedCheckpointWithMessageId, H160, H256,
     };
+    use hyperlane_ethereum::SingletonSigner;
     use prometheus::Registry;
-    use std::{fmt::Debug, sync::Arc, time::Duration};
+    use std::{
+        fmt::Debug,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
     use tokio::sync::mpsc;
 
     mockall::mock! {
@@ -438,6 +473,15 @@ mod test {
                 &self,
                 message_id: &H256,
             ) -> DbResult<Option<u32>>;
+            fn store_pending_message_next_attempt_by_message_id(
+                &self,
+                message_id: &H256,
+                timestamp: &u64,
+            ) -> DbResult<()>;
+            fn retrieve_pending_message_next_attempt_by_message_id(
+                &self,
+                message_id: &H256,
+            ) -> DbResult<Option<u64>>;
             fn store_merkle_tree_insertion_by_leaf_index(
                 &self,
                 leaf_index: &u32,
@@ -464,6 +508,7 @@ mod test {
             ) -> DbResult<Option<u64>>;
             fn store_highest_seen_message_nonce_number(&self, nonce: &u32) -> DbResult<()>;
             fn retrieve_highest_seen_message_nonce_number(&self) -> DbResult<Option<u32>>;
+            fn take_lowest_invalidated_message_nonce(&self) -> DbResult<Option<u32>>;
 
         }
     }
@@ -629,6 +674,7 @@ mod test {
             Arc::new(mock_merkle_tree_hook),
             dummy_singleton_handle(),
             Arc::new(mock_checkpoint_syncer),
+            Arc::new(watch::channel(None).0),
             Arc::new(db),
             dummy_metrics(),
         );
@@ -650,4 +696,73 @@ mod test {
             )
             .await;
     }
+
+    #[tokio::test]
+    async fn latest_index_is_updated_after_all_checkpoints_are_submitted() {
+        let (signer, signer_handle) =
+            SingletonSigner::new(LocalWallet::new(&mut ethers::core::rand::thread_rng()).into());
+        tokio::spawn(signer.run());
+
+        let mut mock_merkle_tree_hook = MockMerkleTreeHook::new();
+        mock_merkle_tree_hook
+            .expect_address()
+            .returning(|| H256::from_low_u64_be(0));
+
+        let written_indices = Arc::new(Mutex::new(vec![]));
+        let mut mock_checkpoint_syncer = MockCheckpointSyncer::new();
+        mock_checkpoint_syncer
+            .expect_fetch_checkpoint()
+            .returning(|_| Ok(None));
+        let written_indices_clone = written_indices.clone();
+        mock_checkpoint_syncer
+            .expect_write_checkpoint()
+            .times(25)
+            .returning(move |signed_checkpoint| {
+                written_indices_clone
+                    .lock()
+                    .unwrap()
+                    .push(signed_checkpoint.value.index);
+                Ok(())
+            });
+        let written_indices_clone = written_indices.clone();
+        mock_checkpoint_syncer
+            .expect_update_latest_index()
+            .once()
+            .returning(move |index| {
+                assert_eq!(index, 24);
+                // every checkpoint up to the latest index is in the store
+                let mut written_indices = written_indices_clone.lock().unwrap().clone();
+                written_indices.sort();
+                assert_eq!(written_indices, (0..25).collect::<Vec<_>>());
+                Ok(())
+            });
+
+        let latest_index = Arc::new(watch::channel(None).0);
+        let validator_submitter = ValidatorSubmitter::new(
+            Duration::from_secs(1),
+            0,
+            Arc::new(mock_merkle_tree_hook),
+            signer_handle,
+            Arc::new(mock_checkpoint_syncer),
+            latest_index.clone(),
+            Arc::new(MockDb::new()),
+            dummy_metrics(),
+        );
+
+        let checkpoints = (0..25)
+            .map(|index| CheckpointWithMessageId {
+                checkpoint: Checkpoint {
+                    root: H256::random(),
+                    index,
+                    merkle_tree_hook_address: H256::from_low_u64_be(0),
+                    mailbox_domain: 0,
+                },
+                message_id: H256::random(),
+            })
+            .collect();
+        validator_submitter
+            .sign_and_submit_checkpoints(checkpoints)
+            .await;
+        assert_eq!(*latest_index.borrow(), Some(24));
+    }
 }
@@ -6,21 +6,22 @@ use derive_more::AsRef;
 use eyre::Result;
 
 use futures_util::future::try_join_all;
-use tokio::{task::JoinHandle, time::sleep};
+use tokio::{sync::watch, task::JoinHandle, time::sleep};
 use tracing::{error, info, info_span, instrument::Instrumented, warn, Instrument};
 
 use hyperlane_base::{
     db::{HyperlaneDb, HyperlaneRocksDB, DB},
     metrics::AgentMetrics,
     settings::ChainConf,
-    AgentMetadata, BaseAgent, ChainMetrics, CheckpointSyncer, ContractSyncMetrics, ContractSyncer,
-    CoreMetrics, HyperlaneAgentCore, MetricsUpdater, SequencedDataContractSync,
+    spawn_named, AgentMetadata, BaseAgent, ChainMetrics, CheckpointSyncer, ContractSyncMetrics,
+    ContractSyncer, CoreMetrics, HyperlaneAgentCore, MetricsUpdater, SequencedDataContractSync,
+    TaskSupervisor,
 };
 
 use hyperlane_core::{
     Announcement, ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneSigner,
-    HyperlaneSignerExt, Mailbox, MerkleTreeHook, MerkleTreeInsertion, TxOutcome, ValidatorAnnounce,
-    H256, U256,
+    HyperlaneSignerExt, Mailbox, MerkleTreeHook, MerkleTreeInsertion, SignedAnnouncement,
+    TxOutcome, ValidatorAnnounce, H256, U256,
 };
 use hyperlane_ethereum::{SingletonSigner, SingletonSignerHandle};
 
@@ -47,6 +48,12 @@ pub struct Validator {
     reorg_period: u64,
     interval: Duration,
     checkpoint_syncer: Arc<dyn CheckpointSyncer>,
+    /// Index of the latest checkpoint written to the checkpoint storage,
+    /// served to relayers
+    latest_index: Arc<watch::Sender<Option<u32>>>,
+    /// The public url of the server serving the latest index, if relayers
+    /// should long poll it
+    checkpoint_endpoint_url: Option<String>,
     core_metrics: Arc<CoreMetrics>,
     agent_metrics: AgentMetrics,
     chain_metrics: ChainMetrics,
@@ -65,7 +72,6 @@ impl BaseAgent for Validator {
         metrics: Arc<CoreMetrics>,
         agent_metrics: AgentMetrics,
         chain_metrics: ChainMetrics,
-        _tokio_console_server: console_subscriber::Server,
     ) -> Result<Self>
     where
         Self: Sized,
@@ -79,7 +85,7 @@ impl BaseAgent for Validator {
         let core = settings.build_hyperlane_core(metrics.clone());
         let checkpoint_syncer = settings
             .checkpoint_syncer
-            .build_and_validate(None)
+            .build_and_validate(None, settings.proxy.as_ref())
             .await?
             .into();
 
@@ -126,6 +132,8 @@ impl BaseAgent for Validator {
             reorg_period: settings.reorg_period,
             interval: settings.interval,
             checkpoint_syncer,
+            latest_index: Arc::new(watch::channel(None).0),
+            checkpoint_endpoint_url: settings.checkpoint_endpoint_url.clone(),
             agent_metrics,
             chain_metrics,
             core_metrics: metrics,
@@ -138,14 +146,17 @@ impl BaseAgent for Validator {
         let mut tasks = vec![];
 
         // run server
-        let custom_routes =
-            validator_server::routes(self.origin_chain.clone(), self.core.metrics.clone());
+        let custom_routes = validator_server::routes(
+            self.origin_chain.clone(),
+            self.core.metrics.clone(),
+            self.latest_index.subscribe(),
+        );
         let server = self
             .core
             .settings
             .server(self.core_metrics.clone())
             .expect("Failed to create server");
-        let server_task = tokio::spawn(async move {
+        let server_task = spawn_named("server", None, async move {
             server.run_with_custom_routes(custom_routes);
         })
         .instrument(info_span!("Validator server"));
@@ -153,7 +164,7 @@ impl BaseAgent for Validator {
 
         if let Some(signer_instance) = self.signer_instance.take() {
             tasks.push(
-                tokio::spawn(async move {
+                spawn_named("singleton_signer", Some(&self.origin_chain), async move {
                     signer_instance.run().await;
                 })
                 .instrument(info_span!("SingletonSigner")),
@@ -170,7 +181,7 @@ impl BaseAgent for Validator {
         .await
         .unwrap();
         tasks.push(
-            tokio::spawn(async move {
+            spawn_named("metrics_updater", Some(&self.origin_chain), async move {
                 metrics_updater.spawn().await.unwrap();
             })
             .instrument(info_span!("MetricsUpdater")),
@@ -195,7 +206,7 @@ impl BaseAgent for Validator {
                     sleep(self.interval).await;
                 }
                 Ok(_) => {
-                    tasks.push(self.run_merkle_tree_hook_sync().await);
+                    tasks.push(self.run_merkle_tree_hook_sync());
                     for checkpoint_sync_task in self.run_checkpoint_submitters().await {
                         tasks.push(checkpoint_sync_task);
                     }
@@ -216,26 +227,34 @@ impl BaseAgent for Validator {
 }
 
 impl Validator {
-    async fn run_merkle_tree_hook_sync(&self) -> Instrumented<JoinHandle<()>> {
+    fn run_merkle_tree_hook_sync(&self) -> Instrumented<JoinHandle<()>> {
         let index_settings =
             self.as_ref().settings.chains[self.origin_chain.name()].index_settings();
         let contract_sync = self.merkle_tree_hook_sync.clone();
-        let cursor = contract_sync
-            .cursor(index_settings)
-            .await
-            .unwrap_or_else(|err| {
-                panic!(
-                    "Error getting merkle tree hook cursor for origin {0}: {err}",
-                    self.origin_chain
-                )
-            });
-        tokio::spawn(async move {
-            contract_sync
-                .clone()
-                .sync("merkle_tree_hook", cursor.into())
-                .await;
-        })
-        .instrument(info_span!("MerkleTreeHookSyncer"))
+        let origin = self.origin_chain.clone();
+        TaskSupervisor::new(&self.core_metrics)
+            .spawn(
+                "merkle_tree_hook_sync",
+                Some(&self.origin_chain),
+                move || {
+                    let index_settings = index_settings.clone();
+                    let contract_sync = contract_sync.clone();
+                    let origin = origin.clone();
+                    async move {
+                        let cursor =
+                            contract_sync
+                                .cursor(index_settings)
+                                .await
+                                .unwrap_or_else(|err| {
+                                    panic!(
+                                "Error getting merkle tree hook cursor for origin {origin}: {err}"
+                            )
+                                });
+                        contract_sync.sync("merkle_tree_hook", cursor.into()).await;
+                    }
+                },
+            )
+            .instrument(info_span!("MerkleTreeHookSyncer"))
     }
 
     async fn run_checkpoint_submitters(&self) -> Vec<Instrumented<JoinHandle<()>>> {
@@ -245,6 +264,7 @@ impl Validator {
             self.merkle_tree_hook.clone(),
             self.signer.clone(),
             self.checkpoint_syncer.clone(),
+            self.latest_index.clone(),
             Arc::new(self.db.clone()) as Arc<dyn HyperlaneDb>,
             ValidatorSubmitterMetrics::new(&self.core.metrics, &self.origin_chain),
         );
@@ -265,17 +285,25 @@ impl Validator {
 
         let mut tasks = vec![];
         tasks.push(
-            tokio::spawn(async move {
-                backfill_submitter
-                    .backfill_checkpoint_submitter(backfill_target)
-                    .await
-            })
+            spawn_named(
+                "backfill_checkpoint_submitter",
+                Some(&self.origin_chain),
+                async move {
+                    backfill_submitter
+                        .backfill_checkpoint_submitter(backfill_target)
+                        .await
+                },
+            )
             .instrument(info_span!("BackfillCheckpointSubmitter")),
         );
 
         tasks.push(
-            tokio::spawn(async move { submitter.checkpoint_submitter(tip_tree).await })
-                .instrument(info_span!("TipCheckpointSubmitter")),
+            spawn_named(
+                "tip_checkpoint_submitter",
+                Some(&self.origin_chain),
+                async move { submitter.checkpoint_submitter(tip_tree).await },
+            )
+            .instrument(info_span!("TipCheckpointSubmitter")),
         );
 
         tasks
@@ -319,20 +347,36 @@ impl Validator {
     }
 
     async fn announce(&self) -> Result<()> {
-        let address = self.signer.eth_address();
         let announcement_location = self.checkpoint_syncer.announcement_location();
+        let signed_announcement = self.sign_announcement(announcement_location).await?;
+        self.checkpoint_syncer
+            .write_announcement(&signed_announcement)
+            .await?;
+        self.ensure_announced(signed_announcement).await?;
+
+        // Relayers long poll the server for the latest checkpoint index once
+        // its url is announced as another location of the validator
+        if let Some(url) = &self.checkpoint_endpoint_url {
+            let signed_announcement = self.sign_announcement(url.clone()).await?;
+            self.ensure_announced(signed_announcement).await?;
+        }
+        Ok(())
+    }
 
-        // Sign and post the validator announcement
+    async fn sign_announcement(&self, storage_location: String) -> Result<SignedAnnouncement> {
         let announcement = Announcement {
-            validator: address,
+            validator: self.signer.eth_address(),
             mailbox_address: self.mailbox.address(),
             mailbox_domain: self.mailbox.domain().id(),
-            storage_location: announcement_location.clone(),
+            storage_location,
         };
-        let signed_announcement = self.signer.sign(announcement.clone()).await?;
-        self.checkpoint_syncer
-            .write_announcement(&signed_announcement)
-            .await?;
+        Ok(self.signer.sign(announcement).await?)
+    }
+
+    async fn ensure_announced(&self, signed_announcement: SignedAnnouncement) -> Result<()> {
+        let announcement = &signed_announcement.value;
+        let announcement_location = &announcement.storage_location;
+        let address = announcement.validator;
 
         // Ensure that the validator has announced themselves before we enter
         // the main validator submit loop. This is to avoid a situation in
@@ -347,7 +391,7 @@ impl Validator {
                 .await?
                 .first()
             {
-                if locations.contains(&announcement_location) {
+                if locations.contains(announcement_location) {
                     info!(
                         ?locations,
                         ?announcement_location,
@@ -0,0 +1,260 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use derive_more::AsRef;
+use eyre::Result;
+use futures_util::future::{join_all, try_join_all};
+use prometheus::{GaugeVec, IntGaugeVec};
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::{debug, error, info_span, instrument::Instrumented, warn, Instrument};
+
+use hyperlane_base::{
+    metrics::AgentMetrics, spawn_named, AgentMetadata, BaseAgent, ChainMetrics, CoreMetrics,
+    HyperlaneAgentCore,
+};
+use hyperlane_core::{ChainResult, HyperlaneProvider, TokenRouter, U256};
+
+use crate::{
+    route::{normalize, normalized_as_f64, RouteBalances, TokenType},
+    settings::{WarpMonitorSettings, WarpRouteConf, WarpRouterConf},
+};
+
+/// Metrics of the warp monitor.
+#[derive(Debug, Clone)]
+struct WarpMonitorMetrics {
+    /// Balance backing or minted by each router, in whole tokens.
+    router_balance: GaugeVec,
+    /// Collateral minus synthetic supply of each route, in whole tokens.
+    route_surplus: GaugeVec,
+    /// Whether the synthetic supply of a route exceeds its collateral.
+    route_undercollateralized: IntGaugeVec,
+}
+
+impl WarpMonitorMetrics {
+    fn new(metrics: &CoreMetrics) -> Result<Self> {
+        Ok(Self {
+            router_balance: metrics.new_gauge(
+                "warp_route_router_balance",
+                "Collateral locked or synthetic supply minted by a warp route router",
+                &["warp_route", "chain", "token_type"],
+            )?,
+            route_surplus: metrics.new_gauge(
+                "warp_route_collateral_surplus",
+                "Collateral minus synthetic supply across a warp route",
+                &["warp_route"],
+            )?,
+            route_undercollateralized: metrics.new_int_gauge(
+                "warp_route_undercollateralized",
+                "1 if the synthetic supply of a warp route exceeds its collateral, 0 otherwise",
+                &["warp_route"],
+            )?,
+        })
+    }
+}
+
+/// A warp monitor agent
+#[derive(Debug, AsRef)]
+pub struct WarpMonitor {
+    #[as_ref]
+    core: HyperlaneAgentCore,
+    route_monitors: Vec<RouteMonitor>,
+    core_metrics: Arc<CoreMetrics>,
+}
+
+#[async_trait]
+impl BaseAgent for WarpMonitor {
+    const AGENT_NAME: &'static str = "warp-monitor";
+
+    type Settings = WarpMonitorSettings;
+
+    async fn from_settings(
+        _agent_metadata: AgentMetadata,
+        settings: Self::Settings,
+        metrics: Arc<CoreMetrics>,
+        _agent_metrics: AgentMetrics,
+        _chain_metrics: ChainMetrics,
+    ) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        let core = settings.build_hyperlane_core(metrics.clone());
+        let monitor_metrics = WarpMonitorMetrics::new(&metrics)?;
+
+        let mut route_monitors = Vec::with_capacity(settings.warp_routes.len());
+        for route in &settings.warp_routes {
+            route_monitors.push(
+                RouteMonitor::new(
+                    &settings,
+                    route,
+                    &metrics,
+                    settings.interval,
+                    monitor_metrics.clone(),
+                )
+                .await?,
+            );
+        }
+
+        Ok(Self {
+            core,
+            route_monitors,
+            core_metrics: metrics,
+        })
+    }
+
+    #[allow(clippy::async_yields_async)]
+    async fn run(self) {
+        let mut tasks = vec![];
+
+        let server = self
+            .core
+            .settings
+            .server(self.core_metrics.clone())
+            .expect("Failed to create server");
+        tasks.push(server.run().instrument(info_span!("Warp monitor server")));
+
+        for route_monitor in self.route_monitors {
+            tasks.push(route_monitor.spawn());
+        }
+
+        // Note that this only returns an error if one of the tasks panics
+        if let Err(err) = try_join_all(tasks).await {
+            error!(?err, "One of the warp monitor tasks returned an error");
+        }
+    }
+}
+
+/// Reads the balance of one router of a route.
+#[derive(Debug)]
+enum RouterBalanceReader {
+    /// ERC20 collateral and synthetic routers, read through the router
+    TokenRouter(Box<dyn TokenRouter>),
+    /// Native routers, whose collateral is the native balance of an account
+    Native(Box<dyn HyperlaneProvider>),
+}
+
+#[derive(Debug)]
+struct RouterMonitor {
+    conf: WarpRouterConf,
+    reader: RouterBalanceReader,
+}
+
+impl RouterMonitor {
+    async fn balance(&self) -> ChainResult<U256> {
+        match (&self.reader, self.conf.token_type) {
+            (RouterBalanceReader::Native(provider), _) => {
+                provider
+                    .get_balance(self.conf.collateral_account.clone())
+                    .await
+            }
+            (RouterBalanceReader::TokenRouter(router), TokenType::Synthetic) => {
+                router.synthetic_supply().await
+            }
+            (RouterBalanceReader::TokenRouter(router), _) => router.collateral_balance().await,
+        }
+    }
+}
+
+/// Checks that the collateral of a warp route covers its synthetic supply.
+#[derive(Debug)]
+struct RouteMonitor {
+    name: String,
+    routers: Vec<RouterMonitor>,
+    interval: Duration,
+    metrics: WarpMonitorMetrics,
+}
+
+impl RouteMonitor {
+    async fn new(
+        settings: &WarpMonitorSettings,
+        route: &WarpRouteConf,
+        core_metrics: &CoreMetrics,
+        interval: Duration,
+        metrics: WarpMonitorMetrics,
+    ) -> Result<Self> {
+        let mut routers = Vec::with_capacity(route.routers.len());
+        for router in &route.routers {
+            let chain_conf = settings.chain_setup(&router.domain)?;
+            let reader = match router.token_type {
+                TokenType::Native => {
+                    RouterBalanceReader::Native(chain_conf.build_provider(core_metrics).await?)
+                }
+                TokenType::Collateral | TokenType::Synthetic => RouterBalanceReader::TokenRouter(
+                    chain_conf
+                        .build_token_router(router.address, core_metrics)
+                        .await?,
+                ),
+            };
+            routers.push(RouterMonitor {
+                conf: router.clone(),
+                reader,
+            });
+        }
+        Ok(Self {
+            name: route.name.clone(),
+            routers,
+            interval,
+            metrics,
+        })
+    }
+
+    async fn check(&self) {
+        let results = join_all(self.routers.iter().map(RouterMonitor::balance)).await;
+
+        let mut balances = RouteBalances::default();
+        let mut complete = true;
+        for (router, result) in self.routers.iter().zip(results) {
+            let conf = &router.conf;
+            match result {
+                Ok(balance) => {
+                    self.metrics
+                        .router_balance
+                        .with_label_values(&[
+                            &self.name,
+                            conf.domain.name(),
+                            &conf.token_type.to_string(),
+                        ])
+                        .set(normalized_as_f64(normalize(balance, conf.decimals)));
+                    balances.add(conf.token_type, balance, conf.decimals);
+                }
+                Err(err) => {
+                    warn!(warp_route=%self.name, domain=%conf.domain, router=?conf.address, ?err, "Failed to fetch warp route router balance");
+                    complete = false;
+                }
+            }
+        }
+
+        // A missing balance could hide or fake a violation, so only evaluate
+        // the invariant once every router was read.
+        if !complete {
+            return;
+        }
+
+        self.metrics
+            .route_surplus
+            .with_label_values(&[&self.name])
+            .set(balances.surplus());
+        let undercollateralized = balances.is_undercollateralized();
+        self.metrics
+            .route_undercollateralized
+            .with_label_values(&[&self.name])
+            .set(undercollateralized as i64);
+        if undercollateralized {
+            error!(warp_route=%self.name, collateral=%balances.collateral, synthetic=%balances.synthetic, "Warp route synthetic supply exceeds its collateral");
+        } else {
+            debug!(warp_route=%self.name, collateral=%balances.collateral, synthetic=%balances.synthetic, "Warp route is collateralized");
+        }
+    }
+
+    fn spawn(self) -> Instrumented<JoinHandle<()>> {
+        let span = info_span!("RouteMonitor", warp_route=%self.name);
+        spawn_named("route_monitor", None, async move {
+            let mut interval = tokio::time::interval(self.interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                self.check().await;
+            }
+        })
+        .instrument(span)
+    }
+}
@@ -0,0 +1,26 @@
+//! The warp monitor watches the balances backing warp routes across chains and
+//! reports routes whose synthetic supply is not fully backed by collateral.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use std::process::ExitCode;
+
+use eyre::Result;
+
+use hyperlane_base::{agent_main, AgentOutcome};
+
+use crate::agent::WarpMonitor;
+
+mod agent;
+mod route;
+mod settings;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<ExitCode> {
+    let outcome = agent_main::<WarpMonitor>().await?;
+    if let AgentOutcome::ConfigValidated(report) = &outcome {
+        println!("{}", report.to_json()?);
+    }
+    Ok(outcome.exit_code())
+}
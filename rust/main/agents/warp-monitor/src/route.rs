@@ -0,0 +1,111 @@
+use hyperlane_core::U256;
+use strum::{Display, EnumString};
+
+/// Decimals all balances of a route are normalized to before comparing them.
+const NORMALIZED_DECIMALS: u8 = 18;
+
+/// How a warp route router backs the token it bridges.
+#[derive(Debug, Display, EnumString, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum TokenType {
+    /// Locks an existing ERC20 token as collateral.
+    Collateral,
+    /// Locks the chain's native token as collateral.
+    Native,
+    /// Mints and burns a synthetic token.
+    Synthetic,
+}
+
+impl TokenType {
+    /// Whether the balance of this router backs the route, as opposed to
+    /// being backed by it.
+    pub fn is_collateral(&self) -> bool {
+        matches!(self, Self::Collateral | Self::Native)
+    }
+}
+
+/// Convert an amount with `decimals` decimals to `NORMALIZED_DECIMALS`.
+pub fn normalize(amount: U256, decimals: u8) -> U256 {
+    if decimals <= NORMALIZED_DECIMALS {
+        amount.saturating_mul(U256::exp10((NORMALIZED_DECIMALS - decimals) as usize))
+    } else {
+        amount / U256::exp10((decimals - NORMALIZED_DECIMALS) as usize)
+    }
+}
+
+/// Scale a normalized amount to whole tokens, for metrics.
+pub fn normalized_as_f64(amount: U256) -> f64 {
+    amount.to_f64_lossy() / 10f64.powi(NORMALIZED_DECIMALS as i32)
+}
+
+/// The normalized balances of every router of a route, summed by side.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RouteBalances {
+    /// Total collateral locked across the route
+    pub collateral: U256,
+    /// Total synthetic supply across the route
+    pub synthetic: U256,
+}
+
+impl RouteBalances {
+    /// Add the balance of one router, in the token's decimals on its chain.
+    pub fn add(&mut self, token_type: TokenType, balance: U256, decimals: u8) {
+        let normalized = normalize(balance, decimals);
+        if token_type.is_collateral() {
+            self.collateral = self.collateral.saturating_add(normalized);
+        } else {
+            self.synthetic = self.synthetic.saturating_add(normalized);
+        }
+    }
+
+    /// Whether the synthetic supply exceeds the collateral backing it.
+    pub fn is_undercollateralized(&self) -> bool {
+        self.collateral < self.synthetic
+    }
+
+    /// Collateral minus synthetic supply, in whole tokens. Negative if the
+    /// route is undercollateralized.
+    pub fn surplus(&self) -> f64 {
+        normalized_as_f64(self.collateral) - normalized_as_f64(self.synthetic)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_token_type() {
+        assert_eq!("collateral".parse::<TokenType>(), Ok(TokenType::Collateral));
+        assert_eq!("Native".parse::<TokenType>(), Ok(TokenType::Native));
+        assert_eq!("synthetic".parse::<TokenType>(), Ok(TokenType::Synthetic));
+        assert!("xerc20".parse::<TokenType>().is_err());
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(1.into(), 6), U256::exp10(12));
+        assert_eq!(normalize(1.into(), 18), 1.into());
+        assert_eq!(normalize(U256::exp10(2), 20), 1.into());
+    }
+
+    #[test]
+    fn test_balances_across_decimals() {
+        // 5 USDC with 6 decimals locked, 5 synthetic with 18 decimals minted
+        let mut balances = RouteBalances::default();
+        balances.add(TokenType::Collateral, 5_000_000.into(), 6);
+        balances.add(TokenType::Synthetic, U256::exp10(18) * 5u64, 18);
+        assert!(!balances.is_undercollateralized());
+        assert_eq!(balances.surplus(), 0.0);
+    }
+
+    #[test]
+    fn test_undercollateralized() {
+        let mut balances = RouteBalances::default();
+        balances.add(TokenType::Native, U256::exp10(18), 18);
+        balances.add(TokenType::Synthetic, U256::exp10(9), 9);
+        balances.add(TokenType::Synthetic, U256::exp10(9), 9);
+        assert!(balances.is_undercollateralized());
+        assert_eq!(balances.surplus(), -1.0);
+    }
+}
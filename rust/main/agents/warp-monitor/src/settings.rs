@@ -0,0 +1,215 @@
+//! Warp monitor configuration.
+//!
+//! The correct settings shape is defined in the TypeScript SDK metadata. While the exact shape
+//! and validations it defines are not applied here, we should mirror them.
+//! ANY CHANGES HERE NEED TO BE REFLECTED IN THE TYPESCRIPT SDK.
+
+use std::{collections::HashSet, time::Duration};
+
+use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use eyre::{eyre, Context};
+use hyperlane_base::{
+    impl_loadable_from_settings,
+    settings::{
+        parser::{RawAgentConf, ValueParser},
+        Settings,
+    },
+};
+use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain, HyperlaneDomainProtocol, H256};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::route::TokenType;
+
+/// Settings for `WarpMonitor`
+#[derive(Debug, AsRef, AsMut, Deref, DerefMut)]
+pub struct WarpMonitorSettings {
+    #[as_ref]
+    #[as_mut]
+    #[deref]
+    #[deref_mut]
+    base: Settings,
+
+    /// The warp routes to monitor
+    pub warp_routes: Vec<WarpRouteConf>,
+    /// How frequently to check the route balances
+    pub interval: Duration,
+}
+
+/// A warp route and the routers it is made of.
+#[derive(Debug, Clone)]
+pub struct WarpRouteConf {
+    /// Name of the route, used to label metrics
+    pub name: String,
+    /// The routers of the route
+    pub routers: Vec<WarpRouterConf>,
+}
+
+/// A router of a warp route on one chain.
+#[derive(Debug, Clone)]
+pub struct WarpRouterConf {
+    /// The chain the router is deployed on
+    pub domain: HyperlaneDomain,
+    /// Address of the router
+    pub address: H256,
+    /// Account holding the native collateral of a native router, in the
+    /// chain's own address format. Defaults to the router itself.
+    pub collateral_account: String,
+    /// How the router backs the token
+    pub token_type: TokenType,
+    /// Decimals of the token on this chain
+    pub decimals: u8,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+struct RawWarpMonitorSettings(Value);
+
+impl_loadable_from_settings!(WarpMonitor, RawWarpMonitorSettings -> WarpMonitorSettings);
+
+impl FromRawConf<RawWarpMonitorSettings> for WarpMonitorSettings {
+    fn from_config_filtered(
+        raw: RawWarpMonitorSettings,
+        cwp: &ConfigPath,
+        _filter: (),
+    ) -> ConfigResult<Self> {
+        let mut err = ConfigParsingError::default();
+
+        let p = ValueParser::new(cwp.clone(), &raw.0);
+
+        let chain_names: Option<HashSet<&str>> = p
+            .chain(&mut err)
+            .get_key("warpRoutes")
+            .into_obj_iter()
+            .map(|routes| {
+                routes
+                    .filter_map(|(_, routers)| routers.into_array_iter().ok())
+                    .flatten()
+                    .filter_map(|router| router.get_key("chain").ok())
+                    .filter_map(|chain| chain.parse_string().ok())
+                    .collect()
+            });
+
+        let base = p
+            .parse_from_raw_config::<Settings, RawAgentConf, Option<&HashSet<&str>>>(
+                chain_names.as_ref(),
+                "Parsing base config",
+            )
+            .take_config_err(&mut err);
+
+        let interval = p
+            .chain(&mut err)
+            .get_opt_key("interval")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(60));
+
+        cfg_unwrap_all!(&p.cwp, err: [base]);
+
+        let warp_routes = p
+            .chain(&mut err)
+            .get_opt_key("warpRoutes")
+            .into_obj_iter()
+            .map(|routes| {
+                routes
+                    .filter_map(|(name, routers)| {
+                        let routers = routers.chain(&mut err).into_array_iter().map(|itr| {
+                            itr.filter_map(|router| parse_router(&base, router, &mut err))
+                                .collect::<Vec<_>>()
+                        })?;
+                        Some(WarpRouteConf { name, routers })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        err.into_result(Self {
+            base,
+            warp_routes,
+            interval,
+        })
+    }
+}
+
+/// Expects WarpMonitorAgentConfig.warpRoutes.<route>[]
+fn parse_router(
+    base: &Settings,
+    router: ValueParser,
+    err: &mut ConfigParsingError,
+) -> Option<WarpRouterConf> {
+    let domain = router
+        .chain(err)
+        .get_key("chain")
+        .parse_string()
+        .end()
+        .and_then(|chain| {
+            base.lookup_domain(chain)
+                .context("Missing configuration for a chain in `warpRoutes`")
+                .into_config_result(|| &router.cwp + "chain")
+                .take_config_err(err)
+        });
+    let address_str = router
+        .chain(err)
+        .get_key("router")
+        .parse_string()
+        .end()
+        .map(str::to_owned);
+    let address = router
+        .chain(err)
+        .get_key("router")
+        .parse_address_hash()
+        .end();
+    let collateral_account = router
+        .chain(err)
+        .get_opt_key("collateralAccount")
+        .parse_string()
+        .end()
+        .map(str::to_owned)
+        .or(address_str);
+    let token_type = router
+        .chain(err)
+        .get_key("type")
+        .parse_string()
+        .end()
+        .and_then(|token_type| {
+            token_type
+                .parse::<TokenType>()
+                .map_err(|_| eyre!("Unknown warp route token type `{token_type}`"))
+                .into_config_result(|| &router.cwp + "type")
+                .take_config_err(err)
+        });
+    // Only the native balances of routers can be read on these chains yet
+    if let (Some(domain), Some(token_type @ (TokenType::Collateral | TokenType::Synthetic))) =
+        (&domain, token_type)
+    {
+        let protocol = domain.domain_protocol();
+        if matches!(
+            protocol,
+            HyperlaneDomainProtocol::Cosmos | HyperlaneDomainProtocol::Fuel
+        ) {
+            err.push(
+                &router.cwp + "type",
+                eyre!("{token_type} routers aren't supported on {protocol:?} chains yet"),
+            );
+        }
+    }
+    let decimals = router
+        .chain(err)
+        .get_key("decimals")
+        .parse_u32()
+        .end()
+        .and_then(|decimals| {
+            u8::try_from(decimals)
+                .context("Expected decimals to fit in a u8")
+                .into_config_result(|| &router.cwp + "decimals")
+                .take_config_err(err)
+        });
+
+    Some(WarpRouterConf {
+        domain: domain?,
+        address: address?,
+        collateral_account: collateral_account?,
+        token_type: token_type?,
+        decimals: decimals?,
+    })
+}
@@ -0,0 +1,74 @@
+use hyperlane_base::NotificationEvent;
+use hyperlane_core::{H160, H256};
+
+/// Evidence of a validator signing a checkpoint it shouldn't have
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FraudAlert {
+    /// The validator signed a root or message id that doesn't match the
+    /// canonical tree at the checkpoint's index
+    InvalidCheckpoint {
+        origin: String,
+        validator: H160,
+        index: u32,
+        signed_root: H256,
+        canonical_root: H256,
+        signed_message_id: H256,
+        canonical_message_id: H256,
+    },
+    /// The validator signed different roots for the same index
+    DoubleSigning {
+        origin: String,
+        validator: H160,
+        index: u32,
+        first_root: H256,
+        second_root: H256,
+    },
+}
+
+impl FraudAlert {
+    /// Short name of the kind of fraud, used to label metrics
+    pub fn kind(&self) -> &'static str {
+        match self {
+            FraudAlert::InvalidCheckpoint { .. } => "invalid_checkpoint",
+            FraudAlert::DoubleSigning { .. } => "double_signing",
+        }
+    }
+
+    /// The notification reporting the fraud to operators
+    pub fn notification(&self) -> NotificationEvent {
+        match self {
+            FraudAlert::InvalidCheckpoint {
+                origin,
+                validator,
+                index,
+                signed_root,
+                canonical_root,
+                signed_message_id,
+                canonical_message_id,
+            } => NotificationEvent::ValidatorFraud {
+                chain: origin.clone(),
+                validator: format!("{validator:?}"),
+                index: *index,
+                reason: format!(
+                    "{}: signed root {signed_root:?} and message id {signed_message_id:?}, canonical root {canonical_root:?} and message id {canonical_message_id:?}",
+                    self.kind()
+                ),
+            },
+            FraudAlert::DoubleSigning {
+                origin,
+                validator,
+                index,
+                first_root,
+                second_root,
+            } => NotificationEvent::ValidatorFraud {
+                chain: origin.clone(),
+                validator: format!("{validator:?}"),
+                index: *index,
+                reason: format!(
+                    "{}: signed roots {first_root:?} and {second_root:?}",
+                    self.kind()
+                ),
+            },
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! The watcher monitors the checkpoints signed by all validators announced
+//! for a mailbox, and raises alerts when a validator signs a root that doesn't
+//! match the canonical merkle tree, or signs conflicting roots for the same
+//! index.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use std::process::ExitCode;
+
+use eyre::Result;
+
+use hyperlane_base::{agent_main, AgentOutcome};
+
+use crate::watcher::Watcher;
+
+mod alert;
+mod settings;
+mod tree;
+mod watcher;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<ExitCode> {
+    let outcome = agent_main::<Watcher>().await?;
+    if let AgentOutcome::ConfigValidated(report) = &outcome {
+        println!("{}", report.to_json()?);
+    }
+    Ok(outcome.exit_code())
+}
@@ -0,0 +1,112 @@
+//! Watcher configuration.
+//!
+//! The correct settings shape is defined in the TypeScript SDK metadata. While the exact shape
+//! and validations it defines are not applied here, we should mirror them.
+//! ANY CHANGES HERE NEED TO BE REFLECTED IN THE TYPESCRIPT SDK.
+
+use std::{collections::HashSet, path::PathBuf, time::Duration};
+
+use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use eyre::Context;
+use hyperlane_base::{
+    impl_loadable_from_settings,
+    settings::{
+        parser::{RawAgentConf, ValueParser},
+        Settings,
+    },
+};
+use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Settings for `Watcher`
+#[derive(Debug, AsRef, AsMut, Deref, DerefMut)]
+pub struct WatcherSettings {
+    #[as_ref]
+    #[as_mut]
+    #[deref]
+    #[deref_mut]
+    base: Settings,
+
+    /// Database path
+    pub db: PathBuf,
+    /// Chains whose validators are watched
+    pub origin_chains: HashSet<HyperlaneDomain>,
+    /// How frequently to check the latest checkpoints of the validators
+    pub interval: Duration,
+    /// If true, allows local storage based checkpoint syncers.
+    /// Not intended for production use.
+    pub allow_local_checkpoint_syncers: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+struct RawWatcherSettings(Value);
+
+impl_loadable_from_settings!(Watcher, RawWatcherSettings -> WatcherSettings);
+
+impl FromRawConf<RawWatcherSettings> for WatcherSettings {
+    fn from_config_filtered(
+        raw: RawWatcherSettings,
+        cwp: &ConfigPath,
+        _filter: (),
+    ) -> ConfigResult<Self> {
+        let mut err = ConfigParsingError::default();
+
+        let p = ValueParser::new(cwp.clone(), &raw.0);
+
+        let origin_chain_names: Option<HashSet<&str>> = p
+            .chain(&mut err)
+            .get_key("chainsToWatch")
+            .parse_string()
+            .end()
+            .map(|v| v.split(',').collect());
+
+        let base = p
+            .parse_from_raw_config::<Settings, RawAgentConf, Option<&HashSet<&str>>>(
+                origin_chain_names.as_ref(),
+                "Parsing base config",
+            )
+            .take_config_err(&mut err);
+
+        let db = p
+            .chain(&mut err)
+            .get_opt_key("db")
+            .parse_from_str("Expected database path")
+            .unwrap_or_else(|| std::env::current_dir().unwrap().join("hyperlane_db"));
+
+        let interval = p
+            .chain(&mut err)
+            .get_opt_key("interval")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(60));
+
+        let allow_local_checkpoint_syncers = p
+            .chain(&mut err)
+            .get_opt_key("allowLocalCheckpointSyncers")
+            .parse_bool()
+            .unwrap_or(false);
+
+        cfg_unwrap_all!(cwp, err: [base]);
+
+        let origin_chains = origin_chain_names
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|chain| {
+                base.lookup_domain(chain)
+                    .context("Missing configuration for a chain in `chainsToWatch`")
+                    .into_config_result(|| cwp + "chains_to_watch")
+                    .take_config_err(&mut err)
+            })
+            .collect();
+
+        err.into_result(Self {
+            base,
+            db,
+            origin_chains,
+            interval,
+            allow_local_checkpoint_syncers,
+        })
+    }
+}
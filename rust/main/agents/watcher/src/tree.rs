@@ -0,0 +1,158 @@
+use hyperlane_base::db::{DbResult, HyperlaneDb, HyperlaneRocksDB};
+use hyperlane_core::{accumulator::incremental::IncrementalMerkle, H256};
+
+/// The root and message id of the merkle tree at a leaf index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CanonicalCheckpoint {
+    pub root: H256,
+    pub message_id: H256,
+}
+
+/// The merkle tree of an origin, computed from the tree insertions indexed
+/// into the database. Keeps the checkpoint of every leaf index, so that
+/// checkpoints signed at any index can be checked.
+#[derive(Debug)]
+pub struct CanonicalTree {
+    db: HyperlaneRocksDB,
+    tree: IncrementalMerkle,
+    checkpoints: Vec<CanonicalCheckpoint>,
+}
+
+impl CanonicalTree {
+    pub fn new(db: HyperlaneRocksDB) -> Self {
+        Self {
+            db,
+            tree: IncrementalMerkle::default(),
+            checkpoints: vec![],
+        }
+    }
+
+    /// Ingest the insertions indexed since the last update, in order of their
+    /// leaf index. Insertions orphaned by a reorg since the last update are
+    /// rewound first, so that the tree follows the canonical chain.
+    pub fn update(&mut self) -> DbResult<()> {
+        if let Some(index) = self.db.take_lowest_invalidated_leaf_index()? {
+            self.rewind(index);
+        }
+        while let Some(insertion) = self
+            .db
+            .retrieve_merkle_tree_insertion_by_leaf_index(&(self.checkpoints.len() as u32))?
+        {
+            self.tree.ingest(insertion.message_id());
+            self.checkpoints.push(CanonicalCheckpoint {
+                root: self.tree.root(),
+                message_id: insertion.message_id(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Forget the insertions from `index` on, rebuilding the tree from the
+    /// ones before it
+    fn rewind(&mut self, index: u32) {
+        if index as usize >= self.checkpoints.len() {
+            return;
+        }
+        self.checkpoints.truncate(index as usize);
+        self.tree = IncrementalMerkle::default();
+        for checkpoint in &self.checkpoints {
+            self.tree.ingest(checkpoint.message_id);
+        }
+    }
+
+    /// The checkpoint of the tree at `index`, or None if the insertion at
+    /// `index` hasn't been indexed yet
+    pub fn checkpoint(&self, index: u32) -> Option<CanonicalCheckpoint> {
+        self.checkpoints.get(index as usize).copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::{HyperlaneDomain, MerkleTreeInsertion};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_checkpoints_of_indexed_insertions() {
+        test_utils::run_test_db(|db| async move {
+            let db = HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("test_tree"), db);
+            let leaves = (1..=3).map(H256::from_low_u64_be).collect::<Vec<_>>();
+            let mut tree = CanonicalTree::new(db.clone());
+
+            // Insertions are ingested in order, so a gap stops the update
+            for index in [0, 2] {
+                db.store_merkle_tree_insertion_by_leaf_index(
+                    &index,
+                    &MerkleTreeInsertion::new(index, leaves[index as usize]),
+                )
+                .unwrap();
+            }
+            tree.update().unwrap();
+            assert!(tree.checkpoint(0).is_some());
+            assert_eq!(tree.checkpoint(1), None);
+
+            db.store_merkle_tree_insertion_by_leaf_index(
+                &1,
+                &MerkleTreeInsertion::new(1, leaves[1]),
+            )
+            .unwrap();
+            tree.update().unwrap();
+
+            let mut expected = IncrementalMerkle::default();
+            for (index, leaf) in leaves.iter().enumerate() {
+                expected.ingest(*leaf);
+                assert_eq!(
+                    tree.checkpoint(index as u32),
+                    Some(CanonicalCheckpoint {
+                        root: expected.root(),
+                        message_id: *leaf,
+                    })
+                );
+            }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_rewinds_orphaned_insertions() {
+        test_utils::run_test_db(|db| async move {
+            let db = HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("test_tree"), db);
+            let mut tree = CanonicalTree::new(db.clone());
+            let insertions = (0..3)
+                .map(|index| MerkleTreeInsertion::new(index, H256::from_low_u64_be(index.into())))
+                .collect::<Vec<_>>();
+            for insertion in &insertions {
+                db.store_merkle_tree_insertion_by_leaf_index(&insertion.index(), insertion)
+                    .unwrap();
+            }
+            tree.update().unwrap();
+
+            // A reorg replaces the insertion at index 1 and orphans the one at index 2
+            for insertion in &insertions[1..] {
+                assert!(db.invalidate_tree_insertion(insertion).unwrap());
+            }
+            let canonical_leaf = H256::from_low_u64_be(10);
+            db.store_merkle_tree_insertion_by_leaf_index(
+                &1,
+                &MerkleTreeInsertion::new(1, canonical_leaf),
+            )
+            .unwrap();
+            tree.update().unwrap();
+
+            let mut expected = IncrementalMerkle::default();
+            expected.ingest(insertions[0].message_id());
+            expected.ingest(canonical_leaf);
+            assert_eq!(
+                tree.checkpoint(1),
+                Some(CanonicalCheckpoint {
+                    root: expected.root(),
+                    message_id: canonical_leaf,
+                })
+            );
+            assert_eq!(tree.checkpoint(2), None);
+        })
+        .await;
+    }
+}
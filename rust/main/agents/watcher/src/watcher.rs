@@ -0,0 +1,355 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use derive_more::AsRef;
+use eyre::Result;
+use futures_util::future::try_join_all;
+use prometheus::{IntCounterVec, IntGaugeVec};
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::{debug, error, info_span, instrument::Instrumented, warn, Instrument};
+
+use hyperlane_base::{
+    db::{HyperlaneRocksDB, DB},
+    metrics::AgentMetrics,
+    settings::CheckpointSyncerConf,
+    spawn_named, AgentMetadata, BaseAgent, ChainMetrics, CheckpointSyncer, ContractSyncMetrics,
+    ContractSyncer, CoreMetrics, HyperlaneAgentCore, Notifier, SequencedDataContractSync,
+};
+use hyperlane_core::{
+    config::ProxyConf, CheckpointWithMessageId, HyperlaneDomain, MerkleTreeInsertion,
+    ValidatorAnnounce, H160, H256,
+};
+
+use crate::{alert::FraudAlert, settings::WatcherSettings, tree::CanonicalTree};
+
+/// Metrics of the watcher.
+#[derive(Debug, Clone)]
+struct WatcherMetrics {
+    /// Fraudulent checkpoints found, by the kind of fraud.
+    fraudulent_checkpoints: IntCounterVec,
+    /// Index of the latest checkpoint of each validator checked against the
+    /// canonical tree.
+    checked_checkpoint_index: IntGaugeVec,
+}
+
+impl WatcherMetrics {
+    fn new(metrics: &CoreMetrics) -> Result<Self> {
+        Ok(Self {
+            fraudulent_checkpoints: metrics.new_int_counter(
+                "watcher_fraudulent_checkpoints",
+                "Checkpoints signed by a validator that don't match the canonical merkle tree or conflict with another of its checkpoints",
+                &["origin", "validator", "kind"],
+            )?,
+            checked_checkpoint_index: metrics.new_int_gauge(
+                "watcher_checked_checkpoint_index",
+                "Index of the latest checkpoint of a validator checked against the canonical merkle tree",
+                &["origin", "validator"],
+            )?,
+        })
+    }
+}
+
+/// A watcher agent
+#[derive(Debug, AsRef)]
+pub struct Watcher {
+    #[as_ref]
+    core: HyperlaneAgentCore,
+    origin_watchers: Vec<OriginWatcher>,
+    merkle_tree_hook_syncs: Vec<Arc<SequencedDataContractSync<MerkleTreeInsertion>>>,
+    core_metrics: Arc<CoreMetrics>,
+}
+
+#[async_trait]
+impl BaseAgent for Watcher {
+    const AGENT_NAME: &'static str = "watcher";
+
+    type Settings = WatcherSettings;
+
+    async fn from_settings(
+        _agent_metadata: AgentMetadata,
+        settings: Self::Settings,
+        metrics: Arc<CoreMetrics>,
+        _agent_metrics: AgentMetrics,
+        _chain_metrics: ChainMetrics,
+    ) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        let core = settings.build_hyperlane_core(metrics.clone());
+        let db = DB::from_path(&settings.db)?;
+        let watcher_metrics = WatcherMetrics::new(&metrics)?;
+        let contract_sync_metrics = Arc::new(ContractSyncMetrics::new(&metrics));
+        let notifier = settings.notifier();
+
+        let mut origin_watchers = Vec::with_capacity(settings.origin_chains.len());
+        let mut merkle_tree_hook_syncs = Vec::with_capacity(settings.origin_chains.len());
+        for origin in &settings.origin_chains {
+            let origin_db = HyperlaneRocksDB::new(origin, db.clone());
+            merkle_tree_hook_syncs.push(
+                settings
+                    .sequenced_contract_sync::<MerkleTreeInsertion, _>(
+                        origin,
+                        &metrics,
+                        &contract_sync_metrics,
+                        origin_db.clone().into(),
+                    )
+                    .await?,
+            );
+            origin_watchers.push(OriginWatcher {
+                origin: origin.clone(),
+                merkle_tree_hook: settings.chain_setup(origin)?.addresses.merkle_tree_hook,
+                validator_announce: settings
+                    .build_validator_announce(origin, &metrics)
+                    .await?
+                    .into(),
+                tree: CanonicalTree::new(origin_db),
+                checkpoint_syncers: HashMap::new(),
+                pending: HashMap::new(),
+                latest_checked: HashMap::new(),
+                alerted: HashSet::new(),
+                allow_local_checkpoint_syncers: settings.allow_local_checkpoint_syncers,
+                proxy: settings.proxy.clone(),
+                interval: settings.interval,
+                notifier: notifier.clone(),
+                metrics: watcher_metrics.clone(),
+            });
+        }
+
+        Ok(Self {
+            core,
+            origin_watchers,
+            merkle_tree_hook_syncs,
+            core_metrics: metrics,
+        })
+    }
+
+    #[allow(clippy::async_yields_async)]
+    async fn run(self) {
+        let mut tasks = vec![];
+
+        let server = self
+            .core
+            .settings
+            .server(self.core_metrics.clone())
+            .expect("Failed to create server");
+        tasks.push(server.run().instrument(info_span!("Watcher server")));
+
+        for contract_sync in self.merkle_tree_hook_syncs {
+            let origin = contract_sync.domain().clone();
+            let index_settings = self.core.settings.chains[origin.name()].index_settings();
+            let cursor = contract_sync
+                .cursor(index_settings)
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("Error getting merkle tree hook cursor for origin {origin}: {err}")
+                });
+            tasks.push(
+                spawn_named("merkle_tree_hook_sync", Some(&origin), async move {
+                    contract_sync.sync("merkle_tree_hook", cursor.into()).await;
+                })
+                .instrument(info_span!("MerkleTreeHookSyncer", %origin)),
+            );
+        }
+
+        for origin_watcher in self.origin_watchers {
+            tasks.push(origin_watcher.spawn());
+        }
+
+        // Note that this only returns an error if one of the tasks panics
+        if let Err(err) = try_join_all(tasks).await {
+            error!(?err, "One of the watcher tasks returned an error");
+        }
+    }
+}
+
+/// Checks the checkpoints signed by the validators announced on an origin
+/// against the canonical merkle tree of the origin.
+#[derive(Debug)]
+struct OriginWatcher {
+    origin: HyperlaneDomain,
+    merkle_tree_hook: H256,
+    validator_announce: Arc<dyn ValidatorAnnounce>,
+    tree: CanonicalTree,
+    checkpoint_syncers: HashMap<H160, Arc<dyn CheckpointSyncer>>,
+    /// Latest checkpoint of each validator whose index isn't in the canonical
+    /// tree yet
+    pending: HashMap<H160, CheckpointWithMessageId>,
+    /// Latest checkpoint seen of each validator
+    latest_checked: HashMap<H160, CheckpointWithMessageId>,
+    /// Validators and indices already alerted on, to only alert once
+    alerted: HashSet<(H160, u32)>,
+    allow_local_checkpoint_syncers: bool,
+    /// The proxy requests to the storage of validators are routed through
+    proxy: Option<ProxyConf>,
+    interval: Duration,
+    notifier: Notifier,
+    metrics: WatcherMetrics,
+}
+
+impl OriginWatcher {
+    async fn check(&mut self) {
+        if let Err(err) = self.tree.update() {
+            warn!(?err, "Failed to update the canonical merkle tree");
+            return;
+        }
+
+        // Checkpoints signed before their index was indexed locally
+        for (validator, checkpoint) in std::mem::take(&mut self.pending) {
+            self.check_against_tree(validator, checkpoint);
+        }
+
+        let validators = match self.validator_announce.get_announced_validators().await {
+            Ok(validators) => validators,
+            Err(err) => {
+                warn!(?err, "Failed to fetch announced validators");
+                return;
+            }
+        };
+        for validator in validators.into_iter().map(H160::from) {
+            if let Err(err) = self.check_validator(validator).await {
+                warn!(
+                    ?validator,
+                    ?err,
+                    "Failed to check latest checkpoint of validator"
+                );
+            }
+        }
+    }
+
+    async fn check_validator(&mut self, validator: H160) -> Result<()> {
+        let Some(checkpoint_syncer) = self.checkpoint_syncer(validator).await? else {
+            debug!(?validator, "No valid checkpoint syncer for validator");
+            return Ok(());
+        };
+        let Some(index) = checkpoint_syncer.latest_index().await? else {
+            return Ok(());
+        };
+        let Some(signed) = checkpoint_syncer.fetch_checkpoint(index).await? else {
+            return Ok(());
+        };
+        // Anyone with access to the storage of a validator could write
+        // checkpoints to it, so only checkpoints signed by the validator
+        // count as evidence against it
+        if signed.recover()? != validator {
+            warn!(
+                ?validator,
+                index, "Checkpoint in validator storage isn't signed by the validator"
+            );
+            return Ok(());
+        }
+        let checkpoint = signed.value;
+        if checkpoint.mailbox_domain != self.origin.id()
+            || checkpoint.merkle_tree_hook_address != self.merkle_tree_hook
+        {
+            debug!(
+                ?validator,
+                ?checkpoint,
+                "Checkpoint is for another merkle tree"
+            );
+            return Ok(());
+        }
+
+        if let Some(previous) = self.latest_checked.insert(validator, checkpoint) {
+            if previous.index == checkpoint.index && previous.root != checkpoint.root {
+                let alert = FraudAlert::DoubleSigning {
+                    origin: self.origin.name().to_owned(),
+                    validator,
+                    index,
+                    first_root: previous.root,
+                    second_root: checkpoint.root,
+                };
+                self.alert(validator, index, alert);
+            }
+        }
+        self.check_against_tree(validator, checkpoint);
+        Ok(())
+    }
+
+    fn check_against_tree(&mut self, validator: H160, checkpoint: CheckpointWithMessageId) {
+        let Some(canonical) = self.tree.checkpoint(checkpoint.index) else {
+            self.pending.insert(validator, checkpoint);
+            return;
+        };
+        self.metrics
+            .checked_checkpoint_index
+            .with_label_values(&[self.origin.name(), &format!("{validator:?}")])
+            .set(checkpoint.index.into());
+        if canonical.root == checkpoint.root && canonical.message_id == checkpoint.message_id {
+            return;
+        }
+        let alert = FraudAlert::InvalidCheckpoint {
+            origin: self.origin.name().to_owned(),
+            validator,
+            index: checkpoint.index,
+            signed_root: checkpoint.root,
+            canonical_root: canonical.root,
+            signed_message_id: checkpoint.message_id,
+            canonical_message_id: canonical.message_id,
+        };
+        self.alert(validator, checkpoint.index, alert);
+    }
+
+    fn alert(&mut self, validator: H160, index: u32, alert: FraudAlert) {
+        if !self.alerted.insert((validator, index)) {
+            return;
+        }
+        self.metrics
+            .fraudulent_checkpoints
+            .with_label_values(&[self.origin.name(), &format!("{validator:?}"), alert.kind()])
+            .inc();
+        self.notifier.notify(&alert.notification());
+    }
+
+    /// The checkpoint syncer of the most recently announced valid storage
+    /// location of `validator`
+    async fn checkpoint_syncer(
+        &mut self,
+        validator: H160,
+    ) -> Result<Option<Arc<dyn CheckpointSyncer>>> {
+        if let Some(checkpoint_syncer) = self.checkpoint_syncers.get(&validator) {
+            return Ok(Some(checkpoint_syncer.clone()));
+        }
+        let storage_locations = self
+            .validator_announce
+            .get_announced_storage_locations(&[validator.into()])
+            .await?;
+        for storage_location in storage_locations.iter().flatten().rev() {
+            let Ok(config) = CheckpointSyncerConf::from_str(storage_location) else {
+                continue;
+            };
+            if !self.allow_local_checkpoint_syncers
+                && matches!(config, CheckpointSyncerConf::LocalStorage { .. })
+            {
+                continue;
+            }
+            if let Ok(checkpoint_syncer) =
+                config.build_and_validate(None, self.proxy.as_ref()).await
+            {
+                let checkpoint_syncer: Arc<dyn CheckpointSyncer> = checkpoint_syncer.into();
+                self.checkpoint_syncers
+                    .insert(validator, checkpoint_syncer.clone());
+                return Ok(Some(checkpoint_syncer));
+            }
+        }
+        Ok(None)
+    }
+
+    fn spawn(mut self) -> Instrumented<JoinHandle<()>> {
+        let span = info_span!("OriginWatcher", origin=%self.origin);
+        let origin = self.origin.clone();
+        spawn_named("origin_watcher", Some(&origin), async move {
+            let mut interval = tokio::time::interval(self.interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                self.check().await;
+            }
+        })
+        .instrument(span)
+    }
+}
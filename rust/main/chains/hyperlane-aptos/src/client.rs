@@ -0,0 +1,413 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hyperlane_core::{ChainResult, H256};
+use reqwest::{Client, Response};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::{debug, instrument};
+use url::Url;
+
+use crate::{
+    utils::{deserialize_u64_string, format_address},
+    HyperlaneAptosError, Signer,
+};
+
+/// How long a signed transaction stays valid for
+const TX_EXPIRATION: Duration = Duration::from_secs(60);
+/// How often the status of a submitted transaction is polled
+const TX_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// How many times the status of a submitted transaction is polled
+const TX_POLL_ATTEMPTS: usize = 60;
+
+/// Client of the Aptos REST API
+#[derive(Debug, Clone)]
+pub struct AptosClient {
+    url: Url,
+    client: Client,
+}
+
+/// Information about the latest state of the ledger
+#[derive(Debug, Deserialize)]
+pub struct LedgerInfo {
+    /// Latest block height
+    #[serde(deserialize_with = "deserialize_u64_string")]
+    pub block_height: u64,
+    /// Latest transaction version
+    #[serde(deserialize_with = "deserialize_u64_string")]
+    pub ledger_version: u64,
+    /// Timestamp of the latest block in microseconds
+    #[serde(deserialize_with = "deserialize_u64_string")]
+    pub ledger_timestamp: u64,
+}
+
+/// A block, without its transactions
+#[derive(Debug, Deserialize)]
+pub struct AptosBlock {
+    /// Height of the block
+    #[serde(deserialize_with = "deserialize_u64_string")]
+    pub block_height: u64,
+    /// Hash of the block
+    pub block_hash: String,
+    /// Timestamp of the block in microseconds
+    #[serde(deserialize_with = "deserialize_u64_string")]
+    pub block_timestamp: u64,
+    /// Version of the first transaction of the block
+    #[serde(deserialize_with = "deserialize_u64_string")]
+    pub first_version: u64,
+}
+
+/// An event of an event stream
+#[derive(Debug, Deserialize)]
+pub struct AptosEvent {
+    /// Version of the transaction that emitted the event
+    #[serde(deserialize_with = "deserialize_u64_string")]
+    pub version: u64,
+    /// Position of the event in its stream
+    #[serde(deserialize_with = "deserialize_u64_string")]
+    pub sequence_number: u64,
+    /// Fields of the event
+    pub data: Value,
+}
+
+/// A committed user transaction
+#[derive(Debug, Deserialize)]
+pub struct AptosTransaction {
+    /// Hash of the transaction
+    pub hash: String,
+    /// Version of the transaction
+    #[serde(deserialize_with = "deserialize_u64_string")]
+    pub version: u64,
+    /// Whether the transaction executed successfully
+    pub success: bool,
+    /// Status of the Move VM after execution
+    pub vm_status: String,
+    /// Gas units used by the transaction
+    #[serde(deserialize_with = "deserialize_u64_string")]
+    pub gas_used: u64,
+    /// Price paid per gas unit
+    #[serde(default, deserialize_with = "deserialize_opt_u64_string")]
+    pub gas_unit_price: Option<u64>,
+    /// Max gas units the transaction could use
+    #[serde(default, deserialize_with = "deserialize_opt_u64_string")]
+    pub max_gas_amount: Option<u64>,
+    /// Sender of the transaction
+    #[serde(default)]
+    pub sender: Option<String>,
+    /// Sequence number of the sender
+    #[serde(default, deserialize_with = "deserialize_opt_u64_string")]
+    pub sequence_number: Option<u64>,
+}
+
+/// An entry function call
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryFunctionPayload {
+    #[serde(rename = "type")]
+    payload_type: &'static str,
+    function: String,
+    type_arguments: Vec<String>,
+    arguments: Vec<Value>,
+}
+
+impl EntryFunctionPayload {
+    /// Call `function`, given as `address::module::name`, with `arguments`
+    pub fn new(function: String, arguments: Vec<Value>) -> Self {
+        Self {
+            payload_type: "entry_function_payload",
+            function,
+            type_arguments: vec![],
+            arguments,
+        }
+    }
+}
+
+/// An unsigned transaction
+#[derive(Debug, Clone, Serialize)]
+struct UnsignedTransaction {
+    sender: String,
+    #[serde(serialize_with = "serialize_u64_string")]
+    sequence_number: u64,
+    #[serde(serialize_with = "serialize_u64_string")]
+    max_gas_amount: u64,
+    #[serde(serialize_with = "serialize_u64_string")]
+    gas_unit_price: u64,
+    #[serde(serialize_with = "serialize_u64_string")]
+    expiration_timestamp_secs: u64,
+    payload: EntryFunctionPayload,
+}
+
+impl AptosClient {
+    /// Create a new client of the REST API at `url`
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            client: Client::new(),
+        }
+    }
+
+    /// Information about the latest state of the ledger
+    pub async fn ledger_info(&self) -> ChainResult<LedgerInfo> {
+        self.get("", &[]).await
+    }
+
+    /// The block at `height`
+    pub async fn block_by_height(&self, height: u64) -> ChainResult<AptosBlock> {
+        self.get(&format!("blocks/by_height/{height}"), &[]).await
+    }
+
+    /// The block containing the transaction `version`
+    pub async fn block_by_version(&self, version: u64) -> ChainResult<AptosBlock> {
+        self.get(&format!("blocks/by_version/{version}"), &[]).await
+    }
+
+    /// The transaction at `version`
+    pub async fn transaction_by_version(&self, version: u64) -> ChainResult<AptosTransaction> {
+        self.get(&format!("transactions/by_version/{version}"), &[])
+            .await
+    }
+
+    /// The transaction with hash `hash`
+    pub async fn transaction_by_hash(&self, hash: &str) -> ChainResult<AptosTransaction> {
+        self.get(&format!("transactions/by_hash/{hash}"), &[]).await
+    }
+
+    /// The resource of type `resource_type` stored at `address`
+    pub async fn resource(&self, address: H256, resource_type: &str) -> ChainResult<Value> {
+        let resource: Value = self
+            .get(
+                &format!(
+                    "accounts/{}/resource/{resource_type}",
+                    format_address(address)
+                ),
+                &[],
+            )
+            .await?;
+        Ok(resource["data"].clone())
+    }
+
+    /// The sequence number of the next transaction of `address`
+    pub async fn sequence_number(&self, address: H256) -> ChainResult<u64> {
+        #[derive(Deserialize)]
+        struct Account {
+            #[serde(deserialize_with = "deserialize_u64_string")]
+            sequence_number: u64,
+        }
+        let account: Account = self
+            .get(&format!("accounts/{}", format_address(address)), &[])
+            .await?;
+        Ok(account.sequence_number)
+    }
+
+    /// Events of the stream created by `address` with `creation_number`,
+    /// starting at sequence number `start`
+    pub async fn events_by_creation_number(
+        &self,
+        address: H256,
+        creation_number: u64,
+        start: u64,
+        limit: u64,
+    ) -> ChainResult<Vec<AptosEvent>> {
+        self.get(
+            &format!(
+                "accounts/{}/events/{creation_number}",
+                format_address(address)
+            ),
+            &[("start", start.to_string()), ("limit", limit.to_string())],
+        )
+        .await
+    }
+
+    /// Call the view function `function` with `arguments`
+    pub async fn view(
+        &self,
+        function: String,
+        type_arguments: Vec<String>,
+        arguments: Vec<Value>,
+    ) -> ChainResult<Vec<Value>> {
+        let body = json!({
+            "function": function,
+            "type_arguments": type_arguments,
+            "arguments": arguments,
+        });
+        self.post("view", &[], &body).await
+    }
+
+    /// The gas unit price that gets transactions included in a timely manner
+    pub async fn estimate_gas_price(&self) -> ChainResult<u64> {
+        #[derive(Deserialize)]
+        struct GasEstimate {
+            gas_estimate: u64,
+        }
+        let estimate: GasEstimate = self.get("estimate_gas_price", &[]).await?;
+        Ok(estimate.gas_estimate)
+    }
+
+    /// Simulate `payload` sent by `signer`, at the estimated gas price and with
+    /// the max gas amount the account can afford.
+    #[instrument(skip(self, signer))]
+    pub async fn simulate(
+        &self,
+        signer: &Signer,
+        payload: EntryFunctionPayload,
+    ) -> ChainResult<AptosTransaction> {
+        let transaction = UnsignedTransaction {
+            sender: format_address(signer.address),
+            sequence_number: self.sequence_number(signer.address).await?,
+            max_gas_amount: 0,
+            gas_unit_price: 0,
+            expiration_timestamp_secs: expiration_timestamp(),
+            payload,
+        };
+        // Simulations must not be validly signed
+        let body = signed_transaction_body(&transaction, signer, &[0u8; 64]);
+        let mut results: Vec<AptosTransaction> = self
+            .post(
+                "transactions/simulate",
+                &[
+                    ("estimate_gas_unit_price", "true".to_owned()),
+                    ("estimate_max_gas_amount", "true".to_owned()),
+                ],
+                &body,
+            )
+            .await?;
+        results.pop().ok_or_else(|| {
+            HyperlaneAptosError::InvalidResponse("empty simulation result".to_owned()).into()
+        })
+    }
+
+    /// Sign and submit `payload` from `signer`, then wait for the transaction
+    /// to be committed.
+    #[instrument(skip(self, signer))]
+    pub async fn submit_and_wait(
+        &self,
+        signer: &Signer,
+        payload: EntryFunctionPayload,
+        max_gas_amount: u64,
+        gas_unit_price: u64,
+    ) -> ChainResult<AptosTransaction> {
+        let transaction = UnsignedTransaction {
+            sender: format_address(signer.address),
+            sequence_number: self.sequence_number(signer.address).await?,
+            max_gas_amount,
+            gas_unit_price,
+            expiration_timestamp_secs: expiration_timestamp(),
+            payload,
+        };
+
+        // The node encodes the transaction into the message to sign, which
+        // saves implementing BCS here
+        let signing_message: String = self
+            .post("transactions/encode_submission", &[], &transaction)
+            .await?;
+        let signing_message = hex::decode(signing_message.trim_start_matches("0x"))
+            .map_err(HyperlaneAptosError::from)?;
+        let signature = signer.sign(&signing_message);
+
+        #[derive(Deserialize)]
+        struct PendingTransaction {
+            hash: String,
+        }
+        let body = signed_transaction_body(&transaction, signer, &signature);
+        let pending: PendingTransaction = self.post("transactions", &[], &body).await?;
+        debug!(hash = %pending.hash, "Submitted transaction");
+
+        for _ in 0..TX_POLL_ATTEMPTS {
+            tokio::time::sleep(TX_POLL_INTERVAL).await;
+            let transaction: Value = self
+                .get(&format!("transactions/by_hash/{}", pending.hash), &[])
+                .await?;
+            if transaction["type"] != "pending_transaction" {
+                return serde_json::from_value(transaction)
+                    .map_err(|e| HyperlaneAptosError::InvalidResponse(e.to_string()).into());
+            }
+        }
+        Err(HyperlaneAptosError::TransactionTimeout(pending.hash).into())
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> ChainResult<T> {
+        let response = self
+            .client
+            .get(self.endpoint(path))
+            .query(query)
+            .send()
+            .await
+            .map_err(HyperlaneAptosError::from)?;
+        parse_response(response).await
+    }
+
+    async fn post<T: DeserializeOwned, B: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+        body: &B,
+    ) -> ChainResult<T> {
+        let response = self
+            .client
+            .post(self.endpoint(path))
+            .query(query)
+            .json(body)
+            .send()
+            .await
+            .map_err(HyperlaneAptosError::from)?;
+        parse_response(response).await
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}/{path}", self.url.as_str().trim_end_matches('/'))
+    }
+}
+
+async fn parse_response<T: DeserializeOwned>(response: Response) -> ChainResult<T> {
+    let status = response.status();
+    if !status.is_success() {
+        let message = response.text().await.unwrap_or_default();
+        return Err(HyperlaneAptosError::ApiError {
+            status: status.as_u16(),
+            message,
+        }
+        .into());
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| HyperlaneAptosError::from(e).into())
+}
+
+fn signed_transaction_body(
+    transaction: &UnsignedTransaction,
+    signer: &Signer,
+    signature: &[u8],
+) -> Value {
+    let mut body = serde_json::to_value(transaction).expect("transaction serializes to JSON");
+    body["signature"] = json!({
+        "type": "ed25519_signature",
+        "public_key": format!("0x{}", hex::encode(signer.public_key().as_bytes())),
+        "signature": format!("0x{}", hex::encode(signature)),
+    });
+    body
+}
+
+fn expiration_timestamp() -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    (now + TX_EXPIRATION).as_secs()
+}
+
+fn serialize_u64_string<S: serde::Serializer>(
+    value: &u64,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_string())
+}
+
+fn deserialize_opt_u64_string<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<u64>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| value.parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
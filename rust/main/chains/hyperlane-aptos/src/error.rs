@@ -0,0 +1,41 @@
+use hyperlane_core::ChainCommunicationError;
+
+/// Errors from the crates specific to the hyperlane-aptos
+/// implementation.
+/// This error can then be converted into the broader error type
+/// in hyperlane-core using the `From` trait impl
+#[derive(Debug, thiserror::Error)]
+pub enum HyperlaneAptosError {
+    /// Error sending a request to the REST API
+    #[error(transparent)]
+    ReqwestError(#[from] reqwest::Error),
+    /// The REST API returned an error
+    #[error("Aptos API error {status}: {message}")]
+    ApiError {
+        /// HTTP status of the response
+        status: u16,
+        /// Error message returned by the node
+        message: String,
+    },
+    /// The REST API returned data that can't be decoded
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+    /// Error decoding hex data
+    #[error(transparent)]
+    HexError(#[from] hex::FromHexError),
+    /// Invalid signer key
+    #[error("Invalid ed25519 key: {0}")]
+    InvalidKey(String),
+    /// Simulating a transaction failed
+    #[error("Transaction simulation failed: {0}")]
+    SimulationFailed(String),
+    /// The transaction was not committed before the timeout
+    #[error("Transaction {0} was not committed in time")]
+    TransactionTimeout(String),
+}
+
+impl From<HyperlaneAptosError> for ChainCommunicationError {
+    fn from(value: HyperlaneAptosError) -> Self {
+        ChainCommunicationError::from_other(value)
+    }
+}
@@ -0,0 +1,16 @@
+//! Implementation of hyperlane for Aptos and other Move chains exposing the
+//! Aptos REST API.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+pub use self::{client::*, error::*, mailbox::*, provider::*, signers::*, trait_builder::*};
+
+mod client;
+mod error;
+mod mailbox;
+mod provider;
+mod signers;
+mod trait_builder;
+mod transaction;
+mod utils;
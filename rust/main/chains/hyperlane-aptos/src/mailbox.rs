@@ -0,0 +1,339 @@
+use std::{
+    fmt::{Debug, Formatter},
+    num::NonZeroU64,
+    ops::RangeInclusive,
+};
+
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use hyperlane_core::{
+    utils::bytes_to_hex, ChainCommunicationError, ChainResult, ContractLocator, Decode,
+    HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider,
+    Indexed, Indexer, LogMeta, Mailbox, RawHyperlaneMessage, SequenceAwareIndexer, TxCostEstimate,
+    TxOutcome, H256, U256,
+};
+use serde_json::{json, Value};
+use tracing::instrument;
+
+use crate::{
+    transaction::{estimate_costs, send_transaction},
+    utils::{format_address, parse_address, parse_hex_bytes},
+    AptosEvent, AptosProvider, ConnectionConf, EntryFunctionPayload, HyperlaneAptosError, Signer,
+};
+
+/// Name of the mailbox module
+const MAILBOX_MODULE: &str = "mailbox";
+/// Name of the resource holding the mailbox state and its event handles
+const MAILBOX_STATE: &str = "MailBoxState";
+/// Event handle of dispatched messages
+const DISPATCH_EVENTS: &str = "dispatch_events";
+/// Event handle of processed messages
+const PROCESS_EVENTS: &str = "process_events";
+
+/// A reference to a Mailbox module on some Aptos chain
+pub struct AptosMailbox {
+    provider: AptosProvider,
+    address: H256,
+    signer: Option<Signer>,
+}
+
+impl AptosMailbox {
+    /// Create a new Aptos mailbox
+    pub fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        signer: Option<Signer>,
+    ) -> Self {
+        Self {
+            provider: AptosProvider::new(locator.domain.clone(), conf),
+            address: locator.address,
+            signer,
+        }
+    }
+
+    fn signer(&self) -> ChainResult<&Signer> {
+        self.signer
+            .as_ref()
+            .ok_or(ChainCommunicationError::SignerUnavailable)
+    }
+
+    fn function(&self, name: &str) -> String {
+        format!("{}::{MAILBOX_MODULE}::{name}", format_address(self.address))
+    }
+
+    fn process_payload(&self, message: &HyperlaneMessage, metadata: &[u8]) -> EntryFunctionPayload {
+        EntryFunctionPayload::new(
+            self.function("inbox_process"),
+            vec![
+                hex_argument(&RawHyperlaneMessage::from(message)),
+                hex_argument(metadata),
+            ],
+        )
+    }
+
+    /// Call a view function of the mailbox, returning its first return value
+    async fn view(&self, name: &str, arguments: Vec<Value>) -> ChainResult<Value> {
+        let result = self
+            .provider
+            .client()
+            .view(self.function(name), vec![], arguments)
+            .await?;
+        result.into_iter().next().ok_or_else(|| {
+            HyperlaneAptosError::InvalidResponse(format!("{name} returned no value")).into()
+        })
+    }
+
+    /// Creation number and number of events of one of the mailbox event
+    /// handles
+    async fn event_handle(&self, handle: &str) -> ChainResult<(u64, u64)> {
+        let state = self
+            .provider
+            .client()
+            .resource(
+                self.address,
+                &format!(
+                    "{}::{MAILBOX_MODULE}::{MAILBOX_STATE}",
+                    format_address(self.address)
+                ),
+            )
+            .await?;
+        let handle = &state[handle];
+        let creation_number = parse_u64(&handle["guid"]["id"]["creation_num"])?;
+        let counter = parse_u64(&handle["counter"])?;
+        Ok((creation_number, counter))
+    }
+
+    /// Fetch the events of one of the mailbox event handles in a range of
+    /// sequence numbers, with their log metadata.
+    async fn fetch_events(
+        &self,
+        handle: &str,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(AptosEvent, LogMeta)>> {
+        let (creation_number, _) = self.event_handle(handle).await?;
+        let start = u64::from(*range.start());
+        let limit = u64::from(*range.end()).saturating_sub(start) + 1;
+        let events = self
+            .provider
+            .client()
+            .events_by_creation_number(self.address, creation_number, start, limit)
+            .await?;
+        try_join_all(events.into_iter().map(|event| async move {
+            let meta = self
+                .provider
+                .log_meta(self.address, event.version, event.sequence_number)
+                .await?;
+            Ok::<_, ChainCommunicationError>((event, meta))
+        }))
+        .await
+    }
+}
+
+impl HyperlaneContract for AptosMailbox {
+    fn address(&self) -> H256 {
+        self.address
+    }
+}
+
+impl HyperlaneChain for AptosMailbox {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.provider.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+impl Debug for AptosMailbox {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self as &dyn HyperlaneContract)
+    }
+}
+
+#[async_trait]
+impl Mailbox for AptosMailbox {
+    #[instrument(level = "debug", err, ret, skip(self))]
+    async fn count(&self, lag: Option<NonZeroU64>) -> ChainResult<u32> {
+        assert!(
+            lag.is_none(),
+            "Aptos does not support querying point-in-time"
+        );
+        let nonce = self.view("outbox_get_nonce", vec![]).await?;
+        parse_u64(&nonce)?
+            .try_into()
+            .map_err(|e| HyperlaneAptosError::InvalidResponse(format!("{e}")).into())
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    async fn delivered(&self, id: H256) -> ChainResult<bool> {
+        let delivered = self
+            .view("delivered", vec![hex_argument(id.as_bytes())])
+            .await?;
+        delivered
+            .as_bool()
+            .ok_or_else(|| HyperlaneAptosError::InvalidResponse(delivered.to_string()).into())
+    }
+
+    #[instrument(err, ret, skip(self))]
+    async fn default_ism(&self) -> ChainResult<H256> {
+        let ism = self.view("get_default_ism", vec![]).await?;
+        parse_address_value(&ism)
+    }
+
+    #[instrument(err, ret, skip(self))]
+    async fn recipient_ism(&self, recipient: H256) -> ChainResult<H256> {
+        let ism = self
+            .view("get_recipient_ism", vec![json!(format_address(recipient))])
+            .await?;
+        parse_address_value(&ism)
+    }
+
+    #[instrument(err, ret, skip(self), fields(msg=%message, metadata=%bytes_to_hex(metadata)))]
+    async fn process(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+        tx_gas_limit: Option<U256>,
+    ) -> ChainResult<TxOutcome> {
+        let payload = self.process_payload(message, metadata);
+        send_transaction(
+            self.provider.client(),
+            self.signer()?,
+            payload,
+            tx_gas_limit,
+        )
+        .await
+    }
+
+    #[instrument(err, ret, skip(self), fields(msg=%message, metadata=%bytes_to_hex(metadata)))]
+    async fn process_estimate_costs(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<TxCostEstimate> {
+        let payload = self.process_payload(message, metadata);
+        estimate_costs(self.provider.client(), self.signer()?, payload).await
+    }
+
+    fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8> {
+        serde_json::to_vec(&self.process_payload(message, metadata))
+            .expect("payload serializes to JSON")
+    }
+}
+
+/// Struct that retrieves event data for an Aptos Mailbox module
+#[derive(Debug)]
+pub struct AptosMailboxIndexer {
+    mailbox: AptosMailbox,
+}
+
+impl AptosMailboxIndexer {
+    /// Create a new AptosMailboxIndexer
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> Self {
+        Self {
+            mailbox: AptosMailbox::new(conf, locator, None),
+        }
+    }
+}
+
+#[async_trait]
+impl Indexer<HyperlaneMessage> for AptosMailboxIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
+        let events = self.mailbox.fetch_events(DISPATCH_EVENTS, range).await?;
+        events
+            .into_iter()
+            .map(|(event, meta)| {
+                let bytes = parse_hex_bytes(event_field(&event, "message")?)?;
+                let message = HyperlaneMessage::read_from(&mut bytes.as_slice())?;
+                Ok((message.into(), meta))
+            })
+            .collect()
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.mailbox.provider.get_block_height().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<HyperlaneMessage> for AptosMailboxIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<HyperlaneMessage>::get_finalized_block_number(self).await?;
+        let sequence = self.mailbox.count(None).await?;
+        Ok((Some(sequence), tip))
+    }
+}
+
+#[async_trait]
+impl Indexer<H256> for AptosMailboxIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
+        let events = self.mailbox.fetch_events(PROCESS_EVENTS, range).await?;
+        events
+            .into_iter()
+            .map(|(event, meta)| {
+                let bytes = parse_hex_bytes(event_field(&event, "message_id")?)?;
+                if bytes.len() != 32 {
+                    return Err(HyperlaneAptosError::InvalidResponse(format!(
+                        "invalid message id {}",
+                        event.data
+                    ))
+                    .into());
+                }
+                Ok((H256::from_slice(&bytes).into(), meta))
+            })
+            .collect()
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.mailbox.provider.get_block_height().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<H256> for AptosMailboxIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<H256>::get_finalized_block_number(self).await?;
+        // Deliveries are indexed by their position in the process event
+        // stream, which counts them
+        let (_, count) = self.mailbox.event_handle(PROCESS_EVENTS).await?;
+        let count = count
+            .try_into()
+            .map_err(|e| HyperlaneAptosError::InvalidResponse(format!("{e}")))?;
+        Ok((Some(count), tip))
+    }
+}
+
+/// `vector<u8>` arguments are passed as 0x-prefixed hex
+fn hex_argument(bytes: &[u8]) -> Value {
+    json!(format!("0x{}", hex::encode(bytes)))
+}
+
+/// Parse an integer, which the REST API returns as a number when it fits in
+/// 32 bits and as a string otherwise
+fn parse_u64(value: &Value) -> ChainResult<u64> {
+    value
+        .as_u64()
+        .or_else(|| value.as_str().and_then(|value| value.parse().ok()))
+        .ok_or_else(|| HyperlaneAptosError::InvalidResponse(value.to_string()).into())
+}
+
+fn parse_address_value(value: &Value) -> ChainResult<H256> {
+    let address = value
+        .as_str()
+        .ok_or_else(|| HyperlaneAptosError::InvalidResponse(value.to_string()))?;
+    parse_address(address)
+}
+
+fn event_field<'a>(event: &'a AptosEvent, field: &str) -> ChainResult<&'a str> {
+    event.data[field].as_str().ok_or_else(|| {
+        HyperlaneAptosError::InvalidResponse(format!("event without {field}: {}", event.data))
+            .into()
+    })
+}
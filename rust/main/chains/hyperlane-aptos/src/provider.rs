@@ -0,0 +1,143 @@
+use async_trait::async_trait;
+use hyperlane_core::{
+    BlockInfo, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain, HyperlaneProvider,
+    HyperlaneProviderError, LogMeta, TxnInfo, TxnReceiptInfo, H256, U256,
+};
+use serde_json::json;
+
+use crate::{
+    utils::{parse_address, parse_hash},
+    AptosClient, ConnectionConf, HyperlaneAptosError,
+};
+
+/// Type of the native coin
+const APTOS_COIN: &str = "0x1::aptos_coin::AptosCoin";
+
+/// A wrapper around an Aptos REST client to get generic blockchain
+/// information.
+#[derive(Debug, Clone)]
+pub struct AptosProvider {
+    domain: HyperlaneDomain,
+    client: AptosClient,
+}
+
+impl AptosProvider {
+    /// Create a new Aptos provider
+    pub fn new(domain: HyperlaneDomain, conf: &ConnectionConf) -> Self {
+        Self {
+            domain,
+            client: AptosClient::new(conf.url.clone()),
+        }
+    }
+
+    /// Get the inner REST client
+    pub fn client(&self) -> &AptosClient {
+        &self.client
+    }
+
+    /// Height of the latest block. Aptos has instant finality, so the latest
+    /// block is final.
+    pub async fn get_block_height(&self) -> ChainResult<u32> {
+        let ledger = self.client.ledger_info().await?;
+        ledger
+            .block_height
+            .try_into()
+            .map_err(|e| HyperlaneAptosError::InvalidResponse(format!("{e}")).into())
+    }
+
+    /// Build the log metadata of an event emitted by the transaction at
+    /// `version`, by the contract at `address`.
+    pub(crate) async fn log_meta(
+        &self,
+        address: H256,
+        version: u64,
+        sequence_number: u64,
+    ) -> ChainResult<LogMeta> {
+        let block = self.client.block_by_version(version).await?;
+        let transaction = self.client.transaction_by_version(version).await?;
+        Ok(LogMeta {
+            address,
+            block_number: block.block_height,
+            block_hash: parse_hash(&block.block_hash)?,
+            transaction_id: parse_hash(&transaction.hash)?.into(),
+            transaction_index: version - block.first_version,
+            // Events are indexed per stream, so their position in the stream
+            // orders them
+            log_index: sequence_number.into(),
+        })
+    }
+}
+
+impl HyperlaneChain for AptosProvider {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.clone())
+    }
+}
+
+#[async_trait]
+impl HyperlaneProvider for AptosProvider {
+    async fn get_block_by_hash(&self, hash: &H256) -> ChainResult<BlockInfo> {
+        // The REST API only looks up blocks by height or version
+        Err(HyperlaneProviderError::CouldNotFindObjectByHash(*hash).into())
+    }
+
+    async fn get_txn_by_hash(&self, hash: &H256) -> ChainResult<TxnInfo> {
+        let transaction = self
+            .client
+            .transaction_by_hash(&format!("0x{}", hex::encode(hash.as_bytes())))
+            .await?;
+        let sender = transaction
+            .sender
+            .as_deref()
+            .map(parse_address)
+            .transpose()?
+            .unwrap_or_default();
+        let gas_price = transaction.gas_unit_price.map(U256::from);
+        Ok(TxnInfo {
+            hash: *hash,
+            gas_limit: transaction.max_gas_amount.unwrap_or_default().into(),
+            max_priority_fee_per_gas: None,
+            max_fee_per_gas: None,
+            gas_price,
+            nonce: transaction.sequence_number.unwrap_or_default(),
+            sender,
+            recipient: None,
+            receipt: Some(TxnReceiptInfo {
+                gas_used: transaction.gas_used.into(),
+                cumulative_gas_used: transaction.gas_used.into(),
+                effective_gas_price: gas_price,
+            }),
+        })
+    }
+
+    async fn is_contract(&self, _address: &H256) -> ChainResult<bool> {
+        // Modules are published under regular accounts, so any account may
+        // hold contracts
+        Ok(true)
+    }
+
+    async fn get_balance(&self, address: String) -> ChainResult<U256> {
+        let result = self
+            .client
+            .view(
+                "0x1::coin::balance".to_owned(),
+                vec![APTOS_COIN.to_owned()],
+                vec![json!(address)],
+            )
+            .await?;
+        let balance = result
+            .first()
+            .and_then(|value| value.as_str())
+            .and_then(|value| value.parse::<u64>().ok())
+            .ok_or_else(|| HyperlaneAptosError::InvalidResponse(format!("{result:?}")))?;
+        Ok(balance.into())
+    }
+
+    async fn get_chain_metrics(&self) -> ChainResult<Option<ChainInfo>> {
+        Ok(None)
+    }
+}
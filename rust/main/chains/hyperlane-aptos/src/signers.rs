@@ -0,0 +1,63 @@
+use std::fmt::{Debug, Formatter};
+
+use ed25519_dalek::{ExpandedSecretKey, PublicKey, SecretKey};
+use hyperlane_core::{ChainResult, H256};
+use sha3::{Digest, Sha3_256};
+
+use crate::HyperlaneAptosError;
+
+/// Authentication key scheme of single ed25519 keys
+const ED25519_SCHEME: u8 = 0;
+
+/// Signer for Aptos chains, an ed25519 key controlling the account whose
+/// authentication key it derives.
+#[derive(Clone)]
+pub struct Signer {
+    secret_key: [u8; 32],
+    public_key: PublicKey,
+    /// Address of the account
+    pub address: H256,
+}
+
+impl Signer {
+    /// Create a new signer from an ed25519 private key
+    pub fn new(private_key: H256) -> ChainResult<Self> {
+        let secret = SecretKey::from_bytes(private_key.as_bytes())
+            .map_err(|e| HyperlaneAptosError::InvalidKey(e.to_string()))?;
+        let public_key = PublicKey::from(&secret);
+
+        // Accounts that never rotated their key are addressed by their
+        // authentication key
+        let mut hasher = Sha3_256::new();
+        hasher.update(public_key.as_bytes());
+        hasher.update([ED25519_SCHEME]);
+        let address = H256::from_slice(&hasher.finalize());
+
+        Ok(Self {
+            secret_key: private_key.0,
+            public_key,
+            address,
+        })
+    }
+
+    /// The public key of the signer
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Sign a message, returning the 64 byte signature
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        let secret = SecretKey::from_bytes(&self.secret_key).expect("key was validated");
+        ExpandedSecretKey::from(&secret)
+            .sign(message, &self.public_key)
+            .to_bytes()
+    }
+}
+
+impl Debug for Signer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Signer")
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,20 @@
+use url::Url;
+
+/// Aptos connection configuration
+#[derive(Debug, Clone)]
+pub struct ConnectionConf {
+    /// Fully qualified URL of the REST API, including the version path
+    /// (e.g. `https://fullnode.mainnet.aptoslabs.com/v1`)
+    pub url: Url,
+}
+
+/// An error type when parsing a connection configuration.
+#[derive(thiserror::Error, Debug)]
+pub enum ConnectionConfError {
+    /// Missing `url` for connection configuration
+    #[error("Missing `url` for connection configuration")]
+    MissingConnectionUrl,
+    /// Invalid `url` for connection configuration
+    #[error("Invalid `url` for connection configuration: `{0}` ({1})")]
+    InvalidConnectionUrl(String, url::ParseError),
+}
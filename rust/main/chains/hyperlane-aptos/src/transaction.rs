@@ -0,0 +1,68 @@
+use hyperlane_core::{ChainResult, TxCostEstimate, TxOutcome, U256};
+use tracing::warn;
+
+use crate::{
+    utils::parse_hash, AptosClient, AptosTransaction, EntryFunctionPayload, HyperlaneAptosError,
+    Signer,
+};
+
+/// Gas limit margin over the simulated gas usage, in percent
+const GAS_LIMIT_MARGIN_PERCENT: u64 = 50;
+
+/// Estimate the gas usage and price of `payload` by simulating it
+pub(crate) async fn estimate_costs(
+    client: &AptosClient,
+    signer: &Signer,
+    payload: EntryFunctionPayload,
+) -> ChainResult<TxCostEstimate> {
+    let simulation = simulate(client, signer, payload).await?;
+    Ok(TxCostEstimate {
+        gas_limit: simulation.gas_used.into(),
+        gas_price: U256::from(simulation.gas_unit_price.unwrap_or_default()).try_into()?,
+        l2_gas_limit: None,
+        l1_data_fee: None,
+    })
+}
+
+/// Send `payload` and wait for it to be committed. Without a gas limit, the
+/// transaction is simulated first and given a margin over the gas it used.
+pub(crate) async fn send_transaction(
+    client: &AptosClient,
+    signer: &Signer,
+    payload: EntryFunctionPayload,
+    gas_limit: Option<U256>,
+) -> ChainResult<TxOutcome> {
+    let gas_unit_price = client.estimate_gas_price().await?;
+    let max_gas_amount = match gas_limit {
+        Some(gas_limit) => u64::try_from(gas_limit).unwrap_or(u64::MAX),
+        None => {
+            let simulation = simulate(client, signer, payload.clone()).await?;
+            simulation.gas_used * (100 + GAS_LIMIT_MARGIN_PERCENT) / 100
+        }
+    };
+
+    let transaction = client
+        .submit_and_wait(signer, payload, max_gas_amount, gas_unit_price)
+        .await?;
+    if !transaction.success {
+        warn!(hash = %transaction.hash, vm_status = %transaction.vm_status, "Transaction failed");
+    }
+    Ok(TxOutcome {
+        transaction_id: parse_hash(&transaction.hash)?.into(),
+        executed: transaction.success,
+        gas_used: transaction.gas_used.into(),
+        gas_price: U256::from(transaction.gas_unit_price.unwrap_or(gas_unit_price)).try_into()?,
+    })
+}
+
+async fn simulate(
+    client: &AptosClient,
+    signer: &Signer,
+    payload: EntryFunctionPayload,
+) -> ChainResult<AptosTransaction> {
+    let simulation = client.simulate(signer, payload).await?;
+    if !simulation.success {
+        return Err(HyperlaneAptosError::SimulationFailed(simulation.vm_status).into());
+    }
+    Ok(simulation)
+}
@@ -0,0 +1,60 @@
+use hyperlane_core::{ChainResult, H256};
+use serde::{Deserialize, Deserializer};
+
+use crate::HyperlaneAptosError;
+
+/// Format an address the way the REST API expects it
+pub(crate) fn format_address(address: H256) -> String {
+    format!("0x{}", hex::encode(address.as_bytes()))
+}
+
+/// Parse an address returned by the REST API, which strips leading zeros
+pub(crate) fn parse_address(address: &str) -> ChainResult<H256> {
+    let hex_str = address.trim_start_matches("0x");
+    if hex_str.len() > 64 {
+        return Err(HyperlaneAptosError::InvalidResponse(format!(
+            "address {address} is longer than 32 bytes"
+        ))
+        .into());
+    }
+    let padded = format!("{hex_str:0>64}");
+    let bytes = hex::decode(padded).map_err(HyperlaneAptosError::from)?;
+    Ok(H256::from_slice(&bytes))
+}
+
+/// Decode `vector<u8>` values, which the REST API returns as 0x-prefixed hex
+pub(crate) fn parse_hex_bytes(value: &str) -> ChainResult<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x")).map_err(|e| HyperlaneAptosError::from(e).into())
+}
+
+/// Parse a 0x-prefixed 32 byte hash
+pub(crate) fn parse_hash(hash: &str) -> ChainResult<H256> {
+    let bytes = parse_hex_bytes(hash)?;
+    if bytes.len() != 32 {
+        return Err(HyperlaneAptosError::InvalidResponse(format!("invalid hash {hash}")).into());
+    }
+    Ok(H256::from_slice(&bytes))
+}
+
+/// Deserialize a u64, which the REST API encodes as a string
+pub(crate) fn deserialize_u64_string<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u64, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    value.parse().map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_address_roundtrip() {
+        assert_eq!(parse_address("0x1").unwrap(), H256::from_low_u64_be(1));
+
+        let address = H256::repeat_byte(0xab);
+        assert_eq!(parse_address(&format_address(address)).unwrap(), address);
+
+        assert!(parse_address(&format!("0x{}", "1".repeat(65))).is_err());
+    }
+}
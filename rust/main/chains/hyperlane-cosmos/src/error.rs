@@ -71,6 +71,30 @@ pub enum HyperlaneCosmosError {
     /// Parsing attempt failed
     #[error("Parsing attempt failed. (Errors: {0:?})")]
     ParsingAttemptsFailed(Vec<HyperlaneCosmosError>),
+    /// An event is missing an attribute, which may have been renamed to one
+    /// of its unexpected attributes
+    #[error("{event} event is missing its {attribute} attribute (unexpected: {unexpected:?})")]
+    MissingEventAttribute {
+        /// The kind of event
+        event: &'static str,
+        /// The missing attribute
+        attribute: &'static str,
+        /// The keys of the attributes of the event missing from its schema
+        unexpected: Vec<String>,
+    },
+    /// An event attribute can't be decoded
+    #[error("Invalid {attribute} attribute of {event} event: {reason}")]
+    InvalidEventAttribute {
+        /// The kind of event
+        event: &'static str,
+        /// The invalid attribute
+        attribute: &'static str,
+        /// Why it can't be decoded
+        reason: String,
+    },
+    /// A configured RPC header can't be sent as gRPC metadata
+    #[error("Invalid RPC header `{0}`")]
+    InvalidRpcHeader(String),
 }
 
 impl From<HyperlaneCosmosError> for ChainCommunicationError {
@@ -0,0 +1,218 @@
+//! CosmWasm contracts emit events as string attributes, whose keys and values
+//! some chains base64 encode. Events are decoded against a schema listing the
+//! keys each contract version has used for an attribute, so that a missing or
+//! renamed attribute fails with an error naming it rather than a generic one.
+
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use tendermint::abci::EventAttribute;
+
+use hyperlane_core::{ChainResult, H160, H256, U256};
+
+use crate::rpc::ParsedEvent;
+use crate::utils::CONTRACT_ADDRESS_ATTRIBUTE_KEY;
+use crate::HyperlaneCosmosError;
+
+/// The attributes an event is decoded from
+#[derive(Debug)]
+pub(crate) struct EventSchema {
+    /// The kind of event, for errors
+    pub event: &'static str,
+    /// The attributes of the event, besides the contract address
+    pub attributes: &'static [AttributeSchema],
+}
+
+/// An attribute of an event
+#[derive(Debug)]
+pub(crate) struct AttributeSchema {
+    /// The name the attribute is fetched by
+    pub field: &'static str,
+    /// The keys of the attribute, newest contract version first
+    pub keys: &'static [&'static str],
+}
+
+impl AttributeSchema {
+    const CONTRACT_ADDRESS: AttributeSchema = AttributeSchema {
+        field: "contract_address",
+        keys: &[CONTRACT_ADDRESS_ATTRIBUTE_KEY],
+    };
+}
+
+impl EventSchema {
+    fn attribute(&self, key: &str) -> Option<&AttributeSchema> {
+        std::iter::once(&AttributeSchema::CONTRACT_ADDRESS)
+            .chain(self.attributes)
+            .find(|attribute| attribute.keys.contains(&key))
+    }
+
+    /// Decode the attributes of an event, whether base64 encoded or not. The
+    /// contract address is required, the other attributes only when fetched.
+    pub fn decode(&'static self, attrs: &[EventAttribute]) -> ChainResult<DecodedEvent> {
+        let mut values = HashMap::new();
+        let mut unexpected = vec![];
+        for attr in attrs {
+            let (key, value) = (attr.key.as_str(), attr.value.as_str());
+            if let Some(attribute) = self.attribute(key) {
+                values.insert(attribute.field, value.to_owned());
+                continue;
+            }
+            let decoded_key = decode_base64(key).ok();
+            match decoded_key.as_deref().and_then(|key| self.attribute(key)) {
+                Some(attribute) => {
+                    let value = decode_base64(value).map_err(|err| {
+                        HyperlaneCosmosError::InvalidEventAttribute {
+                            event: self.event,
+                            attribute: attribute.field,
+                            reason: err.to_string(),
+                        }
+                    })?;
+                    values.insert(attribute.field, value);
+                }
+                None => unexpected.push(decoded_key.unwrap_or_else(|| key.to_owned())),
+            }
+        }
+        let event = DecodedEvent {
+            schema: self,
+            values,
+            unexpected,
+        };
+        event.value(AttributeSchema::CONTRACT_ADDRESS.field)?;
+        Ok(event)
+    }
+}
+
+fn decode_base64(encoded: &str) -> Result<String, HyperlaneCosmosError> {
+    String::from_utf8(BASE64.decode(encoded)?)
+        .map_err(|err| HyperlaneCosmosError::ParsingFailed(err.to_string()))
+}
+
+/// The attributes of an event, decoded against its schema
+#[derive(Debug)]
+pub(crate) struct DecodedEvent {
+    schema: &'static EventSchema,
+    values: HashMap<&'static str, String>,
+    /// Keys of the attributes missing from the schema
+    unexpected: Vec<String>,
+}
+
+impl DecodedEvent {
+    /// The value of an attribute
+    pub fn value(&self, field: &'static str) -> ChainResult<&str> {
+        self.values.get(field).map(String::as_str).ok_or_else(|| {
+            HyperlaneCosmosError::MissingEventAttribute {
+                event: self.schema.event,
+                attribute: field,
+                unexpected: self.unexpected.clone(),
+            }
+            .into()
+        })
+    }
+
+    fn invalid(&self, field: &'static str, reason: impl ToString) -> HyperlaneCosmosError {
+        HyperlaneCosmosError::InvalidEventAttribute {
+            event: self.schema.event,
+            attribute: field,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Parse the value of an attribute
+    pub fn parse<T>(&self, field: &'static str) -> ChainResult<T>
+    where
+        T: std::str::FromStr,
+        T::Err: ToString,
+    {
+        Ok(self
+            .value(field)?
+            .parse()
+            .map_err(|err| self.invalid(field, err))?)
+    }
+
+    /// Parse the value of a decimal amount attribute. U256's from_str assumes
+    /// a radix of 16.
+    pub fn parse_dec_u256(&self, field: &'static str) -> ChainResult<U256> {
+        Ok(U256::from_dec_str(self.value(field)?).map_err(|err| self.invalid(field, err))?)
+    }
+
+    /// Decode the value of a hex attribute, optionally 0x prefixed
+    pub fn parse_hex(&self, field: &'static str) -> ChainResult<Vec<u8>> {
+        let value = self.value(field)?;
+        Ok(hex::decode(value.strip_prefix("0x").unwrap_or(value))
+            .map_err(|err| self.invalid(field, err))?)
+    }
+
+    /// Decode the value of a hex attribute holding a 32 bytes hash
+    pub fn parse_h256(&self, field: &'static str) -> ChainResult<H256> {
+        let bytes = self.parse_hex(field)?;
+        if bytes.len() != H256::len_bytes() {
+            return Err(self
+                .invalid(field, format!("{} bytes long", bytes.len()))
+                .into());
+        }
+        Ok(H256::from_slice(&bytes))
+    }
+
+    /// Decode the value of a hex attribute holding a 20 bytes address
+    pub fn parse_h160(&self, field: &'static str) -> ChainResult<H160> {
+        let bytes = self.parse_hex(field)?;
+        if bytes.len() != H160::len_bytes() {
+            return Err(self
+                .invalid(field, format!("{} bytes long", bytes.len()))
+                .into());
+        }
+        Ok(H160::from_slice(&bytes))
+    }
+
+    /// The event of the contract which emitted it
+    pub fn into_parsed<T: PartialEq>(self, event: T) -> ParsedEvent<T> {
+        let contract_address = self
+            .values
+            .get(AttributeSchema::CONTRACT_ADDRESS.field)
+            .cloned()
+            .unwrap_or_default();
+        ParsedEvent::new(contract_address, event)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::utils::event_attributes_from_str;
+
+    use super::*;
+
+    static SCHEMA: EventSchema = EventSchema {
+        event: "test",
+        attributes: &[
+            AttributeSchema {
+                field: "message_id",
+                keys: &["message_id", "id"],
+            },
+            AttributeSchema {
+                field: "amount",
+                keys: &["amount"],
+            },
+        ],
+    };
+
+    #[test]
+    fn test_decode_event() {
+        // `id` is the key of `message_id` in an older version, base64 encoded
+        let attrs = event_attributes_from_str(
+            r#"[{"key":"_contract_address","value":"contract","index":true},{"key":"aWQ=","value":"MHgwMQ==","index":true},{"key":"fee","value":"1","index":true}]"#,
+        );
+        let event = SCHEMA.decode(&attrs).unwrap();
+        assert_eq!(event.parse_hex("message_id").unwrap(), vec![1]);
+        assert!(event.parse_h256("message_id").is_err());
+
+        // The amount was renamed to `fee`, which the error points at
+        let err = event.parse_dec_u256("amount").unwrap_err().to_string();
+        assert!(err.contains("amount") && err.contains("fee"), "{err}");
+
+        let parsed = event.into_parsed(());
+        assert_eq!(parsed, ParsedEvent::new("contract".to_owned(), ()));
+
+        let attrs = event_attributes_from_str(r#"[{"key":"amount","value":"1","index":true}]"#);
+        assert!(SCHEMA.decode(&attrs).is_err());
+    }
+}
@@ -1,24 +1,20 @@
 use std::ops::RangeInclusive;
 
 use async_trait::async_trait;
-use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
-use once_cell::sync::Lazy;
 use tendermint::abci::EventAttribute;
 use tracing::instrument;
 
 use hyperlane_core::{
-    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
-    HyperlaneDomain, HyperlaneProvider, Indexed, Indexer, InterchainGasPaymaster,
-    InterchainGasPayment, LogMeta, SequenceAwareIndexer, H256, H512, U256,
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneProvider, Indexed, Indexer, InterchainGasPaymaster, InterchainGasPayment, LogMeta,
+    SequenceAwareIndexer, H256, H512,
 };
 
+use crate::events::{AttributeSchema, EventSchema};
 use crate::rpc::{CosmosWasmRpcProvider, ParsedEvent, WasmRpcProvider};
 use crate::signers::Signer;
-use crate::utils::{
-    execute_and_parse_log_futures, parse_logs_in_range, parse_logs_in_tx,
-    CONTRACT_ADDRESS_ATTRIBUTE_KEY, CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64,
-};
-use crate::{ConnectionConf, CosmosProvider, HyperlaneCosmosError};
+use crate::utils::{execute_and_parse_log_futures, parse_logs_in_range, parse_logs_in_tx};
+use crate::{ConnectionConf, CosmosProvider};
 
 /// A reference to a InterchainGasPaymaster contract on some Cosmos chain
 #[derive(Debug)]
@@ -70,21 +66,27 @@ impl CosmosInterchainGasPaymaster {
 
 // ------------------ Indexer ------------------
 
-const MESSAGE_ID_ATTRIBUTE_KEY: &str = "message_id";
-static MESSAGE_ID_ATTRIBUTE_KEY_BASE64: Lazy<String> =
-    Lazy::new(|| BASE64.encode(MESSAGE_ID_ATTRIBUTE_KEY));
-
-const PAYMENT_ATTRIBUTE_KEY: &str = "payment";
-static PAYMENT_ATTRIBUTE_KEY_BASE64: Lazy<String> =
-    Lazy::new(|| BASE64.encode(PAYMENT_ATTRIBUTE_KEY));
-
-const GAS_AMOUNT_ATTRIBUTE_KEY: &str = "gas_amount";
-static GAS_AMOUNT_ATTRIBUTE_KEY_BASE64: Lazy<String> =
-    Lazy::new(|| BASE64.encode(GAS_AMOUNT_ATTRIBUTE_KEY));
-
-const DESTINATION_ATTRIBUTE_KEY: &str = "dest_domain";
-static DESTINATION_ATTRIBUTE_KEY_BASE64: Lazy<String> =
-    Lazy::new(|| BASE64.encode(DESTINATION_ATTRIBUTE_KEY));
+static INTERCHAIN_GAS_PAYMENT_SCHEMA: EventSchema = EventSchema {
+    event: "interchain gas payment",
+    attributes: &[
+        AttributeSchema {
+            field: "message_id",
+            keys: &["message_id"],
+        },
+        AttributeSchema {
+            field: "payment",
+            keys: &["payment"],
+        },
+        AttributeSchema {
+            field: "gas_amount",
+            keys: &["gas_amount"],
+        },
+        AttributeSchema {
+            field: "destination",
+            keys: &["dest_domain"],
+        },
+    ],
+};
 
 /// A reference to a InterchainGasPaymasterIndexer contract on some Cosmos chain
 #[derive(Debug, Clone)]
@@ -118,87 +120,15 @@ impl CosmosInterchainGasPaymasterIndexer {
     fn interchain_gas_payment_parser(
         attrs: &Vec<EventAttribute>,
     ) -> ChainResult<ParsedEvent<InterchainGasPayment>> {
-        let mut contract_address: Option<String> = None;
-        let mut gas_payment = IncompleteInterchainGasPayment::default();
-
-        for attr in attrs {
-            let key = attr.key.as_str();
-            let value = attr.value.as_str();
-
-            match key {
-                CONTRACT_ADDRESS_ATTRIBUTE_KEY => {
-                    contract_address = Some(value.to_string());
-                }
-                v if *CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64 == v => {
-                    contract_address = Some(String::from_utf8(
-                        BASE64
-                            .decode(value)
-                            .map_err(Into::<HyperlaneCosmosError>::into)?,
-                    )?);
-                }
-
-                MESSAGE_ID_ATTRIBUTE_KEY => {
-                    gas_payment.message_id = Some(H256::from_slice(hex::decode(value)?.as_slice()));
-                }
-                v if *MESSAGE_ID_ATTRIBUTE_KEY_BASE64 == v => {
-                    gas_payment.message_id = Some(H256::from_slice(
-                        hex::decode(String::from_utf8(
-                            BASE64
-                                .decode(value)
-                                .map_err(Into::<HyperlaneCosmosError>::into)?,
-                        )?)?
-                        .as_slice(),
-                    ));
-                }
-
-                PAYMENT_ATTRIBUTE_KEY => {
-                    gas_payment.payment = Some(U256::from_dec_str(value)?);
-                }
-                v if *PAYMENT_ATTRIBUTE_KEY_BASE64 == v => {
-                    let dec_str = String::from_utf8(
-                        BASE64
-                            .decode(value)
-                            .map_err(Into::<HyperlaneCosmosError>::into)?,
-                    )?;
-                    // U256's from_str assumes a radix of 16, so we explicitly use from_dec_str.
-                    gas_payment.payment = Some(U256::from_dec_str(dec_str.as_str())?);
-                }
-
-                GAS_AMOUNT_ATTRIBUTE_KEY => {
-                    gas_payment.gas_amount = Some(U256::from_dec_str(value)?);
-                }
-                v if *GAS_AMOUNT_ATTRIBUTE_KEY_BASE64 == v => {
-                    let dec_str = String::from_utf8(
-                        BASE64
-                            .decode(value)
-                            .map_err(Into::<HyperlaneCosmosError>::into)?,
-                    )?;
-                    // U256's from_str assumes a radix of 16, so we explicitly use from_dec_str.
-                    gas_payment.gas_amount = Some(U256::from_dec_str(dec_str.as_str())?);
-                }
-
-                DESTINATION_ATTRIBUTE_KEY => {
-                    gas_payment.destination = Some(value.parse::<u32>()?);
-                }
-                v if *DESTINATION_ATTRIBUTE_KEY_BASE64 == v => {
-                    gas_payment.destination = Some(
-                        String::from_utf8(
-                            BASE64
-                                .decode(value)
-                                .map_err(Into::<HyperlaneCosmosError>::into)?,
-                        )?
-                        .parse()?,
-                    );
-                }
-
-                _ => {}
-            }
-        }
-
-        let contract_address = contract_address
-            .ok_or_else(|| ChainCommunicationError::from_other_str("missing contract_address"))?;
-
-        Ok(ParsedEvent::new(contract_address, gas_payment.try_into()?))
+        let event = INTERCHAIN_GAS_PAYMENT_SCHEMA.decode(attrs)?;
+        let gas_payment = InterchainGasPayment {
+            message_id: event.parse_h256("message_id")?,
+            payment: event.parse_dec_u256("payment")?,
+            gas_amount: event.parse_dec_u256("gas_amount")?,
+            destination: event.parse("destination")?,
+        };
+
+        Ok(event.into_parsed(gas_payment))
     }
 }
 
@@ -246,40 +176,6 @@ impl SequenceAwareIndexer<InterchainGasPayment> for CosmosInterchainGasPaymaster
     }
 }
 
-#[derive(Default)]
-struct IncompleteInterchainGasPayment {
-    message_id: Option<H256>,
-    payment: Option<U256>,
-    gas_amount: Option<U256>,
-    destination: Option<u32>,
-}
-
-impl TryInto<InterchainGasPayment> for IncompleteInterchainGasPayment {
-    type Error = ChainCommunicationError;
-
-    fn try_into(self) -> Result<InterchainGasPayment, Self::Error> {
-        let message_id = self
-            .message_id
-            .ok_or_else(|| ChainCommunicationError::from_other_str("missing message_id"))?;
-        let payment = self
-            .payment
-            .ok_or_else(|| ChainCommunicationError::from_other_str("missing payment"))?;
-        let gas_amount = self
-            .gas_amount
-            .ok_or_else(|| ChainCommunicationError::from_other_str("missing gas_amount"))?;
-        let destination = self
-            .destination
-            .ok_or_else(|| ChainCommunicationError::from_other_str("missing destination"))?;
-
-        Ok(InterchainGasPayment {
-            message_id,
-            payment,
-            gas_amount,
-            destination,
-        })
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
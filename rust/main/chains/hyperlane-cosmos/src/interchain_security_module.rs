@@ -78,7 +78,7 @@ impl InterchainSecurityModule for CosmosInterchainSecurityModule {
         let data = self
             .provider
             .grpc()
-            .wasm_query(QueryIsmGeneralRequest { ism: query }, None)
+            .wasm_query_cached("module_type", QueryIsmGeneralRequest { ism: query })
             .await?;
 
         let module_type_response =
@@ -8,6 +8,7 @@
 
 mod aggregation_ism;
 mod error;
+mod events;
 mod interchain_gas;
 mod interchain_security_module;
 mod libs;
@@ -2,13 +2,12 @@ use std::num::NonZeroU64;
 use std::str::FromStr;
 
 use async_trait::async_trait;
-use cosmrs::proto::cosmos::base::abci::v1beta1::TxResponse;
 use tracing::instrument;
 
 use hyperlane_core::{
     utils::bytes_to_hex, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
     HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Mailbox, RawHyperlaneMessage,
-    TxCostEstimate, TxOutcome, H256, U256,
+    TxCostEstimate, TxOutcome, TxSubmitter, TxSubmitterConf, H256, U256,
 };
 
 use crate::grpc::WasmProvider;
@@ -16,7 +15,6 @@ use crate::payloads::general;
 use crate::payloads::mailbox::{
     GeneralMailboxQuery, ProcessMessageRequest, ProcessMessageRequestInner,
 };
-use crate::types::tx_response_to_outcome;
 use crate::utils::get_block_height_for_lag;
 use crate::{payloads, ConnectionConf, CosmosAddress, CosmosProvider, Signer};
 
@@ -167,13 +165,9 @@ impl Mailbox for CosmosMailbox {
             },
         };
 
-        let response: TxResponse = self
-            .provider
-            .grpc()
-            .wasm_send(process_message, tx_gas_limit)
-            .await?;
-
-        Ok(tx_response_to_outcome(response)?)
+        let provider = self.provider.grpc();
+        let tx = provider.wasm_tx(&process_message, tx_gas_limit)?;
+        provider.submit(tx, &TxSubmitterConf::default()).await
     }
 
     #[instrument(err, ret, skip(self), fields(msg=%message, metadata=%bytes_to_hex(metadata)))]
@@ -200,6 +194,7 @@ impl Mailbox for CosmosMailbox {
             gas_limit: gas_limit.into(),
             gas_price: self.provider.grpc().gas_price(),
             l2_gas_limit: None,
+            l1_data_fee: None,
         };
 
         Ok(result)
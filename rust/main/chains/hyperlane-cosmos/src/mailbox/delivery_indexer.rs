@@ -3,28 +3,28 @@ use std::fmt::{Debug, Formatter};
 use std::ops::RangeInclusive;
 
 use async_trait::async_trait;
-use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
-use once_cell::sync::Lazy;
 use tendermint::abci::EventAttribute;
 use tracing::instrument;
 
 use hyperlane_core::{
-    ChainCommunicationError, ChainResult, ContractLocator, Delivery, HyperlaneMessage, Indexed,
-    Indexer, LogMeta, SequenceAwareIndexer, H256, H512,
+    ChainResult, ContractLocator, Delivery, HyperlaneMessage, Indexed, Indexer, LogMeta,
+    SequenceAwareIndexer, H256, H512,
 };
 
+use crate::events::{AttributeSchema, EventSchema};
 use crate::rpc::{CosmosWasmRpcProvider, ParsedEvent, WasmRpcProvider};
-use crate::utils::{
-    execute_and_parse_log_futures, parse_logs_in_range, parse_logs_in_tx,
-    CONTRACT_ADDRESS_ATTRIBUTE_KEY, CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64,
-};
-use crate::{ConnectionConf, HyperlaneCosmosError, Signer};
+use crate::utils::{execute_and_parse_log_futures, parse_logs_in_range, parse_logs_in_tx};
+use crate::{ConnectionConf, Signer};
 
 /// The message process event type from the CW contract.
 const MESSAGE_DELIVERY_EVENT_TYPE: &str = "mailbox_process_id";
-const MESSAGE_ID_ATTRIBUTE_KEY: &str = "message_id";
-static MESSAGE_ID_ATTRIBUTE_KEY_BASE64: Lazy<String> =
-    Lazy::new(|| BASE64.encode(MESSAGE_ID_ATTRIBUTE_KEY));
+static MESSAGE_DELIVERY_SCHEMA: EventSchema = EventSchema {
+    event: "process",
+    attributes: &[AttributeSchema {
+        field: "message_id",
+        keys: &["message_id"],
+    }],
+};
 
 /// Struct that retrieves delivery event data for a Cosmos Mailbox contract
 pub struct CosmosMailboxDeliveryIndexer {
@@ -56,47 +56,10 @@ impl CosmosMailboxDeliveryIndexer {
     fn hyperlane_delivery_parser(
         attrs: &Vec<EventAttribute>,
     ) -> ChainResult<ParsedEvent<Delivery>> {
-        let mut contract_address: Option<String> = None;
-        let mut message_id: Option<Delivery> = None;
-
-        for attr in attrs {
-            let key = attr.key.as_str();
-            let value = attr.value.as_str();
-
-            match key {
-                CONTRACT_ADDRESS_ATTRIBUTE_KEY => {
-                    contract_address = Some(value.to_string());
-                }
-                v if *CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64 == v => {
-                    contract_address = Some(String::from_utf8(
-                        BASE64
-                            .decode(value)
-                            .map_err(Into::<HyperlaneCosmosError>::into)?,
-                    )?);
-                }
-
-                MESSAGE_ID_ATTRIBUTE_KEY => {
-                    message_id = Some(value.parse::<H256>()?);
-                }
-                v if *MESSAGE_ID_ATTRIBUTE_KEY_BASE64 == v => {
-                    let hex = String::from_utf8(
-                        BASE64
-                            .decode(value)
-                            .map_err(Into::<HyperlaneCosmosError>::into)?,
-                    )?;
-                    message_id = Some(hex.parse::<H256>()?);
-                }
-
-                _ => {}
-            }
-        }
-
-        let contract_address = contract_address
-            .ok_or_else(|| ChainCommunicationError::from_other_str("missing contract_address"))?;
-        let message_id = message_id
-            .ok_or_else(|| ChainCommunicationError::from_other_str("missing message_id"))?;
-
-        Ok(ParsedEvent::new(contract_address, message_id))
+        let event = MESSAGE_DELIVERY_SCHEMA.decode(attrs)?;
+        let message_id = event.parse_h256("message_id")?;
+
+        Ok(event.into_parsed(message_id))
     }
 }
 
@@ -2,28 +2,28 @@ use std::io::Cursor;
 use std::ops::RangeInclusive;
 
 use async_trait::async_trait;
-use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
-use once_cell::sync::Lazy;
 use tendermint::abci::EventAttribute;
 use tracing::instrument;
 
 use hyperlane_core::{
-    ChainCommunicationError, ChainResult, ContractLocator, Decode, HyperlaneMessage, Indexed,
-    Indexer, LogMeta, SequenceAwareIndexer, H512,
+    ChainResult, ContractLocator, Decode, HyperlaneMessage, Indexed, Indexer, LogMeta,
+    SequenceAwareIndexer, H512,
 };
 
+use crate::events::{AttributeSchema, EventSchema};
 use crate::rpc::{CosmosWasmRpcProvider, ParsedEvent, WasmRpcProvider};
-use crate::utils::{
-    execute_and_parse_log_futures, parse_logs_in_range, parse_logs_in_tx,
-    CONTRACT_ADDRESS_ATTRIBUTE_KEY, CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64,
-};
-use crate::{ConnectionConf, CosmosMailbox, HyperlaneCosmosError, Signer};
+use crate::utils::{execute_and_parse_log_futures, parse_logs_in_range, parse_logs_in_tx};
+use crate::{ConnectionConf, CosmosMailbox, Signer};
 
 /// The message dispatch event type from the CW contract.
 const MESSAGE_DISPATCH_EVENT_TYPE: &str = "mailbox_dispatch";
-const MESSAGE_ATTRIBUTE_KEY: &str = "message";
-static MESSAGE_ATTRIBUTE_KEY_BASE64: Lazy<String> =
-    Lazy::new(|| BASE64.encode(MESSAGE_ATTRIBUTE_KEY));
+static MESSAGE_DISPATCH_SCHEMA: EventSchema = EventSchema {
+    event: "dispatch",
+    attributes: &[AttributeSchema {
+        field: "message",
+        keys: &["message"],
+    }],
+};
 
 /// Struct that retrieves event data for a Cosmos Mailbox contract
 #[derive(Debug, Clone)]
@@ -59,52 +59,13 @@ impl CosmosMailboxDispatchIndexer {
     fn hyperlane_message_parser(
         attrs: &Vec<EventAttribute>,
     ) -> ChainResult<ParsedEvent<HyperlaneMessage>> {
-        let mut contract_address: Option<String> = None;
-        let mut message: Option<HyperlaneMessage> = None;
-
-        for attr in attrs {
-            let key = attr.key.as_str();
-            let value = attr.value.as_str();
-
-            match key {
-                CONTRACT_ADDRESS_ATTRIBUTE_KEY => {
-                    contract_address = Some(value.to_string());
-                }
-                v if *CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64 == v => {
-                    contract_address = Some(String::from_utf8(
-                        BASE64
-                            .decode(value)
-                            .map_err(Into::<HyperlaneCosmosError>::into)?,
-                    )?);
-                }
-
-                MESSAGE_ATTRIBUTE_KEY => {
-                    // Intentionally using read_from to get a Result::Err if there's
-                    // an issue with the message.
-                    let mut reader = Cursor::new(hex::decode(value)?);
-                    message = Some(HyperlaneMessage::read_from(&mut reader)?);
-                }
-                v if *MESSAGE_ATTRIBUTE_KEY_BASE64 == v => {
-                    // Intentionally using read_from to get a Result::Err if there's
-                    // an issue with the message.
-                    let mut reader = Cursor::new(hex::decode(String::from_utf8(
-                        BASE64
-                            .decode(value)
-                            .map_err(Into::<HyperlaneCosmosError>::into)?,
-                    )?)?);
-                    message = Some(HyperlaneMessage::read_from(&mut reader)?);
-                }
-
-                _ => {}
-            }
-        }
-
-        let contract_address = contract_address
-            .ok_or_else(|| ChainCommunicationError::from_other_str("missing contract_address"))?;
-        let message =
-            message.ok_or_else(|| ChainCommunicationError::from_other_str("missing message"))?;
-
-        Ok(ParsedEvent::new(contract_address, message))
+        let event = MESSAGE_DISPATCH_SCHEMA.decode(attrs)?;
+        // Intentionally using read_from to get a Result::Err if there's
+        // an issue with the message.
+        let mut reader = Cursor::new(event.parse_hex("message")?);
+        let message = HyperlaneMessage::read_from(&mut reader)?;
+
+        Ok(event.into_parsed(message))
     }
 }
 
@@ -169,7 +130,7 @@ mod tests {
 
         let expected = ParsedEvent::new(
             "neutron1sjzzd4gwkggy6hrrs8kxxatexzcuz3jecsxm3wqgregkulzj8r7qlnuef4".into(),
-            HyperlaneMessage::from(hex::decode("03000000006e74726e0000000000000000000000006ba6343a09a60ac048d0e99f50b76fd99eff1063000000a9000000000000000000000000281973b53c9aacec128ac964a6f750fea40912aa48656c6c6f2066726f6d204e657574726f6e204d61696e6e657420746f204d616e74612050616369666963206f63742032392c2031323a353520616d").unwrap()),
+            HyperlaneMessage::try_from(hex::decode("03000000006e74726e0000000000000000000000006ba6343a09a60ac048d0e99f50b76fd99eff1063000000a9000000000000000000000000281973b53c9aacec128ac964a6f750fea40912aa48656c6c6f2066726f6d204e657574726f6e204d61696e6e657420746f204d616e74612050616369666963206f63742032392c2031323a353520616d").unwrap()).unwrap(),
         );
 
         let assert_parsed_event = |attrs: &Vec<EventAttribute>| {
@@ -1,8 +1,6 @@
 use std::{fmt::Debug, num::NonZeroU64, ops::RangeInclusive, str::FromStr};
 
 use async_trait::async_trait;
-use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
-use once_cell::sync::Lazy;
 use tendermint::abci::EventAttribute;
 use tracing::instrument;
 
@@ -13,14 +11,14 @@ use hyperlane_core::{
     MerkleTreeHook, MerkleTreeInsertion, SequenceAwareIndexer, H256, H512,
 };
 
+use crate::events::{AttributeSchema, EventSchema};
 use crate::grpc::WasmProvider;
 use crate::payloads::{general, merkle_tree_hook};
 use crate::rpc::{CosmosWasmRpcProvider, ParsedEvent, WasmRpcProvider};
 use crate::utils::{
     execute_and_parse_log_futures, get_block_height_for_lag, parse_logs_in_range, parse_logs_in_tx,
-    CONTRACT_ADDRESS_ATTRIBUTE_KEY, CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64,
 };
-use crate::{ConnectionConf, CosmosProvider, HyperlaneCosmosError, Signer};
+use crate::{ConnectionConf, CosmosProvider, Signer};
 
 #[derive(Debug, Clone)]
 /// A reference to a MerkleTreeHook contract on some Cosmos chain
@@ -174,13 +172,19 @@ impl CosmosMerkleTreeHook {
 
 // ------------------ Indexer ------------------
 
-const INDEX_ATTRIBUTE_KEY: &str = "index";
-pub(crate) static INDEX_ATTRIBUTE_KEY_BASE64: Lazy<String> =
-    Lazy::new(|| BASE64.encode(INDEX_ATTRIBUTE_KEY));
-
-const MESSAGE_ID_ATTRIBUTE_KEY: &str = "message_id";
-pub(crate) static MESSAGE_ID_ATTRIBUTE_KEY_BASE64: Lazy<String> =
-    Lazy::new(|| BASE64.encode(MESSAGE_ID_ATTRIBUTE_KEY));
+static MERKLE_TREE_INSERTION_SCHEMA: EventSchema = EventSchema {
+    event: "merkle tree insertion",
+    attributes: &[
+        AttributeSchema {
+            field: "leaf_index",
+            keys: &["index"],
+        },
+        AttributeSchema {
+            field: "message_id",
+            keys: &["message_id"],
+        },
+    ],
+};
 
 #[derive(Debug, Clone)]
 /// A reference to a MerkleTreeHookIndexer contract on some Cosmos chain
@@ -219,61 +223,11 @@ impl CosmosMerkleTreeHookIndexer {
     fn merkle_tree_insertion_parser(
         attrs: &Vec<EventAttribute>,
     ) -> ChainResult<ParsedEvent<MerkleTreeInsertion>> {
-        let mut contract_address: Option<String> = None;
-        let mut insertion = IncompleteMerkleTreeInsertion::default();
-
-        for attr in attrs {
-            let key = attr.key.as_str();
-            let value = attr.value.as_str();
-
-            match key {
-                CONTRACT_ADDRESS_ATTRIBUTE_KEY => {
-                    contract_address = Some(value.to_string());
-                }
-                v if *CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64 == v => {
-                    contract_address = Some(String::from_utf8(
-                        BASE64
-                            .decode(value)
-                            .map_err(Into::<HyperlaneCosmosError>::into)?,
-                    )?);
-                }
-
-                MESSAGE_ID_ATTRIBUTE_KEY => {
-                    insertion.message_id = Some(H256::from_slice(hex::decode(value)?.as_slice()));
-                }
-                v if *MESSAGE_ID_ATTRIBUTE_KEY_BASE64 == v => {
-                    insertion.message_id = Some(H256::from_slice(
-                        hex::decode(String::from_utf8(
-                            BASE64
-                                .decode(value)
-                                .map_err(Into::<HyperlaneCosmosError>::into)?,
-                        )?)?
-                        .as_slice(),
-                    ));
-                }
-
-                INDEX_ATTRIBUTE_KEY => {
-                    insertion.leaf_index = Some(value.parse::<u32>()?);
-                }
-                v if *INDEX_ATTRIBUTE_KEY_BASE64 == v => {
-                    insertion.leaf_index = Some(
-                        String::from_utf8(
-                            BASE64
-                                .decode(value)
-                                .map_err(Into::<HyperlaneCosmosError>::into)?,
-                        )?
-                        .parse()?,
-                    );
-                }
-
-                _ => {}
-            }
-        }
-
-        let contract_address = contract_address
-            .ok_or_else(|| ChainCommunicationError::from_other_str("missing contract_address"))?;
-
-        Ok(ParsedEvent::new(contract_address, insertion.try_into()?))
+        let event = MERKLE_TREE_INSERTION_SCHEMA.decode(attrs)?;
+        let insertion =
+            MerkleTreeInsertion::new(event.parse("leaf_index")?, event.parse_h256("message_id")?);
+
+        Ok(event.into_parsed(insertion))
     }
 }
 
@@ -327,27 +281,6 @@ impl SequenceAwareIndexer<MerkleTreeInsertion> for CosmosMerkleTreeHookIndexer {
     }
 }
 
-#[derive(Default)]
-struct IncompleteMerkleTreeInsertion {
-    leaf_index: Option<u32>,
-    message_id: Option<H256>,
-}
-
-impl TryInto<MerkleTreeInsertion> for IncompleteMerkleTreeInsertion {
-    type Error = ChainCommunicationError;
-
-    fn try_into(self) -> Result<MerkleTreeInsertion, Self::Error> {
-        let leaf_index = self
-            .leaf_index
-            .ok_or_else(|| ChainCommunicationError::from_other_str("missing leaf_index"))?;
-        let message_id = self
-            .message_id
-            .ok_or_else(|| ChainCommunicationError::from_other_str("missing message_id"))?;
-
-        Ok(MerkleTreeInsertion::new(leaf_index, message_id))
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
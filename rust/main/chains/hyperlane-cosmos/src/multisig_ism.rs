@@ -71,10 +71,12 @@ impl MultisigIsm for CosmosMultisigIsm {
             },
         };
 
+        // The validator set only depends on the origin of the message
+        let cache_key = format!("validators_and_threshold_{}", message.origin);
         let data = self
             .provider
             .grpc()
-            .wasm_query(QueryIsmGeneralRequest { ism: payload }, None)
+            .wasm_query_cached(&cache_key, QueryIsmGeneralRequest { ism: payload })
             .await?;
         let response: multisig_ism::VerifyInfoResponse = serde_json::from_slice(&data)?;
 
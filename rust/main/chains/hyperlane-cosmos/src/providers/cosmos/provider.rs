@@ -25,6 +25,7 @@ use hyperlane_core::{
 use crate::grpc::{WasmGrpcProvider, WasmProvider};
 use crate::providers::cosmos::provider::parse::PacketData;
 use crate::providers::rpc::CosmosRpcClient;
+use crate::signers::{ETHERMINT_PUBLIC_KEY_TYPE_URL, INJECTIVE_PUBLIC_KEY_TYPE_URL};
 use crate::{
     ConnectionConf, CosmosAccountId, CosmosAddress, CosmosAmount, HyperlaneCosmosError, Signer,
 };
@@ -34,9 +35,6 @@ mod parse;
 /// Exponent value for atto units (10^-18).
 const ATTO_EXPONENT: u32 = 18;
 
-/// Injective public key type URL for protobuf Any
-const INJECTIVE_PUBLIC_KEY_TYPE_URL: &str = "/injective.crypto.v1beta1.ethsecp256k1.PubKey";
-
 /// Abstraction over a connection to a Cosmos chain
 #[derive(Debug, Clone)]
 pub struct CosmosProvider {
@@ -127,44 +125,46 @@ impl CosmosProvider {
                 (SignerPublicKey::from(pk), AccountAddressType::Bitcoin)
             }
             SignerPublicKey::Any(pk) => {
+                let is_ethsecp256k1 = pk.type_url == INJECTIVE_PUBLIC_KEY_TYPE_URL
+                    || pk.type_url == ETHERMINT_PUBLIC_KEY_TYPE_URL;
                 if pk.type_url != PublicKey::ED25519_TYPE_URL
                     && pk.type_url != PublicKey::SECP256K1_TYPE_URL
-                    && pk.type_url != INJECTIVE_PUBLIC_KEY_TYPE_URL
+                    && !is_ethsecp256k1
                 {
                     let msg = format!(
-                        "can only normalize public keys with a known TYPE_URL: {}, {}, {}",
+                        "can only normalize public keys with a known TYPE_URL: {}, {}, {}, {}",
                         PublicKey::ED25519_TYPE_URL,
                         PublicKey::SECP256K1_TYPE_URL,
-                        INJECTIVE_PUBLIC_KEY_TYPE_URL
+                        INJECTIVE_PUBLIC_KEY_TYPE_URL,
+                        ETHERMINT_PUBLIC_KEY_TYPE_URL
                     );
                     warn!(pk.type_url, msg);
                     Err(HyperlaneCosmosError::PublicKeyError(msg.to_owned()))?
                 }
 
-                let (pub_key, account_address_type) =
-                    if pk.type_url == INJECTIVE_PUBLIC_KEY_TYPE_URL {
-                        let any = Any {
-                            type_url: PublicKey::SECP256K1_TYPE_URL.to_owned(),
-                            value: pk.value,
-                        };
+                let (pub_key, account_address_type) = if is_ethsecp256k1 {
+                    let any = Any {
+                        type_url: PublicKey::SECP256K1_TYPE_URL.to_owned(),
+                        value: pk.value,
+                    };
 
-                        let proto = proto::cosmos::crypto::secp256k1::PubKey::from_any(&any)
-                            .map_err(Into::<HyperlaneCosmosError>::into)?;
+                    let proto = proto::cosmos::crypto::secp256k1::PubKey::from_any(&any)
+                        .map_err(Into::<HyperlaneCosmosError>::into)?;
 
-                        let decompressed = decompress_public_key(&proto.key)
-                            .map_err(|e| HyperlaneCosmosError::PublicKeyError(e.to_string()))?;
+                    let decompressed = decompress_public_key(&proto.key)
+                        .map_err(|e| HyperlaneCosmosError::PublicKeyError(e.to_string()))?;
 
-                        let tendermint = tendermint::PublicKey::from_raw_secp256k1(&decompressed)
-                            .ok_or_else(|| {
+                    let tendermint = tendermint::PublicKey::from_raw_secp256k1(&decompressed)
+                        .ok_or_else(|| {
                             HyperlaneCosmosError::PublicKeyError(
                                 "cannot create tendermint public key".to_owned(),
                             )
                         })?;
 
-                        (PublicKey::from(tendermint), AccountAddressType::Ethereum)
-                    } else {
-                        (PublicKey::try_from(pk)?, AccountAddressType::Bitcoin)
-                    };
+                    (PublicKey::from(tendermint), AccountAddressType::Ethereum)
+                } else {
+                    (PublicKey::try_from(pk)?, AccountAddressType::Bitcoin)
+                };
 
                 (SignerPublicKey::Single(pub_key), account_address_type)
             }
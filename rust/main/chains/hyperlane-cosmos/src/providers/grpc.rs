@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Duration};
 
 use async_trait::async_trait;
 use cosmrs::{
@@ -14,7 +14,7 @@ use cosmrs::{
             },
             tx::v1beta1::{
                 service_client::ServiceClient as TxServiceClient, BroadcastMode,
-                BroadcastTxRequest, SimulateRequest, TxRaw,
+                BroadcastTxRequest, GetTxRequest, SimulateRequest, TxRaw,
             },
         },
         cosmwasm::wasm::v1::{
@@ -23,38 +23,111 @@ use cosmrs::{
         },
         traits::Message,
     },
-    tx::{self, Fee, MessageExt, SignDoc, SignerInfo},
+    tx::{self, AuthInfo, Fee, MessageExt, SignDoc, SignerInfo},
     Any, Coin,
 };
 use derive_new::new;
+use futures::future::try_join_all;
 use protobuf::Message as _;
 use serde::Serialize;
+use tokio::time::sleep;
 use tonic::{
-    transport::{Channel, Endpoint},
-    GrpcMethod, IntoRequest,
+    codegen::InterceptedService,
+    metadata::{AsciiMetadataKey, AsciiMetadataValue},
+    service::Interceptor,
+    transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity},
+    GrpcMethod, IntoRequest, Status,
 };
 use tracing::{debug, instrument};
 use url::Url;
 
 use hyperlane_core::{
-    rpc_clients::{BlockNumberGetter, FallbackProvider},
-    ChainCommunicationError, ChainResult, ContractLocator, FixedPointNumber, HyperlaneDomain, U256,
+    config::{RpcHeadersConf, RpcTlsConf},
+    rpc_clients::{BlockNumberGetter, FallbackProvider, ProxyConnector},
+    ChainCommunicationError, ChainResult, ContractLocator, FixedPointNumber, HyperlaneDomain,
+    TxAdapter, TxErrorClass, TxOutcome, U256,
 };
 
-use crate::{rpc_clients::CosmosFallbackProvider, HyperlaneCosmosError};
+use crate::{
+    rpc_clients::CosmosFallbackProvider, types::tx_response_to_outcome, HyperlaneCosmosError,
+};
 use crate::{signers::Signer, ConnectionConf};
 use crate::{CosmosAddress, CosmosAmount};
 
-/// A multiplier applied to a simulated transaction's gas usage to
-/// calculate the estimated gas.
-const GAS_ESTIMATE_MULTIPLIER: f64 = 1.25;
+use self::cache::QUERY_CACHE;
+use self::gas_multiplier::GAS_MULTIPLIERS;
+use self::usage::RpcUsageService;
+
+mod cache;
+mod gas_multiplier;
+mod usage;
+
 /// The number of blocks in the future in which a transaction will
 /// be valid for.
 const TIMEOUT_BLOCKS: u64 = 1000;
+/// How many times to look for a transaction sent with an estimated gas limit,
+/// to learn from its outcome.
+const TX_OUTCOME_POLL_ATTEMPTS: usize = 10;
+/// How long to wait between looking for a transaction.
+const TX_OUTCOME_POLL_INTERVAL: Duration = Duration::from_secs(3);
+/// The protobuf type name of the accounts of EVM compatible chains.
+const ETH_ACCOUNT_TYPE_NAME: &str = ".EthAccount";
+
+/// Adds the configured RPC headers to the metadata of every request
+#[derive(Clone, Default)]
+struct HeaderInterceptor {
+    headers: Vec<(AsciiMetadataKey, AsciiMetadataValue)>,
+}
+
+impl HeaderInterceptor {
+    fn new(rpc_headers: &RpcHeadersConf) -> Result<Self, HyperlaneCosmosError> {
+        let headers = rpc_headers
+            .all()
+            .into_iter()
+            .map(|(name, value)| {
+                let invalid = || HyperlaneCosmosError::InvalidRpcHeader(name.clone());
+                let key = AsciiMetadataKey::from_bytes(name.as_bytes()).map_err(|_| invalid())?;
+                let mut value = value.parse::<AsciiMetadataValue>().map_err(|_| invalid())?;
+                value.set_sensitive(true);
+                Ok((key, value))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { headers })
+    }
+}
+
+impl Interceptor for HeaderInterceptor {
+    fn call(&mut self, mut request: tonic::Request<()>) -> Result<tonic::Request<()>, Status> {
+        for (key, value) in &self.headers {
+            request.metadata_mut().insert(key.clone(), value.clone());
+        }
+        Ok(request)
+    }
+}
+
+// Header values often hold API keys, so only their names are logged
+impl Debug for HeaderInterceptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<_> = self.headers.iter().map(|(key, _)| key.as_str()).collect();
+        f.debug_struct("HeaderInterceptor")
+            .field("headers", &names)
+            .finish()
+    }
+}
+
+/// The TLS config presenting the client certificate of `tls`
+fn client_tls_config(tls: &RpcTlsConf) -> ClientTlsConfig {
+    let config =
+        ClientTlsConfig::new().identity(Identity::from_pem(&tls.client_cert, &tls.client_key));
+    match &tls.ca_cert {
+        Some(ca_cert) => config.ca_certificate(Certificate::from_pem(ca_cert)),
+        None => config,
+    }
+}
 
 #[derive(Debug, Clone, new)]
 struct CosmosChannel {
-    channel: Channel,
+    channel: InterceptedService<RpcUsageService<Channel>, HeaderInterceptor>,
     /// The url that this channel is connected to.
     /// Not explicitly used, but useful for debugging.
     _url: Url,
@@ -99,6 +172,24 @@ pub trait WasmProvider: Send + Sync {
         block_height: Option<u64>,
     ) -> ChainResult<Vec<u8>>;
 
+    /// Perform several wasm queries against the stored contract address,
+    /// concurrently and over the same connection. Responses are returned in
+    /// the order of `payloads`.
+    async fn wasm_query_batch<T: Serialize + Sync + Send + Clone + Debug>(
+        &self,
+        payloads: Vec<T>,
+        block_height: Option<u64>,
+    ) -> ChainResult<Vec<Vec<u8>>>;
+
+    /// Perform a wasm query against the stored contract address at the latest
+    /// block, reusing the result of a recent query made with the same
+    /// `cache_key`. Only use this for data that rarely changes.
+    async fn wasm_query_cached<T: Serialize + Sync + Send + Clone + Debug>(
+        &self,
+        cache_key: &str,
+        payload: T,
+    ) -> ChainResult<Vec<u8>>;
+
     /// Request contract info from the stored contract address.
     async fn wasm_contract_info(&self) -> ChainResult<ContractInfo>;
 
@@ -144,13 +235,21 @@ impl WasmGrpcProvider {
         signer: Option<Signer>,
     ) -> ChainResult<Self> {
         // get all the configured grpc urls and convert them to a Vec<Endpoint>
+        let interceptor = HeaderInterceptor::new(conf.get_rpc_headers())?;
         let channels: Result<Vec<CosmosChannel>, _> = conf
             .get_grpc_urls()
             .into_iter()
             .map(|url| {
-                Endpoint::new(url.to_string())
-                    .map(|e| CosmosChannel::new(e.connect_lazy(), url))
-                    .map_err(Into::<HyperlaneCosmosError>::into)
+                let mut endpoint = Endpoint::new(url.to_string())?;
+                if let Some(tls) = conf.get_rpc_tls() {
+                    endpoint = endpoint.tls_config(client_tls_config(tls))?;
+                }
+                let connector = ProxyConnector::new(conf.get_proxy().cloned());
+                let channel = endpoint.connect_with_connector_lazy(connector);
+                let channel =
+                    RpcUsageService::new(channel, url.clone(), conf.get_rpc_usage().cloned());
+                let channel = InterceptedService::new(channel, interceptor.clone());
+                Ok::<_, HyperlaneCosmosError>(CosmosChannel::new(channel, url))
             })
             .collect();
         let mut builder = FallbackProvider::builder();
@@ -186,17 +285,48 @@ impl WasmGrpcProvider {
         self.gas_price.amount.clone()
     }
 
-    /// Generates an unsigned SignDoc for a transaction and the Coin amount
-    /// required to pay for tx fees.
-    async fn generate_unsigned_sign_doc_and_fee(
+    /// The accounts signing transactions: the signer, followed by the fee
+    /// payer if one is configured.
+    fn get_tx_signers(&self) -> ChainResult<Vec<&Signer>> {
+        let signer = self.get_signer()?;
+        Ok(std::iter::once(signer)
+            .chain(self.conf.get_fee_payer())
+            .collect())
+    }
+
+    /// The fee for a transaction, charged to the fee payer and deducted
+    /// from the allowance of the fee granter if either is configured.
+    fn build_fee(&self, fee_coin: Coin, gas_limit: u64) -> ChainResult<Fee> {
+        let mut fee = Fee::from_amount_and_gas(fee_coin, gas_limit);
+        fee.payer = self
+            .conf
+            .get_fee_payer()
+            .map(|fee_payer| fee_payer.address.parse())
+            .transpose()
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+        fee.granter = self
+            .conf
+            .get_fee_granter()
+            .map(str::parse)
+            .transpose()
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+        Ok(fee)
+    }
+
+    /// Generates an unsigned SignDoc for each of the transaction signers and the
+    /// Coin amount required to pay for tx fees.
+    async fn generate_unsigned_sign_docs_and_fee(
         &self,
         msgs: Vec<cosmrs::Any>,
         gas_limit: u64,
-    ) -> ChainResult<(SignDoc, Coin)> {
+    ) -> ChainResult<(Vec<SignDoc>, Coin)> {
         // As this function is only used for estimating gas or sending transactions,
         // we can reasonably expect to have a signer.
-        let signer = self.get_signer()?;
-        let account_info = self.account_query(signer.address.clone()).await?;
+        let signers = self.get_tx_signers()?;
+        let mut accounts = Vec::with_capacity(signers.len());
+        for signer in &signers {
+            accounts.push(self.account_query(signer.address.clone()).await?);
+        }
         let current_height = self.latest_block_height().await?;
         let timeout_height = current_height + TIMEOUT_BLOCKS;
 
@@ -206,7 +336,11 @@ impl WasmGrpcProvider {
             TryInto::<u32>::try_into(timeout_height)
                 .map_err(ChainCommunicationError::from_other)?,
         );
-        let signer_info = SignerInfo::single_direct(Some(signer.public_key), account_info.sequence);
+        let signer_infos = signers
+            .iter()
+            .zip(&accounts)
+            .map(|(signer, account_info)| signer.signer_info(account_info.sequence))
+            .collect();
 
         let amount: u128 = (FixedPointNumber::from(gas_limit) * self.gas_price())
             .ceil_to_integer()
@@ -217,8 +351,10 @@ impl WasmGrpcProvider {
             self.conf.get_canonical_asset().as_str(),
         )
         .map_err(Into::<HyperlaneCosmosError>::into)?;
-        let auth_info =
-            signer_info.auth_info(Fee::from_amount_and_gas(fee_coin.clone(), gas_limit));
+        let auth_info = AuthInfo {
+            signer_infos,
+            fee: self.build_fee(fee_coin.clone(), gas_limit)?,
+        };
 
         let chain_id = self
             .conf
@@ -226,11 +362,14 @@ impl WasmGrpcProvider {
             .parse()
             .map_err(Into::<HyperlaneCosmosError>::into)?;
 
-        Ok((
-            SignDoc::new(&tx_body, &auth_info, &chain_id, account_info.account_number)
-                .map_err(Into::<HyperlaneCosmosError>::into)?,
-            fee_coin,
-        ))
+        let sign_docs = accounts
+            .iter()
+            .map(|account_info| {
+                SignDoc::new(&tx_body, &auth_info, &chain_id, account_info.account_number)
+                    .map_err(Into::<HyperlaneCosmosError>::into)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((sign_docs, fee_coin))
     }
 
     /// Generates a raw signed transaction including `msgs`, estimating gas if a limit is not provided,
@@ -246,34 +385,55 @@ impl WasmGrpcProvider {
             self.estimate_gas(msgs.clone()).await?
         };
 
-        let (sign_doc, fee) = self
-            .generate_unsigned_sign_doc_and_fee(msgs, gas_limit)
+        let (sign_docs, fee) = self
+            .generate_unsigned_sign_docs_and_fee(msgs, gas_limit)
             .await?;
 
-        let signer = self.get_signer()?;
-        let tx_signed = sign_doc
-            .sign(&signer.signing_key()?)
-            .map_err(Into::<HyperlaneCosmosError>::into)?;
+        // Every signer signs a SignDoc over the same body and auth info, only the
+        // account number differs
+        let signers = self.get_tx_signers()?;
+        let mut signatures = Vec::with_capacity(signers.len());
+        for (signer, sign_doc) in signers.iter().zip(&sign_docs) {
+            let sign_doc_bytes = sign_doc
+                .clone()
+                .into_bytes()
+                .map_err(Into::<HyperlaneCosmosError>::into)?;
+            signatures.push(signer.sign(&sign_doc_bytes)?);
+        }
+        let sign_doc = sign_docs
+            .into_iter()
+            .next()
+            .ok_or(ChainCommunicationError::SignerUnavailable)?;
+        let raw_tx = TxRaw {
+            body_bytes: sign_doc.body_bytes,
+            auth_info_bytes: sign_doc.auth_info_bytes,
+            signatures,
+        };
         Ok((
-            tx_signed
+            raw_tx
                 .to_bytes()
-                .map_err(Into::<HyperlaneCosmosError>::into)?,
+                .map_err(ChainCommunicationError::from_other)?,
             fee,
         ))
     }
 
     /// Estimates gas for a transaction containing `msgs`.
     async fn estimate_gas(&self, msgs: Vec<cosmrs::Any>) -> ChainResult<u64> {
-        // Get a sign doc with 0 gas, because we plan to simulate
-        let (sign_doc, _) = self.generate_unsigned_sign_doc_and_fee(msgs, 0).await?;
+        // Get sign docs with 0 gas, because we plan to simulate
+        let (sign_docs, _) = self.generate_unsigned_sign_docs_and_fee(msgs, 0).await?;
+        let signer_count = sign_docs.len();
+        let sign_doc = sign_docs
+            .into_iter()
+            .next()
+            .ok_or(ChainCommunicationError::SignerUnavailable)?;
 
         let raw_tx = TxRaw {
             body_bytes: sign_doc.body_bytes,
             auth_info_bytes: sign_doc.auth_info_bytes,
             // The poorly documented trick to simulating a tx without a valid signature is to just pass
-            // in a single empty signature. Taken from cosmjs:
+            // in a single empty signature per signer. Taken from cosmjs:
             // https://github.com/cosmos/cosmjs/blob/44893af824f0712d1f406a8daa9fcae335422235/packages/stargate/src/modules/tx/queries.ts#L67
-            signatures: vec![vec![]],
+            signatures: vec![vec![]; signer_count],
         };
         let tx_bytes = raw_tx
             .to_bytes()
@@ -306,11 +466,85 @@ impl WasmGrpcProvider {
             })
             .await?;
 
-        let gas_estimate = (gas_used as f64 * GAS_ESTIMATE_MULTIPLIER) as u64;
+        let multiplier = GAS_MULTIPLIERS.get(self.domain.id());
+        let gas_estimate = (gas_used as f64 * multiplier) as u64;
 
         Ok(gas_estimate)
     }
 
+    /// Waits for the transaction with hash `txhash`, sent with an estimated gas
+    /// limit, to be included and updates the gas estimate multiplier of the chain
+    /// with its outcome.
+    fn learn_from_tx_outcome(&self, txhash: String) {
+        let provider = self.provider.clone();
+        let domain = self.domain.id();
+        tokio::spawn(async move {
+            for _ in 0..TX_OUTCOME_POLL_ATTEMPTS {
+                sleep(TX_OUTCOME_POLL_INTERVAL).await;
+                let hash = txhash.clone();
+                let response = provider
+                    .call(move |provider| {
+                        let hash = hash.clone();
+                        let future = async move {
+                            let mut client = TxServiceClient::new(provider.channel.clone());
+                            client
+                                .get_tx(GetTxRequest { hash })
+                                .await
+                                .map_err(ChainCommunicationError::from_other)?
+                                .into_inner()
+                                .tx_response
+                                .ok_or_else(|| {
+                                    ChainCommunicationError::from_other_str("Empty tx_response")
+                                })
+                        };
+                        Box::pin(future)
+                    })
+                    .await;
+                if let Ok(response) = response {
+                    GAS_MULTIPLIERS.record(domain, &response);
+                    return;
+                }
+            }
+            debug!(%txhash, domain, "Transaction not found, not updating gas multiplier");
+        });
+    }
+
+    /// A transaction executing the stored contract with `payload`, whose gas
+    /// is estimated if a limit is not provided
+    pub fn wasm_tx<T: Serialize>(
+        &self,
+        payload: &T,
+        gas_limit: Option<U256>,
+    ) -> ChainResult<CosmosTx> {
+        let signer = self.get_signer()?;
+        let contract_address = self.get_contract_address();
+        let msgs = vec![MsgExecuteContract {
+            sender: signer.address.clone(),
+            contract: contract_address.address(),
+            msg: serde_json::to_string(payload)?.as_bytes().to_vec(),
+            funds: vec![],
+        }
+        .to_any()
+        .map_err(ChainCommunicationError::from_other)?];
+        // We often use U256s to represent gas limits, but Cosmos expects u64s. Try to convert,
+        // and if it fails, just fallback to None which will result in gas estimation.
+        let gas_limit: Option<u64> = gas_limit.and_then(|limit| match limit.try_into() {
+            Ok(limit) => Some(limit),
+            Err(err) => {
+                tracing::warn!(
+                    ?err,
+                    "failed to convert gas_limit to u64, falling back to estimation"
+                );
+                None
+            }
+        });
+        Ok(CosmosTx {
+            msgs,
+            gas_limit,
+            estimated: false,
+        })
+    }
+
     /// Fetches balance for a given `address` and `denom`
     pub async fn get_balance(&self, address: String, denom: String) -> ChainResult<U256> {
         let response = self
@@ -366,14 +600,15 @@ impl WasmGrpcProvider {
             })
             .await?;
 
-        let account = BaseAccount::decode(
-            response
-                .account
-                .ok_or_else(|| ChainCommunicationError::from_other_str("account not present"))?
-                .value
-                .as_slice(),
-        )
-        .map_err(Into::<HyperlaneCosmosError>::into)?;
+        let account = response
+            .account
+            .ok_or_else(|| ChainCommunicationError::from_other_str("account not present"))?;
+        // Accounts of EVM compatible chains, e.g. Evmos, wrap a base account
+        if account.type_url.ends_with(ETH_ACCOUNT_TYPE_NAME) {
+            return Self::base_account_from_eth_account(&account.value);
+        }
+        let account = BaseAccount::decode(account.value.as_slice())
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
         Ok(account)
     }
 
@@ -420,15 +655,21 @@ impl WasmGrpcProvider {
             })
             .await?;
 
-        let mut eth_account = injective_protobuf::proto::account::EthAccount::parse_from_bytes(
-            response
+        Self::base_account_from_eth_account(
+            &response
                 .into_inner()
                 .account
                 .ok_or_else(|| ChainCommunicationError::from_other_str("account not present"))?
-                .value
-                .as_slice(),
+                .value,
         )
-        .map_err(Into::<HyperlaneCosmosError>::into)?;
+    }
+
+    /// Decodes the base account of an EthAccount, which Injective and
+    /// Ethermint based chains encode alike.
+    fn base_account_from_eth_account(bytes: &[u8]) -> ChainResult<BaseAccount> {
+        let mut eth_account =
+            injective_protobuf::proto::account::EthAccount::parse_from_bytes(bytes)
+                .map_err(Into::<HyperlaneCosmosError>::into)?;
 
         let base_account = eth_account.take_base_account();
         let pub_key = base_account.pub_key.into_option();
@@ -516,6 +757,65 @@ impl WasmProvider for WasmGrpcProvider {
         Ok(response.data)
     }
 
+    async fn wasm_query_batch<T>(
+        &self,
+        payloads: Vec<T>,
+        block_height: Option<u64>,
+    ) -> ChainResult<Vec<Vec<u8>>>
+    where
+        T: Serialize + Send + Sync + Clone + Debug,
+    {
+        let contract_address = self.get_contract_address();
+        let queries = payloads
+            .iter()
+            .map(|payload| Ok(serde_json::to_string(payload)?.as_bytes().to_vec()))
+            .collect::<ChainResult<Vec<_>>>()?;
+        self.provider
+            .call(move |provider| {
+                let to = contract_address.address().clone();
+                let queries = queries.clone();
+                let future = async move {
+                    // Requests multiplex over the channel, so they share a connection
+                    let client = WasmQueryClient::new(provider.channel.clone());
+                    let requests = queries.into_iter().map(|query_data| {
+                        let mut client = client.clone();
+                        let mut request = tonic::Request::new(QuerySmartContractStateRequest {
+                            address: to.clone(),
+                            query_data,
+                        });
+                        if let Some(block_height) = block_height {
+                            request
+                                .metadata_mut()
+                                .insert("x-cosmos-block-height", block_height.into());
+                        }
+                        async move {
+                            client
+                                .smart_contract_state(request)
+                                .await
+                                .map(|response| response.into_inner().data)
+                                .map_err(ChainCommunicationError::from_other)
+                        }
+                    });
+                    try_join_all(requests).await
+                };
+                Box::pin(future)
+            })
+            .await
+    }
+
+    async fn wasm_query_cached<T>(&self, cache_key: &str, payload: T) -> ChainResult<Vec<u8>>
+    where
+        T: Serialize + Send + Sync + Clone + Debug,
+    {
+        let contract_address = self.get_contract_address().address();
+        if let Some(data) = QUERY_CACHE.get(&contract_address, cache_key) {
+            return Ok(data);
+        }
+        let data = self.wasm_query(payload, None).await?;
+        QUERY_CACHE.insert(&contract_address, cache_key, data.clone());
+        Ok(data)
+    }
+
     async fn wasm_contract_info(&self) -> ChainResult<ContractInfo> {
         let contract_address = self.get_contract_address();
         let response = self
@@ -551,39 +851,84 @@ impl WasmProvider for WasmGrpcProvider {
     where
         T: Serialize + Send + Sync + Clone + Debug,
     {
+        let mut tx = self.wasm_tx(&payload, gas_limit)?;
+        self.estimate(&mut tx).await?;
+        let tx_res = self.send(&mut tx).await?;
+        debug!(tx_result=?tx_res, domain=?self.domain, ?payload, "Wasm transaction sent");
+        Ok(tx_res)
+    }
+
+    async fn wasm_estimate_gas<T>(&self, payload: T) -> ChainResult<u64>
+    where
+        T: Serialize + Send + Sync,
+    {
+        // Estimating gas requires a signer, which we can reasonably expect to have
+        // since we need one to send a tx with the estimated gas anyways.
         let signer = self.get_signer()?;
         let contract_address = self.get_contract_address();
-        let msgs = vec![MsgExecuteContract {
+        let msg = MsgExecuteContract {
             sender: signer.address.clone(),
             contract: contract_address.address(),
             msg: serde_json::to_string(&payload)?.as_bytes().to_vec(),
             funds: vec![],
+        };
+
+        let response = self
+            .estimate_gas(vec![msg
+                .to_any()
+                .map_err(ChainCommunicationError::from_other)?])
+            .await?;
+
+        Ok(response)
+    }
+}
+
+/// A transaction of Cosmos messages
+#[derive(Debug, Clone)]
+pub struct CosmosTx {
+    msgs: Vec<cosmrs::Any>,
+    gas_limit: Option<u64>,
+    /// Whether the gas limit was estimated, in which case the outcome of the
+    /// transaction updates the gas estimate multiplier of the chain
+    estimated: bool,
+}
+
+#[async_trait]
+impl TxAdapter for WasmGrpcProvider {
+    type Tx = CosmosTx;
+    type Pending = TxResponse;
+
+    async fn estimate(&self, tx: &mut CosmosTx) -> ChainResult<()> {
+        if tx.gas_limit.is_none() {
+            tx.gas_limit = Some(self.estimate_gas(tx.msgs.clone()).await?);
+            tx.estimated = true;
         }
-        .to_any()
-        .map_err(ChainCommunicationError::from_other)?];
-        let gas_limit: Option<u64> = gas_limit.and_then(|limit| match limit.try_into() {
-            Ok(limit) => Some(limit),
-            Err(err) => {
-                tracing::warn!(
-                    ?err,
-                    "failed to convert gas_limit to u64, falling back to estimation"
-                );
-                None
-            }
-        });
-        let (tx_bytes, fee) = self.generate_raw_signed_tx_and_fee(msgs, gas_limit).await?;
+        Ok(())
+    }
 
-        // Check if the signer has enough funds to pay for the fee so we can get
-        // a more informative error.
-        let signer_balance = self
-            .get_balance(signer.address.clone(), fee.denom.to_string())
+    /// Broadcasts the transaction once it passed `CheckTx`, without waiting
+    /// for it to be included
+    async fn send(&self, tx: &mut CosmosTx) -> ChainResult<TxResponse> {
+        let signer = self.get_signer()?;
+        let (tx_bytes, fee) = self
+            .generate_raw_signed_tx_and_fee(tx.msgs.clone(), tx.gas_limit)
             .await?;
-        let fee_amount: U256 = fee.amount.into();
-        if signer_balance < fee_amount {
-            return Err(ChainCommunicationError::InsufficientFunds {
-                required: fee_amount,
-                available: signer_balance,
-            });
+
+        // Check if the fee payer has enough funds to pay for the fee so we can get
+        // a more informative error. Fees paid through a grant are only checked
+        // when broadcasting.
+        if self.conf.get_fee_granter().is_none() {
+            let fee_payer = self.conf.get_fee_payer().unwrap_or(signer);
+            let payer_balance = self
+                .get_balance(fee_payer.address.clone(), fee.denom.to_string())
+                .await?;
+            let fee_amount: U256 = fee.amount.into();
+            if payer_balance < fee_amount {
+                return Err(ChainCommunicationError::InsufficientFunds {
+                    required: fee_amount,
+                    available: payer_balance,
+                });
+            }
         }
 
         let tx_res = self
@@ -592,8 +937,6 @@ impl WasmProvider for WasmGrpcProvider {
                 let tx_bytes = tx_bytes.clone();
                 let future = async move {
                     let mut client = TxServiceClient::new(provider.channel.clone());
-                    // We often use U256s to represent gas limits, but Cosmos expects u64s. Try to convert,
-                    // and if it fails, just fallback to None which will result in gas estimation.
                     let tx_req = BroadcastTxRequest {
                         tx_bytes,
                         mode: BroadcastMode::Sync as i32,
@@ -609,32 +952,35 @@ impl WasmProvider for WasmGrpcProvider {
                 Box::pin(future)
             })
             .await?;
-        debug!(tx_result=?tx_res, domain=?self.domain, ?payload, "Wasm transaction sent");
+        // Only transactions sent with an estimated gas limit say anything about
+        // the gas estimate multiplier
+        if tx.estimated {
+            if tx_res.code == 0 {
+                self.learn_from_tx_outcome(tx_res.txhash.clone());
+            } else {
+                GAS_MULTIPLIERS.record(self.domain.id(), &tx_res);
+            }
+        }
         Ok(tx_res)
     }
 
-    async fn wasm_estimate_gas<T>(&self, payload: T) -> ChainResult<u64>
-    where
-        T: Serialize + Send + Sync,
-    {
-        // Estimating gas requires a signer, which we can reasonably expect to have
-        // since we need one to send a tx with the estimated gas anyways.
-        let signer = self.get_signer()?;
-        let contract_address = self.get_contract_address();
-        let msg = MsgExecuteContract {
-            sender: signer.address.clone(),
-            contract: contract_address.address(),
-            msg: serde_json::to_string(&payload)?.as_bytes().to_vec(),
-            funds: vec![],
-        };
+    async fn confirm(&self, tx_res: &TxResponse) -> ChainResult<Option<TxOutcome>> {
+        Ok(Some(tx_response_to_outcome(tx_res.clone())?))
+    }
 
-        let response = self
-            .estimate_gas(vec![msg
-                .to_any()
-                .map_err(ChainCommunicationError::from_other)?])
-            .await?;
+    /// Fees are paid at the configured gas price
+    fn escalate_fee(&self, _tx: &mut CosmosTx, _multiplier: f64) -> bool {
+        false
+    }
 
-        Ok(response)
+    fn classify(&self, err: &ChainCommunicationError) -> TxErrorClass {
+        const RETRYABLE: &[&str] = &["transport error", "unavailable", "timed out"];
+        let message = err.to_string().to_lowercase();
+        if RETRYABLE.iter().any(|pattern| message.contains(pattern)) {
+            TxErrorClass::Retryable
+        } else {
+            TxErrorClass::Fatal
+        }
     }
 }
 
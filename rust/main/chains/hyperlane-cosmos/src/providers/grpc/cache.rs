@@ -0,0 +1,79 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+
+/// How long the result of a cached query is reused for. Only data that
+/// rarely changes is cached, like the type of an ISM or its validator set,
+/// so this mostly saves the repeated queries made for each delivery.
+const QUERY_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Results of cached queries, shared by every provider since contract
+/// objects are often rebuilt for each message.
+pub(super) static QUERY_CACHE: Lazy<QueryCache> = Lazy::new(|| QueryCache::new(QUERY_CACHE_TTL));
+
+/// A query cache entry is keyed by the contract address and a key describing
+/// the query.
+type QueryCacheKey = (String, String);
+
+/// A cache of smart query results that expire after a fixed time.
+#[derive(Debug)]
+pub(super) struct QueryCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<QueryCacheKey, (Instant, Vec<u8>)>>,
+}
+
+impl QueryCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Default::default(),
+        }
+    }
+
+    /// Get the result cached for `key` on `contract`, if it hasn't expired
+    pub(super) fn get(&self, contract: &str, key: &str) -> Option<Vec<u8>> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(&(contract.to_owned(), key.to_owned()))
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, data)| data.clone())
+    }
+
+    /// Cache `data` as the result for `key` on `contract`, dropping any
+    /// expired entries.
+    pub(super) fn insert(&self, contract: &str, key: &str, data: Vec<u8>) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (fetched_at, _)| fetched_at.elapsed() < self.ttl);
+        entries.insert(
+            (contract.to_owned(), key.to_owned()),
+            (Instant::now(), data),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cached_result() {
+        let cache = QueryCache::new(Duration::from_secs(60));
+        cache.insert("contract", "module_type", vec![1]);
+
+        assert_eq!(cache.get("contract", "module_type"), Some(vec![1]));
+        assert_eq!(cache.get("contract", "other"), None);
+        assert_eq!(cache.get("other_contract", "module_type"), None);
+    }
+
+    #[test]
+    fn test_expired_result() {
+        let cache = QueryCache::new(Duration::ZERO);
+        cache.insert("contract", "module_type", vec![1]);
+
+        assert_eq!(cache.get("contract", "module_type"), None);
+    }
+}
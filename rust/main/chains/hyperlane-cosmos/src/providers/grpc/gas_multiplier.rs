@@ -0,0 +1,123 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use cosmrs::proto::cosmos::base::abci::v1beta1::TxResponse;
+use once_cell::sync::Lazy;
+
+/// The multiplier applied to a simulated transaction's gas usage until the
+/// outcome of transactions on the chain says otherwise. It is also the lowest
+/// the multiplier can go.
+const DEFAULT_GAS_ESTIMATE_MULTIPLIER: f64 = 1.25;
+/// The highest the multiplier can go, so a misbehaving chain can't drive up
+/// fees indefinitely.
+const MAX_GAS_ESTIMATE_MULTIPLIER: f64 = 3.0;
+/// Factor the multiplier grows by after a transaction runs out of gas.
+const OUT_OF_GAS_INCREASE: f64 = 1.2;
+/// Factor the multiplier shrinks by after a transaction succeeds. Successes are
+/// much more common than failures, so this is much closer to 1.
+const SUCCESS_DECREASE: f64 = 0.99;
+
+/// Codespace of the errors defined by the Cosmos SDK.
+const SDK_CODESPACE: &str = "sdk";
+/// Code of the Cosmos SDK `ErrOutOfGas` error.
+const OUT_OF_GAS_CODE: u32 = 11;
+
+/// The gas estimate multipliers of every chain, shared by all providers since
+/// contract objects are often rebuilt for each message.
+pub(super) static GAS_MULTIPLIERS: Lazy<GasMultipliers> = Lazy::new(Default::default);
+
+/// Gas estimate multipliers per domain, learned from the outcome of the
+/// transactions sent with estimated gas limits.
+#[derive(Debug, Default)]
+pub(super) struct GasMultipliers {
+    multipliers: Mutex<HashMap<u32, f64>>,
+}
+
+impl GasMultipliers {
+    /// The multiplier to apply to simulated gas usage on `domain`
+    pub(super) fn get(&self, domain: u32) -> f64 {
+        let multipliers = self.multipliers.lock().unwrap();
+        multipliers
+            .get(&domain)
+            .copied()
+            .unwrap_or(DEFAULT_GAS_ESTIMATE_MULTIPLIER)
+    }
+
+    /// Update the multiplier of `domain` with the result of a transaction
+    /// sent with an estimated gas limit.
+    pub(super) fn record(&self, domain: u32, response: &TxResponse) {
+        if is_out_of_gas(response) {
+            self.update(domain, OUT_OF_GAS_INCREASE);
+        } else if response.code == 0 {
+            self.update(domain, SUCCESS_DECREASE);
+        }
+    }
+
+    fn update(&self, domain: u32, factor: f64) {
+        let mut multipliers = self.multipliers.lock().unwrap();
+        let multiplier = multipliers
+            .entry(domain)
+            .or_insert(DEFAULT_GAS_ESTIMATE_MULTIPLIER);
+        *multiplier = (*multiplier * factor)
+            .clamp(DEFAULT_GAS_ESTIMATE_MULTIPLIER, MAX_GAS_ESTIMATE_MULTIPLIER);
+    }
+}
+
+/// Whether the transaction failed because it ran out of gas
+fn is_out_of_gas(response: &TxResponse) -> bool {
+    response.codespace == SDK_CODESPACE && response.code == OUT_OF_GAS_CODE
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn response(codespace: &str, code: u32) -> TxResponse {
+        TxResponse {
+            codespace: codespace.to_owned(),
+            code,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_out_of_gas_increases_multiplier() {
+        let multipliers = GasMultipliers::default();
+        assert_eq!(multipliers.get(1), DEFAULT_GAS_ESTIMATE_MULTIPLIER);
+
+        multipliers.record(1, &response(SDK_CODESPACE, OUT_OF_GAS_CODE));
+        assert_eq!(
+            multipliers.get(1),
+            DEFAULT_GAS_ESTIMATE_MULTIPLIER * OUT_OF_GAS_INCREASE
+        );
+        // Other chains are unaffected
+        assert_eq!(multipliers.get(2), DEFAULT_GAS_ESTIMATE_MULTIPLIER);
+
+        for _ in 0..100 {
+            multipliers.record(1, &response(SDK_CODESPACE, OUT_OF_GAS_CODE));
+        }
+        assert_eq!(multipliers.get(1), MAX_GAS_ESTIMATE_MULTIPLIER);
+    }
+
+    #[test]
+    fn test_success_decreases_multiplier() {
+        let multipliers = GasMultipliers::default();
+        multipliers.record(1, &response(SDK_CODESPACE, OUT_OF_GAS_CODE));
+        let increased = multipliers.get(1);
+
+        multipliers.record(1, &response("", 0));
+        assert!(multipliers.get(1) < increased);
+
+        for _ in 0..100 {
+            multipliers.record(1, &response("", 0));
+        }
+        assert_eq!(multipliers.get(1), DEFAULT_GAS_ESTIMATE_MULTIPLIER);
+    }
+
+    #[test]
+    fn test_other_failures_are_ignored() {
+        let multipliers = GasMultipliers::default();
+        multipliers.record(1, &response("wasm", 5));
+        multipliers.record(1, &response(SDK_CODESPACE, 5));
+        assert_eq!(multipliers.get(1), DEFAULT_GAS_ESTIMATE_MULTIPLIER);
+    }
+}
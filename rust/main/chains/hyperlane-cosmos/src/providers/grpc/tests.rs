@@ -63,11 +63,17 @@ fn provider(address: &str) -> WasmGrpcProvider {
             OperationBatchConfig {
                 batch_contract_address: None,
                 max_batch_size: 1,
+                data_cost_model: None,
             },
             NativeToken {
                 decimals: 6,
                 denom: "untrn".to_owned(),
             },
+            None,
+            None,
+            Default::default(),
+            None,
+            None,
         ),
         CosmosAmount {
             denom: "untrn".to_owned(),
@@ -0,0 +1,66 @@
+use tonic::{
+    body::BoxBody,
+    codegen::{http, Body, BoxFuture, Context, Poll, Service},
+};
+use url::Url;
+
+use hyperlane_core::rpc_clients::RpcUsageMetrics;
+
+/// Records the gRPC requests made through a channel by method, along with
+/// the bytes of their encoded payloads as they are sent.
+#[derive(Clone, Debug)]
+pub(super) struct RpcUsageService<S> {
+    inner: S,
+    url: Url,
+    rpc_usage: Option<RpcUsageMetrics>,
+}
+
+impl<S> RpcUsageService<S> {
+    pub(super) fn new(inner: S, url: Url, rpc_usage: Option<RpcUsageMetrics>) -> Self {
+        Self {
+            inner,
+            url,
+            rpc_usage,
+        }
+    }
+}
+
+impl<S> Service<http::Request<BoxBody>> for RpcUsageService<S>
+where
+    S: Service<http::Request<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<S::Response, S::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<BoxBody>) -> Self::Future {
+        let Some(rpc_usage) = self.rpc_usage.clone() else {
+            return Box::pin(self.inner.call(request));
+        };
+        let url = self.url.clone();
+        // gRPC methods are the path of their requests,
+        // e.g. `/cosmos.tx.v1beta1.Service/Simulate`
+        let method = request.uri().path().to_owned();
+        let request = request.map(|body| {
+            let (rpc_usage, url, method) = (rpc_usage.clone(), url.clone(), method.clone());
+            body.map_data(move |data| {
+                rpc_usage.record_payload_bytes(&url, &method, data.len());
+                data
+            })
+            .boxed_unsync()
+        });
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let response = response.await;
+            // Errors returned by the node itself are only known once its
+            // response is read, so only transport errors count as failures
+            rpc_usage.record_request(&url, &method, response.is_ok());
+            response
+        })
+    }
+}
@@ -4,7 +4,8 @@ use tendermint_rpc::client::CompatMode;
 use tendermint_rpc::endpoint::{block, block_by_hash, block_results, tx};
 use tendermint_rpc::{Client, HttpClient};
 
-use hyperlane_core::ChainResult;
+use hyperlane_core::{rpc_clients::RpcUsageMetrics, ChainResult};
+use url::Url;
 
 use crate::{ConnectionConf, HyperlaneCosmosError};
 
@@ -12,66 +13,73 @@ use crate::{ConnectionConf, HyperlaneCosmosError};
 #[derive(Clone, Debug)]
 pub struct CosmosRpcClient {
     client: HttpClient,
+    url: Url,
+    rpc_usage: Option<RpcUsageMetrics>,
 }
 
 impl CosmosRpcClient {
     /// Create new `CosmosRpcClient`
     pub fn new(conf: &ConnectionConf) -> ChainResult<Self> {
-        let client = HttpClient::builder(
+        let url = Url::parse(&conf.get_rpc_url())
+            .map_err(|err| HyperlaneCosmosError::ParsingFailed(err.to_string()))?;
+        let mut builder = HttpClient::builder(
             conf.get_rpc_url()
                 .parse()
                 .map_err(Into::<HyperlaneCosmosError>::into)?,
         )
         // Consider supporting different compatibility modes.
-        .compat_mode(CompatMode::latest())
-        .build()
-        .map_err(Into::<HyperlaneCosmosError>::into)?;
+        .compat_mode(CompatMode::latest());
+        if let Some(proxy) = conf.get_proxy() {
+            builder = builder.proxy_url(
+                proxy
+                    .url()
+                    .as_str()
+                    .parse()
+                    .map_err(Into::<HyperlaneCosmosError>::into)?,
+            );
+        }
+        let client = builder
+            .build()
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            url,
+            rpc_usage: conf.get_rpc_usage().cloned(),
+        })
     }
 
     /// Request block by block height
     pub async fn get_block(&self, height: u32) -> ChainResult<block::Response> {
-        Ok(self
-            .client
-            .block(height)
-            .await
-            .map_err(Into::<HyperlaneCosmosError>::into)?)
+        self.track("block", self.client.block(height).await)
     }
 
     /// Request block results by block height
     pub async fn get_block_results(&self, height: u32) -> ChainResult<block_results::Response> {
-        Ok(self
-            .client
-            .block_results(height)
-            .await
-            .map_err(Into::<HyperlaneCosmosError>::into)?)
+        self.track("block_results", self.client.block_results(height).await)
     }
 
     /// Request block by block hash
     pub async fn get_block_by_hash(&self, hash: Hash) -> ChainResult<block_by_hash::Response> {
-        Ok(self
-            .client
-            .block_by_hash(hash)
-            .await
-            .map_err(Into::<HyperlaneCosmosError>::into)?)
+        self.track("block_by_hash", self.client.block_by_hash(hash).await)
     }
 
     /// Request the latest block
     pub async fn get_latest_block(&self) -> ChainResult<block::Response> {
-        Ok(self
-            .client
-            .latest_block()
-            .await
-            .map_err(Into::<HyperlaneCosmosError>::into)?)
+        self.track("block", self.client.latest_block().await)
     }
 
     /// Request transaction by transaction hash
     pub async fn get_tx_by_hash(&self, hash: Hash) -> ChainResult<tx::Response> {
-        Ok(self
-            .client
-            .tx(hash, false)
-            .await
-            .map_err(Into::<HyperlaneCosmosError>::into)?)
+        self.track("tx", self.client.tx(hash, false).await)
+    }
+
+    /// Records the request made, and maps its error. The payloads of requests
+    /// are serialized by the tendermint client, so their size isn't recorded.
+    fn track<T>(&self, method: &str, result: Result<T, tendermint_rpc::Error>) -> ChainResult<T> {
+        if let Some(rpc_usage) = &self.rpc_usage {
+            rpc_usage.record_request(&self.url, method, result.is_ok());
+        }
+        Ok(result.map_err(Into::<HyperlaneCosmosError>::into)?)
     }
 }
@@ -1,8 +1,19 @@
 use cosmrs::crypto::{secp256k1::SigningKey, PublicKey};
+use cosmrs::proto::{self, traits::Message};
+use cosmrs::tx::{SequenceNumber, SignerInfo, SignerPublicKey};
+use cosmrs::Any;
 use hyperlane_core::{AccountAddressType, ChainResult};
+use hyperlane_cosmwasm_interface::types::keccak256_hash;
+use k256::ecdsa::{signature::hazmat::PrehashSigner, Signature};
 
 use crate::{CosmosAddress, HyperlaneCosmosError};
 
+/// Ethermint (e.g. Evmos) ethsecp256k1 public key type URL for protobuf Any
+pub(crate) const ETHERMINT_PUBLIC_KEY_TYPE_URL: &str = "/ethermint.crypto.v1.ethsecp256k1.PubKey";
+/// Injective ethsecp256k1 public key type URL for protobuf Any
+pub(crate) const INJECTIVE_PUBLIC_KEY_TYPE_URL: &str =
+    "/injective.crypto.v1beta1.ethsecp256k1.PubKey";
+
 #[derive(Clone, Debug)]
 /// Signer for cosmos chain
 pub struct Signer {
@@ -13,6 +24,11 @@ pub struct Signer {
     pub address: String,
     /// address prefix
     pub prefix: String,
+    /// the type of account address, Ethereum style accounts sign with
+    /// ethsecp256k1 keys
+    pub account_address_type: AccountAddressType,
+    /// type URL of the public key of ethsecp256k1 keys
+    public_key_type_url: String,
     private_key: Vec<u8>,
 }
 
@@ -23,20 +39,33 @@ impl Signer {
     /// * `private_key` - private key for signer
     /// * `prefix` - prefix for signer address
     /// * `account_address_type` - the type of account address used for signer
+    /// * `public_key_type_url` - type URL of the public key of ethsecp256k1
+    ///   keys, defaulting to Injective's on Injective and to Ethermint's
+    ///   otherwise
     pub fn new(
         private_key: Vec<u8>,
         prefix: String,
         account_address_type: &AccountAddressType,
+        public_key_type_url: Option<String>,
     ) -> ChainResult<Self> {
         let address =
             CosmosAddress::from_privkey(&private_key, &prefix, account_address_type)?.address();
         let signing_key = Self::build_signing_key(&private_key)?;
         let public_key = signing_key.public_key();
+        let public_key_type_url = public_key_type_url.unwrap_or_else(|| {
+            match prefix.as_str() {
+                "inj" => INJECTIVE_PUBLIC_KEY_TYPE_URL,
+                _ => ETHERMINT_PUBLIC_KEY_TYPE_URL,
+            }
+            .to_owned()
+        });
         Ok(Self {
             public_key,
             private_key,
             address,
             prefix,
+            account_address_type: account_address_type.clone(),
+            public_key_type_url,
         })
     }
 
@@ -50,4 +79,74 @@ impl Signer {
         Ok(SigningKey::from_slice(private_key.as_slice())
             .map_err(Into::<HyperlaneCosmosError>::into)?)
     }
+
+    /// Signer info of a transaction signed in direct mode at `sequence`
+    pub fn signer_info(&self, sequence: SequenceNumber) -> SignerInfo {
+        let mut signer_info = SignerInfo::single_direct(Some(self.public_key), sequence);
+        if let AccountAddressType::Ethereum = self.account_address_type {
+            // ethsecp256k1 public keys are encoded like secp256k1 ones, only
+            // their type URL differs
+            let public_key = proto::cosmos::crypto::secp256k1::PubKey {
+                key: self.public_key.to_bytes(),
+            };
+            signer_info.public_key = Some(SignerPublicKey::Any(Any {
+                type_url: self.public_key_type_url.clone(),
+                value: public_key.encode_to_vec(),
+            }));
+        }
+        signer_info
+    }
+
+    /// Sign the bytes of a SignDoc. ethsecp256k1 keys sign their Keccak256
+    /// hash rather than their SHA256 hash.
+    pub fn sign(&self, sign_doc_bytes: &[u8]) -> ChainResult<Vec<u8>> {
+        let signature = match self.account_address_type {
+            AccountAddressType::Bitcoin => self
+                .signing_key()?
+                .sign(sign_doc_bytes)
+                .map_err(Into::<HyperlaneCosmosError>::into)?
+                .to_vec(),
+            AccountAddressType::Ethereum => {
+                let signing_key = k256::ecdsa::SigningKey::from_slice(&self.private_key)
+                    .map_err(|err| HyperlaneCosmosError::SignerInfoError(err.to_string()))?;
+                let hash = keccak256_hash(sign_doc_bytes);
+                let signature: Signature = signing_key
+                    .sign_prehash(hash.as_slice())
+                    .map_err(|err| HyperlaneCosmosError::SignerInfoError(err.to_string()))?;
+                signature.to_bytes().to_vec()
+            }
+        };
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use k256::ecdsa::{signature::hazmat::PrehashVerifier, VerifyingKey};
+
+    use super::*;
+
+    #[test]
+    fn test_ethsecp256k1_signer() {
+        let signer = Signer::new(
+            vec![0x11; 32],
+            "evmos".to_owned(),
+            &AccountAddressType::Ethereum,
+            None,
+        )
+        .unwrap();
+
+        let signer_info = signer.signer_info(1);
+        let Some(SignerPublicKey::Any(public_key)) = signer_info.public_key else {
+            panic!("Expected an ethsecp256k1 public key");
+        };
+        assert_eq!(public_key.type_url, ETHERMINT_PUBLIC_KEY_TYPE_URL);
+
+        let sign_doc_bytes = b"sign doc";
+        let signature = Signature::from_slice(&signer.sign(sign_doc_bytes).unwrap()).unwrap();
+        let verifying_key = VerifyingKey::from_sec1_bytes(&signer.public_key.to_bytes()).unwrap();
+        verifying_key
+            .verify_prehash(keccak256_hash(sign_doc_bytes).as_slice(), &signature)
+            .unwrap();
+    }
 }
@@ -3,7 +3,13 @@ use std::str::FromStr;
 use derive_new::new;
 use url::Url;
 
-use hyperlane_core::{config::OperationBatchConfig, ChainCommunicationError, FixedPointNumber};
+use hyperlane_core::{
+    config::{OperationBatchConfig, ProxyConf, RpcHeadersConf, RpcTlsConf},
+    rpc_clients::RpcUsageMetrics,
+    ChainCommunicationError, FixedPointNumber,
+};
+
+use crate::Signer;
 
 /// Cosmos connection configuration
 #[derive(Debug, Clone)]
@@ -30,6 +36,21 @@ pub struct ConnectionConf {
     pub operation_batch: OperationBatchConfig,
     /// Native Token
     native_token: NativeToken,
+    /// Address of an account that granted the signer an allowance through the
+    /// `feegrant` module. Transaction fees are deducted from this allowance.
+    fee_granter: Option<String>,
+    /// Account paying transaction fees instead of the signer. It co-signs every
+    /// transaction.
+    fee_payer: Option<Signer>,
+    /// Headers sent as metadata with every gRPC request
+    rpc_headers: RpcHeadersConf,
+    /// The HTTP proxy RPC and gRPC requests are routed through
+    proxy: Option<ProxyConf>,
+    /// The client certificate presented to gRPC endpoints requiring mutual
+    /// TLS. The tendermint RPC client can't present one.
+    rpc_tls: Option<RpcTlsConf>,
+    /// Where the RPC and gRPC requests made are recorded, if anywhere
+    rpc_usage: Option<RpcUsageMetrics>,
 }
 
 /// Untyped cosmos amount
@@ -130,6 +151,42 @@ impl ConnectionConf {
         self.contract_address_bytes
     }
 
+    /// Get the address of the account granting fee allowances to the signer
+    pub fn get_fee_granter(&self) -> Option<&str> {
+        self.fee_granter.as_deref()
+    }
+
+    /// Get the account paying transaction fees instead of the signer
+    pub fn get_fee_payer(&self) -> Option<&Signer> {
+        self.fee_payer.as_ref()
+    }
+
+    /// Get the headers sent with every gRPC request
+    pub fn get_rpc_headers(&self) -> &RpcHeadersConf {
+        &self.rpc_headers
+    }
+
+    /// Get the proxy requests are routed through
+    pub fn get_proxy(&self) -> Option<&ProxyConf> {
+        self.proxy.as_ref()
+    }
+
+    /// Get the client certificate presented to gRPC endpoints
+    pub fn get_rpc_tls(&self) -> Option<&RpcTlsConf> {
+        self.rpc_tls.as_ref()
+    }
+
+    /// Get where the RPC and gRPC requests made are recorded
+    pub fn get_rpc_usage(&self) -> Option<&RpcUsageMetrics> {
+        self.rpc_usage.as_ref()
+    }
+
+    /// Record the RPC and gRPC requests made in `rpc_usage`
+    pub fn with_rpc_usage(mut self, rpc_usage: RpcUsageMetrics) -> Self {
+        self.rpc_usage = Some(rpc_usage);
+        self
+    }
+
     /// Create a new connection configuration
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -142,6 +199,11 @@ impl ConnectionConf {
         contract_address_bytes: usize,
         operation_batch: OperationBatchConfig,
         native_token: NativeToken,
+        fee_granter: Option<String>,
+        fee_payer: Option<Signer>,
+        rpc_headers: RpcHeadersConf,
+        proxy: Option<ProxyConf>,
+        rpc_tls: Option<RpcTlsConf>,
     ) -> Self {
         Self {
             grpc_urls,
@@ -153,6 +215,12 @@ impl ConnectionConf {
             contract_address_bytes,
             operation_batch,
             native_token,
+            fee_granter,
+            fee_payer,
+            rpc_headers,
+            proxy,
+            rpc_tls,
+            rpc_usage: None,
         }
     }
 }
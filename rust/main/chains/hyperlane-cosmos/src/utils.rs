@@ -2,9 +2,7 @@ use std::fmt::Debug;
 use std::num::NonZeroU64;
 use std::ops::RangeInclusive;
 
-use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use futures::future;
-use once_cell::sync::Lazy;
 use tendermint::abci::EventAttribute;
 use tendermint::hash::Algorithm;
 use tendermint::Hash;
@@ -20,9 +18,6 @@ type FutureChainResults<T> = Vec<JoinHandle<(ChainResult<Vec<(T, LogMeta)>>, u32
 
 /// The event attribute key for the contract address.
 pub(crate) const CONTRACT_ADDRESS_ATTRIBUTE_KEY: &str = "_contract_address";
-/// Base64 encoded version of the contract address attribute key, i.e.
-pub(crate) static CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64: Lazy<String> =
-    Lazy::new(|| BASE64.encode(CONTRACT_ADDRESS_ATTRIBUTE_KEY));
 
 /// Given a lag, returns the block height at the moment.
 /// If the lag is None, a block height of None is given, indicating that the
@@ -1,19 +1,27 @@
+use std::ops::RangeInclusive;
+
 use async_trait::async_trait;
 
 use cosmrs::proto::cosmos::base::abci::v1beta1::TxResponse;
 use hyperlane_core::{
     Announcement, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
-    HyperlaneProvider, SignedType, TxOutcome, ValidatorAnnounce, H160, H256, U256,
+    HyperlaneProvider, Indexed, Indexer, LogMeta, SequenceAwareIndexer, SignedType, TxOutcome,
+    ValidatorAnnounce, ValidatorAnnouncement, H160, H256, H512, U256,
 };
+use tendermint::abci::EventAttribute;
+use tracing::instrument;
 
 use crate::{
+    events::{AttributeSchema, EventSchema},
     grpc::WasmProvider,
     payloads::validator_announce::{
         self, AnnouncementRequest, AnnouncementRequestInner, GetAnnounceStorageLocationsRequest,
         GetAnnounceStorageLocationsRequestInner,
     },
+    rpc::{CosmosWasmRpcProvider, ParsedEvent, WasmRpcProvider},
     signers::Signer,
     types::tx_response_to_outcome,
+    utils::{execute_and_parse_log_futures, parse_logs_in_range, parse_logs_in_tx},
     ConnectionConf, CosmosProvider,
 };
 
@@ -117,3 +125,133 @@ impl ValidatorAnnounce for CosmosValidatorAnnounce {
         Some(0u64.into())
     }
 }
+
+static VALIDATOR_ANNOUNCEMENT_SCHEMA: EventSchema = EventSchema {
+    event: "validator announcement",
+    attributes: &[
+        AttributeSchema {
+            field: "validator",
+            keys: &["validator"],
+        },
+        AttributeSchema {
+            field: "storage_location",
+            keys: &["storage_location"],
+        },
+    ],
+};
+
+/// Struct that retrieves the announcements of a ValidatorAnnounce contract on
+/// some Cosmos chain
+#[derive(Debug, Clone)]
+pub struct CosmosValidatorAnnounceIndexer {
+    provider: Box<CosmosWasmRpcProvider>,
+}
+
+impl CosmosValidatorAnnounceIndexer {
+    const VALIDATOR_ANNOUNCEMENT_EVENT_TYPE: &'static str = "hpl_validator_announce::announce";
+
+    /// create a new instance of CosmosValidatorAnnounceIndexer
+    pub fn new(
+        conf: ConnectionConf,
+        locator: ContractLocator,
+        reorg_period: u32,
+    ) -> ChainResult<Self> {
+        let provider = CosmosWasmRpcProvider::new(
+            conf,
+            locator,
+            Self::VALIDATOR_ANNOUNCEMENT_EVENT_TYPE.into(),
+            reorg_period,
+        )?;
+
+        Ok(Self {
+            provider: Box::new(provider),
+        })
+    }
+
+    #[instrument(err)]
+    fn validator_announcement_parser(
+        attrs: &Vec<EventAttribute>,
+    ) -> ChainResult<ParsedEvent<ValidatorAnnouncement>> {
+        let event = VALIDATOR_ANNOUNCEMENT_SCHEMA.decode(attrs)?;
+        let announcement = ValidatorAnnouncement {
+            validator: event.parse_h160("validator")?.into(),
+            storage_location: event.value("storage_location")?.to_owned(),
+        };
+
+        Ok(event.into_parsed(announcement))
+    }
+}
+
+#[async_trait]
+impl Indexer<ValidatorAnnouncement> for CosmosValidatorAnnounceIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<ValidatorAnnouncement>, LogMeta)>> {
+        let logs_futures = parse_logs_in_range(
+            range,
+            self.provider.clone(),
+            Self::validator_announcement_parser,
+            "ValidatorAnnouncementCursor",
+        );
+
+        execute_and_parse_log_futures(logs_futures).await
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.provider.get_finalized_block_number().await
+    }
+
+    async fn fetch_logs_by_tx_hash(
+        &self,
+        tx_hash: H512,
+    ) -> ChainResult<Vec<(Indexed<ValidatorAnnouncement>, LogMeta)>> {
+        parse_logs_in_tx(
+            &tx_hash.into(),
+            self.provider.clone(),
+            Self::validator_announcement_parser,
+            "ValidatorAnnouncementReceiver",
+        )
+        .await
+        .map(|v| v.into_iter().map(|(m, l)| (m.into(), l)).collect())
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<ValidatorAnnouncement> for CosmosValidatorAnnounceIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = self.get_finalized_block_number().await?;
+        Ok((None, tip))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::event_attributes_from_str;
+
+    use super::*;
+
+    #[test]
+    fn test_validator_announcement_parser() {
+        let attrs = event_attributes_from_str(
+            r#"[{"key":"_contract_address","value":"neutron17w4q6efzym3p4c6umyp4cjf2ustjtmwfqdhd7rt2fpcpk9fmjzsq0kj0f9","index":true},{"key":"sender","value":"neutron1vdazwhwkh9wy6ue66pjpuvrxcrywv2ww956dq6ls2gh0n7t9f5rs2hydt2","index":true},{"key":"validator","value":"42b6de2edbaa62c2ea2309ad85d20b3e37d38acf","index":true},{"key":"storage_location","value":"s3://hyperlane-validator-signatures-neutron/us-east-1","index":true}]"#,
+        );
+        let parsed_event =
+            CosmosValidatorAnnounceIndexer::validator_announcement_parser(&attrs).unwrap();
+
+        assert_eq!(
+            parsed_event,
+            ParsedEvent::new(
+                "neutron17w4q6efzym3p4c6umyp4cjf2ustjtmwfqdhd7rt2fpcpk9fmjzsq0kj0f9".into(),
+                ValidatorAnnouncement {
+                    validator: H160::from_slice(
+                        &hex::decode("42b6de2edbaa62c2ea2309ad85d20b3e37d38acf").unwrap()
+                    )
+                    .into(),
+                    storage_location: "s3://hyperlane-validator-signatures-neutron/us-east-1"
+                        .to_owned(),
+                }
+            )
+        );
+    }
+}
@@ -1,6 +1,17 @@
-use hyperlane_core::{config::OperationBatchConfig, U256};
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+
+use ethers::abi::{self, Token};
+use ethers::types::Address;
+use ethers::utils::id;
+use hyperlane_core::{
+    config::{OperationBatchConfig, ProxyConf, RpcHeadersConf, RpcTlsConf},
+    H256, U256,
+};
 use url::Url;
 
+use crate::RpcTraceConf;
+
 /// Ethereum RPC connection configuration
 #[derive(Debug, Clone)]
 pub enum RpcConnectionConf {
@@ -24,6 +35,14 @@ pub enum RpcConnectionConf {
         /// Url to connect to
         url: Url,
     },
+    /// A websocket connection, falling back to an HTTP fallback set while
+    /// it is down.
+    WsHttpHybrid {
+        /// Websocket url to connect to
+        ws_url: Url,
+        /// List of HTTP urls to fall back to in order of priority
+        http_urls: Vec<Url>,
+    },
 }
 
 /// Ethereum connection configuration
@@ -35,6 +54,103 @@ pub struct ConnectionConf {
     pub transaction_overrides: TransactionOverrides,
     /// Operation batching configuration
     pub operation_batch: OperationBatchConfig,
+    /// How to trace `process` simulations which revert, to find the revert
+    /// reason of the inner call. Tracing requires an archive or tracing node,
+    /// so it is disabled if `None`.
+    pub revert_tracing: Option<RevertTraceMethod>,
+    /// How the finalized block is determined
+    pub finality_source: FinalitySource,
+    /// Request-per-second budgets of the HTTP RPC urls. The requests over an
+    /// url's budget are queued until it allows them.
+    pub requests_per_second: HashMap<Url, NonZeroU32>,
+    /// Whether transactions are sent to all the RPC urls of a fallback set at
+    /// once, the first to accept them winning, rather than to the best one
+    pub broadcast_transactions: bool,
+    /// The contract deliveries are routed through, if they don't call the
+    /// mailbox directly
+    pub processor: Option<ProcessorContract>,
+    /// Headers sent with every HTTP request. WebSockets only send the
+    /// authentication.
+    pub rpc_headers: RpcHeadersConf,
+    /// The proxy RPC requests are routed through. WebSockets can't be
+    /// proxied, so hybrid connections only use HTTP when it is set.
+    pub proxy: Option<ProxyConf>,
+    /// The client certificate presented to RPCs requiring mutual TLS.
+    /// WebSockets can't present one, so hybrid connections only use HTTP
+    /// when it is set.
+    pub rpc_tls: Option<RpcTlsConf>,
+    /// Whether the mailbox is a legacy (v2) one, which holds the merkle tree
+    /// itself. Indexing it requires the `legacy` feature.
+    pub legacy_mailbox: bool,
+    /// Whether the requests to the RPCs are recorded to or replayed from an
+    /// RPC trace, to reproduce incidents deterministically
+    pub rpc_trace: Option<RpcTraceConf>,
+}
+
+/// A contract routing deliveries to the mailbox, e.g. to refund or batch
+/// them, which the relayer calls instead of the mailbox
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessorContract {
+    /// Address of the processor contract
+    pub address: H256,
+    /// Name of the function delivering a message
+    pub function: String,
+    /// The arguments the function takes, in order
+    pub arguments: Vec<ProcessArgument>,
+}
+
+/// An argument of the delivery function of a processor contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessArgument {
+    /// The ISM metadata, as `bytes`
+    Metadata,
+    /// The message, as `bytes`
+    Message,
+    /// The address of the mailbox, as `address`
+    Mailbox,
+}
+
+impl ProcessorContract {
+    /// The calldata delivering `message` with `metadata` through the
+    /// processor
+    pub fn calldata(&self, mailbox: Address, message: &[u8], metadata: &[u8]) -> Vec<u8> {
+        let (types, tokens): (Vec<_>, Vec<_>) = self
+            .arguments
+            .iter()
+            .map(|argument| match argument {
+                ProcessArgument::Metadata => ("bytes", Token::Bytes(metadata.to_vec())),
+                ProcessArgument::Message => ("bytes", Token::Bytes(message.to_vec())),
+                ProcessArgument::Mailbox => ("address", Token::Address(mailbox)),
+            })
+            .unzip();
+        let signature = format!("{}({})", self.function, types.join(","));
+        let mut calldata = id(signature).to_vec();
+        calldata.extend(abi::encode(&tokens));
+        calldata
+    }
+}
+
+/// How the finalized block of a chain is determined
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FinalitySource {
+    /// The latest block minus the reorg period
+    #[default]
+    BlockDepth,
+    /// The block tagged `finalized` by the node, falling back to the block
+    /// depth if the node does not support the tag
+    FinalizedTag,
+    /// The block tagged `safe` by the node, falling back to the block depth
+    /// if the node does not support the tag
+    SafeTag,
+}
+
+/// The RPC method used to trace reverted calls
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevertTraceMethod {
+    /// `debug_traceCall` with the call tracer, served by geth-like nodes
+    DebugTraceCall,
+    /// `trace_call`, served by erigon and other parity-like nodes
+    TraceCall,
 }
 
 /// Ethereum transaction overrides.
@@ -52,3 +168,62 @@ pub struct TransactionOverrides {
     /// Max priority fee per gas to use for EIP-1559 transactions.
     pub max_priority_fee_per_gas: Option<U256>,
 }
+
+#[cfg(test)]
+mod test {
+    use ethers::abi::AbiEncode;
+
+    use crate::interfaces::i_mailbox::ProcessCall;
+
+    use super::*;
+
+    #[test]
+    fn test_processor_calldata() {
+        let mailbox = Address::from_low_u64_be(1);
+        let (message, metadata) = (vec![1; 77], vec![2; 130]);
+
+        // Routing through a processor with the mailbox layout only changes
+        // the target of the call
+        let processor = ProcessorContract {
+            address: H256::from_low_u64_be(2),
+            function: "process".to_owned(),
+            arguments: vec![ProcessArgument::Metadata, ProcessArgument::Message],
+        };
+        let process_call = ProcessCall {
+            metadata: metadata.clone().into(),
+            message: message.clone().into(),
+        };
+        assert_eq!(
+            processor.calldata(mailbox, &message, &metadata),
+            process_call.encode()
+        );
+
+        let processor = ProcessorContract {
+            function: "processFor".to_owned(),
+            arguments: vec![
+                ProcessArgument::Mailbox,
+                ProcessArgument::Message,
+                ProcessArgument::Metadata,
+            ],
+            ..processor
+        };
+        let calldata = processor.calldata(mailbox, &message, &metadata);
+        assert_eq!(calldata[..4], id("processFor(address,bytes,bytes)"));
+        assert_eq!(
+            abi::decode(
+                &[
+                    abi::ParamType::Address,
+                    abi::ParamType::Bytes,
+                    abi::ParamType::Bytes
+                ],
+                &calldata[4..]
+            )
+            .unwrap(),
+            vec![
+                Token::Address(mailbox),
+                Token::Bytes(message),
+                Token::Bytes(metadata)
+            ]
+        );
+    }
+}
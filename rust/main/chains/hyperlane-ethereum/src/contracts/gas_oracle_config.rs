@@ -0,0 +1,201 @@
+#![allow(missing_docs)]
+
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use ethers::prelude::Middleware;
+use ethers::types::{Address, ValueOrArray};
+use hyperlane_core::{
+    ChainResult, ContractLocator, GasOracleConfigUpdate, Indexed, Indexer, LogMeta,
+    SequenceAwareIndexer, H256, U256,
+};
+use tracing::instrument;
+
+use super::utils::{get_block_hash, get_finalized_block_number};
+use crate::interfaces::{
+    interchain_gas_paymaster::InterchainGasPaymaster as EthereumInterchainGasPaymasterEvents,
+    storage_gas_oracle::StorageGasOracle,
+};
+use crate::{BuildableWithProvider, ConnectionConf, FinalitySource};
+
+pub struct GasOracleConfigIndexerBuilder {
+    pub reorg_period: u32,
+    /// The gas oracles configured before the indexed range
+    pub gas_oracles: Vec<H256>,
+}
+
+#[async_trait]
+impl BuildableWithProvider for GasOracleConfigIndexerBuilder {
+    type Output = Box<dyn SequenceAwareIndexer<GasOracleConfigUpdate>>;
+    const NEEDS_SIGNER: bool = false;
+
+    async fn build_with_provider<M: Middleware + 'static>(
+        &self,
+        provider: M,
+        conn: &ConnectionConf,
+        locator: &ContractLocator,
+    ) -> Self::Output {
+        Box::new(EthereumGasOracleConfigIndexer::new(
+            Arc::new(provider),
+            locator,
+            self.reorg_period,
+            conn.finality_source,
+            &self.gas_oracles,
+        ))
+    }
+}
+
+#[derive(Debug)]
+/// Struct that retrieves the gas oracle config updates of an Ethereum
+/// InterchainGasPaymaster and of its storage gas oracles
+pub struct EthereumGasOracleConfigIndexer<M>
+where
+    M: Middleware,
+{
+    igp: Arc<EthereumInterchainGasPaymasterEvents<M>>,
+    gas_oracle: Arc<StorageGasOracle<M>>,
+    /// The gas oracles configured by the IGP so far, whose gas data is indexed
+    gas_oracles: RwLock<HashSet<Address>>,
+    provider: Arc<M>,
+    reorg_period: u32,
+    finality_source: FinalitySource,
+}
+
+impl<M> EthereumGasOracleConfigIndexer<M>
+where
+    M: Middleware + 'static,
+{
+    /// Create new EthereumGasOracleConfigIndexer, indexing the gas data of
+    /// `gas_oracles` and of the gas oracles configured from then on
+    pub fn new(
+        provider: Arc<M>,
+        locator: &ContractLocator,
+        reorg_period: u32,
+        finality_source: FinalitySource,
+        gas_oracles: &[H256],
+    ) -> Self {
+        Self {
+            igp: Arc::new(EthereumInterchainGasPaymasterEvents::new(
+                locator.address,
+                provider.clone(),
+            )),
+            // Only used for its event filter, which is restricted to the
+            // addresses of the gas oracles instead
+            gas_oracle: Arc::new(StorageGasOracle::new(locator.address, provider.clone())),
+            gas_oracles: RwLock::new(gas_oracles.iter().map(|&oracle| oracle.into()).collect()),
+            provider,
+            reorg_period,
+            finality_source,
+        }
+    }
+}
+
+#[async_trait]
+impl<M> Indexer<GasOracleConfigUpdate> for EthereumGasOracleConfigIndexer<M>
+where
+    M: Middleware + 'static,
+{
+    /// Note: This call may return duplicates depending on the provider used
+    #[instrument(err, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<GasOracleConfigUpdate>, LogMeta)>> {
+        let configs = self
+            .igp
+            .destination_gas_config_set_filter()
+            .from_block(*range.start())
+            .to_block(*range.end())
+            .query_with_meta()
+            .await?;
+
+        // The gas oracles are separate contracts, only known from the configs
+        // of the IGP. Those set before the indexed range were either passed
+        // in or found in earlier ranges.
+        let known = self.gas_oracles.read().unwrap().clone();
+        let new_oracles: HashSet<Address> = configs
+            .iter()
+            .map(|(log, _)| log.gas_oracle)
+            .filter(|oracle| !known.contains(oracle))
+            .collect();
+        let gas_oracles: Vec<Address> = known.union(&new_oracles).copied().collect();
+        let gas_data = if gas_oracles.is_empty() {
+            vec![]
+        } else {
+            self.gas_oracle
+                .remote_gas_data_set_filter()
+                .address(ValueOrArray::Array(gas_oracles))
+                .from_block(*range.start())
+                .to_block(*range.end())
+                .query_with_meta()
+                .await?
+        };
+
+        // The gas data of an oracle is usually set before the IGP is configured
+        // to use it, so the current gas data of newly configured oracles is
+        // read rather than looked for in earlier ranges
+        let mut current_gas_data = vec![];
+        for (log, log_meta) in &configs {
+            if !new_oracles.contains(&log.gas_oracle) {
+                continue;
+            }
+            let (token_exchange_rate, gas_price) =
+                StorageGasOracle::new(log.gas_oracle, self.provider.clone())
+                    .get_exchange_rate_and_gas_price(log.remote_domain)
+                    .call()
+                    .await?;
+            let update = GasOracleConfigUpdate::RemoteGasData {
+                gas_oracle: log.gas_oracle.into(),
+                remote_domain: log.remote_domain,
+                token_exchange_rate: U256::from(token_exchange_rate),
+                gas_price: U256::from(gas_price),
+            };
+            current_gas_data.push((Indexed::new(update), log_meta.clone().into()));
+        }
+        self.gas_oracles.write().unwrap().extend(new_oracles);
+
+        let configs = configs.into_iter().map(|(log, log_meta)| {
+            let update = GasOracleConfigUpdate::DestinationGasConfig {
+                remote_domain: log.remote_domain,
+                gas_oracle: log.gas_oracle.into(),
+                gas_overhead: U256::from(log.gas_overhead),
+            };
+            (Indexed::new(update), log_meta.into())
+        });
+        let gas_data = gas_data.into_iter().map(|(log, log_meta)| {
+            let update = GasOracleConfigUpdate::RemoteGasData {
+                gas_oracle: log_meta.address.into(),
+                remote_domain: log.remote_domain,
+                token_exchange_rate: U256::from(log.token_exchange_rate),
+                gas_price: U256::from(log.gas_price),
+            };
+            (Indexed::new(update), log_meta.into())
+        });
+        Ok(configs.chain(current_gas_data).chain(gas_data).collect())
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        get_finalized_block_number(&*self.provider, self.finality_source, self.reorg_period).await
+    }
+
+    async fn get_block_hash(&self, height: u32) -> ChainResult<Option<H256>> {
+        get_block_hash(&*self.provider, height).await
+    }
+}
+
+#[async_trait]
+impl<M> SequenceAwareIndexer<GasOracleConfigUpdate> for EthereumGasOracleConfigIndexer<M>
+where
+    M: Middleware + 'static,
+{
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        // Config updates aren't sequenced, they are only indexed by block
+        let tip = self.get_finalized_block_number().await?;
+        Ok((None, tip))
+    }
+}
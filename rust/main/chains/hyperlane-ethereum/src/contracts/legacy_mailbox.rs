@@ -0,0 +1,207 @@
+#![allow(missing_docs)]
+
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ethers::prelude::Middleware;
+use hyperlane_core::{
+    ChainResult, ContractLocator, HyperlaneMessage, Indexed, Indexer, LogMeta, MerkleTreeInsertion,
+    SequenceAwareIndexer, H256, H512,
+};
+use tracing::instrument;
+
+use crate::interfaces::merkle_tree_hook::MerkleTreeHook as MerkleTreeHookContract;
+use crate::{BuildableWithProvider, ConnectionConf, EthereumMailboxIndexer};
+
+/// Builder for indexers of the messages of a legacy (v2) mailbox
+pub struct LegacySequenceIndexerBuilder {
+    pub reorg_period: u32,
+}
+
+#[async_trait]
+impl BuildableWithProvider for LegacySequenceIndexerBuilder {
+    type Output = Box<dyn SequenceAwareIndexer<HyperlaneMessage>>;
+    const NEEDS_SIGNER: bool = false;
+
+    async fn build_with_provider<M: Middleware + 'static>(
+        &self,
+        provider: M,
+        conn: &ConnectionConf,
+        locator: &ContractLocator,
+    ) -> Self::Output {
+        Box::new(EthereumLegacyMailboxIndexer::new(
+            Arc::new(provider),
+            conn,
+            locator,
+            self.reorg_period,
+        ))
+    }
+}
+
+/// Builder for indexers of the merkle tree insertions of a legacy (v2)
+/// mailbox
+pub struct LegacyMerkleTreeHookIndexerBuilder {
+    pub reorg_period: u32,
+}
+
+#[async_trait]
+impl BuildableWithProvider for LegacyMerkleTreeHookIndexerBuilder {
+    type Output = Box<dyn SequenceAwareIndexer<MerkleTreeInsertion>>;
+    const NEEDS_SIGNER: bool = false;
+
+    async fn build_with_provider<M: Middleware + 'static>(
+        &self,
+        provider: M,
+        conn: &ConnectionConf,
+        locator: &ContractLocator,
+    ) -> Self::Output {
+        Box::new(EthereumLegacyMailboxIndexer::new(
+            Arc::new(provider),
+            conn,
+            locator,
+            self.reorg_period,
+        ))
+    }
+}
+
+/// Retrieves the messages of a legacy (v2) mailbox, and the insertions into
+/// its merkle tree. Legacy mailboxes emit the same `Dispatch` event as
+/// current ones, but count their messages with `count()` rather than
+/// `nonce()`, and insert every message into their own merkle tree at the
+/// index of its nonce rather than through a merkle tree hook.
+#[derive(Debug, Clone)]
+pub struct EthereumLegacyMailboxIndexer<M>
+where
+    M: Middleware,
+{
+    dispatches: EthereumMailboxIndexer<M>,
+    /// The legacy mailbox, whose `count()` has the same signature as that of
+    /// merkle tree hooks
+    tree: Arc<MerkleTreeHookContract<M>>,
+}
+
+impl<M> EthereumLegacyMailboxIndexer<M>
+where
+    M: Middleware + 'static,
+{
+    /// Create new EthereumLegacyMailboxIndexer
+    pub fn new(
+        provider: Arc<M>,
+        conn: &ConnectionConf,
+        locator: &ContractLocator,
+        reorg_period: u32,
+    ) -> Self {
+        Self {
+            tree: Arc::new(MerkleTreeHookContract::new(
+                locator.address,
+                provider.clone(),
+            )),
+            dispatches: EthereumMailboxIndexer::new(
+                provider,
+                locator,
+                reorg_period,
+                conn.finality_source,
+            ),
+        }
+    }
+
+    async fn latest_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<HyperlaneMessage>::get_finalized_block_number(&self.dispatches).await?;
+        let count = self.tree.count().block(u64::from(tip)).call().await?;
+        Ok((Some(count), tip))
+    }
+}
+
+/// The insertion of a message into the merkle tree of a legacy mailbox
+fn insertion(
+    (message, meta): (Indexed<HyperlaneMessage>, LogMeta),
+) -> (Indexed<MerkleTreeInsertion>, LogMeta) {
+    let message = message.inner();
+    (
+        MerkleTreeInsertion::new(message.nonce, message.id()).into(),
+        meta,
+    )
+}
+
+#[async_trait]
+impl<M> Indexer<HyperlaneMessage> for EthereumLegacyMailboxIndexer<M>
+where
+    M: Middleware + 'static,
+{
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        Indexer::<HyperlaneMessage>::get_finalized_block_number(&self.dispatches).await
+    }
+
+    async fn get_block_hash(&self, height: u32) -> ChainResult<Option<H256>> {
+        Indexer::<HyperlaneMessage>::get_block_hash(&self.dispatches, height).await
+    }
+
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
+        Indexer::<HyperlaneMessage>::fetch_logs_in_range(&self.dispatches, range).await
+    }
+
+    async fn fetch_logs_by_tx_hash(
+        &self,
+        tx_hash: H512,
+    ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
+        Indexer::<HyperlaneMessage>::fetch_logs_by_tx_hash(&self.dispatches, tx_hash).await
+    }
+}
+
+#[async_trait]
+impl<M> SequenceAwareIndexer<HyperlaneMessage> for EthereumLegacyMailboxIndexer<M>
+where
+    M: Middleware + 'static,
+{
+    #[instrument(err, skip(self), ret)]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        self.latest_count_and_tip().await
+    }
+}
+
+#[async_trait]
+impl<M> Indexer<MerkleTreeInsertion> for EthereumLegacyMailboxIndexer<M>
+where
+    M: Middleware + 'static,
+{
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        Indexer::<HyperlaneMessage>::get_finalized_block_number(&self.dispatches).await
+    }
+
+    async fn get_block_hash(&self, height: u32) -> ChainResult<Option<H256>> {
+        Indexer::<HyperlaneMessage>::get_block_hash(&self.dispatches, height).await
+    }
+
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<MerkleTreeInsertion>, LogMeta)>> {
+        let dispatches =
+            Indexer::<HyperlaneMessage>::fetch_logs_in_range(&self.dispatches, range).await?;
+        Ok(dispatches.into_iter().map(insertion).collect())
+    }
+
+    async fn fetch_logs_by_tx_hash(
+        &self,
+        tx_hash: H512,
+    ) -> ChainResult<Vec<(Indexed<MerkleTreeInsertion>, LogMeta)>> {
+        let dispatches =
+            Indexer::<HyperlaneMessage>::fetch_logs_by_tx_hash(&self.dispatches, tx_hash).await?;
+        Ok(dispatches.into_iter().map(insertion).collect())
+    }
+}
+
+#[async_trait]
+impl<M> SequenceAwareIndexer<MerkleTreeInsertion> for EthereumLegacyMailboxIndexer<M>
+where
+    M: Middleware + 'static,
+{
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        self.latest_count_and_tip().await
+    }
+}
@@ -16,13 +16,14 @@ use futures_util::future::join_all;
 use hyperlane_core::rpc_clients::call_and_retry_indefinitely;
 use hyperlane_core::{BatchResult, QueueOperation, H512};
 use itertools::Itertools;
-use tracing::instrument;
+use tracing::{instrument, warn};
 
 use hyperlane_core::{
     utils::bytes_to_hex, BatchItem, ChainCommunicationError, ChainResult, ContractLocator,
     HyperlaneAbi, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
-    HyperlaneProtocolError, HyperlaneProvider, Indexed, Indexer, LogMeta, Mailbox,
-    RawHyperlaneMessage, SequenceAwareIndexer, TxCostEstimate, TxOutcome, H160, H256, U256,
+    HyperlaneMessageRef, HyperlaneProtocolError, HyperlaneProvider, IndexError, Indexed, Indexer,
+    LogMeta, Mailbox, RawHyperlaneMessage, SequenceAwareIndexer, SkippedLog, TxCostEstimate,
+    TxOutcome, TxSubmitter, TxSubmitterConf, H160, H256, U256,
 };
 
 use crate::error::HyperlaneEthereumError;
@@ -31,11 +32,15 @@ use crate::interfaces::i_mailbox::{
     IMailbox as EthereumMailboxInternal, ProcessCall, IMAILBOX_ABI,
 };
 use crate::interfaces::mailbox::DispatchFilter;
-use crate::tx::{call_with_lag, fill_tx_gas_params, report_tx};
-use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider, TransactionOverrides};
+use crate::interfaces::op_gas_price_oracle::OpGasPriceOracle;
+use crate::tx::{call_with_lag, fill_tx_gas_params, EthereumTxAdapter};
+use crate::{
+    BuildableWithProvider, ConnectionConf, EthereumProvider, FinalitySource, TransactionOverrides,
+};
 
 use super::multicall::{self, build_multicall};
-use super::utils::fetch_raw_logs_and_meta;
+use super::revert::trace_revert;
+use super::utils::{fetch_raw_logs_and_meta, get_block_hash, get_finalized_block_number};
 
 impl<M> std::fmt::Display for EthereumMailboxInternal<M>
 where
@@ -58,13 +63,14 @@ impl BuildableWithProvider for SequenceIndexerBuilder {
     async fn build_with_provider<M: Middleware + 'static>(
         &self,
         provider: M,
-        _conn: &ConnectionConf,
+        conn: &ConnectionConf,
         locator: &ContractLocator,
     ) -> Self::Output {
         Box::new(EthereumMailboxIndexer::new(
             Arc::new(provider),
             locator,
             self.reorg_period,
+            conn.finality_source,
         ))
     }
 }
@@ -81,13 +87,14 @@ impl BuildableWithProvider for DeliveryIndexerBuilder {
     async fn build_with_provider<M: Middleware + 'static>(
         &self,
         provider: M,
-        _conn: &ConnectionConf,
+        conn: &ConnectionConf,
         locator: &ContractLocator,
     ) -> Self::Output {
         Box::new(EthereumMailboxIndexer::new(
             Arc::new(provider),
             locator,
             self.reorg_period,
+            conn.finality_source,
         ))
     }
 }
@@ -101,6 +108,7 @@ where
     contract: Arc<EthereumMailboxInternal<M>>,
     provider: Arc<M>,
     reorg_period: u32,
+    finality_source: FinalitySource,
 }
 
 impl<M> EthereumMailboxIndexer<M>
@@ -108,7 +116,12 @@ where
     M: Middleware + 'static,
 {
     /// Create new EthereumMailboxIndexer
-    pub fn new(provider: Arc<M>, locator: &ContractLocator, reorg_period: u32) -> Self {
+    pub fn new(
+        provider: Arc<M>,
+        locator: &ContractLocator,
+        reorg_period: u32,
+        finality_source: FinalitySource,
+    ) -> Self {
         let contract = Arc::new(EthereumMailboxInternal::new(
             locator.address,
             provider.clone(),
@@ -117,21 +130,71 @@ where
             contract,
             provider,
             reorg_period,
+            finality_source,
         }
     }
 
     #[instrument(level = "debug", err, ret, skip(self))]
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        get_finalized_block_number(&*self.provider, self.finality_source, self.reorg_period).await
+    }
+
+    async fn query_dispatches(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(DispatchFilter, LogMeta)>> {
         Ok(self
-            .provider
-            .get_block_number()
-            .await
-            .map_err(ChainCommunicationError::from_other)?
-            .as_u32()
-            .saturating_sub(self.reorg_period))
+            .contract
+            .dispatch_filter()
+            .from_block(*range.start())
+            .to_block(*range.end())
+            .query_with_meta()
+            .await?
+            .into_iter()
+            .map(|(event, meta)| (event, meta.into()))
+            .collect())
     }
 }
 
+/// Decode the message of a dispatch log. A malformed message won't decode
+/// however often it is fetched again.
+fn decode_dispatched_message(raw: &[u8]) -> Result<HyperlaneMessage, IndexError> {
+    HyperlaneMessageRef::new(raw)
+        .map(HyperlaneMessageRef::into_owned)
+        .map_err(|err| IndexError::MalformedLog {
+            log: "dispatch",
+            reason: err.to_string(),
+        })
+}
+
+/// Reads the nonce of a dispatched message which couldn't be decoded, if the
+/// message is long enough to hold it
+fn malformed_message_nonce(raw: &[u8]) -> Option<u32> {
+    let nonce = raw.get(1..5)?.try_into().ok()?;
+    Some(u32::from_be_bytes(nonce))
+}
+
+/// Decodes the messages of dispatch logs, skipping the malformed ones
+fn decode_dispatches(
+    dispatches: Vec<(DispatchFilter, LogMeta)>,
+) -> (Vec<(Indexed<HyperlaneMessage>, LogMeta)>, Vec<SkippedLog>) {
+    let mut messages = vec![];
+    let mut skipped = vec![];
+    for (event, meta) in dispatches {
+        match decode_dispatched_message(&event.message) {
+            Ok(message) => messages.push((message.into(), meta)),
+            Err(err) => {
+                warn!(?err, ?meta, "Skipping malformed dispatch log");
+                skipped.push(SkippedLog::new(
+                    malformed_message_nonce(&event.message),
+                    meta,
+                ));
+            }
+        }
+    }
+    (messages, skipped)
+}
+
 #[async_trait]
 impl<M> Indexer<HyperlaneMessage> for EthereumMailboxIndexer<M>
 where
@@ -141,6 +204,10 @@ where
         self.get_finalized_block_number().await
     }
 
+    async fn get_block_hash(&self, height: u32) -> ChainResult<Option<H256>> {
+        get_block_hash(&*self.provider, height).await
+    }
+
     /// Note: This call may return duplicates depending on the provider used
     #[instrument(err, skip(self))]
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
@@ -149,25 +216,31 @@ where
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
         let mut events: Vec<(Indexed<HyperlaneMessage>, LogMeta)> = self
-            .contract
-            .dispatch_filter()
-            .from_block(*range.start())
-            .to_block(*range.end())
-            .query_with_meta()
+            .query_dispatches(range)
             .await?
             .into_iter()
             .map(|(event, meta)| {
-                (
-                    HyperlaneMessage::from(event.message.to_vec()).into(),
-                    meta.into(),
-                )
+                let message = decode_dispatched_message(&event.message)?;
+                Ok((message.into(), meta))
             })
-            .collect();
+            .collect::<ChainResult<_>>()?;
 
         events.sort_by(|a, b| a.0.inner().nonce.cmp(&b.0.inner().nonce));
         Ok(events)
     }
 
+    /// Note: This call may return duplicates depending on the provider used
+    #[instrument(err, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn fetch_logs_in_range_skipping_malformed(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<(Vec<(Indexed<HyperlaneMessage>, LogMeta)>, Vec<SkippedLog>)> {
+        let (mut events, skipped) = decode_dispatches(self.query_dispatches(range).await?);
+        events.sort_by(|a, b| a.0.inner().nonce.cmp(&b.0.inner().nonce));
+        Ok((events, skipped))
+    }
+
     async fn fetch_logs_by_tx_hash(
         &self,
         tx_hash: H512,
@@ -180,16 +253,9 @@ where
             })
         })
         .await;
-        let logs = raw_logs_and_meta
-            .into_iter()
-            .map(|(log, log_meta)| {
-                (
-                    HyperlaneMessage::from(log.message.to_vec()).into(),
-                    log_meta,
-                )
-            })
-            .collect();
-        Ok(logs)
+        // The other messages of the transaction are still worth indexing
+        let (messages, _) = decode_dispatches(raw_logs_and_meta);
+        Ok(messages)
     }
 }
 
@@ -205,6 +271,11 @@ where
         let sequence = self.contract.nonce().block(u64::from(tip)).call().await?;
         Ok((Some(sequence), tip))
     }
+
+    async fn sequence_count_at_block(&self, block: u32) -> ChainResult<Option<u32>> {
+        let sequence = self.contract.nonce().block(u64::from(block)).call().await?;
+        Ok(Some(sequence))
+    }
 }
 
 #[async_trait]
@@ -216,6 +287,10 @@ where
         self.get_finalized_block_number().await
     }
 
+    async fn get_block_hash(&self, height: u32) -> ChainResult<Option<H256>> {
+        get_block_hash(&*self.provider, height).await
+    }
+
     /// Note: This call may return duplicates depending on the provider used
     #[instrument(err, skip(self))]
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
@@ -276,6 +351,7 @@ where
     domain: HyperlaneDomain,
     provider: Arc<M>,
     arbitrum_node_interface: Option<Arc<ArbitrumNodeInterface<M>>>,
+    op_gas_price_oracle: Option<Arc<OpGasPriceOracle<M>>>,
     conn: ConnectionConf,
 }
 
@@ -296,6 +372,17 @@ where
                 provider.clone(),
             ))
         });
+        // OP stack chains charge a fee for posting the transaction data to L1
+        // on top of the gas, quoted by the GasPriceOracle predeploy.
+        // See https://docs.optimism.io/stack/transactions/fees#l1-data-fee
+        let op_gas_price_oracle = locator.domain.is_op_stack().then(|| {
+            Arc::new(OpGasPriceOracle::new(
+                "0x420000000000000000000000000000000000000F"
+                    .parse::<H160>()
+                    .unwrap(),
+                provider.clone(),
+            ))
+        });
 
         Self {
             contract: Arc::new(EthereumMailboxInternal::new(
@@ -305,10 +392,62 @@ where
             domain: locator.domain.clone(),
             provider,
             arbitrum_node_interface,
+            op_gas_price_oracle,
             conn: conn.clone(),
         }
     }
 
+    /// The fee for posting the data of `contract_call` to L1 on rollups, which
+    /// its estimated gas doesn't account for
+    async fn estimate_l1_data_fee(
+        &self,
+        contract_call: &ContractCall<M, ()>,
+    ) -> ChainResult<Option<ethers::types::U256>> {
+        if let Some(arbitrum_node_interface) = &self.arbitrum_node_interface {
+            let (gas_estimate_for_l1, base_fee, _) = arbitrum_node_interface
+                .gas_estimate_l1_component(
+                    self.process_target(),
+                    false,
+                    contract_call.calldata().unwrap_or_default(),
+                )
+                .call()
+                .await?;
+            return Ok(Some(base_fee.saturating_mul(gas_estimate_for_l1.into())));
+        }
+        if let Some(op_gas_price_oracle) = &self.op_gas_price_oracle {
+            let l1_data_fee = op_gas_price_oracle
+                .get_l1_fee(contract_call.tx.rlp())
+                .call()
+                .await?;
+            return Ok(Some(l1_data_fee));
+        }
+        Ok(None)
+    }
+
+    /// The contract deliveries are sent to, the processor contract if one is
+    /// configured and the mailbox otherwise
+    fn process_target(&self) -> ethers::types::Address {
+        match &self.conn.processor {
+            Some(processor) => processor.address.into(),
+            None => self.contract.address(),
+        }
+    }
+
+    /// Returns a ContractCall that processes the provided message, through
+    /// the processor contract if one is configured.
+    fn process_call(&self, message: &HyperlaneMessage, metadata: &[u8]) -> ContractCall<M, ()> {
+        let raw_message = RawHyperlaneMessage::from(message).to_vec();
+        let mut call = self
+            .contract
+            .process(metadata.to_vec().into(), raw_message.clone().into());
+        if let Some(processor) = &self.conn.processor {
+            let calldata = processor.calldata(self.contract.address(), &raw_message, metadata);
+            call.tx.set_to(self.process_target());
+            call.tx.set_data(calldata.into());
+        }
+        call
+    }
+
     /// Returns a ContractCall that processes the provided message.
     async fn process_contract_call(
         &self,
@@ -316,16 +455,27 @@ where
         metadata: &[u8],
         tx_gas_estimate: Option<U256>,
     ) -> ChainResult<ContractCall<M, ()>> {
-        let mut tx = self.contract.process(
-            metadata.to_vec().into(),
-            RawHyperlaneMessage::from(message).to_vec().into(),
-        );
+        let mut tx = self.process_call(message, metadata);
         if let Some(gas_estimate) = tx_gas_estimate {
             tx = tx.gas(gas_estimate);
         }
         self.add_gas_overrides(tx).await
     }
 
+    /// Trace a `process` call whose simulation failed, if revert tracing is
+    /// enabled, and log the reason of its innermost reverted call.
+    async fn log_process_revert(&self, message: &HyperlaneMessage, metadata: &[u8]) {
+        let Some(method) = self.conn.revert_tracing else {
+            return;
+        };
+        let tx = self.process_call(message, metadata).tx;
+        match trace_revert(self.provider.as_ref(), &tx, method).await {
+            Ok(Some(revert)) => warn!(%revert, "Simulation of process reverted"),
+            Ok(None) => {}
+            Err(err) => warn!(error=?err, "Error tracing simulation of process"),
+        }
+    }
+
     async fn add_gas_overrides<D: Detokenize>(
         &self,
         tx: ContractCall<M, D>,
@@ -422,10 +572,9 @@ pub struct SubmittableBatch<M> {
 
 impl<M: Middleware + 'static> SubmittableBatch<M> {
     pub async fn submit(self) -> ChainResult<TxOutcome> {
-        let call_with_gas_overrides =
-            fill_tx_gas_params(self.call, self.provider, &self.transaction_overrides).await?;
-        let outcome = report_tx(call_with_gas_overrides).await?;
-        Ok(outcome.into())
+        EthereumTxAdapter::new(self.provider, self.transaction_overrides)
+            .submit(self.call, &TxSubmitterConf::default())
+            .await
     }
 }
 
@@ -493,11 +642,16 @@ where
         metadata: &[u8],
         tx_gas_limit: Option<U256>,
     ) -> ChainResult<TxOutcome> {
-        let contract_call = self
-            .process_contract_call(message, metadata, tx_gas_limit)
-            .await?;
-        let receipt = report_tx(contract_call).await?;
-        Ok(receipt.into())
+        let mut contract_call = self.process_call(message, metadata);
+        if let Some(gas_limit) = tx_gas_limit {
+            contract_call = contract_call.gas(gas_limit);
+        }
+        EthereumTxAdapter::new(
+            self.provider.clone(),
+            self.conn.transaction_overrides.clone(),
+        )
+        .submit(contract_call, &TxSubmitterConf::default())
+        .await
     }
 
     #[instrument(skip(self, ops), fields(size=%ops.len()))]
@@ -538,7 +692,13 @@ where
         message: &HyperlaneMessage,
         metadata: &[u8],
     ) -> ChainResult<TxCostEstimate> {
-        let contract_call = self.process_contract_call(message, metadata, None).await?;
+        let contract_call = match self.process_contract_call(message, metadata, None).await {
+            Ok(contract_call) => contract_call,
+            Err(err) => {
+                self.log_process_revert(message, metadata).await;
+                return Err(err);
+            }
+        };
         let gas_limit = contract_call
             .tx
             .gas()
@@ -553,7 +713,7 @@ where
                         H160::zero().into(),
                         // Give the sender a deposit, otherwise it reverts
                         U256::MAX.into(),
-                        self.contract.address(),
+                        self.process_target(),
                         U256::zero().into(),
                         H160::zero().into(),
                         H160::zero().into(),
@@ -566,6 +726,8 @@ where
             None
         };
 
+        let l1_data_fee = self.estimate_l1_data_fee(&contract_call).await?;
+
         let gas_price: U256 = self
             .provider
             .get_gas_price()
@@ -577,10 +739,18 @@ where
             gas_limit: gas_limit.into(),
             gas_price: gas_price.try_into()?,
             l2_gas_limit: l2_gas_limit.map(|v| v.into()),
+            l1_data_fee: l1_data_fee.map(|v| v.into()),
         })
     }
 
     fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8> {
+        if let Some(processor) = &self.conn.processor {
+            return processor.calldata(
+                self.contract.address(),
+                &RawHyperlaneMessage::from(message).to_vec(),
+                metadata,
+            );
+        }
         let process_call = ProcessCall {
             message: RawHyperlaneMessage::from(message).to_vec().into(),
             metadata: metadata.to_vec().into(),
@@ -605,17 +775,20 @@ mod test {
     use std::{str::FromStr, sync::Arc};
 
     use ethers::{
+        abi::Token,
         providers::{MockProvider, Provider},
-        types::{Block, Transaction, U256 as EthersU256},
+        types::{Block, Bytes, Transaction, U256 as EthersU256},
     };
 
     use hyperlane_core::{
         ContractLocator, HyperlaneDomain, HyperlaneMessage, KnownHyperlaneDomain, Mailbox,
-        TxCostEstimate, H160, H256, U256,
+        RawHyperlaneMessage, TxCostEstimate, H160, H256, U256,
     };
 
     use crate::{contracts::EthereumMailbox, ConnectionConf, RpcConnectionConf};
 
+    use super::{decode_dispatched_message, malformed_message_nonce};
+
     /// An amount of gas to add to the estimated gas
     const GAS_ESTIMATE_BUFFER: u32 = 75_000;
 
@@ -633,6 +806,16 @@ mod test {
             },
             transaction_overrides: Default::default(),
             operation_batch: Default::default(),
+            revert_tracing: None,
+            finality_source: Default::default(),
+            requests_per_second: Default::default(),
+            broadcast_transactions: false,
+            processor: None,
+            rpc_headers: Default::default(),
+            proxy: None,
+            rpc_tls: None,
+            legacy_mailbox: false,
+            rpc_trace: None,
         };
 
         let mailbox = EthereumMailbox::new(
@@ -673,6 +856,18 @@ mod test {
             EthersU256::from(ethers::utils::parse_units("15", "gwei").unwrap()).into();
         mock_provider.push(gas_price).unwrap();
 
+        // RPC 5: eth_call to the ArbitrumNodeInterface's gasEstimateL1Component function
+        // by process_estimate_costs. Return 50k L1 gas at a 0.1 gwei base fee
+        let gas_estimate_for_l1 = EthersU256::from(50000u32);
+        let base_fee = EthersU256::from(ethers::utils::parse_units("0.1", "gwei").unwrap());
+        mock_provider
+            .push(Bytes::from(ethers::abi::encode(&[
+                Token::Uint(gas_estimate_for_l1),
+                Token::Uint(base_fee),
+                Token::Uint(EthersU256::zero()),
+            ])))
+            .unwrap();
+
         // RPC 4: eth_estimateGas to the ArbitrumNodeInterface's estimateRetryableTicket function by process_estimate_costs
         let l2_gas_limit = U256::from(200000); // 200k gas
         mock_provider.push(l2_gas_limit).unwrap();
@@ -704,10 +899,61 @@ mod test {
                 gas_limit: estimated_gas_limit,
                 gas_price: gas_price.try_into().unwrap(),
                 l2_gas_limit: Some(l2_gas_limit),
+                l1_data_fee: Some((gas_estimate_for_l1 * base_fee).into()),
             },
         );
     }
 
+    #[tokio::test]
+    async fn test_process_estimate_costs_sets_l1_data_fee_for_op_stack() {
+        // An OP stack chain
+        let (mailbox, mock_provider) =
+            get_test_mailbox(HyperlaneDomain::Known(KnownHyperlaneDomain::Optimism));
+
+        let message = HyperlaneMessage::default();
+        let metadata: Vec<u8> = vec![];
+
+        // The MockProvider responses we push are processed in LIFO
+        // order, so we start with the final RPCs and work toward the first
+        // RPCs
+
+        // RPC 4: eth_gasPrice by process_estimate_costs
+        let gas_price: U256 =
+            EthersU256::from(ethers::utils::parse_units("0.01", "gwei").unwrap()).into();
+        mock_provider.push(gas_price).unwrap();
+
+        // RPC 3: eth_call to the GasPriceOracle's getL1Fee function by process_estimate_costs
+        let l1_data_fee = EthersU256::from(ethers::utils::parse_units("0.00002", "ether").unwrap());
+        mock_provider
+            .push(Bytes::from(ethers::abi::encode(&[Token::Uint(
+                l1_data_fee,
+            )])))
+            .unwrap();
+
+        let latest_block: Block<Transaction> = Block {
+            gas_limit: ethers::types::U256::MAX,
+            ..Block::<Transaction>::default()
+        };
+        // RPC 2: eth_getBlockByNumber from the fill_tx_gas_params call in process_contract_call
+        mock_provider.push(latest_block).unwrap();
+
+        // RPC 1: eth_estimateGas from the estimate_gas call in process_contract_call
+        let gas_limit = U256::from(1000000u32);
+        mock_provider.push(gas_limit).unwrap();
+
+        let tx_cost_estimate = mailbox
+            .process_estimate_costs(&message, &metadata)
+            .await
+            .unwrap();
+
+        assert_eq!(tx_cost_estimate.l1_data_fee, Some(l1_data_fee.into()));
+        // The L1 data fee is worth 2M gas at the gas price
+        assert_eq!(
+            tx_cost_estimate.enforceable_gas_limit(),
+            gas_limit.saturating_add((GAS_ESTIMATE_BUFFER + 2_000_000).into())
+        );
+    }
+
     #[tokio::test]
     async fn test_tx_gas_limit_caps_at_block_gas_limit() {
         let (mailbox, mock_provider) =
@@ -752,7 +998,23 @@ mod test {
                 gas_limit: latest_block_gas_limit,
                 gas_price: gas_price.try_into().unwrap(),
                 l2_gas_limit: None,
+                l1_data_fee: None,
             },
         );
     }
+
+    #[test]
+    fn test_reads_nonce_of_malformed_message() {
+        let message = HyperlaneMessage {
+            nonce: 7,
+            ..Default::default()
+        };
+        let raw = RawHyperlaneMessage::from(&message);
+
+        // Truncated messages can't be decoded, but their nonce may still be read
+        let truncated = &raw[..10];
+        assert!(decode_dispatched_message(truncated).is_err());
+        assert_eq!(malformed_message_nonce(truncated), Some(7));
+        assert_eq!(malformed_message_nonce(&raw[..3]), None);
+    }
 }
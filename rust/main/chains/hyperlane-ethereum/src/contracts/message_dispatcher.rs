@@ -0,0 +1,208 @@
+#![allow(missing_docs)]
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ethers::abi::{Detokenize, Function};
+use ethers::prelude::Middleware;
+use ethers::types::{Bytes, U256 as EthersU256};
+use ethers_contract::builders::ContractCall;
+use ethers_contract::parse_log;
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, ContractLocator, DispatchOutcome, HyperlaneChain,
+    HyperlaneDomain, HyperlaneProvider, MessageDispatcher, TxOutcome, H256, U256,
+};
+use tracing::{info, instrument};
+
+use crate::interfaces::i_interchain_gas_paymaster::IInterchainGasPaymaster;
+use crate::interfaces::i_mailbox::{DispatchIdFilter, IMailbox, IMAILBOX_ABI};
+use crate::tx::{fill_tx_gas_params, report_tx};
+use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider, TransactionOverrides};
+
+/// Builder for message dispatchers of the mailbox at the locator's address.
+/// Requires a signer, which is the account messages are dispatched from.
+pub struct MessageDispatcherBuilder {
+    pub interchain_gas_paymaster: H256,
+}
+
+#[async_trait]
+impl BuildableWithProvider for MessageDispatcherBuilder {
+    type Output = Box<dyn MessageDispatcher>;
+    const NEEDS_SIGNER: bool = true;
+
+    async fn build_with_provider<M: Middleware + 'static>(
+        &self,
+        provider: M,
+        conn: &ConnectionConf,
+        locator: &ContractLocator,
+    ) -> Self::Output {
+        Box::new(EthereumMessageDispatcher::new(
+            Arc::new(provider),
+            conn,
+            locator,
+            self.interchain_gas_paymaster,
+        ))
+    }
+}
+
+/// Dispatches messages through an Ethereum mailbox from the signer of the
+/// underlying provider.
+#[derive(Debug)]
+pub struct EthereumMessageDispatcher<M> {
+    mailbox: IMailbox<M>,
+    interchain_gas_paymaster: IInterchainGasPaymaster<M>,
+    provider: Arc<M>,
+    transaction_overrides: TransactionOverrides,
+    domain: HyperlaneDomain,
+}
+
+impl<M> EthereumMessageDispatcher<M>
+where
+    M: Middleware + 'static,
+{
+    /// Create a dispatcher for the mailbox at the locator's address, paying
+    /// for gas to the `interchain_gas_paymaster`
+    pub fn new(
+        provider: Arc<M>,
+        conn: &ConnectionConf,
+        locator: &ContractLocator,
+        interchain_gas_paymaster: H256,
+    ) -> Self {
+        Self {
+            mailbox: IMailbox::new(locator.address, provider.clone()),
+            interchain_gas_paymaster: IInterchainGasPaymaster::new(
+                interchain_gas_paymaster,
+                provider.clone(),
+            ),
+            provider,
+            transaction_overrides: conn.transaction_overrides.clone(),
+            domain: locator.domain.clone(),
+        }
+    }
+
+    /// The overload of `name` taking just the destination, recipient and
+    /// body. Its generated binding is named after the other overloads.
+    fn default_hook_overload(name: &str) -> ChainResult<&'static Function> {
+        IMAILBOX_ABI
+            .functions_by_name(name)
+            .map_err(ChainCommunicationError::from_other)?
+            .iter()
+            .find(|function| function.inputs.len() == 3)
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str(&format!(
+                    "Mailbox ABI is missing the 3 argument `{name}`"
+                ))
+            })
+    }
+
+    fn default_hook_call<D: Detokenize>(
+        &self,
+        name: &str,
+        destination: u32,
+        recipient: H256,
+        body: &[u8],
+    ) -> ChainResult<ContractCall<M, D>> {
+        let function = Self::default_hook_overload(name)?;
+        self.mailbox
+            .method_hash(
+                function.short_signature(),
+                (
+                    destination,
+                    recipient.to_fixed_bytes(),
+                    Bytes::from(body.to_vec()),
+                ),
+            )
+            .map_err(ChainCommunicationError::from_other)
+    }
+
+    /// Ensures transactions are sent from a signer
+    fn signer(&self) -> ChainResult<()> {
+        if self.provider.default_sender().is_none() {
+            return Err(ChainCommunicationError::SignerUnavailable);
+        }
+        Ok(())
+    }
+}
+
+impl<M> HyperlaneChain for EthereumMessageDispatcher<M>
+where
+    M: Middleware + 'static,
+{
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(EthereumProvider::new(
+            self.provider.clone(),
+            self.domain.clone(),
+        ))
+    }
+}
+
+#[async_trait]
+impl<M> MessageDispatcher for EthereumMessageDispatcher<M>
+where
+    M: Middleware + 'static,
+{
+    #[instrument(err, skip(self, body), fields(body_len = body.len()))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn dispatch(
+        &self,
+        destination: u32,
+        recipient: H256,
+        body: Vec<u8>,
+    ) -> ChainResult<DispatchOutcome> {
+        self.signer()?;
+        let fee: EthersU256 = self
+            .default_hook_call("quoteDispatch", destination, recipient, &body)?
+            .call()
+            .await?;
+        let tx = self
+            .default_hook_call::<[u8; 32]>("dispatch", destination, recipient, &body)?
+            .value(fee);
+        let tx = fill_tx_gas_params(tx, self.provider.clone(), &self.transaction_overrides).await?;
+        info!(%fee, "Dispatching message");
+        let receipt = report_tx(tx).await?;
+        let message_id = receipt
+            .logs
+            .iter()
+            .filter(|log| log.address == self.mailbox.address())
+            .find_map(|log| parse_log::<DispatchIdFilter>(log.clone().into()).ok())
+            .map(|dispatched| H256::from(dispatched.message_id));
+        Ok(DispatchOutcome {
+            message_id,
+            tx_outcome: receipt.into(),
+        })
+    }
+
+    #[instrument(err, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn pay_for_gas(
+        &self,
+        message_id: H256,
+        destination: u32,
+        gas_amount: U256,
+    ) -> ChainResult<TxOutcome> {
+        self.signer()?;
+        let refund_address = self.provider.default_sender().unwrap_or_default();
+        let payment = self
+            .interchain_gas_paymaster
+            .quote_gas_payment(destination, gas_amount.into())
+            .call()
+            .await?;
+        let tx = self
+            .interchain_gas_paymaster
+            .pay_for_gas(
+                message_id.into(),
+                destination,
+                gas_amount.into(),
+                refund_address,
+            )
+            .value(payment);
+        let tx = fill_tx_gas_params(tx, self.provider.clone(), &self.transaction_overrides).await?;
+        info!(%payment, "Paying for gas");
+        let receipt = report_tx(tx).await?;
+        Ok(receipt.into())
+    }
+}
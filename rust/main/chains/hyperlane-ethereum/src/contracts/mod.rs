@@ -1,8 +1,19 @@
-pub use {interchain_gas::*, mailbox::*, merkle_tree_hook::*, validator_announce::*};
+#[cfg(feature = "legacy")]
+pub use legacy_mailbox::*;
+pub use {
+    gas_oracle_config::*, interchain_gas::*, mailbox::*, merkle_tree_hook::*,
+    message_dispatcher::*, token_router::*, validator_announce::*,
+};
 
+mod gas_oracle_config;
 mod interchain_gas;
+#[cfg(feature = "legacy")]
+mod legacy_mailbox;
 mod mailbox;
 mod merkle_tree_hook;
+mod message_dispatcher;
 mod multicall;
-mod utils;
+mod revert;
+mod token_router;
+pub(crate) mod utils;
 mod validator_announce;
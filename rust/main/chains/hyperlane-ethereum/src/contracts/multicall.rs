@@ -7,7 +7,7 @@ use hyperlane_core::{
 };
 use tracing::warn;
 
-use crate::{ConnectionConf, EthereumProvider};
+use crate::{error_registry::decode_contract_error, ConnectionConf, EthereumProvider};
 
 const ALLOW_BATCH_FAILURES: bool = true;
 
@@ -72,7 +72,11 @@ where
     });
 
     let mut batch_call = multicall.as_aggregate_3_value();
-    let mut gas_limit: U256 = batch_call.estimate_gas().await?.into();
+    let mut gas_limit: U256 = batch_call
+        .estimate_gas()
+        .await
+        .map_err(decode_contract_error)?
+        .into();
     // Use the max of the sum of individual estimates and the estimate for the entire batch
     if let Some(gas_sum) = individual_estimates_sum {
         gas_limit = gas_limit.max(gas_sum)
@@ -0,0 +1,126 @@
+//! Extraction of revert reasons through the tracing RPCs of archive nodes.
+//!
+//! `eth_call` and `eth_estimateGas` only report the revert data of the
+//! outermost call, which is empty when a contract bubbles up a failed call
+//! without its reason. Tracing the call finds the innermost call that
+//! reverted, whose revert data is decoded against the registry of known
+//! contract errors.
+
+use std::fmt::{Display, Formatter};
+
+use ethers::prelude::Middleware;
+use ethers::types::{
+    transaction::eip2718::TypedTransaction, Action, CallFrame, GethDebugBuiltInTracerType,
+    GethDebugTracerType, GethDebugTracingCallOptions, GethTrace, GethTraceFrame, TraceType, H160,
+};
+use hyperlane_core::{ChainCommunicationError, ChainResult};
+
+use crate::{ContractErrorRegistry, RevertTraceMethod};
+
+/// The innermost reverted call of a traced call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevertTrace {
+    /// The contract which reverted, if known
+    pub address: Option<H160>,
+    /// The decoded revert reason
+    pub reason: String,
+}
+
+impl Display for RevertTrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.address {
+            Some(address) => write!(f, "call to {address:?} reverted: {}", self.reason),
+            None => write!(f, "call reverted: {}", self.reason),
+        }
+    }
+}
+
+/// Trace `tx` with `method` and return its innermost reverted call, or `None`
+/// if the call does not revert.
+pub(crate) async fn trace_revert<M: Middleware + 'static>(
+    provider: &M,
+    tx: &TypedTransaction,
+    method: RevertTraceMethod,
+) -> ChainResult<Option<RevertTrace>> {
+    match method {
+        RevertTraceMethod::DebugTraceCall => {
+            let mut options = GethDebugTracingCallOptions::default();
+            options.tracing_options.tracer = Some(GethDebugTracerType::BuiltInTracer(
+                GethDebugBuiltInTracerType::CallTracer,
+            ));
+            let trace = provider
+                .debug_trace_call(tx.clone(), None, options)
+                .await
+                .map_err(ChainCommunicationError::from_other)?;
+            let GethTrace::Known(GethTraceFrame::CallTracer(frame)) = trace else {
+                return Err(ChainCommunicationError::from_other_str(
+                    "Unexpected debug_traceCall response",
+                ));
+            };
+            if frame.error.is_none() {
+                return Ok(None);
+            }
+            let frame = innermost_reverted_frame(&frame);
+            Ok(Some(RevertTrace {
+                address: frame.to.as_ref().and_then(|to| to.as_address().copied()),
+                reason: match frame.output.as_ref().filter(|output| !output.is_empty()) {
+                    Some(output) => ContractErrorRegistry::hyperlane().decode_or_hex(output),
+                    None => frame.error.clone().unwrap_or_default(),
+                },
+            }))
+        }
+        RevertTraceMethod::TraceCall => {
+            let trace = provider
+                .trace_call(tx.clone(), vec![TraceType::Trace], None)
+                .await
+                .map_err(ChainCommunicationError::from_other)?;
+            // Parity-like traces do not include the revert data, only the
+            // error of each reverted call
+            Ok(trace
+                .trace
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|trace| trace.error.is_some())
+                .max_by_key(|trace| trace.trace_address.len())
+                .map(|trace| RevertTrace {
+                    address: match trace.action {
+                        Action::Call(call) => Some(call.to),
+                        _ => None,
+                    },
+                    reason: trace.error.unwrap_or_default(),
+                }))
+        }
+    }
+}
+
+/// The last reverted call of the deepest chain of reverted calls under
+/// `frame`. Contracts which bubble up failed calls revert right after them.
+fn innermost_reverted_frame(frame: &CallFrame) -> &CallFrame {
+    frame
+        .calls
+        .iter()
+        .flatten()
+        .filter(|call| call.error.is_some())
+        .last()
+        .map(innermost_reverted_frame)
+        .unwrap_or(frame)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_innermost_reverted_frame() {
+        let reverted = |calls: Vec<CallFrame>, output: Vec<u8>| CallFrame {
+            error: Some("execution reverted".into()),
+            output: Some(output.into()),
+            calls: Some(calls),
+            ..Default::default()
+        };
+        let inner = reverted(vec![], vec![0x01]);
+        let succeeded = CallFrame::default();
+        let outer = reverted(vec![succeeded, inner.clone()], vec![]);
+        assert_eq!(innermost_reverted_frame(&outer), &inner);
+    }
+}
@@ -0,0 +1,120 @@
+#![allow(missing_docs)]
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ethers::providers::Middleware;
+use hyperlane_core::{
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneProvider, TokenRouter, H256, U256,
+};
+use tracing::instrument;
+
+use crate::{
+    error_registry::decode_contract_error,
+    interfaces::{i_hyp_erc20_collateral::IHypERC20Collateral, ierc20::IERC20},
+    BuildableWithProvider, ConnectionConf, EthereumProvider,
+};
+
+pub struct TokenRouterBuilder {}
+
+#[async_trait]
+impl BuildableWithProvider for TokenRouterBuilder {
+    type Output = Box<dyn TokenRouter>;
+    const NEEDS_SIGNER: bool = false;
+
+    async fn build_with_provider<M: Middleware + 'static>(
+        &self,
+        provider: M,
+        _conn: &ConnectionConf,
+        locator: &ContractLocator,
+    ) -> Self::Output {
+        Box::new(EthereumTokenRouter::new(Arc::new(provider), locator))
+    }
+}
+
+/// A reference to a warp route TokenRouter contract on some Ethereum chain
+#[derive(Debug)]
+pub struct EthereumTokenRouter<M>
+where
+    M: Middleware,
+{
+    contract: Arc<IHypERC20Collateral<M>>,
+    domain: HyperlaneDomain,
+    provider: Arc<M>,
+}
+
+impl<M> EthereumTokenRouter<M>
+where
+    M: Middleware + 'static,
+{
+    /// Create a reference to a TokenRouter contract at a specific Ethereum
+    /// address on some chain
+    pub fn new(provider: Arc<M>, locator: &ContractLocator) -> Self {
+        Self {
+            contract: Arc::new(IHypERC20Collateral::new(locator.address, provider.clone())),
+            domain: locator.domain.clone(),
+            provider,
+        }
+    }
+}
+
+impl<M> HyperlaneChain for EthereumTokenRouter<M>
+where
+    M: Middleware + 'static,
+{
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(EthereumProvider::new(
+            self.contract.client(),
+            self.domain.clone(),
+        ))
+    }
+}
+
+impl<M> HyperlaneContract for EthereumTokenRouter<M>
+where
+    M: Middleware + 'static,
+{
+    fn address(&self) -> H256 {
+        self.contract.address().into()
+    }
+}
+
+#[async_trait]
+impl<M> TokenRouter for EthereumTokenRouter<M>
+where
+    M: Middleware + 'static,
+{
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn collateral_balance(&self) -> ChainResult<U256> {
+        let wrapped_token = self
+            .contract
+            .wrapped_token()
+            .call()
+            .await
+            .map_err(decode_contract_error)?;
+        let balance = IERC20::new(wrapped_token, self.provider.clone())
+            .balance_of(self.contract.address())
+            .call()
+            .await
+            .map_err(decode_contract_error)?;
+        Ok(balance.into())
+    }
+
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn synthetic_supply(&self) -> ChainResult<U256> {
+        // Synthetic routers are the ERC20 they mint
+        let supply = IERC20::new(self.contract.address(), self.provider.clone())
+            .total_supply()
+            .call()
+            .await
+            .map_err(decode_contract_error)?;
+        Ok(supply.into())
+    }
+}
@@ -3,10 +3,13 @@ use std::sync::Arc;
 use ethers::{
     abi::RawLog,
     providers::Middleware,
-    types::{H160 as EthersH160, H256 as EthersH256},
+    types::{BlockNumber, H160 as EthersH160, H256 as EthersH256},
 };
 use ethers_contract::{ContractError, EthEvent, LogMeta as EthersLogMeta};
-use hyperlane_core::{ChainResult, LogMeta, H512};
+use hyperlane_core::{ChainCommunicationError, ChainResult, LogMeta, H256, H512};
+use tracing::warn;
+
+use crate::FinalitySource;
 
 pub async fn fetch_raw_logs_and_meta<T: EthEvent, M>(
     tx_hash: H512,
@@ -44,3 +47,72 @@ where
         .collect();
     Ok(logs)
 }
+
+/// Get the latest finalized block number, from the block tag of
+/// `finality_source` if it has one and the node supports it, or else by
+/// subtracting `reorg_period` from the latest block number.
+pub async fn get_finalized_block_number<M>(
+    provider: &M,
+    finality_source: FinalitySource,
+    reorg_period: u32,
+) -> ChainResult<u32>
+where
+    M: Middleware + 'static,
+{
+    if let Some(number) = get_tagged_block_number(provider, finality_source).await {
+        return Ok(number);
+    }
+    Ok(provider
+        .get_block_number()
+        .await
+        .map_err(ChainCommunicationError::from_other)?
+        .as_u32()
+        .saturating_sub(reorg_period))
+}
+
+/// Get the number of the block tagged by `finality_source`, or `None` if it
+/// has no tag or the node does not support it.
+pub async fn get_tagged_block_number<M>(
+    provider: &M,
+    finality_source: FinalitySource,
+) -> Option<u32>
+where
+    M: Middleware + 'static,
+{
+    let tag = match finality_source {
+        FinalitySource::BlockDepth => return None,
+        FinalitySource::FinalizedTag => BlockNumber::Finalized,
+        FinalitySource::SafeTag => BlockNumber::Safe,
+    };
+    match provider.get_block(tag).await {
+        Ok(block) => {
+            let number = block.and_then(|block| block.number);
+            if number.is_none() {
+                warn!(?tag, "Block tag not found, falling back to block depth");
+            }
+            number.map(|number| number.as_u32())
+        }
+        Err(err) => {
+            warn!(
+                ?tag,
+                ?err,
+                "Failed to get tagged block, falling back to block depth"
+            );
+            None
+        }
+    }
+}
+
+/// Get the hash of the canonical block at `height`, or `None` if the node
+/// does not have it.
+pub async fn get_block_hash<M>(provider: &M, height: u32) -> ChainResult<Option<H256>>
+where
+    M: Middleware + 'static,
+{
+    Ok(provider
+        .get_block(u64::from(height))
+        .await
+        .map_err(ChainCommunicationError::from_other)?
+        .and_then(|block| block.hash)
+        .map(Into::into))
+}
@@ -1,23 +1,27 @@
 #![allow(clippy::enum_variant_names)]
 #![allow(missing_docs)]
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, ops::RangeInclusive, sync::Arc};
 
 use async_trait::async_trait;
 use ethers::providers::Middleware;
 use ethers_contract::builders::ContractCall;
 use hyperlane_core::{
-    Announcement, ChainResult, ContractLocator, HyperlaneAbi, HyperlaneChain, HyperlaneContract,
-    HyperlaneDomain, HyperlaneProvider, SignedType, TxOutcome, ValidatorAnnounce, H160, H256, U256,
+    rpc_clients::call_and_retry_indefinitely, Announcement, ChainResult, ContractLocator,
+    HyperlaneAbi, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneProvider, Indexed,
+    Indexer, LogMeta, SequenceAwareIndexer, SignedType, TxOutcome, ValidatorAnnounce,
+    ValidatorAnnouncement, H160, H256, H512, U256,
 };
 use tracing::{instrument, log::trace};
 
+use super::utils::{fetch_raw_logs_and_meta, get_block_hash, get_finalized_block_number};
 use crate::{
     interfaces::i_validator_announce::{
-        IValidatorAnnounce as EthereumValidatorAnnounceInternal, IVALIDATORANNOUNCE_ABI,
+        IValidatorAnnounce as EthereumValidatorAnnounceInternal, ValidatorAnnouncementFilter,
+        IVALIDATORANNOUNCE_ABI,
     },
     tx::{fill_tx_gas_params, report_tx},
-    BuildableWithProvider, ConnectionConf, EthereumProvider,
+    BuildableWithProvider, ConnectionConf, EthereumProvider, FinalitySource,
 };
 
 impl<M> std::fmt::Display for EthereumValidatorAnnounceInternal<M>
@@ -140,6 +144,11 @@ where
         Ok(storage_locations)
     }
 
+    async fn get_announced_validators(&self) -> ChainResult<Vec<H256>> {
+        let validators = self.contract.get_announced_validators().call().await?;
+        Ok(validators.into_iter().map(Into::into).collect())
+    }
+
     #[instrument(ret, skip(self))]
     async fn announce_tokens_needed(&self, announcement: SignedType<Announcement>) -> Option<U256> {
         let validator = announcement.value.validator;
@@ -171,6 +180,145 @@ where
     }
 }
 
+pub struct ValidatorAnnounceIndexerBuilder {
+    pub reorg_period: u32,
+}
+
+#[async_trait]
+impl BuildableWithProvider for ValidatorAnnounceIndexerBuilder {
+    type Output = Box<dyn SequenceAwareIndexer<ValidatorAnnouncement>>;
+    const NEEDS_SIGNER: bool = false;
+
+    async fn build_with_provider<M: Middleware + 'static>(
+        &self,
+        provider: M,
+        conn: &ConnectionConf,
+        locator: &ContractLocator,
+    ) -> Self::Output {
+        Box::new(EthereumValidatorAnnounceIndexer::new(
+            Arc::new(provider),
+            locator,
+            self.reorg_period,
+            conn.finality_source,
+        ))
+    }
+}
+
+#[derive(Debug)]
+/// Struct that retrieves the announcements of an Ethereum ValidatorAnnounce
+pub struct EthereumValidatorAnnounceIndexer<M>
+where
+    M: Middleware,
+{
+    contract: Arc<EthereumValidatorAnnounceInternal<M>>,
+    provider: Arc<M>,
+    reorg_period: u32,
+    finality_source: FinalitySource,
+}
+
+impl<M> EthereumValidatorAnnounceIndexer<M>
+where
+    M: Middleware + 'static,
+{
+    /// Create new EthereumValidatorAnnounceIndexer
+    pub fn new(
+        provider: Arc<M>,
+        locator: &ContractLocator,
+        reorg_period: u32,
+        finality_source: FinalitySource,
+    ) -> Self {
+        Self {
+            contract: Arc::new(EthereumValidatorAnnounceInternal::new(
+                locator.address,
+                provider.clone(),
+            )),
+            provider,
+            reorg_period,
+            finality_source,
+        }
+    }
+}
+
+impl From<ValidatorAnnouncementFilter> for ValidatorAnnouncement {
+    fn from(log: ValidatorAnnouncementFilter) -> Self {
+        Self {
+            validator: log.validator.into(),
+            storage_location: log.storage_location,
+        }
+    }
+}
+
+#[async_trait]
+impl<M> Indexer<ValidatorAnnouncement> for EthereumValidatorAnnounceIndexer<M>
+where
+    M: Middleware + 'static,
+{
+    /// Note: This call may return duplicates depending on the provider used
+    #[instrument(err, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<ValidatorAnnouncement>, LogMeta)>> {
+        let events = self
+            .contract
+            .validator_announcement_filter()
+            .from_block(*range.start())
+            .to_block(*range.end())
+            .query_with_meta()
+            .await?;
+
+        Ok(events
+            .into_iter()
+            .map(|(log, log_meta)| (Indexed::new(log.into()), log_meta.into()))
+            .collect())
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        get_finalized_block_number(&*self.provider, self.finality_source, self.reorg_period).await
+    }
+
+    async fn get_block_hash(&self, height: u32) -> ChainResult<Option<H256>> {
+        get_block_hash(&*self.provider, height).await
+    }
+
+    async fn fetch_logs_by_tx_hash(
+        &self,
+        tx_hash: H512,
+    ) -> ChainResult<Vec<(Indexed<ValidatorAnnouncement>, LogMeta)>> {
+        let raw_logs_and_meta = call_and_retry_indefinitely(|| {
+            let provider = self.provider.clone();
+            let contract = self.contract.address();
+            Box::pin(async move {
+                fetch_raw_logs_and_meta::<ValidatorAnnouncementFilter, M>(
+                    tx_hash, provider, contract,
+                )
+                .await
+            })
+        })
+        .await;
+
+        Ok(raw_logs_and_meta
+            .into_iter()
+            .map(|(log, log_meta)| (Indexed::new(log.into()), log_meta))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl<M> SequenceAwareIndexer<ValidatorAnnouncement> for EthereumValidatorAnnounceIndexer<M>
+where
+    M: Middleware + 'static,
+{
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        // Announcements aren't sequenced, they are only indexed by block
+        let tip = self.get_finalized_block_number().await?;
+        Ok((None, tip))
+    }
+}
+
 pub struct EthereumValidatorAnnounceAbi;
 
 impl HyperlaneAbi for EthereumValidatorAnnounceAbi {
@@ -18,6 +18,15 @@ pub enum HyperlaneEthereumError {
     /// Some details from a queried block are missing
     #[error("Some details from a queried block are missing")]
     MissingBlockDetails,
+
+    /// A contract call reverted with a known error
+    #[error("Contract reverted: {reason} (revert data {data})")]
+    ContractRevert {
+        /// The decoded error
+        reason: String,
+        /// The raw revert data
+        data: String,
+    },
 }
 
 impl From<HyperlaneEthereumError> for ChainCommunicationError {
@@ -0,0 +1,169 @@
+//! Registry of the custom errors of the Hyperlane contracts, used to decode
+//! revert data into human-readable errors.
+
+use std::collections::HashMap;
+
+use ethers::abi::{decode, Abi, AbiError, ParamType};
+use ethers::prelude::{Lazy, Middleware};
+use ethers_contract::ContractError;
+use hyperlane_core::ChainCommunicationError;
+use itertools::Itertools;
+use tracing::debug;
+
+use crate::error::HyperlaneEthereumError;
+use crate::interfaces::{
+    i_aggregation_ism::IAGGREGATIONISM_ABI, i_ccip_read_ism::ICCIPREADISM_ABI,
+    i_hyp_erc20_collateral::IHYPERC20COLLATERAL_ABI,
+    i_interchain_gas_paymaster::IINTERCHAINGASPAYMASTER_ABI,
+    i_interchain_security_module::IINTERCHAINSECURITYMODULE_ABI, i_mailbox::IMAILBOX_ABI,
+    i_multisig_ism::IMULTISIGISM_ABI, i_routing_ism::IROUTINGISM_ABI, ierc20::IERC20_ABI,
+    mailbox::MAILBOX_ABI, merkle_tree_hook::MERKLETREEHOOK_ABI,
+};
+
+/// Selector of `Error(string)`, the error of `require` and `revert` with a
+/// reason string
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// Selector of `Panic(uint256)`, the error of failed assertions and
+/// arithmetic errors
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+static CONTRACT_ERRORS: Lazy<ContractErrorRegistry> = Lazy::new(|| {
+    ContractErrorRegistry::from_abis([
+        &*MAILBOX_ABI,
+        &*IMAILBOX_ABI,
+        &*MERKLETREEHOOK_ABI,
+        &*IINTERCHAINSECURITYMODULE_ABI,
+        &*IMULTISIGISM_ABI,
+        &*IROUTINGISM_ABI,
+        &*IAGGREGATIONISM_ABI,
+        &*ICCIPREADISM_ABI,
+        &*IINTERCHAINGASPAYMASTER_ABI,
+        &*IHYPERC20COLLATERAL_ABI,
+        &*IERC20_ABI,
+    ])
+});
+
+/// Custom errors by selector
+#[derive(Debug, Clone, Default)]
+pub struct ContractErrorRegistry {
+    errors: HashMap<[u8; 4], AbiError>,
+}
+
+impl ContractErrorRegistry {
+    /// Build a registry of the custom errors declared in `abis`
+    pub fn from_abis<'a>(abis: impl IntoIterator<Item = &'a Abi>) -> Self {
+        let errors = abis
+            .into_iter()
+            .flat_map(|abi| abi.errors())
+            .map(|error| {
+                let mut selector = [0; 4];
+                selector.copy_from_slice(&error.signature()[..4]);
+                (selector, error.clone())
+            })
+            .collect();
+        Self { errors }
+    }
+
+    /// The registry of the errors of the Hyperlane contracts
+    pub fn hyperlane() -> &'static Self {
+        &CONTRACT_ERRORS
+    }
+
+    /// Decode revert data as a reason string, a panic or a registered custom
+    /// error, or `None` if it is none of them.
+    pub fn decode(&self, data: &[u8]) -> Option<String> {
+        let (selector, args) = (data.get(..4)?, &data[4..]);
+        match selector {
+            s if s == ERROR_STRING_SELECTOR => decode(&[ParamType::String], args)
+                .ok()
+                .map(|tokens| tokens.into_iter().join("")),
+            s if s == PANIC_SELECTOR => decode(&[ParamType::Uint(256)], args)
+                .ok()
+                .map(|tokens| format!("panic 0x{}", tokens.into_iter().join(""))),
+            s => {
+                let error = self.errors.get(s)?;
+                let tokens = error.decode(args).ok()?;
+                Some(format!("{}({})", error.name, tokens.into_iter().join(", ")))
+            }
+        }
+    }
+
+    /// Decode revert data, falling back to its hex encoding
+    pub fn decode_or_hex(&self, data: &[u8]) -> String {
+        self.decode(data)
+            .unwrap_or_else(|| format!("unknown error 0x{}", hex::encode(data)))
+    }
+}
+
+/// Convert a contract error to a chain communication error, with its revert
+/// data decoded if it is a known error.
+pub(crate) fn decode_contract_error<M: Middleware + 'static>(
+    err: ContractError<M>,
+) -> ChainCommunicationError {
+    let decoded = err.as_revert().and_then(|data| {
+        ContractErrorRegistry::hyperlane()
+            .decode(data)
+            .map(|reason| (reason, data.clone()))
+    });
+    match decoded {
+        Some((reason, data)) => {
+            debug!(%reason, "Decoded contract revert");
+            ChainCommunicationError::from_contract_error(HyperlaneEthereumError::ContractRevert {
+                reason,
+                data: data.to_string(),
+            })
+        }
+        None => err.into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethers::abi::{encode, Token};
+
+    use super::*;
+
+    fn revert_data(selector: [u8; 4], tokens: &[Token]) -> Vec<u8> {
+        [selector.as_slice(), &encode(tokens)].concat()
+    }
+
+    #[test]
+    fn test_decode_reason_string() {
+        let data = revert_data(
+            ERROR_STRING_SELECTOR,
+            &[Token::String("Mailbox: ISM verification failed".into())],
+        );
+        assert_eq!(
+            ContractErrorRegistry::default().decode(&data).unwrap(),
+            "Mailbox: ISM verification failed"
+        );
+    }
+
+    #[test]
+    fn test_decode_panic() {
+        let data = revert_data(PANIC_SELECTOR, &[Token::Uint(0x11.into())]);
+        assert_eq!(
+            ContractErrorRegistry::default().decode(&data).unwrap(),
+            "panic 0x11"
+        );
+    }
+
+    #[test]
+    fn test_decode_custom_error() {
+        let abi: Abi = serde_json::from_str(
+            r#"[{"type": "error", "name": "InvalidThreshold", "inputs": [{"name": "threshold", "type": "uint8"}]}]"#,
+        )
+        .unwrap();
+        let registry = ContractErrorRegistry::from_abis([&abi]);
+        let error = abi.errors().next().unwrap();
+        let mut data = error.signature()[..4].to_vec();
+        data.extend(encode(&[Token::Uint(3.into())]));
+
+        assert_eq!(registry.decode(&data).unwrap(), "InvalidThreshold(3)");
+        assert_eq!(
+            registry.decode_or_hex(&[0xde, 0xad, 0xbe, 0xef]),
+            "unknown error 0xdeadbeef"
+        );
+        assert!(registry.decode(&[0x01]).is_none());
+    }
+}
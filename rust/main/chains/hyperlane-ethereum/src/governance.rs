@@ -0,0 +1,296 @@
+//! Governance transactions of contracts owned by a multisig are built here
+//! rather than submitted: operators review them, then submit them through
+//! their Safe, either as a Safe transaction or by importing them in the Safe
+//! transaction builder, or as raw calldata.
+
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ethers::abi::{self, Token};
+use ethers::types::{Address, Bytes, U256};
+use ethers::utils::id;
+use hyperlane_core::{HyperlaneDomain, H256};
+use serde::Serialize;
+
+/// The MultiSendCallOnly contract of Safe 1.3.0, deployed at the same address
+/// on most chains
+pub const MULTI_SEND_CALL_ONLY: &str = "0x40A2aCCbd92BCA938b02010E17A5b8929b49130D";
+
+/// A governance operation on a contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GovernanceCall {
+    /// Set the ISM of a mailbox, or of a mailbox client such as a warp route
+    SetInterchainSecurityModule {
+        /// The new ISM
+        ism: H256,
+    },
+    /// Enroll the routers of a router, e.g. a warp route, on remote domains
+    EnrollRemoteRouters {
+        /// The remote domains and the router enrolled for each
+        routers: Vec<(u32, H256)>,
+    },
+    /// Transfer the ownership of an `Ownable` contract
+    TransferOwnership {
+        /// The new owner
+        owner: H256,
+    },
+}
+
+impl GovernanceCall {
+    /// The calldata of the call
+    pub fn calldata(&self) -> Vec<u8> {
+        let (signature, tokens) = match self {
+            Self::SetInterchainSecurityModule { ism } => (
+                "setInterchainSecurityModule(address)",
+                vec![Token::Address(Address::from(*ism))],
+            ),
+            Self::EnrollRemoteRouters { routers } => {
+                let (domains, routers): (Vec<_>, Vec<_>) = routers
+                    .iter()
+                    .map(|(domain, router)| {
+                        (
+                            Token::Uint((*domain).into()),
+                            Token::FixedBytes(router.as_bytes().to_vec()),
+                        )
+                    })
+                    .unzip();
+                (
+                    "enrollRemoteRouters(uint32[],bytes32[])",
+                    vec![Token::Array(domains), Token::Array(routers)],
+                )
+            }
+            Self::TransferOwnership { owner } => (
+                "transferOwnership(address)",
+                vec![Token::Address(Address::from(*owner))],
+            ),
+        };
+        let mut calldata = id(signature).to_vec();
+        calldata.extend(abi::encode(&tokens));
+        calldata
+    }
+}
+
+/// A call to a contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GovernanceTransaction {
+    /// The contract called
+    pub to: Address,
+    /// The value sent, in decimal as the Safe transaction builder expects
+    #[serde(serialize_with = "serialize_decimal")]
+    pub value: U256,
+    /// The calldata
+    pub data: Bytes,
+}
+
+fn serialize_decimal<S: serde::Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_string())
+}
+
+/// Whether a Safe calls or delegate calls the target of a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafeOperation {
+    /// A call
+    Call = 0,
+    /// A delegate call, to batch calls through MultiSend
+    DelegateCall = 1,
+}
+
+impl Serialize for SafeOperation {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+/// A Safe transaction, to be signed by the owners of the Safe and executed
+/// with `execTransaction`. Gas is not refunded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeTransaction {
+    /// The contract called
+    pub to: Address,
+    /// The value sent
+    #[serde(serialize_with = "serialize_decimal")]
+    pub value: U256,
+    /// The calldata
+    pub data: Bytes,
+    /// Whether the target is called or delegate called
+    pub operation: SafeOperation,
+    /// The nonce of the Safe the transaction is executed at
+    #[serde(serialize_with = "serialize_decimal")]
+    pub nonce: U256,
+}
+
+/// A batch of transactions in the format imported by the Safe transaction
+/// builder
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeTxBuilderBatch {
+    version: &'static str,
+    /// The EVM chain ID
+    chain_id: String,
+    /// When the batch was built, in milliseconds since the epoch
+    created_at: u128,
+    meta: SafeTxBuilderMeta,
+    transactions: Vec<GovernanceTransaction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SafeTxBuilderMeta {
+    name: String,
+    created_from_safe_address: Address,
+}
+
+/// The governance transactions of a chain, all submitted by its owner
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GovernanceBatch {
+    /// The transactions, in the order they are executed in
+    pub transactions: Vec<GovernanceTransaction>,
+}
+
+impl GovernanceBatch {
+    /// Add a call to a contract
+    pub fn push(&mut self, contract: H256, call: GovernanceCall) -> &mut Self {
+        self.transactions.push(GovernanceTransaction {
+            to: contract.into(),
+            value: U256::zero(),
+            data: call.calldata().into(),
+        });
+        self
+    }
+
+    /// The Safe transaction executing the batch, calling its only transaction
+    /// or delegate calling `multi_send` to execute them all at once. None if
+    /// the batch is empty.
+    pub fn safe_transaction(&self, multi_send: Address, nonce: U256) -> Option<SafeTransaction> {
+        let transaction = match self.transactions.as_slice() {
+            [] => return None,
+            [transaction] => SafeTransaction {
+                to: transaction.to,
+                value: transaction.value,
+                data: transaction.data.clone(),
+                operation: SafeOperation::Call,
+                nonce,
+            },
+            transactions => SafeTransaction {
+                to: multi_send,
+                value: U256::zero(),
+                data: multi_send_calldata(transactions).into(),
+                operation: SafeOperation::DelegateCall,
+                nonce,
+            },
+        };
+        Some(transaction)
+    }
+
+    /// The batch in the format imported by the Safe transaction builder
+    pub fn safe_tx_builder_batch(
+        &self,
+        name: &str,
+        chain_id: u64,
+        safe: Address,
+    ) -> SafeTxBuilderBatch {
+        SafeTxBuilderBatch {
+            version: "1.0",
+            chain_id: chain_id.to_string(),
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis(),
+            meta: SafeTxBuilderMeta {
+                name: name.to_owned(),
+                created_from_safe_address: safe,
+            },
+            transactions: self.transactions.clone(),
+        }
+    }
+}
+
+/// The calldata of `multiSend`, whose transactions are packed one after the
+/// other as operation, target, value, data length and data
+fn multi_send_calldata(transactions: &[GovernanceTransaction]) -> Vec<u8> {
+    let mut packed = vec![];
+    for transaction in transactions {
+        let mut word = [0u8; 32];
+        packed.push(SafeOperation::Call as u8);
+        packed.extend(transaction.to.as_bytes());
+        transaction.value.to_big_endian(&mut word);
+        packed.extend(word);
+        U256::from(transaction.data.len()).to_big_endian(&mut word);
+        packed.extend(word);
+        packed.extend(transaction.data.iter());
+    }
+    let mut calldata = id("multiSend(bytes)").to_vec();
+    calldata.extend(abi::encode(&[Token::Bytes(packed)]));
+    calldata
+}
+
+/// Governance batches across chains
+#[derive(Debug, Clone, Default)]
+pub struct GovernanceBundle {
+    batches: BTreeMap<u32, (HyperlaneDomain, GovernanceBatch)>,
+}
+
+impl GovernanceBundle {
+    /// The batch of a chain, created if it has none yet
+    pub fn batch(&mut self, domain: &HyperlaneDomain) -> &mut GovernanceBatch {
+        &mut self
+            .batches
+            .entry(domain.id())
+            .or_insert_with(|| (domain.clone(), GovernanceBatch::default()))
+            .1
+    }
+
+    /// The batches of each chain, by domain id
+    pub fn batches(&self) -> impl Iterator<Item = (&HyperlaneDomain, &GovernanceBatch)> {
+        self.batches.values().map(|(domain, batch)| (domain, batch))
+    }
+
+    /// The raw calldata of each chain, by domain name
+    pub fn calldata(&self) -> BTreeMap<String, Vec<GovernanceTransaction>> {
+        self.batches()
+            .map(|(domain, batch)| (domain.name().to_owned(), batch.transactions.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_governance_batch() {
+        let owner = H256::from_low_u64_be(0xbeef);
+        assert_eq!(
+            hex::encode(GovernanceCall::TransferOwnership { owner }.calldata()),
+            format!("f2fde38b{:0>64}", "beef")
+        );
+
+        let mut batch = GovernanceBatch::default();
+        let multi_send: Address = MULTI_SEND_CALL_ONLY.parse().unwrap();
+        assert_eq!(batch.safe_transaction(multi_send, 0.into()), None);
+
+        let contract = H256::from_low_u64_be(0xc0ffee);
+        batch.push(contract, GovernanceCall::TransferOwnership { owner });
+        let single = batch.safe_transaction(multi_send, 0.into()).unwrap();
+        assert_eq!(single.operation, SafeOperation::Call);
+        assert_eq!(single.to, Address::from(contract));
+
+        let routers = vec![(1, H256::from_low_u64_be(1)), (2, H256::from_low_u64_be(2))];
+        batch.push(contract, GovernanceCall::EnrollRemoteRouters { routers });
+        let multi = batch.safe_transaction(multi_send, 1.into()).unwrap();
+        assert_eq!(multi.operation, SafeOperation::DelegateCall);
+        assert_eq!(multi.to, multi_send);
+        let packed_len = batch
+            .transactions
+            .iter()
+            .map(|transaction| 1 + 20 + 32 + 32 + transaction.data.len())
+            .sum::<usize>();
+        // Selector, offset and length of the packed transactions, padded
+        assert_eq!(multi.data.len(), 4 + 32 + 32 + packed_len.div_ceil(32) * 32);
+
+        let json = serde_json::to_value(&multi).unwrap();
+        assert_eq!(json["operation"], 1);
+        assert_eq!(json["nonce"], "1");
+    }
+}
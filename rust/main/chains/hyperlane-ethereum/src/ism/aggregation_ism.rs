@@ -16,7 +16,9 @@ use hyperlane_core::{
 use crate::interfaces::i_aggregation_ism::{
     IAggregationIsm as EthereumAggregationIsmInternal, IAGGREGATIONISM_ABI,
 };
-use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider};
+use crate::{
+    error_registry::decode_contract_error, BuildableWithProvider, ConnectionConf, EthereumProvider,
+};
 
 pub struct AggregationIsmBuilder {}
 
@@ -102,7 +104,8 @@ where
             .contract
             .modules_and_threshold(RawHyperlaneMessage::from(message).to_vec().into())
             .call()
-            .await?;
+            .await
+            .map_err(decode_contract_error)?;
         let isms_h256 = isms.iter().map(|address| (*address).into()).collect();
         Ok((isms_h256, threshold))
     }
@@ -6,9 +6,9 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use ethers::providers::Middleware;
-use tracing::{instrument, warn};
+use tracing::{debug, instrument, warn};
 
-use futures_util::future::try_join;
+use futures_util::future::join;
 use hyperlane_core::{
     ChainResult, ContractLocator, HyperlaneAbi, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
     HyperlaneMessage, HyperlaneProvider, InterchainSecurityModule, ModuleType, RawHyperlaneMessage,
@@ -20,7 +20,9 @@ use crate::interfaces::i_interchain_security_module::{
     IInterchainSecurityModule as EthereumInterchainSecurityModuleInternal,
     IINTERCHAINSECURITYMODULE_ABI,
 };
-use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider};
+use crate::{
+    error_registry::decode_contract_error, BuildableWithProvider, ConnectionConf, EthereumProvider,
+};
 
 pub struct InterchainSecurityModuleBuilder {}
 
@@ -101,7 +103,12 @@ where
 {
     #[instrument]
     async fn module_type(&self) -> ChainResult<ModuleType> {
-        let module = self.contract.module_type().call().await?;
+        let module = self
+            .contract
+            .module_type()
+            .call()
+            .await
+            .map_err(decode_contract_error)?;
         if let Some(module_type) = ModuleType::from_u8(module) {
             Ok(module_type)
         } else {
@@ -120,12 +127,22 @@ where
             metadata.to_owned().into(),
             RawHyperlaneMessage::from(message).to_vec().into(),
         );
-        let (verifies, gas_estimate) = try_join(tx.call(), tx.estimate_gas()).await?;
-        if verifies {
-            Ok(Some(gas_estimate.into()))
-        } else {
-            Ok(None)
+        let (verifies, gas_estimate) = join(tx.call(), tx.estimate_gas()).await;
+        let verifies = match verifies {
+            Ok(verifies) => verifies,
+            // ISMs mostly reject invalid metadata by reverting rather than
+            // returning false, which isn't an error of the dry run
+            Err(err) if err.as_revert().is_some() => {
+                debug!(error = %decode_contract_error(err), "ISM verify reverted");
+                return Ok(None);
+            }
+            Err(err) => return Err(decode_contract_error(err)),
+        };
+        if !verifies {
+            return Ok(None);
         }
+        let gas_estimate = gas_estimate.map_err(decode_contract_error)?;
+        Ok(Some(gas_estimate.into()))
     }
 }
 
@@ -16,7 +16,9 @@ use hyperlane_core::{
 use crate::interfaces::i_multisig_ism::{
     IMultisigIsm as EthereumMultisigIsmInternal, IMULTISIGISM_ABI,
 };
-use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider};
+use crate::{
+    error_registry::decode_contract_error, BuildableWithProvider, ConnectionConf, EthereumProvider,
+};
 
 impl<M> std::fmt::Display for EthereumMultisigIsmInternal<M>
 where
@@ -108,7 +110,8 @@ where
             .contract
             .validators_and_threshold(RawHyperlaneMessage::from(message).to_vec().into())
             .call()
-            .await?;
+            .await
+            .map_err(decode_contract_error)?;
         let validators: Vec<H256> = validator_addresses.iter().map(|&x| H256::from(x)).collect();
         Ok((validators, threshold))
     }
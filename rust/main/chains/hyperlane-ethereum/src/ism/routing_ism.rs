@@ -16,7 +16,9 @@ use hyperlane_core::{
 use crate::interfaces::i_routing_ism::{
     IRoutingIsm as EthereumRoutingIsmInternal, IROUTINGISM_ABI,
 };
-use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider};
+use crate::{
+    error_registry::decode_contract_error, BuildableWithProvider, ConnectionConf, EthereumProvider,
+};
 
 pub struct RoutingIsmBuilder {}
 
@@ -96,7 +98,8 @@ where
             .contract
             .route(RawHyperlaneMessage::from(message).to_vec().into())
             .call()
-            .await?;
+            .await
+            .map_err(decode_contract_error)?;
         Ok(ism.into())
     }
 }
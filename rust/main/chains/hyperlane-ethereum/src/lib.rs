@@ -8,17 +8,27 @@ use std::collections::HashMap;
 use ethers::abi::FunctionExt;
 use ethers::prelude::{abi, Lazy, Middleware};
 
-pub use self::{config::*, contracts::*, ism::*, rpc_clients::*, signer::*};
+pub use self::{
+    config::*, contracts::*, error_registry::ContractErrorRegistry, ism::*, rpc_clients::*,
+    signer::*,
+};
 
 mod tx;
 
 mod contracts;
 
+/// Governance transactions built for multisig owners
+pub mod governance;
+
 mod ism;
 
 /// Generated contract bindings.
 mod interfaces;
 
+/// Local anvil chains for integration tests.
+#[cfg(feature = "integration")]
+pub mod localnet;
+
 /// Ethers JSONRPC Client implementations
 mod rpc_clients;
 
@@ -26,6 +36,7 @@ mod signer;
 
 mod config;
 mod error;
+mod error_registry;
 
 fn extract_fn_map(abi: &'static Lazy<abi::Abi>) -> HashMap<Vec<u8>, &'static str> {
     abi.functions()
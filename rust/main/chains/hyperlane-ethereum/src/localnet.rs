@@ -0,0 +1,208 @@
+//! Local anvil chains with the core contracts deployed, for integration tests
+//! which run without docker or the typescript deployer.
+//!
+//! The contracts are deployed from the artifacts bundled in `artifacts/`,
+//! which `solidity/update_rust_artifacts.sh` exports from the solidity build.
+//! Anvil must be installed and on the `PATH`.
+
+#![allow(missing_docs)]
+
+use std::{fs, path::PathBuf, sync::Arc, time::Duration};
+
+use ethers::{
+    abi::{Abi, Tokenize},
+    contract::ContractFactory,
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Bytes, TransactionRequest, U256},
+    utils::{Anvil, AnvilInstance},
+};
+use eyre::{eyre, Context, Result};
+use hyperlane_core::{ContractLocator, HyperlaneDomain, KnownHyperlaneDomain, H160, H256};
+use serde::Deserialize;
+
+pub use crate::interfaces::{
+    i_interchain_security_module::IInterchainSecurityModule,
+    i_validator_announce::IValidatorAnnounce, mailbox::Mailbox, merkle_tree_hook::MerkleTreeHook,
+};
+
+/// Client used to deploy and call the localnet contracts, signing with the
+/// first of anvil's funded accounts
+pub type LocalnetClient = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+/// How often the provider polls anvil for pending transactions
+const POLLING_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A compiled contract, as exported to `artifacts/<Contract>.json`
+#[derive(Debug, Deserialize)]
+struct Artifact {
+    abi: Abi,
+    bytecode: Bytes,
+}
+
+impl Artifact {
+    fn load(contract: &str) -> Result<Self> {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("artifacts")
+            .join(format!("{contract}.json"));
+        let artifact = fs::read_to_string(&path).wrap_err_with(|| {
+            format!(
+                "Missing artifact {}, run solidity/update_rust_artifacts.sh",
+                path.display()
+            )
+        })?;
+        serde_json::from_str(&artifact).wrap_err_with(|| format!("Invalid artifact {contract}"))
+    }
+}
+
+/// Handles to the core contracts deployed on a localnet
+#[derive(Debug, Clone)]
+pub struct LocalnetCore {
+    pub mailbox: Mailbox<LocalnetClient>,
+    pub merkle_tree_hook: MerkleTreeHook<LocalnetClient>,
+    pub validator_announce: IValidatorAnnounce<LocalnetClient>,
+    /// ISM accepting any message, set as the mailbox's default ISM
+    pub default_ism: IInterchainSecurityModule<LocalnetClient>,
+    /// Recipient accepting any message
+    pub test_recipient: H160,
+}
+
+/// An anvil chain, killed when dropped
+#[derive(Debug)]
+pub struct Localnet {
+    domain: HyperlaneDomain,
+    client: Arc<LocalnetClient>,
+    anvil: AnvilInstance,
+}
+
+impl Localnet {
+    /// Launches an anvil chain for the `Test1` domain
+    pub async fn launch() -> Result<Self> {
+        Self::launch_for(HyperlaneDomain::Known(KnownHyperlaneDomain::Test1)).await
+    }
+
+    /// Launches an anvil chain whose chain id is the id of `domain`
+    pub async fn launch_for(domain: HyperlaneDomain) -> Result<Self> {
+        let anvil = Anvil::new().chain_id(domain.id() as u64).spawn();
+        let provider = Provider::<Http>::try_from(anvil.endpoint())?.interval(POLLING_INTERVAL);
+        let wallet = LocalWallet::from(anvil.keys()[0].clone()).with_chain_id(anvil.chain_id());
+        let client = Arc::new(SignerMiddleware::new(provider, wallet));
+        Ok(Self {
+            domain,
+            client,
+            anvil,
+        })
+    }
+
+    pub fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    /// The http url of the anvil node
+    pub fn endpoint(&self) -> String {
+        self.anvil.endpoint()
+    }
+
+    pub fn client(&self) -> Arc<LocalnetClient> {
+        self.client.clone()
+    }
+
+    /// Locator of a contract deployed on the localnet
+    pub fn locator(&self, address: H160) -> ContractLocator {
+        ContractLocator {
+            domain: &self.domain,
+            address: address.into(),
+        }
+    }
+
+    /// Deploys a contract from its bundled artifact, returning its address
+    pub async fn deploy<T: Tokenize>(&self, contract: &str, constructor_args: T) -> Result<H160> {
+        let artifact = Artifact::load(contract)?;
+        let deployed = ContractFactory::new(artifact.abi, artifact.bytecode, self.client.clone())
+            .deploy(constructor_args)?
+            .send()
+            .await
+            .wrap_err_with(|| format!("Failed to deploy {contract}"))?;
+        Ok(deployed.address())
+    }
+
+    /// Deploys and initializes a mailbox with a merkle tree hook as its
+    /// required hook, a noop ISM as its default ISM and a validator announce
+    pub async fn deploy_core(&self) -> Result<LocalnetCore> {
+        let mailbox = self.deploy("Mailbox", self.domain.id()).await?;
+        let merkle_tree_hook = self.deploy("MerkleTreeHook", mailbox).await?;
+        let default_ism = self.deploy("NoopIsm", ()).await?;
+        let validator_announce = self.deploy("ValidatorAnnounce", mailbox).await?;
+        let test_recipient = self.deploy("TestRecipient", ()).await?;
+
+        let mailbox = Mailbox::new(mailbox, self.client.clone());
+        mailbox
+            .initialize(
+                self.client.address(),
+                default_ism,
+                merkle_tree_hook,
+                merkle_tree_hook,
+            )
+            .send()
+            .await?
+            .await?
+            .ok_or_else(|| eyre!("Mailbox initialization was dropped"))?;
+
+        Ok(LocalnetCore {
+            mailbox,
+            merkle_tree_hook: MerkleTreeHook::new(merkle_tree_hook, self.client.clone()),
+            validator_announce: IValidatorAnnounce::new(validator_announce, self.client.clone()),
+            default_ism: IInterchainSecurityModule::new(default_ism, self.client.clone()),
+            test_recipient,
+        })
+    }
+
+    /// Mines `blocks` empty blocks
+    pub async fn mine(&self, blocks: u64) -> Result<()> {
+        self.client
+            .provider()
+            .request::<_, ()>("anvil_mine", [U256::from(blocks)])
+            .await?;
+        Ok(())
+    }
+}
+
+impl LocalnetCore {
+    /// Dispatches a message through the mailbox, returning its id. The
+    /// merkle tree hook charges no fee, so nothing is paid.
+    pub async fn dispatch(
+        &self,
+        destination: u32,
+        recipient: H256,
+        body: impl Into<Bytes>,
+    ) -> Result<H256> {
+        // `dispatch` is overloaded, and so are its generated bindings' names
+        let dispatch = self
+            .mailbox
+            .abi()
+            .functions_by_name("dispatch")?
+            .iter()
+            .find(|function| function.inputs.len() == 3)
+            .ok_or_else(|| eyre!("Mailbox ABI has no 3 argument dispatch"))?;
+        let args = (destination, recipient, body.into()).into_tokens();
+        let data = dispatch.encode_input(&args)?;
+        let client = self.mailbox.client();
+        let receipt = client
+            .send_transaction(
+                TransactionRequest::new()
+                    .to(self.mailbox.address())
+                    .data(data),
+                None,
+            )
+            .await?
+            .await?
+            .ok_or_else(|| eyre!("Dispatch transaction was dropped"))?;
+        let dispatched = receipt
+            .logs
+            .iter()
+            .find(|log| log.address == self.mailbox.address() && log.topics.len() == 2)
+            .ok_or_else(|| eyre!("Dispatch transaction emitted no DispatchId"))?;
+        Ok(dispatched.topics[1])
+    }
+}
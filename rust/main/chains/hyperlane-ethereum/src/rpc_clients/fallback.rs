@@ -1,30 +1,112 @@
-use derive_new::new;
-use hyperlane_core::rpc_clients::{BlockNumberGetter, FallbackProvider};
+use hyperlane_core::rpc_clients::{BlockNumberGetter, FallbackProvider, PrioritizedProviderInner};
 use std::fmt::{Debug, Formatter};
 use std::ops::Deref;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 use async_trait::async_trait;
 use ethers::providers::{HttpClientError, JsonRpcClient, ProviderError};
+use ethers::types::U64;
+use futures_util::stream::{FuturesUnordered, StreamExt};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
 use tokio::time::sleep;
-use tracing::{instrument, warn_span};
+use tracing::{debug, instrument, warn_span};
 
-use ethers_prometheus::json_rpc_client::{JsonRpcBlockGetter, PrometheusJsonRpcClientConfigExt};
+use ethers_prometheus::json_rpc_client::{
+    JsonRpcBlockGetter, JsonRpcClientMetrics, PrometheusJsonRpcClientConfigExt, BLOCK_NUMBER_RPC,
+};
 
-use crate::rpc_clients::{categorize_client_response, CategorizedResponse};
+use crate::rpc_clients::{
+    categorize_client_response, CategorizedResponse, ProviderRanking, RequestCategory,
+};
 
-/// Wrapper of `FallbackProvider` for use in `hyperlane-ethereum`
-#[derive(new)]
-pub struct EthereumFallbackProvider<C, B>(FallbackProvider<C, B>);
+/// Wrapper of `FallbackProvider` for use in `hyperlane-ethereum`.
+///
+/// Requests go to the providers ranked best for their category by the
+/// latency, error rate and block height of their latest requests, with the
+/// fallback provider's priorities breaking ties.
+pub struct EthereumFallbackProvider<C, B> {
+    fallback: FallbackProvider<C, B>,
+    ranking: ProviderRanking,
+    metrics: Option<JsonRpcClientMetrics>,
+    /// Whether transactions are sent to all providers at once
+    broadcast: bool,
+}
+
+impl<C, B> EthereumFallbackProvider<C, B> {
+    /// Wrap `fallback`, ranking its providers
+    pub fn new(fallback: FallbackProvider<C, B>) -> Self {
+        let ranking = ProviderRanking::new(fallback.inner.providers.len());
+        Self {
+            fallback,
+            ranking,
+            metrics: None,
+            broadcast: false,
+        }
+    }
+
+    /// Send transactions to all providers at once rather than to the best
+    /// one, so that a provider dropping them does not delay them
+    pub fn with_broadcast(mut self, broadcast: bool) -> Self {
+        self.broadcast = broadcast;
+        self
+    }
+
+    /// Export the rank of the providers to `metrics`
+    pub fn with_metrics(mut self, metrics: JsonRpcClientMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+}
+
+impl<C, B> EthereumFallbackProvider<C, B>
+where
+    C: PrometheusJsonRpcClientConfigExt,
+{
+    /// The priorities of the providers, sorted from the best to the worst
+    /// provider for `category`
+    async fn ranked_priorities(&self, category: RequestCategory) -> Vec<PrioritizedProviderInner> {
+        let mut priorities = self.take_priorities_snapshot().await;
+        self.ranking.rank(category, &mut priorities);
+        if let Some(metrics) = &self.metrics {
+            for (rank, priority) in priorities.iter().enumerate() {
+                let provider = &self.inner.providers[priority.index];
+                metrics.record_provider_rank(provider, category.as_str(), rank);
+            }
+        }
+        priorities
+    }
+
+    /// Record the outcome of a request to the provider at `index` in its
+    /// ranking
+    fn record_response(
+        &self,
+        index: usize,
+        method: &str,
+        latency: Duration,
+        response: &CategorizedResponse<Value>,
+    ) {
+        use CategorizedResponse::*;
+        // The node answered an invalid request, which says nothing of the node
+        let success = matches!(response, IsOk(_) | NonRetryableErr(_));
+        self.ranking
+            .record_request(index, RequestCategory::of(method), latency, success);
+        if let IsOk(value) = response {
+            if method == BLOCK_NUMBER_RPC {
+                if let Ok(height) = serde_json::from_value::<U64>(value.clone()) {
+                    self.ranking.record_block_height(index, height.as_u64());
+                }
+            }
+        }
+    }
+}
 
 impl<C, B> Deref for EthereumFallbackProvider<C, B> {
     type Target = FallbackProvider<C, B>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.fallback
     }
 }
 
@@ -58,6 +140,58 @@ where
     }
 }
 
+impl<C> EthereumFallbackProvider<C, JsonRpcBlockGetter<C>>
+where
+    C: JsonRpcClient<Error = HttpClientError> + PrometheusJsonRpcClientConfigExt + Clone + 'static,
+{
+    /// Send a transaction to all providers at once, returning the first
+    /// acceptance. The requests to the other providers are still sent, but
+    /// their responses are ignored.
+    async fn broadcast<R>(&self, method: &str, params: Value) -> Result<R, ProviderError>
+    where
+        R: DeserializeOwned,
+    {
+        use CategorizedResponse::*;
+        let mut requests = self
+            .inner
+            .providers
+            .iter()
+            .enumerate()
+            .map(|(index, provider)| {
+                let provider = provider.clone();
+                let method = method.to_owned();
+                let params = params.clone();
+                tokio::spawn(async move {
+                    let start = Instant::now();
+                    let resp = provider.request::<_, Value>(&method, params).await;
+                    (index, start.elapsed(), resp)
+                })
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        let mut errors = vec![];
+        while let Some(joined) = requests.next().await {
+            let (index, latency, resp) = match joined {
+                Ok(joined) => joined,
+                Err(error) => {
+                    errors.push(ProviderError::CustomError(error.to_string()));
+                    continue;
+                }
+            };
+            let resp = categorize_client_response(method, resp);
+            self.record_response(index, method, latency, &resp);
+            match resp {
+                IsOk(v) => {
+                    debug!(provider_index = index, "Transaction broadcast accepted");
+                    return Ok(serde_json::from_value(v)?);
+                }
+                RetryableErr(e) | RateLimitErr(e) | NonRetryableErr(e) => errors.push(e.into()),
+            }
+        }
+        Err(FallbackError::AllProvidersFailed(errors).into())
+    }
+}
+
 /// Errors specific to fallback provider.
 #[derive(Error, Debug)]
 pub enum FallbackError {
@@ -79,7 +213,8 @@ where
     C: JsonRpcClient<Error = HttpClientError>
         + Into<JsonRpcBlockGetter<C>>
         + PrometheusJsonRpcClientConfigExt
-        + Clone,
+        + Clone
+        + 'static,
     JsonRpcBlockGetter<C>: BlockNumberGetter,
 {
     type Error = ProviderError;
@@ -93,6 +228,9 @@ where
     {
         use CategorizedResponse::*;
         let params = serde_json::to_value(params).expect("valid");
+        if self.broadcast && RequestCategory::of(method) == RequestCategory::Send {
+            return self.broadcast(method, params).await;
+        }
 
         let mut errors = vec![];
         // make sure we do at least 4 total retries.
@@ -100,19 +238,23 @@ where
             if !errors.is_empty() {
                 sleep(Duration::from_millis(100)).await;
             }
-            let priorities_snapshot = self.take_priorities_snapshot().await;
+            let priorities_snapshot = self.ranked_priorities(RequestCategory::of(method)).await;
             for (idx, priority) in priorities_snapshot.iter().enumerate() {
                 let provider = &self.inner.providers[priority.index];
                 let fut = match params {
                     Value::Null => provider.request(method, ()),
                     _ => provider.request(method, &params),
                 };
+                let start = Instant::now();
                 let resp = fut.await;
+                let latency = start.elapsed();
                 self.handle_stalled_provider(priority, provider).await;
                 let _span =
                     warn_span!("request", fallback_count=%idx, provider_index=%priority.index, ?provider).entered();
 
-                match categorize_client_response(method, resp) {
+                let resp = categorize_client_response(method, resp);
+                self.record_response(priority.index, method, latency, &resp);
+                match resp {
                     IsOk(v) => return Ok(serde_json::from_value(v)?),
                     RetryableErr(e) | RateLimitErr(e) => errors.push(e.into()),
                     NonRetryableErr(e) => return Err(e.into()),
@@ -239,6 +381,30 @@ mod tests {
         assert_eq!(provider_call_count, vec![0, 0, 2]);
     }
 
+    #[tokio::test]
+    async fn test_transactions_are_broadcast_to_all_providers() {
+        let providers = vec![
+            EthereumProviderMock::new(Some(Duration::from_millis(10))),
+            EthereumProviderMock::default(),
+            EthereumProviderMock::default(),
+        ];
+        let fallback_provider = FallbackProviderBuilder::default()
+            .add_providers(providers)
+            .build();
+        let ethereum_fallback_provider =
+            EthereumFallbackProvider::new(fallback_provider).with_broadcast(true);
+        ethereum_fallback_provider
+            .request::<_, u64>("eth_sendRawTransaction", ())
+            .await
+            .unwrap();
+        // The slow provider is still sent the transaction after the first
+        // acceptance
+        sleep(Duration::from_millis(50)).await;
+        let provider_call_count: Vec<_> =
+            ProviderMock::get_call_counts(&ethereum_fallback_provider).await;
+        assert_eq!(provider_call_count, vec![1, 1, 1]);
+    }
+
     // TODO: make `categorize_client_response` generic over `ProviderError` to allow testing
     // two stalled providers (so that the for loop in `request` doesn't stop after the first provider)
 }
@@ -0,0 +1,213 @@
+//! A JSON-RPC client which uses a WebSocket connection while it is up and
+//! falls back to HTTP while it is down.
+//!
+//! A background task keeps the WebSocket connected and subscribed to new
+//! heads, so the latest block number is known without polling. When the
+//! connection drops, or a request over it stalls, requests go over HTTP until
+//! it is reconnected.
+
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use ethers::providers::{
+    Authorization, JsonRpcClient, Middleware, Provider, ProviderError, RpcError, Ws, WsClientError,
+};
+use ethers::types::U64;
+use futures_util::StreamExt;
+use reqwest::Url;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout};
+use tracing::{debug, info, warn};
+
+/// How long to wait before reconnecting a dropped WebSocket
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+/// How long a request over the WebSocket may take before it is retried over
+/// HTTP. A WebSocket can stall without closing.
+const WS_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long the head from the subscription is trusted without a new one. A
+/// WebSocket can stall without closing.
+const MAX_HEAD_AGE: Duration = Duration::from_secs(60);
+
+/// The state of the WebSocket connection, shared with its background task
+#[derive(Debug, Default)]
+struct WsConnection {
+    /// The connected client, `None` while disconnected
+    client: RwLock<Option<Ws>>,
+    /// The latest head received from the subscription, and when
+    head: RwLock<Option<(u64, Instant)>>,
+}
+
+impl WsConnection {
+    fn client(&self) -> Option<Ws> {
+        self.client.read().unwrap().clone()
+    }
+
+    fn set_client(&self, client: Option<Ws>) {
+        *self.client.write().unwrap() = client;
+    }
+
+    fn update_head(&self, number: u64) {
+        *self.head.write().unwrap() = Some((number, Instant::now()));
+    }
+
+    /// The latest head if the subscription is live and recent
+    fn live_head(&self) -> Option<u64> {
+        self.client.read().unwrap().as_ref()?;
+        self.head
+            .read()
+            .unwrap()
+            .filter(|(_, received_at)| received_at.elapsed() < MAX_HEAD_AGE)
+            .map(|(number, _)| number)
+    }
+
+    /// Keep the WebSocket connected and subscribed to new heads
+    async fn maintain(self: Arc<Self>, url: Url, auth: Option<Authorization>) {
+        loop {
+            match connect_ws(&url, auth.clone()).await {
+                Ok(ws) => {
+                    let provider = Provider::new(ws.clone());
+                    match provider.subscribe_blocks().await {
+                        Ok(mut heads) => {
+                            info!(%url, "WebSocket connected, subscribed to new heads");
+                            self.set_client(Some(ws));
+                            while let Some(head) = heads.next().await {
+                                if let Some(number) = head.number {
+                                    self.update_head(number.as_u64());
+                                }
+                            }
+                            warn!(%url, "WebSocket head subscription ended, falling back to HTTP");
+                        }
+                        Err(error) => warn!(%url, ?error, "Failed to subscribe to new heads"),
+                    }
+                }
+                Err(error) => debug!(%url, ?error, "Failed to connect WebSocket"),
+            }
+            self.set_client(None);
+            sleep(RECONNECT_INTERVAL).await;
+        }
+    }
+}
+
+/// Connect a WebSocket, authenticating with `auth` if set. Unlike HTTP
+/// clients, WebSockets can't send custom headers.
+pub(crate) async fn connect_ws(
+    url: &Url,
+    auth: Option<Authorization>,
+) -> Result<Ws, WsClientError> {
+    match auth {
+        Some(auth) => Ws::connect_with_auth(url.as_str(), auth).await,
+        None => Ws::connect(url.as_str()).await,
+    }
+}
+
+/// A JSON-RPC client preferring a WebSocket connection, with an HTTP client
+/// to fall back to while it is down
+pub struct WsHttpHybridClient<C> {
+    http: Arc<C>,
+    ws_url: Url,
+    connection: Arc<WsConnection>,
+    maintainer: JoinHandle<()>,
+}
+
+impl<C> WsHttpHybridClient<C>
+where
+    C: JsonRpcClient + 'static,
+    C::Error: Into<ProviderError>,
+{
+    /// Create a client connecting to `ws_url` in the background, with `auth`
+    /// if set. Requests go over `http` until the WebSocket is connected.
+    pub fn new(ws_url: Url, auth: Option<Authorization>, http: C) -> Self {
+        let connection = Arc::new(WsConnection::default());
+        let maintainer = tokio::spawn(connection.clone().maintain(ws_url.clone(), auth));
+        Self {
+            http: Arc::new(http),
+            ws_url,
+            connection,
+            maintainer,
+        }
+    }
+}
+
+impl<C> Drop for WsHttpHybridClient<C> {
+    fn drop(&mut self) {
+        self.maintainer.abort();
+    }
+}
+
+impl<C> Debug for WsHttpHybridClient<C>
+where
+    C: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WsHttpHybridClient")
+            .field("ws_url", &self.ws_url.as_str())
+            .field("ws_connected", &self.connection.client().is_some())
+            .field("http", &self.http)
+            .finish()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<C> JsonRpcClient for WsHttpHybridClient<C>
+where
+    C: JsonRpcClient + 'static,
+    C::Error: Into<ProviderError>,
+{
+    type Error = ProviderError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        if method == "eth_blockNumber" {
+            if let Some(head) = self.connection.live_head() {
+                return Ok(serde_json::from_value(serde_json::to_value(U64::from(
+                    head,
+                ))?)?);
+            }
+        }
+        if let Some(ws) = self.connection.client() {
+            match timeout(WS_REQUEST_TIMEOUT, ws.request(method, &params)).await {
+                Ok(Ok(response)) => return Ok(response),
+                // The node answered, so the request would fail over HTTP too
+                Ok(Err(error)) if error.as_error_response().is_some() => return Err(error.into()),
+                Ok(Err(error)) => {
+                    warn!(
+                        method,
+                        ?error,
+                        "WebSocket request failed, retrying over HTTP"
+                    )
+                }
+                Err(_) => warn!(
+                    method,
+                    timeout = ?WS_REQUEST_TIMEOUT,
+                    "WebSocket request timed out, retrying over HTTP"
+                ),
+            }
+        }
+        self.http.request(method, &params).await.map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethers::providers::MockProvider;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_requests_fall_back_to_http() {
+        let http = MockProvider::new();
+        http.push(U64::from(42)).unwrap();
+        let client = WsHttpHybridClient::new("ws://127.0.0.1:1".parse().unwrap(), None, http);
+
+        assert!(client.connection.client().is_none());
+        let block_number: U64 = client.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block_number, U64::from(42));
+    }
+}
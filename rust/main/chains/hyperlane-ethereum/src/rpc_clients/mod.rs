@@ -1,11 +1,19 @@
 use ethers::providers::HttpClientError;
 use tracing::{info, trace, warn};
 
-pub use self::{fallback::*, provider::*, retrying::*, trait_builder::*};
+pub use self::{
+    fallback::*, hybrid::*, native_transfer::*, provider::*, ranking::*, retrying::*, rpc_trace::*,
+    throttled::*, trait_builder::*,
+};
 
 mod fallback;
+mod hybrid;
+mod native_transfer;
 mod provider;
+mod ranking;
 mod retrying;
+mod rpc_trace;
+mod throttled;
 mod trait_builder;
 
 enum CategorizedResponse<R> {
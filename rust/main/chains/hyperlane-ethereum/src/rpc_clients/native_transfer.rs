@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ethers::{
+    prelude::Middleware,
+    types::{Address, TransactionRequest},
+};
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneDomain,
+    HyperlaneProvider, NativeTokenTransfer, TxOutcome, U256,
+};
+use tracing::{info, instrument};
+
+use crate::{tx::track_pending_tx, BuildableWithProvider, ConnectionConf, EthereumProvider};
+
+/// Builder for native token transferers. Requires a signer, which is the
+/// account funds are sent from.
+pub struct NativeTokenTransferBuilder {}
+
+#[async_trait]
+impl BuildableWithProvider for NativeTokenTransferBuilder {
+    type Output = Box<dyn NativeTokenTransfer>;
+    const NEEDS_SIGNER: bool = true;
+
+    async fn build_with_provider<M: Middleware + 'static>(
+        &self,
+        provider: M,
+        _conn: &ConnectionConf,
+        locator: &ContractLocator,
+    ) -> Self::Output {
+        Box::new(EthereumNativeTokenTransfer::new(
+            Arc::new(provider),
+            locator.domain.clone(),
+        ))
+    }
+}
+
+/// Sends the native token of an Ethereum chain from the signer of the
+/// underlying provider.
+#[derive(Debug)]
+pub struct EthereumNativeTokenTransfer<M> {
+    provider: Arc<M>,
+    domain: HyperlaneDomain,
+}
+
+impl<M> EthereumNativeTokenTransfer<M>
+where
+    M: Middleware + 'static,
+{
+    /// Create a new native token transferer
+    pub fn new(provider: Arc<M>, domain: HyperlaneDomain) -> Self {
+        Self { provider, domain }
+    }
+}
+
+impl<M> HyperlaneChain for EthereumNativeTokenTransfer<M>
+where
+    M: Middleware + 'static,
+{
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(EthereumProvider::new(
+            self.provider.clone(),
+            self.domain.clone(),
+        ))
+    }
+}
+
+#[async_trait]
+impl<M> NativeTokenTransfer for EthereumNativeTokenTransfer<M>
+where
+    M: Middleware + 'static,
+{
+    fn sender_address(&self) -> String {
+        self.provider
+            .default_sender()
+            .map(|address| format!("{address:?}"))
+            .unwrap_or_default()
+    }
+
+    #[instrument(err, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn transfer_native(&self, recipient: String, amount: U256) -> ChainResult<TxOutcome> {
+        if self.provider.default_sender().is_none() {
+            return Err(ChainCommunicationError::SignerUnavailable);
+        }
+        // Can't use the address directly as a string, because ethers interprets it
+        // as an ENS name rather than an address.
+        let to: Address = recipient.parse()?;
+        let tx = TransactionRequest::new().to(to).value(amount);
+        info!(?to, %amount, "Sending native token transfer");
+        let pending_tx = self
+            .provider
+            .send_transaction(tx, None)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        let receipt = track_pending_tx(pending_tx).await?;
+        Ok(receipt.into())
+    }
+}
@@ -5,18 +5,29 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use derive_new::new;
+use ethers::abi::{decode, ParamType};
 use ethers::prelude::Middleware;
+use ethers::types::TransactionRequest;
+use ethers::utils::{id, keccak256};
 use ethers_core::{abi::Address, types::BlockNumber};
-use hyperlane_core::{ethers_core_types, ChainInfo, HyperlaneCustomErrorWrapper, U256};
+use futures_util::try_join;
+use hyperlane_core::{ethers_core_types, ChainInfo, HyperlaneCustomErrorWrapper, L1DataFee, U256};
 use tokio::time::sleep;
 use tracing::instrument;
 
 use hyperlane_core::{
     BlockInfo, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
-    HyperlaneDomain, HyperlaneProvider, HyperlaneProviderError, TxnInfo, TxnReceiptInfo, H256,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, HyperlaneProviderError, TxnInfo,
+    TxnReceiptInfo, H256, H512,
 };
 
-use crate::{BuildableWithProvider, ConnectionConf};
+use crate::contracts::utils::get_tagged_block_number;
+use crate::{BuildableWithProvider, ConnectionConf, FinalitySource};
+
+/// The `GasPriceOracle` predeploy of OP stack chains
+const OP_GAS_PRICE_ORACLE: ethers_core_types::H160 = ethers_core_types::H160([
+    0x42, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x0f,
+]);
 
 /// Connection to an ethereum provider. Useful for querying information about
 /// the blockchain.
@@ -24,6 +35,8 @@ use crate::{BuildableWithProvider, ConnectionConf};
 pub struct EthereumProvider<M> {
     provider: Arc<M>,
     domain: HyperlaneDomain,
+    #[new(default)]
+    finality_source: FinalitySource,
 }
 
 impl<M> HyperlaneChain for EthereumProvider<M>
@@ -35,10 +48,10 @@ where
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        Box::new(EthereumProvider::new(
-            self.provider.clone(),
-            self.domain.clone(),
-        ))
+        Box::new(
+            EthereumProvider::new(self.provider.clone(), self.domain.clone())
+                .with_finality_source(self.finality_source),
+        )
     }
 }
 
@@ -107,6 +120,17 @@ where
         Ok(!code.is_empty())
     }
 
+    #[instrument(err, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn code_hash(&self, address: &H256) -> ChainResult<Option<H256>> {
+        let code = self
+            .provider
+            .get_code(ethers_core_types::H160::from(*address), None)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        Ok(Some(keccak256(&code).into()))
+    }
+
     #[instrument(err, skip(self))]
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
     async fn get_balance(&self, address: String) -> ChainResult<U256> {
@@ -138,7 +162,7 @@ where
         // if `block` is Some at this point, we're guaranteed to have its `hash` and `number` defined,
         // so it's safe to unwrap below
         // more info at <https://docs.rs/ethers/latest/ethers/core/types/struct.Block.html#structfield.number>
-        let chain_metrics = ChainInfo::new(
+        let mut chain_metrics = ChainInfo::new(
             BlockInfo {
                 hash: block.hash.unwrap().into(),
                 timestamp: block.timestamp.as_u64(),
@@ -146,14 +170,80 @@ where
             },
             block.base_fee_per_gas.map(Into::into),
         );
+        chain_metrics.finalized_block_number =
+            get_tagged_block_number(&*self.provider, self.finality_source)
+                .await
+                .map(Into::into);
         Ok(Some(chain_metrics))
     }
+
+    #[instrument(err, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn l1_data_fee(&self) -> ChainResult<Option<L1DataFee>> {
+        let oracle_code = self
+            .provider
+            .get_code(OP_GAS_PRICE_ORACLE, None)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        if oracle_code.is_empty() {
+            return Ok(None);
+        }
+        let (l1_base_fee, blob_base_fee, base_scalar, blob_scalar, gas_price) = try_join!(
+            self.call_gas_price_oracle("l1BaseFee()"),
+            self.call_gas_price_oracle("blobBaseFee()"),
+            self.call_gas_price_oracle("baseFeeScalar()"),
+            self.call_gas_price_oracle("blobBaseFeeScalar()"),
+            async {
+                self.provider
+                    .get_gas_price()
+                    .await
+                    .map_err(ChainCommunicationError::from_other)
+            },
+        )?;
+        // The L1 fee of a compressed byte since Ecotone, whose scalars are
+        // scaled up by 1e6. Calldata bytes cost 16 L1 gas, blob bytes 1 blob gas.
+        let fee_per_compressed_byte =
+            (l1_base_fee * 16u64 * base_scalar + blob_base_fee * blob_scalar) / 1_000_000u64;
+        Ok(Some(L1DataFee {
+            fee_per_compressed_byte: fee_per_compressed_byte.into(),
+            gas_price: gas_price.into(),
+        }))
+    }
+
+    #[instrument(err, skip(self, message))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn get_dispatch_hook_metadata(
+        &self,
+        hash: &H512,
+        mailbox: &H256,
+        message: &HyperlaneMessage,
+    ) -> ChainResult<Option<Vec<u8>>> {
+        let hash: ethers_core_types::H256 = (*hash).into();
+        let Some(txn) = self
+            .provider
+            .get_transaction(hash)
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+        else {
+            return Ok(None);
+        };
+        if txn.to != Some(ethers_core_types::H160::from(*mailbox)) {
+            return Ok(None);
+        }
+        Ok(decode_dispatch_hook_metadata(&txn.input, message))
+    }
 }
 
 impl<M> EthereumProvider<M>
 where
     M: Middleware + 'static,
 {
+    /// Use `finality_source` to find the finalized block in the chain metrics
+    pub fn with_finality_source(mut self, finality_source: FinalitySource) -> Self {
+        self.finality_source = finality_source;
+        self
+    }
+
     #[instrument(err, skip(self))]
     async fn get_storage_at(&self, address: H256, location: H256) -> ChainResult<H256> {
         let storage = self
@@ -167,6 +257,25 @@ where
             .map_err(ChainCommunicationError::from_other)?;
         Ok(storage.into())
     }
+
+    /// Call a getter of the `GasPriceOracle` of OP stack chains. Its
+    /// Ecotone getters revert on chains that haven't activated Ecotone yet.
+    async fn call_gas_price_oracle(&self, signature: &str) -> ChainResult<ethers::types::U256> {
+        let call = TransactionRequest::new()
+            .to(OP_GAS_PRICE_ORACLE)
+            .data(id(signature).to_vec());
+        let output = self
+            .provider
+            .call(&call.into(), None)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        if output.len() != 32 {
+            return Err(ChainCommunicationError::from_other_str(&format!(
+                "Unexpected output of GasPriceOracle.{signature}: {output}"
+            )));
+        }
+        Ok(ethers::types::U256::from_big_endian(&output))
+    }
 }
 
 /// Builder for hyperlane providers.
@@ -180,13 +289,47 @@ impl BuildableWithProvider for HyperlaneProviderBuilder {
     async fn build_with_provider<M: Middleware + 'static>(
         &self,
         provider: M,
-        _conn: &ConnectionConf,
+        conn: &ConnectionConf,
         locator: &ContractLocator,
     ) -> Self::Output {
-        Box::new(EthereumProvider::new(
-            Arc::new(provider),
-            locator.domain.clone(),
-        ))
+        Box::new(
+            EthereumProvider::new(Arc::new(provider), locator.domain.clone())
+                .with_finality_source(conn.finality_source),
+        )
+    }
+}
+
+/// The hook metadata of a call to `dispatch` on the mailbox, if `input` is
+/// the calldata of one dispatching `message`. Dispatches without hook
+/// metadata pass empty metadata to the hooks.
+fn decode_dispatch_hook_metadata(input: &[u8], message: &HyperlaneMessage) -> Option<Vec<u8>> {
+    let (selector, args) = (input.get(..4)?, &input[4..]);
+    let mut params = vec![
+        ParamType::Uint(32),
+        ParamType::FixedBytes(32),
+        ParamType::Bytes,
+    ];
+    if selector == id("dispatch(uint32,bytes32,bytes,bytes)") {
+        params.push(ParamType::Bytes);
+    } else if selector == id("dispatch(uint32,bytes32,bytes,bytes,address)") {
+        params.extend([ParamType::Bytes, ParamType::Address]);
+    } else if selector != id("dispatch(uint32,bytes32,bytes)") {
+        return None;
+    }
+    let mut tokens = decode(&params, args).ok()?.into_iter();
+    let destination = tokens.next()?.into_uint()?;
+    let recipient = tokens.next()?.into_fixed_bytes()?;
+    let body = tokens.next()?.into_bytes()?;
+    // The transaction may have dispatched other messages through other calls
+    if destination != message.destination.into()
+        || recipient != message.recipient.as_bytes()
+        || body != message.body
+    {
+        return None;
+    }
+    match tokens.next() {
+        Some(metadata) => metadata.into_bytes(),
+        None => Some(vec![]),
     }
 }
 
@@ -212,3 +355,94 @@ where
     }
     Err(HyperlaneProviderError::CouldNotFindObjectByHash(*hash).into())
 }
+
+#[cfg(test)]
+mod test {
+    use ethers::abi::{encode, Token};
+    use hyperlane_core::{Encode, StandardHookMetadata};
+
+    use super::*;
+
+    fn dispatch_calldata(
+        signature: &str,
+        message: &HyperlaneMessage,
+        extra: Vec<Token>,
+    ) -> Vec<u8> {
+        let mut tokens = vec![
+            Token::Uint(message.destination.into()),
+            Token::FixedBytes(message.recipient.as_bytes().to_vec()),
+            Token::Bytes(message.body.clone()),
+        ];
+        tokens.extend(extra);
+        [id(signature).to_vec(), encode(&tokens)].concat()
+    }
+
+    #[test]
+    fn test_decodes_dispatch_hook_metadata() {
+        let message = HyperlaneMessage {
+            destination: 42,
+            recipient: H256::repeat_byte(0xaa),
+            body: vec![1, 2, 3],
+            ..Default::default()
+        };
+        let metadata = StandardHookMetadata::default()
+            .with_gas_limit(300_000.into())
+            .to_vec();
+
+        let calldata = dispatch_calldata(
+            "dispatch(uint32,bytes32,bytes,bytes)",
+            &message,
+            vec![Token::Bytes(metadata.clone())],
+        );
+        assert_eq!(
+            decode_dispatch_hook_metadata(&calldata, &message),
+            Some(metadata.clone())
+        );
+
+        let calldata = dispatch_calldata(
+            "dispatch(uint32,bytes32,bytes,bytes,address)",
+            &message,
+            vec![
+                Token::Bytes(metadata.clone()),
+                Token::Address(ethers_core_types::H160::repeat_byte(0xbb)),
+            ],
+        );
+        assert_eq!(
+            decode_dispatch_hook_metadata(&calldata, &message),
+            Some(metadata.clone())
+        );
+
+        let calldata = dispatch_calldata("dispatch(uint32,bytes32,bytes)", &message, vec![]);
+        assert_eq!(
+            decode_dispatch_hook_metadata(&calldata, &message),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn test_ignores_calls_not_dispatching_the_message() {
+        let message = HyperlaneMessage {
+            destination: 42,
+            body: vec![1, 2, 3],
+            ..Default::default()
+        };
+        let other = HyperlaneMessage {
+            body: vec![4],
+            ..message.clone()
+        };
+        let calldata = dispatch_calldata(
+            "dispatch(uint32,bytes32,bytes,bytes)",
+            &other,
+            vec![Token::Bytes(vec![0, 1])],
+        );
+        assert_eq!(decode_dispatch_hook_metadata(&calldata, &message), None);
+
+        let calldata = [
+            id("transferRemote(uint32,bytes32,uint256)").to_vec(),
+            vec![0; 96],
+        ]
+        .concat();
+        assert_eq!(decode_dispatch_hook_metadata(&calldata, &message), None);
+        assert_eq!(decode_dispatch_hook_metadata(&[], &message), None);
+    }
+}
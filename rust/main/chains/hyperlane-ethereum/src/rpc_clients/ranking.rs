@@ -0,0 +1,239 @@
+//! Ranking of the providers of a fallback provider by how well they have been
+//! serving requests.
+//!
+//! Each provider is scored on the latency percentile and error rate of its
+//! latest requests and on how far its latest block is behind the other
+//! providers'. Reads and sends are ranked separately, with sends weighing
+//! errors and stale blocks more than latency.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use hyperlane_core::rpc_clients::PrioritizedProviderInner;
+
+/// How many of the latest requests of a category a provider is ranked on
+const SAMPLE_WINDOW: usize = 100;
+/// Providers with fewer samples than this are assumed to have the neutral
+/// latency, so that they are tried if the ranked providers are slower
+const MIN_SAMPLES: usize = 5;
+const NEUTRAL_LATENCY: Duration = Duration::from_millis(500);
+/// The latency percentile providers are ranked on
+const LATENCY_PERCENTILE: f64 = 0.9;
+/// Block heights older than this are not compared
+const MAX_BLOCK_HEIGHT_AGE: Duration = Duration::from_secs(60);
+
+const SEND_METHODS: &[&str] = &["eth_sendRawTransaction", "eth_sendTransaction"];
+
+/// The category of a request, whose providers are ranked separately
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RequestCategory {
+    /// Requests reading chain state
+    Read,
+    /// Requests submitting transactions
+    Send,
+}
+
+impl RequestCategory {
+    /// The category of requests of `method`
+    pub fn of(method: &str) -> Self {
+        if SEND_METHODS.contains(&method) {
+            Self::Send
+        } else {
+            Self::Read
+        }
+    }
+
+    /// The name of the category, used as a metric label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Send => "send",
+        }
+    }
+
+    /// How much latency, errors and block lag weigh in a provider's score,
+    /// as milliseconds per millisecond of latency, per error rate of 1 and
+    /// per block behind
+    fn weights(&self) -> (f64, f64, f64) {
+        match self {
+            Self::Read => (1., 5_000., 1_000.),
+            // A send to a stale or failing node may never land, whereas a slow
+            // one only delays it
+            Self::Send => (0.2, 20_000., 4_000.),
+        }
+    }
+}
+
+/// The outcome of a request to a provider
+#[derive(Clone, Copy, Debug)]
+struct Sample {
+    latency: Duration,
+    success: bool,
+}
+
+#[derive(Debug, Default)]
+struct ProviderStats {
+    samples: HashMap<RequestCategory, VecDeque<Sample>>,
+    /// The latest block height the provider returned, and when
+    block_height: Option<(u64, Instant)>,
+}
+
+impl ProviderStats {
+    fn fresh_block_height(&self) -> Option<u64> {
+        self.block_height
+            .filter(|(_, at)| at.elapsed() < MAX_BLOCK_HEIGHT_AGE)
+            .map(|(height, _)| height)
+    }
+
+    /// The score of the provider for `category`, lower being better
+    fn score(&self, category: RequestCategory, highest_block: Option<u64>) -> f64 {
+        let (latency_weight, error_weight, lag_weight) = category.weights();
+        let samples = self.samples.get(&category);
+        let sample_count = samples.map_or(0, VecDeque::len);
+
+        let latency = match samples {
+            Some(samples) if sample_count >= MIN_SAMPLES => {
+                let mut latencies = samples.iter().map(|s| s.latency).collect::<Vec<_>>();
+                latencies.sort();
+                let index = ((sample_count - 1) as f64 * LATENCY_PERCENTILE).round() as usize;
+                latencies[index]
+            }
+            _ => NEUTRAL_LATENCY,
+        };
+        let error_rate = match samples {
+            Some(samples) if sample_count > 0 => {
+                samples.iter().filter(|s| !s.success).count() as f64 / sample_count as f64
+            }
+            _ => 0.,
+        };
+        let blocks_behind = match (highest_block, self.fresh_block_height()) {
+            (Some(highest), Some(height)) => highest.saturating_sub(height),
+            _ => 0,
+        };
+
+        latency.as_millis() as f64 * latency_weight
+            + error_rate * error_weight
+            + blocks_behind as f64 * lag_weight
+    }
+}
+
+/// Rolling request statistics of the providers of a fallback provider, used to
+/// rank them
+#[derive(Debug)]
+pub struct ProviderRanking {
+    stats: Mutex<Vec<ProviderStats>>,
+}
+
+impl ProviderRanking {
+    /// Create a ranking of `provider_count` providers
+    pub fn new(provider_count: usize) -> Self {
+        Self {
+            stats: Mutex::new((0..provider_count).map(|_| Default::default()).collect()),
+        }
+    }
+
+    /// Record the outcome of a request to the provider at `index`. Failures
+    /// are errors of the provider rather than of the request.
+    pub fn record_request(
+        &self,
+        index: usize,
+        category: RequestCategory,
+        latency: Duration,
+        success: bool,
+    ) {
+        let mut stats = self.stats.lock().unwrap();
+        let Some(provider) = stats.get_mut(index) else {
+            return;
+        };
+        let samples = provider.samples.entry(category).or_default();
+        samples.push_back(Sample { latency, success });
+        if samples.len() > SAMPLE_WINDOW {
+            samples.pop_front();
+        }
+    }
+
+    /// Record the latest block height returned by the provider at `index`
+    pub fn record_block_height(&self, index: usize, height: u64) {
+        if let Some(provider) = self.stats.lock().unwrap().get_mut(index) {
+            provider.block_height = Some((height, Instant::now()));
+        }
+    }
+
+    /// Sort `priorities` from the best to the worst provider for `category`.
+    /// Providers with the same score keep their order.
+    pub fn rank(&self, category: RequestCategory, priorities: &mut [PrioritizedProviderInner]) {
+        let stats = self.stats.lock().unwrap();
+        let highest_block = stats.iter().filter_map(|s| s.fresh_block_height()).max();
+        let score = |priority: &PrioritizedProviderInner| {
+            stats
+                .get(priority.index)
+                .map_or(f64::MAX, |s| s.score(category, highest_block))
+        };
+        priorities.sort_by(|a, b| score(a).total_cmp(&score(b)));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ranked(ranking: &ProviderRanking, category: RequestCategory) -> Vec<usize> {
+        let mut priorities = (0..3)
+            .map(PrioritizedProviderInner::new)
+            .collect::<Vec<_>>();
+        ranking.rank(category, &mut priorities);
+        priorities.iter().map(|p| p.index).collect()
+    }
+
+    fn record_requests(ranking: &ProviderRanking, index: usize, latency_ms: u64, success: bool) {
+        for _ in 0..MIN_SAMPLES {
+            ranking.record_request(
+                index,
+                RequestCategory::Read,
+                Duration::from_millis(latency_ms),
+                success,
+            );
+        }
+    }
+
+    #[test]
+    fn test_unranked_providers_keep_their_priority() {
+        let ranking = ProviderRanking::new(3);
+        assert_eq!(ranked(&ranking, RequestCategory::Read), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_slow_and_failing_providers_are_ranked_last() {
+        let ranking = ProviderRanking::new(3);
+        record_requests(&ranking, 0, 2_000, true);
+        record_requests(&ranking, 1, 100, false);
+        record_requests(&ranking, 2, 100, true);
+
+        assert_eq!(ranked(&ranking, RequestCategory::Read), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn test_stale_providers_are_ranked_last() {
+        let ranking = ProviderRanking::new(3);
+        ranking.record_block_height(0, 100);
+        ranking.record_block_height(1, 105);
+        ranking.record_block_height(2, 105);
+
+        assert_eq!(ranked(&ranking, RequestCategory::Send), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn test_categories_are_ranked_separately() {
+        let ranking = ProviderRanking::new(3);
+        record_requests(&ranking, 0, 100, false);
+
+        assert_eq!(ranked(&ranking, RequestCategory::Read), vec![1, 2, 0]);
+        assert_eq!(ranked(&ranking, RequestCategory::Send), vec![0, 1, 2]);
+        assert_eq!(
+            RequestCategory::of("eth_sendRawTransaction"),
+            RequestCategory::Send
+        );
+        assert_eq!(RequestCategory::of("eth_call"), RequestCategory::Read);
+    }
+}
@@ -1,6 +1,6 @@
 use std::{fmt::Debug, str::FromStr, time::Duration};
 
-use crate::rpc_clients::{categorize_client_response, CategorizedResponse};
+use crate::rpc_clients::{categorize_client_response, CategorizedResponse, ThrottledJsonRpcClient};
 use async_trait::async_trait;
 use ethers::providers::{Http, JsonRpcClient, ProviderError};
 use ethers_prometheus::json_rpc_client::{
@@ -165,8 +165,8 @@ where
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
-impl JsonRpcClient for RetryingProvider<PrometheusJsonRpcClient<Http>> {
-    type Error = RetryingProviderError<PrometheusJsonRpcClient<Http>>;
+impl JsonRpcClient for RetryingProvider<PrometheusJsonRpcClient<ThrottledJsonRpcClient<Http>>> {
+    type Error = RetryingProviderError<PrometheusJsonRpcClient<ThrottledJsonRpcClient<Http>>>;
 
     #[instrument(skip(self), fields(provider_host = %self.inner.node_host(), chain_name = %self.inner.chain_name()))]
     async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
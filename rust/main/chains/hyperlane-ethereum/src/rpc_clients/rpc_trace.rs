@@ -0,0 +1,368 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Debug, Formatter};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::thread;
+
+use async_trait::async_trait;
+use ethers::providers::{HttpClientError, JsonRpcClient, JsonRpcError, ProviderError, RpcError};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+/// A request sent to the RPCs of a chain and the response it got, as
+/// written to RPC traces
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcTraceEntry {
+    /// The JSON-RPC method
+    pub method: String,
+    /// The params of the request
+    pub params: Value,
+    /// The response to the request
+    pub response: RpcTraceResponse,
+}
+
+/// The response to a request in an RPC trace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RpcTraceResponse {
+    /// The result of a successful request
+    Result(Value),
+    /// A JSON-RPC error returned by the node
+    Error(JsonRpcError),
+    /// Any other error, e.g. the node being unreachable
+    TransportError(String),
+}
+
+/// Whether the requests to the RPCs of a chain are recorded to or replayed
+/// from an RPC trace
+#[derive(Debug, Clone)]
+pub enum RpcTraceConf {
+    /// Record the requests and their responses
+    Record(Arc<RpcTraceRecorder>),
+    /// Answer requests with the responses recorded for them, without
+    /// connecting to the RPCs
+    Replay(Arc<RpcTraceReplayer>),
+}
+
+/// Appends the requests to the RPCs of a chain and their responses to an RPC
+/// trace file, one JSON entry per line. It is shared by all the providers of
+/// the chain.
+///
+/// The file is only created once the first client recording to it is built,
+/// and is written by a dedicated thread, so that requests never wait on it.
+pub struct RpcTraceRecorder {
+    path: PathBuf,
+    writer: Mutex<Option<mpsc::Sender<Vec<u8>>>>,
+}
+
+impl RpcTraceRecorder {
+    /// Record to the file at `path`, replacing any previous trace
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            writer: Mutex::new(None),
+        }
+    }
+
+    /// The path of the trace file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The channel to the thread writing the trace file, creating the file
+    /// and starting the thread if no client recorded to it yet
+    fn writer(&self) -> io::Result<mpsc::Sender<Vec<u8>>> {
+        let mut writer = self.writer.lock().unwrap();
+        if let Some(writer) = writer.as_ref() {
+            return Ok(writer.clone());
+        }
+        let mut file = File::create(&self.path)?;
+        let (sender, receiver) = mpsc::channel::<Vec<u8>>();
+        thread::Builder::new()
+            .name("rpc-trace-writer".to_owned())
+            .spawn(move || {
+                for line in receiver {
+                    if let Err(err) = file.write_all(&line) {
+                        warn!(?err, "Failed to record RPC trace entry");
+                    }
+                }
+            })?;
+        *writer = Some(sender.clone());
+        Ok(sender)
+    }
+}
+
+impl Debug for RpcTraceRecorder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcTraceRecorder")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Answers requests with the responses an RPC trace recorded for the same
+/// method and params, in the order they were recorded. Once only one is
+/// left, it answers every further request, so that polling the same state
+/// keeps getting its last recorded value.
+///
+/// The trace file is only read once the first client replaying it is built.
+pub struct RpcTraceReplayer {
+    path: PathBuf,
+    responses: OnceLock<Mutex<HashMap<(String, String), VecDeque<RpcTraceResponse>>>>,
+}
+
+impl RpcTraceReplayer {
+    /// Replay the trace recorded in the file at `path`
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            responses: OnceLock::new(),
+        }
+    }
+
+    /// Replay the given entries, e.g. ones written by hand in a test
+    pub fn from_entries(entries: impl IntoIterator<Item = RpcTraceEntry>) -> Self {
+        let replayer = Self::new(PathBuf::new());
+        let _ = replayer.responses.set(Mutex::new(Self::index(entries)));
+        replayer
+    }
+
+    /// The path of the trace file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read the trace file, unless it was already read
+    fn load(&self) -> io::Result<()> {
+        if self.responses.get().is_some() {
+            return Ok(());
+        }
+        let entries = BufReader::new(File::open(&self.path)?)
+            .lines()
+            .filter(|line| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+            .map(|line| {
+                serde_json::from_str::<RpcTraceEntry>(&line?)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        // Another client of the chain may have read it in the meantime
+        let _ = self.responses.set(Mutex::new(Self::index(entries)));
+        Ok(())
+    }
+
+    fn index(
+        entries: impl IntoIterator<Item = RpcTraceEntry>,
+    ) -> HashMap<(String, String), VecDeque<RpcTraceResponse>> {
+        let mut responses: HashMap<_, VecDeque<_>> = HashMap::new();
+        for entry in entries {
+            responses
+                .entry((entry.method, entry.params.to_string()))
+                .or_default()
+                .push_back(entry.response);
+        }
+        responses
+    }
+
+    fn next_response(&self, method: &str, params: &Value) -> Option<RpcTraceResponse> {
+        let mut responses = self.responses.get()?.lock().unwrap();
+        let queue = responses.get_mut(&(method.to_owned(), params.to_string()))?;
+        if queue.len() > 1 {
+            queue.pop_front()
+        } else {
+            queue.front().cloned()
+        }
+    }
+}
+
+impl Debug for RpcTraceReplayer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcTraceReplayer")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A JSON-RPC client recording the requests sent through it and their
+/// responses to an RPC trace
+#[derive(Debug, Clone)]
+pub struct RecordingJsonRpcClient<C> {
+    inner: C,
+    writer: mpsc::Sender<Vec<u8>>,
+}
+
+impl<C> RecordingJsonRpcClient<C> {
+    /// Wrap `inner`, recording its requests with `recorder`. Fails if the
+    /// trace file can't be created.
+    pub fn new(inner: C, recorder: &RpcTraceRecorder) -> io::Result<Self> {
+        Ok(Self {
+            inner,
+            writer: recorder.writer()?,
+        })
+    }
+
+    fn record(&self, entry: &RpcTraceEntry) {
+        let mut line = match serde_json::to_vec(entry) {
+            Ok(line) => line,
+            Err(err) => {
+                warn!(?err, method = %entry.method, "Failed to serialize RPC trace entry");
+                return;
+            }
+        };
+        line.push(b'\n');
+        if self.writer.send(line).is_err() {
+            warn!(method = %entry.method, "RPC trace writer stopped, not recording entry");
+        }
+    }
+
+    /// The inner RpcClient implementation
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<C> JsonRpcClient for RecordingJsonRpcClient<C>
+where
+    C: JsonRpcClient,
+    C::Error: Into<ProviderError>,
+{
+    type Error = ProviderError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        let params_value = serde_json::to_value(&params)?;
+        let result = self
+            .inner
+            .request::<T, Value>(method, params)
+            .await
+            .map_err(Into::into);
+        let response = match &result {
+            Ok(value) => RpcTraceResponse::Result(value.clone()),
+            Err(err) => match err.as_error_response() {
+                Some(err) => RpcTraceResponse::Error(err.clone()),
+                None => RpcTraceResponse::TransportError(err.to_string()),
+            },
+        };
+        self.record(&RpcTraceEntry {
+            method: method.to_owned(),
+            params: params_value,
+            response,
+        });
+        Ok(serde_json::from_value(result?)?)
+    }
+}
+
+/// A JSON-RPC client answering requests from an RPC trace, without
+/// connecting to any RPC. It replays incidents deterministically, e.g. in
+/// tests.
+#[derive(Debug, Clone)]
+pub struct ReplayJsonRpcClient {
+    replayer: Arc<RpcTraceReplayer>,
+}
+
+impl ReplayJsonRpcClient {
+    /// Answer requests with the responses of `replayer`. Fails if the trace
+    /// file can't be read.
+    pub fn new(replayer: Arc<RpcTraceReplayer>) -> io::Result<Self> {
+        replayer.load()?;
+        Ok(Self { replayer })
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl JsonRpcClient for ReplayJsonRpcClient {
+    type Error = ProviderError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        let params = serde_json::to_value(&params)?;
+        let Some(response) = self.replayer.next_response(method, &params) else {
+            return Err(ProviderError::CustomError(format!(
+                "No response recorded for `{method}` with params {params}"
+            )));
+        };
+        match response {
+            RpcTraceResponse::Result(value) => Ok(serde_json::from_value(value)?),
+            // Replayed as HTTP errors, so that they are handled like the
+            // recorded ones were
+            RpcTraceResponse::Error(err) => Err(ProviderError::JsonRpcClientError(Box::new(
+                HttpClientError::JsonRpcError(err),
+            ))),
+            RpcTraceResponse::TransportError(err) => Err(ProviderError::CustomError(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethers::providers::MockProvider;
+    use ethers::types::U64;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_replays_recorded_responses() {
+        let path = std::env::temp_dir().join(format!("rpc_trace_{}.jsonl", std::process::id()));
+        std::fs::write(&path, "previous trace\n").unwrap();
+        let recorder = RpcTraceRecorder::new(path.clone());
+        // Configuring the trace doesn't touch the file yet
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "previous trace\n");
+        let recording = RecordingJsonRpcClient::new(MockProvider::new(), &recorder).unwrap();
+        // The mock provider answers with the last pushed response first
+        recording.inner().push(U64::from(8)).unwrap();
+        recording.inner().push(U64::from(7)).unwrap();
+        for expected in [7u64, 8] {
+            let block: U64 = recording.request("eth_blockNumber", ()).await.unwrap();
+            assert_eq!(block, U64::from(expected));
+        }
+        // The entries are written in the background
+        for _ in 0..100 {
+            if std::fs::read_to_string(&path).unwrap().lines().count() == 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let replay =
+            ReplayJsonRpcClient::new(Arc::new(RpcTraceReplayer::new(path.clone()))).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        for expected in [7u64, 8, 8] {
+            let block: U64 = replay.request("eth_blockNumber", ()).await.unwrap();
+            assert_eq!(block, U64::from(expected));
+        }
+        assert!(replay.request::<_, U64>("eth_chainId", ()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replays_json_rpc_errors() {
+        let error = JsonRpcError {
+            code: -32000,
+            message: "header not found".to_owned(),
+            data: None,
+        };
+        let replay =
+            ReplayJsonRpcClient::new(Arc::new(RpcTraceReplayer::from_entries([RpcTraceEntry {
+                method: "eth_getBlockByNumber".to_owned(),
+                params: serde_json::json!(["0x10", false]),
+                response: RpcTraceResponse::Error(error.clone()),
+            }])))
+            .unwrap();
+
+        let err = replay
+            .request::<_, Value>("eth_getBlockByNumber", ("0x10", false))
+            .await
+            .unwrap_err();
+        assert_eq!(err.as_error_response(), Some(&error));
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt::{Debug, Formatter};
+use std::num::NonZeroU32;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ethers::providers::JsonRpcClient;
+use ethers_prometheus::json_rpc_client::{JsonRpcClientMetrics, PrometheusJsonRpcClientConfig};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::time::{sleep_until, Instant};
+use tracing::trace;
+
+/// A JSON-RPC client which keeps the requests sent to its node within a
+/// request-per-second budget. Requests over the budget are queued, and sent in
+/// order as the budget allows.
+pub struct ThrottledJsonRpcClient<C> {
+    inner: C,
+    /// The time between two requests, or `None` if there is no budget
+    interval: Option<Duration>,
+    /// When the next request may be sent, shared by the clones of the client
+    next_slot: Arc<Mutex<Instant>>,
+    metrics: JsonRpcClientMetrics,
+    config: PrometheusJsonRpcClientConfig,
+}
+
+impl<C> ThrottledJsonRpcClient<C> {
+    /// Wrap `inner`, allowing up to `requests_per_second` requests a second if
+    /// set. The time requests spend queued is recorded in `metrics`.
+    pub fn new(
+        inner: C,
+        requests_per_second: Option<NonZeroU32>,
+        metrics: JsonRpcClientMetrics,
+        config: PrometheusJsonRpcClientConfig,
+    ) -> Self {
+        Self {
+            inner,
+            interval: requests_per_second.map(|rps| Duration::from_secs(1) / rps.get()),
+            next_slot: Arc::new(Mutex::new(Instant::now())),
+            metrics,
+            config,
+        }
+    }
+
+    /// The inner RpcClient implementation
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Reserve the next free slot in the budget, returning when it starts
+    fn reserve_slot(&self, interval: Duration) -> Instant {
+        let mut next_slot = self.next_slot.lock().unwrap();
+        let slot = (*next_slot).max(Instant::now());
+        *next_slot = slot + interval;
+        slot
+    }
+
+    /// Wait until the request may be sent within the budget
+    async fn wait_for_slot(&self, method: &str) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        let slot = self.reserve_slot(interval);
+        let wait = slot.saturating_duration_since(Instant::now());
+        if wait.is_zero() {
+            return;
+        }
+        trace!(method, ?wait, "Request budget used up, queueing request");
+        self.metrics.record_queue_wait(&self.config, method, wait);
+        sleep_until(slot).await;
+    }
+}
+
+impl<C: Clone> Clone for ThrottledJsonRpcClient<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            interval: self.interval,
+            next_slot: self.next_slot.clone(),
+            metrics: self.metrics.clone(),
+            config: self.config.clone(),
+        }
+    }
+}
+
+impl<C> Debug for ThrottledJsonRpcClient<C>
+where
+    C: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThrottledJsonRpcClient")
+            .field("inner", &self.inner)
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<C> JsonRpcClient for ThrottledJsonRpcClient<C>
+where
+    C: JsonRpcClient,
+{
+    type Error = C::Error;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        self.wait_for_slot(method).await;
+        self.inner.request(method, params).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethers::providers::MockProvider;
+    use ethers_prometheus::json_rpc_client::JsonRpcClientMetricsBuilder;
+
+    use super::*;
+
+    fn throttled(requests_per_second: Option<u32>) -> ThrottledJsonRpcClient<MockProvider> {
+        ThrottledJsonRpcClient::new(
+            MockProvider::new(),
+            requests_per_second.and_then(NonZeroU32::new),
+            JsonRpcClientMetricsBuilder::default().build().unwrap(),
+            PrometheusJsonRpcClientConfig::default(),
+        )
+    }
+
+    #[test]
+    fn test_slots_are_spaced_by_the_budget() {
+        let client = throttled(Some(4));
+
+        let slots = (0..4)
+            .map(|_| client.reserve_slot(client.interval.unwrap()))
+            .collect::<Vec<_>>();
+        for pair in slots.windows(2) {
+            assert_eq!(pair[1] - pair[0], Duration::from_millis(250));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requests_over_budget_are_queued() {
+        let client = throttled(Some(20));
+        for _ in 0..3 {
+            client.inner().push(1u64).unwrap();
+        }
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            let _: u64 = client.request("eth_blockNumber", ()).await.unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_requests_without_budget_are_not_delayed() {
+        let client = throttled(None);
+        for _ in 0..3 {
+            client.inner().push(1u64).unwrap();
+        }
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            let _: u64 = client.request("eth_blockNumber", ()).await.unwrap();
+        }
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
+}
@@ -7,12 +7,14 @@ use ethers::middleware::gas_oracle::{
     GasCategory, GasOracle, GasOracleMiddleware, Polygon, ProviderOracle,
 };
 use ethers::prelude::{
-    Http, JsonRpcClient, Middleware, NonceManagerMiddleware, Provider, Quorum, QuorumProvider,
-    SignerMiddleware, WeightedProvider, Ws, WsClientError,
+    Authorization, Http, JsonRpcClient, Middleware, NonceManagerMiddleware, Provider,
+    ProviderError, Quorum, QuorumProvider, SignerMiddleware, WeightedProvider, WsClientError,
 };
-use hyperlane_core::rpc_clients::FallbackProvider;
+use hyperlane_core::rpc_clients::{with_dns_failover, FallbackProvider};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::{Client, Url};
 use thiserror::Error;
+use tracing::warn;
 
 use ethers_prometheus::json_rpc_client::{
     JsonRpcBlockGetter, JsonRpcClientMetrics, JsonRpcClientMetricsBuilder, NodeInfo,
@@ -20,11 +22,16 @@ use ethers_prometheus::json_rpc_client::{
 };
 use ethers_prometheus::middleware::{MiddlewareMetrics, PrometheusMiddlewareConf};
 use hyperlane_core::{
+    config::{RpcAuth, RpcHeadersConf},
     ChainCommunicationError, ChainResult, ContractLocator, HyperlaneDomain, KnownHyperlaneDomain,
 };
 
 use crate::signer::Signers;
-use crate::{ConnectionConf, EthereumFallbackProvider, RetryingProvider, RpcConnectionConf};
+use crate::{
+    connect_ws, ConnectionConf, EthereumFallbackProvider, RecordingJsonRpcClient,
+    ReplayJsonRpcClient, RetryingProvider, RpcConnectionConf, RpcTraceConf, ThrottledJsonRpcClient,
+    WsHttpHybridClient,
+};
 
 // This should be whatever the prometheus scrape interval is
 const HTTP_CLIENT_TIMEOUT: Duration = Duration::from_secs(60);
@@ -38,6 +45,23 @@ pub enum EthereumProviderConnectionError {
     /// Underlying websocket library threw an error
     #[error(transparent)]
     WebsocketClientError(#[from] WsClientError),
+    /// A configured RPC header isn't a valid HTTP header
+    #[error("Invalid RPC header `{0}`")]
+    InvalidHeader(String),
+    /// WebSocket connections can't be routed through a proxy
+    #[error("WebSocket connections can't be routed through a proxy")]
+    WebsocketProxy,
+    /// WebSocket connections can't present a client certificate
+    #[error("WebSocket connections can't present a client certificate")]
+    WebsocketClientCertificate,
+    /// The RPC trace file can't be created or read
+    #[error("Failed to open RPC trace `{path}`: {err}")]
+    RpcTrace {
+        /// The path of the trace file
+        path: String,
+        /// The underlying error
+        err: std::io::Error,
+    },
 }
 
 impl From<EthereumProviderConnectionError> for ChainCommunicationError {
@@ -66,15 +90,20 @@ pub trait BuildableWithProvider {
         rpc_metrics: Option<JsonRpcClientMetrics>,
         middleware_metrics: Option<(MiddlewareMetrics, PrometheusMiddlewareConf)>,
     ) -> ChainResult<Self::Output> {
+        if let Some(RpcTraceConf::Replay(replayer)) = &conn.rpc_trace {
+            let replay_client = ReplayJsonRpcClient::new(replayer.clone()).map_err(|err| {
+                EthereumProviderConnectionError::RpcTrace {
+                    path: replayer.path().display().to_string(),
+                    err,
+                }
+            })?;
+            return self.build(replay_client, conn, locator, signer).await;
+        }
         Ok(match &conn.rpc_connection {
             RpcConnectionConf::HttpQuorum { urls } => {
                 let mut builder = QuorumProvider::builder().quorum(Quorum::Majority);
-                let http_client = Client::builder()
-                    .timeout(HTTP_CLIENT_TIMEOUT)
-                    .build()
-                    .map_err(EthereumProviderConnectionError::from)?;
+                let http_client = http_client(conn)?;
                 for url in urls {
-                    let http_provider = Http::new_with_client(url.clone(), http_client.clone());
                     // Wrap the inner providers as RetryingProviders rather than the QuorumProvider.
                     // We've observed issues where the QuorumProvider will first get the latest
                     // block number and then submit an RPC at that block height,
@@ -84,9 +113,10 @@ pub trait BuildableWithProvider {
                     // RPCs being retried, while retrying at the inner provider
                     // level will result in only the second RPC being retried
                     // (the one with the error), which is the desired behavior.
-                    let metrics_provider = self.wrap_rpc_with_metrics(
-                        http_provider,
-                        url.clone(),
+                    let metrics_provider = self.build_http_client(
+                        url,
+                        &http_client,
+                        conn,
                         &rpc_metrics,
                         &middleware_metrics,
                     );
@@ -99,38 +129,17 @@ pub trait BuildableWithProvider {
                 self.build(quorum_provider, conn, locator, signer).await?
             }
             RpcConnectionConf::HttpFallback { urls } => {
-                let mut builder = FallbackProvider::builder();
-                let http_client = Client::builder()
-                    .timeout(HTTP_CLIENT_TIMEOUT)
-                    .build()
-                    .map_err(EthereumProviderConnectionError::from)?;
-                for url in urls {
-                    let http_provider = Http::new_with_client(url.clone(), http_client.clone());
-                    let metrics_provider = self.wrap_rpc_with_metrics(
-                        http_provider,
-                        url.clone(),
-                        &rpc_metrics,
-                        &middleware_metrics,
-                    );
-                    builder = builder.add_provider(metrics_provider);
-                }
-                let fallback_provider = builder.build();
-                let ethereum_fallback_provider = EthereumFallbackProvider::<
-                    _,
-                    JsonRpcBlockGetter<PrometheusJsonRpcClient<Http>>,
-                >::new(fallback_provider);
+                let ethereum_fallback_provider =
+                    self.build_fallback_provider(urls, conn, &rpc_metrics, &middleware_metrics)?;
                 self.build(ethereum_fallback_provider, conn, locator, signer)
                     .await?
             }
             RpcConnectionConf::Http { url } => {
-                let http_client = Client::builder()
-                    .timeout(HTTP_CLIENT_TIMEOUT)
-                    .build()
-                    .map_err(EthereumProviderConnectionError::from)?;
-                let http_provider = Http::new_with_client(url.clone(), http_client);
-                let metrics_provider = self.wrap_rpc_with_metrics(
-                    http_provider,
-                    url.clone(),
+                let http_client = http_client(conn)?;
+                let metrics_provider = self.build_http_client(
+                    url,
+                    &http_client,
+                    conn,
                     &rpc_metrics,
                     &middleware_metrics,
                 );
@@ -138,15 +147,101 @@ pub trait BuildableWithProvider {
                 self.build(retrying_http_provider, conn, locator, signer)
                     .await?
             }
+            RpcConnectionConf::Ws { .. } if conn.proxy.is_some() => {
+                return Err(EthereumProviderConnectionError::WebsocketProxy.into());
+            }
+            RpcConnectionConf::Ws { .. } if conn.rpc_tls.is_some() => {
+                return Err(EthereumProviderConnectionError::WebsocketClientCertificate.into());
+            }
             RpcConnectionConf::Ws { url } => {
-                let ws = Ws::connect(url)
+                let ws = connect_ws(url, ws_auth(&conn.rpc_headers))
                     .await
                     .map_err(EthereumProviderConnectionError::from)?;
                 self.build(ws, conn, locator, signer).await?
             }
+            RpcConnectionConf::WsHttpHybrid { http_urls, .. }
+                if conn.proxy.is_some() || conn.rpc_tls.is_some() =>
+            {
+                warn!(
+                    "WebSockets can't be proxied or present a client certificate, only using \
+                    the HTTP urls"
+                );
+                let ethereum_fallback_provider = self.build_fallback_provider(
+                    http_urls,
+                    conn,
+                    &rpc_metrics,
+                    &middleware_metrics,
+                )?;
+                self.build(ethereum_fallback_provider, conn, locator, signer)
+                    .await?
+            }
+            RpcConnectionConf::WsHttpHybrid { ws_url, http_urls } => {
+                let ethereum_fallback_provider = self.build_fallback_provider(
+                    http_urls,
+                    conn,
+                    &rpc_metrics,
+                    &middleware_metrics,
+                )?;
+                let hybrid_client = WsHttpHybridClient::new(
+                    ws_url.clone(),
+                    ws_auth(&conn.rpc_headers),
+                    ethereum_fallback_provider,
+                );
+                self.build(hybrid_client, conn, locator, signer).await?
+            }
         })
     }
 
+    /// Build a fallback provider over HTTP urls, in order of priority.
+    fn build_fallback_provider(
+        &self,
+        urls: &[Url],
+        conn: &ConnectionConf,
+        rpc_metrics: &Option<JsonRpcClientMetrics>,
+        middleware_metrics: &Option<(MiddlewareMetrics, PrometheusMiddlewareConf)>,
+    ) -> ChainResult<
+        EthereumFallbackProvider<
+            PrometheusJsonRpcClient<ThrottledJsonRpcClient<Http>>,
+            JsonRpcBlockGetter<PrometheusJsonRpcClient<ThrottledJsonRpcClient<Http>>>,
+        >,
+    > {
+        let mut builder = FallbackProvider::builder();
+        let http_client = http_client(conn)?;
+        for url in urls {
+            let metrics_provider =
+                self.build_http_client(url, &http_client, conn, rpc_metrics, middleware_metrics);
+            builder = builder.add_provider(metrics_provider);
+        }
+        Ok(EthereumFallbackProvider::new(builder.build())
+            .with_metrics(rpc_client_metrics(rpc_metrics))
+            .with_broadcast(conn.broadcast_transactions))
+    }
+
+    /// Create an HTTP client for `url` wrapped with metrics, which keeps the
+    /// requests within the request budget of the url if it has one.
+    fn build_http_client(
+        &self,
+        url: &Url,
+        http_client: &Client,
+        conn: &ConnectionConf,
+        rpc_metrics: &Option<JsonRpcClientMetrics>,
+        middleware_metrics: &Option<(MiddlewareMetrics, PrometheusMiddlewareConf)>,
+    ) -> PrometheusJsonRpcClient<ThrottledJsonRpcClient<Http>> {
+        let http_provider = Http::new_with_client(url.clone(), http_client.clone());
+        let throttled_provider = ThrottledJsonRpcClient::new(
+            http_provider,
+            conn.requests_per_second.get(url).copied(),
+            rpc_client_metrics(rpc_metrics),
+            rpc_client_config(url, middleware_metrics),
+        );
+        self.wrap_rpc_with_metrics(
+            throttled_provider,
+            url.clone(),
+            rpc_metrics,
+            middleware_metrics,
+        )
+    }
+
     /// Wrap a JsonRpcClient with metrics for use with a quorum provider.
     fn wrap_rpc_with_metrics<C>(
         &self,
@@ -157,35 +252,43 @@ pub trait BuildableWithProvider {
     ) -> PrometheusJsonRpcClient<C> {
         PrometheusJsonRpcClient::new(
             client,
-            rpc_metrics
-                .clone()
-                .unwrap_or_else(|| JsonRpcClientMetricsBuilder::default().build().unwrap()),
-            PrometheusJsonRpcClientConfig {
-                node: Some(NodeInfo {
-                    host: {
-                        let mut s = String::new();
-                        if let Some(host) = url.host_str() {
-                            s.push_str(host);
-                            if let Some(port) = url.port() {
-                                write!(&mut s, ":{port}").unwrap();
-                            }
-                            Some(s)
-                        } else {
-                            None
-                        }
-                    },
-                }),
-                // steal the chain info from the middleware conf
-                chain: middleware_metrics
-                    .as_ref()
-                    .and_then(|(_, v)| v.chain.clone()),
-            },
+            rpc_client_metrics(rpc_metrics),
+            rpc_client_config(&url, middleware_metrics),
         )
     }
 
+    /// Record the requests of the client to the RPC trace if one is being
+    /// recorded, and then create the provider.
+    async fn build<P>(
+        &self,
+        client: P,
+        conn: &ConnectionConf,
+        locator: &ContractLocator,
+        signer: Option<Signers>,
+    ) -> ChainResult<Self::Output>
+    where
+        P: JsonRpcClient + 'static,
+        P::Error: Into<ProviderError>,
+    {
+        match &conn.rpc_trace {
+            Some(RpcTraceConf::Record(recorder)) => {
+                let recording_client =
+                    RecordingJsonRpcClient::new(client, recorder).map_err(|err| {
+                        EthereumProviderConnectionError::RpcTrace {
+                            path: recorder.path().display().to_string(),
+                            err,
+                        }
+                    })?;
+                self.build_with_client(recording_client, conn, locator, signer)
+                    .await
+            }
+            _ => self.build_with_client(client, conn, locator, signer).await,
+        }
+    }
+
     /// Create the provider, applying any middlewares (e.g. gas oracle, signer) as needed,
     /// and then create the associated trait.
-    async fn build<P>(
+    async fn build_with_client<P>(
         &self,
         client: P,
         conn: &ConnectionConf,
@@ -234,6 +337,38 @@ pub trait BuildableWithProvider {
         M: Middleware + 'static;
 }
 
+fn rpc_client_metrics(rpc_metrics: &Option<JsonRpcClientMetrics>) -> JsonRpcClientMetrics {
+    rpc_metrics
+        .clone()
+        .unwrap_or_else(|| JsonRpcClientMetricsBuilder::default().build().unwrap())
+}
+
+fn rpc_client_config(
+    url: &Url,
+    middleware_metrics: &Option<(MiddlewareMetrics, PrometheusMiddlewareConf)>,
+) -> PrometheusJsonRpcClientConfig {
+    PrometheusJsonRpcClientConfig {
+        node: Some(NodeInfo {
+            host: {
+                let mut s = String::new();
+                if let Some(host) = url.host_str() {
+                    s.push_str(host);
+                    if let Some(port) = url.port() {
+                        write!(&mut s, ":{port}").unwrap();
+                    }
+                    Some(s)
+                } else {
+                    None
+                }
+            },
+        }),
+        // steal the chain info from the middleware conf
+        chain: middleware_metrics
+            .as_ref()
+            .and_then(|(_, v)| v.chain.clone()),
+    }
+}
+
 async fn wrap_with_signer<M: Middleware>(
     provider: M,
     signer: Signers,
@@ -248,6 +383,51 @@ async fn wrap_with_signer<M: Middleware>(
     Ok(signing_provider)
 }
 
+/// The HTTP client of a chain's RPCs, sending the configured headers with
+/// every request through the configured proxy, presenting the configured
+/// client certificate and failing over between the addresses of the hosts
+fn http_client(conn: &ConnectionConf) -> ChainResult<Client> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in conn.rpc_headers.all() {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| EthereumProviderConnectionError::InvalidHeader(name.clone()))?;
+        let mut value = HeaderValue::from_str(&value).map_err(|_| {
+            EthereumProviderConnectionError::InvalidHeader(name.as_str().to_owned())
+        })?;
+        value.set_sensitive(true);
+        headers.insert(name, value);
+    }
+    let mut builder = with_dns_failover(Client::builder())
+        .timeout(HTTP_CLIENT_TIMEOUT)
+        .default_headers(headers);
+    if let Some(proxy) = &conn.proxy {
+        let proxy = reqwest::Proxy::all(proxy.url().as_str())
+            .map_err(EthereumProviderConnectionError::from)?;
+        builder = builder.proxy(proxy);
+    }
+    if let Some(tls) = &conn.rpc_tls {
+        let identity = reqwest::Identity::from_pkcs8_pem(&tls.client_cert, &tls.client_key)
+            .map_err(EthereumProviderConnectionError::from)?;
+        builder = builder.identity(identity);
+        if let Some(ca_cert) = &tls.ca_cert {
+            let ca_cert = reqwest::Certificate::from_pem(ca_cert)
+                .map_err(EthereumProviderConnectionError::from)?;
+            builder = builder.add_root_certificate(ca_cert);
+        }
+    }
+    Ok(builder
+        .build()
+        .map_err(EthereumProviderConnectionError::from)?)
+}
+
+/// The authentication of WebSocket connections
+fn ws_auth(rpc_headers: &RpcHeadersConf) -> Option<Authorization> {
+    rpc_headers.auth.as_ref().map(|auth| match auth {
+        RpcAuth::Basic { username, password } => Authorization::basic(username, password),
+        RpcAuth::Bearer { token } => Authorization::bearer(token),
+    })
+}
+
 fn build_polygon_gas_oracle(chain: ethers_core::types::Chain) -> ChainResult<Box<dyn GasOracle>> {
     let gas_oracle = Polygon::new(chain)
         .map_err(ChainCommunicationError::from_other)?
@@ -268,11 +448,10 @@ where
 {
     let provider = Arc::new(provider);
     let gas_oracle: Box<dyn GasOracle> = {
-        match domain {
-            HyperlaneDomain::Known(KnownHyperlaneDomain::Polygon) => {
-                build_polygon_gas_oracle(ethers_core::types::Chain::Polygon)?
-            }
-            _ => Box::new(ProviderOracle::new(provider.clone())),
+        if domain.is(KnownHyperlaneDomain::Polygon) {
+            build_polygon_gas_oracle(ethers_core::types::Chain::Polygon)?
+        } else {
+            Box::new(ProviderOracle::new(provider.clone()))
         }
     };
     Ok(GasOracleMiddleware::new(provider, gas_oracle))
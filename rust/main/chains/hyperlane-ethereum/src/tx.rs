@@ -1,12 +1,14 @@
+use std::marker::PhantomData;
 use std::num::NonZeroU64;
 use std::sync::Arc;
 use std::time::Duration;
 
+use async_trait::async_trait;
 use ethers::{
     abi::Detokenize,
     prelude::{NameOrAddress, TransactionReceipt},
     providers::{JsonRpcClient, PendingTransaction, ProviderError},
-    types::{Block, Eip1559TransactionRequest, TxHash},
+    types::{transaction::eip2718::TypedTransaction, Block, Eip1559TransactionRequest, TxHash},
 };
 use ethers_contract::builders::ContractCall;
 use ethers_core::{
@@ -16,10 +18,15 @@ use ethers_core::{
         EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE,
     },
 };
-use hyperlane_core::{utils::bytes_to_hex, ChainCommunicationError, ChainResult, H256, U256};
+use hyperlane_core::{
+    utils::bytes_to_hex, ChainCommunicationError, ChainResult, TxAdapter, TxErrorClass, TxOutcome,
+    H256, U256,
+};
 use tracing::{debug, error, info, warn};
 
-use crate::{Middleware, TransactionOverrides};
+use crate::{
+    error_registry::decode_contract_error, HyperlaneEthereumError, Middleware, TransactionOverrides,
+};
 
 /// An amount of gas to add to the estimated gas
 pub const GAS_ESTIMATE_BUFFER: u32 = 75_000;
@@ -52,7 +59,8 @@ where
     // We can set the gas higher here!
     let dispatch_fut = tx.send();
     let dispatched = dispatch_fut
-        .await?
+        .await
+        .map_err(decode_contract_error)?
         .interval(PENDING_TRANSACTION_POLLING_INTERVAL);
     track_pending_tx(dispatched).await
 }
@@ -99,7 +107,11 @@ where
     // either use the pre-estimated gas limit or estimate it
     let estimated_gas_limit: U256 = match tx.tx.gas() {
         Some(&estimate) => estimate.into(),
-        None => tx.estimate_gas().await?.into(),
+        None => tx
+            .estimate_gas()
+            .await
+            .map_err(decode_contract_error)?
+            .into(),
     };
     let estimated_gas_limit = apply_gas_estimate_buffer(estimated_gas_limit);
     let gas_limit: U256 = if let Some(gas_limit) = transaction_overrides.gas_limit {
@@ -216,6 +228,147 @@ where
     Ok((base_fee_per_gas, max_fee_per_gas, max_priority_fee_per_gas))
 }
 
+/// Submits contract calls returning `D`, estimating their gas with the
+/// transaction overrides of the chain
+pub(crate) struct EthereumTxAdapter<M, D> {
+    provider: Arc<M>,
+    transaction_overrides: TransactionOverrides,
+    _return: PhantomData<fn() -> D>,
+}
+
+impl<M, D> EthereumTxAdapter<M, D> {
+    pub(crate) fn new(provider: Arc<M>, transaction_overrides: TransactionOverrides) -> Self {
+        Self {
+            provider,
+            transaction_overrides,
+            _return: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<M, D> TxAdapter for EthereumTxAdapter<M, D>
+where
+    M: Middleware + 'static,
+    D: Detokenize + Send + Sync + 'static,
+{
+    type Tx = ContractCall<M, D>;
+    type Pending = H256;
+
+    async fn estimate(&self, tx: &mut Self::Tx) -> ChainResult<()> {
+        *tx = fill_tx_gas_params(
+            tx.clone(),
+            self.provider.clone(),
+            &self.transaction_overrides,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn send(&self, tx: &mut Self::Tx) -> ChainResult<H256> {
+        // Filling the transaction through the middlewares assigns its nonce,
+        // which is kept when it is resubmitted so that it replaces the sent one
+        if tx.tx.nonce().is_none() {
+            self.provider
+                .fill_transaction(&mut tx.tx, None)
+                .await
+                .map_err(ChainCommunicationError::from_other)?;
+        }
+        let data = tx
+            .tx
+            .data()
+            .map(|b| bytes_to_hex(b))
+            .unwrap_or_else(|| "None".into());
+        info!(to=?tx.tx.to(), nonce=?tx.tx.nonce(), %data, "Dispatching transaction");
+        let pending = tx.send().await.map_err(decode_contract_error)?;
+        let tx_hash: H256 = (*pending).into();
+        info!(?tx_hash, "Dispatched tx");
+        Ok(tx_hash)
+    }
+
+    async fn confirm(&self, tx_hash: &H256) -> ChainResult<Option<TxOutcome>> {
+        let pending = PendingTransaction::new((*tx_hash).into(), self.provider.provider())
+            .interval(PENDING_TRANSACTION_POLLING_INTERVAL);
+        match track_pending_tx(pending).await {
+            Ok(receipt) => Ok(Some(receipt.into())),
+            Err(ChainCommunicationError::TransactionTimeout())
+            | Err(ChainCommunicationError::TransactionDropped(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn escalate_fee(&self, tx: &mut Self::Tx, multiplier: f64) -> bool {
+        match &mut tx.tx {
+            TypedTransaction::Eip1559(request) => {
+                let (Some(max_fee), Some(max_priority_fee)) =
+                    (request.max_fee_per_gas, request.max_priority_fee_per_gas)
+                else {
+                    return false;
+                };
+                request.max_fee_per_gas = Some(escalate(max_fee, multiplier));
+                request.max_priority_fee_per_gas = Some(escalate(max_priority_fee, multiplier));
+            }
+            tx => {
+                let Some(gas_price) = tx.gas_price() else {
+                    return false;
+                };
+                tx.set_gas_price(escalate(gas_price, multiplier));
+            }
+        }
+        true
+    }
+
+    fn classify(&self, err: &ChainCommunicationError) -> TxErrorClass {
+        match err {
+            // Node errors, e.g. of underpriced transactions, are contract
+            // errors as well, so only decoded reverts are fatal as such
+            ChainCommunicationError::ContractError(err)
+                if matches!(
+                    err.downcast_ref::<HyperlaneEthereumError>(),
+                    Some(HyperlaneEthereumError::ContractRevert { .. })
+                ) =>
+            {
+                TxErrorClass::Fatal
+            }
+            err => classify_tx_error(&err.to_string()),
+        }
+    }
+}
+
+/// Multiply a fee, rounding up so that it always increases
+fn escalate(fee: EthersU256, multiplier: f64) -> EthersU256 {
+    const PRECISION: u64 = 1_000;
+    let numerator = EthersU256::from((multiplier * PRECISION as f64).ceil() as u64);
+    let escalated = fee.saturating_mul(numerator) / PRECISION;
+    escalated.max(fee.saturating_add(1.into()))
+}
+
+/// Classify the error message of a node
+fn classify_tx_error(message: &str) -> TxErrorClass {
+    const UNDERPRICED: &[&str] = &[
+        "underpriced",
+        "fee too low",
+        "tip too low",
+        "max fee per gas less than block base fee",
+    ];
+    const RETRYABLE: &[&str] = &[
+        "connection",
+        "timed out",
+        "timeout",
+        "too many requests",
+        "rate limit",
+        "temporarily unavailable",
+    ];
+    let message = message.to_lowercase();
+    if UNDERPRICED.iter().any(|pattern| message.contains(pattern)) {
+        TxErrorClass::Underpriced
+    } else if RETRYABLE.iter().any(|pattern| message.contains(pattern)) {
+        TxErrorClass::Retryable
+    } else {
+        TxErrorClass::Fatal
+    }
+}
+
 pub(crate) async fn call_with_lag<M, T>(
     call: ethers::contract::builders::ContractCall<M, T>,
     provider: &M,
@@ -237,3 +390,78 @@ where
         Ok(call)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use ethers::{
+        providers::{HttpClientError, JsonRpcError, Provider},
+        types::{Address, Bytes},
+    };
+    use serde::{de::DeserializeOwned, Serialize};
+
+    use crate::interfaces::i_mailbox::IMailbox;
+
+    use super::*;
+
+    /// A client whose node rejects every request as underpriced
+    #[derive(Debug)]
+    struct UnderpricedClient;
+
+    #[async_trait]
+    impl JsonRpcClient for UnderpricedClient {
+        type Error = HttpClientError;
+
+        async fn request<T, R>(&self, _method: &str, _params: T) -> Result<R, Self::Error>
+        where
+            T: std::fmt::Debug + Serialize + Send + Sync,
+            R: DeserializeOwned,
+        {
+            Err(HttpClientError::JsonRpcError(JsonRpcError {
+                code: -32000,
+                message: "replacement transaction underpriced".into(),
+                data: None,
+            }))
+        }
+    }
+
+    #[test]
+    fn test_escalate_and_classify() {
+        assert_eq!(escalate(100.into(), 1.125), 112.into());
+        assert_eq!(escalate(1.into(), 1.125), 2.into());
+
+        assert_eq!(
+            classify_tx_error("replacement transaction underpriced"),
+            TxErrorClass::Underpriced
+        );
+        assert_eq!(
+            classify_tx_error("(code: 429, message: Too Many Requests)"),
+            TxErrorClass::Retryable
+        );
+        assert_eq!(classify_tx_error("nonce too low"), TxErrorClass::Fatal);
+    }
+
+    #[tokio::test]
+    async fn test_send_classifies_node_errors() {
+        let provider = Arc::new(Provider::new(UnderpricedClient));
+        let mut call = IMailbox::new(Address::zero(), provider.clone())
+            .process(Bytes::new(), Bytes::new())
+            .legacy();
+        // Set everything the provider would otherwise request
+        call.tx.set_from(Address::zero());
+        call.tx.set_nonce(1);
+        call.tx.set_gas(100_000);
+        call.tx.set_gas_price(1);
+
+        let adapter = EthereumTxAdapter::new(provider, Default::default());
+        let err = adapter.send(&mut call).await.unwrap_err();
+        assert!(matches!(err, ChainCommunicationError::ContractError(_)));
+        assert_eq!(adapter.classify(&err), TxErrorClass::Underpriced);
+
+        let revert =
+            ChainCommunicationError::from_contract_error(HyperlaneEthereumError::ContractRevert {
+                reason: "underpriced".into(),
+                data: "0x".into(),
+            });
+        assert_eq!(adapter.classify(&revert), TxErrorClass::Fatal);
+    }
+}
@@ -0,0 +1,63 @@
+//! Integration tests against a local anvil chain.
+//! Run with `cargo test -p hyperlane-ethereum --features integration`.
+
+#![cfg(feature = "integration")]
+
+use hyperlane_core::{Indexer, SequenceAwareIndexer, H256};
+use hyperlane_ethereum::{
+    localnet::Localnet, EthereumMailboxIndexer, EthereumMerkleTreeHookIndexer, FinalitySource,
+};
+
+#[tokio::test]
+async fn test_dispatched_messages_are_indexed() {
+    let localnet = Localnet::launch().await.unwrap();
+    let core = localnet.deploy_core().await.unwrap();
+    assert_eq!(
+        core.mailbox.local_domain().call().await.unwrap(),
+        localnet.domain().id()
+    );
+
+    let recipient = H256::from(core.test_recipient);
+    let mut ids = vec![];
+    for body in [b"one".to_vec(), b"two".to_vec(), b"three".to_vec()] {
+        ids.push(
+            core.dispatch(localnet.domain().id(), recipient, body)
+                .await
+                .unwrap(),
+        );
+    }
+    localnet.mine(1).await.unwrap();
+
+    let mailbox_locator = localnet.locator(core.mailbox.address());
+    let mailbox_indexer = EthereumMailboxIndexer::new(
+        localnet.client(),
+        &mailbox_locator,
+        0,
+        FinalitySource::BlockDepth,
+    );
+    let (count, tip) = mailbox_indexer
+        .latest_sequence_count_and_tip()
+        .await
+        .unwrap();
+    assert_eq!(count, Some(3));
+    let messages = mailbox_indexer.fetch_logs_in_range(0..=tip).await.unwrap();
+    let indexed_ids = messages
+        .iter()
+        .map(|(message, _)| message.inner().id())
+        .collect::<Vec<_>>();
+    assert_eq!(indexed_ids, ids);
+
+    let hook_locator = localnet.locator(core.merkle_tree_hook.address());
+    let hook_indexer = EthereumMerkleTreeHookIndexer::new(
+        localnet.client(),
+        &hook_locator,
+        0,
+        FinalitySource::BlockDepth,
+    );
+    let insertions = hook_indexer.fetch_logs_in_range(0..=tip).await.unwrap();
+    let inserted_ids = insertions
+        .iter()
+        .map(|(insertion, _)| insertion.inner().message_id())
+        .collect::<Vec<_>>();
+    assert_eq!(inserted_ids, ids);
+}
@@ -3,27 +3,47 @@ use std::ops::RangeInclusive;
 use async_trait::async_trait;
 
 use hyperlane_core::{
-    ChainResult, HyperlaneChain, HyperlaneContract, Indexed, Indexer, InterchainGasPaymaster,
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    Indexed, Indexer, InterchainGasPaymaster,
 };
 use hyperlane_core::{HyperlaneDomain, HyperlaneProvider, InterchainGasPayment, LogMeta, H256};
 
+use crate::{ConnectionConf, FuelProvider};
+
 /// A reference to an IGP contract on some Fuel chain
 #[derive(Debug)]
-pub struct FuelInterchainGasPaymaster {}
+pub struct FuelInterchainGasPaymaster {
+    address: H256,
+    provider: FuelProvider,
+    domain: HyperlaneDomain,
+}
+
+impl FuelInterchainGasPaymaster {
+    /// Create a new fuel IGP
+    pub async fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        let provider = FuelProvider::new(locator.domain.clone(), conf).await;
+
+        Ok(FuelInterchainGasPaymaster {
+            address: locator.address,
+            provider,
+            domain: locator.domain.clone(),
+        })
+    }
+}
 
 impl HyperlaneContract for FuelInterchainGasPaymaster {
     fn address(&self) -> H256 {
-        todo!()
+        self.address
     }
 }
 
 impl HyperlaneChain for FuelInterchainGasPaymaster {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        &self.domain
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.provider.clone())
     }
 }
 
@@ -31,7 +51,18 @@ impl InterchainGasPaymaster for FuelInterchainGasPaymaster {}
 
 /// Struct that retrieves event data for a Fuel IGP contract
 #[derive(Debug)]
-pub struct FuelInterchainGasPaymasterIndexer {}
+pub struct FuelInterchainGasPaymasterIndexer {
+    provider: FuelProvider,
+}
+
+impl FuelInterchainGasPaymasterIndexer {
+    /// Create a new FuelInterchainGasPaymasterIndexer
+    pub async fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        let provider = FuelProvider::new(locator.domain.clone(), conf).await;
+
+        Ok(FuelInterchainGasPaymasterIndexer { provider })
+    }
+}
 
 #[async_trait]
 impl Indexer<InterchainGasPayment> for FuelInterchainGasPaymasterIndexer {
@@ -39,10 +70,12 @@ impl Indexer<InterchainGasPayment> for FuelInterchainGasPaymasterIndexer {
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<InterchainGasPayment>, LogMeta)>> {
-        todo!()
+        Err(ChainCommunicationError::from_other_str(
+            "Fuel does not support indexing gas payments yet",
+        ))
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
-        todo!()
+        self.provider.get_finalized_block_number().await
     }
 }
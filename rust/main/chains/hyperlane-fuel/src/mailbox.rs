@@ -210,6 +210,7 @@ impl Mailbox for FuelMailbox {
             gas_limit: call_res.total_fee.into(),
             gas_price: call_res.gas_price.into(),
             l2_gas_limit: None,
+            l1_data_fee: None,
         })
     }
 
@@ -231,10 +232,12 @@ impl FuelMailboxIndexer {
     pub async fn new(
         conf: &ConnectionConf,
         locator: ContractLocator<'_>,
-        wallet: WalletUnlocked,
+        mut wallet: WalletUnlocked,
     ) -> ChainResult<Self> {
         let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await;
 
+        // The wallet needs a provider to simulate the `nonce` call
+        wallet.set_provider(fuel_provider.provider().clone());
         let address = Bech32ContractId::from_h256(&locator.address);
         let contract = FuelMailboxInner::new(address, wallet);
 
@@ -268,7 +271,10 @@ impl Indexer<H256> for FuelMailboxIndexer {
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
-        todo!() // Needed for scraper
+        let mailbox_address = self.contract.contract_id().clone();
+        self.provider
+            .index_deliveries_in_range(range, mailbox_address)
+            .await
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
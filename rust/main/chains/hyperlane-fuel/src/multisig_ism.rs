@@ -1,27 +1,46 @@
 use async_trait::async_trait;
 
 use hyperlane_core::{
-    ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
-    HyperlaneProvider, MultisigIsm, H256,
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, MultisigIsm, H256,
 };
 
+use crate::{ConnectionConf, FuelProvider};
+
 /// A reference to a MultisigIsm contract on some Fuel chain
 #[derive(Debug)]
-pub struct FuelMultisigIsm {}
+pub struct FuelMultisigIsm {
+    address: H256,
+    provider: FuelProvider,
+    domain: HyperlaneDomain,
+}
+
+impl FuelMultisigIsm {
+    /// Create a new fuel multisig ISM
+    pub async fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        let provider = FuelProvider::new(locator.domain.clone(), conf).await;
+
+        Ok(FuelMultisigIsm {
+            address: locator.address,
+            provider,
+            domain: locator.domain.clone(),
+        })
+    }
+}
 
 impl HyperlaneContract for FuelMultisigIsm {
     fn address(&self) -> H256 {
-        todo!()
+        self.address
     }
 }
 
 impl HyperlaneChain for FuelMultisigIsm {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        &self.domain
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.provider.clone())
     }
 }
 
@@ -32,6 +51,8 @@ impl MultisigIsm for FuelMultisigIsm {
         &self,
         message: &HyperlaneMessage,
     ) -> ChainResult<(Vec<H256>, u8)> {
-        todo!()
+        Err(ChainCommunicationError::from_other_str(
+            "Fuel does not support querying multisig ISM validators yet",
+        ))
     }
 }
@@ -21,9 +21,13 @@ use hyperlane_core::{
     BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain,
     HyperlaneMessage, HyperlaneProvider, Indexed, LogMeta, TxnInfo, H256, H512, U256,
 };
+use tracing::warn;
 
 use crate::{make_client, make_provider, prelude::FuelIntoH256, ConnectionConf};
 
+/// Log id of the `ProcessEvent` the Mailbox logs when it delivers a message.
+const PROCESS_EVENT_LOG_ID: u64 = 7929134096091764817;
+
 /// A wrapper around a fuel provider to get generic blockchain information.
 #[derive(Debug, Clone)]
 pub struct FuelProvider {
@@ -168,7 +172,7 @@ impl FuelProvider {
         range: std::ops::RangeInclusive<u32>,
         mailbox_contract: Bech32ContractId,
     ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
-        let (blocks, transaction_map) = self.get_block_data(range.clone()).await.unwrap();
+        let (blocks, transaction_map) = self.get_block_data(range.clone()).await?;
 
         // Transaction ids from selected blocks
         let transaction_ids = blocks
@@ -243,7 +247,13 @@ impl FuelProvider {
                 if !receipt_log_data.is_empty() {
                     // We cut out the message id, recipient and domain which are encoded in the first 76 bytes
                     receipt_log_data.drain(0..76);
-                    let encoded_message = HyperlaneMessage::from(receipt_log_data);
+                    let encoded_message = match HyperlaneMessage::try_from(receipt_log_data) {
+                        Ok(message) => message,
+                        Err(err) => {
+                            warn!(?err, ?tx_id, "Skipping malformed dispatched message");
+                            return None;
+                        }
+                    };
                     Some((tx_id, tx_data, encoded_message, log_index))
                 } else {
                     None
@@ -269,6 +279,69 @@ impl FuelProvider {
             .collect::<Vec<_>>();
         Ok(indexed_logs)
     }
+
+    /// Index the ids of the messages delivered by the mailbox in a range of blocks
+    pub async fn index_deliveries_in_range(
+        &self,
+        range: std::ops::RangeInclusive<u32>,
+        mailbox_contract: Bech32ContractId,
+    ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
+        let (blocks, transaction_map) = self.get_block_data(range).await?;
+        let mailbox_id = ContractId::new(*mailbox_contract.hash);
+
+        let futures = blocks
+            .into_iter()
+            .flat_map(|block| block.transactions)
+            .map(|tx_id| {
+                let provider = self.provider.clone();
+                async move {
+                    let result = provider.get_transaction_by_id(&tx_id).await;
+                    (tx_id, result)
+                }
+            });
+
+        let mut indexed_logs = vec![];
+        for (tx_id, result) in join_all(futures).await {
+            let tx = result.map_err(|e| {
+                ChainCommunicationError::CustomError(format!("Failed to get transaction: {}", e))
+            })?;
+            let Some(tx) = tx else {
+                continue;
+            };
+            let (TxStatus::Success { receipts }, Some(block_height)) =
+                (&tx.status, tx.block_height)
+            else {
+                continue;
+            };
+            let Some((block_hash, transaction_index)) = transaction_map.get(&tx_id) else {
+                continue;
+            };
+
+            // The message id is the first field of the `ProcessEvent`
+            for (log_index, receipt) in receipts.iter().enumerate() {
+                let Receipt::LogData { id, rb, .. } = receipt else {
+                    continue;
+                };
+                let Some(data) = receipt.data() else {
+                    continue;
+                };
+                if *id != mailbox_id || *rb != PROCESS_EVENT_LOG_ID || data.len() < 32 {
+                    continue;
+                }
+
+                let log_meta = LogMeta {
+                    address: mailbox_contract.clone().into_h256(),
+                    block_number: *block_height.deref() as u64,
+                    block_hash: block_hash.into_h256(),
+                    transaction_id: H512::from(tx_id.into_h256()),
+                    transaction_index: *transaction_index,
+                    log_index: U256::from(log_index),
+                };
+                indexed_logs.push((H256::from_slice(&data[..32]).into(), log_meta));
+            }
+        }
+        Ok(indexed_logs)
+    }
 }
 
 impl HyperlaneChain for FuelProvider {
@@ -1,27 +1,46 @@
 use async_trait::async_trait;
 
 use hyperlane_core::{
-    ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
-    HyperlaneProvider, RoutingIsm, H256,
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, RoutingIsm, H256,
 };
 
+use crate::{ConnectionConf, FuelProvider};
+
 /// A reference to a RoutingIsm contract on some Fuel chain
 #[derive(Debug)]
-pub struct FuelRoutingIsm {}
+pub struct FuelRoutingIsm {
+    address: H256,
+    provider: FuelProvider,
+    domain: HyperlaneDomain,
+}
+
+impl FuelRoutingIsm {
+    /// Create a new fuel routing ISM
+    pub async fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        let provider = FuelProvider::new(locator.domain.clone(), conf).await;
+
+        Ok(FuelRoutingIsm {
+            address: locator.address,
+            provider,
+            domain: locator.domain.clone(),
+        })
+    }
+}
 
 impl HyperlaneContract for FuelRoutingIsm {
     fn address(&self) -> H256 {
-        todo!()
+        self.address
     }
 }
 
 impl HyperlaneChain for FuelRoutingIsm {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        &self.domain
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.provider.clone())
     }
 }
 
@@ -29,6 +48,8 @@ impl HyperlaneChain for FuelRoutingIsm {
 impl RoutingIsm for FuelRoutingIsm {
     /// Returns the ism needed to verify message
     async fn route(&self, message: &HyperlaneMessage) -> ChainResult<H256> {
-        todo!()
+        Err(ChainCommunicationError::from_other_str(
+            "Fuel does not support routing ISMs yet",
+        ))
     }
 }
@@ -1,27 +1,47 @@
 use async_trait::async_trait;
 
 use hyperlane_core::{
-    Announcement, ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
-    HyperlaneProvider, SignedType, TxOutcome, ValidatorAnnounce, H256, U256,
+    Announcement, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneProvider, SignedType, TxOutcome,
+    ValidatorAnnounce, H256, U256,
 };
 
+use crate::{ConnectionConf, FuelProvider};
+
 /// A reference to a ValidatorAnnounce contract on some Fuel chain
 #[derive(Debug)]
-pub struct FuelValidatorAnnounce {}
+pub struct FuelValidatorAnnounce {
+    address: H256,
+    provider: FuelProvider,
+    domain: HyperlaneDomain,
+}
+
+impl FuelValidatorAnnounce {
+    /// Create a new fuel validator announce
+    pub async fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        let provider = FuelProvider::new(locator.domain.clone(), conf).await;
+
+        Ok(FuelValidatorAnnounce {
+            address: locator.address,
+            provider,
+            domain: locator.domain.clone(),
+        })
+    }
+}
 
 impl HyperlaneContract for FuelValidatorAnnounce {
     fn address(&self) -> H256 {
-        todo!()
+        self.address
     }
 }
 
 impl HyperlaneChain for FuelValidatorAnnounce {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        &self.domain
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.provider.clone())
     }
 }
 
@@ -31,14 +51,18 @@ impl ValidatorAnnounce for FuelValidatorAnnounce {
         &self,
         validators: &[H256],
     ) -> ChainResult<Vec<Vec<String>>> {
-        todo!()
+        Err(ChainCommunicationError::from_other_str(
+            "Fuel does not support querying announced storage locations yet",
+        ))
     }
 
     async fn announce(&self, announcement: SignedType<Announcement>) -> ChainResult<TxOutcome> {
-        todo!()
+        Err(ChainCommunicationError::from_other_str(
+            "Fuel does not support announcing validators yet",
+        ))
     }
 
     async fn announce_tokens_needed(&self, announcement: SignedType<Announcement>) -> Option<U256> {
-        todo!()
+        None
     }
 }
@@ -0,0 +1,123 @@
+use borsh::BorshDeserialize;
+use hyperlane_core::ChainResult;
+use tracing::warn;
+
+use crate::error::HyperlaneSealevelError;
+
+/// How the data of a program account compares to the layout of the account
+/// known to this crate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AccountLayoutVersion {
+    /// The data is the known layout, possibly followed by zeroed padding left
+    /// by reallocations
+    Known,
+    /// The data continues past the known layout, with fields appended by a
+    /// newer version of the program
+    Newer {
+        /// Number of bytes past the known layout
+        extra_bytes: usize,
+    },
+}
+
+/// Decodes the data of an `AccountData` account, whose layout program
+/// upgrades may have extended by appending fields. Only the known prefix of
+/// the data is decoded and the fields appended after it are ignored, so that
+/// the agents keep working until they are upgraded to the new layout.
+pub(crate) fn decode_versioned_account<T: BorshDeserialize>(
+    account: &'static str,
+    data: &[u8],
+) -> ChainResult<(T, AccountLayoutVersion)> {
+    let Some((initialized, mut buf)) = data.split_first() else {
+        return Err(HyperlaneSealevelError::AccountNotInitialized(account).into());
+    };
+    if *initialized == 0 {
+        return Err(HyperlaneSealevelError::AccountNotInitialized(account).into());
+    }
+    let decoded = T::deserialize(&mut buf)
+        .map_err(|source| HyperlaneSealevelError::IncompatibleAccountLayout { account, source })?;
+    let version = if buf.iter().all(|b| *b == 0) {
+        AccountLayoutVersion::Known
+    } else {
+        AccountLayoutVersion::Newer {
+            extra_bytes: buf.len(),
+        }
+    };
+    Ok((decoded, version))
+}
+
+/// Decodes the data of an `AccountData` account, warning when it was written
+/// with a newer layout than the one known to this crate.
+pub(crate) fn decode_account<T: BorshDeserialize>(
+    account: &'static str,
+    data: &[u8],
+) -> ChainResult<T> {
+    let (decoded, version) = decode_versioned_account(account, data)?;
+    if let AccountLayoutVersion::Newer { extra_bytes } = version {
+        warn!(
+            account,
+            extra_bytes,
+            "Account was written by a newer program version, ignoring the fields it appended. \
+             The agent should be upgraded."
+        );
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod test {
+    use borsh::BorshSerialize;
+    use hyperlane_sealevel_mailbox::accounts::Inbox;
+    use solana_sdk::pubkey::Pubkey;
+
+    use super::*;
+
+    fn inbox_data() -> (Inbox, Vec<u8>) {
+        let inbox = Inbox {
+            local_domain: 1399811149,
+            inbox_bump_seed: 255,
+            default_ism: Pubkey::new_unique(),
+            processed_count: 42,
+        };
+        let data = [vec![1], inbox.try_to_vec().unwrap()].concat();
+        (inbox, data)
+    }
+
+    #[test]
+    fn test_decode_known_layout() {
+        let (inbox, data) = inbox_data();
+        assert_eq!(
+            decode_versioned_account::<Inbox>("inbox", &data).unwrap(),
+            (inbox, AccountLayoutVersion::Known)
+        );
+
+        // Padding left by reallocations
+        let (inbox, data) = inbox_data();
+        let padded = [data, vec![0; 1024]].concat();
+        assert_eq!(
+            decode_versioned_account::<Inbox>("inbox", &padded).unwrap(),
+            (inbox, AccountLayoutVersion::Known)
+        );
+    }
+
+    #[test]
+    fn test_decode_newer_layout() {
+        let (inbox, data) = inbox_data();
+        let extended = [data, vec![7; 8]].concat();
+        assert_eq!(
+            decode_versioned_account::<Inbox>("inbox", &extended).unwrap(),
+            (inbox, AccountLayoutVersion::Newer { extra_bytes: 8 })
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_data() {
+        assert!(decode_versioned_account::<Inbox>("inbox", &[]).is_err());
+
+        let (_, mut data) = inbox_data();
+        data[0] = 0;
+        assert!(decode_versioned_account::<Inbox>("inbox", &data).is_err());
+
+        let (_, data) = inbox_data();
+        assert!(decode_versioned_account::<Inbox>("inbox", &data[..10]).is_err());
+    }
+}
@@ -14,6 +14,19 @@ pub enum HyperlaneSealevelError {
     /// ClientError error
     #[error("{0}")]
     ClientError(#[from] ClientError),
+    /// An account was read before its program initialized it
+    #[error("{0} account is not initialized")]
+    AccountNotInitialized(&'static str),
+    /// An account's data doesn't start with the layout known to this crate,
+    /// e.g. because a program upgrade changed fields rather than appending
+    /// new ones
+    #[error("{account} account data doesn't match the known layout: {source}")]
+    IncompatibleAccountLayout {
+        /// The account's name
+        account: &'static str,
+        /// The decoding error
+        source: std::io::Error,
+    },
 }
 
 impl From<HyperlaneSealevelError> for ChainCommunicationError {
@@ -1,23 +1,19 @@
 use async_trait::async_trait;
 use hyperlane_core::{
     config::StrOrIntParseError, ChainCommunicationError, ChainResult, ContractLocator,
-    HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneProvider, Indexed, Indexer,
-    InterchainGasPaymaster, InterchainGasPayment, LogMeta, SequenceAwareIndexer, H256, H512,
-};
-use hyperlane_sealevel_igp::{
-    accounts::{GasPaymentAccount, ProgramDataAccount},
-    igp_gas_payment_pda_seeds, igp_program_data_pda_seeds,
-};
-use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
-use solana_client::{
-    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
-    rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
+    HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneProvider, IndexError, Indexed,
+    Indexer, InterchainGasPaymaster, InterchainGasPayment, LogMeta, SequenceAwareIndexer, H256,
+    H512,
 };
+use hyperlane_sealevel_igp::accounts::GasPaymentAccount;
+use solana_account_decoder::UiDataSliceConfig;
 use std::ops::RangeInclusive;
 use tracing::{info, instrument};
 
-use crate::{ConnectionConf, SealevelProvider, SealevelRpcClient};
-use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use crate::{
+    provider::slot_block_hash, ConnectionConf, IgpPdas, SealevelProvider, SealevelRpcClient,
+};
+use solana_sdk::pubkey::Pubkey;
 
 use derive_new::new;
 
@@ -26,6 +22,11 @@ use derive_new::new;
 /// and an 8 byte discriminator.
 const UNIQUE_GAS_PAYMENT_PUBKEY_OFFSET: usize = 1 + 8 + 8 + 32 + 4 + 32 + 8 + 8;
 
+/// The offset to get the `payment_count` field from the serialized ProgramData.
+/// The account data includes prefixes that are accounted for here: a 1 byte initialized flag
+/// and an 8 byte discriminator, followed by the 1 byte bump seed.
+const PAYMENT_COUNT_OFFSET: usize = 1 + 8 + 1;
+
 /// A reference to an IGP contract on some Sealevel chain
 #[derive(Debug)]
 pub struct SealevelInterchainGasPaymaster {
@@ -45,8 +46,7 @@ impl SealevelInterchainGasPaymaster {
         let provider = SealevelProvider::new(igp_account_locator.domain.clone(), conf);
         let program_id =
             Self::determine_igp_program_id(provider.rpc(), &igp_account_locator.address).await?;
-        let (data_pda_pubkey, _) =
-            Pubkey::find_program_address(igp_program_data_pda_seeds!(), &program_id);
+        let (data_pda_pubkey, _) = IgpPdas::new(program_id).program_data()?;
 
         Ok(Self {
             program_id,
@@ -110,7 +110,7 @@ impl SealevelInterchainGasPaymasterIndexer {
         igp_account_locator: ContractLocator<'_>,
     ) -> ChainResult<Self> {
         // Set the `processed` commitment at rpc level
-        let rpc_client = SealevelRpcClient::new(conf.url.to_string());
+        let rpc_client = SealevelRpcClient::new(conf);
 
         let igp = SealevelInterchainGasPaymaster::new(conf, &igp_account_locator).await?;
         Ok(Self { rpc_client, igp })
@@ -126,38 +126,21 @@ impl SealevelInterchainGasPaymasterIndexer {
             &sequence_number.to_le_bytes()[..],
         ]
         .concat();
-        #[allow(deprecated)]
-        let payment_bytes: String = base64::encode(payment_bytes);
 
         // First, find all accounts with the matching gas payment data.
         // To keep responses small in case there is ever more than 1
         // match, we don't request the full account data, and just request
         // the `unique_gas_payment_pubkey` field.
-        #[allow(deprecated)]
-        let memcmp = RpcFilterType::Memcmp(Memcmp {
-            // Ignore the first byte, which is the `initialized` bool flag.
-            offset: 1,
-            bytes: MemcmpEncodedBytes::Base64(payment_bytes),
-            encoding: None,
-        });
-        let config = RpcProgramAccountsConfig {
-            filters: Some(vec![memcmp]),
-            account_config: RpcAccountInfoConfig {
-                encoding: Some(UiAccountEncoding::Base64),
-                // Don't return any data
-                data_slice: Some(UiDataSliceConfig {
-                    offset: UNIQUE_GAS_PAYMENT_PUBKEY_OFFSET,
-                    length: 32, // the length of the `unique_gas_payment_pubkey` field
-                }),
-                commitment: Some(CommitmentConfig::finalized()),
-                min_context_slot: None,
-            },
-            with_context: Some(false),
-        };
-        tracing::debug!(config=?config, "Fetching program accounts");
         let accounts = self
             .rpc_client
-            .get_program_accounts_with_config(&self.igp.program_id, config)
+            .get_program_accounts_with_prefix(
+                &self.igp.program_id,
+                payment_bytes,
+                UiDataSliceConfig {
+                    offset: UNIQUE_GAS_PAYMENT_PUBKEY_OFFSET,
+                    length: 32, // the length of the `unique_gas_payment_pubkey` field
+                },
+            )
             .await?;
 
         tracing::debug!(accounts=?accounts, "Fetched program accounts");
@@ -168,23 +151,17 @@ impl SealevelInterchainGasPaymasterIndexer {
 
         for (pubkey, account) in accounts {
             let unique_gas_payment_pubkey = Pubkey::new(&account.data);
-            let (expected_pubkey, _bump) = Pubkey::try_find_program_address(
-                igp_gas_payment_pda_seeds!(unique_gas_payment_pubkey),
-                &self.igp.program_id,
-            )
-            .ok_or_else(|| {
-                ChainCommunicationError::from_other_str(
-                    "Could not find program address for unique_gas_payment_pubkey",
-                )
-            })?;
+            let (expected_pubkey, _bump) =
+                IgpPdas::new(self.igp.program_id).gas_payment(&unique_gas_payment_pubkey)?;
             if expected_pubkey == pubkey {
                 valid_payment_pda_pubkey = Some(pubkey);
                 break;
             }
         }
 
-        let valid_payment_pda_pubkey = valid_payment_pda_pubkey.ok_or_else(|| {
-            ChainCommunicationError::from_other_str("Could not find valid gas payment PDA pubkey")
+        let valid_payment_pda_pubkey = valid_payment_pda_pubkey.ok_or(IndexError::LogNotFound {
+            log: "gas payment",
+            sequence: sequence_number,
         })?;
 
         // Now that we have the valid gas payment PDA pubkey, we can get the full account data.
@@ -214,9 +191,9 @@ impl SealevelInterchainGasPaymasterIndexer {
             LogMeta {
                 address: self.igp.program_id.to_bytes().into(),
                 block_number: gas_payment_account.slot,
-                // TODO: get these when building out scraper support.
-                // It's inconvenient to get these :|
-                block_hash: H256::zero(),
+                block_hash: slot_block_hash(gas_payment_account.slot),
+                // TODO: get this when building out scraper support.
+                // It's inconvenient to get :|
                 transaction_id: H512::zero(),
                 transaction_index: 0,
                 log_index: sequence_number.into(),
@@ -266,17 +243,22 @@ impl SequenceAwareIndexer<InterchainGasPayment> for SealevelInterchainGasPaymast
     #[instrument(err, skip(self))]
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
     async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
-        let program_data_account = self
+        // Only the payment count is needed out of the program data
+        let payment_count_bytes = self
             .rpc_client
-            .get_account_with_finalized_commitment(&self.igp.data_pda_pubkey)
+            .get_account_data_slice_with_finalized_commitment(
+                &self.igp.data_pda_pubkey,
+                PAYMENT_COUNT_OFFSET,
+                8,
+            )
             .await?;
-        let program_data = ProgramDataAccount::fetch(&mut program_data_account.data.as_ref())
-            .map_err(ChainCommunicationError::from_other)?
-            .into_inner();
-        let payment_count = program_data
-            .payment_count
-            .try_into()
-            .map_err(StrOrIntParseError::from)?;
+        let payment_count = u64::from_le_bytes(
+            payment_count_bytes
+                .try_into()
+                .map_err(|_| ChainCommunicationError::from_other_str("Invalid payment count"))?,
+        )
+        .try_into()
+        .map_err(StrOrIntParseError::from)?;
         let tip = self.rpc_client.get_block_height().await?;
         Ok((Some(payment_count), tip))
     }
@@ -317,3 +299,27 @@ fn test_unique_gas_payment_pubkey_offset() {
         sliced_unique_gas_payment_pubkey
     );
 }
+
+#[test]
+fn test_payment_count_offset() {
+    use borsh::BorshSerialize;
+    use hyperlane_sealevel_igp::accounts::{ProgramData, ProgramDataAccount};
+
+    let program_data = ProgramDataAccount::new(
+        ProgramData {
+            bump_seed: 255,
+            payment_count: 123,
+        }
+        .into(),
+    );
+
+    let serialized = program_data.into_inner().try_to_vec().unwrap();
+    // The dummy `ProgramDataAccount` is not prefixed by an `is_initialized` boolean,
+    // so 1 is subtracted from the offset, see `test_unique_gas_payment_pubkey_offset`.
+    let sliced_payment_count = u64::from_le_bytes(
+        serialized[(PAYMENT_COUNT_OFFSET - 1)..(PAYMENT_COUNT_OFFSET + 8 - 1)]
+            .try_into()
+            .unwrap(),
+    );
+    assert_eq!(sliced_payment_count, 123);
+}
@@ -1,13 +1,20 @@
 use async_trait::async_trait;
 use num_traits::cast::FromPrimitive;
-use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+};
 use tracing::warn;
 
 use hyperlane_core::{
-    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
-    HyperlaneDomain, HyperlaneMessage, InterchainSecurityModule, ModuleType, H256, U256,
+    ChainCommunicationError, ChainResult, ContractLocator, Encode as _, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneMessage, InterchainSecurityModule, ModuleType,
+    H256, U256,
+};
+use hyperlane_sealevel_interchain_security_module_interface::{
+    InterchainSecurityModuleInstruction, VerifyInstruction, VERIFY_ACCOUNT_METAS_PDA_SEEDS,
 };
-use hyperlane_sealevel_interchain_security_module_interface::InterchainSecurityModuleInstruction;
 use serializable_account_meta::SimulationReturnData;
 
 use crate::{ConnectionConf, SealevelProvider, SealevelRpcClient};
@@ -86,12 +93,47 @@ impl InterchainSecurityModule for SealevelInterchainSecurityModule {
         }
     }
 
+    /// Simulates the ISM's `Verify` instruction and returns
+    /// `Some(compute_units)` if it succeeds.
     async fn dry_run_verify(
         &self,
-        _message: &HyperlaneMessage,
-        _metadata: &[u8],
+        message: &HyperlaneMessage,
+        metadata: &[u8],
     ) -> ChainResult<Option<U256>> {
-        // TODO: Implement this once we have aggregation ISM support in Sealevel
-        Ok(Some(U256::zero()))
+        let payer = self
+            .payer
+            .as_ref()
+            .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?;
+        let verify = VerifyInstruction {
+            metadata: metadata.to_vec(),
+            message: message.to_vec(),
+        };
+
+        let (account_metas_pda_key, _) =
+            Pubkey::find_program_address(VERIFY_ACCOUNT_METAS_PDA_SEEDS, &self.program_id);
+        let account_metas_instruction = Instruction::new_with_bytes(
+            self.program_id,
+            &InterchainSecurityModuleInstruction::VerifyAccountMetas(verify.clone())
+                .encode()
+                .map_err(ChainCommunicationError::from_other)?,
+            vec![AccountMeta::new(account_metas_pda_key, false)],
+        );
+        let account_metas = self
+            .rpc()
+            .get_account_metas(payer, account_metas_instruction)
+            .await?;
+
+        let instruction = Instruction::new_with_bytes(
+            self.program_id,
+            &InterchainSecurityModuleInstruction::Verify(verify)
+                .encode()
+                .map_err(ChainCommunicationError::from_other)?,
+            account_metas,
+        );
+        let compute_units = self
+            .rpc()
+            .simulate_instruction_compute_units(payer, instruction)
+            .await?;
+        Ok(compute_units.map(U256::from))
     }
 }
@@ -9,19 +9,29 @@ pub use interchain_gas::*;
 pub use interchain_security_module::*;
 pub use mailbox::*;
 pub use merkle_tree_hook::*;
+pub use pda::*;
 pub use provider::*;
 pub(crate) use rpc::SealevelRpcClient;
 pub use solana_sdk::signer::keypair::Keypair;
+pub use token_router::*;
 pub use trait_builder::*;
 pub use validator_announce::*;
 
+mod account;
 mod error;
 mod interchain_gas;
 mod interchain_security_module;
 mod mailbox;
 mod merkle_tree_hook;
 mod multisig_ism;
+mod pda;
 mod provider;
 mod rpc;
+mod slot_timestamp;
+mod token_router;
 mod trait_builder;
 mod validator_announce;
+
+/// Local solana validators for integration tests.
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
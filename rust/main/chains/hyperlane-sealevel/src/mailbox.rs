@@ -10,31 +10,26 @@ use tracing::{debug, info, instrument, warn};
 use hyperlane_core::{
     accumulator::incremental::IncrementalMerkle, BatchItem, ChainCommunicationError,
     ChainCommunicationError::ContractError, ChainResult, Checkpoint, ContractLocator, Decode as _,
-    Encode as _, FixedPointNumber, HyperlaneAbi, HyperlaneChain, HyperlaneContract,
-    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Indexed, Indexer, KnownHyperlaneDomain,
-    LogMeta, Mailbox, MerkleTreeHook, SequenceAwareIndexer, TxCostEstimate, TxOutcome, H256, H512,
-    U256,
+    DeliveryStatus, Encode as _, FixedPointNumber, HyperlaneAbi, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, IndexError, Indexed, Indexer,
+    KnownHyperlaneDomain, LogMeta, Mailbox, MerkleTreeHook, SequenceAwareIndexer, TxAdapter,
+    TxCostEstimate, TxErrorClass, TxOutcome, TxSubmitter, TxSubmitterConf, H256, H512, U256,
 };
 use hyperlane_sealevel_interchain_security_module_interface::{
     InterchainSecurityModuleInstruction, VerifyInstruction,
 };
 use hyperlane_sealevel_mailbox::{
-    accounts::{DispatchedMessageAccount, InboxAccount, OutboxAccount},
+    accounts::{DispatchedMessageAccount, Inbox, OutboxAccount},
     instruction::InboxProcess,
-    mailbox_dispatched_message_pda_seeds, mailbox_inbox_pda_seeds, mailbox_outbox_pda_seeds,
-    mailbox_process_authority_pda_seeds, mailbox_processed_message_pda_seeds,
 };
 use hyperlane_sealevel_message_recipient_interface::{
     HandleInstruction, MessageRecipientInstruction,
 };
 use serializable_account_meta::SimulationReturnData;
-use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
+use solana_account_decoder::UiDataSliceConfig;
 use solana_client::{
-    nonblocking::rpc_client::RpcClient,
-    rpc_client::SerializableTransaction,
-    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSendTransactionConfig},
-    rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
-    rpc_response::Response,
+    nonblocking::rpc_client::RpcClient, rpc_client::SerializableTransaction,
+    rpc_config::RpcSendTransactionConfig, rpc_response::Response,
 };
 use solana_sdk::{
     account::Account,
@@ -55,7 +50,15 @@ use solana_transaction_status::{
     UiTransaction, UiTransactionReturnData, UiTransactionStatusMeta,
 };
 
-use crate::{ConnectionConf, SealevelProvider, SealevelRpcClient};
+use crate::{
+    account::decode_account, provider::slot_block_hash, rpc::proxied_client_builder,
+    ConnectionConf, MailboxPdas, SealevelProvider, SealevelRpcClient,
+};
+
+/// The offset to get the `unique_message_pubkey` field from the serialized DispatchedMessage.
+/// The account data includes prefixes that are accounted for here: a 1 byte initialized flag
+/// and an 8 byte discriminator, followed by the 4 byte nonce and 8 byte slot.
+const UNIQUE_MESSAGE_PUBKEY_OFFSET: usize = 1 + 8 + 4 + 8;
 
 const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
 const SPL_NOOP: &str = "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV";
@@ -89,6 +92,8 @@ pub struct SealevelMailbox {
     pub(crate) outbox: (Pubkey, u8),
     pub(crate) provider: SealevelProvider,
     payer: Option<Keypair>,
+    /// Client of the Jito block engine, through the proxy of the chain
+    jito_client: reqwest::Client,
 }
 
 impl SealevelMailbox {
@@ -101,8 +106,9 @@ impl SealevelMailbox {
         let provider = SealevelProvider::new(locator.domain.clone(), conf);
         let program_id = Pubkey::from(<[u8; 32]>::from(locator.address));
         let domain = locator.domain.id();
-        let inbox = Pubkey::find_program_address(mailbox_inbox_pda_seeds!(), &program_id);
-        let outbox = Pubkey::find_program_address(mailbox_outbox_pda_seeds!(), &program_id);
+        let pdas = MailboxPdas::new(program_id);
+        let inbox = pdas.inbox()?;
+        let outbox = pdas.outbox()?;
 
         debug!(
             "domain={}\nmailbox={}\ninbox=({}, {})\noutbox=({}, {})",
@@ -115,6 +121,9 @@ impl SealevelMailbox {
             outbox,
             provider,
             payer,
+            jito_client: proxied_client_builder(conf.proxy.as_ref())
+                .build()
+                .unwrap_or_default(),
         })
     }
 
@@ -129,6 +138,21 @@ impl SealevelMailbox {
         self.provider.rpc()
     }
 
+    /// Whether the account recording the processing of a message exists,
+    /// which only needs the account's existence rather than its data
+    async fn processed_message_exists(
+        &self,
+        id: H256,
+        commitment: CommitmentConfig,
+    ) -> ChainResult<bool> {
+        let (processed_message_account_key, _processed_message_account_bump) =
+            MailboxPdas::new(self.program_id).processed_message(id)?;
+
+        self.rpc()
+            .account_exists_with_commitment(&processed_message_account_key, commitment)
+            .await
+    }
+
     /// Simulates an instruction, and attempts to deserialize it into a T.
     /// If no return data at all was returned, returns Ok(None).
     /// If some return data was returned but deserialization was unsuccessful,
@@ -272,10 +296,7 @@ impl SealevelMailbox {
     }
 
     fn use_jito(&self) -> bool {
-        matches!(
-            self.domain(),
-            HyperlaneDomain::Known(KnownHyperlaneDomain::SolanaMainnet)
-        )
+        self.domain().is(KnownHyperlaneDomain::SolanaMainnet)
     }
 
     async fn send_and_confirm_transaction(
@@ -324,7 +345,8 @@ impl SealevelMailbox {
                 "Sending sealevel transaction to Jito as bundle"
             );
 
-            let jito_response = reqwest::Client::new()
+            let jito_response = self
+                .jito_client
                 .post("https://mainnet.block-engine.jito.wtf:443/api/v1/bundles")
                 .json(&jito_request_body)
                 .send()
@@ -389,6 +411,84 @@ impl SealevelMailbox {
     }
 }
 
+/// "processed" level commitment does not guarantee finality.
+/// roughly 5% of blocks end up on a dropped fork.
+/// However we don't want transaction submission to be a bottleneck and there
+/// already is retry logic in the agents.
+fn process_commitment() -> CommitmentConfig {
+    CommitmentConfig::processed()
+}
+
+#[async_trait]
+impl TxAdapter for SealevelMailbox {
+    type Tx = Vec<Instruction>;
+    type Pending = Signature;
+
+    /// The compute unit limit is set by the instructions
+    async fn estimate(&self, _instructions: &mut Vec<Instruction>) -> ChainResult<()> {
+        Ok(())
+    }
+
+    /// Signs the instructions with a recent blockhash, so that transactions
+    /// sent again after their blockhash expired can be included
+    async fn send(&self, instructions: &mut Vec<Instruction>) -> ChainResult<Signature> {
+        let payer = self
+            .payer
+            .as_ref()
+            .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?;
+        let recent_blockhash = self
+            .rpc()
+            .get_latest_blockhash_with_commitment(process_commitment())
+            .await?;
+
+        let txn = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        );
+
+        tracing::info!(?txn, "Created sealevel transaction to process message");
+
+        let signature = self.send_and_confirm_transaction(&txn).await?;
+
+        tracing::info!(?txn, ?signature, "Sealevel transaction sent");
+        Ok(signature)
+    }
+
+    async fn confirm(&self, signature: &Signature) -> ChainResult<Option<TxOutcome>> {
+        let executed = self
+            .rpc()
+            .confirm_transaction_with_commitment(signature, process_commitment())
+            .await
+            .map_err(|err| warn!("Failed to confirm inbox process transaction: {}", err))
+            .unwrap_or(false);
+
+        Ok(Some(TxOutcome {
+            transaction_id: (*signature).into(),
+            executed,
+            // TODO use correct data upon integrating IGP support
+            gas_price: U256::zero().try_into()?,
+            gas_used: U256::zero(),
+        }))
+    }
+
+    /// Jito tips and prioritization fees are fixed
+    fn escalate_fee(&self, _instructions: &mut Vec<Instruction>, _multiplier: f64) -> bool {
+        false
+    }
+
+    fn classify(&self, err: &ChainCommunicationError) -> TxErrorClass {
+        const RETRYABLE: &[&str] = &["blockhash not found", "connection", "timed out"];
+        let message = err.to_string().to_lowercase();
+        if RETRYABLE.iter().any(|pattern| message.contains(pattern)) {
+            TxErrorClass::Retryable
+        } else {
+            TxErrorClass::Fatal
+        }
+    }
+}
+
 impl HyperlaneContract for SealevelMailbox {
     fn address(&self) -> H256 {
         self.program_id.to_bytes().into()
@@ -422,26 +522,34 @@ impl Mailbox for SealevelMailbox {
 
     #[instrument(err, ret, skip(self))]
     async fn delivered(&self, id: H256) -> ChainResult<bool> {
-        let (processed_message_account_key, _processed_message_account_bump) =
-            Pubkey::find_program_address(
-                mailbox_processed_message_pda_seeds!(id),
-                &self.program_id,
-            );
-
-        let account = self
-            .rpc()
-            .get_possible_account_with_finalized_commitment(&processed_message_account_key)
-            .await?;
+        self.processed_message_exists(id, CommitmentConfig::finalized())
+            .await
+    }
 
-        Ok(account.is_some())
+    /// Deliveries are seen at confirmed commitment well before they are
+    /// finalized, so that they can be treated as successful while their
+    /// finality is checked later
+    #[instrument(err, ret, skip(self))]
+    async fn delivery_status(&self, id: H256) -> ChainResult<DeliveryStatus> {
+        if self.delivered(id).await? {
+            return Ok(DeliveryStatus::Finalized);
+        }
+        Ok(
+            if self
+                .processed_message_exists(id, CommitmentConfig::confirmed())
+                .await?
+            {
+                DeliveryStatus::Unfinalized
+            } else {
+                DeliveryStatus::NotDelivered
+            },
+        )
     }
 
     #[instrument(err, ret, skip(self))]
     async fn default_ism(&self) -> ChainResult<H256> {
         let inbox_account = self.rpc().get_account(&self.inbox.0).await?;
-        let inbox = InboxAccount::fetch(&mut inbox_account.data.as_ref())
-            .map_err(ChainCommunicationError::from_other)?
-            .into_inner();
+        let inbox = decode_account::<Inbox>("inbox", &inbox_account.data)?;
 
         Ok(inbox.default_ism.to_bytes().into())
     }
@@ -498,31 +606,11 @@ impl Mailbox for SealevelMailbox {
                 PROCESS_DESIRED_PRIORITIZATION_FEE_LAMPORTS_PER_TX,
             ));
         }
-        // "processed" level commitment does not guarantee finality.
-        // roughly 5% of blocks end up on a dropped fork.
-        // However we don't want this function to be a bottleneck and there already
-        // is retry logic in the agents.
-        let commitment = CommitmentConfig::processed();
-
-        let (process_authority_key, _process_authority_bump) = Pubkey::try_find_program_address(
-            mailbox_process_authority_pda_seeds!(&recipient),
-            &self.program_id,
-        )
-        .ok_or_else(|| {
-            ChainCommunicationError::from_other_str(
-                "Could not find program address for process authority",
-            )
-        })?;
+        let pdas = MailboxPdas::new(self.program_id);
+        let (process_authority_key, _process_authority_bump) =
+            pdas.process_authority(&recipient)?;
         let (processed_message_account_key, _processed_message_account_bump) =
-            Pubkey::try_find_program_address(
-                mailbox_processed_message_pda_seeds!(message.id()),
-                &self.program_id,
-            )
-            .ok_or_else(|| {
-                ChainCommunicationError::from_other_str(
-                    "Could not find program address for processed message account",
-                )
-            })?;
+            pdas.processed_message(message.id())?;
 
         // Get the account metas required for the recipient.InterchainSecurityModule instruction.
         let ism_getter_account_metas = self.get_ism_getter_account_metas(recipient).await?;
@@ -574,39 +662,8 @@ impl Mailbox for SealevelMailbox {
             accounts,
         };
         instructions.push(inbox_instruction);
-        let recent_blockhash = self
-            .rpc()
-            .get_latest_blockhash_with_commitment(commitment)
-            .await?;
-
-        let txn = Transaction::new_signed_with_payer(
-            &instructions,
-            Some(&payer.pubkey()),
-            &[payer],
-            recent_blockhash,
-        );
-
-        tracing::info!(?txn, "Created sealevel transaction to process message");
-
-        let signature = self.send_and_confirm_transaction(&txn).await?;
-
-        tracing::info!(?txn, ?signature, "Sealevel transaction sent");
-
-        let executed = self
-            .rpc()
-            .confirm_transaction_with_commitment(&signature, commitment)
-            .await
-            .map_err(|err| warn!("Failed to confirm inbox process transaction: {}", err))
-            .unwrap_or(false);
-        let txid = signature.into();
 
-        Ok(TxOutcome {
-            transaction_id: txid,
-            executed,
-            // TODO use correct data upon integrating IGP support
-            gas_price: U256::zero().try_into()?,
-            gas_used: U256::zero(),
-        })
+        self.submit(instructions, &TxSubmitterConf::default()).await
     }
 
     #[instrument(err, ret, skip(self))]
@@ -620,6 +677,7 @@ impl Mailbox for SealevelMailbox {
             gas_limit: U256::zero(),
             gas_price: FixedPointNumber::zero(),
             l2_gas_limit: None,
+            l1_data_fee: None,
         })
     }
 
@@ -662,35 +720,21 @@ impl SealevelMailboxIndexer {
             &nonce.to_le_bytes()[..],
         ]
         .concat();
-        let target_message_account_bytes = base64::encode(target_message_account_bytes);
 
         // First, find all accounts with the matching account data.
         // To keep responses small in case there is ever more than 1
         // match, we don't request the full account data, and just request
         // the `unique_message_pubkey` field.
-        let memcmp = RpcFilterType::Memcmp(Memcmp {
-            // Ignore the first byte, which is the `initialized` bool flag.
-            offset: 1,
-            bytes: MemcmpEncodedBytes::Base64(target_message_account_bytes),
-            encoding: None,
-        });
-        let config = RpcProgramAccountsConfig {
-            filters: Some(vec![memcmp]),
-            account_config: RpcAccountInfoConfig {
-                encoding: Some(UiAccountEncoding::Base64),
-                // Don't return any data
-                data_slice: Some(UiDataSliceConfig {
-                    offset: 1 + 8 + 4 + 8, // the offset to get the `unique_message_pubkey` field
-                    length: 32,            // the length of the `unique_message_pubkey` field
-                }),
-                commitment: Some(CommitmentConfig::finalized()),
-                min_context_slot: None,
-            },
-            with_context: Some(false),
-        };
         let accounts = self
             .rpc()
-            .get_program_accounts_with_config(&self.mailbox.program_id, config)
+            .get_program_accounts_with_prefix(
+                &self.mailbox.program_id,
+                target_message_account_bytes,
+                UiDataSliceConfig {
+                    offset: UNIQUE_MESSAGE_PUBKEY_OFFSET,
+                    length: 32, // the length of the `unique_message_pubkey` field
+                },
+            )
             .await?;
 
         // Now loop through matching accounts and find the one with a valid account pubkey
@@ -699,15 +743,8 @@ impl SealevelMailboxIndexer {
 
         for (pubkey, account) in accounts {
             let unique_message_pubkey = Pubkey::new(&account.data);
-            let (expected_pubkey, _bump) = Pubkey::try_find_program_address(
-                mailbox_dispatched_message_pda_seeds!(unique_message_pubkey),
-                &self.mailbox.program_id,
-            )
-            .ok_or_else(|| {
-                ChainCommunicationError::from_other_str(
-                    "Could not find program address for unique_message_pubkey",
-                )
-            })?;
+            let (expected_pubkey, _bump) = MailboxPdas::new(self.mailbox.program_id)
+                .dispatched_message(&unique_message_pubkey)?;
             if expected_pubkey == pubkey {
                 valid_message_storage_pda_pubkey = Some(pubkey);
                 break;
@@ -715,10 +752,9 @@ impl SealevelMailboxIndexer {
         }
 
         let valid_message_storage_pda_pubkey =
-            valid_message_storage_pda_pubkey.ok_or_else(|| {
-                ChainCommunicationError::from_other_str(
-                    "Could not find valid message storage PDA pubkey",
-                )
+            valid_message_storage_pda_pubkey.ok_or(IndexError::LogNotFound {
+                log: "dispatch",
+                sequence: nonce.into(),
             })?;
 
         // Now that we have the valid message storage PDA pubkey, we can get the full account data.
@@ -738,9 +774,9 @@ impl SealevelMailboxIndexer {
             LogMeta {
                 address: self.mailbox.program_id.to_bytes().into(),
                 block_number: dispatched_message_account.slot,
-                // TODO: get these when building out scraper support.
-                // It's inconvenient to get these :|
-                block_hash: H256::zero(),
+                block_hash: slot_block_hash(dispatched_message_account.slot),
+                // TODO: get this when building out scraper support.
+                // It's inconvenient to get :|
                 transaction_id: H512::zero(),
                 transaction_index: 0,
                 log_index: U256::zero(),
@@ -818,3 +854,22 @@ impl HyperlaneAbi for SealevelMailboxAbi {
         todo!()
     }
 }
+
+#[test]
+fn test_unique_message_pubkey_offset() {
+    use borsh::BorshSerialize;
+    use hyperlane_sealevel_mailbox::accounts::DispatchedMessage;
+    let expected_unique_message_pubkey = Pubkey::new_unique();
+
+    let dispatched_message = DispatchedMessageAccount::new(
+        DispatchedMessage::new(123, 456, expected_unique_message_pubkey, vec![1, 2, 3]).into(),
+    );
+
+    let serialized = dispatched_message.into_inner().try_to_vec().unwrap();
+    // The dummy `DispatchedMessageAccount` is not prefixed by an `is_initialized` boolean,
+    // so 1 is subtracted from the offset, see `test_unique_gas_payment_pubkey_offset`.
+    let sliced_unique_message_pubkey = Pubkey::new(
+        &serialized[(UNIQUE_MESSAGE_PUBKEY_OFFSET - 1)..(UNIQUE_MESSAGE_PUBKEY_OFFSET + 32 - 1)],
+    );
+    assert_eq!(expected_unique_message_pubkey, sliced_unique_message_pubkey);
+}
@@ -7,10 +7,10 @@ use hyperlane_core::{
     HyperlaneChain, HyperlaneMessage, Indexed, Indexer, LogMeta, MerkleTreeHook,
     MerkleTreeInsertion, SequenceAwareIndexer,
 };
-use hyperlane_sealevel_mailbox::accounts::OutboxAccount;
+use hyperlane_sealevel_mailbox::accounts::Outbox;
 use tracing::instrument;
 
-use crate::{SealevelMailbox, SealevelMailboxIndexer};
+use crate::{account::decode_account, SealevelMailbox, SealevelMailboxIndexer};
 
 #[async_trait]
 impl MerkleTreeHook for SealevelMailbox {
@@ -26,9 +26,7 @@ impl MerkleTreeHook for SealevelMailbox {
             .rpc()
             .get_account_with_finalized_commitment(&self.outbox.0)
             .await?;
-        let outbox = OutboxAccount::fetch(&mut outbox_account.data.as_ref())
-            .map_err(ChainCommunicationError::from_other)?
-            .into_inner();
+        let outbox = decode_account::<Outbox>("outbox", &outbox_account.data)?;
 
         Ok(outbox.tree)
     }
@@ -0,0 +1,272 @@
+//! Derivation of the PDAs owned by the Hyperlane Sealevel programs.
+//!
+//! Every helper is parameterized by the program id it derives for, so tooling
+//! and tests can target self-hosted deployments as well as the canonical ones.
+
+use hyperlane_core::{ChainCommunicationError, ChainResult, H160, H256};
+use hyperlane_sealevel_igp::{
+    igp_gas_payment_pda_seeds, igp_pda_seeds, igp_program_data_pda_seeds, overhead_igp_pda_seeds,
+};
+use hyperlane_sealevel_mailbox::{
+    mailbox_dispatched_message_pda_seeds, mailbox_inbox_pda_seeds,
+    mailbox_message_dispatch_authority_pda_seeds, mailbox_outbox_pda_seeds,
+    mailbox_process_authority_pda_seeds, mailbox_processed_message_pda_seeds,
+};
+use hyperlane_sealevel_multisig_ism_message_id::{access_control_pda_seeds, domain_data_pda_seeds};
+use hyperlane_sealevel_validator_announce::{
+    replay_protection_pda_seeds, validator_announce_pda_seeds,
+    validator_storage_locations_pda_seeds,
+};
+use solana_sdk::pubkey::Pubkey;
+
+/// Find the PDA for `seeds`, naming the account in the error if there is none.
+fn find_pda(seeds: &[&[u8]], program_id: &Pubkey, account: &str) -> ChainResult<(Pubkey, u8)> {
+    Pubkey::try_find_program_address(seeds, program_id).ok_or_else(|| {
+        ChainCommunicationError::from_other_str(&format!(
+            "Could not find program address for {account}"
+        ))
+    })
+}
+
+/// PDAs of a Mailbox program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MailboxPdas {
+    program_id: Pubkey,
+}
+
+impl MailboxPdas {
+    /// PDAs of the Mailbox deployed at `program_id`
+    pub fn new(program_id: Pubkey) -> Self {
+        Self { program_id }
+    }
+
+    /// The Mailbox program id
+    pub fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    /// The Inbox account
+    pub fn inbox(&self) -> ChainResult<(Pubkey, u8)> {
+        find_pda(mailbox_inbox_pda_seeds!(), &self.program_id, "inbox")
+    }
+
+    /// The Outbox account
+    pub fn outbox(&self) -> ChainResult<(Pubkey, u8)> {
+        find_pda(mailbox_outbox_pda_seeds!(), &self.program_id, "outbox")
+    }
+
+    /// The account storing a dispatched message, keyed by the unique message
+    /// account used when dispatching it
+    pub fn dispatched_message(&self, unique_message_pubkey: &Pubkey) -> ChainResult<(Pubkey, u8)> {
+        find_pda(
+            mailbox_dispatched_message_pda_seeds!(unique_message_pubkey),
+            &self.program_id,
+            "dispatched message",
+        )
+    }
+
+    /// The authority the Mailbox signs with when calling `recipient`
+    pub fn process_authority(&self, recipient: &Pubkey) -> ChainResult<(Pubkey, u8)> {
+        find_pda(
+            mailbox_process_authority_pda_seeds!(recipient),
+            &self.program_id,
+            "process authority",
+        )
+    }
+
+    /// The account marking the message with id `message_id` as processed
+    pub fn processed_message(&self, message_id: H256) -> ChainResult<(Pubkey, u8)> {
+        find_pda(
+            mailbox_processed_message_pda_seeds!(message_id),
+            &self.program_id,
+            "processed message account",
+        )
+    }
+}
+
+/// The authority `sender_program_id` signs with when dispatching messages
+/// through the Mailbox. This PDA is owned by the sender, not the Mailbox.
+pub fn dispatch_authority_pda(sender_program_id: &Pubkey) -> ChainResult<(Pubkey, u8)> {
+    find_pda(
+        mailbox_message_dispatch_authority_pda_seeds!(),
+        sender_program_id,
+        "dispatch authority",
+    )
+}
+
+/// PDAs of an IGP program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IgpPdas {
+    program_id: Pubkey,
+}
+
+impl IgpPdas {
+    /// PDAs of the IGP program deployed at `program_id`
+    pub fn new(program_id: Pubkey) -> Self {
+        Self { program_id }
+    }
+
+    /// The IGP program id
+    pub fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    /// The singleton program data account
+    pub fn program_data(&self) -> ChainResult<(Pubkey, u8)> {
+        find_pda(
+            igp_program_data_pda_seeds!(),
+            &self.program_id,
+            "IGP program data",
+        )
+    }
+
+    /// The IGP account created with `salt`
+    pub fn igp(&self, salt: H256) -> ChainResult<(Pubkey, u8)> {
+        find_pda(igp_pda_seeds!(salt), &self.program_id, "IGP account")
+    }
+
+    /// The overhead IGP account created with `salt`
+    pub fn overhead_igp(&self, salt: H256) -> ChainResult<(Pubkey, u8)> {
+        find_pda(
+            overhead_igp_pda_seeds!(salt),
+            &self.program_id,
+            "overhead IGP account",
+        )
+    }
+
+    /// The account storing a gas payment, keyed by the unique gas payment
+    /// account used when paying
+    pub fn gas_payment(&self, unique_gas_payment_pubkey: &Pubkey) -> ChainResult<(Pubkey, u8)> {
+        find_pda(
+            igp_gas_payment_pda_seeds!(unique_gas_payment_pubkey),
+            &self.program_id,
+            "gas payment",
+        )
+    }
+}
+
+/// PDAs of a message id multisig ISM program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MultisigIsmPdas {
+    program_id: Pubkey,
+}
+
+impl MultisigIsmPdas {
+    /// PDAs of the multisig ISM deployed at `program_id`
+    pub fn new(program_id: Pubkey) -> Self {
+        Self { program_id }
+    }
+
+    /// The multisig ISM program id
+    pub fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    /// The account holding the owner of the ISM
+    pub fn access_control(&self) -> ChainResult<(Pubkey, u8)> {
+        find_pda(
+            access_control_pda_seeds!(),
+            &self.program_id,
+            "access control",
+        )
+    }
+
+    /// The account holding the validators and threshold for messages from
+    /// `origin`
+    pub fn domain_data(&self, origin: u32) -> ChainResult<(Pubkey, u8)> {
+        find_pda(
+            domain_data_pda_seeds!(origin),
+            &self.program_id,
+            "domain data",
+        )
+    }
+}
+
+/// PDAs of a ValidatorAnnounce program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidatorAnnouncePdas {
+    program_id: Pubkey,
+}
+
+impl ValidatorAnnouncePdas {
+    /// PDAs of the ValidatorAnnounce deployed at `program_id`
+    pub fn new(program_id: Pubkey) -> Self {
+        Self { program_id }
+    }
+
+    /// The ValidatorAnnounce program id
+    pub fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    /// The singleton ValidatorAnnounce account
+    pub fn validator_announce(&self) -> ChainResult<(Pubkey, u8)> {
+        find_pda(
+            validator_announce_pda_seeds!(),
+            &self.program_id,
+            "validator announce",
+        )
+    }
+
+    /// The account holding the storage locations announced by `validator`
+    pub fn storage_locations(&self, validator: H160) -> ChainResult<(Pubkey, u8)> {
+        find_pda(
+            validator_storage_locations_pda_seeds!(validator),
+            &self.program_id,
+            "validator storage locations",
+        )
+    }
+
+    /// The account preventing an announcement with `replay_id` from being
+    /// replayed
+    pub fn replay_protection(&self, replay_id: [u8; 32]) -> ChainResult<(Pubkey, u8)> {
+        find_pda(
+            replay_protection_pda_seeds!(replay_id),
+            &self.program_id,
+            "replay protection",
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pdas_depend_on_program_id() {
+        let canonical = MailboxPdas::new(Pubkey::new_unique());
+        let self_hosted = MailboxPdas::new(Pubkey::new_unique());
+        assert_ne!(canonical.inbox().unwrap(), self_hosted.inbox().unwrap());
+        assert_ne!(canonical.inbox().unwrap(), canonical.outbox().unwrap());
+
+        let (inbox, bump) = canonical.inbox().unwrap();
+        assert_eq!(
+            Pubkey::create_program_address(mailbox_inbox_pda_seeds!(bump), &canonical.program_id())
+                .unwrap(),
+            inbox
+        );
+    }
+
+    #[test]
+    fn test_keyed_pdas() {
+        let igp = IgpPdas::new(Pubkey::new_unique());
+        let salt = H256::repeat_byte(1);
+        assert_ne!(igp.igp(salt).unwrap(), igp.overhead_igp(salt).unwrap());
+        assert_ne!(igp.igp(salt).unwrap(), igp.igp(H256::zero()).unwrap());
+
+        let ism = MultisigIsmPdas::new(Pubkey::new_unique());
+        assert_ne!(ism.domain_data(1).unwrap(), ism.domain_data(2).unwrap());
+
+        let validator_announce = ValidatorAnnouncePdas::new(Pubkey::new_unique());
+        let validator = H160::repeat_byte(2);
+        let (storage_locations, bump) = validator_announce.storage_locations(validator).unwrap();
+        assert_eq!(
+            Pubkey::create_program_address(
+                validator_storage_locations_pda_seeds!(validator, bump),
+                &validator_announce.program_id()
+            )
+            .unwrap(),
+            storage_locations
+        );
+    }
+}
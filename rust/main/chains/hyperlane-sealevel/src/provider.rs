@@ -3,33 +3,55 @@ use std::{str::FromStr, sync::Arc};
 use async_trait::async_trait;
 
 use hyperlane_core::{
-    BlockInfo, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain, HyperlaneProvider, TxnInfo,
-    H256, U256,
+    BlockInfo, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain, HyperlaneProvider,
+    HyperlaneProviderError, TxnInfo, H256, U256,
 };
 use solana_sdk::pubkey::Pubkey;
 
-use crate::{error::HyperlaneSealevelError, ConnectionConf, SealevelRpcClient};
+use crate::{
+    error::HyperlaneSealevelError, slot_timestamp::SlotTimestampEstimator, ConnectionConf,
+    SealevelRpcClient,
+};
+
+/// The hash the logs of Sealevel indexers identify their block by. Nodes
+/// can't look blocks up by their hash, so the slot of the block is used
+/// instead.
+pub(crate) fn slot_block_hash(slot: u64) -> H256 {
+    H256::from_low_u64_be(slot)
+}
 
 /// A wrapper around a Sealevel provider to get generic blockchain information.
 #[derive(Debug)]
 pub struct SealevelProvider {
     domain: HyperlaneDomain,
     rpc_client: Arc<SealevelRpcClient>,
+    slot_timestamps: Arc<SlotTimestampEstimator>,
 }
 
 impl SealevelProvider {
     /// Create a new Sealevel provider.
     pub fn new(domain: HyperlaneDomain, conf: &ConnectionConf) -> Self {
         // Set the `processed` commitment at rpc level
-        let rpc_client = Arc::new(SealevelRpcClient::new(conf.url.to_string()));
+        let rpc_client = Arc::new(SealevelRpcClient::new(conf));
+        let slot_timestamps = Arc::new(SlotTimestampEstimator::new(rpc_client.clone()));
 
-        SealevelProvider { domain, rpc_client }
+        SealevelProvider {
+            domain,
+            rpc_client,
+            slot_timestamps,
+        }
     }
 
     /// Get an rpc client
     pub fn rpc(&self) -> &SealevelRpcClient {
         &self.rpc_client
     }
+
+    /// The unix timestamp of `slot` in seconds. Slots without a block time,
+    /// e.g. skipped ones, are timestamped from the block times around them.
+    pub async fn get_slot_timestamp(&self, slot: u64) -> ChainResult<u64> {
+        self.slot_timestamps.timestamp(slot).await
+    }
 }
 
 impl HyperlaneChain for SealevelProvider {
@@ -41,18 +63,31 @@ impl HyperlaneChain for SealevelProvider {
         Box::new(SealevelProvider {
             domain: self.domain.clone(),
             rpc_client: self.rpc_client.clone(),
+            slot_timestamps: self.slot_timestamps.clone(),
         })
     }
 }
 
 #[async_trait]
 impl HyperlaneProvider for SealevelProvider {
-    async fn get_block_by_hash(&self, _hash: &H256) -> ChainResult<BlockInfo> {
-        todo!() // FIXME
+    /// Only blocks identified by [`slot_block_hash`] can be looked up. They
+    /// are timestamped by the block time of their slot, or an estimate of it.
+    async fn get_block_by_hash(&self, hash: &H256) -> ChainResult<BlockInfo> {
+        let slot = hash.to_low_u64_be();
+        if *hash != slot_block_hash(slot) {
+            return Err(HyperlaneProviderError::CouldNotFindObjectByHash(*hash).into());
+        }
+        Ok(BlockInfo {
+            hash: *hash,
+            timestamp: self.get_slot_timestamp(slot).await?,
+            number: slot,
+        })
     }
 
-    async fn get_txn_by_hash(&self, _hash: &H256) -> ChainResult<TxnInfo> {
-        todo!() // FIXME
+    /// Indexed logs don't carry the signature of their transaction yet, so
+    /// transactions can't be looked up
+    async fn get_txn_by_hash(&self, hash: &H256) -> ChainResult<TxnInfo> {
+        Err(HyperlaneProviderError::CouldNotFindObjectByHash(*hash).into())
     }
 
     async fn is_contract(&self, _address: &H256) -> ChainResult<bool> {
@@ -1,3 +1,5 @@
 pub use client::SealevelRpcClient;
+pub(crate) use sender::proxied_client_builder;
 
 mod client;
+mod sender;
@@ -2,8 +2,14 @@ use base64::Engine;
 use borsh::{BorshDeserialize, BorshSerialize};
 use hyperlane_core::{ChainCommunicationError, ChainResult, U256};
 use serializable_account_meta::{SerializableAccountMeta, SimulationReturnData};
+use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
 use solana_client::{
-    nonblocking::rpc_client::RpcClient, rpc_config::RpcProgramAccountsConfig,
+    client_error::ClientErrorKind,
+    nonblocking::rpc_client::RpcClient,
+    rpc_client::RpcClientConfig,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
+    rpc_request::RpcError,
     rpc_response::Response,
 };
 use solana_sdk::{
@@ -18,15 +24,25 @@ use solana_sdk::{
 };
 use solana_transaction_status::{TransactionStatus, UiReturnDataEncoding, UiTransactionReturnData};
 
-use crate::error::HyperlaneSealevelError;
+use crate::{error::HyperlaneSealevelError, ConnectionConf};
+
+use super::sender::HttpRpcSender;
+
+/// JSON-RPC error codes of nodes without the block of a slot: the block isn't
+/// available, the slot was skipped, or the block is missing from long-term
+/// storage
+const NO_BLOCK_TIME_ERROR_CODES: [i64; 3] = [-32004, -32007, -32009];
 
 pub struct SealevelRpcClient(RpcClient);
 
 impl SealevelRpcClient {
-    pub fn new(rpc_endpoint: String) -> Self {
-        Self(RpcClient::new_with_commitment(
-            rpc_endpoint,
-            CommitmentConfig::processed(),
+    /// Create a client of the RPC of `conf` with the `processed` commitment,
+    /// sending its headers through its proxy and presenting its client
+    /// certificate, if set
+    pub fn new(conf: &ConnectionConf) -> Self {
+        Self(RpcClient::new_sender(
+            HttpRpcSender::new(conf),
+            RpcClientConfig::with_commitment(CommitmentConfig::processed()),
         ))
     }
 
@@ -97,6 +113,24 @@ impl SealevelRpcClient {
         Ok(account)
     }
 
+    /// The estimated production time of the block of `slot`, or `None` if the
+    /// slot was skipped or its block time isn't available from the node
+    pub async fn get_block_time(&self, slot: u64) -> ChainResult<Option<i64>> {
+        match self.0.get_block_time(slot).await {
+            Ok(timestamp) => Ok(Some(timestamp)),
+            // A null block time is returned as an error for the user
+            Err(err) => match err.kind() {
+                ClientErrorKind::RpcError(RpcError::ForUser(_)) => Ok(None),
+                ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. })
+                    if NO_BLOCK_TIME_ERROR_CODES.contains(code) =>
+                {
+                    Ok(None)
+                }
+                _ => Err(ChainCommunicationError::from_other(err)),
+            },
+        }
+    }
+
     pub async fn get_block_height(&self) -> ChainResult<u32> {
         let height = self
             .0
@@ -109,6 +143,13 @@ impl SealevelRpcClient {
         Ok(height)
     }
 
+    pub async fn get_slot_with_finalized_commitment(&self) -> ChainResult<u64> {
+        self.0
+            .get_slot_with_commitment(CommitmentConfig::finalized())
+            .await
+            .map_err(ChainCommunicationError::from_other)
+    }
+
     pub async fn get_multiple_accounts_with_finalized_commitment(
         &self,
         pubkeys: &[Pubkey],
@@ -145,6 +186,99 @@ impl SealevelRpcClient {
             .map_err(ChainCommunicationError::from_other)
     }
 
+    /// Fetches the accounts of a program whose data, after the 1 byte
+    /// `initialized` flag, starts with `prefix`. Only `data_slice` of each
+    /// account's data is returned, so that finding an account doesn't
+    /// download every account matching the filter in full.
+    pub async fn get_program_accounts_with_prefix(
+        &self,
+        program_id: &Pubkey,
+        prefix: &[u8],
+        data_slice: UiDataSliceConfig,
+    ) -> ChainResult<Vec<(Pubkey, Account)>> {
+        #[allow(deprecated)]
+        let memcmp = RpcFilterType::Memcmp(Memcmp {
+            // Ignore the first byte, which is the `initialized` bool flag.
+            offset: 1,
+            bytes: MemcmpEncodedBytes::Base64(
+                base64::engine::general_purpose::STANDARD.encode(prefix),
+            ),
+            encoding: None,
+        });
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![memcmp]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: Some(data_slice),
+                commitment: Some(CommitmentConfig::finalized()),
+                min_context_slot: None,
+            },
+            with_context: Some(false),
+        };
+        self.get_program_accounts_with_config(program_id, config)
+            .await
+    }
+
+    /// Fetches `length` bytes of an account's data from `offset`, at
+    /// finalized commitment
+    pub async fn get_account_data_slice_with_finalized_commitment(
+        &self,
+        pubkey: &Pubkey,
+        offset: usize,
+        length: usize,
+    ) -> ChainResult<Vec<u8>> {
+        let account = self
+            .get_account_with_data_slice(
+                pubkey,
+                UiDataSliceConfig { offset, length },
+                CommitmentConfig::finalized(),
+            )
+            .await?
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str("Could not find account data")
+            })?;
+        Ok(account.data)
+    }
+
+    /// Whether an account exists at the given commitment, without fetching
+    /// its data
+    pub async fn account_exists_with_commitment(
+        &self,
+        pubkey: &Pubkey,
+        commitment: CommitmentConfig,
+    ) -> ChainResult<bool> {
+        let account = self
+            .get_account_with_data_slice(
+                pubkey,
+                UiDataSliceConfig {
+                    offset: 0,
+                    length: 0,
+                },
+                commitment,
+            )
+            .await?;
+        Ok(account.is_some())
+    }
+
+    async fn get_account_with_data_slice(
+        &self,
+        pubkey: &Pubkey,
+        data_slice: UiDataSliceConfig,
+        commitment: CommitmentConfig,
+    ) -> ChainResult<Option<Account>> {
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: Some(data_slice),
+            commitment: Some(commitment),
+            min_context_slot: None,
+        };
+        self.0
+            .get_account_with_config(pubkey, config)
+            .await
+            .map(|response| response.value)
+            .map_err(ChainCommunicationError::from_other)
+    }
+
     pub async fn get_signature_statuses(
         &self,
         signatures: &[Signature],
@@ -166,6 +300,28 @@ impl SealevelRpcClient {
         Ok(balance.into())
     }
 
+    /// Balance of an SPL token account, in the token's base units.
+    pub async fn get_token_account_balance(&self, pubkey: &Pubkey) -> ChainResult<U256> {
+        let balance = self
+            .0
+            .get_token_account_balance_with_commitment(pubkey, CommitmentConfig::finalized())
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+        parse_token_amount(&balance.amount)
+    }
+
+    /// Total supply of an SPL token mint, in the token's base units.
+    pub async fn get_token_supply(&self, mint: &Pubkey) -> ChainResult<U256> {
+        let supply = self
+            .0
+            .get_token_supply_with_commitment(mint, CommitmentConfig::finalized())
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+        parse_token_amount(&supply.amount)
+    }
+
     pub async fn is_blockhash_valid(&self, hash: &Hash) -> ChainResult<bool> {
         self.0
             .is_blockhash_valid(hash, CommitmentConfig::processed())
@@ -219,6 +375,34 @@ impl SealevelRpcClient {
         Ok(None)
     }
 
+    /// Simulates an Instruction, returning the compute units it consumed if
+    /// it succeeded, or None if it failed.
+    pub async fn simulate_instruction_compute_units(
+        &self,
+        payer: &Keypair,
+        instruction: Instruction,
+    ) -> ChainResult<Option<u64>> {
+        let commitment = CommitmentConfig::finalized();
+        let recent_blockhash = self
+            .get_latest_blockhash_with_commitment(commitment)
+            .await?;
+        let transaction = Transaction::new_unsigned(Message::new_with_blockhash(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &recent_blockhash,
+        ));
+        let result = self
+            .0
+            .simulate_transaction(&transaction)
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+        if result.err.is_some() {
+            return Ok(None);
+        }
+        Ok(Some(result.units_consumed.unwrap_or_default()))
+    }
+
     async fn simulate_transaction(
         &self,
         transaction: &Transaction,
@@ -240,3 +424,10 @@ impl std::fmt::Debug for SealevelRpcClient {
         f.write_str("RpcClient { ... }")
     }
 }
+
+/// Token amounts are returned as decimal strings so they don't lose precision.
+fn parse_token_amount(amount: &str) -> ChainResult<U256> {
+    U256::from_dec_str(amount).map_err(|_| {
+        ChainCommunicationError::from_other_str(&format!("Invalid token amount `{amount}`"))
+    })
+}
@@ -0,0 +1,186 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use hyperlane_core::{
+    config::{ProxyConf, RpcTlsConf},
+    rpc_clients::{with_dns_failover, RpcUsageMetrics},
+};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, RETRY_AFTER},
+    StatusCode,
+};
+use serde_json::Value;
+use solana_client::{
+    client_error::Result as ClientResult,
+    rpc_custom_error::JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE,
+    rpc_request::{RpcError, RpcRequest, RpcResponseErrorData},
+    rpc_response::RpcSimulateTransactionResult,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use tokio::time::sleep;
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::ConnectionConf;
+
+/// The timeout of the default solana HTTP sender
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// How many times a rate limited request is retried, as by the default
+/// solana HTTP sender
+const MAX_RATE_LIMIT_RETRIES: usize = 5;
+/// How long to wait before retrying a rate limited request which doesn't
+/// say how long to wait
+const RATE_LIMIT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Sends JSON-RPC requests over HTTP with custom headers, through a proxy or
+/// with a client certificate, and fails over between the addresses of the
+/// RPC host, which the default solana HTTP sender doesn't support
+pub(crate) struct HttpRpcSender {
+    client: reqwest::Client,
+    url: Url,
+    request_id: AtomicU64,
+    rpc_usage: Option<RpcUsageMetrics>,
+}
+
+impl HttpRpcSender {
+    pub fn new(conf: &ConnectionConf) -> Self {
+        let mut headers = HeaderMap::new();
+        for (name, value) in conf.rpc_headers.all() {
+            // Headers are validated when the config is parsed
+            let (Ok(name), Ok(mut value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(&value),
+            ) else {
+                warn!(%name, "Skipping invalid RPC header");
+                continue;
+            };
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        let builder = proxied_client_builder(conf.proxy.as_ref())
+            .default_headers(headers)
+            .timeout(REQUEST_TIMEOUT);
+        let client = with_client_certificate(builder, conf.rpc_tls.as_ref())
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            url: conf.url.clone(),
+            request_id: AtomicU64::new(0),
+            rpc_usage: conf.rpc_usage.clone(),
+        }
+    }
+}
+
+/// A reqwest client builder routing requests through `proxy` if set, and
+/// failing over between the addresses of hosts
+pub(crate) fn proxied_client_builder(proxy: Option<&ProxyConf>) -> reqwest::ClientBuilder {
+    let builder = with_dns_failover(reqwest::Client::builder());
+    // Proxies are validated when the config is parsed
+    match proxy.and_then(|proxy| reqwest::Proxy::all(proxy.url().as_str()).ok()) {
+        Some(proxy) => builder.proxy(proxy),
+        None => builder,
+    }
+}
+
+/// Present the client certificate of `tls` if set
+fn with_client_certificate(
+    builder: reqwest::ClientBuilder,
+    tls: Option<&RpcTlsConf>,
+) -> reqwest::ClientBuilder {
+    let Some(tls) = tls else {
+        return builder;
+    };
+    // Certificates are validated when the config is parsed
+    let mut builder = match reqwest::Identity::from_pkcs8_pem(&tls.client_cert, &tls.client_key) {
+        Ok(identity) => builder.identity(identity),
+        Err(err) => {
+            warn!(?err, "Skipping invalid RPC client certificate");
+            builder
+        }
+    };
+    if let Some(ca_cert) = tls.ca_cert.as_deref() {
+        match reqwest::Certificate::from_pem(ca_cert) {
+            Ok(ca_cert) => builder = builder.add_root_certificate(ca_cert),
+            Err(err) => warn!(?err, "Skipping invalid RPC CA certificate"),
+        }
+    }
+    builder
+}
+
+#[async_trait]
+impl RpcSender for HttpRpcSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let request_id = self.request_id.fetch_add(1, Ordering::Relaxed);
+        let body = request.build_request_json(request_id, params).to_string();
+        let method = request.to_string();
+        let mut retries = 0;
+        let response = loop {
+            let response = self
+                .client
+                .post(self.url.clone())
+                .body(body.clone())
+                .send()
+                .await;
+            if let Some(rpc_usage) = &self.rpc_usage {
+                let success = matches!(&response, Ok(response) if response.status().is_success());
+                rpc_usage.record_request(&self.url, &method, success);
+                rpc_usage.record_payload_bytes(&self.url, &method, body.len());
+            }
+            let response = response?;
+            if response.status() != StatusCode::TOO_MANY_REQUESTS
+                || retries == MAX_RATE_LIMIT_RETRIES
+            {
+                break response.error_for_status()?;
+            }
+            retries += 1;
+            let delay = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|delay| delay.to_str().ok()?.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(RATE_LIMIT_RETRY_DELAY);
+            debug!(
+                ?request,
+                ?delay,
+                retries,
+                "Rate limited by the RPC, retrying"
+            );
+            sleep(delay).await;
+        };
+        let mut json: Value = serde_json::from_slice(&response.bytes().await?)?;
+        if let Some(error) = json.get("error") {
+            return Err(response_error(error).into());
+        }
+        Ok(json["result"].take())
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        RpcTransportStats::default()
+    }
+
+    fn url(&self) -> String {
+        self.url.to_string()
+    }
+}
+
+/// The error of a JSON-RPC response, keeping the simulation result of failed
+/// preflight checks as the default sender does
+fn response_error(error: &Value) -> RpcError {
+    let code = error["code"].as_i64().unwrap_or_default();
+    let data = match code {
+        JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE => {
+            serde_json::from_value::<RpcSimulateTransactionResult>(error["data"].clone())
+                .map(RpcResponseErrorData::SendTransactionPreflightFailure)
+                .unwrap_or(RpcResponseErrorData::Empty)
+        }
+        _ => RpcResponseErrorData::Empty,
+    };
+    RpcError::RpcResponseError {
+        code,
+        message: error["message"].as_str().unwrap_or_default().to_owned(),
+        data,
+    }
+}
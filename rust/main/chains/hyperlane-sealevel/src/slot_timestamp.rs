@@ -0,0 +1,182 @@
+//! Timestamps of slots. Nodes only know the block time of slots which
+//! produced a block, and often not of old ones, so the timestamps of the
+//! other slots are estimated from the block times sampled around them.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use hyperlane_core::{ChainCommunicationError, ChainResult};
+use tracing::debug;
+
+use crate::SealevelRpcClient;
+
+/// The target duration of a slot, used until the actual one is sampled
+const NOMINAL_SLOT_DURATION_SECS: f64 = 0.4;
+
+/// How often the block time of the latest finalized slot is sampled
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How many block times are kept, the oldest ones being dropped first
+const MAX_SAMPLES: usize = 4096;
+
+/// The minimum number of slots between two samples for the slot duration
+/// measured between them to be trusted. Block times are in whole seconds,
+/// so the duration measured over fewer slots is mostly rounding.
+const MIN_DRIFT_SPAN_SLOTS: u64 = 150;
+
+/// Block times sampled from the node, by slot
+#[derive(Debug, Default)]
+struct SlotTimeSamples {
+    samples: BTreeMap<u64, i64>,
+    last_sampled_at: Option<Instant>,
+}
+
+impl SlotTimeSamples {
+    fn insert(&mut self, slot: u64, timestamp: i64) {
+        self.samples.insert(slot, timestamp);
+        while self.samples.len() > MAX_SAMPLES {
+            self.samples.pop_first();
+        }
+    }
+
+    fn sample_due(&self) -> bool {
+        self.last_sampled_at
+            .map_or(true, |sampled_at| sampled_at.elapsed() >= SAMPLE_INTERVAL)
+    }
+
+    /// The duration of a slot, measured from the first of `samples` to the
+    /// next one far enough from it. Slots drift from their target duration as
+    /// leaders are slower or faster, so the duration measured the nearest to
+    /// a slot is what its timestamp is extrapolated with.
+    fn slot_duration(mut samples: impl Iterator<Item = (u64, i64)>) -> f64 {
+        let Some((nearest_slot, nearest_timestamp)) = samples.next() else {
+            return NOMINAL_SLOT_DURATION_SECS;
+        };
+        samples
+            .find(|(slot, _)| nearest_slot.abs_diff(*slot) >= MIN_DRIFT_SPAN_SLOTS)
+            .map(|(slot, timestamp)| {
+                (nearest_timestamp - timestamp) as f64 / (nearest_slot as f64 - slot as f64)
+            })
+            .filter(|duration| *duration > 0.)
+            .unwrap_or(NOMINAL_SLOT_DURATION_SECS)
+    }
+
+    /// The timestamp of `slot`, interpolated between the samples around it,
+    /// or extrapolated from the nearest ones if it's outside of the samples
+    fn estimate(&self, slot: u64) -> Option<i64> {
+        if let Some(timestamp) = self.samples.get(&slot) {
+            return Some(*timestamp);
+        }
+        let before = self.samples.range(..slot).next_back();
+        let after = self.samples.range(slot..).next();
+        let estimate = match (before, after) {
+            (Some((&before_slot, &before_timestamp)), Some((&after_slot, &after_timestamp))) => {
+                let progress = (slot - before_slot) as f64 / (after_slot - before_slot) as f64;
+                before_timestamp as f64 + (after_timestamp - before_timestamp) as f64 * progress
+            }
+            (Some((&before_slot, &before_timestamp)), None) => {
+                let samples = self.samples.iter().rev().map(|(s, t)| (*s, *t));
+                let slot_duration = Self::slot_duration(samples);
+                before_timestamp as f64 + (slot - before_slot) as f64 * slot_duration
+            }
+            (None, Some((&after_slot, &after_timestamp))) => {
+                let samples = self.samples.iter().map(|(s, t)| (*s, *t));
+                let slot_duration = Self::slot_duration(samples);
+                after_timestamp as f64 - (after_slot - slot) as f64 * slot_duration
+            }
+            (None, None) => return None,
+        };
+        Some(estimate.round() as i64)
+    }
+}
+
+/// Timestamps slots from the block times of the node, estimating those of
+/// the slots the node has no block time for. The block time of the latest
+/// finalized slot is sampled periodically as timestamps are requested, so
+/// that estimates follow the drift of the slot duration.
+#[derive(Debug)]
+pub(crate) struct SlotTimestampEstimator {
+    rpc_client: Arc<SealevelRpcClient>,
+    samples: Mutex<SlotTimeSamples>,
+}
+
+impl SlotTimestampEstimator {
+    pub(crate) fn new(rpc_client: Arc<SealevelRpcClient>) -> Self {
+        Self {
+            rpc_client,
+            samples: Default::default(),
+        }
+    }
+
+    /// The unix timestamp of `slot` in seconds, as reported by the node if
+    /// it has a block time for it, and estimated otherwise
+    pub(crate) async fn timestamp(&self, slot: u64) -> ChainResult<u64> {
+        self.sample_latest_if_due().await?;
+        let sampled = self.samples.lock().unwrap().samples.get(&slot).copied();
+        let timestamp = match sampled {
+            Some(timestamp) => timestamp,
+            None => match self.rpc_client.get_block_time(slot).await? {
+                Some(timestamp) => {
+                    self.samples.lock().unwrap().insert(slot, timestamp);
+                    timestamp
+                }
+                None => {
+                    let estimate = self.samples.lock().unwrap().estimate(slot);
+                    debug!(slot, ?estimate, "No block time for slot, estimated it");
+                    estimate.ok_or_else(|| {
+                        ChainCommunicationError::from_other_str(
+                            "No block time sampled to estimate the timestamp of the slot from",
+                        )
+                    })?
+                }
+            },
+        };
+        Ok(timestamp.max(0) as u64)
+    }
+
+    async fn sample_latest_if_due(&self) -> ChainResult<()> {
+        {
+            let mut samples = self.samples.lock().unwrap();
+            if !samples.sample_due() {
+                return Ok(());
+            }
+            // Sampled at most once per interval, even if sampling fails
+            samples.last_sampled_at = Some(Instant::now());
+        }
+        let slot = self.rpc_client.get_slot_with_finalized_commitment().await?;
+        if let Some(timestamp) = self.rpc_client.get_block_time(slot).await? {
+            self.samples.lock().unwrap().insert(slot, timestamp);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_estimates_follow_slot_duration_drift() {
+        let mut samples = SlotTimeSamples::default();
+        assert_eq!(samples.estimate(100), None);
+
+        // Slots of half a second rather than the nominal 400ms
+        samples.insert(1_000, 10_000);
+        samples.insert(2_000, 10_500);
+        assert_eq!(samples.estimate(2_000), Some(10_500));
+        // Interpolated between the samples around the slot
+        assert_eq!(samples.estimate(1_500), Some(10_250));
+        // Extrapolated with the slot duration measured near the slot
+        assert_eq!(samples.estimate(3_000), Some(11_000));
+        assert_eq!(samples.estimate(0), Some(9_500));
+
+        // Too few slots between samples to measure their duration
+        let mut samples = SlotTimeSamples::default();
+        samples.insert(1_000, 10_000);
+        samples.insert(1_010, 10_010);
+        assert_eq!(samples.estimate(2_010), Some(10_410));
+    }
+}
@@ -0,0 +1,295 @@
+//! A local solana test validator with the core programs deployed, for
+//! integration tests which run without the sealevel client or docker.
+//!
+//! The programs are deployed from the shared objects bundled in `artifacts/`,
+//! which `rust/sealevel/update_rust_artifacts.sh` exports from the sealevel
+//! build. The solana CLI tools must be installed and on the `PATH`.
+
+#![allow(missing_docs)]
+
+use std::{
+    fs,
+    net::TcpListener,
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use hyperlane_core::{
+    config::OperationBatchConfig, ContractLocator, HyperlaneDomain, KnownHyperlaneDomain, H256,
+};
+use hyperlane_sealevel_igp::igp_pda_seeds;
+use hyperlane_sealevel_mailbox::{mailbox_inbox_pda_seeds, mailbox_outbox_pda_seeds};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+};
+use url::Url;
+
+use crate::ConnectionConf;
+
+/// How long the validator gets to start answering requests
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long airdrops and transactions get to be confirmed
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often the validator is polled while waiting on it
+const POLLING_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Lamports airdropped to the payer when the validator starts
+const PAYER_AIRDROP: u64 = 100 * LAMPORTS_PER_SOL;
+
+/// The mailbox program, deployed from `artifacts/<name>.so`
+const MAILBOX_PROGRAM: &str = "hyperlane_sealevel_mailbox";
+
+/// The IGP program, deployed from `artifacts/<name>.so`
+const IGP_PROGRAM: &str = "hyperlane_sealevel_igp";
+
+/// Salt of the IGP account created by `deploy_core`
+const IGP_SALT: H256 = H256::zero();
+
+fn artifact(program: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("artifacts")
+        .join(format!("{program}.so"));
+    if !path.exists() {
+        bail!(
+            "Missing artifact {}, run rust/sealevel/update_rust_artifacts.sh",
+            path.display()
+        );
+    }
+    Ok(path)
+}
+
+/// A port nothing is listening on
+fn free_port() -> Result<u16> {
+    Ok(TcpListener::bind("127.0.0.1:0")?.local_addr()?.port())
+}
+
+fn pda(seeds: &[&[u8]], program_id: &Pubkey) -> Result<Pubkey> {
+    Pubkey::try_find_program_address(seeds, program_id)
+        .map(|(pda, _bump)| pda)
+        .ok_or_else(|| anyhow!("No PDA for the seeds of {program_id}"))
+}
+
+/// The accounts of the core programs deployed on a localnet
+#[derive(Debug, Clone)]
+pub struct LocalnetCore {
+    /// The mailbox program, dispatching and processing messages
+    pub mailbox: Pubkey,
+    /// The inbox PDA of the mailbox
+    pub inbox: Pubkey,
+    /// The outbox PDA of the mailbox
+    pub outbox: Pubkey,
+    /// The IGP program
+    pub igp_program: Pubkey,
+    /// The IGP account gas is paid to, owned by the payer
+    pub igp_account: Pubkey,
+}
+
+/// A solana test validator with the mailbox and IGP programs loaded,
+/// killed when dropped
+pub struct Localnet {
+    domain: HyperlaneDomain,
+    rpc_url: Url,
+    rpc: RpcClient,
+    payer: Keypair,
+    mailbox_program: Pubkey,
+    igp_program: Pubkey,
+    validator: Child,
+    ledger_dir: PathBuf,
+}
+
+impl Localnet {
+    /// Launches a validator for the `SealevelTest1` domain
+    pub async fn launch() -> Result<Self> {
+        Self::launch_for(HyperlaneDomain::Known(KnownHyperlaneDomain::SealevelTest1)).await
+    }
+
+    /// Launches a validator with the mailbox and IGP programs deployed at
+    /// new program ids, and funds a payer through an airdrop
+    pub async fn launch_for(domain: HyperlaneDomain) -> Result<Self> {
+        let mailbox_program = Keypair::new().pubkey();
+        let igp_program = Keypair::new().pubkey();
+        let rpc_port = free_port()?;
+        let ledger_dir = std::env::temp_dir().join(format!(
+            "hyperlane-sealevel-localnet-{}-{rpc_port}",
+            std::process::id()
+        ));
+
+        let validator = Command::new("solana-test-validator")
+            .arg("--quiet")
+            .arg("--reset")
+            .arg("--ledger")
+            .arg(&ledger_dir)
+            .args(["--rpc-port", &rpc_port.to_string()])
+            .args(["--faucet-port", &free_port()?.to_string()])
+            .arg("--bpf-program")
+            .arg(mailbox_program.to_string())
+            .arg(artifact(MAILBOX_PROGRAM)?)
+            .arg("--bpf-program")
+            .arg(igp_program.to_string())
+            .arg(artifact(IGP_PROGRAM)?)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Failed to launch solana-test-validator, is it on the PATH?")?;
+
+        let rpc_url: Url = format!("http://127.0.0.1:{rpc_port}").parse()?;
+        let rpc =
+            RpcClient::new_with_commitment(rpc_url.to_string(), CommitmentConfig::confirmed());
+        let localnet = Self {
+            domain,
+            rpc_url,
+            rpc,
+            payer: Keypair::new(),
+            mailbox_program,
+            igp_program,
+            validator,
+            ledger_dir,
+        };
+        localnet.wait_until_healthy().await?;
+        localnet
+            .airdrop(&localnet.payer.pubkey(), PAYER_AIRDROP)
+            .await?;
+        Ok(localnet)
+    }
+
+    async fn wait_until_healthy(&self) -> Result<()> {
+        let deadline = Instant::now() + STARTUP_TIMEOUT;
+        while self.rpc.get_health().await.is_err() {
+            if Instant::now() > deadline {
+                bail!("solana-test-validator didn't start in {STARTUP_TIMEOUT:?}");
+            }
+            tokio::time::sleep(POLLING_INTERVAL).await;
+        }
+        Ok(())
+    }
+
+    async fn wait_for_confirmation(&self, signature: &Signature) -> Result<()> {
+        let deadline = Instant::now() + CONFIRMATION_TIMEOUT;
+        while !self.rpc.confirm_transaction(signature).await? {
+            if Instant::now() > deadline {
+                bail!("Transaction {signature} wasn't confirmed in {CONFIRMATION_TIMEOUT:?}");
+            }
+            tokio::time::sleep(POLLING_INTERVAL).await;
+        }
+        Ok(())
+    }
+
+    pub fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    /// The http url of the validator's RPC
+    pub fn rpc_url(&self) -> &Url {
+        &self.rpc_url
+    }
+
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    /// The funded account paying for deployments and transactions
+    pub fn payer(&self) -> &Keypair {
+        &self.payer
+    }
+
+    /// Configuration connecting the agents' sealevel clients to the
+    /// validator
+    pub fn connection_conf(&self) -> ConnectionConf {
+        ConnectionConf {
+            url: self.rpc_url.clone(),
+            operation_batch: OperationBatchConfig::default(),
+            rpc_headers: Default::default(),
+            proxy: None,
+            rpc_tls: None,
+            rpc_usage: None,
+        }
+    }
+
+    /// Locator of a program deployed on the localnet
+    pub fn locator(&self, program_id: Pubkey) -> ContractLocator {
+        ContractLocator {
+            domain: &self.domain,
+            address: H256(program_id.to_bytes()),
+        }
+    }
+
+    /// Requests an airdrop of `lamports` to `account` from the validator's
+    /// faucet, returning once it is confirmed
+    pub async fn airdrop(&self, account: &Pubkey, lamports: u64) -> Result<()> {
+        let signature = self
+            .rpc
+            .request_airdrop(account, lamports)
+            .await
+            .with_context(|| format!("Failed to request an airdrop to {account}"))?;
+        self.wait_for_confirmation(&signature).await
+    }
+
+    /// Sends the instructions in a transaction paid for by the payer,
+    /// returning once it is confirmed
+    pub async fn send(&self, instructions: &[Instruction]) -> Result<Signature> {
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        );
+        let signature = self.rpc.send_transaction(&transaction).await?;
+        self.wait_for_confirmation(&signature).await?;
+        Ok(signature)
+    }
+
+    /// Initializes the mailbox and the IGP. The mailbox charges no protocol
+    /// fee, and its default ISM is the system program until one is set, so
+    /// that it only dispatches messages. The payer owns the IGP account.
+    pub async fn deploy_core(&self) -> Result<LocalnetCore> {
+        let payer = self.payer.pubkey();
+        let mailbox = self.mailbox_program;
+        let init_mailbox = hyperlane_sealevel_mailbox::instruction::init_instruction(
+            mailbox,
+            self.domain.id(),
+            solana_sdk::system_program::id(),
+            0,
+            Default::default(),
+            payer,
+        )?;
+        let init_igp_program =
+            hyperlane_sealevel_igp::instruction::init_instruction(self.igp_program, payer)?;
+        let init_igp = hyperlane_sealevel_igp::instruction::init_igp_instruction(
+            self.igp_program,
+            payer,
+            IGP_SALT,
+            Some(payer),
+            payer,
+        )?;
+        self.send(&[init_mailbox, init_igp_program, init_igp])
+            .await
+            .context("Failed to initialize the core programs")?;
+
+        Ok(LocalnetCore {
+            mailbox,
+            inbox: pda(mailbox_inbox_pda_seeds!(), &mailbox)?,
+            outbox: pda(mailbox_outbox_pda_seeds!(), &mailbox)?,
+            igp_program: self.igp_program,
+            igp_account: pda(igp_pda_seeds!(IGP_SALT), &self.igp_program)?,
+        })
+    }
+}
+
+impl Drop for Localnet {
+    fn drop(&mut self) {
+        let _ = self.validator.kill();
+        let _ = self.validator.wait();
+        let _ = fs::remove_dir_all(&self.ledger_dir);
+    }
+}
@@ -0,0 +1,236 @@
+use std::{fmt::Debug, ops::RangeInclusive};
+
+use async_trait::async_trait;
+use borsh::{BorshDeserialize, BorshSerialize};
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Indexed, Indexer, LogMeta,
+    SequenceAwareIndexer, TokenRouter, TransferRemote, H256, U256,
+};
+use hyperlane_sealevel_token::plugin::SyntheticPlugin;
+use hyperlane_sealevel_token_collateral::plugin::CollateralPlugin;
+use hyperlane_sealevel_token_lib::{
+    accounts::{HyperlaneToken, HyperlaneTokenAccount},
+    hyperlane_token_pda_seeds,
+};
+use solana_sdk::pubkey::Pubkey;
+use tracing::{info, instrument};
+
+use crate::{ConnectionConf, SealevelMailboxIndexer, SealevelProvider, SealevelRpcClient};
+
+/// A reference to a warp route token program on some Sealevel chain
+#[derive(Debug)]
+pub struct SealevelTokenRouter {
+    program_id: Pubkey,
+    token_pda: Pubkey,
+    domain: HyperlaneDomain,
+    provider: SealevelProvider,
+}
+
+impl SealevelTokenRouter {
+    /// Create a new Sealevel token router
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> ChainResult<Self> {
+        let provider = SealevelProvider::new(locator.domain.clone(), conf);
+        let program_id = Pubkey::from(<[u8; 32]>::from(locator.address));
+        let (token_pda, _bump) =
+            Pubkey::try_find_program_address(hyperlane_token_pda_seeds!(), &program_id)
+                .ok_or_else(|| {
+                    ChainCommunicationError::from_other_str(
+                        "Could not find program address for the token account",
+                    )
+                })?;
+        Ok(Self {
+            program_id,
+            token_pda,
+            domain: locator.domain.clone(),
+            provider,
+        })
+    }
+
+    fn rpc(&self) -> &SealevelRpcClient {
+        self.provider.rpc()
+    }
+
+    /// Fetch the token account of the program, with the data of plugin `T`.
+    async fn fetch_token<T>(&self) -> ChainResult<HyperlaneToken<T>>
+    where
+        T: BorshDeserialize + BorshSerialize + Default + Debug,
+    {
+        let account = self
+            .rpc()
+            .get_account_with_finalized_commitment(&self.token_pda)
+            .await?;
+        let token = HyperlaneTokenAccount::<T>::fetch(&mut &account.data[..])
+            .map_err(ChainCommunicationError::from_other)?
+            .into_inner();
+        Ok(*token)
+    }
+}
+
+impl HyperlaneContract for SealevelTokenRouter {
+    fn address(&self) -> H256 {
+        self.program_id.to_bytes().into()
+    }
+}
+
+impl HyperlaneChain for SealevelTokenRouter {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        self.provider.provider()
+    }
+}
+
+#[async_trait]
+impl TokenRouter for SealevelTokenRouter {
+    /// The balance of the escrow account collateral is locked in
+    #[instrument(err, ret, skip(self))]
+    async fn collateral_balance(&self) -> ChainResult<U256> {
+        let token = self.fetch_token::<CollateralPlugin>().await?;
+        self.rpc()
+            .get_token_account_balance(&token.plugin_data.escrow)
+            .await
+    }
+
+    /// The supply of the mint the program mints synthetic tokens from
+    #[instrument(err, ret, skip(self))]
+    async fn synthetic_supply(&self) -> ChainResult<U256> {
+        let token = self.fetch_token::<SyntheticPlugin>().await?;
+        self.rpc().get_token_supply(&token.plugin_data.mint).await
+    }
+}
+
+/// Indexes the transfers a warp route token program sent to remote chains.
+///
+/// Sealevel token programs don't emit a dedicated event, so transfers are
+/// read from the messages the program dispatched through the mailbox, which
+/// can only be looked up by nonce. The ranges this indexer is queried with,
+/// and its tip, are therefore mailbox nonces rather than slots. Other senders
+/// dispatch through the same mailbox, so transfers aren't sequenced and are
+/// meant to be indexed with a rate limited cursor.
+#[derive(Debug)]
+pub struct SealevelTransferRemoteIndexer {
+    mailbox_indexer: SealevelMailboxIndexer,
+    router: H256,
+}
+
+impl SealevelTransferRemoteIndexer {
+    /// Create a new indexer for the transfers sent by `router` through the
+    /// mailbox at `mailbox_locator`
+    pub fn new(
+        conf: &ConnectionConf,
+        mailbox_locator: ContractLocator,
+        router: H256,
+    ) -> ChainResult<Self> {
+        Ok(Self {
+            mailbox_indexer: SealevelMailboxIndexer::new(conf, mailbox_locator)?,
+            router,
+        })
+    }
+
+    async fn message_count(&self) -> ChainResult<u32> {
+        let (count, _) = SequenceAwareIndexer::<HyperlaneMessage>::latest_sequence_count_and_tip(
+            &self.mailbox_indexer,
+        )
+        .await?;
+        count.ok_or_else(|| {
+            ChainCommunicationError::from_other_str("Failed to query the mailbox message count")
+        })
+    }
+}
+
+/// The transfers among `messages` which were sent by `router`
+fn transfers_sent_by(
+    router: H256,
+    messages: Vec<(Indexed<HyperlaneMessage>, LogMeta)>,
+) -> Vec<(Indexed<TransferRemote>, LogMeta)> {
+    messages
+        .into_iter()
+        .filter(|(message, _)| message.inner().sender == router)
+        .filter_map(|(message, meta)| {
+            TransferRemote::from_message(message.inner()).map(|transfer| (transfer.into(), meta))
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Indexer<TransferRemote> for SealevelTransferRemoteIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<TransferRemote>, LogMeta)>> {
+        info!(
+            ?range,
+            router=?self.router,
+            "Fetching SealevelTransferRemoteIndexer TransferRemote logs"
+        );
+
+        // Nonces which weren't dispatched yet have no message to look up
+        let Some(latest_nonce) = self.message_count().await?.checked_sub(1) else {
+            return Ok(vec![]);
+        };
+        let nonces = *range.start()..=u32::min(*range.end(), latest_nonce);
+        let messages =
+            Indexer::<HyperlaneMessage>::fetch_logs_in_range(&self.mailbox_indexer, nonces).await?;
+        Ok(transfers_sent_by(self.router, messages))
+    }
+
+    /// The latest nonce dispatched through the mailbox
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        Ok(self.message_count().await?.saturating_sub(1))
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<TransferRemote> for SealevelTransferRemoteIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<TransferRemote>::get_finalized_block_number(self).await?;
+        Ok((None, tip))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_transfers_sent_by() {
+        let router = H256::repeat_byte(1);
+        let token_message = |nonce, sender| -> (Indexed<HyperlaneMessage>, LogMeta) {
+            let message = HyperlaneMessage {
+                nonce,
+                sender,
+                body: vec![0; 64],
+                ..Default::default()
+            };
+            (message.into(), LogMeta::default())
+        };
+        let non_token_message: (Indexed<HyperlaneMessage>, LogMeta) = {
+            let message = HyperlaneMessage {
+                nonce: 2,
+                sender: router,
+                body: vec![0; 40],
+                ..Default::default()
+            };
+            (message.into(), LogMeta::default())
+        };
+        let messages = vec![
+            token_message(0, router),
+            token_message(1, H256::repeat_byte(2)),
+            non_token_message,
+            token_message(3, router),
+        ];
+
+        let transfers = transfers_sent_by(router, messages);
+        let nonces = transfers
+            .iter()
+            .map(|(transfer, _)| transfer.inner().nonce)
+            .collect::<Vec<_>>();
+        assert_eq!(nonces, vec![0, 3]);
+        assert!(transfers
+            .iter()
+            .all(|(transfer, _)| transfer.sequence.is_none()));
+    }
+}
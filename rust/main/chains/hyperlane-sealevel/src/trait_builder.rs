@@ -1,4 +1,8 @@
-use hyperlane_core::{config::OperationBatchConfig, ChainCommunicationError};
+use hyperlane_core::{
+    config::{OperationBatchConfig, ProxyConf, RpcHeadersConf, RpcTlsConf},
+    rpc_clients::RpcUsageMetrics,
+    ChainCommunicationError,
+};
 use url::Url;
 
 /// Sealevel connection configuration
@@ -8,6 +12,14 @@ pub struct ConnectionConf {
     pub url: Url,
     /// Operation batching configuration
     pub operation_batch: OperationBatchConfig,
+    /// Headers sent with every RPC request
+    pub rpc_headers: RpcHeadersConf,
+    /// The proxy RPC requests are routed through
+    pub proxy: Option<ProxyConf>,
+    /// The client certificate presented to RPCs requiring mutual TLS
+    pub rpc_tls: Option<RpcTlsConf>,
+    /// Where the RPC requests made are recorded, if anywhere
+    pub rpc_usage: Option<RpcUsageMetrics>,
 }
 
 /// An error type when parsing a connection configuration.
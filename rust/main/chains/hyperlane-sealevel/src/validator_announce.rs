@@ -3,13 +3,11 @@ use hyperlane_core::{
     Announcement, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
     SignedType, TxOutcome, ValidatorAnnounce, H160, H256, H512, U256,
 };
-use hyperlane_sealevel_validator_announce::{
-    accounts::ValidatorStorageLocationsAccount, validator_storage_locations_pda_seeds,
-};
+use hyperlane_sealevel_validator_announce::accounts::ValidatorStorageLocationsAccount;
 use solana_sdk::pubkey::Pubkey;
 use tracing::{info, instrument, warn};
 
-use crate::{ConnectionConf, SealevelProvider, SealevelRpcClient};
+use crate::{ConnectionConf, SealevelProvider, SealevelRpcClient, ValidatorAnnouncePdas};
 
 /// A reference to a ValidatorAnnounce contract on some Sealevel chain
 #[derive(Debug)]
@@ -61,17 +59,15 @@ impl ValidatorAnnounce for SealevelValidatorAnnounce {
         info!(program_id=?self.program_id, validators=?validators, "Getting validator storage locations");
 
         // Get the validator storage location PDAs for each validator.
+        let pdas = ValidatorAnnouncePdas::new(self.program_id);
         let account_pubkeys: Vec<Pubkey> = validators
             .iter()
             .map(|v| {
-                let (key, _bump) = Pubkey::find_program_address(
-                    // The seed is based off the H160 representation of the validator address.
-                    validator_storage_locations_pda_seeds!(H160::from_slice(&v.as_bytes()[12..])),
-                    &self.program_id,
-                );
-                key
+                // The seed is based off the H160 representation of the validator address.
+                let (key, _bump) = pdas.storage_locations(H160::from_slice(&v.as_bytes()[12..]))?;
+                Ok(key)
             })
-            .collect();
+            .collect::<ChainResult<_>>()?;
 
         // Get all validator storage location accounts.
         // If an account doesn't exist, it will be returned as None.
@@ -0,0 +1,35 @@
+//! Integration tests against a local solana test validator, which need the
+//! solana CLI tools and the bundled program artifacts, so they're ignored by
+//! default. Run with
+//! `cargo test -p hyperlane-sealevel --features test-utils -- --ignored`.
+
+#![cfg(feature = "test-utils")]
+
+use hyperlane_core::Mailbox;
+use hyperlane_sealevel::{test_utils::Localnet, SealevelMailbox};
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Keypair, signer::Signer};
+
+#[tokio::test]
+#[ignore]
+async fn test_core_programs_are_deployed() {
+    let localnet = Localnet::launch().await.unwrap();
+
+    let account = Keypair::new().pubkey();
+    localnet.airdrop(&account, LAMPORTS_PER_SOL).await.unwrap();
+    assert_eq!(
+        localnet.rpc().get_balance(&account).await.unwrap(),
+        LAMPORTS_PER_SOL
+    );
+
+    let core = localnet.deploy_core().await.unwrap();
+    assert!(localnet.rpc().get_account(&core.outbox).await.is_ok());
+    assert!(localnet.rpc().get_account(&core.igp_account).await.is_ok());
+
+    let mailbox = SealevelMailbox::new(
+        &localnet.connection_conf(),
+        localnet.locator(core.mailbox),
+        None,
+    )
+    .unwrap();
+    assert_eq!(mailbox.count(None).await.unwrap(), 0);
+}
@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use hyperlane_core::{ChainResult, TxCostEstimate, TxOutcome, U256};
+use starknet::{
+    accounts::{Account, Call, ExecutionEncoding, SingleOwnerAccount},
+    core::types::{FieldElement, TransactionExecutionStatus, TransactionStatus},
+    providers::{
+        jsonrpc::{HttpTransport, JsonRpcClient},
+        Provider,
+    },
+    signers::LocalWallet,
+};
+use tracing::{debug, instrument};
+
+use crate::{codec::h256_from_felt, ConnectionConf, HyperlaneStarknetError, Signer};
+
+/// Account contract that signs and pays for the transactions of an agent
+pub(crate) type StarknetAccount = SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>;
+
+/// Margin added to the simulated gas usage of a transaction, as a percentage
+const GAS_LIMIT_MARGIN_PERCENT: u64 = 50;
+/// How often the status of a sent transaction is polled
+const TX_STATUS_POLL_INTERVAL: Duration = Duration::from_secs(3);
+/// How many times the status of a sent transaction is polled before giving up
+const TX_STATUS_POLL_ATTEMPTS: usize = 40;
+
+/// Build the account of `signer` on the chain `conf` connects to
+pub(crate) async fn build_account(
+    conf: &ConnectionConf,
+    signer: &Signer,
+) -> ChainResult<StarknetAccount> {
+    let rpc_client = JsonRpcClient::new(HttpTransport::new(conf.url.clone()));
+    let chain_id = rpc_client
+        .chain_id()
+        .await
+        .map_err(HyperlaneStarknetError::from)?;
+    Ok(SingleOwnerAccount::new(
+        rpc_client,
+        signer.local_wallet(),
+        signer.address,
+        chain_id,
+        ExecutionEncoding::New,
+    ))
+}
+
+/// Simulate `call` from `account` and estimate its cost
+pub(crate) async fn estimate_costs(
+    account: &StarknetAccount,
+    call: Call,
+) -> ChainResult<TxCostEstimate> {
+    let estimate = account
+        .execute_v1(vec![call])
+        .estimate_fee()
+        .await
+        .map_err(HyperlaneStarknetError::from)?;
+    Ok(TxCostEstimate {
+        gas_limit: felt_to_u256(estimate.gas_consumed),
+        gas_price: felt_to_u256(estimate.gas_price).try_into()?,
+        l2_gas_limit: None,
+        l1_data_fee: None,
+    })
+}
+
+/// Send `call` from `account` and wait for it to be accepted on L2.
+///
+/// The max fee of the transaction is `gas_limit` times the current gas price,
+/// or the simulated gas usage plus a margin if no limit is given.
+#[instrument(skip(account, call), fields(to = %format!("{:#x}", call.to)))]
+pub(crate) async fn send_transaction(
+    account: &StarknetAccount,
+    call: Call,
+    gas_limit: Option<U256>,
+) -> ChainResult<TxOutcome> {
+    let execution = account.execute_v1(vec![call]);
+    let estimate = execution
+        .estimate_fee()
+        .await
+        .map_err(HyperlaneStarknetError::from)?;
+    let gas_price = felt_to_u256(estimate.gas_price);
+    let gas_limit = gas_limit.unwrap_or_else(|| {
+        felt_to_u256(estimate.gas_consumed) * (100 + GAS_LIMIT_MARGIN_PERCENT) / 100
+    });
+    let max_fee = u256_to_felt(gas_limit.saturating_mul(gas_price))?;
+
+    let result = execution
+        .max_fee(max_fee)
+        .send()
+        .await
+        .map_err(HyperlaneStarknetError::from)?;
+    let tx_hash = result.transaction_hash;
+    debug!(tx_hash = %format!("{tx_hash:#x}"), "Sent transaction");
+
+    let executed = wait_for_execution(account.provider(), tx_hash).await?;
+    Ok(TxOutcome {
+        transaction_id: h256_from_felt(tx_hash).into(),
+        executed,
+        gas_used: felt_to_u256(estimate.gas_consumed),
+        gas_price: gas_price.try_into()?,
+    })
+}
+
+/// Poll the status of a transaction until it is accepted or rejected.
+/// Returns whether it executed successfully.
+async fn wait_for_execution(
+    provider: &JsonRpcClient<HttpTransport>,
+    tx_hash: FieldElement,
+) -> ChainResult<bool> {
+    for _ in 0..TX_STATUS_POLL_ATTEMPTS {
+        tokio::time::sleep(TX_STATUS_POLL_INTERVAL).await;
+        // The node may not know about the transaction right after it was sent
+        let Ok(status) = provider.get_transaction_status(tx_hash).await else {
+            continue;
+        };
+        match status {
+            TransactionStatus::Received => continue,
+            TransactionStatus::Rejected => return Ok(false),
+            TransactionStatus::AcceptedOnL2(execution)
+            | TransactionStatus::AcceptedOnL1(execution) => {
+                return Ok(execution == TransactionExecutionStatus::Succeeded)
+            }
+        }
+    }
+    Err(HyperlaneStarknetError::TransactionTimeout(format!("{tx_hash:#x}")).into())
+}
+
+fn felt_to_u256(value: FieldElement) -> U256 {
+    U256::from_big_endian(&value.to_bytes_be())
+}
+
+fn u256_to_felt(value: U256) -> ChainResult<FieldElement> {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    FieldElement::from_bytes_be(&bytes).map_err(|e| HyperlaneStarknetError::from(e).into())
+}
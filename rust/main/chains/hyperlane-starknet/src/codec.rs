@@ -0,0 +1,265 @@
+//! Conversions between hyperlane types and the Cairo serialization of the
+//! Starknet contracts, where every value is a sequence of field elements.
+
+use hyperlane_core::{ChainResult, HyperlaneMessage, H256};
+use starknet::core::types::FieldElement;
+
+use crate::HyperlaneStarknetError;
+
+/// Bytes are packed into u128 words
+const WORD_SIZE: usize = 16;
+/// Number of bytes of a Cairo short string
+const SHORT_STRING_SIZE: usize = 31;
+
+/// Convert an H256 into a field element, failing if it exceeds the field
+/// modulus
+pub(crate) fn felt_from_h256(value: H256) -> ChainResult<FieldElement> {
+    FieldElement::from_bytes_be(&value.0).map_err(|e| HyperlaneStarknetError::from(e).into())
+}
+
+/// Convert a field element into an H256
+pub(crate) fn h256_from_felt(value: FieldElement) -> H256 {
+    H256(value.to_bytes_be())
+}
+
+/// Encode an H256 as a Cairo u256, which is a (low, high) pair of u128s
+pub(crate) fn encode_u256(value: H256) -> [FieldElement; 2] {
+    let mut high = [0u8; 32];
+    let mut low = [0u8; 32];
+    high[WORD_SIZE..].copy_from_slice(&value.0[..WORD_SIZE]);
+    low[WORD_SIZE..].copy_from_slice(&value.0[WORD_SIZE..]);
+    [felt_from_word(low), felt_from_word(high)]
+}
+
+/// Encode bytes as a Cairo `Bytes` struct: the byte length followed by the
+/// array of u128 words, with the last word right-padded with zeros.
+pub(crate) fn encode_bytes(value: &[u8]) -> Vec<FieldElement> {
+    let words = value.chunks(WORD_SIZE).map(|chunk| {
+        let mut word = [0u8; 32];
+        word[WORD_SIZE..WORD_SIZE + chunk.len()].copy_from_slice(chunk);
+        felt_from_word(word)
+    });
+    let word_count = (value.len() + WORD_SIZE - 1) / WORD_SIZE;
+
+    let mut encoded = Vec::with_capacity(word_count + 2);
+    encoded.push(FieldElement::from(value.len() as u64));
+    encoded.push(FieldElement::from(word_count as u64));
+    encoded.extend(words);
+    encoded
+}
+
+/// Encode a hyperlane message as the Cairo `Message` struct
+pub(crate) fn encode_message(message: &HyperlaneMessage) -> Vec<FieldElement> {
+    let mut encoded = vec![
+        FieldElement::from(message.version),
+        FieldElement::from(message.nonce),
+        FieldElement::from(message.origin),
+    ];
+    encoded.extend(encode_u256(message.sender));
+    encoded.push(FieldElement::from(message.destination));
+    encoded.extend(encode_u256(message.recipient));
+    encoded.extend(encode_bytes(&message.body));
+    encoded
+}
+
+/// Encode a string as an array of Cairo short strings
+pub(crate) fn encode_string(value: &str) -> Vec<FieldElement> {
+    let chunks = value.as_bytes().chunks(SHORT_STRING_SIZE);
+    let mut encoded = vec![FieldElement::from(chunks.len() as u64)];
+    encoded.extend(chunks.map(|chunk| {
+        let mut word = [0u8; 32];
+        word[32 - chunk.len()..].copy_from_slice(chunk);
+        felt_from_word(word)
+    }));
+    encoded
+}
+
+/// Build a field element from 32 bytes which are known to be in range
+fn felt_from_word(word: [u8; 32]) -> FieldElement {
+    FieldElement::from_bytes_be(&word).expect("word is smaller than the field modulus")
+}
+
+/// Reads hyperlane types from the field elements returned by a contract call
+/// or emitted in an event.
+pub(crate) struct FeltReader<'a> {
+    felts: &'a [FieldElement],
+    position: usize,
+}
+
+impl<'a> FeltReader<'a> {
+    pub(crate) fn new(felts: &'a [FieldElement]) -> Self {
+        Self { felts, position: 0 }
+    }
+
+    /// Read the next field element
+    pub(crate) fn felt(&mut self) -> ChainResult<FieldElement> {
+        let felt = self.felts.get(self.position).copied().ok_or_else(|| {
+            HyperlaneStarknetError::InvalidResponse(format!(
+                "expected at least {} field elements, got {}",
+                self.position + 1,
+                self.felts.len()
+            ))
+        })?;
+        self.position += 1;
+        Ok(felt)
+    }
+
+    /// Read the next field element as a u64
+    pub(crate) fn u64(&mut self) -> ChainResult<u64> {
+        let bytes = self.felt()?.to_bytes_be();
+        if bytes[..24].iter().any(|b| *b != 0) {
+            return Err(HyperlaneStarknetError::InvalidResponse(
+                "field element does not fit in a u64".to_owned(),
+            )
+            .into());
+        }
+        Ok(u64::from_be_bytes(bytes[24..].try_into().unwrap()))
+    }
+
+    /// Read the next field element as a u32
+    pub(crate) fn u32(&mut self) -> ChainResult<u32> {
+        let value = self.u64()?;
+        u32::try_from(value).map_err(|_| {
+            HyperlaneStarknetError::InvalidResponse(format!("{value} does not fit in a u32")).into()
+        })
+    }
+
+    /// Read the next field element as a u8
+    pub(crate) fn u8(&mut self) -> ChainResult<u8> {
+        let value = self.u64()?;
+        u8::try_from(value).map_err(|_| {
+            HyperlaneStarknetError::InvalidResponse(format!("{value} does not fit in a u8")).into()
+        })
+    }
+
+    /// Read the next field element as a bool
+    pub(crate) fn bool(&mut self) -> ChainResult<bool> {
+        Ok(self.felt()? != FieldElement::ZERO)
+    }
+
+    /// Read the next field element as an H256
+    pub(crate) fn h256(&mut self) -> ChainResult<H256> {
+        self.felt().map(h256_from_felt)
+    }
+
+    /// Read a Cairo u256 into an H256
+    pub(crate) fn u256(&mut self) -> ChainResult<H256> {
+        let low = self.word()?;
+        let high = self.word()?;
+        let mut value = H256::zero();
+        value.0[..WORD_SIZE].copy_from_slice(&high);
+        value.0[WORD_SIZE..].copy_from_slice(&low);
+        Ok(value)
+    }
+
+    /// Read a Cairo `Bytes` struct
+    pub(crate) fn bytes(&mut self) -> ChainResult<Vec<u8>> {
+        let size = self.u32()? as usize;
+        let word_count = self.u32()? as usize;
+        let mut bytes = Vec::with_capacity(word_count * WORD_SIZE);
+        for _ in 0..word_count {
+            bytes.extend_from_slice(&self.word()?);
+        }
+        if size > bytes.len() {
+            return Err(HyperlaneStarknetError::InvalidResponse(format!(
+                "bytes of size {size} only contain {} bytes",
+                bytes.len()
+            ))
+            .into());
+        }
+        bytes.truncate(size);
+        Ok(bytes)
+    }
+
+    /// Read a Cairo `Message` struct
+    pub(crate) fn message(&mut self) -> ChainResult<HyperlaneMessage> {
+        Ok(HyperlaneMessage {
+            version: self.u8()?,
+            nonce: self.u32()?,
+            origin: self.u32()?,
+            sender: self.u256()?,
+            destination: self.u32()?,
+            recipient: self.u256()?,
+            body: self.bytes()?,
+        })
+    }
+
+    /// Read an array of Cairo short strings as one string
+    pub(crate) fn string(&mut self) -> ChainResult<String> {
+        let len = self.u32()?;
+        let mut bytes = vec![];
+        for _ in 0..len {
+            let word = self.felt()?.to_bytes_be();
+            let start = word.iter().position(|b| *b != 0).unwrap_or(word.len());
+            bytes.extend_from_slice(&word[start..]);
+        }
+        String::from_utf8(bytes)
+            .map_err(|e| HyperlaneStarknetError::InvalidResponse(e.to_string()).into())
+    }
+
+    /// Read an array using `read` for every element
+    pub(crate) fn array<T>(
+        &mut self,
+        mut read: impl FnMut(&mut Self) -> ChainResult<T>,
+    ) -> ChainResult<Vec<T>> {
+        let len = self.u32()?;
+        (0..len).map(|_| read(self)).collect()
+    }
+
+    /// Read the next field element as a u128 word
+    fn word(&mut self) -> ChainResult<[u8; WORD_SIZE]> {
+        let bytes = self.felt()?.to_bytes_be();
+        if bytes[..WORD_SIZE].iter().any(|b| *b != 0) {
+            return Err(HyperlaneStarknetError::InvalidResponse(
+                "field element does not fit in a u128".to_owned(),
+            )
+            .into());
+        }
+        Ok(bytes[WORD_SIZE..].try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_message_roundtrip() {
+        let message = HyperlaneMessage {
+            version: 3,
+            nonce: 42,
+            origin: 23448593,
+            sender: H256::repeat_byte(0xab),
+            destination: 1,
+            recipient: H256::from_low_u64_be(0x1234),
+            body: (0u8..35).collect(),
+        };
+
+        let encoded = encode_message(&message);
+        // 3 header fields, 2 u256s of 2 felts, the destination, the body size,
+        // the word count and 3 words
+        assert_eq!(encoded.len(), 13);
+
+        let mut reader = FeltReader::new(&encoded);
+        assert_eq!(reader.message().unwrap(), message);
+        assert!(reader.felt().is_err());
+    }
+
+    #[test]
+    fn test_u256_is_low_then_high() {
+        let mut value = H256::zero();
+        value.0[15] = 1;
+        value.0[31] = 2;
+        let [low, high] = encode_u256(value);
+        assert_eq!(low, FieldElement::from(2u64));
+        assert_eq!(high, FieldElement::from(1u64));
+    }
+
+    #[test]
+    fn test_string_roundtrip() {
+        let location = "s3://hyperlane-validator-signatures-starknet/us-east-1";
+        let encoded = encode_string(location);
+        assert_eq!(encoded[0], FieldElement::from(2u64));
+        assert_eq!(FeltReader::new(&encoded).string().unwrap(), location);
+    }
+}
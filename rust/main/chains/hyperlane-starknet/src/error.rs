@@ -0,0 +1,37 @@
+use hyperlane_core::ChainCommunicationError;
+use starknet::{accounts::AccountError, core::types::FromByteArrayError, providers::ProviderError};
+
+/// Errors from the crates specific to the hyperlane-starknet
+/// implementation.
+/// This error can then be converted into the broader error type
+/// in hyperlane-core using the `From` trait impl
+#[derive(Debug, thiserror::Error)]
+pub enum HyperlaneStarknetError {
+    /// Error from the JSON-RPC provider
+    #[error(transparent)]
+    ProviderError(#[from] ProviderError),
+    /// Error building, signing or sending a transaction from the account
+    #[error("{0}")]
+    AccountError(String),
+    /// A value does not fit in a field element
+    #[error("Invalid field element: {0}")]
+    InvalidFieldElement(#[from] FromByteArrayError),
+    /// A contract returned data that can't be decoded
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+    /// The transaction was not included before the timeout
+    #[error("Transaction {0} was not included in time")]
+    TransactionTimeout(String),
+}
+
+impl<S: std::error::Error> From<AccountError<S>> for HyperlaneStarknetError {
+    fn from(value: AccountError<S>) -> Self {
+        HyperlaneStarknetError::AccountError(value.to_string())
+    }
+}
+
+impl From<HyperlaneStarknetError> for ChainCommunicationError {
+    fn from(value: HyperlaneStarknetError) -> Self {
+        ChainCommunicationError::from_other(value)
+    }
+}
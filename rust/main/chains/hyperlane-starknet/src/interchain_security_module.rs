@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use hyperlane_core::{
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneMessage, HyperlaneProvider, InterchainSecurityModule, ModuleType, H256, U256,
+};
+use num_traits::FromPrimitive;
+use starknet::{core::types::FieldElement, macros::selector};
+use tracing::{instrument, warn};
+
+use crate::{
+    codec::{encode_bytes, encode_message, felt_from_h256, h256_from_felt, FeltReader},
+    ConnectionConf, StarknetProvider,
+};
+
+/// A reference to an ISM contract on some Starknet chain
+#[derive(Debug)]
+pub struct StarknetInterchainSecurityModule {
+    provider: StarknetProvider,
+    address: FieldElement,
+}
+
+impl StarknetInterchainSecurityModule {
+    /// Create a new Starknet ISM
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        Ok(Self {
+            provider: StarknetProvider::new(locator.domain.clone(), conf),
+            address: felt_from_h256(locator.address)?,
+        })
+    }
+}
+
+impl HyperlaneContract for StarknetInterchainSecurityModule {
+    fn address(&self) -> H256 {
+        h256_from_felt(self.address)
+    }
+}
+
+impl HyperlaneChain for StarknetInterchainSecurityModule {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.provider.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+#[async_trait]
+impl InterchainSecurityModule for StarknetInterchainSecurityModule {
+    #[instrument]
+    async fn module_type(&self) -> ChainResult<ModuleType> {
+        let result = self
+            .provider
+            .call(self.address, selector!("module_type"), vec![])
+            .await?;
+        // The Cairo `ModuleType` enum is serialized as its variant index
+        // followed by the variant data, and its variants are in the same order
+        // as the core enum.
+        let variant = FeltReader::new(&result).u32()?;
+        if let Some(module_type) = ModuleType::from_u32(variant) {
+            Ok(module_type)
+        } else {
+            warn!(%variant, "Unknown module type");
+            Ok(ModuleType::Unused)
+        }
+    }
+
+    #[instrument(err)]
+    async fn dry_run_verify(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<Option<U256>> {
+        let mut calldata = encode_bytes(metadata);
+        calldata.extend(encode_message(message));
+        let result = self
+            .provider
+            .call(self.address, selector!("verify"), calldata)
+            .await?;
+        let verified = FeltReader::new(&result).bool()?;
+        // Simulating `verify` from an account costs a signature for every ISM,
+        // so the view call is used and a dummy gas value is returned instead.
+        let dummy_gas_value = U256::one();
+        Ok(verified.then_some(dummy_gas_value))
+    }
+}
@@ -0,0 +1,20 @@
+//! Implementation of hyperlane for Starknet.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+pub use self::{
+    error::*, interchain_security_module::*, mailbox::*, multisig_ism::*, provider::*, signers::*,
+    trait_builder::*, validator_announce::*,
+};
+
+mod account;
+mod codec;
+mod error;
+mod interchain_security_module;
+mod mailbox;
+mod multisig_ism;
+mod provider;
+mod signers;
+mod trait_builder;
+mod validator_announce;
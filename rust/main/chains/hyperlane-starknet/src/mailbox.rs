@@ -0,0 +1,252 @@
+use std::{
+    fmt::{Debug, Formatter},
+    num::NonZeroU64,
+    ops::RangeInclusive,
+};
+
+use async_trait::async_trait;
+use hyperlane_core::{
+    utils::bytes_to_hex, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Indexed, Indexer,
+    LogMeta, Mailbox, SequenceAwareIndexer, TxCostEstimate, TxOutcome, H256, U256,
+};
+use starknet::{accounts::Call, core::types::FieldElement, macros::selector};
+use tracing::instrument;
+
+use crate::{
+    account::{build_account, estimate_costs, send_transaction, StarknetAccount},
+    codec::{
+        encode_bytes, encode_message, encode_u256, felt_from_h256, h256_from_felt, FeltReader,
+    },
+    ConnectionConf, Signer, StarknetProvider,
+};
+
+/// A reference to a Mailbox contract on some Starknet chain
+pub struct StarknetMailbox {
+    provider: StarknetProvider,
+    address: FieldElement,
+    account: Option<StarknetAccount>,
+}
+
+impl StarknetMailbox {
+    /// Create a new Starknet mailbox
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        signer: Option<Signer>,
+    ) -> ChainResult<Self> {
+        let account = match signer {
+            Some(signer) => Some(build_account(conf, &signer).await?),
+            None => None,
+        };
+        Ok(Self {
+            provider: StarknetProvider::new(locator.domain.clone(), conf),
+            address: felt_from_h256(locator.address)?,
+            account,
+        })
+    }
+
+    fn account(&self) -> ChainResult<&StarknetAccount> {
+        self.account
+            .as_ref()
+            .ok_or(ChainCommunicationError::SignerUnavailable)
+    }
+
+    fn process_call(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Call {
+        let mut calldata = encode_bytes(metadata);
+        calldata.extend(encode_message(message));
+        Call {
+            to: self.address,
+            selector: selector!("process"),
+            calldata,
+        }
+    }
+}
+
+impl HyperlaneContract for StarknetMailbox {
+    fn address(&self) -> H256 {
+        h256_from_felt(self.address)
+    }
+}
+
+impl HyperlaneChain for StarknetMailbox {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.provider.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+impl Debug for StarknetMailbox {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self as &dyn HyperlaneContract)
+    }
+}
+
+#[async_trait]
+impl Mailbox for StarknetMailbox {
+    #[instrument(level = "debug", err, ret, skip(self))]
+    async fn count(&self, lag: Option<NonZeroU64>) -> ChainResult<u32> {
+        assert!(
+            lag.is_none(),
+            "Starknet does not support querying point-in-time"
+        );
+        let result = self
+            .provider
+            .call(self.address, selector!("nonce"), vec![])
+            .await?;
+        FeltReader::new(&result).u32()
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    async fn delivered(&self, id: H256) -> ChainResult<bool> {
+        let result = self
+            .provider
+            .call(
+                self.address,
+                selector!("delivered"),
+                encode_u256(id).to_vec(),
+            )
+            .await?;
+        FeltReader::new(&result).bool()
+    }
+
+    #[instrument(err, ret, skip(self))]
+    async fn default_ism(&self) -> ChainResult<H256> {
+        let result = self
+            .provider
+            .call(self.address, selector!("get_default_ism"), vec![])
+            .await?;
+        FeltReader::new(&result).h256()
+    }
+
+    #[instrument(err, ret, skip(self))]
+    async fn recipient_ism(&self, recipient: H256) -> ChainResult<H256> {
+        let result = self
+            .provider
+            .call(
+                self.address,
+                selector!("recipient_ism"),
+                vec![felt_from_h256(recipient)?],
+            )
+            .await?;
+        FeltReader::new(&result).h256()
+    }
+
+    #[instrument(err, ret, skip(self), fields(msg=%message, metadata=%bytes_to_hex(metadata)))]
+    async fn process(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+        tx_gas_limit: Option<U256>,
+    ) -> ChainResult<TxOutcome> {
+        let call = self.process_call(message, metadata);
+        send_transaction(self.account()?, call, tx_gas_limit).await
+    }
+
+    #[instrument(err, ret, skip(self), fields(msg=%message, metadata=%bytes_to_hex(metadata)))]
+    async fn process_estimate_costs(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<TxCostEstimate> {
+        let call = self.process_call(message, metadata);
+        estimate_costs(self.account()?, call).await
+    }
+
+    fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8> {
+        self.process_call(message, metadata)
+            .calldata
+            .iter()
+            .flat_map(|felt| felt.to_bytes_be())
+            .collect()
+    }
+}
+
+/// Struct that retrieves event data for a Starknet Mailbox contract
+#[derive(Debug)]
+pub struct StarknetMailboxIndexer {
+    mailbox: StarknetMailbox,
+}
+
+impl StarknetMailboxIndexer {
+    /// Create a new StarknetMailboxIndexer
+    pub async fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        Ok(Self {
+            mailbox: StarknetMailbox::new(conf, locator, None).await?,
+        })
+    }
+}
+
+#[async_trait]
+impl Indexer<HyperlaneMessage> for StarknetMailboxIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
+        let events = self
+            .mailbox
+            .provider
+            .get_events(self.mailbox.address, "Dispatch", range)
+            .await?;
+        events
+            .into_iter()
+            .map(|(event, meta)| {
+                let message = FeltReader::new(&event.data).message()?;
+                Ok((message.into(), meta))
+            })
+            .collect()
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.mailbox.provider.get_block_number().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<HyperlaneMessage> for StarknetMailboxIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<HyperlaneMessage>::get_finalized_block_number(self).await?;
+        let sequence = self.mailbox.count(None).await?;
+        Ok((Some(sequence), tip))
+    }
+}
+
+#[async_trait]
+impl Indexer<H256> for StarknetMailboxIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
+        let events = self
+            .mailbox
+            .provider
+            .get_events(self.mailbox.address, "ProcessId", range)
+            .await?;
+        events
+            .into_iter()
+            .map(|(event, meta)| {
+                // The first key is the event selector, the message id is the
+                // u256 in the following keys
+                let message_id = FeltReader::new(event.keys.get(1..).unwrap_or_default()).u256()?;
+                Ok((message_id.into(), meta))
+            })
+            .collect()
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.mailbox.provider.get_block_number().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<H256> for StarknetMailboxIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<H256>::get_finalized_block_number(self).await?;
+
+        // No sequence for message deliveries.
+        Ok((None, tip))
+    }
+}
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use hyperlane_core::{
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneMessage, HyperlaneProvider, MultisigIsm, H256,
+};
+use starknet::{core::types::FieldElement, macros::selector};
+use tracing::instrument;
+
+use crate::{
+    codec::{encode_message, felt_from_h256, h256_from_felt, FeltReader},
+    ConnectionConf, StarknetProvider,
+};
+
+/// A reference to a MultisigIsm contract on some Starknet chain
+#[derive(Debug)]
+pub struct StarknetMultisigIsm {
+    provider: StarknetProvider,
+    address: FieldElement,
+}
+
+impl StarknetMultisigIsm {
+    /// Create a new Starknet MultisigIsm
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        Ok(Self {
+            provider: StarknetProvider::new(locator.domain.clone(), conf),
+            address: felt_from_h256(locator.address)?,
+        })
+    }
+}
+
+impl HyperlaneContract for StarknetMultisigIsm {
+    fn address(&self) -> H256 {
+        h256_from_felt(self.address)
+    }
+}
+
+impl HyperlaneChain for StarknetMultisigIsm {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.provider.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+#[async_trait]
+impl MultisigIsm for StarknetMultisigIsm {
+    #[instrument(err)]
+    async fn validators_and_threshold(
+        &self,
+        message: &HyperlaneMessage,
+    ) -> ChainResult<(Vec<H256>, u8)> {
+        let result = self
+            .provider
+            .call(
+                self.address,
+                selector!("validators_and_threshold"),
+                encode_message(message),
+            )
+            .await?;
+        let mut reader = FeltReader::new(&result);
+        // Validators are Ethereum addresses, which are left-padded like in
+        // the other VMs
+        let validators = reader.array(FeltReader::h256)?;
+        let threshold = reader.u8()?;
+        Ok((validators, threshold))
+    }
+}
@@ -0,0 +1,243 @@
+use std::{ops::RangeInclusive, sync::Arc};
+
+use async_trait::async_trait;
+use hyperlane_core::{
+    BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain,
+    HyperlaneProvider, HyperlaneProviderError, LogMeta, TxnInfo, H256, U256,
+};
+use starknet::{
+    core::{
+        types::{
+            BlockId, BlockTag, EmittedEvent, EventFilter, FieldElement, FunctionCall,
+            InvokeTransaction, MaybePendingBlockWithTxHashes, StarknetError, Transaction,
+        },
+        utils::get_selector_from_name,
+    },
+    providers::{
+        jsonrpc::{HttpTransport, JsonRpcClient},
+        Provider, ProviderError,
+    },
+};
+
+use crate::{
+    codec::{felt_from_h256, h256_from_felt, FeltReader},
+    ConnectionConf, HyperlaneStarknetError,
+};
+
+/// Address of the ETH token contract fees are paid in, which is the same on
+/// every Starknet network.
+const FEE_TOKEN_ADDRESS: &str =
+    "0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7";
+/// Number of events requested per page when indexing
+const EVENTS_CHUNK_SIZE: u64 = 100;
+
+/// A wrapper around a Starknet JSON-RPC client to get generic blockchain
+/// information.
+#[derive(Debug, Clone)]
+pub struct StarknetProvider {
+    domain: HyperlaneDomain,
+    rpc_client: Arc<JsonRpcClient<HttpTransport>>,
+}
+
+impl StarknetProvider {
+    /// Create a new Starknet provider
+    pub fn new(domain: HyperlaneDomain, conf: &ConnectionConf) -> Self {
+        let rpc_client = JsonRpcClient::new(HttpTransport::new(conf.url.clone()));
+        Self {
+            domain,
+            rpc_client: Arc::new(rpc_client),
+        }
+    }
+
+    /// Get the inner JSON-RPC client
+    pub fn rpc_client(&self) -> Arc<JsonRpcClient<HttpTransport>> {
+        self.rpc_client.clone()
+    }
+
+    /// Call a view function of a contract at the latest block
+    pub(crate) async fn call(
+        &self,
+        contract_address: FieldElement,
+        entry_point_selector: FieldElement,
+        calldata: Vec<FieldElement>,
+    ) -> ChainResult<Vec<FieldElement>> {
+        let request = FunctionCall {
+            contract_address,
+            entry_point_selector,
+            calldata,
+        };
+        self.rpc_client
+            .call(request, BlockId::Tag(BlockTag::Latest))
+            .await
+            .map_err(|e| HyperlaneStarknetError::from(e).into())
+    }
+
+    /// Get the latest block number
+    pub(crate) async fn get_block_number(&self) -> ChainResult<u32> {
+        let block_number = self
+            .rpc_client
+            .block_number()
+            .await
+            .map_err(HyperlaneStarknetError::from)?;
+        u32::try_from(block_number).map_err(ChainCommunicationError::from_other)
+    }
+
+    /// Fetch the events emitted by `contract_address` in `range` whose first
+    /// key is the selector of `event_name`, with their log metadata.
+    pub(crate) async fn get_events(
+        &self,
+        contract_address: FieldElement,
+        event_name: &str,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(EmittedEvent, LogMeta)>> {
+        let selector =
+            get_selector_from_name(event_name).map_err(ChainCommunicationError::from_other)?;
+        let filter = EventFilter {
+            from_block: Some(BlockId::Number(*range.start() as u64)),
+            to_block: Some(BlockId::Number(*range.end() as u64)),
+            address: Some(contract_address),
+            keys: Some(vec![vec![selector]]),
+        };
+
+        let mut events = vec![];
+        let mut continuation_token = None;
+        loop {
+            let page = self
+                .rpc_client
+                .get_events(filter.clone(), continuation_token, EVENTS_CHUNK_SIZE)
+                .await
+                .map_err(HyperlaneStarknetError::from)?;
+            events.extend(page.events);
+            continuation_token = page.continuation_token;
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+
+        // Events are returned in the order they were emitted, so the log index
+        // is the position of the event among the events of its block.
+        let mut log_index = 0u64;
+        let mut current_block = None;
+        let events = events
+            .into_iter()
+            .filter(|event| event.block_number.is_some())
+            .map(|event| {
+                if current_block != event.block_number {
+                    current_block = event.block_number;
+                    log_index = 0;
+                }
+                let meta = LogMeta {
+                    address: h256_from_felt(event.from_address),
+                    block_number: event.block_number.unwrap_or_default(),
+                    block_hash: event.block_hash.map(h256_from_felt).unwrap_or_default(),
+                    transaction_id: h256_from_felt(event.transaction_hash).into(),
+                    transaction_index: 0,
+                    log_index: U256::from(log_index),
+                };
+                log_index += 1;
+                (event, meta)
+            })
+            .collect();
+        Ok(events)
+    }
+}
+
+impl HyperlaneChain for StarknetProvider {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.clone())
+    }
+}
+
+#[async_trait]
+impl HyperlaneProvider for StarknetProvider {
+    async fn get_block_by_hash(&self, hash: &H256) -> ChainResult<BlockInfo> {
+        let block = self
+            .rpc_client
+            .get_block_with_tx_hashes(BlockId::Hash(felt_from_h256(*hash)?))
+            .await
+            .map_err(HyperlaneStarknetError::from)?;
+        match block {
+            MaybePendingBlockWithTxHashes::Block(block) => Ok(BlockInfo {
+                hash: h256_from_felt(block.block_hash),
+                timestamp: block.timestamp,
+                number: block.block_number,
+            }),
+            MaybePendingBlockWithTxHashes::PendingBlock(_) => {
+                Err(HyperlaneProviderError::BlockIsNotPartOfChainYet(*hash).into())
+            }
+        }
+    }
+
+    async fn get_txn_by_hash(&self, hash: &H256) -> ChainResult<TxnInfo> {
+        let transaction = self
+            .rpc_client
+            .get_transaction_by_hash(felt_from_h256(*hash)?)
+            .await
+            .map_err(HyperlaneStarknetError::from)?;
+        match transaction {
+            Transaction::Invoke(InvokeTransaction::V1(tx)) => Ok(TxnInfo {
+                hash: *hash,
+                gas_limit: U256::from_big_endian(&tx.max_fee.to_bytes_be()),
+                max_priority_fee_per_gas: None,
+                max_fee_per_gas: None,
+                gas_price: None,
+                nonce: FeltReader::new(&[tx.nonce]).u64()?,
+                sender: h256_from_felt(tx.sender_address),
+                recipient: None,
+                receipt: None,
+            }),
+            _ => Err(HyperlaneStarknetError::InvalidResponse(format!(
+                "transaction {hash:?} is not a v1 invoke transaction"
+            ))
+            .into()),
+        }
+    }
+
+    async fn is_contract(&self, address: &H256) -> ChainResult<bool> {
+        match self
+            .rpc_client
+            .get_class_hash_at(BlockId::Tag(BlockTag::Latest), felt_from_h256(*address)?)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(ProviderError::StarknetError(StarknetError::ContractNotFound)) => Ok(false),
+            Err(e) => Err(HyperlaneStarknetError::from(e).into()),
+        }
+    }
+
+    async fn get_balance(&self, address: String) -> ChainResult<U256> {
+        let address =
+            FieldElement::from_hex_be(&address).map_err(ChainCommunicationError::from_other)?;
+        let fee_token = FieldElement::from_hex_be(FEE_TOKEN_ADDRESS)
+            .expect("fee token address is a valid field element");
+        let selector =
+            get_selector_from_name("balanceOf").map_err(ChainCommunicationError::from_other)?;
+        let result = self.call(fee_token, selector, vec![address]).await?;
+        let balance = FeltReader::new(&result).u256()?;
+        Ok(U256::from_big_endian(balance.as_bytes()))
+    }
+
+    async fn get_chain_metrics(&self) -> ChainResult<Option<ChainInfo>> {
+        let block = self
+            .rpc_client
+            .get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest))
+            .await
+            .map_err(HyperlaneStarknetError::from)?;
+        let MaybePendingBlockWithTxHashes::Block(block) = block else {
+            return Ok(None);
+        };
+        let gas_price = U256::from_big_endian(&block.l1_gas_price.price_in_wei.to_bytes_be());
+        Ok(Some(ChainInfo::new(
+            BlockInfo {
+                hash: h256_from_felt(block.block_hash),
+                timestamp: block.timestamp,
+                number: block.block_number,
+            },
+            Some(gas_price),
+        )))
+    }
+}
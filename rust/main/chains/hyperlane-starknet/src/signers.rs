@@ -0,0 +1,45 @@
+use std::fmt::{Debug, Formatter};
+
+use hyperlane_core::{ChainResult, H256};
+use starknet::{
+    core::types::FieldElement,
+    signers::{LocalWallet, SigningKey},
+};
+
+use crate::codec::felt_from_h256;
+
+/// Signer for Starknet chains. Starknet accounts are contracts, so the signer
+/// is the address of an account contract and the key controlling it.
+#[derive(Clone)]
+pub struct Signer {
+    /// Address of the account contract
+    pub address: FieldElement,
+    private_key: FieldElement,
+}
+
+impl Signer {
+    /// Create a new signer
+    ///
+    /// # Arguments
+    /// * `private_key` - the key the account contract is controlled with
+    /// * `address` - the address of the account contract
+    pub fn new(private_key: H256, address: H256) -> ChainResult<Self> {
+        Ok(Self {
+            address: felt_from_h256(address)?,
+            private_key: felt_from_h256(private_key)?,
+        })
+    }
+
+    /// Build a wallet that signs transactions of the account
+    pub fn local_wallet(&self) -> LocalWallet {
+        LocalWallet::from(SigningKey::from_secret_scalar(self.private_key))
+    }
+}
+
+impl Debug for Signer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Signer")
+            .field("address", &format!("{:#x}", self.address))
+            .finish_non_exhaustive()
+    }
+}
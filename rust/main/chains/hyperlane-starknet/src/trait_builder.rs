@@ -0,0 +1,19 @@
+use url::Url;
+
+/// Starknet connection configuration
+#[derive(Debug, Clone)]
+pub struct ConnectionConf {
+    /// Fully qualified string to connect to the JSON-RPC API
+    pub url: Url,
+}
+
+/// An error type when parsing a connection configuration.
+#[derive(thiserror::Error, Debug)]
+pub enum ConnectionConfError {
+    /// Missing `url` for connection configuration
+    #[error("Missing `url` for connection configuration")]
+    MissingConnectionUrl,
+    /// Invalid `url` for connection configuration
+    #[error("Invalid `url` for connection configuration: `{0}` ({1})")]
+    InvalidConnectionUrl(String, url::ParseError),
+}
@@ -0,0 +1,112 @@
+use std::fmt::{Debug, Formatter};
+
+use async_trait::async_trait;
+use hyperlane_core::{
+    Announcement, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneProvider, SignedType, TxOutcome,
+    ValidatorAnnounce, H256, U256,
+};
+use starknet::{accounts::Call, core::types::FieldElement, macros::selector};
+use tracing::instrument;
+
+use crate::{
+    account::{build_account, send_transaction, StarknetAccount},
+    codec::{encode_bytes, encode_string, felt_from_h256, h256_from_felt, FeltReader},
+    ConnectionConf, Signer, StarknetProvider,
+};
+
+/// A reference to a ValidatorAnnounce contract on some Starknet chain
+pub struct StarknetValidatorAnnounce {
+    provider: StarknetProvider,
+    address: FieldElement,
+    account: Option<StarknetAccount>,
+}
+
+impl StarknetValidatorAnnounce {
+    /// Create a new Starknet ValidatorAnnounce
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        signer: Option<Signer>,
+    ) -> ChainResult<Self> {
+        let account = match signer {
+            Some(signer) => Some(build_account(conf, &signer).await?),
+            None => None,
+        };
+        Ok(Self {
+            provider: StarknetProvider::new(locator.domain.clone(), conf),
+            address: felt_from_h256(locator.address)?,
+            account,
+        })
+    }
+}
+
+impl HyperlaneContract for StarknetValidatorAnnounce {
+    fn address(&self) -> H256 {
+        h256_from_felt(self.address)
+    }
+}
+
+impl HyperlaneChain for StarknetValidatorAnnounce {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.provider.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+impl Debug for StarknetValidatorAnnounce {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self as &dyn HyperlaneContract)
+    }
+}
+
+#[async_trait]
+impl ValidatorAnnounce for StarknetValidatorAnnounce {
+    async fn get_announced_storage_locations(
+        &self,
+        validators: &[H256],
+    ) -> ChainResult<Vec<Vec<String>>> {
+        let mut calldata = vec![FieldElement::from(validators.len() as u64)];
+        for validator in validators {
+            calldata.push(felt_from_h256(*validator)?);
+        }
+        let result = self
+            .provider
+            .call(
+                self.address,
+                selector!("get_announced_storage_locations"),
+                calldata,
+            )
+            .await?;
+        FeltReader::new(&result).array(|reader| reader.array(FeltReader::string))
+    }
+
+    #[instrument(err, skip(self))]
+    async fn announce(&self, announcement: SignedType<Announcement>) -> ChainResult<TxOutcome> {
+        let account = self
+            .account
+            .as_ref()
+            .ok_or(ChainCommunicationError::SignerUnavailable)?;
+        let mut calldata = vec![felt_from_h256(announcement.value.validator.into())?];
+        calldata.extend(encode_string(&announcement.value.storage_location));
+        calldata.extend(encode_bytes(&announcement.signature.to_vec()));
+        let call = Call {
+            to: self.address,
+            selector: selector!("announce"),
+            calldata,
+        };
+        send_transaction(account, call, None).await
+    }
+
+    async fn announce_tokens_needed(
+        &self,
+        _announcement: SignedType<Announcement>,
+    ) -> Option<U256> {
+        // TODO: check the account balance. For now, just try announcing and
+        // allow the announce attempt to fail if there are not enough tokens.
+        Some(0u64.into())
+    }
+}
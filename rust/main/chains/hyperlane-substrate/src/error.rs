@@ -0,0 +1,30 @@
+use hyperlane_core::ChainCommunicationError;
+
+/// Errors from the crates specific to the hyperlane-substrate
+/// implementation.
+/// This error can then be converted into the broader error type
+/// in hyperlane-core using the `From` trait impl
+#[derive(Debug, thiserror::Error)]
+pub enum HyperlaneSubstrateError {
+    /// Error from subxt, covering RPC, metadata and decoding failures
+    #[error(transparent)]
+    SubxtError(#[from] subxt::Error),
+    /// Error decoding a SCALE-encoded value
+    #[error(transparent)]
+    CodecError(#[from] subxt::ext::codec::Error),
+    /// Error building the signer keypair
+    #[error("Invalid sr25519 key: {0}")]
+    InvalidKey(String),
+    /// A block the chain should know about is missing
+    #[error("Block {0} not found")]
+    BlockNotFound(String),
+    /// A storage entry the pallet must have is missing
+    #[error("Storage entry {0} not found")]
+    MissingStorage(&'static str),
+}
+
+impl From<HyperlaneSubstrateError> for ChainCommunicationError {
+    fn from(value: HyperlaneSubstrateError) -> Self {
+        ChainCommunicationError::from_other(value)
+    }
+}
@@ -0,0 +1,80 @@
+//! Events deposited by the Hyperlane mailbox pallet, decoded from the raw
+//! SCALE-encoded fields of the block events.
+
+use hyperlane_core::{ChainResult, HyperlaneMessage, H256};
+use subxt::ext::codec::Decode;
+
+use crate::HyperlaneSubstrateError;
+
+/// Name of the mailbox pallet in the runtime
+pub(crate) const MAILBOX_PALLET: &str = "Mailbox";
+/// Event deposited when a message is dispatched
+pub(crate) const DISPATCH_EVENT: &str = "Dispatch";
+/// Event deposited when a message is processed
+pub(crate) const PROCESS_ID_EVENT: &str = "ProcessId";
+
+/// Fields of the `Dispatch` event
+#[derive(Debug, Decode)]
+#[cfg_attr(test, derive(subxt::ext::codec::Encode))]
+#[codec(crate = subxt::ext::codec)]
+struct DispatchEvent {
+    /// The raw hyperlane message
+    message: Vec<u8>,
+}
+
+/// Fields of the `ProcessId` event
+#[derive(Debug, Decode)]
+#[cfg_attr(test, derive(subxt::ext::codec::Encode))]
+#[codec(crate = subxt::ext::codec)]
+struct ProcessIdEvent {
+    /// Id of the processed message
+    message_id: [u8; 32],
+}
+
+/// Decode the message of a `Dispatch` event from its field bytes
+pub(crate) fn decode_dispatch(mut fields: &[u8]) -> ChainResult<HyperlaneMessage> {
+    let event = DispatchEvent::decode(&mut fields).map_err(HyperlaneSubstrateError::from)?;
+    Ok(HyperlaneMessage::try_from(event.message)?)
+}
+
+/// Decode the message id of a `ProcessId` event from its field bytes
+pub(crate) fn decode_process_id(mut fields: &[u8]) -> ChainResult<H256> {
+    let event = ProcessIdEvent::decode(&mut fields).map_err(HyperlaneSubstrateError::from)?;
+    Ok(H256(event.message_id))
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::RawHyperlaneMessage;
+    use subxt::ext::codec::Encode;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_dispatch() {
+        let message = HyperlaneMessage {
+            version: 3,
+            nonce: 7,
+            origin: 1000,
+            sender: H256::repeat_byte(1),
+            destination: 1,
+            recipient: H256::repeat_byte(2),
+            body: vec![1, 2, 3],
+        };
+        let fields = DispatchEvent {
+            message: RawHyperlaneMessage::from(&message),
+        }
+        .encode();
+        assert_eq!(decode_dispatch(&fields).unwrap(), message);
+    }
+
+    #[test]
+    fn test_decode_process_id() {
+        let fields = ProcessIdEvent {
+            message_id: [9; 32],
+        }
+        .encode();
+        assert_eq!(decode_process_id(&fields).unwrap(), H256::repeat_byte(9));
+        assert!(decode_process_id(&fields[..31]).is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use hyperlane_core::{ChainResult, TxCostEstimate, TxOutcome, H256, H512, U256};
+use subxt::{
+    config::DefaultExtrinsicParamsBuilder, tx::Payload, utils::H256 as SubxtH256, OnlineClient,
+    PolkadotConfig,
+};
+use tokio::sync::Mutex;
+use tracing::{debug, instrument, warn};
+
+use crate::{HyperlaneSubstrateError, Signer};
+
+/// Number of blocks a signed extrinsic stays valid for. Mortal extrinsics
+/// can't be replayed once they expire, and are dropped from the pool instead
+/// of lingering when their nonce is stale.
+const MORTALITY_PERIOD: u64 = 64;
+
+/// Signs and submits extrinsics of one account, handing out nonces so that
+/// extrinsics submitted concurrently don't collide.
+pub(crate) struct ExtrinsicSubmitter {
+    client: OnlineClient<PolkadotConfig>,
+    signer: Signer,
+    /// The nonce of the next extrinsic, if one was submitted before
+    next_nonce: Mutex<Option<u64>>,
+}
+
+impl ExtrinsicSubmitter {
+    pub(crate) fn new(client: OnlineClient<PolkadotConfig>, signer: Signer) -> Self {
+        Self {
+            client,
+            signer,
+            next_nonce: Mutex::new(None),
+        }
+    }
+
+    /// Estimate the fee of `call`. Substrate fees are denominated in the
+    /// native token, so the fee is reported as the gas limit at a price of 1.
+    pub(crate) async fn estimate<Call: Payload>(&self, call: &Call) -> ChainResult<TxCostEstimate> {
+        let params = DefaultExtrinsicParamsBuilder::<PolkadotConfig>::new().build();
+        let fee = self
+            .client
+            .tx()
+            .create_signed(call, self.signer.keypair(), params)
+            .await
+            .map_err(HyperlaneSubstrateError::from)?
+            .partial_fee_estimate()
+            .await
+            .map_err(HyperlaneSubstrateError::from)?;
+        Ok(TxCostEstimate {
+            gas_limit: fee.into(),
+            gas_price: U256::one().try_into()?,
+            l2_gas_limit: None,
+            l1_data_fee: None,
+        })
+    }
+
+    /// Sign `call` with a mortal era and the next nonce of the account, submit
+    /// it and wait for it to be finalized.
+    #[instrument(skip_all)]
+    pub(crate) async fn submit<Call: Payload>(&self, call: &Call) -> ChainResult<TxOutcome> {
+        let (extrinsic, fee) = {
+            // Hold the lock until the extrinsic is in the pool, so the next
+            // extrinsic gets the following nonce
+            let mut next_nonce = self.next_nonce.lock().await;
+            let chain_nonce = self
+                .client
+                .tx()
+                .account_nonce(&self.signer.account_id())
+                .await
+                .map_err(HyperlaneSubstrateError::from)?;
+            // The chain nonce counts the extrinsics in the pool, but a node
+            // behind a load balancer may not have seen the latest ones yet
+            let nonce = next_nonce.map_or(chain_nonce, |n| n.max(chain_nonce));
+
+            let latest_block = self
+                .client
+                .blocks()
+                .at_latest()
+                .await
+                .map_err(HyperlaneSubstrateError::from)?;
+            let params = DefaultExtrinsicParamsBuilder::<PolkadotConfig>::new()
+                .mortal(latest_block.header(), MORTALITY_PERIOD)
+                .nonce(nonce)
+                .build();
+            let extrinsic = self
+                .client
+                .tx()
+                .create_signed(call, self.signer.keypair(), params)
+                .await
+                .map_err(HyperlaneSubstrateError::from)?;
+            let fee = extrinsic
+                .partial_fee_estimate()
+                .await
+                .map_err(HyperlaneSubstrateError::from)?;
+
+            let progress = match extrinsic.submit_and_watch().await {
+                Ok(progress) => progress,
+                Err(err) => {
+                    // Query the nonce from the chain again next time
+                    *next_nonce = None;
+                    return Err(HyperlaneSubstrateError::from(err).into());
+                }
+            };
+            *next_nonce = Some(nonce + 1);
+            debug!(?nonce, tx_hash = ?progress.extrinsic_hash(), "Submitted extrinsic");
+            (progress, fee)
+        };
+
+        let tx_hash = extrinsic.extrinsic_hash();
+        let executed = match extrinsic.wait_for_finalized_success().await {
+            Ok(_) => true,
+            Err(subxt::Error::Runtime(err)) => {
+                warn!(?tx_hash, ?err, "Extrinsic failed");
+                false
+            }
+            Err(err) => return Err(HyperlaneSubstrateError::from(err).into()),
+        };
+        Ok(TxOutcome {
+            transaction_id: h512_from_hash(tx_hash),
+            executed,
+            gas_used: fee.into(),
+            gas_price: U256::one().try_into()?,
+        })
+    }
+}
+
+fn h512_from_hash(hash: SubxtH256) -> H512 {
+    H256(hash.0).into()
+}
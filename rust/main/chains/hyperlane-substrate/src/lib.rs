@@ -0,0 +1,15 @@
+//! Implementation of hyperlane for Substrate chains running the Hyperlane
+//! pallets.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+pub use self::{error::*, mailbox::*, provider::*, signers::*, trait_builder::*};
+
+mod error;
+mod events;
+mod extrinsic;
+mod mailbox;
+mod provider;
+mod signers;
+mod trait_builder;
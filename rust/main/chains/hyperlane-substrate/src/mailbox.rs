@@ -0,0 +1,277 @@
+use std::{
+    fmt::{Debug, Formatter},
+    num::NonZeroU64,
+    ops::RangeInclusive,
+};
+
+use async_trait::async_trait;
+use hyperlane_core::{
+    utils::bytes_to_hex, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Indexed, Indexer,
+    LogMeta, Mailbox, RawHyperlaneMessage, SequenceAwareIndexer, TxCostEstimate, TxOutcome, H256,
+    U256,
+};
+use subxt::{
+    dynamic::{At, Value},
+    tx::DynamicPayload,
+};
+use tracing::instrument;
+
+use crate::{
+    events::{
+        decode_dispatch, decode_process_id, DISPATCH_EVENT, MAILBOX_PALLET, PROCESS_ID_EVENT,
+    },
+    extrinsic::ExtrinsicSubmitter,
+    ConnectionConf, HyperlaneSubstrateError, Signer, SubstrateProvider,
+};
+
+/// A reference to the Mailbox pallet of some Substrate chain
+pub struct SubstrateMailbox {
+    provider: SubstrateProvider,
+    address: H256,
+    submitter: Option<ExtrinsicSubmitter>,
+}
+
+impl SubstrateMailbox {
+    /// Create a new Substrate mailbox
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        signer: Option<Signer>,
+    ) -> ChainResult<Self> {
+        let provider = SubstrateProvider::new(locator.domain.clone(), conf).await?;
+        let submitter =
+            signer.map(|signer| ExtrinsicSubmitter::new(provider.client().clone(), signer));
+        Ok(Self {
+            provider,
+            address: locator.address,
+            submitter,
+        })
+    }
+
+    fn submitter(&self) -> ChainResult<&ExtrinsicSubmitter> {
+        self.submitter
+            .as_ref()
+            .ok_or(ChainCommunicationError::SignerUnavailable)
+    }
+
+    fn process_call(&self, message: &HyperlaneMessage, metadata: &[u8]) -> DynamicPayload {
+        subxt::dynamic::tx(
+            MAILBOX_PALLET,
+            "process",
+            vec![
+                Value::from_bytes(metadata),
+                Value::from_bytes(RawHyperlaneMessage::from(message)),
+            ],
+        )
+    }
+
+    /// Fetch a storage entry holding an H256, if it is set
+    async fn fetch_h256(&self, entry: &'static str, keys: Vec<Value>) -> ChainResult<Option<H256>> {
+        let Some(value) = self
+            .provider
+            .fetch_storage(MAILBOX_PALLET, entry, keys)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let bytes = (0..32)
+            .map(|i| value.at(i).and_then(|byte| byte.as_u128()).map(|b| b as u8))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(HyperlaneSubstrateError::MissingStorage(entry))?;
+        Ok(Some(H256::from_slice(&bytes)))
+    }
+}
+
+impl HyperlaneContract for SubstrateMailbox {
+    fn address(&self) -> H256 {
+        self.address
+    }
+}
+
+impl HyperlaneChain for SubstrateMailbox {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.provider.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+impl Debug for SubstrateMailbox {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self as &dyn HyperlaneContract)
+    }
+}
+
+#[async_trait]
+impl Mailbox for SubstrateMailbox {
+    #[instrument(level = "debug", err, ret, skip(self))]
+    async fn count(&self, lag: Option<NonZeroU64>) -> ChainResult<u32> {
+        assert!(
+            lag.is_none(),
+            "Substrate does not support querying point-in-time"
+        );
+        let nonce = self
+            .provider
+            .fetch_storage(MAILBOX_PALLET, "Nonce", vec![])
+            .await?
+            .and_then(|value| value.as_u128())
+            // The nonce isn't stored until the first message is dispatched
+            .unwrap_or_default();
+        Ok(nonce as u32)
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    async fn delivered(&self, id: H256) -> ChainResult<bool> {
+        let delivery = self
+            .provider
+            .fetch_storage(
+                MAILBOX_PALLET,
+                "Deliveries",
+                vec![Value::from_bytes(id.as_bytes())],
+            )
+            .await?;
+        Ok(delivery.is_some())
+    }
+
+    #[instrument(err, ret, skip(self))]
+    async fn default_ism(&self) -> ChainResult<H256> {
+        self.fetch_h256("DefaultIsm", vec![])
+            .await?
+            .ok_or_else(|| HyperlaneSubstrateError::MissingStorage("DefaultIsm").into())
+    }
+
+    #[instrument(err, ret, skip(self))]
+    async fn recipient_ism(&self, recipient: H256) -> ChainResult<H256> {
+        let recipient_ism = self
+            .fetch_h256(
+                "RecipientIsms",
+                vec![Value::from_bytes(recipient.as_bytes())],
+            )
+            .await?;
+        match recipient_ism {
+            Some(ism) => Ok(ism),
+            // Recipients without a custom ISM use the default one
+            None => self.default_ism().await,
+        }
+    }
+
+    #[instrument(err, ret, skip(self), fields(msg=%message, metadata=%bytes_to_hex(metadata)))]
+    async fn process(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+        _tx_gas_limit: Option<U256>,
+    ) -> ChainResult<TxOutcome> {
+        // Extrinsic weights are fixed by the runtime, so there is no gas limit
+        // to set
+        let call = self.process_call(message, metadata);
+        self.submitter()?.submit(&call).await
+    }
+
+    #[instrument(err, ret, skip(self), fields(msg=%message, metadata=%bytes_to_hex(metadata)))]
+    async fn process_estimate_costs(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<TxCostEstimate> {
+        let call = self.process_call(message, metadata);
+        self.submitter()?.estimate(&call).await
+    }
+
+    fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8> {
+        let mut calldata = metadata.to_vec();
+        calldata.extend(RawHyperlaneMessage::from(message));
+        calldata
+    }
+}
+
+/// Struct that retrieves event data for the Mailbox pallet of a Substrate
+/// chain
+#[derive(Debug)]
+pub struct SubstrateMailboxIndexer {
+    mailbox: SubstrateMailbox,
+}
+
+impl SubstrateMailboxIndexer {
+    /// Create a new SubstrateMailboxIndexer
+    pub async fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        Ok(Self {
+            mailbox: SubstrateMailbox::new(conf, locator, None).await?,
+        })
+    }
+
+    async fn fetch_events<T>(
+        &self,
+        variant: &str,
+        range: RangeInclusive<u32>,
+        decode: impl Fn(&[u8]) -> ChainResult<T>,
+    ) -> ChainResult<Vec<(Indexed<T>, LogMeta)>>
+    where
+        T: Into<Indexed<T>>,
+    {
+        let events = self
+            .mailbox
+            .provider
+            .get_events(MAILBOX_PALLET, variant, range)
+            .await?;
+        events
+            .into_iter()
+            .map(|(fields, mut meta)| {
+                meta.address = self.mailbox.address;
+                Ok((decode(&fields)?.into(), meta))
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Indexer<HyperlaneMessage> for SubstrateMailboxIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
+        self.fetch_events(DISPATCH_EVENT, range, decode_dispatch)
+            .await
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.mailbox.provider.get_finalized_block_number().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<HyperlaneMessage> for SubstrateMailboxIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<HyperlaneMessage>::get_finalized_block_number(self).await?;
+        let sequence = self.mailbox.count(None).await?;
+        Ok((Some(sequence), tip))
+    }
+}
+
+#[async_trait]
+impl Indexer<H256> for SubstrateMailboxIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
+        self.fetch_events(PROCESS_ID_EVENT, range, decode_process_id)
+            .await
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.mailbox.provider.get_finalized_block_number().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<H256> for SubstrateMailboxIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<H256>::get_finalized_block_number(self).await?;
+
+        // No sequence for message deliveries.
+        Ok((None, tip))
+    }
+}
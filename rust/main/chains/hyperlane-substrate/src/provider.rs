@@ -0,0 +1,248 @@
+use std::{
+    fmt::{Debug, Formatter},
+    ops::RangeInclusive,
+    str::FromStr,
+};
+
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use hyperlane_core::{
+    BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain,
+    HyperlaneProvider, HyperlaneProviderError, LogMeta, TxnInfo, H256, H512, U256,
+};
+use subxt::{
+    backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+    config::{substrate::BlakeTwo256, Hasher},
+    dynamic::{At, Value},
+    events::Phase,
+    utils::{AccountId32, H256 as SubxtH256},
+    OnlineClient, PolkadotConfig,
+};
+
+use crate::{ConnectionConf, HyperlaneSubstrateError};
+
+/// Blocks fetched concurrently while indexing
+const INDEXING_CONCURRENCY: usize = 10;
+
+/// A wrapper around a subxt client to get generic blockchain information.
+#[derive(Clone)]
+pub struct SubstrateProvider {
+    domain: HyperlaneDomain,
+    client: OnlineClient<PolkadotConfig>,
+    rpc: LegacyRpcMethods<PolkadotConfig>,
+}
+
+impl SubstrateProvider {
+    /// Connect to the node and create a new Substrate provider
+    pub async fn new(domain: HyperlaneDomain, conf: &ConnectionConf) -> ChainResult<Self> {
+        let rpc_client = RpcClient::from_url(conf.url.as_str())
+            .await
+            .map_err(|e| HyperlaneSubstrateError::from(subxt::Error::from(e)))?;
+        let client = OnlineClient::from_rpc_client(rpc_client.clone())
+            .await
+            .map_err(HyperlaneSubstrateError::from)?;
+        Ok(Self {
+            domain,
+            client,
+            rpc: LegacyRpcMethods::new(rpc_client),
+        })
+    }
+
+    /// Get the inner subxt client
+    pub fn client(&self) -> &OnlineClient<PolkadotConfig> {
+        &self.client
+    }
+
+    /// Number of the latest finalized block
+    pub async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        let hash = self
+            .rpc
+            .chain_get_finalized_head()
+            .await
+            .map_err(HyperlaneSubstrateError::from)?;
+        let header = self
+            .rpc
+            .chain_get_header(Some(hash))
+            .await
+            .map_err(HyperlaneSubstrateError::from)?
+            .ok_or_else(|| HyperlaneSubstrateError::BlockNotFound(format!("{hash:?}")))?;
+        Ok(header.number)
+    }
+
+    /// Fetch a storage entry of the latest finalized block. Returns `None` if
+    /// the entry is not set.
+    pub(crate) async fn fetch_storage(
+        &self,
+        pallet: &str,
+        entry: &str,
+        keys: Vec<Value>,
+    ) -> ChainResult<Option<Value<u32>>> {
+        let address = subxt::dynamic::storage(pallet, entry, keys);
+        let value = self
+            .client
+            .storage()
+            .at_latest()
+            .await
+            .map_err(HyperlaneSubstrateError::from)?
+            .fetch(&address)
+            .await
+            .map_err(HyperlaneSubstrateError::from)?;
+        value
+            .map(|value| value.to_value())
+            .transpose()
+            .map_err(|e| HyperlaneSubstrateError::from(e).into())
+    }
+
+    /// Fetch the fields of the `pallet` events named `variant` deposited in
+    /// the blocks of `range`, with their log metadata.
+    pub(crate) async fn get_events(
+        &self,
+        pallet: &str,
+        variant: &str,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Vec<u8>, LogMeta)>> {
+        let blocks = range.collect::<Vec<_>>();
+        let mut events = vec![];
+        for chunk in blocks.chunks(INDEXING_CONCURRENCY) {
+            let block_events = try_join_all(
+                chunk
+                    .iter()
+                    .map(|number| self.get_block_events(*number, pallet, variant)),
+            )
+            .await?;
+            events.extend(block_events.into_iter().flatten());
+        }
+        Ok(events)
+    }
+
+    async fn get_block_events(
+        &self,
+        number: u32,
+        pallet: &str,
+        variant: &str,
+    ) -> ChainResult<Vec<(Vec<u8>, LogMeta)>> {
+        let hash = self
+            .rpc
+            .chain_get_block_hash(Some(number.into()))
+            .await
+            .map_err(HyperlaneSubstrateError::from)?
+            .ok_or_else(|| HyperlaneSubstrateError::BlockNotFound(number.to_string()))?;
+        let block = self
+            .client
+            .blocks()
+            .at(hash)
+            .await
+            .map_err(HyperlaneSubstrateError::from)?;
+        let extrinsic_hashes = block
+            .extrinsics()
+            .await
+            .map_err(HyperlaneSubstrateError::from)?
+            .iter()
+            .map(|extrinsic| BlakeTwo256::hash(extrinsic.bytes()))
+            .collect::<Vec<_>>();
+
+        let mut events = vec![];
+        for event in block
+            .events()
+            .await
+            .map_err(HyperlaneSubstrateError::from)?
+            .iter()
+        {
+            let event = event.map_err(HyperlaneSubstrateError::from)?;
+            if event.pallet_name() != pallet || event.variant_name() != variant {
+                continue;
+            }
+            // Pallet events are only deposited while applying an extrinsic
+            let Phase::ApplyExtrinsic(extrinsic_index) = event.phase() else {
+                continue;
+            };
+            let transaction_id = extrinsic_hashes
+                .get(extrinsic_index as usize)
+                .map(|hash| H512::from(H256(hash.0)))
+                .unwrap_or_default();
+            let meta = LogMeta {
+                address: H256::zero(),
+                block_number: number.into(),
+                block_hash: H256(hash.0),
+                transaction_id,
+                transaction_index: extrinsic_index.into(),
+                log_index: event.index().into(),
+            };
+            events.push((event.field_bytes().to_vec(), meta));
+        }
+        Ok(events)
+    }
+}
+
+impl Debug for SubstrateProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubstrateProvider")
+            .field("domain", &self.domain)
+            .finish_non_exhaustive()
+    }
+}
+
+impl HyperlaneChain for SubstrateProvider {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.clone())
+    }
+}
+
+#[async_trait]
+impl HyperlaneProvider for SubstrateProvider {
+    async fn get_block_by_hash(&self, hash: &H256) -> ChainResult<BlockInfo> {
+        let block_hash = SubxtH256(hash.0);
+        let header = self
+            .rpc
+            .chain_get_header(Some(block_hash))
+            .await
+            .map_err(HyperlaneSubstrateError::from)?
+            .ok_or(HyperlaneProviderError::CouldNotFindObjectByHash(*hash))?;
+        let timestamp_ms = self
+            .client
+            .storage()
+            .at(block_hash)
+            .fetch(&subxt::dynamic::storage("Timestamp", "Now", vec![]))
+            .await
+            .map_err(HyperlaneSubstrateError::from)?
+            .map(|value| value.to_value())
+            .transpose()
+            .map_err(HyperlaneSubstrateError::from)?
+            .and_then(|value| value.as_u128())
+            .unwrap_or_default();
+        Ok(BlockInfo {
+            hash: *hash,
+            timestamp: (timestamp_ms / 1000) as u64,
+            number: header.number.into(),
+        })
+    }
+
+    async fn get_txn_by_hash(&self, hash: &H256) -> ChainResult<TxnInfo> {
+        // Extrinsics can't be looked up by hash without an indexer
+        Err(HyperlaneProviderError::CouldNotFindObjectByHash(*hash).into())
+    }
+
+    async fn is_contract(&self, _address: &H256) -> ChainResult<bool> {
+        // Pallets aren't deployed at addresses
+        Ok(true)
+    }
+
+    async fn get_balance(&self, address: String) -> ChainResult<U256> {
+        let account =
+            AccountId32::from_str(&address).map_err(ChainCommunicationError::from_other)?;
+        let free = self
+            .fetch_storage("System", "Account", vec![Value::from_bytes(account.0)])
+            .await?
+            .and_then(|info| info.at("data")?.at("free")?.as_u128())
+            .unwrap_or_default();
+        Ok(free.into())
+    }
+
+    async fn get_chain_metrics(&self) -> ChainResult<Option<ChainInfo>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,45 @@
+use std::fmt::{Debug, Formatter};
+
+use hyperlane_core::{ChainResult, H256};
+use subxt::utils::AccountId32;
+use subxt_signer::sr25519::Keypair;
+
+use crate::HyperlaneSubstrateError;
+
+/// Signer for Substrate chains, an sr25519 keypair
+#[derive(Clone)]
+pub struct Signer {
+    keypair: Keypair,
+}
+
+impl Signer {
+    /// Create a new signer from the 32 byte secret seed of an sr25519 key
+    pub fn new(seed: H256) -> ChainResult<Self> {
+        let keypair = Keypair::from_seed(seed.0)
+            .map_err(|e| HyperlaneSubstrateError::InvalidKey(e.to_string()))?;
+        Ok(Self { keypair })
+    }
+
+    /// The keypair extrinsics are signed with
+    pub fn keypair(&self) -> &Keypair {
+        &self.keypair
+    }
+
+    /// The account id of the signer
+    pub fn account_id(&self) -> AccountId32 {
+        self.keypair.public_key().to_account_id()
+    }
+
+    /// The account id of the signer as an H256
+    pub fn address_h256(&self) -> H256 {
+        H256(self.account_id().0)
+    }
+}
+
+impl Debug for Signer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Signer")
+            .field("account_id", &self.account_id().to_string())
+            .finish_non_exhaustive()
+    }
+}
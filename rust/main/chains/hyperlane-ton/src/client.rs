@@ -0,0 +1,388 @@
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hyperlane_core::{ChainResult, H256};
+use reqwest::{Client, Response};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+use tonlib_core::{
+    cell::{ArcCell, BagOfCells, Cell},
+    TonAddress,
+};
+use tracing::instrument;
+use url::Url;
+
+use crate::{codec::h256_from_stack_num, HyperlaneTonError};
+
+/// How often the API is polled for the transaction of a sent message
+const TX_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How many times the API is polled for the transaction of a sent message
+const TX_POLL_ATTEMPTS: usize = 60;
+
+/// Client of the toncenter v3 HTTP API, which serves indexed data from TON
+/// lite-servers.
+#[derive(Debug, Clone)]
+pub struct ToncenterClient {
+    url: Url,
+    client: Client,
+}
+
+/// A message sent or received by a contract
+#[derive(Debug, Deserialize)]
+pub struct TonMessage {
+    /// Hash of the message
+    pub hash: String,
+    /// Logical time the message was created at
+    #[serde(deserialize_with = "deserialize_u64")]
+    pub created_lt: u64,
+    /// Hash of the transaction that sent the message
+    pub out_msg_tx_hash: Option<String>,
+    /// Content of the message
+    pub message_content: Option<TonMessageContent>,
+}
+
+/// Content of a message
+#[derive(Debug, Deserialize)]
+pub struct TonMessageContent {
+    /// Base64 encoded bag of cells of the message body
+    pub body: Option<String>,
+}
+
+/// A transaction of a contract
+#[derive(Debug, Deserialize)]
+pub struct TonTransaction {
+    /// Hash of the transaction
+    pub hash: String,
+    /// Logical time of the transaction
+    #[serde(deserialize_with = "deserialize_u64")]
+    pub lt: u64,
+    /// Sequence number of the masterchain block that commits the transaction
+    pub mc_block_seqno: Option<u32>,
+    /// Fees paid by the contract, in nanotons
+    #[serde(deserialize_with = "deserialize_u64")]
+    pub total_fees: u64,
+    /// Execution details of the transaction
+    pub description: TonTransactionDescription,
+    /// Messages sent by the transaction
+    #[serde(default)]
+    pub out_msgs: Vec<TonMessage>,
+}
+
+impl TonTransaction {
+    /// Whether the transaction executed successfully
+    pub fn success(&self) -> bool {
+        !self.description.aborted
+            && self
+                .description
+                .compute_ph
+                .as_ref()
+                .map_or(false, |compute| compute.success.unwrap_or(false))
+    }
+}
+
+/// Execution details of a transaction
+#[derive(Debug, Deserialize)]
+pub struct TonTransactionDescription {
+    /// Whether the transaction was aborted
+    pub aborted: bool,
+    /// Compute phase of the transaction, missing if it was skipped
+    pub compute_ph: Option<TonComputePhase>,
+}
+
+/// Compute phase of a transaction
+#[derive(Debug, Deserialize)]
+pub struct TonComputePhase {
+    /// Whether the TVM executed successfully
+    pub success: Option<bool>,
+    /// TVM exit code
+    pub exit_code: Option<i32>,
+}
+
+/// An entry of the TVM stack exchanged with get-methods
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StackEntry {
+    /// Type of the entry (`num`, `cell` or `slice`)
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    /// Value of the entry: 0x-prefixed hex for numbers, base64 bags of cells
+    /// otherwise
+    pub value: Value,
+}
+
+impl StackEntry {
+    /// A number entry, given as 0x-prefixed hex
+    pub fn num(value: String) -> Self {
+        Self {
+            entry_type: "num".to_owned(),
+            value: json!(value),
+        }
+    }
+
+    /// The hex value of a number entry
+    fn num_value(&self) -> ChainResult<&str> {
+        match (self.entry_type.as_str(), self.value.as_str()) {
+            ("num", Some(value)) => Ok(value),
+            _ => {
+                Err(HyperlaneTonError::InvalidResponse(format!("{self:?} is not a number")).into())
+            }
+        }
+    }
+
+    /// Decode a number entry that fits in 32 bits
+    pub(crate) fn as_u32(&self) -> ChainResult<u32> {
+        let value = self.num_value()?;
+        u32::from_str_radix(value.trim_start_matches("0x"), 16)
+            .map_err(|e| HyperlaneTonError::InvalidResponse(format!("{value}: {e}")).into())
+    }
+
+    /// Decode a number entry holding a 256 bit unsigned integer
+    pub(crate) fn as_h256(&self) -> ChainResult<H256> {
+        h256_from_stack_num(self.num_value()?)
+    }
+
+    /// Decode a boolean, which TVM represents as -1 for true and 0 for false
+    pub(crate) fn as_bool(&self) -> ChainResult<bool> {
+        Ok(self.num_value()? != "0x0")
+    }
+}
+
+/// The entry at `index` of a get-method result
+pub(crate) fn stack_entry(stack: &[StackEntry], index: usize) -> ChainResult<&StackEntry> {
+    stack.get(index).ok_or_else(|| {
+        HyperlaneTonError::InvalidResponse(format!("missing stack entry {index}")).into()
+    })
+}
+
+impl ToncenterClient {
+    /// Create a new client of the API at `url`. An API key can be given in
+    /// the `api_key` query parameter of the url.
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            client: Client::new(),
+        }
+    }
+
+    /// Sequence number of the latest masterchain block
+    pub async fn masterchain_seqno(&self) -> ChainResult<u32> {
+        #[derive(Deserialize)]
+        struct Block {
+            seqno: u32,
+        }
+        #[derive(Deserialize)]
+        struct MasterchainInfo {
+            last: Block,
+        }
+        let info: MasterchainInfo = self.get("masterchainInfo", &[]).await?;
+        Ok(info.last.seqno)
+    }
+
+    /// Messages sent by `source` with `opcode`, oldest first, skipping the
+    /// first `offset` ones.
+    pub async fn messages_by_opcode(
+        &self,
+        source: &TonAddress,
+        opcode: u32,
+        offset: u64,
+        limit: u64,
+    ) -> ChainResult<Vec<TonMessage>> {
+        #[derive(Deserialize)]
+        struct Messages {
+            messages: Vec<TonMessage>,
+        }
+        let messages: Messages = self
+            .get(
+                "messages",
+                &[
+                    ("source", source.to_hex()),
+                    ("opcode", format!("0x{opcode:08x}")),
+                    ("sort", "asc".to_owned()),
+                    ("offset", offset.to_string()),
+                    ("limit", limit.to_string()),
+                ],
+            )
+            .await?;
+        Ok(messages.messages)
+    }
+
+    /// The transaction with hash `hash`, if it is indexed
+    pub async fn transaction(&self, hash: &str) -> ChainResult<Option<TonTransaction>> {
+        let transactions = self
+            .get::<Transactions>("transactions", &[("hash", hash.to_owned())])
+            .await?;
+        Ok(transactions.transactions.into_iter().next())
+    }
+
+    /// The transaction that received the message with hash `hash`, if it is
+    /// indexed
+    pub async fn transaction_by_in_message(
+        &self,
+        hash: &str,
+    ) -> ChainResult<Option<TonTransaction>> {
+        let transactions = self
+            .get::<Transactions>(
+                "transactionsByMessage",
+                &[
+                    ("msg_hash", hash.to_owned()),
+                    ("direction", "in".to_owned()),
+                ],
+            )
+            .await?;
+        Ok(transactions.transactions.into_iter().next())
+    }
+
+    /// Wait for the transaction that receives the message with hash `hash`
+    #[instrument(skip(self))]
+    pub async fn wait_for_transaction(&self, hash: &str) -> ChainResult<TonTransaction> {
+        for _ in 0..TX_POLL_ATTEMPTS {
+            if let Some(transaction) = self.transaction_by_in_message(hash).await? {
+                return Ok(transaction);
+            }
+            tokio::time::sleep(TX_POLL_INTERVAL).await;
+        }
+        Err(HyperlaneTonError::TransactionTimeout(hash.to_owned()).into())
+    }
+
+    /// Run the get-method `method` of `address` with `stack` as arguments,
+    /// returning the resulting stack.
+    pub async fn run_get_method(
+        &self,
+        address: &TonAddress,
+        method: &str,
+        stack: Vec<StackEntry>,
+    ) -> ChainResult<Vec<StackEntry>> {
+        #[derive(Deserialize)]
+        struct GetMethodResult {
+            exit_code: i32,
+            stack: Vec<StackEntry>,
+        }
+        let body = json!({
+            "address": address.to_hex(),
+            "method": method,
+            "stack": stack,
+        });
+        let result: GetMethodResult = self.post("runGetMethod", &body).await?;
+        if result.exit_code != 0 {
+            return Err(HyperlaneTonError::GetMethodFailed {
+                method: method.to_owned(),
+                exit_code: result.exit_code,
+            }
+            .into());
+        }
+        Ok(result.stack)
+    }
+
+    /// Balance of `address` in nanotons
+    pub async fn balance(&self, address: &TonAddress) -> ChainResult<u64> {
+        #[derive(Deserialize)]
+        struct Account {
+            #[serde(deserialize_with = "deserialize_u64")]
+            balance: u64,
+        }
+        let account: Account = self
+            .get("account", &[("address", address.to_hex())])
+            .await?;
+        Ok(account.balance)
+    }
+
+    /// Send an external message, returning the hash of the message
+    pub async fn send_message(&self, message: Cell) -> ChainResult<String> {
+        #[derive(Deserialize)]
+        struct SentMessage {
+            message_hash: String,
+        }
+        let boc = BagOfCells::from_root(message)
+            .serialize(true)
+            .map_err(HyperlaneTonError::from)?;
+        let sent: SentMessage = self
+            .post("message", &json!({ "boc": STANDARD.encode(boc) }))
+            .await?;
+        Ok(sent.message_hash)
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> ChainResult<T> {
+        let response = self
+            .client
+            .get(self.endpoint(path))
+            .query(query)
+            .send()
+            .await
+            .map_err(HyperlaneTonError::from)?;
+        parse_response(response).await
+    }
+
+    async fn post<T: DeserializeOwned>(&self, path: &str, body: &Value) -> ChainResult<T> {
+        let response = self
+            .client
+            .post(self.endpoint(path))
+            .json(body)
+            .send()
+            .await
+            .map_err(HyperlaneTonError::from)?;
+        parse_response(response).await
+    }
+
+    /// Append `path` to the url, keeping its query parameters
+    fn endpoint(&self, path: &str) -> Url {
+        let mut url = self.url.clone();
+        if let Ok(mut segments) = url.path_segments_mut() {
+            segments.pop_if_empty().push(path);
+        }
+        url
+    }
+}
+
+#[derive(Deserialize)]
+struct Transactions {
+    transactions: Vec<TonTransaction>,
+}
+
+async fn parse_response<T: DeserializeOwned>(response: Response) -> ChainResult<T> {
+    let status = response.status();
+    if !status.is_success() {
+        let message = response.text().await.unwrap_or_default();
+        return Err(HyperlaneTonError::ApiError {
+            status: status.as_u16(),
+            message,
+        }
+        .into());
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| HyperlaneTonError::from(e).into())
+}
+
+/// Decode a hash, which the API returns base64 encoded
+pub(crate) fn parse_hash(hash: &str) -> ChainResult<H256> {
+    let bytes = STANDARD
+        .decode(hash)
+        .map_err(|e| HyperlaneTonError::InvalidResponse(format!("invalid hash {hash}: {e}")))?;
+    if bytes.len() != 32 {
+        return Err(HyperlaneTonError::InvalidResponse(format!("invalid hash {hash}")).into());
+    }
+    Ok(H256::from_slice(&bytes))
+}
+
+/// Decode a bag of cells with a single root, which the API returns base64
+/// encoded
+pub(crate) fn parse_boc(boc: &str) -> ChainResult<ArcCell> {
+    let boc = BagOfCells::parse_base64(boc).map_err(HyperlaneTonError::from)?;
+    let root = boc.single_root().map_err(HyperlaneTonError::from)?;
+    Ok(root.clone())
+}
+
+/// Deserialize a u64, which the API encodes either as a number or a string
+fn deserialize_u64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Number(number) => number
+            .as_u64()
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid u64 {number}"))),
+        Value::String(string) => string.parse().map_err(serde::de::Error::custom),
+        value => Err(serde::de::Error::custom(format!("invalid u64 {value}"))),
+    }
+}
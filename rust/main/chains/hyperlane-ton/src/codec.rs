@@ -0,0 +1,223 @@
+//! Encoding of the cells exchanged with the Hyperlane contracts on TON.
+
+use std::sync::Arc;
+
+use hyperlane_core::{ChainResult, HyperlaneMessage, H256};
+use num_bigint::BigUint;
+use tonlib_core::{
+    cell::{ArcCell, Cell, CellBuilder},
+    TonAddress,
+};
+
+use crate::HyperlaneTonError;
+
+/// Op of the external-out message the mailbox emits for dispatched messages
+pub(crate) const DISPATCH_OP: u32 = 0x1e2f_b2a3;
+/// Op of the external-out message the mailbox emits for processed messages
+pub(crate) const PROCESS_ID_OP: u32 = 0x3b6f_4e91;
+/// Op of the internal message asking the mailbox to process a message
+pub(crate) const PROCESS_OP: u32 = 0x6c2a_2b4d;
+
+/// Bytes stored in each cell of a snake-encoded byte string. Cells hold up to
+/// 1023 bits, so 127 whole bytes.
+const SNAKE_CELL_BYTES: usize = 127;
+
+/// Hyperlane addresses on TON are the account ids of basechain contracts
+pub(crate) fn address_from_h256(address: H256) -> TonAddress {
+    TonAddress::new(0, &address.0)
+}
+
+/// The account id of a TON address
+pub(crate) fn h256_from_address(address: &TonAddress) -> H256 {
+    H256(address.hash_part)
+}
+
+/// Parse an address in any of the raw or user-friendly forms
+pub(crate) fn parse_address(address: &str) -> ChainResult<TonAddress> {
+    address
+        .parse()
+        .map_err(|e| HyperlaneTonError::from(e).into())
+}
+
+fn uint_from_h256(value: H256) -> BigUint {
+    BigUint::from_bytes_be(value.as_bytes())
+}
+
+fn h256_from_uint(value: &BigUint) -> ChainResult<H256> {
+    let bytes = value.to_bytes_be();
+    if bytes.len() > 32 {
+        return Err(
+            HyperlaneTonError::InvalidResponse(format!("{value} overflows 256 bits")).into(),
+        );
+    }
+    let mut padded = [0u8; 32];
+    padded[32 - bytes.len()..].copy_from_slice(&bytes);
+    Ok(H256(padded))
+}
+
+/// Store `bytes` in a chain of cells, each referencing the next one
+pub(crate) fn encode_bytes(bytes: &[u8]) -> ChainResult<ArcCell> {
+    let mut chunks = bytes.chunks(SNAKE_CELL_BYTES).rev();
+    let mut cell = CellBuilder::new()
+        .store_slice(chunks.next().unwrap_or_default())
+        .and_then(|builder| builder.build())
+        .map_err(HyperlaneTonError::from)?;
+    for chunk in chunks {
+        cell = CellBuilder::new()
+            .store_slice(chunk)
+            .and_then(|builder| builder.store_reference(&Arc::new(cell)))
+            .and_then(|builder| builder.build())
+            .map_err(HyperlaneTonError::from)?;
+    }
+    Ok(Arc::new(cell))
+}
+
+/// Load the bytes of a chain of cells, each referencing the next one
+pub(crate) fn decode_bytes(cell: &Cell) -> ChainResult<Vec<u8>> {
+    let mut bytes = vec![];
+    let mut next = Some(cell);
+    while let Some(cell) = next {
+        let mut parser = cell.parser();
+        let len = parser.remaining_bits() / 8;
+        bytes.extend(parser.load_bytes(len).map_err(HyperlaneTonError::from)?);
+        next = cell.reference(0).ok().map(|cell| cell.as_ref());
+    }
+    Ok(bytes)
+}
+
+/// Encode a message into a cell, with its body in a reference
+pub(crate) fn encode_message(message: &HyperlaneMessage) -> ChainResult<ArcCell> {
+    let body = encode_bytes(&message.body)?;
+    let cell = CellBuilder::new()
+        .store_u8(8, message.version)
+        .and_then(|builder| builder.store_u32(32, message.nonce))
+        .and_then(|builder| builder.store_u32(32, message.origin))
+        .and_then(|builder| builder.store_uint(256, &uint_from_h256(message.sender)))
+        .and_then(|builder| builder.store_u32(32, message.destination))
+        .and_then(|builder| builder.store_uint(256, &uint_from_h256(message.recipient)))
+        .and_then(|builder| builder.store_reference(&body))
+        .and_then(|builder| builder.build())
+        .map_err(HyperlaneTonError::from)?;
+    Ok(Arc::new(cell))
+}
+
+/// Decode a message encoded by [`encode_message`]
+pub(crate) fn decode_message(cell: &Cell) -> ChainResult<HyperlaneMessage> {
+    let mut parser = cell.parser();
+    let mut load = || -> Result<_, tonlib_core::cell::TonCellError> {
+        Ok((
+            parser.load_u8(8)?,
+            parser.load_u32(32)?,
+            parser.load_u32(32)?,
+            parser.load_uint(256)?,
+            parser.load_u32(32)?,
+            parser.load_uint(256)?,
+        ))
+    };
+    let (version, nonce, origin, sender, destination, recipient) =
+        load().map_err(HyperlaneTonError::from)?;
+    let body = cell.reference(0).map_err(HyperlaneTonError::from)?;
+    Ok(HyperlaneMessage {
+        version,
+        nonce,
+        origin,
+        sender: h256_from_uint(&sender)?,
+        destination,
+        recipient: h256_from_uint(&recipient)?,
+        body: decode_bytes(body)?,
+    })
+}
+
+/// Body of the internal message asking the mailbox to process `message`
+pub(crate) fn encode_process(message: &HyperlaneMessage, metadata: &[u8]) -> ChainResult<ArcCell> {
+    let message = encode_message(message)?;
+    let metadata = encode_bytes(metadata)?;
+    let cell = CellBuilder::new()
+        .store_u32(32, PROCESS_OP)
+        // The query id is only echoed back in bounced messages
+        .and_then(|builder| builder.store_u64(64, 0))
+        .and_then(|builder| builder.store_reference(&message))
+        .and_then(|builder| builder.store_reference(&metadata))
+        .and_then(|builder| builder.build())
+        .map_err(HyperlaneTonError::from)?;
+    Ok(Arc::new(cell))
+}
+
+/// Decode the body of a dispatch event, returning `None` for other events
+pub(crate) fn decode_dispatch(body: &Cell) -> ChainResult<Option<HyperlaneMessage>> {
+    if load_op(body)? != DISPATCH_OP {
+        return Ok(None);
+    }
+    let message = body.reference(0).map_err(HyperlaneTonError::from)?;
+    decode_message(message).map(Some)
+}
+
+/// Decode the body of a process id event, returning `None` for other events
+pub(crate) fn decode_process_id(body: &Cell) -> ChainResult<Option<H256>> {
+    let mut parser = body.parser();
+    if parser.load_u32(32).map_err(HyperlaneTonError::from)? != PROCESS_ID_OP {
+        return Ok(None);
+    }
+    let message_id = parser.load_uint(256).map_err(HyperlaneTonError::from)?;
+    h256_from_uint(&message_id).map(Some)
+}
+
+fn load_op(body: &Cell) -> ChainResult<u32> {
+    body.parser()
+        .load_u32(32)
+        .map_err(|e| HyperlaneTonError::from(e).into())
+}
+
+/// Decode a 256 bit unsigned integer returned by a get-method
+pub(crate) fn h256_from_stack_num(value: &str) -> ChainResult<H256> {
+    let value = BigUint::parse_bytes(value.trim_start_matches("0x").as_bytes(), 16)
+        .ok_or_else(|| HyperlaneTonError::InvalidResponse(format!("invalid number {value}")))?;
+    h256_from_uint(&value)
+}
+
+/// Encode a 256 bit unsigned integer as a get-method argument
+pub(crate) fn h256_to_stack_num(value: H256) -> String {
+    format!("0x{}", hex::encode(value.as_bytes()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bytes_roundtrip() {
+        for len in [0, 1, SNAKE_CELL_BYTES, SNAKE_CELL_BYTES + 1, 1000] {
+            let bytes = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+            let cell = encode_bytes(&bytes).unwrap();
+            assert_eq!(decode_bytes(&cell).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_message_roundtrip() {
+        let message = HyperlaneMessage {
+            version: 3,
+            nonce: 42,
+            origin: 1000,
+            sender: H256::repeat_byte(0x11),
+            destination: 2000,
+            recipient: H256::repeat_byte(0xff),
+            body: vec![0xab; 300],
+        };
+        let cell = encode_message(&message).unwrap();
+        assert_eq!(decode_message(&cell).unwrap(), message);
+    }
+
+    #[test]
+    fn test_stack_num_roundtrip() {
+        let value = H256::from_low_u64_be(0xdead_beef);
+        assert_eq!(
+            h256_from_stack_num(&h256_to_stack_num(value)).unwrap(),
+            value
+        );
+        assert_eq!(
+            h256_from_stack_num("0x1").unwrap(),
+            H256::from_low_u64_be(1)
+        );
+    }
+}
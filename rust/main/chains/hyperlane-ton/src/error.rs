@@ -0,0 +1,58 @@
+use hyperlane_core::ChainCommunicationError;
+use tonlib_core::{
+    cell::TonCellError, message::TonMessageError, wallet::TonWalletError, TonAddressParseError,
+};
+
+/// Errors from the crates specific to the hyperlane-ton
+/// implementation.
+/// This error can then be converted into the broader error type
+/// in hyperlane-core using the `From` trait impl
+#[derive(Debug, thiserror::Error)]
+pub enum HyperlaneTonError {
+    /// Error sending a request to the HTTP API
+    #[error(transparent)]
+    ReqwestError(#[from] reqwest::Error),
+    /// The HTTP API returned an error
+    #[error("TON API error {status}: {message}")]
+    ApiError {
+        /// HTTP status of the response
+        status: u16,
+        /// Error message returned by the API
+        message: String,
+    },
+    /// The HTTP API returned data that can't be decoded
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+    /// Error building or parsing cells
+    #[error(transparent)]
+    CellError(#[from] TonCellError),
+    /// Error parsing an address
+    #[error(transparent)]
+    AddressError(#[from] TonAddressParseError),
+    /// Error building internal messages
+    #[error(transparent)]
+    MessageError(#[from] TonMessageError),
+    /// Error building wallet messages
+    #[error(transparent)]
+    WalletError(#[from] TonWalletError),
+    /// A get-method exited with a non-zero code
+    #[error("Get-method {method} failed with exit code {exit_code}")]
+    GetMethodFailed {
+        /// Name of the get-method
+        method: String,
+        /// TVM exit code
+        exit_code: i32,
+    },
+    /// Invalid signer key
+    #[error("Invalid ed25519 key: {0}")]
+    InvalidKey(String),
+    /// The message was not processed before the timeout
+    #[error("Message {0} was not processed in time")]
+    TransactionTimeout(String),
+}
+
+impl From<HyperlaneTonError> for ChainCommunicationError {
+    fn from(value: HyperlaneTonError) -> Self {
+        ChainCommunicationError::from_other(value)
+    }
+}
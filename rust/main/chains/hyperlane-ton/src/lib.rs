@@ -0,0 +1,16 @@
+//! Implementation of hyperlane for TON, through the toncenter v3 HTTP API
+//! backed by lite-servers.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+pub use self::{client::*, error::*, mailbox::*, provider::*, signers::*, trait_builder::*};
+
+mod client;
+mod codec;
+mod error;
+mod mailbox;
+mod provider;
+mod signers;
+mod submitter;
+mod trait_builder;
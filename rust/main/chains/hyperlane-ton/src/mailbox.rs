@@ -0,0 +1,285 @@
+use std::{
+    fmt::{Debug, Formatter},
+    num::NonZeroU64,
+    ops::RangeInclusive,
+};
+
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use hyperlane_core::{
+    utils::bytes_to_hex, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Indexed, Indexer,
+    LogMeta, Mailbox, SequenceAwareIndexer, TxCostEstimate, TxOutcome, H256, U256,
+};
+use tonlib_core::{
+    cell::{ArcCell, BagOfCells, Cell},
+    TonAddress,
+};
+use tracing::instrument;
+
+use crate::{
+    client::{parse_boc, stack_entry, StackEntry},
+    codec::{
+        address_from_h256, decode_dispatch, decode_process_id, encode_process, h256_from_address,
+        h256_to_stack_num, DISPATCH_OP, PROCESS_ID_OP,
+    },
+    submitter::MessageSubmitter,
+    ConnectionConf, HyperlaneTonError, Signer, TonMessage, TonProvider,
+};
+
+/// Nanotons attached to process messages when no gas limit is given. They
+/// pay for the execution of the mailbox and the recipient, which return the
+/// excess.
+const DEFAULT_PROCESS_VALUE: u64 = 200_000_000;
+
+/// A reference to a Mailbox contract on some TON chain
+pub struct TonMailbox {
+    provider: TonProvider,
+    address: TonAddress,
+    submitter: Option<MessageSubmitter>,
+}
+
+impl TonMailbox {
+    /// Create a new TON mailbox
+    pub fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        signer: Option<Signer>,
+    ) -> Self {
+        let provider = TonProvider::new(locator.domain.clone(), conf);
+        let submitter =
+            signer.map(|signer| MessageSubmitter::new(provider.client().clone(), signer));
+        Self {
+            provider,
+            address: address_from_h256(locator.address),
+            submitter,
+        }
+    }
+
+    fn submitter(&self) -> ChainResult<&MessageSubmitter> {
+        self.submitter
+            .as_ref()
+            .ok_or(ChainCommunicationError::SignerUnavailable)
+    }
+
+    async fn get_method(&self, method: &str, stack: Vec<StackEntry>) -> ChainResult<StackEntry> {
+        let result = self
+            .provider
+            .client()
+            .run_get_method(&self.address, method, stack)
+            .await?;
+        stack_entry(&result, 0).cloned()
+    }
+
+    /// Fetch the messages the mailbox sent with `opcode` in a range of
+    /// positions among them, with their body and log metadata.
+    async fn fetch_messages(
+        &self,
+        opcode: u32,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(ArcCell, LogMeta)>> {
+        let offset = u64::from(*range.start());
+        let limit = u64::from(*range.end()).saturating_sub(offset) + 1;
+        let messages = self
+            .provider
+            .client()
+            .messages_by_opcode(&self.address, opcode, offset, limit)
+            .await?;
+        try_join_all(messages.iter().map(|message| async move {
+            let body = parse_boc(message_body(message)?)?;
+            let meta = self.provider.log_meta(self.address(), message).await?;
+            Ok::<_, ChainCommunicationError>((body, meta))
+        }))
+        .await
+    }
+}
+
+impl HyperlaneContract for TonMailbox {
+    fn address(&self) -> H256 {
+        h256_from_address(&self.address)
+    }
+}
+
+impl HyperlaneChain for TonMailbox {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.provider.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+impl Debug for TonMailbox {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self as &dyn HyperlaneContract)
+    }
+}
+
+#[async_trait]
+impl Mailbox for TonMailbox {
+    #[instrument(level = "debug", err, ret, skip(self))]
+    async fn count(&self, lag: Option<NonZeroU64>) -> ChainResult<u32> {
+        assert!(lag.is_none(), "TON does not support querying point-in-time");
+        self.get_method("get_nonce", vec![]).await?.as_u32()
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    async fn delivered(&self, id: H256) -> ChainResult<bool> {
+        self.get_method(
+            "get_delivered",
+            vec![StackEntry::num(h256_to_stack_num(id))],
+        )
+        .await?
+        .as_bool()
+    }
+
+    #[instrument(err, ret, skip(self))]
+    async fn default_ism(&self) -> ChainResult<H256> {
+        self.get_method("get_default_ism", vec![]).await?.as_h256()
+    }
+
+    #[instrument(err, ret, skip(self))]
+    async fn recipient_ism(&self, recipient: H256) -> ChainResult<H256> {
+        self.get_method(
+            "get_recipient_ism",
+            vec![StackEntry::num(h256_to_stack_num(recipient))],
+        )
+        .await?
+        .as_h256()
+    }
+
+    #[instrument(err, ret, skip(self), fields(msg=%message, metadata=%bytes_to_hex(metadata)))]
+    async fn process(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+        tx_gas_limit: Option<U256>,
+    ) -> ChainResult<TxOutcome> {
+        let value = tx_gas_limit
+            .map(|limit| u64::try_from(limit).unwrap_or(u64::MAX))
+            .unwrap_or(DEFAULT_PROCESS_VALUE);
+        let body = encode_process(message, metadata)?;
+        self.submitter()?.send(&self.address, value, body).await
+    }
+
+    #[instrument(err, ret, skip(self), fields(msg=%message, metadata=%bytes_to_hex(metadata)))]
+    async fn process_estimate_costs(
+        &self,
+        _message: &HyperlaneMessage,
+        _metadata: &[u8],
+    ) -> ChainResult<TxCostEstimate> {
+        // Execution is paid for by the value attached to the message, so the
+        // cost is at most that value, in nanotons at a price of 1
+        Ok(TxCostEstimate {
+            gas_limit: DEFAULT_PROCESS_VALUE.into(),
+            gas_price: U256::one().try_into()?,
+            l2_gas_limit: None,
+            l1_data_fee: None,
+        })
+    }
+
+    fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8> {
+        // The calldata is the serialized body of the process message
+        encode_process(message, metadata)
+            .ok()
+            .and_then(|body| {
+                BagOfCells::from_root(Cell::clone(&body))
+                    .serialize(true)
+                    .ok()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Struct that retrieves event data for a TON Mailbox contract
+#[derive(Debug)]
+pub struct TonMailboxIndexer {
+    mailbox: TonMailbox,
+}
+
+impl TonMailboxIndexer {
+    /// Create a new TonMailboxIndexer
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> Self {
+        Self {
+            mailbox: TonMailbox::new(conf, locator, None),
+        }
+    }
+}
+
+#[async_trait]
+impl Indexer<HyperlaneMessage> for TonMailboxIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
+        let messages = self.mailbox.fetch_messages(DISPATCH_OP, range).await?;
+        let mut dispatches = vec![];
+        for (body, meta) in messages {
+            if let Some(message) = decode_dispatch(&body)? {
+                dispatches.push((message.into(), meta));
+            }
+        }
+        Ok(dispatches)
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.mailbox.provider.get_finalized_block_number().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<HyperlaneMessage> for TonMailboxIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<HyperlaneMessage>::get_finalized_block_number(self).await?;
+        let sequence = self.mailbox.count(None).await?;
+        Ok((Some(sequence), tip))
+    }
+}
+
+#[async_trait]
+impl Indexer<H256> for TonMailboxIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
+        let messages = self.mailbox.fetch_messages(PROCESS_ID_OP, range).await?;
+        let mut deliveries = vec![];
+        for (body, meta) in messages {
+            if let Some(message_id) = decode_process_id(&body)? {
+                deliveries.push((message_id.into(), meta));
+            }
+        }
+        Ok(deliveries)
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.mailbox.provider.get_finalized_block_number().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<H256> for TonMailboxIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<H256>::get_finalized_block_number(self).await?;
+        // Deliveries are indexed by their position among the process id
+        // events, which the mailbox counts
+        let count = self
+            .mailbox
+            .get_method("get_delivered_count", vec![])
+            .await?
+            .as_u32()?;
+        Ok((Some(count), tip))
+    }
+}
+
+fn message_body(message: &TonMessage) -> ChainResult<&str> {
+    message
+        .message_content
+        .as_ref()
+        .and_then(|content| content.body.as_deref())
+        .ok_or_else(|| {
+            HyperlaneTonError::InvalidResponse(format!("message {} has no body", message.hash))
+                .into()
+        })
+}
@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hyperlane_core::{
+    BlockInfo, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain, HyperlaneProvider,
+    HyperlaneProviderError, LogMeta, TxnInfo, TxnReceiptInfo, H256, U256,
+};
+
+use crate::{
+    client::parse_hash, codec::parse_address, ConnectionConf, HyperlaneTonError, TonMessage,
+    ToncenterClient,
+};
+
+/// A wrapper around a toncenter client to get generic blockchain information.
+#[derive(Debug, Clone)]
+pub struct TonProvider {
+    domain: HyperlaneDomain,
+    client: ToncenterClient,
+}
+
+impl TonProvider {
+    /// Create a new TON provider
+    pub fn new(domain: HyperlaneDomain, conf: &ConnectionConf) -> Self {
+        Self {
+            domain,
+            client: ToncenterClient::new(conf.url.clone()),
+        }
+    }
+
+    /// Get the inner toncenter client
+    pub fn client(&self) -> &ToncenterClient {
+        &self.client
+    }
+
+    /// Sequence number of the latest masterchain block, which commits the
+    /// state of all workchains. Masterchain blocks are final once created.
+    pub async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.client.masterchain_seqno().await
+    }
+
+    /// Build the log metadata of a message sent by the contract at
+    /// `address`.
+    pub(crate) async fn log_meta(
+        &self,
+        address: H256,
+        message: &TonMessage,
+    ) -> ChainResult<LogMeta> {
+        let tx_hash = message.out_msg_tx_hash.as_deref().ok_or_else(|| {
+            HyperlaneTonError::InvalidResponse(format!("message {} has no sender", message.hash))
+        })?;
+        let transaction =
+            self.client.transaction(tx_hash).await?.ok_or_else(|| {
+                HyperlaneTonError::InvalidResponse(format!("unknown tx {tx_hash}"))
+            })?;
+        Ok(LogMeta {
+            address,
+            block_number: transaction.mc_block_seqno.unwrap_or_default().into(),
+            // Transactions are referenced by hash, blocks by sequence number
+            block_hash: H256::zero(),
+            transaction_id: parse_hash(&transaction.hash)?.into(),
+            // Logical times order the transactions of an account, and the
+            // messages they send
+            transaction_index: transaction.lt,
+            log_index: message.created_lt.into(),
+        })
+    }
+}
+
+impl HyperlaneChain for TonProvider {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.clone())
+    }
+}
+
+#[async_trait]
+impl HyperlaneProvider for TonProvider {
+    async fn get_block_by_hash(&self, hash: &H256) -> ChainResult<BlockInfo> {
+        // Blocks are identified by their shard and sequence number
+        Err(HyperlaneProviderError::CouldNotFindObjectByHash(*hash).into())
+    }
+
+    async fn get_txn_by_hash(&self, hash: &H256) -> ChainResult<TxnInfo> {
+        let transaction = self
+            .client
+            .transaction(&STANDARD.encode(hash.as_bytes()))
+            .await?
+            .ok_or(HyperlaneProviderError::CouldNotFindObjectByHash(*hash))?;
+        Ok(TxnInfo {
+            hash: *hash,
+            gas_limit: U256::zero(),
+            max_priority_fee_per_gas: None,
+            max_fee_per_gas: None,
+            gas_price: Some(U256::one()),
+            nonce: transaction.lt,
+            sender: H256::zero(),
+            recipient: None,
+            receipt: Some(TxnReceiptInfo {
+                gas_used: transaction.total_fees.into(),
+                cumulative_gas_used: transaction.total_fees.into(),
+                effective_gas_price: Some(U256::one()),
+            }),
+        })
+    }
+
+    async fn is_contract(&self, _address: &H256) -> ChainResult<bool> {
+        // Every TON account is a contract
+        Ok(true)
+    }
+
+    async fn get_balance(&self, address: String) -> ChainResult<U256> {
+        let address = parse_address(&address)?;
+        Ok(self.client.balance(&address).await?.into())
+    }
+
+    async fn get_chain_metrics(&self) -> ChainResult<Option<ChainInfo>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt::{Debug, Formatter};
+
+use ed25519_dalek::{PublicKey, SecretKey};
+use hyperlane_core::{ChainResult, H256};
+use tonlib_core::{
+    mnemonic::KeyPair,
+    wallet::{TonWallet, WalletVersion},
+    TonAddress,
+};
+
+use crate::HyperlaneTonError;
+
+/// Signer for TON chains, an ed25519 key controlling a v4r2 wallet on the
+/// basechain.
+pub struct Signer {
+    wallet: TonWallet,
+}
+
+impl Signer {
+    /// Create a new signer from an ed25519 private key
+    pub fn new(private_key: H256) -> ChainResult<Self> {
+        let secret = SecretKey::from_bytes(private_key.as_bytes())
+            .map_err(|e| HyperlaneTonError::InvalidKey(e.to_string()))?;
+        let public_key = PublicKey::from(&secret);
+        // Wallets sign with NaCl secret keys, the seed followed by the public
+        // key
+        let key_pair = KeyPair {
+            public_key: public_key.as_bytes().to_vec(),
+            secret_key: [secret.as_bytes(), public_key.as_bytes()].concat(),
+        };
+        let wallet = TonWallet::derive_default(WalletVersion::V4R2, &key_pair)
+            .map_err(HyperlaneTonError::from)?;
+        Ok(Self { wallet })
+    }
+
+    /// Address of the wallet
+    pub fn address(&self) -> &TonAddress {
+        &self.wallet.address
+    }
+
+    pub(crate) fn wallet(&self) -> &TonWallet {
+        &self.wallet
+    }
+}
+
+impl Debug for Signer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Signer")
+            .field("address", &self.wallet.address.to_base64_url())
+            .finish_non_exhaustive()
+    }
+}
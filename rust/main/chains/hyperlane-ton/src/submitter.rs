@@ -0,0 +1,116 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use hyperlane_core::{ChainResult, TxOutcome, U256};
+use num_bigint::BigUint;
+use tokio::sync::Mutex;
+use tonlib_core::{cell::ArcCell, message::TransferMessage, TonAddress};
+use tracing::{debug, instrument, warn};
+
+use crate::{
+    client::{parse_hash, stack_entry},
+    HyperlaneTonError, Signer, TonTransaction, ToncenterClient,
+};
+
+/// How long a signed external message stays valid for
+const MESSAGE_EXPIRATION: Duration = Duration::from_secs(60);
+
+/// Sends internal messages from the wallet of a signer, through external
+/// messages signed by its key.
+pub(crate) struct MessageSubmitter {
+    client: ToncenterClient,
+    signer: Signer,
+    /// Held while a wallet message is in flight. The wallet only accepts the
+    /// external message with its current seqno, which is only incremented once
+    /// the previous one is executed.
+    lock: Mutex<()>,
+}
+
+impl MessageSubmitter {
+    pub(crate) fn new(client: ToncenterClient, signer: Signer) -> Self {
+        Self {
+            client,
+            signer,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Send `body` to `destination` with `value` nanotons attached, and wait
+    /// for the destination to execute it. The value pays for the execution,
+    /// contracts return the excess.
+    #[instrument(skip(self, body))]
+    pub(crate) async fn send(
+        &self,
+        destination: &TonAddress,
+        value: u64,
+        body: ArcCell,
+    ) -> ChainResult<TxOutcome> {
+        let internal = TransferMessage::new(destination, &BigUint::from(value))
+            .with_data(body)
+            .build()
+            .map_err(HyperlaneTonError::from)?;
+
+        let wallet_tx = {
+            let _guard = self.lock.lock().await;
+            let seqno = self.wallet_seqno().await?;
+            let expire_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                + MESSAGE_EXPIRATION;
+            let external = self
+                .signer
+                .wallet()
+                .create_external_message(
+                    expire_at.as_secs() as u32,
+                    seqno,
+                    vec![Arc::new(internal)],
+                    false,
+                )
+                .map_err(HyperlaneTonError::from)?;
+            let message_hash = self.client.send_message(external).await?;
+            debug!(?seqno, %message_hash, "Sent external message");
+            self.client.wait_for_transaction(&message_hash).await?
+        };
+        if !wallet_tx.success() {
+            warn!(hash = %wallet_tx.hash, "Wallet transaction failed");
+            return tx_outcome(&wallet_tx, None);
+        }
+
+        // The wallet forwards the internal message, which is executed by the
+        // destination in a later transaction
+        let Some(internal) = wallet_tx.out_msgs.first() else {
+            warn!(hash = %wallet_tx.hash, "Wallet transaction sent no message");
+            return tx_outcome(&wallet_tx, None);
+        };
+        let destination_tx = self.client.wait_for_transaction(&internal.hash).await?;
+        if !destination_tx.success() {
+            warn!(hash = %destination_tx.hash, "Destination transaction failed");
+        }
+        tx_outcome(&wallet_tx, Some(&destination_tx))
+    }
+
+    async fn wallet_seqno(&self) -> ChainResult<u32> {
+        let stack = self
+            .client
+            .run_get_method(self.signer.address(), "seqno", vec![])
+            .await?;
+        stack_entry(&stack, 0)?.as_u32()
+    }
+}
+
+/// Fees are denominated in nanotons, so they are reported as the gas used at a
+/// price of 1.
+fn tx_outcome(
+    wallet_tx: &TonTransaction,
+    destination_tx: Option<&TonTransaction>,
+) -> ChainResult<TxOutcome> {
+    let fees = wallet_tx.total_fees + destination_tx.map_or(0, |tx| tx.total_fees);
+    Ok(TxOutcome {
+        transaction_id: parse_hash(&wallet_tx.hash)?.into(),
+        executed: destination_tx.map_or(false, |tx| tx.success()),
+        gas_used: fees.into(),
+        gas_price: U256::one().try_into()?,
+    })
+}
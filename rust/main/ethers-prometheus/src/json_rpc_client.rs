@@ -2,7 +2,7 @@
 //! was designed specifically for use with the quorum provider.
 
 use std::fmt::{Debug, Formatter};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use derive_builder::Builder;
@@ -12,8 +12,9 @@ use ethers_core::types::U64;
 use hyperlane_core::rpc_clients::BlockNumberGetter;
 use hyperlane_core::ChainCommunicationError;
 use maplit::hashmap;
-use prometheus::{CounterVec, IntCounterVec};
+use prometheus::{CounterVec, IntCounterVec, IntGaugeVec};
 use serde::{de::DeserializeOwned, Serialize};
+use serde_json::value::to_raw_value;
 
 pub use crate::ChainInfo;
 
@@ -51,6 +52,86 @@ pub struct JsonRpcClientMetrics {
     ///   might still be an "error" but not one with the transport layer.
     #[builder(setter(into, strip_option), default)]
     request_duration_seconds: Option<CounterVec>,
+
+    /// Total number of bytes of request params sent, which providers may bill
+    /// for on top of the requests themselves.
+    /// - `provider_node`: node this is connecting to, e.g. `alchemy.com`,
+    ///   `quicknode.pro`, or `localhost:8545`.
+    /// - `chain`: chain name (or chain id if the name is unknown) of the chain
+    ///   the request was made on.
+    /// - `method`: request method string.
+    #[builder(setter(into, strip_option), default)]
+    request_payload_bytes: Option<IntCounterVec>,
+
+    /// Total number of seconds requests spent queued before being sent,
+    /// because the node's request budget was used up.
+    /// - `provider_node`: node this is connecting to, e.g. `alchemy.com`,
+    ///   `quicknode.pro`, or `localhost:8545`.
+    /// - `chain`: chain name (or chain id if the name is unknown) of the chain
+    ///   the request was made on.
+    /// - `method`: request method string.
+    #[builder(setter(into, strip_option), default)]
+    request_queue_wait_seconds: Option<CounterVec>,
+
+    /// Rank of each provider of a fallback provider, 0 being the provider
+    /// requests are sent to first.
+    /// - `provider_node`: node this is connecting to, e.g. `alchemy.com`,
+    ///   `quicknode.pro`, or `localhost:8545`.
+    /// - `chain`: chain name (or chain id if the name is unknown) of the chain
+    ///   the provider is on.
+    /// - `category`: `read` or `send`, the requests the provider is ranked for.
+    #[builder(setter(into, strip_option), default)]
+    fallback_provider_rank: Option<IntGaugeVec>,
+}
+
+impl JsonRpcClientMetrics {
+    /// The request counts, if they are recorded
+    pub fn request_count(&self) -> Option<&IntCounterVec> {
+        self.request_count.as_ref()
+    }
+
+    /// The bytes of request params sent, if they are recorded
+    pub fn request_payload_bytes(&self) -> Option<&IntCounterVec> {
+        self.request_payload_bytes.as_ref()
+    }
+
+    /// Record the time a request spent queued before being sent to the node
+    /// of `config`.
+    pub fn record_queue_wait(
+        &self,
+        config: &PrometheusJsonRpcClientConfig,
+        method: &str,
+        wait: Duration,
+    ) {
+        if let Some(counter) = &self.request_queue_wait_seconds {
+            counter
+                .with(&hashmap! {
+                    "provider_node" => config.node_host(),
+                    "chain" => config.chain_name(),
+                    "method" => method,
+                })
+                .inc_by(wait.as_secs_f64())
+        }
+    }
+
+    /// Record the rank of `provider` among the providers of a fallback
+    /// provider for requests of `category`.
+    pub fn record_provider_rank(
+        &self,
+        provider: &impl PrometheusJsonRpcClientConfigExt,
+        category: &str,
+        rank: usize,
+    ) {
+        if let Some(gauge) = &self.fallback_provider_rank {
+            gauge
+                .with(&hashmap! {
+                    "provider_node" => provider.node_host(),
+                    "chain" => provider.chain_name(),
+                    "category" => category,
+                })
+                .set(rank as i64)
+        }
+    }
 }
 
 /// Expected label names for the metric.
@@ -64,6 +145,23 @@ pub const REQUEST_DURATION_SECONDS_LABELS: &[&str] =
 /// Help string for the metric.
 pub const REQUEST_DURATION_SECONDS_HELP: &str = "Total number of seconds spent making requests";
 
+/// Expected label names for the metric.
+pub const REQUEST_PAYLOAD_BYTES_LABELS: &[&str] = &["provider_node", "chain", "method"];
+/// Help string for the metric.
+pub const REQUEST_PAYLOAD_BYTES_HELP: &str = "Total number of bytes of request params sent";
+
+/// Expected label names for the metric.
+pub const REQUEST_QUEUE_WAIT_SECONDS_LABELS: &[&str] = &["provider_node", "chain", "method"];
+/// Help string for the metric.
+pub const REQUEST_QUEUE_WAIT_SECONDS_HELP: &str =
+    "Total number of seconds requests spent queued over the request budget of their node";
+
+/// Expected label names for the metric.
+pub const FALLBACK_PROVIDER_RANK_LABELS: &[&str] = &["provider_node", "chain", "category"];
+/// Help string for the metric.
+pub const FALLBACK_PROVIDER_RANK_HELP: &str =
+    "Rank of the provider among the providers of its fallback provider, 0 being tried first";
+
 /// Configuration for the prometheus JsonRpcClioent. This can be loaded via
 /// serde.
 #[derive(Default, Clone, Debug)]
@@ -166,8 +264,28 @@ where
         T: Debug + Serialize + Send + Sync,
         R: DeserializeOwned,
     {
+        // When their size is recorded, the params are serialized here rather
+        // than by the inner client, which only copies the serialized params
+        // into the request, so that they're still only serialized once
+        let raw_params = self
+            .metrics
+            .request_payload_bytes
+            .as_ref()
+            .and_then(|counter| Some((counter, to_raw_value(&params).ok()?)));
         let start = Instant::now();
-        let res = self.inner.request(method, params).await;
+        let res = match raw_params {
+            Some((counter, raw_params)) => {
+                counter
+                    .with(&hashmap! {
+                        "provider_node" => self.config.node_host(),
+                        "chain" => self.config.chain_name(),
+                        "method" => method,
+                    })
+                    .inc_by(raw_params.get().len() as u64);
+                self.inner.request(method, raw_params).await
+            }
+            None => self.inner.request(method, params).await,
+        };
         let labels = hashmap! {
             "provider_node" => self.config.node_host(),
             "chain" => self.config.chain_name(),
@@ -0,0 +1,180 @@
+//! Benchmarks of the sequence aware cursor indexing a backlog of messages,
+//! against an in memory chain and store so only the cursor is measured.
+
+use std::{
+    collections::BTreeMap,
+    ops::RangeInclusive,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hyperlane_base::test_utils::ForwardBackwardSequenceAwareSyncCursor;
+use hyperlane_core::{
+    ChainResult, ContractSyncCursor, CursorAction, HyperlaneLogStore, HyperlaneMessage,
+    HyperlaneSequenceAwareIndexerStoreReader, IndexMode, Indexed, Indexer, LogMeta,
+    SequenceAwareIndexer, H256, H512, U256,
+};
+use tokio::runtime::Builder;
+
+/// Messages in the backlog
+const MESSAGES: u32 = 2_000;
+/// Blocks the backlog is spread over
+const MESSAGES_PER_BLOCK: u32 = 4;
+const CHUNK_SIZE: u32 = 100;
+
+/// A chain whose messages can be revealed after the cursor is created, to
+/// measure forward indexing
+#[derive(Debug)]
+struct InMemoryChain {
+    mode: IndexMode,
+    /// Number of messages dispatched so far
+    visible: Mutex<u32>,
+}
+
+impl InMemoryChain {
+    fn tip(&self) -> u32 {
+        match *self.visible.lock().unwrap() {
+            0 => 0,
+            visible => block_of(visible - 1),
+        }
+    }
+}
+
+fn block_of(nonce: u32) -> u32 {
+    nonce / MESSAGES_PER_BLOCK + 1
+}
+
+#[async_trait]
+impl Indexer<HyperlaneMessage> for InMemoryChain {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
+        let visible = *self.visible.lock().unwrap();
+        Ok((0..visible)
+            .filter(|nonce| match self.mode {
+                IndexMode::Block => range.contains(&block_of(*nonce)),
+                IndexMode::Sequence => range.contains(nonce),
+            })
+            .map(|nonce| {
+                let message = HyperlaneMessage {
+                    nonce,
+                    ..Default::default()
+                };
+                let meta = LogMeta {
+                    address: H256::zero(),
+                    block_number: block_of(nonce).into(),
+                    block_hash: H256::zero(),
+                    transaction_id: H512::zero(),
+                    transaction_index: 0,
+                    log_index: U256::zero(),
+                };
+                (Indexed::new(message).with_sequence(nonce), meta)
+            })
+            .collect())
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        Ok(self.tip())
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<HyperlaneMessage> for InMemoryChain {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let visible = *self.visible.lock().unwrap();
+        Ok((Some(visible), self.tip()))
+    }
+}
+
+#[derive(Debug, Default)]
+struct InMemoryStore {
+    blocks: Mutex<BTreeMap<u32, u64>>,
+}
+
+#[async_trait]
+impl HyperlaneLogStore<HyperlaneMessage> for InMemoryStore {
+    async fn store_logs(&self, logs: &[(Indexed<HyperlaneMessage>, LogMeta)]) -> eyre::Result<u32> {
+        let mut blocks = self.blocks.lock().unwrap();
+        for (message, meta) in logs {
+            blocks.insert(message.inner().nonce, meta.block_number);
+        }
+        Ok(logs.len() as u32)
+    }
+}
+
+#[async_trait]
+impl HyperlaneSequenceAwareIndexerStoreReader<HyperlaneMessage> for InMemoryStore {
+    async fn retrieve_by_sequence(&self, sequence: u32) -> eyre::Result<Option<HyperlaneMessage>> {
+        Ok(self
+            .blocks
+            .lock()
+            .unwrap()
+            .contains_key(&sequence)
+            .then(|| HyperlaneMessage {
+                nonce: sequence,
+                ..Default::default()
+            }))
+    }
+
+    async fn retrieve_log_block_number_by_sequence(
+        &self,
+        sequence: u32,
+    ) -> eyre::Result<Option<u64>> {
+        Ok(self.blocks.lock().unwrap().get(&sequence).copied())
+    }
+}
+
+/// Indexes the backlog, which the cursor finds when it starts if
+/// `backward`, or after it started otherwise. Returns the time spent.
+async fn index_backlog(mode: IndexMode, backward: bool) -> Duration {
+    let chain = Arc::new(InMemoryChain {
+        mode,
+        visible: Mutex::new(if backward { MESSAGES } else { 0 }),
+    });
+    let store = Arc::new(InMemoryStore::default());
+    let mut cursor =
+        ForwardBackwardSequenceAwareSyncCursor::new(chain.clone(), store.clone(), CHUNK_SIZE, mode)
+            .await
+            .unwrap();
+    *chain.visible.lock().unwrap() = MESSAGES;
+
+    let start = Instant::now();
+    while store.blocks.lock().unwrap().len() < MESSAGES as usize {
+        let (action, _) = cursor.next_action().await.unwrap();
+        if let CursorAction::Query(range) = action {
+            let logs = chain.fetch_logs_in_range(range.clone()).await.unwrap();
+            store.store_logs(&logs).await.unwrap();
+            cursor.update(logs, range).await.unwrap();
+        }
+    }
+    start.elapsed()
+}
+
+fn bench_cursor(c: &mut Criterion) {
+    let rt = Builder::new_current_thread().enable_all().build().unwrap();
+    let mut group = c.benchmark_group("sequence_aware_cursor");
+    group.throughput(Throughput::Elements(MESSAGES.into()));
+    for (mode, mode_name) in [
+        (IndexMode::Block, "block"),
+        (IndexMode::Sequence, "sequence"),
+    ] {
+        for (backward, direction) in [(false, "forward"), (true, "backward")] {
+            group.bench_function(BenchmarkId::new(direction, mode_name), |b| {
+                b.to_async(&rt).iter_custom(|iters| async move {
+                    let mut total = Duration::ZERO;
+                    for _ in 0..iters {
+                        total += index_backlog(mode, backward).await;
+                    }
+                    total
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_cursor);
+criterion_main!(benches);
@@ -0,0 +1,97 @@
+//! Benchmarks of storing indexed messages in and reading them from the
+//! relayer's database.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use hyperlane_base::db::{test_utils::setup_db, HyperlaneRocksDB};
+use hyperlane_core::{
+    HyperlaneDomain, HyperlaneLogStore, HyperlaneMessage, Indexed, LogMeta, H256, H512, U256,
+};
+use tempfile::TempDir;
+use tokio::runtime::{Builder, Runtime};
+
+/// Number of messages stored in a batch, about what a chunk of indexing
+/// returns on a busy chain
+const BATCH_SIZE: u32 = 100;
+
+fn runtime() -> Runtime {
+    Builder::new_current_thread().enable_all().build().unwrap()
+}
+
+fn temp_db(dir: &TempDir) -> HyperlaneRocksDB {
+    HyperlaneRocksDB::new(
+        &HyperlaneDomain::new_test_domain("bench"),
+        setup_db(dir.path().to_str().unwrap().into()),
+    )
+}
+
+fn logs(first_nonce: u32, count: u32) -> Vec<(Indexed<HyperlaneMessage>, LogMeta)> {
+    (first_nonce..first_nonce + count)
+        .map(|nonce| {
+            let message = HyperlaneMessage {
+                nonce,
+                version: 3,
+                origin: 1,
+                sender: H256::repeat_byte(1),
+                destination: 2,
+                recipient: H256::repeat_byte(2),
+                body: vec![0xab; 256],
+            };
+            let meta = LogMeta {
+                address: H256::repeat_byte(3),
+                block_number: nonce.into(),
+                block_hash: H256::from_low_u64_be(nonce.into()),
+                transaction_id: H512::from_low_u64_be(nonce.into()),
+                transaction_index: 0,
+                log_index: U256::zero(),
+            };
+            (Indexed::new(message).with_sequence(nonce), meta)
+        })
+        .collect()
+}
+
+fn bench_store_messages(c: &mut Criterion) {
+    let rt = runtime();
+    let dir = TempDir::new().unwrap();
+    let db = temp_db(&dir);
+    let mut next_nonce = 0;
+
+    let mut group = c.benchmark_group("db");
+    group.throughput(Throughput::Elements(BATCH_SIZE.into()));
+    group.bench_function("store_messages", |b| {
+        b.iter_batched(
+            || {
+                let batch = logs(next_nonce, BATCH_SIZE);
+                next_nonce += BATCH_SIZE;
+                batch
+            },
+            |batch| rt.block_on(db.store_logs(&batch)).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_retrieve_messages(c: &mut Criterion) {
+    let rt = runtime();
+    let dir = TempDir::new().unwrap();
+    let db = temp_db(&dir);
+    let stored = 10_000;
+    rt.block_on(db.store_logs(&logs(0, stored))).unwrap();
+    let mut nonce = 0;
+
+    let mut group = c.benchmark_group("db");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("retrieve_message_by_nonce", |b| {
+        b.iter(|| {
+            // Spread the reads over the stored messages
+            nonce = (nonce + 7919) % stored;
+            db.retrieve_message_by_nonce(black_box(nonce))
+                .unwrap()
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_store_messages, bench_retrieve_messages);
+criterion_main!(benches);
@@ -0,0 +1,124 @@
+//! Benchmarks of fetching a quorum of signed checkpoints from validators,
+//! which the relayer does to build multisig ISM metadata. The validators'
+//! storage is mocked in memory, so recovering the signers dominates.
+
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use ethers::signers::LocalWallet;
+use eyre::Result;
+use hyperlane_base::{AgentMetadata, CheckpointSyncer, CoreMetrics, MultisigCheckpointSyncer};
+use hyperlane_core::{
+    Checkpoint, CheckpointWithMessageId, HyperlaneSigner, HyperlaneSignerExt, ReorgEvent,
+    SignedAnnouncement, SignedCheckpointWithMessageId, H160, H256,
+};
+use hyperlane_ethereum::Signers;
+use prometheus::Registry;
+use tokio::runtime::{Builder, Runtime};
+
+const INDEX: u32 = 1_000;
+
+/// A validator's checkpoint storage, holding a single checkpoint
+#[derive(Debug)]
+struct InMemoryCheckpointSyncer {
+    checkpoint: SignedCheckpointWithMessageId,
+}
+
+#[async_trait]
+impl CheckpointSyncer for InMemoryCheckpointSyncer {
+    async fn latest_index(&self) -> Result<Option<u32>> {
+        Ok(Some(self.checkpoint.value.index))
+    }
+
+    async fn write_latest_index(&self, _index: u32) -> Result<()> {
+        Ok(())
+    }
+
+    async fn fetch_checkpoint(&self, index: u32) -> Result<Option<SignedCheckpointWithMessageId>> {
+        Ok((index == self.checkpoint.value.index).then(|| self.checkpoint.clone()))
+    }
+
+    async fn write_checkpoint(
+        &self,
+        _signed_checkpoint: &SignedCheckpointWithMessageId,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn write_metadata(&self, _metadata: &AgentMetadata) -> Result<()> {
+        Ok(())
+    }
+
+    async fn write_announcement(&self, _signed_announcement: &SignedAnnouncement) -> Result<()> {
+        Ok(())
+    }
+
+    fn announcement_location(&self) -> String {
+        "memory://".to_owned()
+    }
+
+    async fn write_reorg_status(&self, _reorg_event: &ReorgEvent) -> Result<()> {
+        Ok(())
+    }
+
+    async fn reorg_status(&self) -> Result<Option<ReorgEvent>> {
+        Ok(None)
+    }
+}
+
+/// A syncer for `validator_count` validators which all signed the
+/// checkpoint at `INDEX`, and the validators in onchain order
+fn multisig_syncer(rt: &Runtime, validator_count: u64) -> (MultisigCheckpointSyncer, Vec<H256>) {
+    let checkpoint = CheckpointWithMessageId {
+        checkpoint: Checkpoint {
+            merkle_tree_hook_address: H256::repeat_byte(1),
+            mailbox_domain: 1,
+            root: H256::repeat_byte(2),
+            index: INDEX,
+        },
+        message_id: H256::repeat_byte(3),
+    };
+    let mut syncers: HashMap<H160, Arc<dyn CheckpointSyncer>> = HashMap::new();
+    let mut validators = vec![];
+    for key in 1..=validator_count {
+        let wallet: LocalWallet = format!("{key:064x}").parse().unwrap();
+        let signer = Signers::Local(wallet);
+        let signed = rt.block_on(signer.sign(checkpoint)).unwrap();
+        let address = signer.eth_address();
+        syncers.insert(
+            address,
+            Arc::new(InMemoryCheckpointSyncer { checkpoint: signed }),
+        );
+        validators.push(address.into());
+    }
+    let metrics = Arc::new(CoreMetrics::new("bench", 0, Registry::new()).unwrap());
+    (
+        MultisigCheckpointSyncer::new(syncers, metrics, None),
+        validators,
+    )
+}
+
+fn bench_fetch_checkpoint(c: &mut Criterion) {
+    let rt = Builder::new_current_thread().enable_all().build().unwrap();
+    let mut group = c.benchmark_group("multisig_fetch_checkpoint");
+    for (validator_count, threshold) in [(3, 2), (10, 7), (20, 14)] {
+        let (syncer, validators) = multisig_syncer(&rt, validator_count);
+        group.bench_function(
+            BenchmarkId::from_parameter(format!("{threshold}_of_{validator_count}")),
+            |b| {
+                b.to_async(&rt).iter(|| async {
+                    syncer
+                        .fetch_checkpoint(&validators, threshold, INDEX)
+                        .await
+                        .unwrap()
+                        .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_fetch_checkpoint);
+criterion_main!(benches);
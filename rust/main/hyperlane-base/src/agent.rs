@@ -1,17 +1,21 @@
 pub use crate::metadata::AgentMetadata;
 
-use std::{env, fmt::Debug, sync::Arc};
+use std::{env, fmt::Debug, process::ExitCode, sync::Arc};
 
 use async_trait::async_trait;
 use eyre::Result;
 use hyperlane_core::config::*;
-use tracing::info;
+use tracing::{error, info};
 
 use crate::{
+    config_report::{ConfigReport, VALIDATE_CONFIG_FLAG},
     create_chain_metrics,
-    metrics::{create_agent_metrics, AgentMetrics, CoreMetrics},
+    metrics::{
+        create_agent_metrics, spawn_named, AgentMetrics, CoreMetrics, LogRpcUsageSink,
+        RpcUsageReporter, RuntimeMetricsUpdater,
+    },
     settings::Settings,
-    ChainMetrics,
+    shutdown_requested, ChainMetrics, ShutdownSignal, ShutdownTrigger,
 };
 
 /// Properties shared across all hyperlane agents
@@ -47,7 +51,6 @@ pub trait BaseAgent: Send + Sync + Debug {
         metrics: Arc<CoreMetrics>,
         agent_metrics: AgentMetrics,
         chain_metrics: ChainMetrics,
-        tokio_console_server: console_subscriber::Server,
     ) -> Result<Self>
     where
         Self: Sized;
@@ -55,13 +58,48 @@ pub trait BaseAgent: Send + Sync + Debug {
     /// Start running this agent.
     #[allow(clippy::async_yields_async)]
     async fn run(self);
+
+    /// Run this agent until `shutdown` is triggered. Agents with work that
+    /// shouldn't be interrupted, like submitting transactions, override this
+    /// to wind it down first.
+    async fn run_until_shutdown(self, shutdown: ShutdownSignal)
+    where
+        Self: Sized,
+    {
+        tokio::select! {
+            _ = self.run() => {}
+            _ = shutdown.triggered() => {}
+        }
+    }
+}
+
+/// How a run of [`agent_main`] ended
+#[derive(Debug, Default)]
+pub enum AgentOutcome {
+    /// The agent ran until it was shut down
+    #[default]
+    Stopped,
+    /// The agent only validated its config, as asked with
+    /// [`VALIDATE_CONFIG_FLAG`]. The binary prints the report.
+    ConfigValidated(ConfigReport),
+}
+
+impl AgentOutcome {
+    /// The exit code of the binary, which is non-zero if the validated config
+    /// has issues
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            Self::Stopped => ExitCode::SUCCESS,
+            Self::ConfigValidated(report) => report.exit_code(),
+        }
+    }
 }
 
 /// Call this from `main` to fully initialize and run the agent for its entire
 /// lifecycle. This assumes only a single agent is being run. This will
 /// initialize the metrics server and tracing as well.
 #[allow(unexpected_cfgs)] // TODO: `rustc` 1.80.1 clippy issue
-pub async fn agent_main<A: BaseAgent>() -> Result<()> {
+pub async fn agent_main<A: BaseAgent>() -> Result<AgentOutcome> {
     if env::var("ONELINE_BACKTRACES")
         .map(|v| v.to_lowercase())
         .as_deref()
@@ -83,11 +121,30 @@ pub async fn agent_main<A: BaseAgent>() -> Result<()> {
 
     let agent_metadata = AgentMetadata::new(git_sha);
 
+    if env::args().any(|arg| arg == VALIDATE_CONFIG_FLAG) {
+        return Ok(AgentOutcome::ConfigValidated(validate_config::<A>().await?));
+    }
+
     let settings = A::Settings::load()?;
     let core_settings: &Settings = settings.as_ref();
 
     let metrics = settings.as_ref().metrics(A::AGENT_NAME)?;
     let tokio_server = core_settings.tracing.start_tracing(&metrics)?;
+    if let Some(tokio_server) = tokio_server {
+        spawn_named("tokio_console", None, async move {
+            info!("Starting tokio console server");
+            if let Err(err) = tokio_server.serve().await {
+                error!(?err, "Tokio console server failed to start");
+            }
+        });
+    }
+    if core_settings.tracing.runtime_instrumentation() {
+        RuntimeMetricsUpdater::new(&metrics)?.spawn();
+    }
+    if let Some(period) = core_settings.tracing.rpc_usage_summary_interval() {
+        RpcUsageReporter::new(&metrics, vec![Box::new(LogRpcUsageSink)]).spawn(period);
+    }
+    core_settings.verify_code_hashes(&metrics).await?;
     let agent_metrics = create_agent_metrics(&metrics)?;
     let chain_metrics = create_chain_metrics(&metrics)?;
     let agent = A::from_settings(
@@ -96,12 +153,33 @@ pub async fn agent_main<A: BaseAgent>() -> Result<()> {
         metrics.clone(),
         agent_metrics,
         chain_metrics,
-        tokio_server,
     )
     .await?;
 
-    // This await will only end if a panic happens. We won't crash, but instead gracefully shut down
-    agent.run().await;
+    let (trigger, shutdown) = ShutdownTrigger::new();
+    spawn_named("shutdown_listener", None, async move {
+        shutdown_requested().await;
+        info!(agent = A::AGENT_NAME, "Shutdown requested");
+        trigger.trigger();
+    });
+
+    // This await only ends on shutdown, or if a panic happens. We won't crash, but instead
+    // gracefully shut down
+    agent.run_until_shutdown(shutdown).await;
     info!(agent = A::AGENT_NAME, "Shutting down agent...");
-    Ok(())
+    Ok(AgentOutcome::Stopped)
+}
+
+/// Parse and validate the agent's config, checking that every chain's RPC
+/// answers and that its contracts are deployed
+async fn validate_config<A: BaseAgent>() -> Result<ConfigReport> {
+    let report = match A::Settings::load() {
+        Ok(settings) => {
+            let settings: &Settings = settings.as_ref();
+            let metrics = settings.metrics(A::AGENT_NAME)?;
+            ConfigReport::check_chains(A::AGENT_NAME, settings, &metrics).await
+        }
+        Err(err) => ConfigReport::from_parsing_error(A::AGENT_NAME, &err),
+    };
+    Ok(report)
 }
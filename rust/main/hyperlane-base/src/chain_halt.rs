@@ -0,0 +1,223 @@
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Formatter},
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use hyperlane_core::{HyperlaneDomain, SequenceAwareIndexer, H256};
+use prometheus::IntGauge;
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::{debug, error, info, info_span, instrument::Instrumented, Instrument};
+
+use crate::{spawn_named, NotificationEvent, Notifier};
+
+/// How often the latest block of a chain is checked for progress
+const CHAIN_HALT_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long paused pipelines wait before checking whether their chain resumed
+pub const CHAIN_HALT_PAUSE: Duration = Duration::from_secs(5);
+
+/// The chains currently halted, shared by the monitors detecting the halts
+/// with the pipelines they pause
+#[derive(Debug, Clone, Default)]
+pub struct ChainHalts(Arc<RwLock<HashSet<u32>>>);
+
+impl ChainHalts {
+    /// Whether `domain` is halted, in which case the pipelines working on it,
+    /// e.g. its contract syncs and submitters, are paused until it resumes
+    pub fn is_halted(&self, domain: &HyperlaneDomain) -> bool {
+        self.0.read().unwrap().contains(&domain.id())
+    }
+
+    fn set_halted(&self, domain: &HyperlaneDomain, halted: bool) {
+        let mut halted_chains = self.0.write().unwrap();
+        if halted {
+            halted_chains.insert(domain.id());
+        } else {
+            halted_chains.remove(&domain.id());
+        }
+    }
+}
+
+/// A change of the halt status of a chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainHaltTransition {
+    /// No new block was seen for longer than the halt threshold
+    Halted {
+        /// How long no new block was seen for
+        halted_for: Duration,
+    },
+    /// A new block was seen after the chain halted
+    Resumed {
+        /// How long the chain was halted for
+        halted_for: Duration,
+    },
+}
+
+/// Tracks when the latest block of a chain last advanced, to tell when the
+/// chain halts and when it resumes
+#[derive(Debug)]
+pub struct ChainHaltDetector {
+    threshold: Duration,
+    latest_block: Option<u64>,
+    advanced_at: Option<Instant>,
+    halted: bool,
+}
+
+impl ChainHaltDetector {
+    /// Considers the chain halted once no new block was seen for `threshold`
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            latest_block: None,
+            advanced_at: None,
+            halted: false,
+        }
+    }
+
+    /// Records the latest block seen at `now`. Returns whether the chain
+    /// just halted or resumed.
+    pub fn record(&mut self, latest_block: u64, now: Instant) -> Option<ChainHaltTransition> {
+        let advanced_at = *self.advanced_at.get_or_insert(now);
+        let advanced = self.latest_block < Some(latest_block);
+        if advanced {
+            self.latest_block = Some(latest_block);
+            self.advanced_at = Some(now);
+        }
+        let halted_for = now.duration_since(advanced_at);
+        match (self.halted, advanced) {
+            (true, true) => {
+                self.halted = false;
+                Some(ChainHaltTransition::Resumed { halted_for })
+            }
+            (false, false) if halted_for >= self.threshold => {
+                self.halted = true;
+                Some(ChainHaltTransition::Halted { halted_for })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Periodically checks the latest finalized block of a chain, and pauses the
+/// pipelines working on the chain while it doesn't produce new blocks, rather
+/// than have them burn RPC calls and log errors until it resumes.
+pub struct ChainHaltMonitor {
+    domain: HyperlaneDomain,
+    indexer: Arc<dyn SequenceAwareIndexer<H256>>,
+    detector: ChainHaltDetector,
+    halts: ChainHalts,
+    halted_gauge: IntGauge,
+    notifier: Notifier,
+}
+
+impl Debug for ChainHaltMonitor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ChainHaltMonitor {{ domain: {}, detector: {:?} }}",
+            self.domain, self.detector
+        )
+    }
+}
+
+impl ChainHaltMonitor {
+    /// Considers `domain` halted in `halts` once its latest finalized block,
+    /// as fetched by the delivery `indexer` of the chain, didn't advance
+    /// for `threshold`
+    pub fn new(
+        domain: HyperlaneDomain,
+        indexer: Arc<dyn SequenceAwareIndexer<H256>>,
+        threshold: Duration,
+        halts: ChainHalts,
+        halted_gauge: IntGauge,
+        notifier: Notifier,
+    ) -> Self {
+        Self {
+            domain,
+            indexer,
+            detector: ChainHaltDetector::new(threshold),
+            halts,
+            halted_gauge,
+            notifier,
+        }
+    }
+
+    async fn check(&mut self) {
+        let latest_block = match self.indexer.get_finalized_block_number().await {
+            Ok(latest_block) => latest_block as u64,
+            // Whether the chain progressed is unknown, which is neither
+            // progress nor the lack of it
+            Err(err) => {
+                debug!(domain=%self.domain, ?err, "Failed to fetch the latest block");
+                return;
+            }
+        };
+        match self.detector.record(latest_block, Instant::now()) {
+            Some(ChainHaltTransition::Halted { halted_for }) => {
+                error!(domain=%self.domain, ?latest_block, ?halted_for, "Chain halted, pausing its indexing and submission");
+                self.halts.set_halted(&self.domain, true);
+                self.halted_gauge.set(1);
+                let event = NotificationEvent::ChainHalted {
+                    chain: self.domain.name().to_owned(),
+                    halted_for_secs: halted_for.as_secs(),
+                };
+                self.notifier.notify(&event);
+            }
+            Some(ChainHaltTransition::Resumed { halted_for }) => {
+                info!(domain=%self.domain, ?latest_block, ?halted_for, "Chain resumed, resuming its indexing and submission");
+                self.halts.set_halted(&self.domain, false);
+                self.halted_gauge.set(0);
+            }
+            None => {}
+        }
+    }
+
+    /// Spawns a tokio task that checks the chain on an interval.
+    pub fn spawn(mut self) -> Instrumented<JoinHandle<()>> {
+        let span = info_span!("ChainHaltMonitor", domain=%self.domain);
+        let domain = self.domain.clone();
+        spawn_named("chain_halt_monitor", Some(&domain), async move {
+            let mut interval = tokio::time::interval(CHAIN_HALT_CHECK_INTERVAL);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                self.check().await;
+            }
+        })
+        .instrument(span)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_halts_and_resumes_with_block_progress() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut detector = ChainHaltDetector::new(Duration::from_secs(300));
+
+        assert_eq!(detector.record(10, at(0)), None);
+        assert_eq!(detector.record(11, at(100)), None);
+        // A stale block isn't progress
+        assert_eq!(detector.record(11, at(200)), None);
+        assert_eq!(detector.record(11, at(300)), None);
+        assert_eq!(
+            detector.record(11, at(400)),
+            Some(ChainHaltTransition::Halted {
+                halted_for: Duration::from_secs(300)
+            })
+        );
+        assert_eq!(detector.record(11, at(500)), None);
+        assert_eq!(
+            detector.record(12, at(600)),
+            Some(ChainHaltTransition::Resumed {
+                halted_for: Duration::from_secs(500)
+            })
+        );
+        assert_eq!(detector.record(12, at(700)), None);
+    }
+}
@@ -0,0 +1,208 @@
+use std::{process::ExitCode, time::Duration};
+
+use futures_util::future::join_all;
+use hyperlane_core::{
+    config::{ConfigParsingError, ConfigPath},
+    H256,
+};
+use serde::Serialize;
+use tokio::time::timeout;
+
+use crate::{
+    settings::{ChainConf, Settings},
+    CoreMetrics,
+};
+
+/// Command line flag making an agent validate its config and exit instead of
+/// running
+pub const VALIDATE_CONFIG_FLAG: &str = "--validate-config";
+
+/// How long a chain's RPC has to answer each check
+const RPC_CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A problem found in the config
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigIssue {
+    /// Path of the config field at fault, e.g. `chains.ethereum.mailbox`
+    pub path: String,
+    /// What is wrong with it
+    pub message: String,
+}
+
+/// Outcome of validating an agent's config, printed as JSON by
+/// `--validate-config`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigReport {
+    /// The agent the config was validated for
+    pub agent: String,
+    /// Whether no issue was found
+    pub valid: bool,
+    /// The chains whose RPCs and contracts were checked
+    pub chains_checked: Vec<String>,
+    /// Every issue found
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl ConfigReport {
+    /// The report as pretty printed JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Non-zero if any issue was found
+    pub fn exit_code(&self) -> ExitCode {
+        if self.valid {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        }
+    }
+
+    /// Report the errors the config failed to parse with
+    pub fn from_parsing_error(agent: &str, err: &ConfigParsingError) -> Self {
+        Self::new(agent, vec![], parsing_issues(err))
+    }
+
+    /// Query every chain's RPC to check that the core contracts are deployed
+    /// at the configured addresses
+    pub async fn check_chains(agent: &str, settings: &Settings, metrics: &CoreMetrics) -> Self {
+        let mut chains = settings.chains.iter().collect::<Vec<_>>();
+        chains.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let issues = join_all(
+            chains
+                .iter()
+                .map(|(name, chain)| check_chain(name, chain, metrics)),
+        )
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+        let chains_checked = chains.into_iter().map(|(name, _)| name.clone()).collect();
+        Self::new(agent, chains_checked, issues)
+    }
+
+    fn new(agent: &str, chains_checked: Vec<String>, issues: Vec<ConfigIssue>) -> Self {
+        Self {
+            agent: agent.to_owned(),
+            valid: issues.is_empty(),
+            chains_checked,
+            issues,
+        }
+    }
+}
+
+fn parsing_issues(err: &ConfigParsingError) -> Vec<ConfigIssue> {
+    err.iter()
+        .map(|(path, report)| ConfigIssue {
+            path: path.json_name(),
+            message: format!("{report:#}"),
+        })
+        .collect()
+}
+
+/// Check that the chain's RPC answers, that its core contracts are
+/// deployed, and that their code matches the pinned code hashes
+async fn check_chain(name: &str, chain: &ChainConf, metrics: &CoreMetrics) -> Vec<ConfigIssue> {
+    let cwp = ConfigPath::default() + "chains" + name.to_lowercase();
+    let issue = |key: &str, message: String| ConfigIssue {
+        path: (&cwp + key).json_name(),
+        message,
+    };
+
+    let provider = match chain.build_provider(metrics).await {
+        Ok(provider) => provider,
+        Err(err) => return vec![issue("rpc_urls", format!("{err:#}"))],
+    };
+    let contracts: [(&str, H256); 4] = [
+        ("mailbox", chain.addresses.mailbox),
+        (
+            "interchain_gas_paymaster",
+            chain.addresses.interchain_gas_paymaster,
+        ),
+        ("validator_announce", chain.addresses.validator_announce),
+        ("merkle_tree_hook", chain.addresses.merkle_tree_hook),
+    ];
+    let formatter = chain.connection.address_formatter();
+    let mut issues = vec![];
+    for (key, address) in contracts {
+        match timeout(RPC_CHECK_TIMEOUT, provider.is_contract(&address)).await {
+            Ok(Ok(true)) => {}
+            Ok(Ok(false)) => issues.push(issue(
+                key,
+                format!("no contract is deployed at {}", formatter.format(address)),
+            )),
+            Ok(Err(err)) => {
+                // The RPC failing will fail the next checks the same way
+                issues.push(issue("rpc_urls", format!("RPC request failed: {err}")));
+                return issues;
+            }
+            Err(_) => {
+                issues.push(issue(
+                    "rpc_urls",
+                    format!("RPC did not answer within {RPC_CHECK_TIMEOUT:?}"),
+                ));
+                return issues;
+            }
+        }
+    }
+    match timeout(
+        RPC_CHECK_TIMEOUT,
+        chain.code_hash_mismatches(provider.as_ref()),
+    )
+    .await
+    {
+        Ok(Ok(mismatches)) => issues.extend(mismatches.into_iter().map(|mismatch| ConfigIssue {
+            path: (&cwp + "code_hashes" + mismatch.contract).json_name(),
+            message: format!(
+                "code at {} hashes to {:?}, expected {:?}",
+                formatter.format(mismatch.address),
+                mismatch.actual,
+                mismatch.expected
+            ),
+        })),
+        Ok(Err(err)) => issues.push(issue("rpc_urls", format!("RPC request failed: {err}"))),
+        Err(_) => issues.push(issue(
+            "rpc_urls",
+            format!("RPC did not answer within {RPC_CHECK_TIMEOUT:?}"),
+        )),
+    }
+    issues
+}
+
+#[cfg(test)]
+mod test {
+    use eyre::eyre;
+
+    use super::*;
+
+    #[test]
+    fn test_parsing_issues() {
+        let mut err = ConfigParsingError::default();
+        err.push(
+            ConfigPath::default() + "chains" + "ethereum" + "reorg_period",
+            eyre!("Expected a number"),
+        );
+        assert_eq!(
+            parsing_issues(&err),
+            vec![ConfigIssue {
+                path: "chains.ethereum.reorgPeriod".to_owned(),
+                message: "Expected a number".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validity() {
+        let mut err = ConfigParsingError::default();
+        let report = ConfigReport::from_parsing_error("relayer", &err);
+        assert!(report.valid);
+
+        err.push(
+            ConfigPath::default() + "db",
+            eyre!("Expected database path"),
+        );
+        let report = ConfigReport::from_parsing_error("relayer", &err);
+        assert!(!report.valid);
+        assert!(report.to_json().unwrap().contains("\"valid\": false"));
+    }
+}
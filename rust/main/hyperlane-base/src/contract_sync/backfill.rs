@@ -0,0 +1,134 @@
+use std::{collections::HashSet, fmt::Debug, hash::Hash, ops::RangeInclusive};
+
+use futures_util::{stream, StreamExt};
+use hyperlane_core::{HyperlaneBackfillStore, HyperlaneLogStore, Indexer};
+use prometheus::core::{AtomicU64, GenericCounter};
+use tokio::time::sleep;
+use tracing::{debug, info, instrument, warn};
+
+use super::{ContractSync, Indexable, SLEEP_DURATION};
+use crate::settings::BackfillConf;
+
+/// Splits the blocks from `from` to `to`, inclusive, into consecutive ranges
+/// of at most `chunk_size` blocks.
+pub(crate) fn backfill_chunks(from: u32, to: u32, chunk_size: u32) -> Vec<RangeInclusive<u32>> {
+    let chunk_size = chunk_size.max(1);
+    let mut chunks = vec![];
+    let mut start = from;
+    while start <= to {
+        let end = start.saturating_add(chunk_size - 1).min(to);
+        chunks.push(start..=end);
+        if end == u32::MAX {
+            break;
+        }
+        start = end + 1;
+    }
+    chunks
+}
+
+impl<T, D, I> ContractSync<T, D, I>
+where
+    T: Indexable + Debug + Send + Sync + Clone + Eq + Hash + 'static,
+    D: HyperlaneLogStore<T>,
+    I: Indexer<T> + 'static,
+{
+    /// Index the logs of a historical block range, alongside the live
+    /// indexing. The range is indexed in chunks, several at a time, and
+    /// completed chunks are recorded in `progress` so that they are skipped
+    /// after a restart. Changing the chunk size starts the backfill over.
+    #[instrument(name = "ContractSyncBackfill", fields(domain=self.domain().name()), skip(self, progress))]
+    pub async fn backfill(
+        &self,
+        label: &'static str,
+        conf: BackfillConf,
+        progress: &dyn HyperlaneBackfillStore,
+    ) {
+        let stored_logs_metric = self
+            .metrics
+            .stored_events
+            .with_label_values(&[label, self.domain.as_ref()]);
+        let chunks = backfill_chunks(conf.from, conf.to, conf.chunk_size);
+        let num_chunks = chunks.len();
+        info!(?conf, num_chunks, "Starting backfill");
+
+        stream::iter(chunks)
+            .for_each_concurrent(conf.concurrency, |range| {
+                self.backfill_chunk(label, range, progress, &stored_logs_metric)
+            })
+            .await;
+        info!(?conf, num_chunks, "Finished backfill");
+    }
+
+    /// Index a single chunk of a backfill, retrying until it succeeds
+    async fn backfill_chunk(
+        &self,
+        label: &str,
+        range: RangeInclusive<u32>,
+        progress: &dyn HyperlaneBackfillStore,
+        stored_logs_metric: &GenericCounter<AtomicU64>,
+    ) {
+        loop {
+            match progress.is_backfill_range_complete(label, &range).await {
+                Ok(true) => {
+                    debug!(?range, "Backfill chunk already indexed, skipping");
+                    return;
+                }
+                Ok(false) => {}
+                Err(err) => {
+                    warn!(?err, ?range, "Error reading backfill progress");
+                    sleep(SLEEP_DURATION).await;
+                    continue;
+                }
+            }
+            let logs = match self.indexer.fetch_logs_in_range(range.clone()).await {
+                Ok(logs) => logs,
+                Err(err) => {
+                    warn!(?err, ?range, "Error fetching logs in backfill range");
+                    sleep(SLEEP_DURATION).await;
+                    continue;
+                }
+            };
+            let logs = Vec::from_iter(HashSet::<_>::from_iter(logs));
+            let stored = match self.db.store_logs(&logs).await {
+                Ok(stored) => stored,
+                Err(err) => {
+                    warn!(?err, ?range, "Error storing backfilled logs in db");
+                    sleep(SLEEP_DURATION).await;
+                    continue;
+                }
+            };
+            stored_logs_metric.inc_by(stored as u64);
+            if let Err(err) = progress.store_backfill_range_complete(label, &range).await {
+                // The logs are stored, so indexing the chunk again after a
+                // restart only costs the RPC calls
+                warn!(?err, ?range, "Error recording backfill progress");
+            }
+            info!(
+                ?range,
+                num_logs = logs.len(),
+                stored,
+                "Backfilled logs in range"
+            );
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_backfill_chunks() {
+        assert_eq!(
+            backfill_chunks(100, 349, 100),
+            vec![100..=199, 200..=299, 300..=349]
+        );
+        assert_eq!(backfill_chunks(100, 100, 100), vec![100..=100]);
+        assert_eq!(backfill_chunks(101, 100, 100), vec![]);
+        assert_eq!(
+            backfill_chunks(u32::MAX - 1, u32::MAX, 1),
+            vec![u32::MAX - 1..=u32::MAX - 1, u32::MAX..=u32::MAX]
+        );
+    }
+}
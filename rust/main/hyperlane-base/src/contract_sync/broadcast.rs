@@ -26,7 +26,9 @@ impl BroadcastMpscSender<H512> {
     /// Send a message to all the receiving channels.
     // This will block if at least one of the receiving channels is full
     pub async fn send(&self, txid: H512) -> Result<()> {
-        let senders = self.sender.lock().await;
+        let mut senders = self.sender.lock().await;
+        // Receivers are dropped when the task holding them is restarted
+        senders.retain(|sender| !sender.is_closed());
         for sender in &*senders {
             sender.send(txid).await?
         }
@@ -1,7 +1,8 @@
 pub(crate) mod sequence_aware;
 
 use hyperlane_core::{
-    Delivery, HyperlaneDomainProtocol, HyperlaneMessage, InterchainGasPayment, MerkleTreeInsertion,
+    Delivery, GasOracleConfigUpdate, HyperlaneDomainProtocol, HyperlaneMessage,
+    InterchainGasPayment, MerkleTreeInsertion, TransferRemote, ValidatorAnnouncement,
 };
 pub(crate) use sequence_aware::ForwardBackwardSequenceAwareSyncCursor;
 
@@ -30,9 +31,13 @@ impl Indexable for HyperlaneMessage {
     fn indexing_cursor(domain: HyperlaneDomainProtocol) -> CursorType {
         match domain {
             HyperlaneDomainProtocol::Ethereum => CursorType::SequenceAware,
-            HyperlaneDomainProtocol::Fuel => todo!(),
+            HyperlaneDomainProtocol::Fuel => CursorType::SequenceAware,
             HyperlaneDomainProtocol::Sealevel => CursorType::SequenceAware,
             HyperlaneDomainProtocol::Cosmos => CursorType::SequenceAware,
+            HyperlaneDomainProtocol::Starknet => CursorType::SequenceAware,
+            HyperlaneDomainProtocol::Substrate => CursorType::SequenceAware,
+            HyperlaneDomainProtocol::Aptos => CursorType::SequenceAware,
+            HyperlaneDomainProtocol::Ton => CursorType::SequenceAware,
         }
     }
 
@@ -49,6 +54,10 @@ impl Indexable for InterchainGasPayment {
             HyperlaneDomainProtocol::Fuel => todo!(),
             HyperlaneDomainProtocol::Sealevel => CursorType::SequenceAware,
             HyperlaneDomainProtocol::Cosmos => CursorType::RateLimited,
+            HyperlaneDomainProtocol::Starknet => CursorType::RateLimited,
+            HyperlaneDomainProtocol::Substrate => CursorType::RateLimited,
+            HyperlaneDomainProtocol::Aptos => CursorType::RateLimited,
+            HyperlaneDomainProtocol::Ton => CursorType::RateLimited,
         }
     }
 }
@@ -60,6 +69,10 @@ impl Indexable for MerkleTreeInsertion {
             HyperlaneDomainProtocol::Fuel => todo!(),
             HyperlaneDomainProtocol::Sealevel => CursorType::SequenceAware,
             HyperlaneDomainProtocol::Cosmos => CursorType::SequenceAware,
+            HyperlaneDomainProtocol::Starknet => CursorType::SequenceAware,
+            HyperlaneDomainProtocol::Substrate => CursorType::SequenceAware,
+            HyperlaneDomainProtocol::Aptos => CursorType::SequenceAware,
+            HyperlaneDomainProtocol::Ton => CursorType::SequenceAware,
         }
     }
 }
@@ -68,9 +81,34 @@ impl Indexable for Delivery {
     fn indexing_cursor(domain: HyperlaneDomainProtocol) -> CursorType {
         match domain {
             HyperlaneDomainProtocol::Ethereum => CursorType::RateLimited,
-            HyperlaneDomainProtocol::Fuel => todo!(),
+            HyperlaneDomainProtocol::Fuel => CursorType::RateLimited,
             HyperlaneDomainProtocol::Sealevel => CursorType::SequenceAware,
             HyperlaneDomainProtocol::Cosmos => CursorType::RateLimited,
+            HyperlaneDomainProtocol::Starknet => CursorType::RateLimited,
+            HyperlaneDomainProtocol::Substrate => CursorType::RateLimited,
+            HyperlaneDomainProtocol::Aptos => CursorType::SequenceAware,
+            HyperlaneDomainProtocol::Ton => CursorType::SequenceAware,
         }
     }
 }
+
+impl Indexable for ValidatorAnnouncement {
+    // Announcements aren't sequenced on any protocol
+    fn indexing_cursor(_domain: HyperlaneDomainProtocol) -> CursorType {
+        CursorType::RateLimited
+    }
+}
+
+impl Indexable for GasOracleConfigUpdate {
+    // Gas oracle config updates aren't sequenced on any protocol
+    fn indexing_cursor(_domain: HyperlaneDomainProtocol) -> CursorType {
+        CursorType::RateLimited
+    }
+}
+
+impl Indexable for TransferRemote {
+    // The transfers of a router aren't sequenced on any protocol
+    fn indexing_cursor(_domain: HyperlaneDomainProtocol) -> CursorType {
+        CursorType::RateLimited
+    }
+}
@@ -214,6 +214,15 @@ where
             }
         }
     }
+
+    async fn rewind_orphaned(
+        &mut self,
+        _orphaned_logs: &[(Indexed<T>, LogMeta)],
+        from_block: u32,
+    ) -> Result<()> {
+        self.sync_state.next_block = u32::min(self.sync_state.next_block, from_block);
+        Ok(())
+    }
 }
 
 impl<T> Debug for RateLimitedContractSyncCursor<T> {
@@ -229,26 +238,13 @@ impl<T> Debug for RateLimitedContractSyncCursor<T> {
 #[cfg(test)]
 pub(crate) mod test {
     use super::*;
-    use hyperlane_core::{ChainResult, HyperlaneLogStore};
+    use hyperlane_core::{HyperlaneLogStore, SkippedLog};
+    use hyperlane_test::mocks::MockIndexer;
     use mockall::{self, Sequence};
 
     const CHUNK_SIZE: u32 = 10;
     const INITIAL_HEIGHT: u32 = 0;
 
-    mockall::mock! {
-        pub Indexer {}
-
-        impl Debug for Indexer {
-            fn fmt<'a>(&self, f: &mut std::fmt::Formatter<'a>) -> std::fmt::Result;
-        }
-
-        #[async_trait]
-        impl Indexer<()> for Indexer {
-            async fn fetch_logs_in_range(&self, range: RangeInclusive<u32>) -> ChainResult<Vec<(hyperlane_core::Indexed<()> , LogMeta)>>;
-            async fn get_finalized_block_number(&self) -> ChainResult<u32>;
-        }
-    }
-
     mockall::mock! {
         pub Db {}
 
@@ -272,7 +268,7 @@ pub(crate) mod test {
         custom_chain_tips: Option<Vec<u32>>,
     ) -> RateLimitedContractSyncCursor<()> {
         let mut seq = Sequence::new();
-        let mut indexer = MockIndexer::new();
+        let mut indexer = MockIndexer::<()>::new();
         match custom_chain_tips {
             Some(chain_tips) => {
                 for tip in chain_tips {
@@ -333,6 +329,27 @@ pub(crate) mod test {
         assert!(matches!(action_3, CursorAction::Query(_expected_range)));
     }
 
+    #[tokio::test]
+    async fn test_next_action_moves_past_skipped_logs() {
+        let mut cursor = mock_rate_limited_cursor(None).await;
+        let (action, _) = cursor.next_action().await.unwrap();
+        let CursorAction::Query(range) = action else {
+            panic!("Expected Query action");
+        };
+
+        let skipped = [SkippedLog::new(None, LogMeta::default())];
+        cursor
+            .update_with_skipped(vec![], &skipped, range.clone())
+            .await
+            .unwrap();
+
+        let (action, _) = cursor.next_action().await.unwrap();
+        let CursorAction::Query(next_range) = action else {
+            panic!("Expected Query action");
+        };
+        assert_eq!(*next_range.start(), range.end() + 1);
+    }
+
     #[tokio::test]
     async fn test_next_action_sleeps_if_tip_is_not_updated() {
         let chain_tips = vec![10];
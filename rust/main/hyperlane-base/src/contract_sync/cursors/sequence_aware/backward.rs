@@ -5,14 +5,14 @@ use std::{collections::HashSet, fmt::Debug, ops::RangeInclusive, sync::Arc, time
 use async_trait::async_trait;
 use eyre::Result;
 use hyperlane_core::{
-    indexed_to_sequence_indexed_array, ContractSyncCursor, CursorAction,
-    HyperlaneSequenceAwareIndexerStoreReader, IndexMode, Indexed, LogMeta, SequenceIndexed,
+    ContractSyncCursor, CursorAction, HyperlaneSequenceAwareIndexerStoreReader, IndexMode, Indexed,
+    LogMeta, SkippedLog,
 };
 use itertools::Itertools;
 use tokio::time::sleep;
 use tracing::{debug, instrument, warn};
 
-use super::{LastIndexedSnapshot, TargetSnapshot};
+use super::{logged_sequences, LastIndexedSnapshot, TargetSnapshot};
 
 const MAX_BACKWARD_SYNC_BLOCKING_TIME: Duration = Duration::from_secs(5);
 
@@ -182,14 +182,15 @@ impl<T: Debug> BackwardSequenceAwareSyncCursor<T> {
 
     /// Updates the cursor with the logs that were found in the range.
     /// Only used in sequence mode.
-    /// Logs are expected to be sorted by sequence in ascending order and deduplicated.
+    /// Logs are given by their sequence and metadata, and are expected to be sorted by sequence in
+    /// ascending order and deduplicated.
     ///
     /// Behavior:
     /// - Empty logs are allowed, but no gaps are allowed. The logs must build upon the last indexed snapshot.
     /// - If there are any gaps, the cursor rewinds to the last indexed snapshot, and ranges will be retried.
     fn update_block_range(
         &mut self,
-        logs: Vec<(SequenceIndexed<T>, LogMeta)>,
+        logs: Vec<(u32, LogMeta)>,
         all_log_sequences: &HashSet<u32>,
         range: RangeInclusive<u32>,
         current_indexing_snapshot: TargetSnapshot,
@@ -226,7 +227,7 @@ impl<T: Debug> BackwardSequenceAwareSyncCursor<T> {
         if let Some(lowest_sequence_log) = logs.first() {
             // Update the last snapshot.
             self.last_indexed_snapshot = LastIndexedSnapshot {
-                sequence: Some(lowest_sequence_log.0.sequence),
+                sequence: Some(lowest_sequence_log.0),
                 at_block: lowest_sequence_log.1.block_number.try_into()?,
             };
         }
@@ -236,14 +237,15 @@ impl<T: Debug> BackwardSequenceAwareSyncCursor<T> {
 
     /// Updates the cursor with the logs that were found in the range.
     /// Only used in sequence mode.
-    /// Logs are expected to be sorted by sequence in ascending order and deduplicated.
+    /// Logs are given by their sequence and metadata, and are expected to be sorted by sequence in
+    /// ascending order and deduplicated.
     ///
     /// Behavior:
     /// - The sequences of the logs must exactly match the range.
     /// - If there are any gaps, the cursor rewinds and the range will be retried.
     fn update_sequence_range(
         &mut self,
-        logs: Vec<(SequenceIndexed<T>, LogMeta)>,
+        logs: Vec<(u32, LogMeta)>,
         all_log_sequences: &HashSet<u32>,
         range: RangeInclusive<u32>,
         current_indexing_snapshot: TargetSnapshot,
@@ -289,7 +291,7 @@ impl<T: Debug> BackwardSequenceAwareSyncCursor<T> {
 
         // Update the last indexed snapshot.
         self.last_indexed_snapshot = LastIndexedSnapshot {
-            sequence: Some(lowest_sequence_log.0.sequence),
+            sequence: Some(lowest_sequence_log.0),
             at_block: lowest_sequence_log.1.block_number.try_into()?,
         };
         // Position the current snapshot to the previous sequence.
@@ -302,7 +304,7 @@ impl<T: Debug> BackwardSequenceAwareSyncCursor<T> {
     /// and logs the inconsistencies.
     fn rewind_due_to_sequence_gaps(
         &mut self,
-        logs: &Vec<(SequenceIndexed<T>, LogMeta)>,
+        logs: &[(u32, LogMeta)],
         all_log_sequences: &HashSet<u32>,
         expected_sequences: &HashSet<u32>,
         expected_sequence_range: &RangeInclusive<u32>,
@@ -349,6 +351,14 @@ impl<T: Send + Sync + Clone + Debug + 'static> ContractSyncCursor<T>
             .unwrap_or(self.last_indexed_snapshot.at_block)
     }
 
+    async fn update(
+        &mut self,
+        logs: Vec<(Indexed<T>, LogMeta)>,
+        range: RangeInclusive<u32>,
+    ) -> Result<()> {
+        self.update_with_skipped(logs, &[], range).await
+    }
+
     /// Updates the cursor with the logs that were found in the range.
     ///
     /// Inconsistencies in the logs are not considered errors, instead they're handled by rewinding the cursor
@@ -357,11 +367,15 @@ impl<T: Send + Sync + Clone + Debug + 'static> ContractSyncCursor<T>
     /// ## logs
     /// The logs to ingest. If any logs are duplicated or their sequence is higher than the current indexing snapshot,
     /// they are filtered out.
-    #[instrument(err, ret, skip(logs), fields(range=?range, logs=?logs.iter().map(|(log, _)| log.sequence).collect::<Vec<_>>()))]
+    ///
+    /// ## skipped
+    /// The malformed logs of the range. Those whose sequence is known are treated as indexed.
+    #[instrument(err, ret, skip(logs, skipped), fields(range=?range, logs=?logs.iter().map(|(log, _)| log.sequence).collect::<Vec<_>>(), skipped=?skipped.iter().map(|log| log.sequence).collect::<Vec<_>>()))]
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
-    async fn update(
+    async fn update_with_skipped(
         &mut self,
         logs: Vec<(Indexed<T>, LogMeta)>,
+        skipped: &[SkippedLog],
         range: RangeInclusive<u32>,
     ) -> Result<()> {
         let Some(current_indexing_snapshot) = self.current_indexing_snapshot.clone() else {
@@ -371,15 +385,11 @@ impl<T: Send + Sync + Clone + Debug + 'static> ContractSyncCursor<T>
 
         // Remove any duplicates, filter out any logs with a higher sequence than our
         // current snapshot, and sort in ascending order.
-        let logs = indexed_to_sequence_indexed_array(logs)?
-            .into_iter()
-            .unique_by(|(log, _)| log.sequence)
-            .filter(|(log, _)| log.sequence <= current_indexing_snapshot.sequence)
-            .sorted_by_key(|(log, _)| log.sequence)
-            .collect::<Vec<_>>();
+        let mut logs = logged_sequences(logs, skipped)?;
+        logs.retain(|(sequence, _)| *sequence <= current_indexing_snapshot.sequence);
         let all_log_sequences = logs
             .iter()
-            .map(|(log, _)| log.sequence)
+            .map(|(sequence, _)| *sequence)
             .collect::<HashSet<_>>();
 
         match &self.index_mode {
@@ -509,6 +519,45 @@ mod test {
             );
         }
 
+        #[tracing_test::traced_test]
+        #[tokio::test]
+        async fn test_moves_past_skipped_logs() {
+            let mut cursor = get_cursor().await;
+
+            let range = cursor.get_next_range().await.unwrap().unwrap();
+            let expected_range = 900..=1000;
+            assert_eq!(range, expected_range);
+
+            // Sequence 97 is malformed, and is the earliest log of the range.
+            cursor
+                .update_with_skipped(
+                    vec![
+                        (MockSequencedData::new(98).into(), log_meta_with_block(980)),
+                        (MockSequencedData::new(99).into(), log_meta_with_block(990)),
+                    ],
+                    &[SkippedLog::new(Some(97), log_meta_with_block(970))],
+                    expected_range,
+                )
+                .await
+                .unwrap();
+
+            // Expect the cursor to have moved past the skipped log.
+            assert_eq!(
+                cursor.current_indexing_snapshot,
+                Some(TargetSnapshot {
+                    sequence: 96,
+                    at_block: 900,
+                })
+            );
+            assert_eq!(
+                cursor.last_indexed_snapshot,
+                LastIndexedSnapshot {
+                    sequence: Some(97),
+                    at_block: 970,
+                }
+            );
+        }
+
         #[tracing_test::traced_test]
         #[tokio::test]
         async fn test_multiple_ranges() {
@@ -889,6 +938,48 @@ mod test {
             );
         }
 
+        #[tracing_test::traced_test]
+        #[tokio::test]
+        async fn test_moves_past_skipped_logs() {
+            let mut cursor = get_cursor().await;
+
+            let range = cursor.get_next_range().await.unwrap().unwrap();
+            let expected_range = 94..=99;
+            assert_eq!(range, expected_range);
+
+            // Sequence 96 is malformed.
+            cursor
+                .update_with_skipped(
+                    vec![
+                        (MockSequencedData::new(94).into(), log_meta_with_block(940)),
+                        (MockSequencedData::new(95).into(), log_meta_with_block(950)),
+                        (MockSequencedData::new(97).into(), log_meta_with_block(970)),
+                        (MockSequencedData::new(98).into(), log_meta_with_block(980)),
+                        (MockSequencedData::new(99).into(), log_meta_with_block(990)),
+                    ],
+                    &[SkippedLog::new(Some(96), log_meta_with_block(960))],
+                    expected_range,
+                )
+                .await
+                .unwrap();
+
+            // Expect the cursor to have moved past the skipped log.
+            assert_eq!(
+                cursor.current_indexing_snapshot,
+                Some(TargetSnapshot {
+                    sequence: 93,
+                    at_block: 940,
+                })
+            );
+            assert_eq!(
+                cursor.last_indexed_snapshot,
+                LastIndexedSnapshot {
+                    sequence: Some(94),
+                    at_block: 940,
+                }
+            );
+        }
+
         #[tracing_test::traced_test]
         #[tokio::test]
         async fn test_rewinds_if_gap_or_unexpected_logs() {
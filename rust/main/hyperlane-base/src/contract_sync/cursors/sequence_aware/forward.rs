@@ -8,14 +8,17 @@ use std::{
 use async_trait::async_trait;
 use eyre::Result;
 use hyperlane_core::{
-    indexed_to_sequence_indexed_array, ContractSyncCursor, CursorAction,
-    HyperlaneSequenceAwareIndexerStoreReader, IndexMode, Indexed, LogMeta, SequenceAwareIndexer,
-    SequenceIndexed,
+    ContractSyncCursor, CursorAction, HyperlaneSequenceAwareIndexerStoreReader, IndexMode, Indexed,
+    LogMeta, SequenceAwareIndexer, SkippedLog,
 };
 use itertools::Itertools;
 use tracing::{debug, instrument, warn};
 
-use super::{LastIndexedSnapshot, TargetSnapshot};
+use super::{logged_sequences, LastIndexedSnapshot, TargetSnapshot};
+
+/// How many chunks behind the tip a cursor in block mode has to be for it to try
+/// fast forwarding past the blocks without new logs.
+const FAST_FORWARD_MIN_CHUNKS: u32 = 10;
 
 /// A sequence-aware cursor that syncs forwards in perpetuity.
 pub(crate) struct ForwardSequenceAwareSyncCursor<T> {
@@ -133,7 +136,10 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
                 });
 
                 match &self.index_mode {
-                    IndexMode::Block => self.get_next_block_range(tip),
+                    IndexMode::Block => {
+                        self.fast_forward(current_sequence, tip).await;
+                        self.get_next_block_range(tip)
+                    }
                     IndexMode::Sequence => {
                         Some(self.get_next_sequence_range(current_sequence, target_sequence))
                     }
@@ -180,6 +186,74 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
         )
     }
 
+    /// Moves the current indexing snapshot up to the log of `current_sequence` when it's far
+    /// behind the tip, rather than scanning chunk by chunk through ranges without logs.
+    /// Strides forward exponentially until the onchain sequence count passes
+    /// `current_sequence`, then bisects the last stride down to a chunk.
+    /// Does nothing if the sequence count can't be queried at past blocks.
+    /// Only used in block mode.
+    async fn fast_forward(&mut self, current_sequence: u32, tip: u32) {
+        let from = self.current_indexing_snapshot.at_block;
+        let chunk_size = self.chunk_size.max(1);
+        if tip.saturating_sub(from) < chunk_size.saturating_mul(FAST_FORWARD_MIN_CHUNKS) {
+            return;
+        }
+
+        // The last block known to be before the log, and the first known to be at or after it.
+        let mut before = from;
+        if self.is_sequence_logged_by(current_sequence, from).await != Some(false) {
+            return;
+        }
+        let mut stride = chunk_size;
+        let mut after = loop {
+            let block = before.saturating_add(stride).min(tip);
+            match self.is_sequence_logged_by(current_sequence, block).await {
+                Some(true) => break block,
+                Some(false) if block < tip => {
+                    before = block;
+                    stride = stride.saturating_mul(2);
+                }
+                // The count at the tip passes the sequence, unless providers are inconsistent.
+                _ => return,
+            }
+        };
+        while after - before > chunk_size {
+            let block = before + (after - before) / 2;
+            match self.is_sequence_logged_by(current_sequence, block).await {
+                Some(true) => after = block,
+                Some(false) => before = block,
+                None => break,
+            }
+        }
+
+        if before > from {
+            debug!(
+                current_sequence,
+                from,
+                to = before,
+                tip,
+                "Fast forwarding past blocks without new logs"
+            );
+            self.current_indexing_snapshot.at_block = before;
+        }
+    }
+
+    /// Whether the log of `sequence` is at or before `block`, or `None` if the sequence
+    /// count can't be queried at `block`.
+    async fn is_sequence_logged_by(&self, sequence: u32, block: u32) -> Option<bool> {
+        match self
+            .latest_sequence_querier
+            .sequence_count_at_block(block)
+            .await
+        {
+            Ok(count) => count.map(|count| count > sequence),
+            Err(err) => {
+                debug!(?err, block, "Failed to query the sequence count at block");
+                None
+            }
+        }
+    }
+
     /// Gets the next sequence range to index.
     /// Only used in sequence mode.
     fn get_next_sequence_range(
@@ -237,7 +311,8 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
 
     /// Updates the cursor with the logs that were found in the range.
     /// Only used in block mode.
-    /// Logs are expected to be sorted by sequence in ascending order and deduplicated.
+    /// Logs are given by their sequence and metadata, and are expected to be sorted by sequence in
+    /// ascending order and deduplicated.
     ///
     /// Behavior:
     /// - Empty logs are allowed, but no gaps are allowed. The logs must build upon the last indexed snapshot.
@@ -245,7 +320,7 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
     /// - If the target block is reached and the target sequence hasn't been reached, the cursor rewinds to the last indexed snapshot.
     fn update_block_range(
         &mut self,
-        logs: Vec<(SequenceIndexed<T>, LogMeta)>,
+        logs: Vec<(u32, LogMeta)>,
         all_log_sequences: &HashSet<u32>,
         range: RangeInclusive<u32>,
     ) -> Result<()> {
@@ -270,7 +345,7 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
         if let Some(highest_sequence_log) = logs.last() {
             // Update the last indexed snapshot.
             self.last_indexed_snapshot = LastIndexedSnapshot {
-                sequence: Some(highest_sequence_log.0.sequence),
+                sequence: Some(highest_sequence_log.0),
                 at_block: highest_sequence_log.1.block_number.try_into()?,
             };
         }
@@ -311,14 +386,15 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
 
     /// Updates the cursor with the logs that were found in the range.
     /// Only used in sequence mode.
-    /// Logs are expected to be sorted by sequence in ascending order and deduplicated.
+    /// Logs are given by their sequence and metadata, and are expected to be sorted by sequence in
+    /// ascending order and deduplicated.
     ///
     /// Behavior:
     /// - The sequences of the logs must exactly match the range.
     /// - If there are any gaps, the cursor rewinds and the range will be retried.
     fn update_sequence_range(
         &mut self,
-        logs: Vec<(SequenceIndexed<T>, LogMeta)>,
+        logs: Vec<(u32, LogMeta)>,
         all_log_sequences: &HashSet<u32>,
         range: RangeInclusive<u32>,
     ) -> Result<()> {
@@ -363,7 +439,7 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
 
         // Update the last indexed snapshot.
         self.last_indexed_snapshot = LastIndexedSnapshot {
-            sequence: Some(highest_sequence_log.0.sequence),
+            sequence: Some(highest_sequence_log.0),
             at_block: highest_sequence_log.1.block_number.try_into()?,
         };
         // Position the current snapshot to the next sequence.
@@ -376,7 +452,7 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
     /// and logs the inconsistencies due to sequence gaps.
     fn rewind_due_to_sequence_gaps(
         &mut self,
-        logs: &Vec<(SequenceIndexed<T>, LogMeta)>,
+        logs: &[(u32, LogMeta)],
         all_log_sequences: &HashSet<u32>,
         expected_sequences: &HashSet<u32>,
         expected_sequence_range: &RangeInclusive<u32>,
@@ -397,6 +473,27 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
         self.rewind();
     }
 
+    /// Rewinds the cursor to index again from `orphaned_sequence`, whose log
+    /// was reorged out, searching from `from_block` on.
+    pub fn rewind_orphaned(&mut self, orphaned_sequence: u32, from_block: u32) {
+        if orphaned_sequence >= self.current_indexing_snapshot.sequence {
+            return;
+        }
+        warn!(
+            orphaned_sequence,
+            from_block,
+            current_indexing_snapshot=?self.current_indexing_snapshot,
+            last_indexed_snapshot=?self.last_indexed_snapshot,
+            "Rewinding cursor to re-index logs orphaned by a reorg",
+        );
+        self.last_indexed_snapshot = LastIndexedSnapshot {
+            sequence: orphaned_sequence.checked_sub(1),
+            at_block: u32::min(self.last_indexed_snapshot.at_block, from_block),
+        };
+        self.target_snapshot = None;
+        self.rewind();
+    }
+
     // Rewinds the cursor to target immediately after the last indexed snapshot.
     fn rewind(&mut self) {
         self.current_indexing_snapshot = self.last_indexed_snapshot.next_target();
@@ -423,6 +520,14 @@ impl<T: Send + Sync + Clone + Debug + 'static> ContractSyncCursor<T>
         self.current_indexing_snapshot.at_block
     }
 
+    async fn update(
+        &mut self,
+        logs: Vec<(Indexed<T>, LogMeta)>,
+        range: RangeInclusive<u32>,
+    ) -> Result<()> {
+        self.update_with_skipped(logs, &[], range).await
+    }
+
     /// Updates the cursor with the logs that were found in the range.
     ///
     /// Inconsistencies in the logs are not considered errors, instead they're handled by rewinding the cursor
@@ -433,29 +538,29 @@ impl<T: Send + Sync + Clone + Debug + 'static> ContractSyncCursor<T>
     /// they are filtered out. See `update_sequence_range` and `update_block_range` for more details based
     /// off the indexing mode.
     ///
+    /// ## skipped
+    /// The malformed logs of the range. Those whose sequence is known are treated as indexed.
+    ///
     /// Note:
     /// - Even if the logs include a gap, in practice these logs will have already been inserted into the DB.
     ///   This means that while gaps result in a rewind here, already known logs may be "fast forwarded" through,
     ///   and the cursor won't actually end up re-indexing already known logs.
-    #[instrument(err, ret, skip(logs), fields(range=?range, logs=?logs.iter().map(|(log, _)| log.sequence).collect::<Vec<_>>()))]
+    #[instrument(err, ret, skip(logs, skipped), fields(range=?range, logs=?logs.iter().map(|(log, _)| log.sequence).collect::<Vec<_>>(), skipped=?skipped.iter().map(|log| log.sequence).collect::<Vec<_>>()))]
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
-    async fn update(
+    async fn update_with_skipped(
         &mut self,
         logs: Vec<(Indexed<T>, LogMeta)>,
+        skipped: &[SkippedLog],
         range: RangeInclusive<u32>,
     ) -> Result<()> {
         // Remove any sequence duplicates, filter out any logs preceding our current snapshot,
         // and sort in ascending order.
-        let logs = indexed_to_sequence_indexed_array(logs)?
-            .into_iter()
-            .unique_by(|(log, _)| log.sequence)
-            .filter(|(log, _)| log.sequence >= self.current_indexing_snapshot.sequence)
-            .sorted_by(|(log_a, _), (log_b, _)| log_a.sequence.cmp(&log_b.sequence))
-            .collect::<Vec<_>>();
+        let mut logs = logged_sequences(logs, skipped)?;
+        logs.retain(|(sequence, _)| *sequence >= self.current_indexing_snapshot.sequence);
 
         let all_log_sequences = logs
             .iter()
-            .map(|(log, _)| log.sequence)
+            .map(|(sequence, _)| *sequence)
             .collect::<HashSet<_>>();
 
         match &self.index_mode {
@@ -470,42 +575,10 @@ impl<T: Send + Sync + Clone + Debug + 'static> ContractSyncCursor<T>
 pub(crate) mod test {
     use derive_new::new;
     use hyperlane_core::{ChainResult, HyperlaneLogStore, Indexed, Indexer, Sequenced};
+    pub use hyperlane_test::mocks::MockLatestSequenceQuerier;
 
     use super::*;
 
-    #[derive(Debug, Clone)]
-    pub struct MockLatestSequenceQuerier {
-        pub latest_sequence_count: Option<u32>,
-        pub tip: u32,
-    }
-
-    #[async_trait]
-    impl<T> SequenceAwareIndexer<T> for MockLatestSequenceQuerier
-    where
-        T: Sequenced + Debug,
-    {
-        async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
-            Ok((self.latest_sequence_count, self.tip))
-        }
-    }
-
-    #[async_trait]
-    impl<T> Indexer<T> for MockLatestSequenceQuerier
-    where
-        T: Sequenced + Debug,
-    {
-        async fn fetch_logs_in_range(
-            &self,
-            _range: RangeInclusive<u32>,
-        ) -> ChainResult<Vec<(Indexed<T>, LogMeta)>> {
-            Ok(vec![])
-        }
-
-        async fn get_finalized_block_number(&self) -> ChainResult<u32> {
-            Ok(self.tip)
-        }
-    }
-
     #[derive(Debug, Clone)]
     pub struct MockHyperlaneSequenceAwareIndexerStore<T> {
         pub logs: Vec<(T, LogMeta)>,
@@ -571,6 +644,45 @@ pub(crate) mod test {
         }
     }
 
+    /// Knows the sequence count at every block, from the blocks of its logs.
+    #[derive(Debug, Clone)]
+    pub struct MockHistoricalSequenceQuerier {
+        /// The block of the log of each sequence
+        pub log_blocks: Vec<u32>,
+        pub tip: u32,
+    }
+
+    impl MockHistoricalSequenceQuerier {
+        fn count_at(&self, block: u32) -> u32 {
+            self.log_blocks.iter().filter(|b| **b <= block).count() as u32
+        }
+    }
+
+    #[async_trait]
+    impl Indexer<MockSequencedData> for MockHistoricalSequenceQuerier {
+        async fn fetch_logs_in_range(
+            &self,
+            _range: RangeInclusive<u32>,
+        ) -> ChainResult<Vec<(Indexed<MockSequencedData>, LogMeta)>> {
+            Ok(vec![])
+        }
+
+        async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+            Ok(self.tip)
+        }
+    }
+
+    #[async_trait]
+    impl SequenceAwareIndexer<MockSequencedData> for MockHistoricalSequenceQuerier {
+        async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+            Ok((Some(self.count_at(self.tip)), self.tip))
+        }
+
+        async fn sequence_count_at_block(&self, block: u32) -> ChainResult<Option<u32>> {
+            Ok(Some(self.count_at(block)))
+        }
+    }
+
     const INITIAL_CURRENT_INDEXING_SNAPSHOT: TargetSnapshot = TargetSnapshot {
         sequence: 5,
         at_block: 90,
@@ -791,6 +903,57 @@ pub(crate) mod test {
             assert_eq!(range, None);
         }
 
+        /// Tests when the cursor is millions of blocks behind the tip, and skips the ranges
+        /// without logs by querying the sequence count at past blocks.
+        #[tracing_test::traced_test]
+        #[tokio::test]
+        async fn test_fast_forwards_past_blocks_without_logs() {
+            let mut cursor = get_cursor().await;
+
+            // Pretend like the tip is 2,000,000, and a message occurred at block 1,000,000.
+            cursor.latest_sequence_querier = Arc::new(MockHistoricalSequenceQuerier {
+                log_blocks: vec![50, 60, 70, 80, 90, 1_000_000],
+                tip: 2_000_000,
+            });
+
+            // Expect the range to be the chunk with the log in it.
+            let range = cursor.get_next_range().await.unwrap().unwrap();
+            assert!(range.contains(&1_000_000));
+            assert!(*range.start() >= 1_000_000 - CHUNK_SIZE);
+
+            // Update the cursor with the found log.
+            cursor
+                .update(
+                    vec![(
+                        Indexed::new(MockSequencedData::new(5)).with_sequence(5),
+                        log_meta_with_block(1_000_000),
+                    )],
+                    range.clone(),
+                )
+                .await
+                .unwrap();
+
+            // Expect the cursor to have moved to the next sequence and updated the last indexed snapshot.
+            assert_eq!(
+                cursor.current_indexing_snapshot,
+                TargetSnapshot {
+                    sequence: 6,
+                    at_block: *range.end(),
+                }
+            );
+            assert_eq!(
+                cursor.last_indexed_snapshot,
+                LastIndexedSnapshot {
+                    sequence: Some(5),
+                    at_block: 1_000_000,
+                }
+            );
+
+            // And now we should get no range to index.
+            let range = cursor.get_next_range().await.unwrap();
+            assert_eq!(range, None);
+        }
+
         /// Tests when the cursor is so behind the tip that it'll need to index multiple ranges, but by the time
         /// it gets to the target snapshot, it realizes it missed a log and needs to rewind.
         #[tracing_test::traced_test]
@@ -948,6 +1111,73 @@ pub(crate) mod test {
             .await;
         }
 
+        /// Tests that skipped malformed logs count as indexed, as long as their sequence is known.
+        #[tracing_test::traced_test]
+        #[tokio::test]
+        async fn test_moves_past_skipped_logs() {
+            let mut cursor = get_cursor().await;
+
+            cursor.latest_sequence_querier = Arc::new(MockLatestSequenceQuerier {
+                latest_sequence_count: Some(8),
+                tip: 200,
+            });
+
+            let range = cursor.get_next_range().await.unwrap().unwrap();
+            let expected_range = 90..=190;
+            assert_eq!(range, expected_range);
+
+            let logs = vec![
+                (MockSequencedData::new(5).into(), log_meta_with_block(95)),
+                (MockSequencedData::new(7).into(), log_meta_with_block(105)),
+            ];
+
+            // Sequence 6 is malformed, but without its sequence there's still a gap to rewind for.
+            cursor
+                .update_with_skipped(
+                    logs.clone(),
+                    &[SkippedLog::new(None, log_meta_with_block(100))],
+                    expected_range.clone(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                cursor.current_indexing_snapshot,
+                TargetSnapshot {
+                    sequence: 5,
+                    at_block: 90,
+                }
+            );
+
+            // With its sequence, the cursor moves past it.
+            let range = cursor.get_next_range().await.unwrap().unwrap();
+            assert_eq!(range, expected_range);
+            cursor
+                .update_with_skipped(
+                    logs,
+                    &[SkippedLog::new(Some(6), log_meta_with_block(100))],
+                    expected_range,
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                cursor.current_indexing_snapshot,
+                TargetSnapshot {
+                    sequence: 8,
+                    at_block: 190,
+                }
+            );
+            assert_eq!(
+                cursor.last_indexed_snapshot,
+                LastIndexedSnapshot {
+                    sequence: Some(7),
+                    at_block: 105,
+                }
+            );
+
+            let range = cursor.get_next_range().await.unwrap();
+            assert_eq!(range, None);
+        }
+
         /// Tests when the cursor is so behind the tip that it'll need to index multiple ranges, but by the time
         /// it gets to the target snapshot, it realizes it missed a log and needs to rewind.
         #[tracing_test::traced_test]
@@ -1139,6 +1369,53 @@ pub(crate) mod test {
             );
         }
 
+        /// Tests that a skipped malformed log counts as indexed, even as the last of the range.
+        #[tracing_test::traced_test]
+        #[tokio::test]
+        async fn test_moves_past_skipped_logs() {
+            let mut cursor = get_cursor().await;
+
+            cursor.latest_sequence_querier = Arc::new(MockLatestSequenceQuerier {
+                latest_sequence_count: Some(8),
+                tip: 120,
+            });
+
+            let range = cursor.get_next_range().await.unwrap().unwrap();
+            let expected_range = 5..=7;
+            assert_eq!(range, expected_range);
+
+            // Sequence 7 is malformed.
+            cursor
+                .update_with_skipped(
+                    vec![
+                        (MockSequencedData::new(5).into(), log_meta_with_block(115)),
+                        (MockSequencedData::new(6).into(), log_meta_with_block(115)),
+                    ],
+                    &[SkippedLog::new(Some(7), log_meta_with_block(120))],
+                    expected_range,
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(
+                cursor.current_indexing_snapshot,
+                TargetSnapshot {
+                    sequence: 8,
+                    at_block: 120,
+                }
+            );
+            assert_eq!(
+                cursor.last_indexed_snapshot,
+                LastIndexedSnapshot {
+                    sequence: Some(7),
+                    at_block: 120,
+                }
+            );
+
+            let range = cursor.get_next_range().await.unwrap();
+            assert_eq!(range, None);
+        }
+
         /// Tests getting a gap in the expected logs
         #[tracing_test::traced_test]
         #[tokio::test]
@@ -3,13 +3,17 @@ use std::{fmt::Debug, sync::Arc, time::Duration};
 use async_trait::async_trait;
 use eyre::Result;
 use hyperlane_core::{
-    ChainCommunicationError, ContractSyncCursor, CursorAction,
+    indexed_to_sequence_indexed_array, ChainCommunicationError, ContractSyncCursor, CursorAction,
     HyperlaneSequenceAwareIndexerStoreReader, IndexMode, Indexed, LogMeta, SequenceAwareIndexer,
+    SkippedLog,
 };
+use itertools::Itertools;
 use std::ops::RangeInclusive;
 
 mod backward;
 mod forward;
+#[cfg(test)]
+mod simulation;
 
 pub(crate) use backward::BackwardSequenceAwareSyncCursor;
 pub(crate) use forward::ForwardSequenceAwareSyncCursor;
@@ -54,6 +58,25 @@ pub(crate) struct TargetSnapshot {
     pub at_block: u32,
 }
 
+/// Returns the sequences and metadata of `logs` and of the `skipped` logs whose
+/// sequence is known, deduplicated and sorted by sequence in ascending order.
+/// Querying skipped logs again won't fix them, so they count as indexed.
+fn logged_sequences<T>(
+    logs: Vec<(Indexed<T>, LogMeta)>,
+    skipped: &[SkippedLog],
+) -> Result<Vec<(u32, LogMeta)>> {
+    let skipped = skipped
+        .iter()
+        .filter_map(|log| Some((log.sequence?, log.meta.clone())));
+    Ok(indexed_to_sequence_indexed_array(logs)?
+        .into_iter()
+        .map(|(log, meta)| (log.sequence, meta))
+        .chain(skipped)
+        .unique_by(|(sequence, _)| *sequence)
+        .sorted_by_key(|(sequence, _)| *sequence)
+        .collect())
+}
+
 #[derive(Debug)]
 pub enum SyncDirection {
     Forward,
@@ -63,7 +86,7 @@ pub enum SyncDirection {
 /// A cursor that prefers to sync forward, but will sync backward if there is nothing to
 /// sync forward.
 #[derive(Debug)]
-pub(crate) struct ForwardBackwardSequenceAwareSyncCursor<T> {
+pub struct ForwardBackwardSequenceAwareSyncCursor<T> {
     forward: ForwardSequenceAwareSyncCursor<T>,
     backward: BackwardSequenceAwareSyncCursor<T>,
     last_direction: SyncDirection,
@@ -130,10 +153,39 @@ impl<T: Send + Sync + Clone + Debug + 'static> ContractSyncCursor<T>
         &mut self,
         logs: Vec<(Indexed<T>, LogMeta)>,
         range: RangeInclusive<u32>,
+    ) -> Result<()> {
+        self.update_with_skipped(logs, &[], range).await
+    }
+
+    async fn update_with_skipped(
+        &mut self,
+        logs: Vec<(Indexed<T>, LogMeta)>,
+        skipped: &[SkippedLog],
+        range: RangeInclusive<u32>,
     ) -> Result<()> {
         match self.last_direction {
-            SyncDirection::Forward => self.forward.update(logs, range).await,
-            SyncDirection::Backward => self.backward.update(logs, range).await,
+            SyncDirection::Forward => self.forward.update_with_skipped(logs, skipped, range).await,
+            SyncDirection::Backward => {
+                self.backward
+                    .update_with_skipped(logs, skipped, range)
+                    .await
+            }
+        }
+    }
+
+    async fn rewind_orphaned(
+        &mut self,
+        orphaned_logs: &[(Indexed<T>, LogMeta)],
+        from_block: u32,
+    ) -> Result<()> {
+        // Orphaned logs are recent, so only the forward cursor can have indexed them.
+        if let Some(sequence) = orphaned_logs
+            .iter()
+            .filter_map(|(log, _)| log.sequence)
+            .min()
+        {
+            self.forward.rewind_orphaned(sequence, from_block);
         }
+        Ok(())
     }
 }
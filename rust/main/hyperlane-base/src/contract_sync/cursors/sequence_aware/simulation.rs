@@ -0,0 +1,431 @@
+//! Deterministic simulation of the sequence aware cursors against scripted
+//! chain histories.
+//!
+//! The simulated chain only changes when the script says so, and the sleeps
+//! the cursor asks for advance a virtual clock instead of waiting, so every
+//! run of a scenario queries the same ranges in the same order. Scenarios
+//! combine gaps in RPC responses, reorgs and bursts of logs, and check that
+//! the cursor ends up having indexed every log at its canonical block.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::RangeInclusive,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use eyre::Result;
+use hyperlane_core::{
+    ChainResult, ContractSyncCursor, CursorAction, HyperlaneLogStore,
+    HyperlaneSequenceAwareIndexerStoreReader, IndexMode, Indexed, Indexer, LogMeta,
+    SequenceAwareIndexer,
+};
+
+use super::forward::test::{log_meta_with_block, MockSequencedData};
+use super::ForwardBackwardSequenceAwareSyncCursor;
+
+/// Number of steps after which a scenario that hasn't converged fails
+const MAX_STEPS: u32 = 10_000;
+
+/// A change to the simulated chain
+#[derive(Debug, Clone, Copy)]
+enum ChainEvent {
+    /// Mine `blocks` blocks without logs
+    Mine { blocks: u32 },
+    /// Mine `blocks` blocks with one log each
+    Dispatch { blocks: u32 },
+    /// Mine a block with `count` logs
+    Burst { count: u32 },
+    /// The RPC omits the log with `sequence` from the next `queries` log
+    /// queries which should have returned it
+    Gap { sequence: u32, queries: u32 },
+    /// The last `depth` blocks are reorged out, and the logs they contained
+    /// are included again in a new block
+    Reorg { depth: u32 },
+}
+
+#[derive(Debug, Default)]
+struct ChainState {
+    tip: u32,
+    /// Block of each log, indexed by sequence
+    log_blocks: Vec<u32>,
+    /// Number of queries each log is still omitted from, by sequence
+    gaps: HashMap<u32, u32>,
+}
+
+/// A chain whose history is scripted, queried by the cursor through its
+/// indexer
+#[derive(Debug)]
+struct SimulatedChain {
+    mode: IndexMode,
+    state: Mutex<ChainState>,
+}
+
+impl SimulatedChain {
+    fn new(mode: IndexMode) -> Self {
+        Self {
+            mode,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Applies `event`, returning the block range orphaned by a reorg
+    fn apply(&self, event: ChainEvent) -> Option<RangeInclusive<u32>> {
+        let mut state = self.state.lock().unwrap();
+        match event {
+            ChainEvent::Mine { blocks } => state.tip += blocks,
+            ChainEvent::Dispatch { blocks } => {
+                for _ in 0..blocks {
+                    state.tip += 1;
+                    let tip = state.tip;
+                    state.log_blocks.push(tip);
+                }
+            }
+            ChainEvent::Burst { count } => {
+                state.tip += 1;
+                let tip = state.tip;
+                state
+                    .log_blocks
+                    .extend(std::iter::repeat(tip).take(count as usize));
+            }
+            ChainEvent::Gap { sequence, queries } => {
+                state.gaps.insert(sequence, queries);
+            }
+            ChainEvent::Reorg { depth } => {
+                let orphaned = state.tip.saturating_sub(depth) + 1..=state.tip;
+                // The new chain is one block longer, and includes the
+                // orphaned logs in its tip block
+                state.tip += 1;
+                let tip = state.tip;
+                for block in state.log_blocks.iter_mut() {
+                    if orphaned.contains(block) {
+                        *block = tip;
+                    }
+                }
+                return Some(orphaned);
+            }
+        }
+        None
+    }
+
+    fn log_blocks(&self) -> Vec<u32> {
+        self.state.lock().unwrap().log_blocks.clone()
+    }
+}
+
+#[async_trait]
+impl Indexer<MockSequencedData> for SimulatedChain {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<MockSequencedData>, LogMeta)>> {
+        let mut state = self.state.lock().unwrap();
+        let ChainState {
+            log_blocks, gaps, ..
+        } = &mut *state;
+        let mut logs = vec![];
+        for (sequence, block) in log_blocks.iter().enumerate() {
+            let sequence = sequence as u32;
+            let in_range = match self.mode {
+                IndexMode::Block => range.contains(block),
+                IndexMode::Sequence => range.contains(&sequence),
+            };
+            if !in_range {
+                continue;
+            }
+            if let Some(queries) = gaps.get_mut(&sequence).filter(|queries| **queries > 0) {
+                *queries -= 1;
+                continue;
+            }
+            logs.push((
+                MockSequencedData::new(sequence).into(),
+                log_meta_with_block((*block).into()),
+            ));
+        }
+        Ok(logs)
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        Ok(self.state.lock().unwrap().tip)
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<MockSequencedData> for SimulatedChain {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let state = self.state.lock().unwrap();
+        Ok((Some(state.log_blocks.len() as u32), state.tip))
+    }
+}
+
+/// The indexed logs, by sequence
+#[derive(Debug, Default)]
+struct SimulatedStore {
+    logs: Mutex<BTreeMap<u32, LogMeta>>,
+}
+
+impl SimulatedStore {
+    /// Removes the logs indexed at blocks in `range`, returning them
+    fn invalidate_blocks(
+        &self,
+        range: &RangeInclusive<u32>,
+    ) -> Vec<(Indexed<MockSequencedData>, LogMeta)> {
+        let mut logs = self.logs.lock().unwrap();
+        let orphaned = logs
+            .iter()
+            .filter(|(_, meta)| range.contains(&(meta.block_number as u32)))
+            .map(|(sequence, meta)| (MockSequencedData::new(*sequence).into(), meta.clone()))
+            .collect::<Vec<_>>();
+        logs.retain(|_, meta| !range.contains(&(meta.block_number as u32)));
+        orphaned
+    }
+
+    /// Block of each indexed log, by sequence
+    fn log_blocks(&self) -> BTreeMap<u32, u32> {
+        self.logs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(sequence, meta)| (*sequence, meta.block_number as u32))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl HyperlaneLogStore<MockSequencedData> for SimulatedStore {
+    async fn store_logs(&self, logs: &[(Indexed<MockSequencedData>, LogMeta)]) -> Result<u32> {
+        let mut stored = self.logs.lock().unwrap();
+        for (log, meta) in logs {
+            stored.insert(log.inner().sequence, meta.clone());
+        }
+        Ok(logs.len() as u32)
+    }
+}
+
+#[async_trait]
+impl HyperlaneSequenceAwareIndexerStoreReader<MockSequencedData> for SimulatedStore {
+    async fn retrieve_by_sequence(&self, sequence: u32) -> Result<Option<MockSequencedData>> {
+        Ok(self
+            .logs
+            .lock()
+            .unwrap()
+            .contains_key(&sequence)
+            .then(|| MockSequencedData::new(sequence)))
+    }
+
+    async fn retrieve_log_block_number_by_sequence(&self, sequence: u32) -> Result<Option<u64>> {
+        Ok(self
+            .logs
+            .lock()
+            .unwrap()
+            .get(&sequence)
+            .map(|meta| meta.block_number))
+    }
+}
+
+/// Drives a cursor the way the contract sync does, against a simulated chain
+struct Simulation {
+    chain: Arc<SimulatedChain>,
+    store: Arc<SimulatedStore>,
+    cursor: ForwardBackwardSequenceAwareSyncCursor<MockSequencedData>,
+    /// Time the cursor spent sleeping
+    elapsed: Duration,
+}
+
+impl Simulation {
+    /// Starts a cursor on a chain which already has the `history`
+    async fn new(mode: IndexMode, chunk_size: u32, history: &[ChainEvent]) -> Self {
+        let chain = Arc::new(SimulatedChain::new(mode));
+        for event in history {
+            chain.apply(*event);
+        }
+        let store = Arc::new(SimulatedStore::default());
+        let cursor = ForwardBackwardSequenceAwareSyncCursor::new(
+            chain.clone(),
+            store.clone(),
+            chunk_size,
+            mode,
+        )
+        .await
+        .unwrap();
+        Self {
+            chain,
+            store,
+            cursor,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    async fn apply(&mut self, event: ChainEvent) {
+        if let Some(orphaned_blocks) = self.chain.apply(event) {
+            // The contract sync notices the orphaned blocks from their hashes
+            let orphaned_logs = self.store.invalidate_blocks(&orphaned_blocks);
+            self.cursor
+                .rewind_orphaned(&orphaned_logs, *orphaned_blocks.start())
+                .await
+                .unwrap();
+        }
+    }
+
+    /// Runs one iteration of the contract sync loop
+    async fn step(&mut self) {
+        let (action, _) = self.cursor.next_action().await.unwrap();
+        match action {
+            CursorAction::Query(range) => {
+                let logs = self.chain.fetch_logs_in_range(range.clone()).await.unwrap();
+                self.store.store_logs(&logs).await.unwrap();
+                self.cursor.update(logs, range).await.unwrap();
+            }
+            CursorAction::Sleep(duration) => self.elapsed += duration,
+        }
+    }
+
+    fn is_synced(&self) -> bool {
+        let expected = self
+            .chain
+            .log_blocks()
+            .into_iter()
+            .enumerate()
+            .map(|(sequence, block)| (sequence as u32, block))
+            .collect::<BTreeMap<_, _>>();
+        self.store.log_blocks() == expected
+    }
+
+    /// Plays the `script` of events, each applied before the step it is
+    /// keyed by, then steps until every log is indexed at its canonical block.
+    /// Returns the number of steps taken.
+    async fn run(&mut self, script: &[(u32, ChainEvent)]) -> u32 {
+        let last_event_step = script.iter().map(|(step, _)| *step).max().unwrap_or(0);
+        for step in 0..MAX_STEPS {
+            for (_, event) in script.iter().filter(|(at, _)| *at == step) {
+                self.apply(*event).await;
+            }
+            if step > last_event_step && self.is_synced() {
+                return step;
+            }
+            self.step().await;
+        }
+        panic!(
+            "Cursor didn't index every log after {MAX_STEPS} steps ({:?} asleep).\n\
+             Chain: {:?}\nIndexed: {:?}",
+            self.elapsed,
+            self.chain.log_blocks(),
+            self.store.log_blocks(),
+        );
+    }
+}
+
+const MODES: [IndexMode; 2] = [IndexMode::Block, IndexMode::Sequence];
+const CHUNK_SIZES: [u32; 3] = [1, 3, 20];
+
+/// Runs the scenario for every index mode and chunk size
+async fn simulate(history: &[ChainEvent], script: &[(u32, ChainEvent)]) {
+    for mode in MODES {
+        for chunk_size in CHUNK_SIZES {
+            let mut simulation = Simulation::new(mode, chunk_size, history).await;
+            simulation.run(script).await;
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_simulated_history_and_new_logs() {
+    simulate(
+        &[
+            ChainEvent::Mine { blocks: 10 },
+            ChainEvent::Dispatch { blocks: 15 },
+            ChainEvent::Mine { blocks: 5 },
+        ],
+        &[
+            (3, ChainEvent::Dispatch { blocks: 4 }),
+            (10, ChainEvent::Mine { blocks: 30 }),
+            (12, ChainEvent::Dispatch { blocks: 2 }),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_simulated_bursts() {
+    simulate(
+        &[
+            ChainEvent::Burst { count: 25 },
+            ChainEvent::Mine { blocks: 3 },
+        ],
+        &[
+            (1, ChainEvent::Burst { count: 40 }),
+            (2, ChainEvent::Burst { count: 1 }),
+            (8, ChainEvent::Burst { count: 17 }),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_simulated_rpc_gaps() {
+    simulate(
+        &[
+            ChainEvent::Dispatch { blocks: 10 },
+            // A historic log the backward cursor must not skip
+            ChainEvent::Gap {
+                sequence: 4,
+                queries: 2,
+            },
+        ],
+        &[
+            (2, ChainEvent::Dispatch { blocks: 6 }),
+            (
+                2,
+                ChainEvent::Gap {
+                    sequence: 12,
+                    queries: 3,
+                },
+            ),
+            (6, ChainEvent::Burst { count: 5 }),
+            (
+                6,
+                ChainEvent::Gap {
+                    sequence: 17,
+                    queries: 1,
+                },
+            ),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_simulated_reorgs() {
+    simulate(
+        &[ChainEvent::Dispatch { blocks: 5 }],
+        &[
+            (1, ChainEvent::Dispatch { blocks: 5 }),
+            (8, ChainEvent::Reorg { depth: 3 }),
+            (9, ChainEvent::Dispatch { blocks: 2 }),
+            (20, ChainEvent::Burst { count: 4 }),
+            (30, ChainEvent::Reorg { depth: 1 }),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_simulated_reorg_during_gap() {
+    simulate(
+        &[ChainEvent::Mine { blocks: 2 }],
+        &[
+            (0, ChainEvent::Dispatch { blocks: 6 }),
+            (
+                0,
+                ChainEvent::Gap {
+                    sequence: 3,
+                    queries: 4,
+                },
+            ),
+            (2, ChainEvent::Reorg { depth: 2 }),
+            (5, ChainEvent::Burst { count: 3 }),
+        ],
+    )
+    .await;
+}
@@ -18,6 +18,20 @@ pub struct ContractSyncMetrics {
     /// - `chain`: Chain the indexer is collecting data from.
     pub stored_events: IntCounterVec,
 
+    /// Reorgs detected in the blocks logs were fetched from
+    ///
+    /// Labels:
+    /// - `data_type`: the data the indexer is recording. E.g. `messages` or `gas_payments`.
+    /// - `chain`: Chain the indexer is collecting data from.
+    pub detected_reorgs: IntCounterVec,
+
+    /// Logs skipped because they were malformed
+    ///
+    /// Labels:
+    /// - `data_type`: the data the indexer is recording. E.g. `messages` or `gas_payments`.
+    /// - `chain`: Chain the indexer is collecting data from.
+    pub skipped_logs: IntCounterVec,
+
     /// See `last_known_message_nonce` in CoreMetrics.
     pub message_nonce: IntGaugeVec,
 }
@@ -41,11 +55,29 @@ impl ContractSyncMetrics {
             )
             .expect("failed to register stored_events metric");
 
+        let detected_reorgs = metrics
+            .new_int_counter(
+                "contract_sync_detected_reorgs",
+                "Number of reorgs detected in the blocks logs were fetched from",
+                &["data_type", "chain"],
+            )
+            .expect("failed to register detected_reorgs metric");
+
+        let skipped_logs = metrics
+            .new_int_counter(
+                "contract_sync_skipped_logs",
+                "Number of logs skipped because they were malformed",
+                &["data_type", "chain"],
+            )
+            .expect("failed to register skipped_logs metric");
+
         let message_nonce = metrics.last_known_message_nonce();
 
         ContractSyncMetrics {
             indexed_height,
             stored_events,
+            detected_reorgs,
+            skipped_logs,
             message_nonce,
         }
     }
@@ -1,34 +1,56 @@
 use std::{
-    collections::HashSet, fmt::Debug, hash::Hash, marker::PhantomData, sync::Arc, time::Duration,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    hash::Hash,
+    marker::PhantomData,
+    ops::RangeInclusive,
+    sync::Arc,
+    time::{Duration, Instant},
 };
 
 use axum::async_trait;
+use backfill::backfill_chunks;
 use broadcast::BroadcastMpscSender;
 use cursors::*;
 use derive_new::new;
 use eyre::Result;
+use futures_util::{stream, StreamExt, TryStreamExt};
 use hyperlane_core::{
     utils::fmt_sync_time, ContractSyncCursor, CursorAction, HyperlaneDomain, HyperlaneLogStore,
     HyperlaneSequenceAwareIndexerStore, HyperlaneWatermarkedLogStore, Indexer,
     SequenceAwareIndexer,
 };
-use hyperlane_core::{Indexed, LogMeta, H512};
+use hyperlane_core::{ChainResult, Indexed, LogMeta, SkippedLog, H512};
 pub use metrics::ContractSyncMetrics;
 use prometheus::core::{AtomicI64, AtomicU64, GenericCounter, GenericGauge};
+use reorg::{is_orphaned, IngestedBlocks};
+use stall::StallDetector;
 use tokio::sync::mpsc::{error::TryRecvError, Receiver as MpscReceiver};
 use tokio::time::sleep;
-use tracing::{debug, info, instrument, trace, warn};
+use tracing::{debug, error, info, instrument, trace, warn};
 
-use crate::settings::IndexSettings;
+use crate::{
+    settings::{BackfillConf, IndexSettings},
+    ChainHalts, NotificationEvent, Notifier, CHAIN_HALT_PAUSE,
+};
 
+mod backfill;
 /// Broadcast channel utility, with async interface for `send`
 pub mod broadcast;
 pub(crate) mod cursors;
 mod eta_calculator;
 mod metrics;
+mod reorg;
+mod stall;
 
 use cursors::ForwardBackwardSequenceAwareSyncCursor;
 
+/// The cursors of the contract sync, exposed for their benchmarks
+#[cfg(feature = "test-utils")]
+pub mod test_utils {
+    pub use super::cursors::sequence_aware::ForwardBackwardSequenceAwareSyncCursor;
+}
+
 const SLEEP_DURATION: Duration = Duration::from_secs(5);
 
 #[derive(Debug, derive_new::new)]
@@ -49,21 +71,48 @@ pub struct ContractSync<T: Indexable, D: HyperlaneLogStore<T>, I: Indexer<T>> {
     indexer: I,
     metrics: ContractSyncMetrics,
     broadcast_sender: Option<BroadcastMpscSender<H512>>,
+    notifier: Notifier,
+    stall_threshold: Duration,
+    /// The number of blocks, or sequences, queried at once
+    chunk_size: u32,
+    /// The number of chunks of a cursor's range queried at the same time
+    concurrency: usize,
     _phantom: PhantomData<T>,
 }
 
 impl<T: Indexable, D: HyperlaneLogStore<T>, I: Indexer<T>> ContractSync<T, D, I> {
-    /// Create a new ContractSync
-    pub fn new(domain: HyperlaneDomain, db: D, indexer: I, metrics: ContractSyncMetrics) -> Self {
+    /// Create a new ContractSync. Indexing failing for longer than
+    /// `stall_threshold` is reported to `notifier`.
+    pub fn new(
+        domain: HyperlaneDomain,
+        db: D,
+        indexer: I,
+        metrics: ContractSyncMetrics,
+        notifier: Notifier,
+        stall_threshold: Duration,
+    ) -> Self {
         Self {
             domain,
             db,
             indexer,
             metrics,
             broadcast_sender: T::broadcast_channel_size().map(BroadcastMpscSender::new),
+            notifier,
+            stall_threshold,
+            chunk_size: u32::MAX,
+            concurrency: 1,
             _phantom: PhantomData,
         }
     }
+
+    /// Split the ranges cursors query into chunks of `chunk_size`, querying
+    /// `concurrency` of them at the same time. Their logs are stored at once,
+    /// in the order of the chunks.
+    pub fn with_concurrent_queries(mut self, chunk_size: u32, concurrency: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self.concurrency = concurrency.max(1);
+        self
+    }
 }
 
 impl<T, D, I> ContractSync<T, D, I>
@@ -93,14 +142,49 @@ where
             .metrics
             .stored_events
             .with_label_values(&[label, chain_name]);
+        let detected_reorgs_metric = self
+            .metrics
+            .detected_reorgs
+            .with_label_values(&[label, chain_name]);
+        let skipped_logs_metric = self
+            .metrics
+            .skipped_logs
+            .with_label_values(&[label, chain_name]);
+        let mut ingested_blocks = IngestedBlocks::default();
+        let mut stall_detector = StallDetector::new(self.stall_threshold);
 
         loop {
+            if opts
+                .chain_halts
+                .as_ref()
+                .is_some_and(|halts| halts.is_halted(&self.domain))
+            {
+                // Nothing new to index until the chain resumes
+                sleep(CHAIN_HALT_PAUSE).await;
+                continue;
+            }
             if let Some(rx) = opts.tx_id_receiver.as_mut() {
                 self.fetch_logs_from_receiver(rx, &stored_logs_metric).await;
             }
             if let Some(cursor) = opts.cursor.as_mut() {
-                self.fetch_logs_with_cursor(cursor, &stored_logs_metric, &indexed_height_metric)
+                let healthy = self
+                    .fetch_logs_with_cursor(
+                        cursor,
+                        &mut ingested_blocks,
+                        &stored_logs_metric,
+                        &indexed_height_metric,
+                        &detected_reorgs_metric,
+                        &skipped_logs_metric,
+                    )
                     .await;
+                if let Some(stalled_for) = stall_detector.record(healthy, Instant::now()) {
+                    let event = NotificationEvent::IndexingStalled {
+                        chain: chain_name.to_owned(),
+                        label: label.to_owned(),
+                        stalled_for_secs: stalled_for.as_secs(),
+                    };
+                    self.notifier.notify(&event);
+                }
             }
         }
     }
@@ -143,38 +227,84 @@ where
         }
     }
 
-    #[instrument(fields(domain=self.domain().name()), skip(self, stored_logs_metric, indexed_height_metric))]
+    #[instrument(
+        fields(domain=self.domain().name()),
+        skip(
+            self,
+            ingested_blocks,
+            stored_logs_metric,
+            indexed_height_metric,
+            detected_reorgs_metric,
+            skipped_logs_metric
+        )
+    )]
     async fn fetch_logs_with_cursor(
         &self,
         cursor: &mut Box<dyn ContractSyncCursor<T>>,
+        ingested_blocks: &mut IngestedBlocks<T>,
         stored_logs_metric: &GenericCounter<AtomicU64>,
         indexed_height_metric: &GenericGauge<AtomicI64>,
-    ) {
+        detected_reorgs_metric: &GenericCounter<AtomicU64>,
+        skipped_logs_metric: &GenericCounter<AtomicU64>,
+    ) -> bool {
         indexed_height_metric.set(cursor.latest_queried_block() as i64);
         let (action, eta) = match cursor.next_action().await {
             Ok((action, eta)) => (action, eta),
             Err(err) => {
                 warn!(?err, "Error getting next action");
                 sleep(SLEEP_DURATION).await;
-                return;
+                return false;
             }
         };
-        let sleep_duration = match action {
+        let (sleep_duration, healthy) = match action {
             // Use `loop` but always break - this allows for returning a value
-            // from the loop (the sleep duration)
+            // from the loop (the sleep duration and whether indexing is healthy)
             #[allow(clippy::never_loop)]
             CursorAction::Query(range) => loop {
                 debug!(?range, "Looking for events in index range");
 
-                let logs = match self.indexer.fetch_logs_in_range(range.clone()).await {
+                let (logs, skipped) = match self.fetch_logs_in_range(range.clone()).await {
                     Ok(logs) => logs,
                     Err(err) => {
                         warn!(?err, ?range, "Error fetching logs in range");
-                        break SLEEP_DURATION;
+                        break (SLEEP_DURATION, false);
                     }
                 };
 
+                // Logs from blocks that were reorged out must not be stored, and the
+                // ones already stored must be replaced by the canonical chain's.
+                match self
+                    .invalidate_orphaned_blocks(cursor, ingested_blocks)
+                    .await
+                {
+                    Ok(false) => {}
+                    Ok(true) => {
+                        detected_reorgs_metric.inc();
+                        break (Default::default(), true);
+                    }
+                    Err(err) => {
+                        warn!(?err, "Error checking ingested blocks for reorgs");
+                        break (SLEEP_DURATION, false);
+                    }
+                }
+                match self.find_orphaned_block(&logs).await {
+                    Ok(None) => {}
+                    Ok(Some(block_number)) => {
+                        warn!(
+                            ?range,
+                            block_number, "Fetched logs from a reorged block, fetching them again"
+                        );
+                        detected_reorgs_metric.inc();
+                        break (SLEEP_DURATION, true);
+                    }
+                    Err(err) => {
+                        warn!(?err, ?range, "Error checking fetched logs for reorgs");
+                        break (SLEEP_DURATION, false);
+                    }
+                }
+
                 let logs = self.dedupe_and_store_logs(logs, stored_logs_metric).await;
+                ingested_blocks.insert(&logs);
                 let logs_found = logs.len() as u64;
                 info!(
                     ?range,
@@ -193,16 +323,121 @@ where
                     }
                 }
 
+                // Querying the malformed logs again won't fix them, so the cursor moves
+                // past them rather than getting stuck on them
+                if !skipped.is_empty() {
+                    error!(?range, ?skipped, "Skipped malformed log(s) in index range");
+                    skipped_logs_metric.inc_by(skipped.len() as u64);
+                }
+
                 // Update cursor
-                if let Err(err) = cursor.update(logs, range).await {
+                if let Err(err) = cursor.update_with_skipped(logs, &skipped, range).await {
                     warn!(?err, "Error updating cursor");
-                    break SLEEP_DURATION;
+                    break (SLEEP_DURATION, false);
                 };
-                break Default::default();
+                break (Default::default(), true);
             },
-            CursorAction::Sleep(duration) => duration,
+            CursorAction::Sleep(duration) => (duration, true),
+        };
+        sleep(sleep_duration).await;
+        healthy
+    }
+
+    /// Fetches the logs in `range`, querying its chunks concurrently if it
+    /// spans several. The malformed logs are skipped and returned apart.
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<(Vec<(Indexed<T>, LogMeta)>, Vec<SkippedLog>)> {
+        let chunks = match self.concurrency {
+            1 => vec![],
+            _ => backfill_chunks(*range.start(), *range.end(), self.chunk_size),
+        };
+        if chunks.len() <= 1 {
+            return self
+                .indexer
+                .fetch_logs_in_range_skipping_malformed(range)
+                .await;
+        }
+        debug!(
+            ?range,
+            num_chunks = chunks.len(),
+            "Querying chunks of range concurrently"
+        );
+        let chunk_logs: Vec<(Vec<_>, Vec<_>)> = stream::iter(chunks)
+            .map(|chunk| self.indexer.fetch_logs_in_range_skipping_malformed(chunk))
+            .buffered(self.concurrency)
+            .try_collect()
+            .await?;
+        let (logs, skipped): (Vec<_>, Vec<_>) = chunk_logs.into_iter().unzip();
+        Ok((
+            logs.into_iter().flatten().collect(),
+            skipped.into_iter().flatten().collect(),
+        ))
+    }
+
+    /// Finds the first block that `logs` were fetched from which is no longer
+    /// canonical, if any
+    async fn find_orphaned_block(
+        &self,
+        logs: &[(Indexed<T>, LogMeta)],
+    ) -> ChainResult<Option<u64>> {
+        let mut block_hashes = HashMap::new();
+        for (_, meta) in logs {
+            let block_hash = *block_hashes
+                .entry(meta.block_number)
+                .or_insert(meta.block_hash);
+            if block_hash != meta.block_hash {
+                return Ok(Some(meta.block_number));
+            }
+        }
+        // The blocks of the logs are all ancestors of the latest one, so they
+        // are canonical if it is.
+        let Some((&block_number, &block_hash)) = block_hashes.iter().max_by_key(|(n, _)| **n)
+        else {
+            return Ok(None);
+        };
+        let canonical_hash = self.indexer.get_block_hash(block_number as u32).await?;
+        Ok(is_orphaned(block_hash, canonical_hash).then_some(block_number))
+    }
+
+    /// Checks that the latest blocks logs were ingested from are still
+    /// canonical. The logs of the blocks orphaned by a reorg are invalidated
+    /// in the db, and the cursor is rewound to index the canonical blocks.
+    /// Returns whether a reorg was found.
+    async fn invalidate_orphaned_blocks(
+        &self,
+        cursor: &mut Box<dyn ContractSyncCursor<T>>,
+        ingested_blocks: &mut IngestedBlocks<T>,
+    ) -> Result<bool> {
+        let mut orphaned_from = None;
+        let mut canonical_up_to = None;
+        for (block_number, block_hash) in ingested_blocks.latest_first().collect::<Vec<_>>() {
+            let canonical_hash = self.indexer.get_block_hash(block_number as u32).await?;
+            if !is_orphaned(block_hash, canonical_hash) {
+                canonical_up_to = Some(block_number);
+                break;
+            }
+            orphaned_from = Some(block_number);
+        }
+        let Some(orphaned_from) = orphaned_from else {
+            return Ok(false);
         };
-        sleep(sleep_duration).await
+
+        // The reorg may have started right after the latest canonical block
+        let from_block = canonical_up_to.map_or(orphaned_from, |n| n + 1) as u32;
+        let orphaned_logs = ingested_blocks.logs_from(orphaned_from);
+        let invalidated = self.db.invalidate_logs(&orphaned_logs).await?;
+        warn!(
+            orphaned_from,
+            from_block,
+            invalidated,
+            orphaned_logs = ?orphaned_logs.iter().map(|(log, meta)| IndexedTxIdAndSequence::new(meta.transaction_id, log.sequence)).collect::<Vec<_>>(),
+            "Blocks that logs were stored from were reorged out, invalidated their logs and rewinding cursor"
+        );
+        cursor.rewind_orphaned(&orphaned_logs, from_block).await?;
+        ingested_blocks.forget_from(orphaned_from);
+        Ok(true)
     }
 
     async fn dedupe_and_store_logs(
@@ -255,6 +490,32 @@ pub trait ContractSyncer<T>: Send + Sync {
     /// Syncs events from the indexer using the provided cursor
     async fn sync(&self, label: &'static str, opts: SyncOptions<T>);
 
+    /// Indexes the events of a historical block range, recording the
+    /// completed chunks in `progress`
+    async fn backfill(
+        &self,
+        label: &'static str,
+        conf: BackfillConf,
+        progress: Arc<dyn HyperlaneBackfillStore>,
+    );
+
+    /// Syncs events like `sync`, indexing the `backfill` range alongside if
+    /// one is configured
+    async fn sync_with_backfill(
+        &self,
+        label: &'static str,
+        opts: SyncOptions<T>,
+        backfill: Option<BackfillConf>,
+        progress: Arc<dyn HyperlaneBackfillStore>,
+    ) {
+        let backfill = async {
+            if let Some(conf) = backfill {
+                self.backfill(label, conf, progress).await;
+            }
+        };
+        tokio::join!(self.sync(label, opts), backfill);
+    }
+
     /// The domain of this syncer
     fn domain(&self) -> &HyperlaneDomain;
 
@@ -270,6 +531,16 @@ pub struct SyncOptions<T> {
     // txids from a channel to other indexing tasks
     cursor: Option<Box<dyn ContractSyncCursor<T>>>,
     tx_id_receiver: Option<MpscReceiver<H512>>,
+    #[new(default)]
+    chain_halts: Option<ChainHalts>,
+}
+
+impl<T> SyncOptions<T> {
+    /// Pause syncing while the chain is halted in `chain_halts`
+    pub fn with_chain_halts(mut self, chain_halts: ChainHalts) -> Self {
+        self.chain_halts = Some(chain_halts);
+        self
+    }
 }
 
 impl<T> From<Box<dyn ContractSyncCursor<T>>> for SyncOptions<T> {
@@ -277,6 +548,7 @@ impl<T> From<Box<dyn ContractSyncCursor<T>>> for SyncOptions<T> {
         Self {
             cursor: Some(cursor),
             tx_id_receiver: None,
+            chain_halts: None,
         }
     }
 }
@@ -296,12 +568,14 @@ where
             from: watermark.unwrap_or(index_settings.from),
             chunk_size: index_settings.chunk_size,
             mode: index_settings.mode,
+            concurrency: index_settings.concurrency,
+            backfill: index_settings.backfill,
         };
         Ok(Box::new(
             RateLimitedContractSyncCursor::new(
                 Arc::new(self.indexer.clone()),
                 self.db.clone(),
-                index_settings.chunk_size,
+                index_settings.cursor_chunk_size(),
                 index_settings.from,
             )
             .await?,
@@ -312,6 +586,15 @@ where
         ContractSync::sync(self, label, opts).await
     }
 
+    async fn backfill(
+        &self,
+        label: &'static str,
+        conf: BackfillConf,
+        progress: Arc<dyn HyperlaneBackfillStore>,
+    ) {
+        ContractSync::backfill(self, label, conf, progress.as_ref()).await
+    }
+
     fn domain(&self) -> &HyperlaneDomain {
         ContractSync::domain(self)
     }
@@ -342,7 +625,7 @@ where
             ForwardBackwardSequenceAwareSyncCursor::new(
                 self.indexer.clone(),
                 Arc::new(self.db.clone()),
-                index_settings.chunk_size,
+                index_settings.cursor_chunk_size(),
                 index_settings.mode,
             )
             .await?,
@@ -353,6 +636,15 @@ where
         ContractSync::sync(self, label, opts).await;
     }
 
+    async fn backfill(
+        &self,
+        label: &'static str,
+        conf: BackfillConf,
+        progress: Arc<dyn HyperlaneBackfillStore>,
+    ) {
+        ContractSync::backfill(self, label, conf, progress.as_ref()).await
+    }
+
     fn domain(&self) -> &HyperlaneDomain {
         ContractSync::domain(self)
     }
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use hyperlane_core::{Indexed, LogMeta, H256};
+
+/// How many of the latest blocks logs were ingested from are kept to check
+/// for reorgs
+const MAX_INGESTED_BLOCKS: usize = 64;
+
+/// The latest blocks logs were ingested from, with their hashes and the logs,
+/// so that the logs can be invalidated if a reorg orphans their block.
+#[derive(Debug)]
+pub(crate) struct IngestedBlocks<T> {
+    blocks: BTreeMap<u64, (H256, Vec<(Indexed<T>, LogMeta)>)>,
+}
+
+impl<T> Default for IngestedBlocks<T> {
+    fn default() -> Self {
+        Self {
+            blocks: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Clone> IngestedBlocks<T> {
+    /// Record the blocks of `logs`, dropping the oldest blocks past the limit
+    pub fn insert(&mut self, logs: &[(Indexed<T>, LogMeta)]) {
+        for (log, meta) in logs {
+            let (_, block_logs) = self
+                .blocks
+                .entry(meta.block_number)
+                .or_insert_with(|| (meta.block_hash, vec![]));
+            block_logs.push((log.clone(), meta.clone()));
+        }
+        while self.blocks.len() > MAX_INGESTED_BLOCKS {
+            self.blocks.pop_first();
+        }
+    }
+
+    /// The number and hash of the ingested blocks, latest first
+    pub fn latest_first(&self) -> impl Iterator<Item = (u64, H256)> + '_ {
+        self.blocks
+            .iter()
+            .rev()
+            .map(|(number, (hash, _))| (*number, *hash))
+    }
+
+    /// The logs of the blocks from `block_number` on
+    pub fn logs_from(&self, block_number: u64) -> Vec<(Indexed<T>, LogMeta)> {
+        self.blocks
+            .range(block_number..)
+            .flat_map(|(_, (_, logs))| logs.iter().cloned())
+            .collect()
+    }
+
+    /// Forget the blocks from `block_number` on
+    pub fn forget_from(&mut self, block_number: u64) {
+        self.blocks.split_off(&block_number);
+    }
+}
+
+/// Whether a block's hash differs from the canonical hash at its height. The
+/// block is assumed canonical if the indexer can't tell.
+pub(crate) fn is_orphaned(block_hash: H256, canonical_hash: Option<H256>) -> bool {
+    canonical_hash.is_some_and(|hash| hash != block_hash)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn log(block_number: u64, block_hash: u64, sequence: u32) -> (Indexed<u32>, LogMeta) {
+        let meta = LogMeta {
+            block_number,
+            block_hash: H256::from_low_u64_be(block_hash),
+            ..Default::default()
+        };
+        (Indexed::new(sequence).with_sequence(sequence), meta)
+    }
+
+    #[test]
+    fn test_forget_orphaned_blocks() {
+        let mut blocks = IngestedBlocks::default();
+        blocks.insert(&[
+            log(10, 10, 0),
+            log(12, 12, 1),
+            log(12, 12, 2),
+            log(15, 15, 3),
+        ]);
+
+        assert_eq!(
+            blocks.latest_first().map(|(n, _)| n).collect::<Vec<_>>(),
+            vec![15, 12, 10]
+        );
+        assert_eq!(
+            blocks
+                .logs_from(11)
+                .iter()
+                .map(|(log, _)| log.sequence.unwrap())
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        blocks.forget_from(11);
+        assert_eq!(
+            blocks.latest_first().collect::<Vec<_>>(),
+            vec![(10, H256::from_low_u64_be(10))]
+        );
+    }
+
+    #[test]
+    fn test_keeps_latest_blocks() {
+        let mut blocks = IngestedBlocks::default();
+        let logs = (0..100).map(|i| log(i, i, i as u32)).collect::<Vec<_>>();
+        blocks.insert(&logs);
+
+        assert_eq!(blocks.latest_first().count(), MAX_INGESTED_BLOCKS);
+        assert_eq!(blocks.latest_first().next().unwrap().0, 99);
+    }
+}
@@ -0,0 +1,66 @@
+use std::time::{Duration, Instant};
+
+/// Tracks how long indexing has been failing for, to report it as stalled
+/// once per stall.
+#[derive(Debug)]
+pub(crate) struct StallDetector {
+    threshold: Duration,
+    failing_since: Option<Instant>,
+    reported: bool,
+}
+
+impl StallDetector {
+    pub(crate) fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            failing_since: None,
+            reported: false,
+        }
+    }
+
+    /// Records the outcome of an indexing iteration at `now`. Returns how
+    /// long indexing has been failing for the first time it exceeds the
+    /// threshold since it last succeeded.
+    pub(crate) fn record(&mut self, healthy: bool, now: Instant) -> Option<Duration> {
+        if healthy {
+            self.failing_since = None;
+            self.reported = false;
+            return None;
+        }
+        let stalled_for = now.duration_since(*self.failing_since.get_or_insert(now));
+        if self.reported || stalled_for < self.threshold {
+            return None;
+        }
+        self.reported = true;
+        Some(stalled_for)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_reports_once_per_stall() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut detector = StallDetector::new(Duration::from_secs(60));
+
+        assert_eq!(detector.record(false, at(0)), None);
+        assert_eq!(detector.record(false, at(30)), None);
+        assert_eq!(
+            detector.record(false, at(60)),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(detector.record(false, at(120)), None);
+
+        // Recovering resets the stall
+        assert_eq!(detector.record(true, at(130)), None);
+        assert_eq!(detector.record(false, at(140)), None);
+        assert_eq!(detector.record(false, at(190)), None);
+        assert_eq!(
+            detector.record(false, at(200)),
+            Some(Duration::from_secs(60))
+        );
+    }
+}
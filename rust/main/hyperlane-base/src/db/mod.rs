@@ -4,11 +4,16 @@ use hyperlane_core::{
     InterchainGasPaymentMeta, MerkleTreeInsertion, PendingOperationStatus, H256,
 };
 pub use rocks::*;
+pub use snapshot::*;
 
-pub use self::storage_types::{InterchainGasExpenditureData, InterchainGasPaymentData};
+pub use self::storage_types::{
+    DestinationGasConfigData, GasReconciliationData, InterchainGasExpenditureData,
+    InterchainGasPaymentData, RemoteGasData,
+};
 
 mod error;
 mod rocks;
+mod snapshot;
 pub(crate) mod storage_types;
 
 #[allow(missing_docs)]
@@ -123,6 +128,21 @@ pub trait HyperlaneDb: Send + Sync {
         message_id: &H256,
     ) -> DbResult<Option<u32>>;
 
+    /// Store when a pending message is next retried, as a unix timestamp in
+    /// seconds, by its message id
+    fn store_pending_message_next_attempt_by_message_id(
+        &self,
+        message_id: &H256,
+        timestamp: &u64,
+    ) -> DbResult<()>;
+
+    /// Retrieve when a pending message is next retried, as a unix timestamp in
+    /// seconds, by its message id
+    fn retrieve_pending_message_next_attempt_by_message_id(
+        &self,
+        message_id: &H256,
+    ) -> DbResult<Option<u64>>;
+
     fn store_merkle_tree_insertion_by_leaf_index(
         &self,
         leaf_index: &u32,
@@ -159,4 +179,8 @@ pub trait HyperlaneDb: Send + Sync {
 
     /// Retrieve the nonce of the highest processed message we're aware of
     fn retrieve_highest_seen_message_nonce_number(&self) -> DbResult<Option<u32>>;
+
+    /// Retrieve and clear the lowest nonce of the messages invalidated by
+    /// reorgs since the last call
+    fn take_lowest_invalidated_message_nonce(&self) -> DbResult<Option<u32>>;
 }
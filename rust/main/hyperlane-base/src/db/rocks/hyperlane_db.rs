@@ -1,17 +1,24 @@
+use std::ops::RangeInclusive;
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use async_trait::async_trait;
 use eyre::{bail, Result};
 use tracing::{debug, instrument, trace};
 
 use hyperlane_core::{
-    Decode, Encode, GasPaymentKey, HyperlaneDomain, HyperlaneLogStore, HyperlaneMessage,
-    HyperlaneSequenceAwareIndexerStoreReader, HyperlaneWatermarkedLogStore, Indexed,
-    InterchainGasExpenditure, InterchainGasPayment, InterchainGasPaymentMeta, LogMeta,
-    MerkleTreeInsertion, PendingOperationStatus, H256,
+    Decode, Encode, GasOracleConfig, GasOracleConfigUpdate, GasPaymentKey, HyperlaneBackfillStore,
+    HyperlaneDomain, HyperlaneLogStore, HyperlaneMessage, HyperlaneSequenceAwareIndexerStoreReader,
+    HyperlaneWatermarkedLogStore, Indexed, InterchainGasExpenditure, InterchainGasPayment,
+    InterchainGasPaymentMeta, LogMeta, MerkleTreeInsertion, PendingOperationStatus,
+    RawHyperlaneMessage, H256, H512,
 };
 
 use super::{DbError, TypedDB, DB};
 use crate::db::{
-    storage_types::{InterchainGasExpenditureData, InterchainGasPaymentData},
+    storage_types::{
+        DestinationGasConfigData, GasReconciliationData, InterchainGasExpenditureData,
+        InterchainGasPaymentData, RemoteGasData,
+    },
     HyperlaneDb,
 };
 
@@ -20,22 +27,36 @@ use crate::db::{
 
 const MESSAGE_ID: &str = "message_id_";
 const MESSAGE_DISPATCHED_BLOCK_NUMBER: &str = "message_dispatched_block_number_";
+const MESSAGE_INDEXED_AT: &str = "message_indexed_at_";
+const MESSAGE_DISPATCH_TRANSACTION: &str = "message_dispatch_transaction_";
+const MESSAGE_DISPATCH_BLOCK_HASH: &str = "message_dispatch_block_hash_";
 const MESSAGE: &str = "message_";
 const NONCE_PROCESSED: &str = "nonce_processed_";
 const GAS_PAYMENT_BY_SEQUENCE: &str = "gas_payment_by_sequence_";
 const GAS_PAYMENT_BLOCK_BY_SEQUENCE: &str = "gas_payment_block_by_sequence_";
 const HIGHEST_SEEN_MESSAGE_NONCE: &str = "highest_seen_message_nonce_";
+const LOWEST_INVALIDATED_MESSAGE_NONCE: &str = "lowest_invalidated_message_nonce_";
+const LOWEST_INVALIDATED_LEAF_INDEX: &str = "lowest_invalidated_leaf_index_";
+const SNAPSHOT_BOOTSTRAP_NEXT_NONCE: &str = "snapshot_bootstrap_next_nonce_";
+const SNAPSHOT_BOOTSTRAP_COMPLETE: &str = "snapshot_bootstrap_complete_";
 const GAS_PAYMENT_FOR_MESSAGE_ID: &str = "gas_payment_sequence_for_message_id_v2_";
 const GAS_PAYMENT_META_PROCESSED: &str = "gas_payment_meta_processed_v3_";
 const GAS_EXPENDITURE_FOR_MESSAGE_ID: &str = "gas_expenditure_for_message_id_v2_";
 const STATUS_BY_MESSAGE_ID: &str = "status_by_message_id_";
 const PENDING_MESSAGE_RETRY_COUNT_FOR_MESSAGE_ID: &str =
     "pending_message_retry_count_for_message_id_";
+const PENDING_MESSAGE_NEXT_ATTEMPT_FOR_MESSAGE_ID: &str =
+    "pending_message_next_attempt_for_message_id_";
 const MERKLE_TREE_INSERTION: &str = "merkle_tree_insertion_";
 const MERKLE_LEAF_INDEX_BY_MESSAGE_ID: &str = "merkle_leaf_index_by_message_id_";
 const MERKLE_TREE_INSERTION_BLOCK_NUMBER_BY_LEAF_INDEX: &str =
     "merkle_tree_insertion_block_number_by_leaf_index_";
 const LATEST_INDEXED_GAS_PAYMENT_BLOCK: &str = "latest_indexed_gas_payment_block";
+const BACKFILL_RANGE_COMPLETE: &str = "backfill_range_complete_";
+const GAS_RECONCILIATION_BY_EPOCH: &str = "gas_reconciliation_by_epoch_";
+const DESTINATION_GAS_CONFIG_BY_REMOTE_DOMAIN: &str = "destination_gas_config_by_remote_domain_";
+const REMOTE_GAS_DATA_BY_GAS_ORACLE: &str = "remote_gas_data_by_gas_oracle_";
+const LATEST_INDEXED_GAS_ORACLE_CONFIG_BLOCK: &str = "latest_indexed_gas_oracle_config_block";
 
 /// Rocks DB result type
 pub type DbResult<T> = std::result::Result<T, DbError>;
@@ -81,6 +102,7 @@ impl HyperlaneRocksDB {
     /// - `nonce` --> `id`
     /// - `id` --> `message`
     /// - `nonce` --> `dispatched block number`
+    /// - `nonce` --> `unix timestamp the message was indexed at`
     pub fn store_message(
         &self,
         message: &HyperlaneMessage,
@@ -102,9 +124,33 @@ impl HyperlaneRocksDB {
         self.try_update_max_seen_message_nonce(message.nonce)?;
         // - `nonce` --> `dispatched block number`
         self.store_dispatched_block_number_by_nonce(&message.nonce, &dispatched_block_number)?;
+        // - `nonce` --> `unix timestamp the message was indexed at`
+        let indexed_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.store_value_by_key(MESSAGE_INDEXED_AT, &message.nonce, &indexed_at)?;
         Ok(true)
     }
 
+    /// Retrieve the unix timestamp the message with `nonce` was indexed at,
+    /// if it was indexed since these timestamps are stored
+    pub fn retrieve_message_indexed_at_by_nonce(&self, nonce: u32) -> DbResult<Option<u64>> {
+        self.retrieve_value_by_key(MESSAGE_INDEXED_AT, &nonce)
+    }
+
+    /// Retrieve the hash of the transaction that dispatched the message with
+    /// `id`, if it was indexed since these hashes are stored
+    pub fn retrieve_dispatch_transaction_by_message_id(&self, id: &H256) -> DbResult<Option<H512>> {
+        self.retrieve_value_by_key(MESSAGE_DISPATCH_TRANSACTION, id)
+    }
+
+    /// Retrieve the hash of the block that dispatched the message with `id`,
+    /// if it was indexed since these hashes are stored
+    pub fn retrieve_dispatch_block_hash_by_message_id(&self, id: &H256) -> DbResult<Option<H256>> {
+        self.retrieve_value_by_key(MESSAGE_DISPATCH_BLOCK_HASH, id)
+    }
+
     /// Retrieve a message by its nonce
     pub fn retrieve_message_by_nonce(&self, nonce: u32) -> DbResult<Option<HyperlaneMessage>> {
         let id = self.retrieve_message_id_by_nonce(&nonce)?;
@@ -114,6 +160,18 @@ impl HyperlaneRocksDB {
         }
     }
 
+    /// Retrieve the encoding of a message by its nonce, so that it can be
+    /// inspected through a `HyperlaneMessageRef` without copying its body
+    pub fn retrieve_raw_message_by_nonce(
+        &self,
+        nonce: u32,
+    ) -> DbResult<Option<RawHyperlaneMessage>> {
+        let Some(id) = self.retrieve_message_id_by_nonce(&nonce)? else {
+            return Ok(None);
+        };
+        self.retrieve_encoded(MESSAGE, id.to_vec())
+    }
+
     /// Update the nonce of the highest processed message we're aware of
     pub fn try_update_max_seen_message_nonce(&self, nonce: u32) -> DbResult<()> {
         let current_max = self
@@ -215,6 +273,138 @@ impl HyperlaneRocksDB {
         Ok(true)
     }
 
+    /// Remove a message whose dispatch was orphaned by a reorg, so that the
+    /// message dispatched with its nonce on the canonical chain can be stored.
+    /// Returns whether the message was stored.
+    ///
+    /// Removes the `nonce` --> `id` and `nonce` --> `dispatched block number`
+    /// mappings, lowers the highest seen nonce below the message's and records
+    /// the nonce for the message processor to rewind to.
+    pub fn invalidate_message(&self, message: &HyperlaneMessage) -> DbResult<bool> {
+        if self.retrieve_message_id_by_nonce(&message.nonce)? != Some(message.id()) {
+            return Ok(false);
+        }
+        debug!(msg=?message, "Removing orphaned message from db");
+
+        self.delete_value_by_key(MESSAGE_ID, &message.nonce)?;
+        self.delete_value_by_key(MESSAGE_DISPATCHED_BLOCK_NUMBER, &message.nonce)?;
+        if self.retrieve_highest_seen_message_nonce()? >= Some(message.nonce) {
+            match message.nonce.checked_sub(1) {
+                Some(nonce) => self.store_highest_seen_message_nonce_number(&nonce)?,
+                None => self.delete_value_by_key(HIGHEST_SEEN_MESSAGE_NONCE, &bool::default())?,
+            }
+        }
+        let lowest_invalidated: Option<u32> =
+            self.retrieve_value_by_key(LOWEST_INVALIDATED_MESSAGE_NONCE, &bool::default())?;
+        if lowest_invalidated.map_or(true, |nonce| message.nonce < nonce) {
+            self.store_value_by_key(
+                LOWEST_INVALIDATED_MESSAGE_NONCE,
+                &bool::default(),
+                &message.nonce,
+            )?;
+        }
+        Ok(true)
+    }
+
+    /// Remove a tree insertion that was orphaned by a reorg, and record its
+    /// leaf index for merkle trees built from the insertions to rewind to.
+    /// Returns whether the insertion was stored.
+    pub fn invalidate_tree_insertion(&self, insertion: &MerkleTreeInsertion) -> DbResult<bool> {
+        if self.retrieve_merkle_tree_insertion_by_leaf_index(&insertion.index())?
+            != Some(*insertion)
+        {
+            return Ok(false);
+        }
+        debug!(insertion=?insertion, "Removing orphaned tree insertion from db");
+
+        self.delete_value_by_key(MERKLE_TREE_INSERTION, &insertion.index())?;
+        self.delete_value_by_key(
+            MERKLE_TREE_INSERTION_BLOCK_NUMBER_BY_LEAF_INDEX,
+            &insertion.index(),
+        )?;
+        if self.retrieve_merkle_leaf_index_by_message_id(&insertion.message_id())?
+            == Some(insertion.index())
+        {
+            self.delete_value_by_key(MERKLE_LEAF_INDEX_BY_MESSAGE_ID, &insertion.message_id())?;
+        }
+        let lowest_invalidated: Option<u32> =
+            self.retrieve_value_by_key(LOWEST_INVALIDATED_LEAF_INDEX, &bool::default())?;
+        if lowest_invalidated.map_or(true, |index| insertion.index() < index) {
+            self.store_value_by_key(
+                LOWEST_INVALIDATED_LEAF_INDEX,
+                &bool::default(),
+                &insertion.index(),
+            )?;
+        }
+        Ok(true)
+    }
+
+    /// Retrieve and clear the lowest leaf index of the tree insertions
+    /// invalidated by reorgs since the last call
+    pub fn take_lowest_invalidated_leaf_index(&self) -> DbResult<Option<u32>> {
+        let index = self.retrieve_value_by_key(LOWEST_INVALIDATED_LEAF_INDEX, &bool::default())?;
+        if index.is_some() {
+            self.delete_value_by_key(LOWEST_INVALIDATED_LEAF_INDEX, &bool::default())?;
+        }
+        Ok(index)
+    }
+
+    /// Store the nonce an interrupted bootstrap from a snapshot resumes from
+    pub fn store_snapshot_bootstrap_next_nonce(&self, nonce: u32) -> DbResult<()> {
+        self.store_value_by_key(SNAPSHOT_BOOTSTRAP_NEXT_NONCE, &bool::default(), &nonce)
+    }
+
+    /// Retrieve the nonce an interrupted bootstrap from a snapshot resumes
+    /// from, if a bootstrap was started
+    pub fn retrieve_snapshot_bootstrap_next_nonce(&self) -> DbResult<Option<u32>> {
+        self.retrieve_value_by_key(SNAPSHOT_BOOTSTRAP_NEXT_NONCE, &bool::default())
+    }
+
+    /// Mark the bootstrap from a snapshot as complete
+    pub fn store_snapshot_bootstrap_complete(&self) -> DbResult<()> {
+        self.store_value_by_key(SNAPSHOT_BOOTSTRAP_COMPLETE, &bool::default(), &true)
+    }
+
+    /// Whether the bootstrap from a snapshot completed
+    pub fn retrieve_snapshot_bootstrap_complete(&self) -> DbResult<bool> {
+        Ok(self
+            .retrieve_value_by_key(SNAPSHOT_BOOTSTRAP_COMPLETE, &bool::default())?
+            .unwrap_or(false))
+    }
+
+    /// Remove a gas payment that was orphaned by a reorg from the total paid
+    /// for its message, and unmark it as processed. Returns whether the
+    /// payment was processed.
+    pub fn invalidate_gas_payment(
+        &self,
+        indexed_payment: Indexed<InterchainGasPayment>,
+        log_meta: &LogMeta,
+    ) -> DbResult<bool> {
+        let payment = *(indexed_payment.inner());
+        let payment_meta = log_meta.into();
+        if !self
+            .retrieve_processed_by_gas_payment_meta(&payment_meta)?
+            .unwrap_or(false)
+        {
+            return Ok(false);
+        }
+        debug!(?payment, ?log_meta, "Removing orphaned gas payment from db");
+
+        self.delete_value_by_key(GAS_PAYMENT_META_PROCESSED, &payment_meta)?;
+        let gas_payment_key = payment.into();
+        if let Some(total) = self.retrieve_gas_payment_by_gas_payment_key(gas_payment_key)? {
+            self.store_interchain_gas_payment_data_by_gas_payment_key(
+                &gas_payment_key,
+                &(total - payment).into(),
+            )?;
+        }
+        if let Some(sequence) = indexed_payment.sequence {
+            self.delete_value_by_key(GAS_PAYMENT_BY_SEQUENCE, &sequence)?;
+            self.delete_value_by_key(GAS_PAYMENT_BLOCK_BY_SEQUENCE, &sequence)?;
+        }
+        Ok(true)
+    }
+
     /// Processes the gas expenditure and store the total expenditure for the
     /// message.
     pub fn process_gas_expenditure(&self, expenditure: InterchainGasExpenditure) -> DbResult<()> {
@@ -279,6 +469,123 @@ impl HyperlaneRocksDB {
             .unwrap_or_default()
             .complete(message_id))
     }
+
+    /// Store the gas reconciliation of the messages to `destination`
+    /// delivered during `epoch`
+    pub fn store_gas_reconciliation(
+        &self,
+        destination: u32,
+        epoch: u64,
+        data: &GasReconciliationData,
+    ) -> DbResult<()> {
+        self.store_encodable(
+            GAS_RECONCILIATION_BY_EPOCH,
+            gas_reconciliation_key(destination, epoch),
+            data,
+        )
+    }
+
+    /// Retrieve the gas reconciliation of the messages to `destination`
+    /// delivered during `epoch`
+    pub fn retrieve_gas_reconciliation(
+        &self,
+        destination: u32,
+        epoch: u64,
+    ) -> DbResult<Option<GasReconciliationData>> {
+        self.retrieve_decodable(
+            GAS_RECONCILIATION_BY_EPOCH,
+            gas_reconciliation_key(destination, epoch),
+        )
+    }
+
+    /// Store a gas oracle config update unless a later one was stored
+    /// already, returning whether it was stored
+    fn process_gas_oracle_config_update(
+        &self,
+        indexed_update: Indexed<GasOracleConfigUpdate>,
+        meta: &LogMeta,
+    ) -> DbResult<bool> {
+        let position = (meta.block_number, meta.log_index);
+        match *indexed_update.inner() {
+            GasOracleConfigUpdate::DestinationGasConfig {
+                remote_domain,
+                gas_oracle,
+                gas_overhead,
+            } => {
+                let stored: Option<DestinationGasConfigData> = self.retrieve_value_by_key(
+                    DESTINATION_GAS_CONFIG_BY_REMOTE_DOMAIN,
+                    &remote_domain,
+                )?;
+                if stored.is_some_and(|s| (s.block_number, s.log_index) >= position) {
+                    return Ok(false);
+                }
+                let data = DestinationGasConfigData {
+                    gas_oracle,
+                    gas_overhead,
+                    block_number: meta.block_number,
+                    log_index: meta.log_index,
+                };
+                self.store_value_by_key(
+                    DESTINATION_GAS_CONFIG_BY_REMOTE_DOMAIN,
+                    &remote_domain,
+                    &data,
+                )?;
+            }
+            GasOracleConfigUpdate::RemoteGasData {
+                gas_oracle,
+                remote_domain,
+                token_exchange_rate,
+                gas_price,
+            } => {
+                let key = remote_gas_data_key(gas_oracle, remote_domain);
+                let stored: Option<RemoteGasData> =
+                    self.retrieve_decodable(REMOTE_GAS_DATA_BY_GAS_ORACLE, &key)?;
+                if stored.is_some_and(|s| (s.block_number, s.log_index) >= position) {
+                    return Ok(false);
+                }
+                let data = RemoteGasData {
+                    token_exchange_rate,
+                    gas_price,
+                    block_number: meta.block_number,
+                    log_index: meta.log_index,
+                };
+                self.store_encodable(REMOTE_GAS_DATA_BY_GAS_ORACLE, key, &data)?;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Retrieve the gas oracle the interchain gas paymaster was last
+    /// configured to use for `remote_domain`
+    pub fn retrieve_gas_oracle(&self, remote_domain: u32) -> DbResult<Option<H256>> {
+        let config: Option<DestinationGasConfigData> =
+            self.retrieve_value_by_key(DESTINATION_GAS_CONFIG_BY_REMOTE_DOMAIN, &remote_domain)?;
+        Ok(config.map(|config| config.gas_oracle))
+    }
+
+    /// Retrieve the latest gas oracle config of the interchain gas paymaster
+    /// for `remote_domain`, if both the paymaster's config and the gas data
+    /// of its oracle were indexed
+    pub fn retrieve_gas_oracle_config(
+        &self,
+        remote_domain: u32,
+    ) -> DbResult<Option<GasOracleConfig>> {
+        let Some(config): Option<DestinationGasConfigData> =
+            self.retrieve_value_by_key(DESTINATION_GAS_CONFIG_BY_REMOTE_DOMAIN, &remote_domain)?
+        else {
+            return Ok(None);
+        };
+        let gas_data: Option<RemoteGasData> = self.retrieve_decodable(
+            REMOTE_GAS_DATA_BY_GAS_ORACLE,
+            remote_gas_data_key(config.gas_oracle, remote_domain),
+        )?;
+        Ok(gas_data.map(|gas_data| GasOracleConfig {
+            gas_oracle: config.gas_oracle,
+            gas_overhead: config.gas_overhead,
+            token_exchange_rate: gas_data.token_exchange_rate,
+            gas_price: gas_data.gas_price,
+        }))
+    }
 }
 
 #[async_trait]
@@ -289,6 +596,9 @@ impl HyperlaneLogStore<HyperlaneMessage> for HyperlaneRocksDB {
         let mut stored = 0;
         for (message, meta) in messages {
             let stored_message = self.store_message(message.inner(), meta.block_number)?;
+            let id = message.inner().id();
+            self.store_value_by_key(MESSAGE_DISPATCH_TRANSACTION, &id, &meta.transaction_id)?;
+            self.store_value_by_key(MESSAGE_DISPATCH_BLOCK_HASH, &id, &meta.block_hash)?;
             if stored_message {
                 stored += 1;
             }
@@ -298,6 +608,21 @@ impl HyperlaneLogStore<HyperlaneMessage> for HyperlaneRocksDB {
         }
         Ok(stored)
     }
+
+    /// Remove a list of dispatched messages that were orphaned by a reorg.
+    #[instrument(skip_all)]
+    async fn invalidate_logs(
+        &self,
+        messages: &[(Indexed<HyperlaneMessage>, LogMeta)],
+    ) -> Result<u32> {
+        let mut removed = 0;
+        for (message, _) in messages {
+            if self.invalidate_message(message.inner())? {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
 }
 
 async fn store_and_count_new<T: Copy>(
@@ -334,6 +659,21 @@ impl HyperlaneLogStore<InterchainGasPayment> for HyperlaneRocksDB {
         )
         .await
     }
+
+    /// Remove a list of interchain gas payments that were orphaned by a reorg.
+    #[instrument(skip_all)]
+    async fn invalidate_logs(
+        &self,
+        payments: &[(Indexed<InterchainGasPayment>, LogMeta)],
+    ) -> Result<u32> {
+        let mut removed = 0;
+        for (payment, meta) in payments {
+            if self.invalidate_gas_payment(*payment, meta)? {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
 }
 
 #[async_trait]
@@ -349,6 +689,21 @@ impl HyperlaneLogStore<MerkleTreeInsertion> for HyperlaneRocksDB {
         }
         Ok(insertions)
     }
+
+    /// Remove a list of tree insertions that were orphaned by a reorg.
+    #[instrument(skip_all)]
+    async fn invalidate_logs(
+        &self,
+        leaves: &[(Indexed<MerkleTreeInsertion>, LogMeta)],
+    ) -> Result<u32> {
+        let mut removed = 0;
+        for (insertion, _) in leaves {
+            if self.invalidate_tree_insertion(insertion.inner())? {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
 }
 
 #[async_trait]
@@ -410,6 +765,40 @@ impl HyperlaneWatermarkedLogStore<InterchainGasPayment> for HyperlaneRocksDB {
     }
 }
 
+#[async_trait]
+impl HyperlaneLogStore<GasOracleConfigUpdate> for HyperlaneRocksDB {
+    /// Store the latest gas oracle config of each remote domain
+    #[instrument(skip_all)]
+    async fn store_logs(
+        &self,
+        updates: &[(Indexed<GasOracleConfigUpdate>, LogMeta)],
+    ) -> Result<u32> {
+        store_and_count_new(
+            self,
+            updates,
+            "gas oracle config updates",
+            HyperlaneRocksDB::process_gas_oracle_config_update,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl HyperlaneWatermarkedLogStore<GasOracleConfigUpdate> for HyperlaneRocksDB {
+    /// Gets the block number high watermark
+    async fn retrieve_high_watermark(&self) -> Result<Option<u32>> {
+        let watermark = self.retrieve_decodable("", LATEST_INDEXED_GAS_ORACLE_CONFIG_BLOCK)?;
+        Ok(watermark)
+    }
+
+    /// Stores the block number high watermark
+    async fn store_high_watermark(&self, block_number: u32) -> Result<()> {
+        let result =
+            self.store_encodable("", LATEST_INDEXED_GAS_ORACLE_CONFIG_BLOCK, &block_number)?;
+        Ok(result)
+    }
+}
+
 // Keep this implementation for type compatibility with the `contract_syncs` sync builder
 #[async_trait]
 impl HyperlaneWatermarkedLogStore<HyperlaneMessage> for HyperlaneRocksDB {
@@ -438,6 +827,45 @@ impl HyperlaneWatermarkedLogStore<MerkleTreeInsertion> for HyperlaneRocksDB {
     }
 }
 
+#[async_trait]
+impl HyperlaneBackfillStore for HyperlaneRocksDB {
+    async fn is_backfill_range_complete(
+        &self,
+        label: &str,
+        range: &RangeInclusive<u32>,
+    ) -> Result<bool> {
+        let complete = self
+            .retrieve_decodable(BACKFILL_RANGE_COMPLETE, backfill_range_key(label, range))?
+            .unwrap_or(false);
+        Ok(complete)
+    }
+
+    async fn store_backfill_range_complete(
+        &self,
+        label: &str,
+        range: &RangeInclusive<u32>,
+    ) -> Result<()> {
+        self.store_encodable(
+            BACKFILL_RANGE_COMPLETE,
+            backfill_range_key(label, range),
+            &true,
+        )?;
+        Ok(())
+    }
+}
+
+fn backfill_range_key(label: &str, range: &RangeInclusive<u32>) -> String {
+    format!("{}_{}_{}", label, range.start(), range.end())
+}
+
+fn gas_reconciliation_key(destination: u32, epoch: u64) -> String {
+    format!("{destination}_{epoch}")
+}
+
+fn remote_gas_data_key(gas_oracle: H256, remote_domain: u32) -> String {
+    format!("{gas_oracle:?}_{remote_domain}")
+}
+
 impl HyperlaneDb for HyperlaneRocksDB {
     fn retrieve_highest_seen_message_nonce(&self) -> DbResult<Option<u32>> {
         self.retrieve_highest_seen_message_nonce_number()
@@ -598,6 +1026,29 @@ impl HyperlaneDb for HyperlaneRocksDB {
         self.retrieve_value_by_key(PENDING_MESSAGE_RETRY_COUNT_FOR_MESSAGE_ID, message_id)
     }
 
+    /// Store when a pending message is next retried, as a unix timestamp in
+    /// seconds, by its message id
+    fn store_pending_message_next_attempt_by_message_id(
+        &self,
+        message_id: &H256,
+        timestamp: &u64,
+    ) -> DbResult<()> {
+        self.store_value_by_key(
+            PENDING_MESSAGE_NEXT_ATTEMPT_FOR_MESSAGE_ID,
+            message_id,
+            timestamp,
+        )
+    }
+
+    /// Retrieve when a pending message is next retried, as a unix timestamp in
+    /// seconds, by its message id
+    fn retrieve_pending_message_next_attempt_by_message_id(
+        &self,
+        message_id: &H256,
+    ) -> DbResult<Option<u64>> {
+        self.retrieve_value_by_key(PENDING_MESSAGE_NEXT_ATTEMPT_FOR_MESSAGE_ID, message_id)
+    }
+
     fn store_merkle_tree_insertion_by_leaf_index(
         &self,
         leaf_index: &u32,
@@ -656,6 +1107,15 @@ impl HyperlaneDb for HyperlaneRocksDB {
         // There's no unit struct Encode/Decode impl, so just use `bool` and always use the `Default::default()` key
         self.retrieve_value_by_key(HIGHEST_SEEN_MESSAGE_NONCE, &bool::default())
     }
+
+    fn take_lowest_invalidated_message_nonce(&self) -> DbResult<Option<u32>> {
+        let nonce =
+            self.retrieve_value_by_key(LOWEST_INVALIDATED_MESSAGE_NONCE, &bool::default())?;
+        if nonce.is_some() {
+            self.delete_value_by_key(LOWEST_INVALIDATED_MESSAGE_NONCE, &bool::default())?;
+        }
+        Ok(nonce)
+    }
 }
 
 impl HyperlaneRocksDB {
@@ -668,6 +1128,10 @@ impl HyperlaneRocksDB {
         self.store_encodable(prefix, key.to_vec(), value)
     }
 
+    fn delete_value_by_key<K: Encode>(&self, prefix: impl AsRef<[u8]>, key: &K) -> DbResult<()> {
+        self.delete_value(prefix, key.to_vec())
+    }
+
     fn retrieve_value_by_key<K: Encode, V: Decode>(
         &self,
         prefix: impl AsRef<[u8]>,
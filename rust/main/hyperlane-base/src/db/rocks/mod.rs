@@ -74,4 +74,16 @@ impl DB {
     pub fn retrieve(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
         Ok(self.0.get(key)?)
     }
+
+    /// Delete a value from the DB
+    pub fn delete(&self, key: &[u8]) -> Result<()> {
+        Ok(self.0.delete(key)?)
+    }
+
+    /// Flush the write ahead log and the memtables to disk, e.g. before
+    /// shutting down
+    pub fn flush(&self) -> Result<()> {
+        self.0.flush_wal(true)?;
+        Ok(self.0.flush()?)
+    }
 }
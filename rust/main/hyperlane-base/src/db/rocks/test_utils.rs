@@ -31,8 +31,8 @@ where
 #[cfg(test)]
 mod test {
     use hyperlane_core::{
-        HyperlaneDomain, HyperlaneLogStore, HyperlaneMessage, Indexed, LogMeta,
-        RawHyperlaneMessage, H256, H512, U256,
+        GasOracleConfigUpdate, HyperlaneDomain, HyperlaneLogStore, HyperlaneMessage, Indexed,
+        LogMeta, RawHyperlaneMessage, H256, H512, U256,
     };
 
     use crate::db::HyperlaneRocksDB;
@@ -77,4 +77,45 @@ mod test {
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn db_keeps_latest_gas_oracle_config() {
+        run_test_db(|db| async move {
+            let db = HyperlaneRocksDB::new(
+                &HyperlaneDomain::new_test_domain("db_keeps_latest_gas_oracle_config"),
+                db,
+            );
+            let oracle = H256::from_low_u64_be(7);
+            let meta = |block_number| LogMeta {
+                block_number,
+                ..LogMeta::random()
+            };
+            let gas_data = |gas_price: u64| GasOracleConfigUpdate::RemoteGasData {
+                gas_oracle: oracle,
+                remote_domain: 12,
+                token_exchange_rate: U256::from(10),
+                gas_price: gas_price.into(),
+            };
+            let config = GasOracleConfigUpdate::DestinationGasConfig {
+                remote_domain: 12,
+                gas_oracle: oracle,
+                gas_overhead: U256::from(50_000),
+            };
+
+            assert_eq!(db.retrieve_gas_oracle_config(12).unwrap(), None);
+            // Updates indexed out of order don't override later ones
+            let updates = vec![
+                (Indexed::new(config), meta(1)),
+                (Indexed::new(gas_data(3)), meta(3)),
+                (Indexed::new(gas_data(2)), meta(2)),
+            ];
+            assert_eq!(db.store_logs(&updates).await.unwrap(), 2);
+
+            let stored = db.retrieve_gas_oracle_config(12).unwrap().unwrap();
+            assert_eq!(stored.gas_oracle, oracle);
+            assert_eq!(stored.gas_overhead, U256::from(50_000));
+            assert_eq!(stored.gas_price, U256::from(3));
+        })
+        .await;
+    }
 }
@@ -67,6 +67,22 @@ impl TypedDB {
             .map_err(Into::into)
     }
 
+    /// Retrieve the encoding of a value, e.g. to inspect it without decoding it
+    pub fn retrieve_encoded(
+        &self,
+        prefix: impl AsRef<[u8]>,
+        key: impl AsRef<[u8]>,
+    ) -> Result<Option<Vec<u8>>> {
+        self.db
+            .retrieve(&self.prefixed_key(prefix.as_ref(), key.as_ref()))
+    }
+
+    /// Delete the value stored under a key
+    pub fn delete_value(&self, prefix: impl AsRef<[u8]>, key: impl AsRef<[u8]>) -> Result<()> {
+        self.db
+            .delete(&self.prefixed_key(prefix.as_ref(), key.as_ref()))
+    }
+
     /// Store encodable kv pair
     pub fn store_keyed_encodable<K: Encode, V: Encode>(
         &self,
@@ -0,0 +1,274 @@
+//! Snapshots of the messages and gas payments indexed for an origin chain,
+//! e.g. by the scraper. A fresh agent database can be bootstrapped from one
+//! instead of indexing years of history from RPCs.
+
+use ethers::types::Bytes;
+use eyre::Result;
+use hyperlane_core::{
+    HyperlaneLogStore, HyperlaneMessage, HyperlaneMessageRef, HyperlaneWatermarkedLogStore,
+    Indexed, InterchainGasPayment, LogMeta, H256, H512, U256,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use super::HyperlaneRocksDB;
+
+/// The messages dispatched from an origin chain, in nonce order, and the gas
+/// payments made for them
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexSnapshot {
+    /// Domain of the origin chain
+    pub domain: u32,
+    /// The dispatched messages
+    pub messages: Vec<SnapshotMessage>,
+    /// The gas payments made for the messages
+    pub gas_payments: Vec<SnapshotGasPayment>,
+    /// The nonce the next page of the snapshot starts at, if there is one
+    #[serde(default)]
+    pub next_nonce: Option<u32>,
+}
+
+/// A message dispatched from the origin chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotMessage {
+    /// The encoded message
+    pub message: Bytes,
+    /// The mailbox the message was dispatched from
+    pub mailbox: H256,
+    /// The block the message was dispatched in
+    pub block_number: u64,
+    /// Hash of the block the message was dispatched in
+    pub block_hash: H256,
+    /// The transaction the message was dispatched by
+    pub transaction_id: H512,
+}
+
+/// A gas payment made for a message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotGasPayment {
+    /// Id of the message
+    pub message_id: H256,
+    /// Destination domain of the message
+    pub destination: u32,
+    /// Amount of native tokens paid
+    pub payment: U256,
+    /// Amount of destination gas paid for
+    pub gas_amount: U256,
+    /// The block the payment was made in
+    pub block_number: u64,
+    /// Hash of the block the payment was made in
+    pub block_hash: H256,
+    /// The transaction the payment was made by
+    pub transaction_id: H512,
+    /// Index of the payment log in its transaction
+    pub log_index: u64,
+}
+
+/// What restoring a snapshot stored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RestoredSnapshot {
+    /// Number of messages stored
+    pub messages: u32,
+    /// Number of gas payments stored
+    pub gas_payments: u32,
+    /// Nonce of the last message of the snapshot
+    pub last_nonce: Option<u32>,
+    /// Block of the last message of the snapshot
+    pub last_block: Option<u64>,
+}
+
+impl IndexSnapshot {
+    /// Store the messages dispatched from `mailbox` and their gas payments,
+    /// as if they had been indexed. Messages of other mailboxes and messages
+    /// that fail to decode are skipped.
+    ///
+    /// Watermarks aren't moved, since a page of a snapshot doesn't cover all
+    /// the payments made up to its last block; see
+    /// [`complete_snapshot_bootstrap`]. Merkle tree insertions aren't part of
+    /// snapshots, and are still indexed.
+    pub async fn restore(&self, db: &HyperlaneRocksDB, mailbox: H256) -> Result<RestoredSnapshot> {
+        let mut last_nonce = None;
+        let mut last_block = None;
+        let messages = self
+            .messages
+            .iter()
+            .filter(|message| message.mailbox == mailbox)
+            .filter_map(|message| {
+                let decoded = match HyperlaneMessageRef::new(&message.message) {
+                    Ok(decoded) => decoded.into_owned(),
+                    Err(err) => {
+                        warn!(?err, ?message, "Skipping malformed message of snapshot");
+                        return None;
+                    }
+                };
+                last_nonce = last_nonce.max(Some(decoded.nonce));
+                last_block = last_block.max(Some(message.block_number));
+                let meta = log_meta(
+                    message.block_number,
+                    message.block_hash,
+                    message.transaction_id,
+                    0,
+                );
+                let nonce = decoded.nonce;
+                Some((Indexed::new(decoded).with_sequence(nonce), meta))
+            })
+            .collect::<Vec<_>>();
+        let stored_messages =
+            HyperlaneLogStore::<HyperlaneMessage>::store_logs(db, &messages).await?;
+
+        let gas_payments = self
+            .gas_payments
+            .iter()
+            .map(|payment| {
+                let indexed = Indexed::new(InterchainGasPayment {
+                    message_id: payment.message_id,
+                    destination: payment.destination,
+                    payment: payment.payment,
+                    gas_amount: payment.gas_amount,
+                });
+                let meta = log_meta(
+                    payment.block_number,
+                    payment.block_hash,
+                    payment.transaction_id,
+                    payment.log_index,
+                );
+                (indexed, meta)
+            })
+            .collect::<Vec<_>>();
+        let stored_gas_payments =
+            HyperlaneLogStore::<InterchainGasPayment>::store_logs(db, &gas_payments).await?;
+
+        debug!(
+            domain = self.domain,
+            stored_messages, stored_gas_payments, last_nonce, "Restored snapshot"
+        );
+        Ok(RestoredSnapshot {
+            messages: stored_messages,
+            gas_payments: stored_gas_payments,
+            last_nonce,
+            last_block,
+        })
+    }
+}
+
+/// Mark the bootstrap of `db` from a snapshot as complete, once every page
+/// was restored. The gas payment watermark is moved up to `last_block`, the
+/// block of the last message received, so that block indexed gas payments
+/// up to it aren't indexed again.
+pub async fn complete_snapshot_bootstrap(
+    db: &HyperlaneRocksDB,
+    last_block: Option<u64>,
+) -> Result<()> {
+    if let Some(last_block) = last_block {
+        let last_block = u32::try_from(last_block)?;
+        let watermark =
+            HyperlaneWatermarkedLogStore::<InterchainGasPayment>::retrieve_high_watermark(db)
+                .await?;
+        if watermark < Some(last_block) {
+            HyperlaneWatermarkedLogStore::<InterchainGasPayment>::store_high_watermark(
+                db, last_block,
+            )
+            .await?;
+        }
+    }
+    db.store_snapshot_bootstrap_complete()?;
+    Ok(())
+}
+
+fn log_meta(block_number: u64, block_hash: H256, transaction_id: H512, log_index: u64) -> LogMeta {
+    LogMeta {
+        address: H256::zero(),
+        block_number,
+        block_hash,
+        transaction_id,
+        transaction_index: 0,
+        log_index: log_index.into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::{HyperlaneDomain, RawHyperlaneMessage};
+
+    use super::*;
+    use crate::db::test_utils;
+
+    #[tokio::test]
+    async fn restores_messages_and_gas_payments_of_mailbox() {
+        test_utils::run_test_db(|db| async move {
+            let db = HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("test"), db);
+            let mailbox = H256::repeat_byte(1);
+            let message = |nonce, mailbox| {
+                let message = HyperlaneMessage {
+                    nonce,
+                    ..Default::default()
+                };
+                SnapshotMessage {
+                    message: RawHyperlaneMessage::from(&message).into(),
+                    mailbox,
+                    block_number: 100 + nonce as u64,
+                    block_hash: H256::repeat_byte(2),
+                    transaction_id: H512::repeat_byte(3),
+                }
+            };
+            let message_id = HyperlaneMessage {
+                nonce: 1,
+                ..Default::default()
+            }
+            .id();
+            let snapshot = IndexSnapshot {
+                domain: 1,
+                messages: vec![
+                    message(0, mailbox),
+                    message(1, mailbox),
+                    message(0, H256::repeat_byte(9)),
+                ],
+                gas_payments: vec![SnapshotGasPayment {
+                    message_id,
+                    destination: 0,
+                    payment: 10.into(),
+                    gas_amount: 20.into(),
+                    block_number: 105,
+                    block_hash: H256::repeat_byte(4),
+                    transaction_id: H512::repeat_byte(5),
+                    log_index: 2,
+                }],
+                next_nonce: None,
+            };
+
+            let restored = snapshot.restore(&db, mailbox).await.unwrap();
+            assert_eq!(
+                restored,
+                RestoredSnapshot {
+                    messages: 2,
+                    gas_payments: 1,
+                    last_nonce: Some(1),
+                    last_block: Some(101),
+                }
+            );
+            assert_eq!(
+                db.retrieve_message_by_nonce(1).unwrap().unwrap().id(),
+                message_id
+            );
+            // The watermark only moves once the bootstrap completes, and only
+            // up to the last message received
+            let watermark =
+                HyperlaneWatermarkedLogStore::<InterchainGasPayment>::retrieve_high_watermark;
+            assert_eq!(watermark(&db).await.unwrap(), None);
+
+            // Restoring again stores nothing new
+            let restored = snapshot.restore(&db, mailbox).await.unwrap();
+            assert_eq!((restored.messages, restored.gas_payments), (0, 0));
+
+            complete_snapshot_bootstrap(&db, restored.last_block)
+                .await
+                .unwrap();
+            assert_eq!(watermark(&db).await.unwrap(), Some(101));
+            assert!(db.retrieve_snapshot_bootstrap_complete().unwrap());
+        })
+        .await
+    }
+}
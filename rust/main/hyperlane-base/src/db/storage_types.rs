@@ -126,3 +126,145 @@ impl Decode for InterchainGasExpenditureData {
         })
     }
 }
+
+/// The gas paid for and spent on the messages of a route delivered during an
+/// epoch, for reconciling gas payments with the cost of deliveries.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct GasReconciliationData {
+    /// The number of messages delivered
+    pub messages: u64,
+    /// The tokens paid on the origin for the delivery of the messages
+    pub payment: U256,
+    /// The destination gas paid for
+    pub gas_amount: U256,
+    /// The destination gas spent delivering the messages
+    pub gas_used: U256,
+    /// The destination tokens spent delivering the messages
+    pub tokens_used: U256,
+}
+
+impl std::ops::Add for GasReconciliationData {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            messages: self.messages + rhs.messages,
+            payment: self.payment.saturating_add(rhs.payment),
+            gas_amount: self.gas_amount.saturating_add(rhs.gas_amount),
+            gas_used: self.gas_used.saturating_add(rhs.gas_used),
+            tokens_used: self.tokens_used.saturating_add(rhs.tokens_used),
+        }
+    }
+}
+
+impl Encode for GasReconciliationData {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: Write,
+    {
+        Ok(self.messages.write_to(writer)?
+            + self.payment.write_to(writer)?
+            + self.gas_amount.write_to(writer)?
+            + self.gas_used.write_to(writer)?
+            + self.tokens_used.write_to(writer)?)
+    }
+}
+
+impl Decode for GasReconciliationData {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: Read,
+        Self: Sized,
+    {
+        Ok(Self {
+            messages: u64::read_from(reader)?,
+            payment: U256::read_from(reader)?,
+            gas_amount: U256::read_from(reader)?,
+            gas_used: U256::read_from(reader)?,
+            tokens_used: U256::read_from(reader)?,
+        })
+    }
+}
+
+/// The gas oracle and gas overhead an interchain gas paymaster set for a
+/// remote domain, and the position of the event setting them, so that events
+/// indexed out of order don't override newer ones.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DestinationGasConfigData {
+    /// The gas oracle quoting the gas of the remote domain
+    pub gas_oracle: H256,
+    /// The destination gas added to the gas limit of messages
+    pub gas_overhead: U256,
+    /// The block of the event
+    pub block_number: u64,
+    /// The index of the event in its block
+    pub log_index: U256,
+}
+
+impl Encode for DestinationGasConfigData {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: Write,
+    {
+        Ok(self.gas_oracle.write_to(writer)?
+            + self.gas_overhead.write_to(writer)?
+            + self.block_number.write_to(writer)?
+            + self.log_index.write_to(writer)?)
+    }
+}
+
+impl Decode for DestinationGasConfigData {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: Read,
+        Self: Sized,
+    {
+        Ok(Self {
+            gas_oracle: H256::read_from(reader)?,
+            gas_overhead: U256::read_from(reader)?,
+            block_number: u64::read_from(reader)?,
+            log_index: U256::read_from(reader)?,
+        })
+    }
+}
+
+/// The gas data a storage gas oracle set for a remote domain, and the
+/// position of the event setting it
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RemoteGasData {
+    /// The exchange rate of the remote token quoted in the local token
+    pub token_exchange_rate: U256,
+    /// The gas price on the remote domain
+    pub gas_price: U256,
+    /// The block of the event
+    pub block_number: u64,
+    /// The index of the event in its block
+    pub log_index: U256,
+}
+
+impl Encode for RemoteGasData {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: Write,
+    {
+        Ok(self.token_exchange_rate.write_to(writer)?
+            + self.gas_price.write_to(writer)?
+            + self.block_number.write_to(writer)?
+            + self.log_index.write_to(writer)?)
+    }
+}
+
+impl Decode for RemoteGasData {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: Read,
+        Self: Sized,
+    {
+        Ok(Self {
+            token_exchange_rate: U256::read_from(reader)?,
+            gas_price: U256::read_from(reader)?,
+            block_number: u64::read_from(reader)?,
+            log_index: U256::read_from(reader)?,
+        })
+    }
+}
@@ -17,6 +17,26 @@ pub mod db;
 
 mod metadata;
 
+/// Validation of an agent's config against its chains
+mod config_report;
+pub use config_report::*;
+
+/// Notifications of critical events
+mod notifications;
+pub use notifications::*;
+
+/// Pausing of the pipelines of halted chains
+mod chain_halt;
+pub use chain_halt::*;
+
+/// Graceful shutdown of agents
+mod shutdown;
+pub use shutdown::*;
+
+/// Restarting of failed tasks
+mod supervisor;
+pub use supervisor::*;
+
 pub mod metrics;
 pub use metrics::*;
 
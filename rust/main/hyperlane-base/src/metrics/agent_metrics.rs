@@ -6,11 +6,10 @@ use std::time::Duration;
 
 use derive_builder::Builder;
 use eyre::Result;
-use hyperlane_core::metrics::agent::decimals_by_protocol;
-use hyperlane_core::metrics::agent::u256_as_scaled_f64;
 use hyperlane_core::metrics::agent::METRICS_SCRAPE_INTERVAL;
 use hyperlane_core::HyperlaneDomain;
 use hyperlane_core::HyperlaneProvider;
+use hyperlane_core::NativeAmount;
 use maplit::hashmap;
 use prometheus::GaugeVec;
 use prometheus::IntGaugeVec;
@@ -19,7 +18,7 @@ use tracing::info_span;
 use tracing::{debug, instrument::Instrumented, trace, warn, Instrument};
 
 use crate::settings::ChainConf;
-use crate::CoreMetrics;
+use crate::{spawn_named, CoreMetrics};
 
 /// Expected label names for the `wallet_balance` metric.
 pub const WALLET_BALANCE_LABELS: &[&str] = &[
@@ -39,6 +38,12 @@ pub const BLOCK_HEIGHT_LABELS: &[&str] = &["chain"];
 /// Help string for the metric.
 pub const BLOCK_HEIGHT_HELP: &str = "Tracks the current block height of the chain";
 
+/// Expected label names for the `block_finality_lag` metric.
+pub const BLOCK_FINALITY_LAG_LABELS: &[&str] = &["chain"];
+/// Help string for the metric.
+pub const BLOCK_FINALITY_LAG_HELP: &str =
+    "Tracks the number of blocks between the latest and the finalized block of the chain";
+
 /// Expected label names for the `gas_price` metric.
 pub const GAS_PRICE_LABELS: &[&str] = &["chain"];
 /// Help string for the metric.
@@ -80,6 +85,14 @@ pub struct ChainMetrics {
     #[builder(setter(into))]
     pub block_height: IntGaugeVec,
 
+    /// Tracks the number of blocks between the latest and the finalized block
+    /// of the chain, from the finalized block tag if the chain uses one, or
+    /// else the reorg period.
+    /// - `chain`: the chain name (or ID if the name is unknown) of the chain
+    ///   the block numbers refer to.
+    #[builder(setter(into, strip_option), default)]
+    pub block_finality_lag: Option<IntGaugeVec>,
+
     /// Tracks the current gas price of the chain. Uses the base_fee_per_gas if
     /// available or else sets this to none.
     /// TODO: use the median of the transactions.
@@ -96,6 +109,11 @@ pub(crate) fn create_chain_metrics(metrics: &CoreMetrics) -> Result<ChainMetrics
             BLOCK_HEIGHT_HELP,
             BLOCK_HEIGHT_LABELS,
         )?)
+        .block_finality_lag(metrics.new_int_gauge(
+            "block_finality_lag",
+            BLOCK_FINALITY_LAG_HELP,
+            BLOCK_FINALITY_LAG_LABELS,
+        )?)
         .gas_price(metrics.new_gauge("gas_price", GAS_PRICE_HELP, GAS_PRICE_LABELS)?)
         .build()?)
 }
@@ -122,6 +140,7 @@ pub struct MetricsUpdater {
     chain_metrics: ChainMetrics,
     conf: AgentMetricsConf,
     provider: Box<dyn HyperlaneProvider>,
+    reorg_period: u32,
 }
 
 impl MetricsUpdater {
@@ -141,6 +160,7 @@ impl MetricsUpdater {
             chain_metrics,
             conf: agent_metrics_conf,
             provider,
+            reorg_period: chain_conf.reorg_period,
         })
     }
 
@@ -156,7 +176,8 @@ impl MetricsUpdater {
 
         match self.provider.get_balance(wallet_addr.clone()).await {
             Ok(balance) => {
-                let balance = u256_as_scaled_f64(balance, self.conf.domain.domain_protocol());
+                let balance = NativeAmount::of_protocol(balance, self.conf.domain.domain_protocol())
+                    .to_whole_f64();
                 trace!("Wallet {wallet_name} ({wallet_addr}) on chain {chain} balance is {balance} of the native currency");
                 wallet_balance_metric
                 .with(&hashmap! {
@@ -198,11 +219,20 @@ impl MetricsUpdater {
         block_height
             .with(&hashmap! { "chain" => chain })
             .set(height);
+        if let Some(block_finality_lag) = &self.chain_metrics.block_finality_lag {
+            let lag = match chain_metrics.finalized_block_number {
+                Some(finalized) => chain_metrics.latest_block.number.saturating_sub(finalized),
+                None => self.reorg_period.into(),
+            };
+            block_finality_lag
+                .with(&hashmap! { "chain" => chain })
+                .set(lag as i64);
+        }
         if let Some(gas_price) = gas_price {
-            let protocol = self.conf.domain.domain_protocol();
-            let decimals_scale = 10f64.powf(decimals_by_protocol(protocol).into());
-            let gas = u256_as_scaled_f64(chain_metrics.min_gas_price.unwrap_or_default(), protocol)
-                * decimals_scale;
+            let gas = chain_metrics
+                .min_gas_price
+                .unwrap_or_default()
+                .to_f64_lossy();
             trace!(
                 chain,
                 gas = format!("{gas:.2}"),
@@ -225,7 +255,8 @@ impl MetricsUpdater {
 
     /// Spawns a tokio task to update the metrics
     pub fn spawn(self) -> Instrumented<JoinHandle<()>> {
-        tokio::spawn(async move {
+        let domain = self.conf.domain.clone();
+        spawn_named("metrics_updater", Some(&domain), async move {
             self.start_updating_on_interval(METRICS_SCRAPE_INTERVAL)
                 .await;
         })
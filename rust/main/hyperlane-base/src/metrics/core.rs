@@ -42,6 +42,11 @@ pub struct CoreMetrics {
 
     operations_processed_count: IntCounterVec,
     messages_processed_count: IntCounterVec,
+    message_delivery_failures_count: IntCounterVec,
+    gas_limit_overrides_count: IntCounterVec,
+    recipient_ism_cache_lookups_count: IntCounterVec,
+    checkpoint_fetch_duration_seconds: HistogramVec,
+    task_restarts_count: IntCounterVec,
 
     latest_checkpoint: IntGaugeVec,
 
@@ -167,6 +172,57 @@ impl CoreMetrics {
             registry
         )?;
 
+        let message_delivery_failures_count = register_int_counter_vec_with_registry!(
+            opts!(
+                namespaced!("message_delivery_failures_count"),
+                "Number of failed attempts to deliver messages, by reason",
+                const_labels_ref
+            ),
+            &["origin", "remote", "reason"],
+            registry
+        )?;
+
+        let gas_limit_overrides_count = register_int_counter_vec_with_registry!(
+            opts!(
+                namespaced!("gas_limit_overrides_count"),
+                "Number of delivery gas estimates raised by a gas limit override",
+                const_labels_ref
+            ),
+            &["origin", "remote"],
+            registry
+        )?;
+
+        let recipient_ism_cache_lookups_count = register_int_counter_vec_with_registry!(
+            opts!(
+                namespaced!("recipient_ism_cache_lookups_count"),
+                "Number of lookups of the ISM of a recipient in the relayer's cache",
+                const_labels_ref
+            ),
+            &["remote", "result"],
+            registry
+        )?;
+
+        let checkpoint_fetch_duration_seconds = register_histogram_vec_with_registry!(
+            histogram_opts!(
+                namespaced!("checkpoint_fetch_duration_seconds"),
+                "Time taken to fetch from a validator's checkpoint storage",
+                vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0],
+                const_labels.clone()
+            ),
+            &["validator", "request", "result"],
+            registry
+        )?;
+
+        let task_restarts_count = register_int_counter_vec_with_registry!(
+            opts!(
+                namespaced!("task_restarts_count"),
+                "Number of times a supervised task was restarted after it panicked or exited",
+                const_labels_ref
+            ),
+            &["role", "chain"],
+            registry
+        )?;
+
         Ok(Self {
             agent_name: for_agent.into(),
             registry,
@@ -182,6 +238,11 @@ impl CoreMetrics {
 
             operations_processed_count,
             messages_processed_count,
+            message_delivery_failures_count,
+            gas_limit_overrides_count,
+            recipient_ism_cache_lookups_count,
+            checkpoint_fetch_duration_seconds,
+            task_restarts_count,
 
             latest_checkpoint,
 
@@ -372,6 +433,59 @@ impl CoreMetrics {
         self.messages_processed_count.clone()
     }
 
+    /// Count of failed attempts to prepare, submit or confirm the delivery of
+    /// messages.
+    ///
+    /// Labels:
+    /// - `origin`: Chain the message came from.
+    /// - `remote`: Chain we tried to deliver the message to.
+    /// - `reason`: Class of the failure, e.g. `gas_enforcement` or `underpriced`.
+    pub fn message_delivery_failures_count(&self) -> IntCounterVec {
+        self.message_delivery_failures_count.clone()
+    }
+
+    /// Count of delivery gas estimates raised by a gas limit override of
+    /// their recipient.
+    ///
+    /// Labels:
+    /// - `origin`: Chain the message came from.
+    /// - `remote`: Chain the message is delivered to.
+    pub fn gas_limit_overrides_count(&self) -> IntCounterVec {
+        self.gas_limit_overrides_count.clone()
+    }
+
+    /// Count of lookups of the ISM of a recipient in the relayer's cache, by
+    /// whether the cached ISM was used. The ratio of hits to all lookups is
+    /// the hit rate of the cache.
+    ///
+    /// Labels:
+    /// - `remote`: Chain of the recipient.
+    /// - `result`: `hit` or `miss`.
+    pub fn recipient_ism_cache_lookups_count(&self) -> IntCounterVec {
+        self.recipient_ism_cache_lookups_count.clone()
+    }
+
+    /// Time taken by each request to a validator's checkpoint storage, whether
+    /// it answered or not. Lets slow or unavailable storage be told apart from
+    /// validators that haven't signed a checkpoint.
+    ///
+    /// Labels:
+    /// - `validator`: Address of the validator.
+    /// - `request`: `latest_index` or `checkpoint`.
+    /// - `result`: `success`, `failure` or `timeout`.
+    pub fn checkpoint_fetch_duration_seconds(&self) -> HistogramVec {
+        self.checkpoint_fetch_duration_seconds.clone()
+    }
+
+    /// Count of restarts of supervised tasks, after they panicked or exited.
+    ///
+    /// Labels:
+    /// - `role`: What the task does, e.g. `message_sync`.
+    /// - `chain`: Chain the task works for, empty if none.
+    pub fn task_restarts_count(&self) -> IntCounterVec {
+        self.task_restarts_count.clone()
+    }
+
     /// Measure of span durations provided by tracing.
     ///
     /// Labels:
@@ -17,5 +17,20 @@ pub(crate) fn create_json_rpc_client_metrics(
             REQUEST_DURATION_SECONDS_HELP,
             REQUEST_DURATION_SECONDS_LABELS,
         )?)
+        .request_payload_bytes(metrics.new_int_counter(
+            "request_payload_bytes",
+            REQUEST_PAYLOAD_BYTES_HELP,
+            REQUEST_PAYLOAD_BYTES_LABELS,
+        )?)
+        .request_queue_wait_seconds(metrics.new_counter(
+            "request_queue_wait_seconds",
+            REQUEST_QUEUE_WAIT_SECONDS_HELP,
+            REQUEST_QUEUE_WAIT_SECONDS_LABELS,
+        )?)
+        .fallback_provider_rank(metrics.new_int_gauge(
+            "fallback_provider_rank",
+            FALLBACK_PROVIDER_RANK_HELP,
+            FALLBACK_PROVIDER_RANK_LABELS,
+        )?)
         .build()?)
 }
@@ -10,5 +10,9 @@ mod core;
 mod agent_metrics;
 mod json_rpc_client;
 mod provider;
+mod rpc_usage;
+mod runtime;
 
 pub use self::agent_metrics::*;
+pub use self::rpc_usage::*;
+pub use self::runtime::*;
@@ -0,0 +1,221 @@
+//! Periodic summaries of the RPC requests agents make, so that the bills of
+//! RPC providers can be attributed to the chains and request methods behind
+//! them. Enabled with `log.rpcUsageSummaryInterval`.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    time::Duration,
+};
+
+use prometheus::{core::Collector, proto::Metric, IntCounterVec};
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::{info, info_span, instrument::Instrumented, Instrument};
+
+use crate::{spawn_named, CoreMetrics};
+
+/// The RPC usage of a provider node of a chain over a summary period
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RpcUsage {
+    /// Name of the chain the requests were made on
+    pub chain: String,
+    /// Host of the node the requests were made to, e.g. `alchemy.com`
+    pub provider_node: String,
+    /// Number of requests made, by method
+    pub requests_by_method: BTreeMap<String, u64>,
+    /// Bytes of request params sent, by method
+    pub payload_bytes_by_method: BTreeMap<String, u64>,
+}
+
+impl RpcUsage {
+    /// Total number of requests made
+    pub fn requests(&self) -> u64 {
+        self.requests_by_method.values().sum()
+    }
+
+    /// Total bytes of request params sent
+    pub fn payload_bytes(&self) -> u64 {
+        self.payload_bytes_by_method.values().sum()
+    }
+}
+
+/// Where RPC usage summaries are exported to
+pub trait RpcUsageSink: Debug + Send + Sync {
+    /// Export the usage of every provider node over the last `period`. Nodes
+    /// which weren't used over the period are left out.
+    fn export(&self, period: Duration, usage: &[RpcUsage]);
+}
+
+/// Logs a line per provider node used over the period
+#[derive(Debug, Default)]
+pub struct LogRpcUsageSink;
+
+impl RpcUsageSink for LogRpcUsageSink {
+    fn export(&self, period: Duration, usage: &[RpcUsage]) {
+        for node in usage {
+            info!(
+                chain = %node.chain,
+                provider_node = %node.provider_node,
+                period_secs = period.as_secs(),
+                requests = node.requests(),
+                payload_bytes = node.payload_bytes(),
+                requests_by_method = ?node.requests_by_method,
+                payload_bytes_by_method = ?node.payload_bytes_by_method,
+                "RPC usage summary"
+            );
+        }
+    }
+}
+
+/// (chain, provider_node, method)
+type UsageKey = (String, String, String);
+
+/// Summarizes the RPC request metrics over a period, and exports the summary
+/// to its sinks
+#[derive(Debug)]
+pub struct RpcUsageReporter {
+    request_count: Option<IntCounterVec>,
+    request_payload_bytes: Option<IntCounterVec>,
+    sinks: Vec<Box<dyn RpcUsageSink>>,
+    /// The (requests, payload bytes) counted as of the last summary
+    last_totals: HashMap<UsageKey, (u64, u64)>,
+}
+
+impl RpcUsageReporter {
+    /// Summarize the usage recorded by the RPC client metrics of `metrics`
+    pub fn new(metrics: &CoreMetrics, sinks: Vec<Box<dyn RpcUsageSink>>) -> Self {
+        let metrics = metrics.json_rpc_client_metrics();
+        Self {
+            request_count: metrics.request_count().cloned(),
+            request_payload_bytes: metrics.request_payload_bytes().cloned(),
+            sinks,
+            last_totals: HashMap::new(),
+        }
+    }
+
+    /// The (requests, payload bytes) counted since startup
+    fn totals(&self) -> HashMap<UsageKey, (u64, u64)> {
+        let mut totals: HashMap<UsageKey, (u64, u64)> = HashMap::new();
+        // Requests are also labelled by status, which is summed over
+        for metric in collect(self.request_count.as_ref()) {
+            totals.entry(usage_key(&metric)).or_default().0 +=
+                metric.get_counter().get_value() as u64;
+        }
+        for metric in collect(self.request_payload_bytes.as_ref()) {
+            totals.entry(usage_key(&metric)).or_default().1 +=
+                metric.get_counter().get_value() as u64;
+        }
+        totals
+    }
+
+    /// The usage since the last summary, by provider node
+    fn summarize(&mut self) -> Vec<RpcUsage> {
+        let totals = self.totals();
+        let mut usage: BTreeMap<(String, String), RpcUsage> = BTreeMap::new();
+        for (key, (requests, payload_bytes)) in &totals {
+            let (last_requests, last_payload_bytes) =
+                self.last_totals.get(key).copied().unwrap_or_default();
+            let requests = requests.saturating_sub(last_requests);
+            let payload_bytes = payload_bytes.saturating_sub(last_payload_bytes);
+            if requests == 0 && payload_bytes == 0 {
+                continue;
+            }
+            let (chain, provider_node, method) = key.clone();
+            let node = usage
+                .entry((chain.clone(), provider_node.clone()))
+                .or_insert_with(|| RpcUsage {
+                    chain,
+                    provider_node,
+                    ..Default::default()
+                });
+            node.requests_by_method.insert(method.clone(), requests);
+            node.payload_bytes_by_method.insert(method, payload_bytes);
+        }
+        self.last_totals = totals;
+        usage.into_values().collect()
+    }
+
+    /// Exports a summary of the RPC usage every `period`
+    pub async fn start_reporting_on_interval(mut self, period: Duration) {
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        // The first tick completes immediately, before any request is made
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let usage = self.summarize();
+            for sink in &self.sinks {
+                sink.export(period, &usage);
+            }
+        }
+    }
+
+    /// Spawns a tokio task to export the summaries
+    pub fn spawn(self, period: Duration) -> Instrumented<JoinHandle<()>> {
+        spawn_named("rpc_usage_reporter", None, async move {
+            self.start_reporting_on_interval(period).await;
+        })
+        .instrument(info_span!("RpcUsageReporter"))
+    }
+}
+
+fn collect(counter: Option<&IntCounterVec>) -> Vec<Metric> {
+    counter
+        .into_iter()
+        .flat_map(|counter| counter.collect())
+        .flat_map(|mut family| family.take_metric().into_vec())
+        .collect()
+}
+
+fn usage_key(metric: &Metric) -> UsageKey {
+    let label = |name: &str| {
+        metric
+            .get_label()
+            .iter()
+            .find(|label| label.get_name() == name)
+            .map(|label| label.get_value().to_owned())
+            .unwrap_or_default()
+    };
+    (label("chain"), label("provider_node"), label("method"))
+}
+
+#[cfg(test)]
+mod test {
+    use prometheus::Registry;
+
+    use super::*;
+
+    #[test]
+    fn test_summarize_usage_since_last_summary() {
+        let core_metrics = CoreMetrics::new("test", 9090, Registry::new()).unwrap();
+        let metrics = core_metrics.json_rpc_client_metrics();
+        let mut reporter = RpcUsageReporter::new(&core_metrics, vec![]);
+        let request_count = metrics.request_count().unwrap();
+        let payload_bytes = metrics.request_payload_bytes().unwrap();
+
+        for status in ["success", "success", "failure"] {
+            request_count
+                .with_label_values(&["alchemy.com", "ethereum", "eth_getLogs", status])
+                .inc();
+        }
+        payload_bytes
+            .with_label_values(&["alchemy.com", "ethereum", "eth_getLogs"])
+            .inc_by(300);
+        let usage = reporter.summarize();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].chain, "ethereum");
+        assert_eq!(usage[0].provider_node, "alchemy.com");
+        assert_eq!(usage[0].requests_by_method["eth_getLogs"], 3);
+        assert_eq!(usage[0].payload_bytes(), 300);
+
+        // Only the requests made since the last summary are counted
+        request_count
+            .with_label_values(&["alchemy.com", "ethereum", "eth_blockNumber", "success"])
+            .inc();
+        let usage = reporter.summarize();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].requests(), 1);
+        assert!(!usage[0].requests_by_method.contains_key("eth_getLogs"));
+        assert!(reporter.summarize().is_empty());
+    }
+}
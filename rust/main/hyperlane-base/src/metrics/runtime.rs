@@ -0,0 +1,220 @@
+//! Instrumentation of the tokio runtime and of the tasks spawned by agents,
+//! to find out which tasks are starving the runtime. Only enabled with
+//! `log.runtimeInstrumentation`, alongside the tokio-console server.
+
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        LazyLock, Mutex,
+    },
+    time::Duration,
+};
+
+use eyre::Result;
+use hyperlane_core::HyperlaneDomain;
+use prometheus::{GaugeVec, IntGaugeVec};
+use tokio::{runtime::Handle, task::JoinHandle, time::MissedTickBehavior};
+use tokio_metrics::{RuntimeMonitor, TaskMonitor};
+use tracing::{info_span, instrument::Instrumented, Instrument};
+
+use crate::CoreMetrics;
+
+/// Interval at which the runtime and task metrics are sampled
+const RUNTIME_METRICS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Expected label names for the task metrics.
+pub const TASK_LABELS: &[&str] = &["role", "chain"];
+
+/// Whether spawned tasks are instrumented, set once the runtime metrics are
+/// started
+static TASKS_INSTRUMENTED: AtomicBool = AtomicBool::new(false);
+
+/// Monitors of the instrumented tasks, by role and chain
+static TASK_MONITORS: LazyLock<Mutex<BTreeMap<(String, String), TaskMonitor>>> =
+    LazyLock::new(Default::default);
+
+/// Spawn a task named after its role, e.g. `message_sync`, and the chain it
+/// works for, if any, so it can be told apart in tokio-console. If runtime
+/// instrumentation is enabled, the time spent polling the task and waiting
+/// to be polled is also accounted for under these labels.
+pub fn spawn_named<F>(
+    role: &str,
+    chain: Option<&HyperlaneDomain>,
+    future: F,
+) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let chain = chain.map(|domain| domain.name()).unwrap_or_default();
+    let name = if chain.is_empty() {
+        role.to_owned()
+    } else {
+        format!("{role}::{chain}")
+    };
+    let builder = tokio::task::Builder::new().name(&name);
+    let spawned = if TASKS_INSTRUMENTED.load(Ordering::Relaxed) {
+        let monitor = TASK_MONITORS
+            .lock()
+            .unwrap()
+            .entry((role.to_owned(), chain.to_owned()))
+            .or_default()
+            .clone();
+        builder.spawn(TaskMonitor::instrument(&monitor, future))
+    } else {
+        builder.spawn(future)
+    };
+    spawned.unwrap_or_else(|err| panic!("Failed to spawn task {name}: {err}"))
+}
+
+/// Periodically exports metrics of the tokio runtime and of the tasks
+/// spawned with `spawn_named`
+#[derive(Debug)]
+pub struct RuntimeMetricsUpdater {
+    workers: IntGaugeVec,
+    alive_tasks: IntGaugeVec,
+    injection_queue_depth: IntGaugeVec,
+    local_queue_depth: IntGaugeVec,
+    busy_ratio: GaugeVec,
+    mean_poll_duration: GaugeVec,
+    forced_yields: IntGaugeVec,
+    task_alive: IntGaugeVec,
+    task_poll_duration: GaugeVec,
+    task_scheduled_duration: GaugeVec,
+    task_slow_polls: IntGaugeVec,
+}
+
+impl RuntimeMetricsUpdater {
+    /// Register the runtime metrics, and start instrumenting the tasks
+    /// spawned from now on
+    pub fn new(metrics: &CoreMetrics) -> Result<Self> {
+        let updater = Self {
+            workers: metrics.new_int_gauge(
+                "tokio_workers",
+                "Number of worker threads of the tokio runtime",
+                &[],
+            )?,
+            alive_tasks: metrics.new_int_gauge(
+                "tokio_alive_tasks",
+                "Number of tasks alive in the tokio runtime",
+                &[],
+            )?,
+            injection_queue_depth: metrics.new_int_gauge(
+                "tokio_injection_queue_depth",
+                "Number of tasks waiting in the global queue of the tokio runtime",
+                &[],
+            )?,
+            local_queue_depth: metrics.new_int_gauge(
+                "tokio_local_queue_depth",
+                "Number of tasks waiting in the local queues of the tokio workers",
+                &[],
+            )?,
+            busy_ratio: metrics.new_gauge(
+                "tokio_busy_ratio",
+                "Share of the last interval the tokio workers spent polling tasks",
+                &[],
+            )?,
+            mean_poll_duration: metrics.new_gauge(
+                "tokio_mean_poll_duration_seconds",
+                "Mean time the tokio workers spent polling a task over the last interval",
+                &[],
+            )?,
+            forced_yields: metrics.new_int_gauge(
+                "tokio_budget_forced_yields",
+                "Number of times tasks were forced to yield over the last interval",
+                &[],
+            )?,
+            task_alive: metrics.new_int_gauge(
+                "tokio_task_alive",
+                "Number of spawned tasks alive, by role and chain",
+                TASK_LABELS,
+            )?,
+            task_poll_duration: metrics.new_gauge(
+                "tokio_task_poll_duration_seconds",
+                "Total time spent polling the spawned tasks, by role and chain",
+                TASK_LABELS,
+            )?,
+            task_scheduled_duration: metrics.new_gauge(
+                "tokio_task_scheduled_duration_seconds",
+                "Total time the spawned tasks waited to be polled once woken, by role and chain",
+                TASK_LABELS,
+            )?,
+            task_slow_polls: metrics.new_int_gauge(
+                "tokio_task_slow_polls",
+                "Number of slow polls of the spawned tasks, by role and chain",
+                TASK_LABELS,
+            )?,
+        };
+        TASKS_INSTRUMENTED.store(true, Ordering::Relaxed);
+        Ok(updater)
+    }
+
+    fn update_task_metrics(&self) {
+        let monitors = TASK_MONITORS.lock().unwrap();
+        for ((role, chain), monitor) in monitors.iter() {
+            let labels = [role.as_str(), chain.as_str()];
+            let cumulative = monitor.cumulative();
+            self.task_alive.with_label_values(&labels).set(
+                cumulative
+                    .instrumented_count
+                    .saturating_sub(cumulative.dropped_count) as i64,
+            );
+            self.task_poll_duration
+                .with_label_values(&labels)
+                .set(cumulative.total_poll_duration.as_secs_f64());
+            self.task_scheduled_duration
+                .with_label_values(&labels)
+                .set(cumulative.total_scheduled_duration.as_secs_f64());
+            self.task_slow_polls
+                .with_label_values(&labels)
+                .set(cumulative.total_slow_poll_count as i64);
+        }
+    }
+
+    /// Periodically updates the metrics
+    pub async fn start_updating_on_interval(self, period: Duration) {
+        let handle = Handle::current();
+        let monitor = RuntimeMonitor::new(&handle);
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        for runtime in monitor.intervals() {
+            self.workers
+                .with_label_values(&[])
+                .set(runtime.workers_count as i64);
+            self.alive_tasks
+                .with_label_values(&[])
+                .set(handle.metrics().num_alive_tasks() as i64);
+            self.injection_queue_depth
+                .with_label_values(&[])
+                .set(runtime.injection_queue_depth as i64);
+            self.local_queue_depth
+                .with_label_values(&[])
+                .set(runtime.total_local_queue_depth as i64);
+            let capacity = runtime.elapsed.as_secs_f64() * runtime.workers_count as f64;
+            if capacity > 0. {
+                self.busy_ratio
+                    .with_label_values(&[])
+                    .set(runtime.total_busy_duration.as_secs_f64() / capacity);
+            }
+            self.mean_poll_duration
+                .with_label_values(&[])
+                .set(runtime.mean_poll_duration.as_secs_f64());
+            self.forced_yields
+                .with_label_values(&[])
+                .set(runtime.budget_forced_yield_count as i64);
+            self.update_task_metrics();
+            interval.tick().await;
+        }
+    }
+
+    /// Spawns a tokio task to update the metrics
+    pub fn spawn(self) -> Instrumented<JoinHandle<()>> {
+        spawn_named("runtime_metrics", None, async move {
+            self.start_updating_on_interval(RUNTIME_METRICS_INTERVAL)
+                .await;
+        })
+        .instrument(info_span!("RuntimeMetricsUpdater"))
+    }
+}
@@ -0,0 +1,268 @@
+use std::{sync::Arc, time::Duration};
+
+use hyperlane_core::config::ProxyConf;
+use reqwest::{Client, RequestBuilder};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use tracing::{error, warn};
+use url::Url;
+
+use crate::{
+    settings::{NotificationSinkConf, NotificationSinkKind},
+    utils::reqwest_client_builder,
+};
+
+/// A critical event agents report to operators
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum NotificationEvent {
+    /// A validator signed a checkpoint that doesn't match the canonical
+    /// merkle tree, or conflicts with another checkpoint it signed
+    ValidatorFraud {
+        /// Origin chain of the checkpoint
+        chain: String,
+        /// Address of the validator
+        validator: String,
+        /// Index of the checkpoint
+        index: u32,
+        /// The kind of fraud
+        reason: String,
+    },
+    /// The balance of an agent signer dropped below its critical threshold
+    BalanceCritical {
+        /// Chain of the signer
+        chain: String,
+        /// Address of the signer
+        address: String,
+        /// Balance of the signer
+        balance: String,
+    },
+    /// Indexing of a chain has been failing for longer than the stall
+    /// threshold
+    IndexingStalled {
+        /// Indexed chain
+        chain: String,
+        /// What is being indexed
+        label: String,
+        /// How long indexing has been failing for
+        stalled_for_secs: u64,
+    },
+    /// A chain didn't produce new blocks for longer than the halt threshold
+    ChainHalted {
+        /// Halted chain
+        chain: String,
+        /// How long the chain has been halted for
+        halted_for_secs: u64,
+    },
+}
+
+impl NotificationEvent {
+    /// Short name of the event
+    pub fn kind(&self) -> &'static str {
+        match self {
+            NotificationEvent::ValidatorFraud { .. } => "validator_fraud",
+            NotificationEvent::BalanceCritical { .. } => "balance_critical",
+            NotificationEvent::IndexingStalled { .. } => "indexing_stalled",
+            NotificationEvent::ChainHalted { .. } => "chain_halted",
+        }
+    }
+
+    /// One line description of the event, used when a sink has no template
+    pub fn summary(&self) -> String {
+        match self {
+            NotificationEvent::ValidatorFraud {
+                chain,
+                validator,
+                index,
+                reason,
+            } => format!(
+                "Validator {validator} signed a fraudulent checkpoint at index {index} on {chain}: {reason}"
+            ),
+            NotificationEvent::BalanceCritical {
+                chain,
+                address,
+                balance,
+            } => format!("Balance of signer {address} on {chain} is critically low: {balance}"),
+            NotificationEvent::IndexingStalled {
+                chain,
+                label,
+                stalled_for_secs,
+            } => format!("Indexing of {label} on {chain} has been stalled for {stalled_for_secs}s"),
+            NotificationEvent::ChainHalted {
+                chain,
+                halted_for_secs,
+            } => format!("{chain} hasn't produced a block for {halted_for_secs}s"),
+        }
+    }
+
+    /// Identifies the incident the event belongs to, so that repeated
+    /// notifications of the same incident can be grouped
+    pub fn dedup_key(&self) -> String {
+        match self {
+            NotificationEvent::ValidatorFraud {
+                chain, validator, ..
+            } => format!("{}:{chain}:{validator}", self.kind()),
+            NotificationEvent::BalanceCritical { chain, address, .. } => {
+                format!("{}:{chain}:{address}", self.kind())
+            }
+            NotificationEvent::IndexingStalled { chain, label, .. } => {
+                format!("{}:{chain}:{label}", self.kind())
+            }
+            NotificationEvent::ChainHalted { chain, .. } => format!("{}:{chain}", self.kind()),
+        }
+    }
+
+    fn fields(&self) -> Map<String, Value> {
+        match serde_json::to_value(self) {
+            Ok(Value::Object(fields)) => fields,
+            _ => Map::new(),
+        }
+    }
+
+    /// Renders `template`, replacing `{{field}}` with the field of the event.
+    /// `{{summary}}` is replaced with the summary of the event.
+    pub fn render(&self, template: &str) -> String {
+        let mut fields = self.fields();
+        fields.insert("summary".to_owned(), self.summary().into());
+        fields
+            .iter()
+            .fold(template.to_owned(), |text, (name, value)| {
+                let value = match value {
+                    Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+                text.replace(&format!("{{{{{name}}}}}"), &value)
+            })
+    }
+}
+
+/// How long sending a notification to a sink may take
+const NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Reports critical events in the logs and to the configured sinks
+#[derive(Debug, Clone, Default)]
+pub struct Notifier {
+    sinks: Arc<Vec<NotificationSinkConf>>,
+    client: Client,
+}
+
+impl Notifier {
+    /// Create a notifier sending to `sinks`, through `proxy` if any
+    pub fn new(sinks: Vec<NotificationSinkConf>, proxy: Option<&ProxyConf>) -> Self {
+        Self {
+            sinks: Arc::new(sinks),
+            client: reqwest_client_builder(proxy)
+                .timeout(NOTIFICATION_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Report `event`. Notifications are sent in the background, so that
+    /// slow sinks don't hold up the caller, and failing to reach a sink is
+    /// only logged.
+    pub fn notify(&self, event: &NotificationEvent) {
+        error!(?event, "{}", event.summary());
+        for sink in self.sinks.iter() {
+            let request = self.request(sink, event);
+            let kind = sink.kind.clone();
+            tokio::spawn(async move {
+                let result = request
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status());
+                if let Err(err) = result {
+                    warn!(?err, sink = ?kind, "Failed to send notification");
+                }
+            });
+        }
+    }
+
+    fn request(&self, sink: &NotificationSinkConf, event: &NotificationEvent) -> RequestBuilder {
+        let (url, payload) = payload(sink, event);
+        self.client.post(url.clone()).json(&payload)
+    }
+}
+
+/// The url to post `event` to and the payload expected by `sink`
+fn payload<'a>(sink: &'a NotificationSinkConf, event: &NotificationEvent) -> (&'a Url, Value) {
+    let text = sink
+        .template
+        .as_deref()
+        .map(|template| event.render(template))
+        .unwrap_or_else(|| event.summary());
+    match &sink.kind {
+        NotificationSinkKind::Webhook { url } => {
+            let mut fields = event.fields();
+            fields.insert("text".to_owned(), text.into());
+            (url, Value::Object(fields))
+        }
+        NotificationSinkKind::Slack { url } => (url, json!({ "text": text })),
+        NotificationSinkKind::PagerDuty { url, routing_key } => (
+            url,
+            json!({
+                "routing_key": routing_key,
+                "event_action": "trigger",
+                "dedup_key": event.dedup_key(),
+                "payload": {
+                    "summary": text,
+                    "source": "hyperlane-agent",
+                    "severity": "critical",
+                    "custom_details": event.fields(),
+                },
+            }),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn fraud() -> NotificationEvent {
+        NotificationEvent::ValidatorFraud {
+            chain: "ethereum".to_owned(),
+            validator: "0xabcd".to_owned(),
+            index: 42,
+            reason: "double_signing".to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_render_template() {
+        assert_eq!(
+            fraud().render("{{event}}: {{validator}} at {{index}} on {{chain}} ({{unknown}})"),
+            "validator_fraud: 0xabcd at 42 on ethereum ({{unknown}})"
+        );
+        assert_eq!(fraud().render("{{summary}}"), fraud().summary());
+    }
+
+    #[test]
+    fn test_sink_payloads() {
+        let url: Url = "http://localhost:3000".parse().unwrap();
+        let sink = |kind| NotificationSinkConf {
+            kind,
+            template: Some("{{chain}}: {{reason}}".to_owned()),
+        };
+
+        let webhook = sink(NotificationSinkKind::Webhook { url: url.clone() });
+        let (_, body) = payload(&webhook, &fraud());
+        assert_eq!(body["event"], "validator_fraud");
+        assert_eq!(body["index"], 42);
+        assert_eq!(body["text"], "ethereum: double_signing");
+
+        let slack = sink(NotificationSinkKind::Slack { url: url.clone() });
+        let (_, body) = payload(&slack, &fraud());
+        assert_eq!(body, json!({ "text": "ethereum: double_signing" }));
+
+        let pagerduty = sink(NotificationSinkKind::PagerDuty {
+            url,
+            routing_key: "key".to_owned(),
+        });
+        let (_, body) = payload(&pagerduty, &fraud());
+        assert_eq!(body["routing_key"], "key");
+        assert_eq!(body["dedup_key"], "validator_fraud:ethereum:0xabcd");
+        assert_eq!(body["payload"]["summary"], "ethereum: double_signing");
+        assert_eq!(body["payload"]["custom_details"]["validator"], "0xabcd");
+    }
+}
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use eyre::{bail, Report, Result};
+use hyperlane_aptos as h_aptos;
+use hyperlane_core::{
+    ContractLocator, HyperlaneDomainProtocol, HyperlaneMessage, HyperlaneProvider, Mailbox,
+    SequenceAwareIndexer, H256,
+};
+
+use super::{BackendConnectionConf, ChainBackend};
+use crate::settings::{BuildableWithSignerConf, ChainConf, ChainSigner, SignerConf};
+
+/// Builds the chain objects of Aptos chains
+pub(super) struct AptosBackend;
+
+fn connection_conf(conf: &BackendConnectionConf) -> h_aptos::ConnectionConf {
+    h_aptos::ConnectionConf {
+        url: conf.url.clone(),
+    }
+}
+
+#[async_trait]
+impl ChainBackend for AptosBackend {
+    fn protocol(&self) -> HyperlaneDomainProtocol {
+        HyperlaneDomainProtocol::Aptos
+    }
+
+    async fn build_provider(
+        &self,
+        _chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn HyperlaneProvider>> {
+        let provider = h_aptos::AptosProvider::new(locator.domain.clone(), &connection_conf(conf));
+        Ok(Box::new(provider))
+    }
+
+    async fn build_mailbox(
+        &self,
+        chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn Mailbox>> {
+        let signer = chain.signer::<h_aptos::Signer>().await?;
+        let mailbox = h_aptos::AptosMailbox::new(&connection_conf(conf), locator, signer);
+        Ok(Box::new(mailbox))
+    }
+
+    async fn build_message_indexer(
+        &self,
+        _chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<HyperlaneMessage>>> {
+        let indexer = h_aptos::AptosMailboxIndexer::new(&connection_conf(conf), locator);
+        Ok(Box::new(indexer))
+    }
+
+    async fn build_delivery_indexer(
+        &self,
+        _chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<H256>>> {
+        let indexer = h_aptos::AptosMailboxIndexer::new(&connection_conf(conf), locator);
+        Ok(Box::new(indexer))
+    }
+
+    async fn build_chain_signer(&self, conf: &SignerConf) -> Result<Box<dyn ChainSigner>> {
+        Ok(Box::new(conf.build::<h_aptos::Signer>().await?))
+    }
+}
+
+#[async_trait]
+impl BuildableWithSignerConf for h_aptos::Signer {
+    async fn build(conf: &SignerConf) -> Result<Self, Report> {
+        if let SignerConf::HexKey { key } = conf {
+            Ok(h_aptos::Signer::new(*key)?)
+        } else {
+            bail!(format!("{conf:?} key is not supported by aptos"));
+        }
+    }
+}
+
+impl ChainSigner for h_aptos::Signer {
+    fn address_string(&self) -> String {
+        format!("{:?}", self.address)
+    }
+}
@@ -0,0 +1,378 @@
+//! Chain backends which are not built into `ChainConnectionConf`.
+//!
+//! Each backend lives behind a cargo feature of this crate and registers the
+//! constructors of its chain objects in the [`ChainBackendRegistry`]. Adding
+//! such a chain only requires a new backend module and its registration in
+//! [`chain_backends`], rather than an arm in every builder of [`ChainConf`].
+
+use std::{
+    fmt::{Debug, Formatter},
+    sync::OnceLock,
+};
+
+use async_trait::async_trait;
+use eyre::{eyre, Result};
+use hyperlane_core::{
+    AggregationIsm, CcipReadIsm, ContractLocator, GasOracleConfigUpdate, HyperlaneDomainProtocol,
+    HyperlaneMessage, HyperlaneProvider, InterchainGasPaymaster, InterchainGasPayment,
+    InterchainSecurityModule, Mailbox, MerkleTreeHook, MerkleTreeInsertion, MessageDispatcher,
+    MultisigIsm, NativeTokenTransfer, RoutingIsm, SequenceAwareIndexer, TokenRouter,
+    TransferRemote, ValidatorAnnounce, ValidatorAnnouncement, H256,
+};
+use url::Url;
+
+use crate::settings::{ChainConf, ChainSigner, SignerConf};
+
+#[cfg(feature = "aptos")]
+mod aptos;
+#[cfg(feature = "starknet")]
+mod starknet;
+#[cfg(feature = "substrate")]
+mod substrate;
+#[cfg(feature = "ton")]
+mod ton;
+
+/// Connection details of a chain served by a [`ChainBackend`].
+#[derive(Clone, Debug)]
+pub struct BackendConnectionConf {
+    /// The protocol of the chain, which selects its backend
+    pub protocol: HyperlaneDomainProtocol,
+    /// Fully qualified url of the chain API
+    pub url: Url,
+}
+
+/// Constructors of the chain objects of one protocol.
+///
+/// Only the provider, mailbox, indexers and signer are required, every other
+/// object is reported as unsupported unless the backend overrides it.
+#[async_trait]
+pub trait ChainBackend: Send + Sync {
+    /// The protocol this backend builds chain objects for
+    fn protocol(&self) -> HyperlaneDomainProtocol;
+
+    /// Build a provider
+    async fn build_provider(
+        &self,
+        chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn HyperlaneProvider>>;
+
+    /// Build a mailbox
+    async fn build_mailbox(
+        &self,
+        chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn Mailbox>>;
+
+    /// Build a message indexer
+    async fn build_message_indexer(
+        &self,
+        chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<HyperlaneMessage>>>;
+
+    /// Build a delivery indexer
+    async fn build_delivery_indexer(
+        &self,
+        chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<H256>>>;
+
+    /// Build the signer configured by `conf`
+    async fn build_chain_signer(&self, conf: &SignerConf) -> Result<Box<dyn ChainSigner>>;
+
+    /// Build a merkle tree hook
+    async fn build_merkle_tree_hook(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn MerkleTreeHook>> {
+        Err(self.unsupported("merkle tree hooks"))
+    }
+
+    /// Build an interchain gas paymaster
+    async fn build_interchain_gas_paymaster(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn InterchainGasPaymaster>> {
+        Err(self.unsupported("interchain gas paymasters"))
+    }
+
+    /// Build an interchain gas payment indexer
+    async fn build_interchain_gas_payment_indexer(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<InterchainGasPayment>>> {
+        Err(self.unsupported("gas payment indexing"))
+    }
+
+    /// Build an indexer of the gas oracle config updates of an IGP, starting
+    /// from the `gas_oracles` it configured before
+    async fn build_gas_oracle_config_indexer(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+        _gas_oracles: Vec<H256>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<GasOracleConfigUpdate>>> {
+        Err(self.unsupported("gas oracle config indexing"))
+    }
+
+    /// Build a merkle tree hook indexer
+    async fn build_merkle_tree_hook_indexer(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<MerkleTreeInsertion>>> {
+        Err(self.unsupported("merkle tree hook indexing"))
+    }
+
+    /// Build a validator announce
+    async fn build_validator_announce(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn ValidatorAnnounce>> {
+        Err(self.unsupported("validator announce"))
+    }
+
+    /// Build a validator announcement indexer
+    async fn build_validator_announce_indexer(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<ValidatorAnnouncement>>> {
+        Err(self.unsupported("validator announcement indexing"))
+    }
+
+    /// Build an ISM
+    async fn build_ism(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn InterchainSecurityModule>> {
+        Err(self.unsupported("ISMs"))
+    }
+
+    /// Build a multisig ISM
+    async fn build_multisig_ism(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn MultisigIsm>> {
+        Err(self.unsupported("multisig ISM"))
+    }
+
+    /// Build a routing ISM
+    async fn build_routing_ism(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn RoutingIsm>> {
+        Err(self.unsupported("routing ISM"))
+    }
+
+    /// Build an aggregation ISM
+    async fn build_aggregation_ism(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn AggregationIsm>> {
+        Err(self.unsupported("aggregation ISM"))
+    }
+
+    /// Build a CCIP read ISM
+    async fn build_ccip_read_ism(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn CcipReadIsm>> {
+        Err(self.unsupported("CCIP read ISM"))
+    }
+
+    /// Build a native token transfer
+    async fn build_native_token_transfer(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn NativeTokenTransfer>> {
+        Err(self.unsupported("native token transfers"))
+    }
+
+    /// Build a message dispatcher
+    async fn build_message_dispatcher(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn MessageDispatcher>> {
+        Err(self.unsupported("message dispatchers"))
+    }
+
+    /// Build a token router
+    async fn build_token_router(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn TokenRouter>> {
+        Err(self.unsupported("token routers"))
+    }
+
+    /// Build a transfer remote indexer
+    async fn build_transfer_remote_indexer(
+        &self,
+        _chain: &ChainConf,
+        _conf: &BackendConnectionConf,
+        _locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<TransferRemote>>> {
+        Err(self.unsupported("transfer remote indexing"))
+    }
+
+    /// The error of a chain object this backend does not support
+    fn unsupported(&self, what: &str) -> eyre::Report {
+        eyre!("{:?} does not support {what} yet", self.protocol())
+    }
+}
+
+/// The chain backends available to the agents, at most one per protocol.
+#[derive(Default)]
+pub struct ChainBackendRegistry {
+    backends: Vec<Box<dyn ChainBackend>>,
+}
+
+impl ChainBackendRegistry {
+    /// Register `backend`, replacing any backend of the same protocol
+    pub fn register(&mut self, backend: impl ChainBackend + 'static) {
+        let protocol = backend.protocol();
+        self.backends.retain(|b| b.protocol() != protocol);
+        self.backends.push(Box::new(backend));
+    }
+
+    /// The backend of `protocol`, if one is registered
+    pub fn get(&self, protocol: HyperlaneDomainProtocol) -> Option<&dyn ChainBackend> {
+        self.backends
+            .iter()
+            .find(|b| b.protocol() == protocol)
+            .map(AsRef::as_ref)
+    }
+}
+
+impl Debug for ChainBackendRegistry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.backends.iter().map(|b| b.protocol()))
+            .finish()
+    }
+}
+
+/// The backends of the chains enabled in this build
+pub fn chain_backends() -> &'static ChainBackendRegistry {
+    static BACKENDS: OnceLock<ChainBackendRegistry> = OnceLock::new();
+    BACKENDS.get_or_init(|| {
+        #[allow(unused_mut)]
+        let mut registry = ChainBackendRegistry::default();
+        #[cfg(feature = "starknet")]
+        registry.register(starknet::StarknetBackend);
+        #[cfg(feature = "substrate")]
+        registry.register(substrate::SubstrateBackend);
+        #[cfg(feature = "aptos")]
+        registry.register(aptos::AptosBackend);
+        #[cfg(feature = "ton")]
+        registry.register(ton::TonBackend);
+        registry
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct DummyBackend(HyperlaneDomainProtocol);
+
+    #[async_trait]
+    impl ChainBackend for DummyBackend {
+        fn protocol(&self) -> HyperlaneDomainProtocol {
+            self.0
+        }
+
+        async fn build_provider(
+            &self,
+            _chain: &ChainConf,
+            _conf: &BackendConnectionConf,
+            _locator: ContractLocator<'_>,
+        ) -> Result<Box<dyn HyperlaneProvider>> {
+            unimplemented!()
+        }
+
+        async fn build_mailbox(
+            &self,
+            _chain: &ChainConf,
+            _conf: &BackendConnectionConf,
+            _locator: ContractLocator<'_>,
+        ) -> Result<Box<dyn Mailbox>> {
+            unimplemented!()
+        }
+
+        async fn build_message_indexer(
+            &self,
+            _chain: &ChainConf,
+            _conf: &BackendConnectionConf,
+            _locator: ContractLocator<'_>,
+        ) -> Result<Box<dyn SequenceAwareIndexer<HyperlaneMessage>>> {
+            unimplemented!()
+        }
+
+        async fn build_delivery_indexer(
+            &self,
+            _chain: &ChainConf,
+            _conf: &BackendConnectionConf,
+            _locator: ContractLocator<'_>,
+        ) -> Result<Box<dyn SequenceAwareIndexer<H256>>> {
+            unimplemented!()
+        }
+
+        async fn build_chain_signer(&self, _conf: &SignerConf) -> Result<Box<dyn ChainSigner>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_register_replaces_backend_of_protocol() {
+        let mut registry = ChainBackendRegistry::default();
+        registry.register(DummyBackend(HyperlaneDomainProtocol::Starknet));
+        registry.register(DummyBackend(HyperlaneDomainProtocol::Ton));
+        registry.register(DummyBackend(HyperlaneDomainProtocol::Starknet));
+
+        assert_eq!(registry.backends.len(), 2);
+        assert!(registry.get(HyperlaneDomainProtocol::Ton).is_some());
+        assert!(registry.get(HyperlaneDomainProtocol::Aptos).is_none());
+    }
+
+    #[test]
+    fn test_unsupported_objects() {
+        let backend = DummyBackend(HyperlaneDomainProtocol::Aptos);
+        assert_eq!(
+            backend.unsupported("routing ISM").to_string(),
+            "Aptos does not support routing ISM yet"
+        );
+    }
+}
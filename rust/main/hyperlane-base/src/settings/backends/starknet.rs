@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use eyre::{bail, Report, Result};
+use hyperlane_core::{
+    ContractLocator, HyperlaneDomainProtocol, HyperlaneMessage, HyperlaneProvider,
+    InterchainSecurityModule, Mailbox, MultisigIsm, SequenceAwareIndexer, ValidatorAnnounce, H256,
+};
+use hyperlane_starknet as h_starknet;
+
+use super::{BackendConnectionConf, ChainBackend};
+use crate::settings::{BuildableWithSignerConf, ChainConf, ChainSigner, SignerConf};
+
+/// Builds the chain objects of Starknet chains
+pub(super) struct StarknetBackend;
+
+fn connection_conf(conf: &BackendConnectionConf) -> h_starknet::ConnectionConf {
+    h_starknet::ConnectionConf {
+        url: conf.url.clone(),
+    }
+}
+
+#[async_trait]
+impl ChainBackend for StarknetBackend {
+    fn protocol(&self) -> HyperlaneDomainProtocol {
+        HyperlaneDomainProtocol::Starknet
+    }
+
+    async fn build_provider(
+        &self,
+        _chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn HyperlaneProvider>> {
+        let provider =
+            h_starknet::StarknetProvider::new(locator.domain.clone(), &connection_conf(conf));
+        Ok(Box::new(provider))
+    }
+
+    async fn build_mailbox(
+        &self,
+        chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn Mailbox>> {
+        let signer = chain.signer::<h_starknet::Signer>().await?;
+        let mailbox =
+            h_starknet::StarknetMailbox::new(&connection_conf(conf), locator, signer).await?;
+        Ok(Box::new(mailbox))
+    }
+
+    async fn build_message_indexer(
+        &self,
+        _chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<HyperlaneMessage>>> {
+        let indexer =
+            h_starknet::StarknetMailboxIndexer::new(&connection_conf(conf), locator).await?;
+        Ok(Box::new(indexer))
+    }
+
+    async fn build_delivery_indexer(
+        &self,
+        _chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<H256>>> {
+        let indexer =
+            h_starknet::StarknetMailboxIndexer::new(&connection_conf(conf), locator).await?;
+        Ok(Box::new(indexer))
+    }
+
+    async fn build_chain_signer(&self, conf: &SignerConf) -> Result<Box<dyn ChainSigner>> {
+        Ok(Box::new(conf.build::<h_starknet::Signer>().await?))
+    }
+
+    async fn build_validator_announce(
+        &self,
+        chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn ValidatorAnnounce>> {
+        let signer = chain.signer::<h_starknet::Signer>().await?;
+        let va =
+            h_starknet::StarknetValidatorAnnounce::new(&connection_conf(conf), locator, signer)
+                .await?;
+        Ok(Box::new(va))
+    }
+
+    async fn build_ism(
+        &self,
+        _chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn InterchainSecurityModule>> {
+        let ism =
+            h_starknet::StarknetInterchainSecurityModule::new(&connection_conf(conf), locator)?;
+        Ok(Box::new(ism))
+    }
+
+    async fn build_multisig_ism(
+        &self,
+        _chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn MultisigIsm>> {
+        let ism = h_starknet::StarknetMultisigIsm::new(&connection_conf(conf), locator)?;
+        Ok(Box::new(ism))
+    }
+}
+
+#[async_trait]
+impl BuildableWithSignerConf for h_starknet::Signer {
+    async fn build(conf: &SignerConf) -> Result<Self, Report> {
+        if let SignerConf::StarknetKey { key, address } = conf {
+            Ok(h_starknet::Signer::new(*key, *address)?)
+        } else {
+            bail!(format!("{conf:?} key is not supported by starknet"));
+        }
+    }
+}
+
+impl ChainSigner for h_starknet::Signer {
+    fn address_string(&self) -> String {
+        format!("{:#x}", self.address)
+    }
+}
@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use eyre::{bail, Report, Result};
+use hyperlane_core::{
+    ContractLocator, HyperlaneDomainProtocol, HyperlaneMessage, HyperlaneProvider, Mailbox,
+    SequenceAwareIndexer, H256,
+};
+use hyperlane_substrate as h_substrate;
+
+use super::{BackendConnectionConf, ChainBackend};
+use crate::settings::{BuildableWithSignerConf, ChainConf, ChainSigner, SignerConf};
+
+/// Builds the chain objects of Substrate chains
+pub(super) struct SubstrateBackend;
+
+fn connection_conf(conf: &BackendConnectionConf) -> h_substrate::ConnectionConf {
+    h_substrate::ConnectionConf {
+        url: conf.url.clone(),
+    }
+}
+
+#[async_trait]
+impl ChainBackend for SubstrateBackend {
+    fn protocol(&self) -> HyperlaneDomainProtocol {
+        HyperlaneDomainProtocol::Substrate
+    }
+
+    async fn build_provider(
+        &self,
+        _chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn HyperlaneProvider>> {
+        let provider =
+            h_substrate::SubstrateProvider::new(locator.domain.clone(), &connection_conf(conf))
+                .await?;
+        Ok(Box::new(provider))
+    }
+
+    async fn build_mailbox(
+        &self,
+        chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn Mailbox>> {
+        let signer = chain.signer::<h_substrate::Signer>().await?;
+        let mailbox =
+            h_substrate::SubstrateMailbox::new(&connection_conf(conf), locator, signer).await?;
+        Ok(Box::new(mailbox))
+    }
+
+    async fn build_message_indexer(
+        &self,
+        _chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<HyperlaneMessage>>> {
+        let indexer =
+            h_substrate::SubstrateMailboxIndexer::new(&connection_conf(conf), locator).await?;
+        Ok(Box::new(indexer))
+    }
+
+    async fn build_delivery_indexer(
+        &self,
+        _chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<H256>>> {
+        let indexer =
+            h_substrate::SubstrateMailboxIndexer::new(&connection_conf(conf), locator).await?;
+        Ok(Box::new(indexer))
+    }
+
+    async fn build_chain_signer(&self, conf: &SignerConf) -> Result<Box<dyn ChainSigner>> {
+        Ok(Box::new(conf.build::<h_substrate::Signer>().await?))
+    }
+}
+
+#[async_trait]
+impl BuildableWithSignerConf for h_substrate::Signer {
+    async fn build(conf: &SignerConf) -> Result<Self, Report> {
+        if let SignerConf::HexKey { key } = conf {
+            Ok(h_substrate::Signer::new(*key)?)
+        } else {
+            bail!(format!("{conf:?} key is not supported by substrate"));
+        }
+    }
+}
+
+impl ChainSigner for h_substrate::Signer {
+    fn address_string(&self) -> String {
+        self.account_id().to_string()
+    }
+}
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use eyre::{bail, Report, Result};
+use hyperlane_core::{
+    ContractLocator, HyperlaneDomainProtocol, HyperlaneMessage, HyperlaneProvider, Mailbox,
+    SequenceAwareIndexer, H256,
+};
+use hyperlane_ton as h_ton;
+
+use super::{BackendConnectionConf, ChainBackend};
+use crate::settings::{BuildableWithSignerConf, ChainConf, ChainSigner, SignerConf};
+
+/// Builds the chain objects of TON chains
+pub(super) struct TonBackend;
+
+fn connection_conf(conf: &BackendConnectionConf) -> h_ton::ConnectionConf {
+    h_ton::ConnectionConf {
+        url: conf.url.clone(),
+    }
+}
+
+#[async_trait]
+impl ChainBackend for TonBackend {
+    fn protocol(&self) -> HyperlaneDomainProtocol {
+        HyperlaneDomainProtocol::Ton
+    }
+
+    async fn build_provider(
+        &self,
+        _chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn HyperlaneProvider>> {
+        let provider = h_ton::TonProvider::new(locator.domain.clone(), &connection_conf(conf));
+        Ok(Box::new(provider))
+    }
+
+    async fn build_mailbox(
+        &self,
+        chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn Mailbox>> {
+        let signer = chain.signer::<h_ton::Signer>().await?;
+        let mailbox = h_ton::TonMailbox::new(&connection_conf(conf), locator, signer);
+        Ok(Box::new(mailbox))
+    }
+
+    async fn build_message_indexer(
+        &self,
+        _chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<HyperlaneMessage>>> {
+        let indexer = h_ton::TonMailboxIndexer::new(&connection_conf(conf), locator);
+        Ok(Box::new(indexer))
+    }
+
+    async fn build_delivery_indexer(
+        &self,
+        _chain: &ChainConf,
+        conf: &BackendConnectionConf,
+        locator: ContractLocator<'_>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<H256>>> {
+        let indexer = h_ton::TonMailboxIndexer::new(&connection_conf(conf), locator);
+        Ok(Box::new(indexer))
+    }
+
+    async fn build_chain_signer(&self, conf: &SignerConf) -> Result<Box<dyn ChainSigner>> {
+        Ok(Box::new(conf.build::<h_ton::Signer>().await?))
+    }
+}
+
+#[async_trait]
+impl BuildableWithSignerConf for h_ton::Signer {
+    async fn build(conf: &SignerConf) -> Result<Self, Report> {
+        if let SignerConf::HexKey { key } = conf {
+            Ok(h_ton::Signer::new(*key)?)
+        } else {
+            bail!(format!("{conf:?} key is not supported by ton"));
+        }
+    }
+}
+
+impl ChainSigner for h_ton::Signer {
+    fn address_string(&self) -> String {
+        self.address().to_base64_url()
+    }
+}
@@ -3,15 +3,20 @@ use std::{collections::HashMap, fmt::Debug, hash::Hash, sync::Arc};
 use eyre::{eyre, Context, Result};
 use futures_util::future::try_join_all;
 use hyperlane_core::{
-    HyperlaneChain, HyperlaneDomain, HyperlaneLogStore, HyperlaneProvider,
-    HyperlaneSequenceAwareIndexerStoreReader, HyperlaneWatermarkedLogStore, InterchainGasPaymaster,
-    Mailbox, MerkleTreeHook, MultisigIsm, SequenceAwareIndexer, ValidatorAnnounce, H256,
+    config::ProxyConf, GasOracleConfigUpdate, HyperlaneChain, HyperlaneDomain, HyperlaneLogStore,
+    HyperlaneProvider, HyperlaneSequenceAwareIndexerStoreReader, HyperlaneWatermarkedLogStore,
+    InterchainGasPaymaster, Mailbox, MerkleTreeHook, MultisigIsm, SequenceAwareIndexer,
+    ValidatorAnnounce, H256,
 };
 
 use crate::{
     cursors::{CursorType, Indexable},
-    settings::{chains::ChainConf, trace::TracingConfig},
-    ContractSync, ContractSyncMetrics, ContractSyncer, CoreMetrics, HyperlaneAgentCore,
+    settings::{
+        chains::{ChainConf, GasOracleConfigIndexer},
+        trace::TracingConfig,
+        NotificationsConf,
+    },
+    ContractSync, ContractSyncMetrics, ContractSyncer, CoreMetrics, HyperlaneAgentCore, Notifier,
     SequenceAwareLogStore, SequencedDataContractSync, Server, WatermarkContractSync,
     WatermarkLogStore,
 };
@@ -50,6 +55,11 @@ pub struct Settings {
     pub metrics_port: u16,
     /// The tracing configuration
     pub tracing: TracingConfig,
+    /// Notifications of critical events
+    pub notifications: NotificationsConf,
+    /// The proxy outbound connections are routed through, unless their chain
+    /// has its own
+    pub proxy: Option<ProxyConf>,
 }
 
 impl Settings {
@@ -103,6 +113,11 @@ impl Settings {
         Ok(Arc::new(Server::new(self.metrics_port, core_metrics)))
     }
 
+    /// Create the notifier reporting critical events to the configured sinks.
+    pub fn notifier(&self) -> Notifier {
+        Notifier::new(self.notifications.sinks.clone(), self.proxy.as_ref())
+    }
+
     /// Private to preserve linearity of AgentCore::from_settings -- creating an
     /// agent consumes the settings.
     fn clone(&self) -> Self {
@@ -110,6 +125,7 @@ impl Settings {
             chains: self.chains.clone(),
             metrics_port: self.metrics_port,
             tracing: self.tracing.clone(),
+            notifications: self.notifications.clone(),
         }
     }
 }
@@ -167,12 +183,17 @@ impl Settings {
         let setup = self.chain_setup(domain)?;
         // Currently, all indexers are of the `SequenceIndexer` type
         let indexer = SequenceIndexer::<T>::try_from_with_metrics(setup, metrics).await?;
-        Ok(Arc::new(ContractSync::new(
-            domain.clone(),
-            db.clone() as SequenceAwareLogStore<_>,
-            indexer,
-            sync_metrics.clone(),
-        )))
+        Ok(Arc::new(
+            ContractSync::new(
+                domain.clone(),
+                db.clone() as SequenceAwareLogStore<_>,
+                indexer,
+                sync_metrics.clone(),
+                self.notifier(),
+                self.notifications.indexing_stall_threshold,
+            )
+            .with_concurrent_queries(setup.index.chunk_size, setup.index.concurrency),
+        ))
     }
 
     /// Build a contract sync for type `T` using log store `D`
@@ -191,12 +212,51 @@ impl Settings {
         let setup = self.chain_setup(domain)?;
         // Currently, all indexers are of the `SequenceIndexer` type
         let indexer = SequenceIndexer::<T>::try_from_with_metrics(setup, metrics).await?;
-        Ok(Arc::new(ContractSync::new(
-            domain.clone(),
-            db.clone() as WatermarkLogStore<_>,
-            indexer,
-            sync_metrics.clone(),
-        )))
+        Ok(Arc::new(
+            ContractSync::new(
+                domain.clone(),
+                db.clone() as WatermarkLogStore<_>,
+                indexer,
+                sync_metrics.clone(),
+                self.notifier(),
+                self.notifications.indexing_stall_threshold,
+            )
+            .with_concurrent_queries(setup.index.chunk_size, setup.index.concurrency),
+        ))
+    }
+
+    /// Build a contract sync of the gas oracle config updates of the IGP of
+    /// `domain`. Its indexer starts from the `gas_oracles` the IGP configured
+    /// before the block the sync resumes from.
+    pub async fn gas_oracle_config_sync<D>(
+        &self,
+        domain: &HyperlaneDomain,
+        gas_oracles: Vec<H256>,
+        metrics: &CoreMetrics,
+        sync_metrics: &ContractSyncMetrics,
+        db: Arc<D>,
+    ) -> eyre::Result<Arc<WatermarkContractSync<GasOracleConfigUpdate>>>
+    where
+        D: HyperlaneLogStore<GasOracleConfigUpdate>
+            + HyperlaneWatermarkedLogStore<GasOracleConfigUpdate>
+            + 'static,
+    {
+        let setup = self.chain_setup(domain)?;
+        let indexer: GasOracleConfigIndexer = setup
+            .build_gas_oracle_config_indexer(metrics, gas_oracles)
+            .await?
+            .into();
+        Ok(Arc::new(
+            ContractSync::new(
+                domain.clone(),
+                db.clone() as WatermarkLogStore<_>,
+                indexer,
+                sync_metrics.clone(),
+                self.notifier(),
+                self.notifications.indexing_stall_threshold,
+            )
+            .with_concurrent_queries(setup.index.chunk_size, setup.index.concurrency),
+        ))
     }
 
     /// Build multiple contract syncs.
@@ -7,11 +7,13 @@ use eyre::{eyre, Context, Result};
 
 use ethers_prometheus::middleware::{ChainInfo, ContractInfo, PrometheusMiddlewareConf};
 use hyperlane_core::{
-    config::OperationBatchConfig, AggregationIsm, CcipReadIsm, ContractLocator, HyperlaneAbi,
-    HyperlaneDomain, HyperlaneDomainProtocol, HyperlaneMessage, HyperlaneProvider, IndexMode,
+    config::OperationBatchConfig, rpc_clients::RpcUsageMetrics, AddressFormatter, AggregationIsm,
+    CcipReadIsm, ContractLocator, GasOracleConfigUpdate, HyperlaneAbi, HyperlaneDomain,
+    HyperlaneDomainProtocol, HyperlaneMessage, HyperlaneProvider, IndexMode,
     InterchainGasPaymaster, InterchainGasPayment, InterchainSecurityModule, Mailbox,
-    MerkleTreeHook, MerkleTreeInsertion, MultisigIsm, RoutingIsm, SequenceAwareIndexer,
-    ValidatorAnnounce, H256,
+    MerkleTreeHook, MerkleTreeInsertion, MessageDispatcher, MessageVersions, MultisigIsm,
+    NativeTokenTransfer, RoutingIsm, SequenceAwareIndexer, TokenRouter, ValidatorAnnounce,
+    ValidatorAnnouncement, H256,
 };
 use hyperlane_cosmos as h_cosmos;
 use hyperlane_ethereum::{
@@ -23,7 +25,11 @@ use hyperlane_sealevel as h_sealevel;
 
 use crate::{
     metrics::AgentMetricsConf,
-    settings::signers::{BuildableWithSignerConf, SignerConf},
+    settings::{
+        chain_backends,
+        signers::{BuildableWithSignerConf, SignerConf},
+        BackendConnectionConf, ChainBackend, CodeHashesConf,
+    },
     CoreMetrics,
 };
 
@@ -56,6 +62,10 @@ pub struct ChainConf {
     pub metrics_conf: PrometheusMiddlewareConf,
     /// Settings for event indexing
     pub index: IndexSettings,
+    /// Code hashes the contracts of the chain are expected to have
+    pub code_hashes: CodeHashesConf,
+    /// Versions of the messages the mailbox dispatches and processes
+    pub message_versions: MessageVersions,
 }
 
 /// A sequence-aware indexer for messages
@@ -67,9 +77,15 @@ pub type DeliveryIndexer = Arc<dyn SequenceAwareIndexer<H256>>;
 /// A sequence-aware indexer for interchain gas payments
 pub type IgpIndexer = Arc<dyn SequenceAwareIndexer<InterchainGasPayment>>;
 
+/// An indexer for the gas oracle config updates of interchain gas paymasters
+pub type GasOracleConfigIndexer = Arc<dyn SequenceAwareIndexer<GasOracleConfigUpdate>>;
+
 /// A sequence-aware indexer for merkle tree hooks
 pub type MerkleTreeHookIndexer = Arc<dyn SequenceAwareIndexer<MerkleTreeInsertion>>;
 
+/// A sequence-aware indexer for validator announcements
+pub type ValidatorAnnounceIndexer = Arc<dyn SequenceAwareIndexer<ValidatorAnnouncement>>;
+
 #[async_trait]
 impl TryFromWithMetrics<ChainConf> for MessageIndexer {
     async fn try_from_with_metrics(conf: &ChainConf, metrics: &CoreMetrics) -> Result<Self> {
@@ -102,6 +118,15 @@ impl TryFromWithMetrics<ChainConf> for MerkleTreeHookIndexer {
     }
 }
 
+#[async_trait]
+impl TryFromWithMetrics<ChainConf> for ValidatorAnnounceIndexer {
+    async fn try_from_with_metrics(conf: &ChainConf, metrics: &CoreMetrics) -> Result<Self> {
+        conf.build_validator_announce_indexer(metrics)
+            .await
+            .map(Into::into)
+    }
+}
+
 /// A connection to _some_ blockchain.
 #[derive(Clone, Debug)]
 pub enum ChainConnectionConf {
@@ -113,6 +138,8 @@ pub enum ChainConnectionConf {
     Sealevel(h_sealevel::ConnectionConf),
     /// Cosmos configuration.
     Cosmos(h_cosmos::ConnectionConf),
+    /// Configuration of a chain built by its [`ChainBackend`].
+    Backend(BackendConnectionConf),
 }
 
 impl ChainConnectionConf {
@@ -123,6 +150,7 @@ impl ChainConnectionConf {
             Self::Fuel(_) => HyperlaneDomainProtocol::Fuel,
             Self::Sealevel(_) => HyperlaneDomainProtocol::Sealevel,
             Self::Cosmos(_) => HyperlaneDomainProtocol::Cosmos,
+            Self::Backend(conf) => conf.protocol,
         }
     }
 
@@ -135,6 +163,17 @@ impl ChainConnectionConf {
             _ => None,
         }
     }
+
+    /// Get how the addresses of this chain are displayed.
+    pub fn address_formatter(&self) -> AddressFormatter {
+        match self {
+            Self::Cosmos(conf) => AddressFormatter::Bech32 {
+                prefix: conf.get_bech32_prefix(),
+                bytes: conf.get_contract_address_bytes(),
+            },
+            _ => AddressFormatter::for_protocol(self.protocol()),
+        }
+    }
 }
 
 /// Addresses for mailbox chain contracts
@@ -159,6 +198,35 @@ pub struct IndexSettings {
     pub chunk_size: u32,
     /// The indexing mode.
     pub mode: IndexMode,
+    /// The number of chunks queried at the same time when catching up. The
+    /// logs of all of them are stored at once, in order.
+    pub concurrency: usize,
+    /// A historical block range to index on top of the live indexing
+    pub backfill: Option<BackfillConf>,
+}
+
+impl IndexSettings {
+    /// The number of blocks, or sequences, cursors query at once: a chunk
+    /// for each concurrent query
+    pub fn cursor_chunk_size(&self) -> u32 {
+        self.chunk_size
+            .saturating_mul(self.concurrency.max(1).try_into().unwrap_or(u32::MAX))
+    }
+}
+
+/// A historical block range indexed in chunks, several at a time. Completed
+/// chunks are recorded in the agent database, so that the backfill resumes
+/// where it left off after a restart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackfillConf {
+    /// The first block of the range
+    pub from: u32,
+    /// The last block of the range, inclusive
+    pub to: u32,
+    /// The number of blocks per chunk
+    pub chunk_size: u32,
+    /// The number of chunks indexed at the same time
+    pub concurrency: usize,
 }
 
 impl ChainConf {
@@ -174,12 +242,14 @@ impl ChainConf {
     ) -> Result<Box<dyn HyperlaneProvider>> {
         let ctx = "Building provider";
         let locator = self.locator(H256::zero());
-        match &self.connection {
+        match &self.instrumented_connection(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::HyperlaneProviderBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => Ok(Box::new(
+                h_fuel::FuelProvider::new(locator.domain.clone(), conf).await,
+            ) as Box<dyn HyperlaneProvider>),
             ChainConnectionConf::Sealevel(conf) => Ok(Box::new(h_sealevel::SealevelProvider::new(
                 locator.domain.clone(),
                 conf,
@@ -193,6 +263,11 @@ impl ChainConf {
                 )?;
                 Ok(Box::new(provider) as Box<dyn HyperlaneProvider>)
             }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_provider(self, conf, locator)
+                    .await
+            }
         }
         .context(ctx)
     }
@@ -202,7 +277,7 @@ impl ChainConf {
         let ctx = "Building mailbox";
         let locator = self.locator(self.addresses.mailbox);
 
-        match &self.connection {
+        match &self.instrumented_connection(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::MailboxBuilder {})
                     .await
@@ -226,6 +301,9 @@ impl ChainConf {
                     .map(|m| Box::new(m) as Box<dyn Mailbox>)
                     .map_err(Into::into)
             }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?.build_mailbox(self, conf, locator).await
+            }
         }
         .context(ctx)
     }
@@ -238,7 +316,7 @@ impl ChainConf {
         let ctx = "Building merkle tree hook";
         let locator = self.locator(self.addresses.merkle_tree_hook);
 
-        match &self.connection {
+        match &self.instrumented_connection(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::MerkleTreeHookBuilder {})
                     .await
@@ -258,6 +336,11 @@ impl ChainConf {
 
                 Ok(Box::new(hook) as Box<dyn MerkleTreeHook>)
             }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_merkle_tree_hook(self, conf, locator)
+                    .await
+            }
         }
         .context(ctx)
     }
@@ -270,7 +353,19 @@ impl ChainConf {
         let ctx = "Building delivery indexer";
         let locator = self.locator(self.addresses.mailbox);
 
-        match &self.connection {
+        match &self.instrumented_connection(metrics) {
+            #[cfg(feature = "legacy")]
+            ChainConnectionConf::Ethereum(conf) if conf.legacy_mailbox => {
+                self.build_ethereum(
+                    conf,
+                    &locator,
+                    metrics,
+                    h_eth::LegacySequenceIndexerBuilder {
+                        reorg_period: self.reorg_period,
+                    },
+                )
+                .await
+            }
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(
                     conf,
@@ -282,7 +377,12 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_signer().await.context(ctx)?;
+                let indexer =
+                    Box::new(h_fuel::FuelMailboxIndexer::new(conf, locator, wallet).await?);
+                Ok(indexer as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let indexer = Box::new(h_sealevel::SealevelMailboxIndexer::new(conf, locator)?);
                 Ok(indexer as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
@@ -297,6 +397,11 @@ impl ChainConf {
                 )?);
                 Ok(indexer as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
             }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_message_indexer(self, conf, locator)
+                    .await
+            }
         }
         .context(ctx)
     }
@@ -309,7 +414,7 @@ impl ChainConf {
         let ctx = "Building delivery indexer";
         let locator = self.locator(self.addresses.mailbox);
 
-        match &self.connection {
+        match &self.instrumented_connection(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(
                     conf,
@@ -321,7 +426,12 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_signer().await.context(ctx)?;
+                let indexer =
+                    Box::new(h_fuel::FuelMailboxIndexer::new(conf, locator, wallet).await?);
+                Ok(indexer as Box<dyn SequenceAwareIndexer<H256>>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let indexer = Box::new(h_sealevel::SealevelMailboxIndexer::new(conf, locator)?);
                 Ok(indexer as Box<dyn SequenceAwareIndexer<H256>>)
@@ -336,6 +446,11 @@ impl ChainConf {
                 )?);
                 Ok(indexer as Box<dyn SequenceAwareIndexer<H256>>)
             }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_delivery_indexer(self, conf, locator)
+                    .await
+            }
         }
         .context(ctx)
     }
@@ -349,7 +464,7 @@ impl ChainConf {
         let ctx = "Building IGP";
         let locator = self.locator(self.addresses.interchain_gas_paymaster);
 
-        match &self.connection {
+        match &self.instrumented_connection(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(
                     conf,
@@ -375,6 +490,11 @@ impl ChainConf {
                 )?);
                 Ok(paymaster as Box<dyn InterchainGasPaymaster>)
             }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_interchain_gas_paymaster(self, conf, locator)
+                    .await
+            }
         }
         .context(ctx)
     }
@@ -387,7 +507,7 @@ impl ChainConf {
         let ctx = "Building IGP indexer";
         let locator = self.locator(self.addresses.interchain_gas_paymaster);
 
-        match &self.connection {
+        match &self.instrumented_connection(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(
                     conf,
@@ -415,6 +535,56 @@ impl ChainConf {
                 )?);
                 Ok(indexer as Box<dyn SequenceAwareIndexer<InterchainGasPayment>>)
             }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_interchain_gas_payment_indexer(self, conf, locator)
+                    .await
+            }
+        }
+        .context(ctx)
+    }
+
+    /// Try to convert the chain settings into an indexer of the gas oracle
+    /// config updates of the IGP and of its storage gas oracles, starting from
+    /// the `gas_oracles` it configured before
+    pub async fn build_gas_oracle_config_indexer(
+        &self,
+        metrics: &CoreMetrics,
+        gas_oracles: Vec<H256>,
+    ) -> Result<Box<dyn SequenceAwareIndexer<GasOracleConfigUpdate>>> {
+        let ctx = "Building gas oracle config indexer";
+        let locator = self.locator(self.addresses.interchain_gas_paymaster);
+
+        match &self.instrumented_connection(metrics) {
+            ChainConnectionConf::Ethereum(conf) => {
+                self.build_ethereum(
+                    conf,
+                    &locator,
+                    metrics,
+                    h_eth::GasOracleConfigIndexerBuilder {
+                        reorg_period: self.reorg_period,
+                        gas_oracles,
+                    },
+                )
+                .await
+            }
+            ChainConnectionConf::Fuel(_) => Err(eyre!(
+                "Fuel does not support gas oracle config indexing yet"
+            ))
+            .context(ctx),
+            ChainConnectionConf::Sealevel(_) => Err(eyre!(
+                "Sealevel does not support gas oracle config indexing yet"
+            ))
+            .context(ctx),
+            ChainConnectionConf::Cosmos(_) => Err(eyre!(
+                "Cosmos does not support gas oracle config indexing yet"
+            ))
+            .context(ctx),
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_gas_oracle_config_indexer(self, conf, locator, gas_oracles)
+                    .await
+            }
         }
         .context(ctx)
     }
@@ -427,7 +597,20 @@ impl ChainConf {
         let ctx = "Building merkle tree hook indexer";
         let locator = self.locator(self.addresses.merkle_tree_hook);
 
-        match &self.connection {
+        match &self.instrumented_connection(metrics) {
+            // Legacy mailboxes are their own merkle tree
+            #[cfg(feature = "legacy")]
+            ChainConnectionConf::Ethereum(conf) if conf.legacy_mailbox => {
+                self.build_ethereum(
+                    conf,
+                    &self.locator(self.addresses.mailbox),
+                    metrics,
+                    h_eth::LegacyMerkleTreeHookIndexerBuilder {
+                        reorg_period: self.reorg_period,
+                    },
+                )
+                .await
+            }
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(
                     conf,
@@ -459,6 +642,11 @@ impl ChainConf {
                 )?);
                 Ok(indexer as Box<dyn SequenceAwareIndexer<MerkleTreeInsertion>>)
             }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_merkle_tree_hook_indexer(self, conf, locator)
+                    .await
+            }
         }
         .context(ctx)
     }
@@ -470,7 +658,7 @@ impl ChainConf {
     ) -> Result<Box<dyn ValidatorAnnounce>> {
         let ctx = "Building validator announce";
         let locator = self.locator(self.addresses.validator_announce);
-        match &self.connection {
+        match &self.instrumented_connection(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::ValidatorAnnounceBuilder {})
                     .await
@@ -490,10 +678,61 @@ impl ChainConf {
 
                 Ok(va as Box<dyn ValidatorAnnounce>)
             }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_validator_announce(self, conf, locator)
+                    .await
+            }
         }
         .context("Building ValidatorAnnounce")
     }
 
+    /// Try to convert the chain settings into an indexer of the announcements
+    /// made to the validator announce contract
+    pub async fn build_validator_announce_indexer(
+        &self,
+        metrics: &CoreMetrics,
+    ) -> Result<Box<dyn SequenceAwareIndexer<ValidatorAnnouncement>>> {
+        let ctx = "Building validator announce indexer";
+        let locator = self.locator(self.addresses.validator_announce);
+
+        match &self.instrumented_connection(metrics) {
+            ChainConnectionConf::Ethereum(conf) => {
+                self.build_ethereum(
+                    conf,
+                    &locator,
+                    metrics,
+                    h_eth::ValidatorAnnounceIndexerBuilder {
+                        reorg_period: self.reorg_period,
+                    },
+                )
+                .await
+            }
+            ChainConnectionConf::Fuel(_) => Err(eyre!(
+                "Fuel does not support validator announcement indexing yet"
+            ))
+            .context(ctx),
+            ChainConnectionConf::Sealevel(_) => Err(eyre!(
+                "Sealevel does not support validator announcement indexing yet"
+            ))
+            .context(ctx),
+            ChainConnectionConf::Cosmos(conf) => {
+                let indexer = Box::new(h_cosmos::CosmosValidatorAnnounceIndexer::new(
+                    conf.clone(),
+                    locator,
+                    self.reorg_period,
+                )?);
+                Ok(indexer as Box<dyn SequenceAwareIndexer<ValidatorAnnouncement>>)
+            }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_validator_announce_indexer(self, conf, locator)
+                    .await
+            }
+        }
+        .context(ctx)
+    }
+
     /// Try to convert the chain setting into an InterchainSecurityModule
     /// contract
     pub async fn build_ism(
@@ -504,7 +743,7 @@ impl ChainConf {
         let ctx = "Building ISM";
         let locator = self.locator(address);
 
-        match &self.connection {
+        match &self.instrumented_connection(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(
                     conf,
@@ -529,6 +768,9 @@ impl ChainConf {
                 )?);
                 Ok(ism as Box<dyn InterchainSecurityModule>)
             }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?.build_ism(self, conf, locator).await
+            }
         }
         .context(ctx)
     }
@@ -542,7 +784,7 @@ impl ChainConf {
         let ctx = "Building multisig ISM";
         let locator = self.locator(address);
 
-        match &self.connection {
+        match &self.instrumented_connection(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::MultisigIsmBuilder {})
                     .await
@@ -563,6 +805,11 @@ impl ChainConf {
                 )?);
                 Ok(ism as Box<dyn MultisigIsm>)
             }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_multisig_ism(self, conf, locator)
+                    .await
+            }
         }
         .context(ctx)
     }
@@ -579,7 +826,7 @@ impl ChainConf {
             address,
         };
 
-        match &self.connection {
+        match &self.instrumented_connection(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::RoutingIsmBuilder {})
                     .await
@@ -597,6 +844,11 @@ impl ChainConf {
                 )?);
                 Ok(ism as Box<dyn RoutingIsm>)
             }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_routing_ism(self, conf, locator)
+                    .await
+            }
         }
         .context(ctx)
     }
@@ -613,7 +865,7 @@ impl ChainConf {
             address,
         };
 
-        match &self.connection {
+        match &self.instrumented_connection(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::AggregationIsmBuilder {})
                     .await
@@ -632,6 +884,11 @@ impl ChainConf {
 
                 Ok(ism as Box<dyn AggregationIsm>)
             }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_aggregation_ism(self, conf, locator)
+                    .await
+            }
         }
         .context(ctx)
     }
@@ -648,7 +905,7 @@ impl ChainConf {
             address,
         };
 
-        match &self.connection {
+        match &self.instrumented_connection(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::CcipReadIsmBuilder {})
                     .await
@@ -660,11 +917,174 @@ impl ChainConf {
             ChainConnectionConf::Cosmos(_) => {
                 Err(eyre!("Cosmos does not support CCIP read ISM yet")).context(ctx)
             }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_ccip_read_ism(self, conf, locator)
+                    .await
+            }
         }
         .context(ctx)
     }
 
-    async fn signer<S: BuildableWithSignerConf>(&self) -> Result<Option<S>> {
+    /// Try to convert the chain setting into a native token transferer, which
+    /// sends the native token from the configured signer.
+    pub async fn build_native_token_transfer(
+        &self,
+        metrics: &CoreMetrics,
+    ) -> Result<Box<dyn NativeTokenTransfer>> {
+        let ctx = "Building native token transfer";
+        let locator = self.locator(H256::zero());
+
+        match &self.instrumented_connection(metrics) {
+            ChainConnectionConf::Ethereum(conf) => {
+                self.build_ethereum(
+                    conf,
+                    &locator,
+                    metrics,
+                    h_eth::NativeTokenTransferBuilder {},
+                )
+                .await
+            }
+            ChainConnectionConf::Fuel(_) => {
+                Err(eyre!("Fuel does not support native token transfers yet")).context(ctx)
+            }
+            ChainConnectionConf::Sealevel(_) => Err(eyre!(
+                "Sealevel does not support native token transfers yet"
+            ))
+            .context(ctx),
+            ChainConnectionConf::Cosmos(_) => {
+                Err(eyre!("Cosmos does not support native token transfers yet")).context(ctx)
+            }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_native_token_transfer(self, conf, locator)
+                    .await
+            }
+        }
+        .context(ctx)
+    }
+
+    /// Try to convert the chain setting into a message dispatcher, which
+    /// dispatches messages through the mailbox from the configured signer.
+    pub async fn build_message_dispatcher(
+        &self,
+        metrics: &CoreMetrics,
+    ) -> Result<Box<dyn MessageDispatcher>> {
+        let ctx = "Building message dispatcher";
+        let locator = self.locator(self.addresses.mailbox);
+
+        match &self.instrumented_connection(metrics) {
+            ChainConnectionConf::Ethereum(conf) => {
+                self.build_ethereum(
+                    conf,
+                    &locator,
+                    metrics,
+                    h_eth::MessageDispatcherBuilder {
+                        interchain_gas_paymaster: self.addresses.interchain_gas_paymaster,
+                    },
+                )
+                .await
+            }
+            ChainConnectionConf::Fuel(_) => {
+                Err(eyre!("Fuel does not support message dispatchers yet")).context(ctx)
+            }
+            ChainConnectionConf::Sealevel(_) => {
+                Err(eyre!("Sealevel does not support message dispatchers yet")).context(ctx)
+            }
+            ChainConnectionConf::Cosmos(_) => {
+                Err(eyre!("Cosmos does not support message dispatchers yet")).context(ctx)
+            }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_message_dispatcher(self, conf, locator)
+                    .await
+            }
+        }
+        .context(ctx)
+    }
+
+    /// Try to convert the chain setting into a warp route TokenRouter contract
+    pub async fn build_token_router(
+        &self,
+        address: H256,
+        metrics: &CoreMetrics,
+    ) -> Result<Box<dyn TokenRouter>> {
+        let ctx = "Building token router";
+        let locator = ContractLocator {
+            domain: &self.domain,
+            address,
+        };
+
+        match &self.instrumented_connection(metrics) {
+            ChainConnectionConf::Ethereum(conf) => {
+                self.build_ethereum(conf, &locator, metrics, h_eth::TokenRouterBuilder {})
+                    .await
+            }
+            ChainConnectionConf::Fuel(_) => {
+                Err(eyre!("Fuel does not support token routers yet")).context(ctx)
+            }
+            ChainConnectionConf::Sealevel(conf) => {
+                let router = Box::new(h_sealevel::SealevelTokenRouter::new(conf, locator)?);
+                Ok(router as Box<dyn TokenRouter>)
+            }
+            ChainConnectionConf::Cosmos(_) => {
+                Err(eyre!("Cosmos does not support token routers yet")).context(ctx)
+            }
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_token_router(self, conf, locator)
+                    .await
+            }
+        }
+        .context(ctx)
+    }
+
+    /// Try to convert the chain settings into an indexer of the transfers the
+    /// warp route router at `router` sent to remote chains
+    pub async fn build_transfer_remote_indexer(
+        &self,
+        router: H256,
+        _metrics: &CoreMetrics,
+    ) -> Result<Box<dyn SequenceAwareIndexer<TransferRemote>>> {
+        let ctx = "Building transfer remote indexer";
+        let locator = self.locator(self.addresses.mailbox);
+
+        match &self.connection {
+            ChainConnectionConf::Ethereum(_) => Err(eyre!(
+                "Ethereum does not support transfer remote indexing yet"
+            ))
+            .context(ctx),
+            ChainConnectionConf::Fuel(_) => {
+                Err(eyre!("Fuel does not support transfer remote indexing yet")).context(ctx)
+            }
+            ChainConnectionConf::Sealevel(conf) => {
+                let indexer = Box::new(h_sealevel::SealevelTransferRemoteIndexer::new(
+                    conf, locator, router,
+                )?);
+                Ok(indexer as Box<dyn SequenceAwareIndexer<TransferRemote>>)
+            }
+            ChainConnectionConf::Cosmos(_) => Err(eyre!(
+                "Cosmos does not support transfer remote indexing yet"
+            ))
+            .context(ctx),
+            ChainConnectionConf::Backend(conf) => {
+                self.backend(conf)?
+                    .build_transfer_remote_indexer(self, conf, locator)
+                    .await
+            }
+        }
+        .context(ctx)
+    }
+
+    /// The backend of a chain which is not built into [`ChainConnectionConf`]
+    fn backend(&self, conf: &BackendConnectionConf) -> Result<&'static dyn ChainBackend> {
+        chain_backends()
+            .get(conf.protocol)
+            .ok_or_else(|| eyre!("{:?} support is not enabled in this build", conf.protocol))
+    }
+
+    /// Build the configured signer, if any, as a signer of type `S`
+    pub(crate) async fn signer<S: BuildableWithSignerConf>(&self) -> Result<Option<S>> {
         if let Some(conf) = &self.signer {
             Ok(Some(conf.build::<S>().await?))
         } else {
@@ -684,6 +1104,9 @@ impl ChainConf {
                     Box::new(conf.build::<h_sealevel::Keypair>().await?)
                 }
                 ChainConnectionConf::Cosmos(_) => Box::new(conf.build::<h_cosmos::Signer>().await?),
+                ChainConnectionConf::Backend(connection) => {
+                    self.backend(connection)?.build_chain_signer(conf).await?
+                }
             };
             Ok(Some(chain_signer))
         } else {
@@ -766,6 +1189,36 @@ impl ChainConf {
         cfg
     }
 
+    /// The connection of the chain, recording the RPC requests made on
+    /// Sealevel and Cosmos chains in `metrics`, as the ethers middleware does
+    /// on Ethereum chains
+    fn instrumented_connection(&self, metrics: &CoreMetrics) -> ChainConnectionConf {
+        let rpc_metrics = metrics.json_rpc_client_metrics();
+        let (Some(request_count), Some(request_payload_bytes)) = (
+            rpc_metrics.request_count(),
+            rpc_metrics.request_payload_bytes(),
+        ) else {
+            return self.connection.clone();
+        };
+        let rpc_usage = RpcUsageMetrics::new(
+            self.domain.name(),
+            request_count.clone(),
+            request_payload_bytes.clone(),
+        );
+        match self.connection.clone() {
+            ChainConnectionConf::Sealevel(conf) => {
+                ChainConnectionConf::Sealevel(h_sealevel::ConnectionConf {
+                    rpc_usage: Some(rpc_usage),
+                    ..conf
+                })
+            }
+            ChainConnectionConf::Cosmos(conf) => {
+                ChainConnectionConf::Cosmos(conf.with_rpc_usage(rpc_usage))
+            }
+            connection => connection,
+        }
+    }
+
     fn locator(&self, address: H256) -> ContractLocator {
         ContractLocator {
             domain: &self.domain,
@@ -4,6 +4,7 @@ use crate::{
 };
 use core::str::FromStr;
 use eyre::{eyre, Context, Report, Result};
+use hyperlane_core::config::ProxyConf;
 use prometheus::IntGauge;
 use rusoto_core::Region;
 use std::{env, path::PathBuf};
@@ -101,7 +102,8 @@ impl FromStr for CheckpointSyncerConf {
 }
 
 impl CheckpointSyncerConf {
-    /// Turn conf info a Checkpoint Syncer
+    /// Turn conf info a Checkpoint Syncer, whose requests to remote storage
+    /// are routed through `proxy`, if any
     ///
     /// # Panics
     ///
@@ -110,8 +112,9 @@ impl CheckpointSyncerConf {
     pub async fn build_and_validate(
         &self,
         latest_index_gauge: Option<IntGauge>,
+        proxy: Option<&ProxyConf>,
     ) -> Result<Box<dyn CheckpointSyncer>, Report> {
-        let syncer: Box<dyn CheckpointSyncer> = self.build(latest_index_gauge).await?;
+        let syncer: Box<dyn CheckpointSyncer> = self.build(latest_index_gauge, proxy).await?;
 
         match syncer.reorg_status().await {
             Ok(Some(reorg_event)) => {
@@ -135,6 +138,7 @@ impl CheckpointSyncerConf {
     async fn build(
         &self,
         latest_index_gauge: Option<IntGauge>,
+        proxy: Option<&ProxyConf>,
     ) -> Result<Box<dyn CheckpointSyncer>, Report> {
         Ok(match self {
             CheckpointSyncerConf::LocalStorage { path } => {
@@ -149,6 +153,7 @@ impl CheckpointSyncerConf {
                 folder.clone(),
                 region.clone(),
                 latest_index_gauge,
+                proxy.cloned(),
             )),
             CheckpointSyncerConf::Gcs {
                 bucket,
@@ -167,6 +172,7 @@ impl CheckpointSyncerConf {
 
                 Box::new(
                     GcsStorageClientBuilder::new(auth)
+                        .with_proxy(proxy.cloned())
                         .build(bucket, folder.to_owned())
                         .await?,
                 )
@@ -195,7 +201,7 @@ mod test {
         // then `drop` it, to simulate a restart
         {
             let checkpoint_syncer = checkpoint_syncer_conf
-                .build_and_validate(None)
+                .build_and_validate(None, None)
                 .await
                 .unwrap();
 
@@ -225,10 +231,11 @@ mod test {
 
         // Initialize a new checkpoint syncer and expect it to panic due to the reorg event.
         // `AssertUnwindSafe` is required for ignoring some type checks so the panic can be caught.
-        let startup_result = AssertUnwindSafe(checkpoint_syncer_conf.build_and_validate(None))
-            .catch_unwind()
-            .await
-            .unwrap_err();
+        let startup_result =
+            AssertUnwindSafe(checkpoint_syncer_conf.build_and_validate(None, None))
+                .catch_unwind()
+                .await
+                .unwrap_err();
         if let Some(err) = startup_result.downcast_ref::<String>() {
             assert_eq!(
                 *err,
@@ -0,0 +1,155 @@
+//! Operators can pin the code hashes of the contracts of a chain, so that an
+//! address mistyped in the config, or pointing to a look-alike deployment, is
+//! caught when the agent starts instead of being trusted.
+
+use std::time::Duration;
+
+use eyre::{bail, Result};
+use futures_util::future::join_all;
+use hyperlane_core::{ChainResult, HyperlaneProvider, H256};
+use tokio::time::timeout;
+use tracing::{error, info, warn};
+
+use crate::{
+    settings::{ChainConf, Settings},
+    CoreMetrics,
+};
+
+/// How long checking the code hashes of a chain may take
+const CODE_HASH_CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The code hashes the contracts of a chain are expected to have
+#[derive(Clone, Debug, Default)]
+pub struct CodeHashesConf {
+    /// The contracts whose code hash is checked
+    pub expected: Vec<ExpectedCodeHash>,
+    /// Whether a mismatch keeps the agent from starting, rather than only
+    /// being logged
+    pub enforce: bool,
+}
+
+/// The code hash a contract is expected to have
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpectedCodeHash {
+    /// The key of the contract's address in the chain config
+    pub contract: String,
+    /// The address of the contract
+    pub address: H256,
+    /// The expected hash of its code
+    pub code_hash: H256,
+}
+
+/// A contract whose code doesn't hash to the expected hash
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeHashMismatch {
+    /// The key of the contract's address in the chain config
+    pub contract: String,
+    /// The address of the contract
+    pub address: H256,
+    /// The expected hash of its code
+    pub expected: H256,
+    /// The hash of the code deployed at the address
+    pub actual: H256,
+}
+
+impl ChainConf {
+    /// The contracts of the chain whose code doesn't hash to the expected
+    /// hash. Chains whose provider can't hash the code of a contract have no
+    /// mismatches.
+    pub async fn code_hash_mismatches(
+        &self,
+        provider: &dyn HyperlaneProvider,
+    ) -> ChainResult<Vec<CodeHashMismatch>> {
+        let mut mismatches = vec![];
+        for expected in &self.code_hashes.expected {
+            let Some(actual) = provider.code_hash(&expected.address).await? else {
+                warn!(chain = %self.domain, "Code hashes can't be checked on this chain");
+                break;
+            };
+            if actual != expected.code_hash {
+                mismatches.push(CodeHashMismatch {
+                    contract: expected.contract.clone(),
+                    address: expected.address,
+                    expected: expected.code_hash,
+                    actual,
+                });
+            }
+        }
+        Ok(mismatches)
+    }
+
+    /// Whether the contracts of the chain have the expected code hashes,
+    /// logging the mismatches
+    async fn verify_code_hashes(&self, metrics: &CoreMetrics) -> Result<bool> {
+        let provider = self.build_provider(metrics).await?;
+        let mismatches = self.code_hash_mismatches(provider.as_ref()).await?;
+        if mismatches.is_empty() {
+            info!(
+                chain = %self.domain,
+                contracts = self.code_hashes.expected.len(),
+                "Verified code hashes"
+            );
+            return Ok(true);
+        }
+        let formatter = self.connection.address_formatter();
+        for mismatch in &mismatches {
+            error!(
+                chain = %self.domain,
+                contract = mismatch.contract,
+                address = %formatter.format(mismatch.address),
+                expected = ?mismatch.expected,
+                actual = ?mismatch.actual,
+                "Contract code doesn't match the expected code hash"
+            );
+        }
+        Ok(false)
+    }
+}
+
+impl Settings {
+    /// Check the code hashes pinned in the config of the agent's chains, which
+    /// are the only chains its settings are loaded with. Mismatches keep the
+    /// agent from starting on chains enforcing their code hashes, and are only
+    /// logged on the others. Failing to check them, e.g. because an RPC is
+    /// down, is only logged, so that it doesn't keep the agent from starting.
+    pub async fn verify_code_hashes(&self, metrics: &CoreMetrics) -> Result<()> {
+        let chains = self
+            .chains
+            .values()
+            .filter(|chain| !chain.code_hashes.expected.is_empty());
+        let results = join_all(chains.map(|chain| async move {
+            let result = timeout(CODE_HASH_CHECK_TIMEOUT, chain.verify_code_hashes(metrics)).await;
+            (chain, result)
+        }))
+        .await;
+
+        let mut mismatched = vec![];
+        for (chain, result) in results {
+            match result {
+                Ok(Ok(true)) => {}
+                Ok(Ok(false)) if chain.code_hashes.enforce => mismatched.push(chain.domain.name()),
+                Ok(Ok(false)) => warn!(
+                    chain = %chain.domain,
+                    "Contracts don't match their expected code hash, continuing anyway"
+                ),
+                Ok(Err(err)) => warn!(
+                    chain = %chain.domain,
+                    ?err,
+                    "Failed to check code hashes, continuing anyway"
+                ),
+                Err(_) => warn!(
+                    chain = %chain.domain,
+                    timeout = ?CODE_HASH_CHECK_TIMEOUT,
+                    "Timed out checking code hashes, continuing anyway"
+                ),
+            }
+        }
+        if !mismatched.is_empty() {
+            bail!(
+                "Contracts don't match their expected code hash on {}",
+                mismatched.join(", ")
+            );
+        }
+        Ok(())
+    }
+}
@@ -62,9 +62,12 @@
 //! 5. Arguments passed to the agent on the command line.
 //!    E.g. `--originChainName ethereum`
 
+pub use backends::*;
 pub use base::*;
 pub use chains::*;
+pub use code_hashes::*;
 pub use checkpoint_syncer::*;
+pub use notifications::*;
 pub use signers::*;
 pub use trace::*;
 
@@ -77,10 +80,16 @@ mod envs {
 
 /// AWS Credentials provider.
 pub(crate) mod aws_credentials;
+/// Feature-gated chain backends
+mod backends;
 mod base;
 /// Chain configuration
 mod chains;
+/// Pinned contract code hashes
+mod code_hashes;
 pub mod loader;
+/// Notifications configuration
+mod notifications;
 /// Signer configuration
 mod signers;
 /// Tracing subscriber management
@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use url::Url;
+
+/// Default Events API v2 endpoint of PagerDuty
+pub const DEFAULT_PAGERDUTY_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// How long indexing of a chain can fail before being reported as stalled, if
+/// not configured
+pub const DEFAULT_INDEXING_STALL_THRESHOLD: Duration = Duration::from_secs(600);
+
+/// Configuration of the notifications agents send on critical events
+#[derive(Debug, Clone)]
+pub struct NotificationsConf {
+    /// Where notifications are sent to, on top of being logged
+    pub sinks: Vec<NotificationSinkConf>,
+    /// How long indexing of a chain can fail before being reported as stalled
+    pub indexing_stall_threshold: Duration,
+}
+
+impl Default for NotificationsConf {
+    fn default() -> Self {
+        Self {
+            sinks: vec![],
+            indexing_stall_threshold: DEFAULT_INDEXING_STALL_THRESHOLD,
+        }
+    }
+}
+
+/// A destination of notifications
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationSinkConf {
+    /// The kind of service notifications are sent to
+    pub kind: NotificationSinkKind,
+    /// Template of the notification text, in which `{{field}}` is replaced by
+    /// the field of the event. Defaults to a summary of the event.
+    pub template: Option<String>,
+}
+
+/// The kind of service notifications are sent to, with its connection info
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationSinkKind {
+    /// Posts the fields of the event and its text as JSON
+    Webhook {
+        /// Url to post to
+        url: Url,
+    },
+    /// Posts the text of the event to a Slack incoming webhook
+    Slack {
+        /// Url of the incoming webhook
+        url: Url,
+    },
+    /// Triggers a PagerDuty incident through the Events API v2
+    PagerDuty {
+        /// Url of the Events API
+        url: Url,
+        /// Integration key of the PagerDuty service
+        routing_key: String,
+    },
+}
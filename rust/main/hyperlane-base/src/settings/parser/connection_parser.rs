@@ -1,15 +1,141 @@
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use eyre::eyre;
+use reqwest::header::{HeaderName, HeaderValue};
 use url::Url;
 
 use h_eth::TransactionOverrides;
-use hyperlane_core::config::{ConfigErrResultExt, OperationBatchConfig};
+use hyperlane_core::config::{
+    ConfigErrResultExt, OperationBatchConfig, ProxyConf, ProxyKind, RpcAuth, RpcHeadersConf,
+    RpcTlsConf,
+};
 use hyperlane_core::{config::ConfigParsingError, HyperlaneDomainProtocol};
 use hyperlane_cosmos::NativeToken;
 
 use crate::settings::envs::*;
-use crate::settings::ChainConnectionConf;
+use crate::settings::{chain_backends, BackendConnectionConf, ChainConnectionConf, SignerConf};
+
+use super::{parse_base_and_override_urls, parse_cosmos_gas_price, parse_signer, ValueParser};
+
+/// Headers sent with every request to the RPCs of a chain. Headers are a list
+/// of names and values rather than a map, so that their names aren't case
+/// converted like config keys.
+fn parse_rpc_headers(chain: &ValueParser, err: &mut ConfigParsingError) -> RpcHeadersConf {
+    let headers = chain
+        .chain(err)
+        .get_opt_key("rpcHeaders")
+        .into_array_iter()
+        .map(|headers| {
+            headers
+                .filter_map(|header| {
+                    let name = header.chain(err).get_key("name").parse_string().end()?;
+                    let value = header.chain(err).get_key("value").parse_string().end()?;
+                    if HeaderName::from_bytes(name.as_bytes()).is_err()
+                        || HeaderValue::from_str(value).is_err()
+                    {
+                        err.push(&header.cwp + "name", eyre!("Invalid rpc header `{name}`"));
+                        return None;
+                    }
+                    Some((name.to_owned(), value.to_owned()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let auth = chain
+        .get_opt_key("rpcAuth")
+        .take_err(err, || &chain.cwp + "rpc_auth")
+        .flatten()
+        .and_then(|auth| {
+            let field = |err: &mut ConfigParsingError, key: &str| {
+                auth.chain(err)
+                    .get_key(key)
+                    .parse_string()
+                    .end()
+                    .map(str::to_owned)
+            };
+            match auth.chain(err).get_key("type").parse_string().end()? {
+                "basic" => Some(RpcAuth::Basic {
+                    username: field(err, "username")?,
+                    password: field(err, "password")?,
+                }),
+                "bearer" => Some(RpcAuth::Bearer {
+                    token: field(err, "token")?,
+                }),
+                ty => {
+                    Err(eyre!("unknown rpc auth type `{ty}`")).take_err(err, || &auth.cwp + "type")
+                }
+            }
+        });
+
+    RpcHeadersConf { headers, auth }
+}
+
+/// The client certificate presented to the RPCs of a chain requiring mutual
+/// TLS, read from the PEM files the config points to. The certificates are
+/// checked here, so that clients can't fail to present them later.
+fn parse_rpc_tls(chain: &ValueParser, err: &mut ConfigParsingError) -> Option<RpcTlsConf> {
+    let tls = chain
+        .get_opt_key("rpcTls")
+        .take_err(err, || &chain.cwp + "rpc_tls")
+        .flatten()?;
+    let read_pem = |err: &mut ConfigParsingError, key: &str, cwp_key: &str| {
+        let path = tls.chain(err).get_opt_key(key).parse_string().end()?;
+        std::fs::read(path)
+            .map_err(|e| eyre!("Failed to read `{path}`: {e}"))
+            .take_err(err, || &tls.cwp + cwp_key)
+    };
+    let client_cert = read_pem(err, "clientCert", "client_cert");
+    let client_key = read_pem(err, "clientKey", "client_key");
+    let ca_cert = read_pem(err, "caCert", "ca_cert");
+    let (Some(client_cert), Some(client_key)) = (client_cert, client_key) else {
+        err.push(
+            &chain.cwp + "rpc_tls",
+            eyre!("Expected both a client certificate and key"),
+        );
+        return None;
+    };
+
+    reqwest::Identity::from_pkcs8_pem(&client_cert, &client_key)
+        .map_err(|e| eyre!("Invalid client certificate or PKCS#8 key: {e}"))
+        .take_err(err, || &tls.cwp + "client_cert")?;
+    if let Some(ca_cert) = &ca_cert {
+        reqwest::Certificate::from_pem(ca_cert)
+            .map_err(|e| eyre!("Invalid CA certificate: {e}"))
+            .take_err(err, || &tls.cwp + "ca_cert")?;
+    }
+    Some(RpcTlsConf {
+        client_cert,
+        client_key,
+        ca_cert,
+    })
+}
 
-use super::{parse_base_and_override_urls, parse_cosmos_gas_price, ValueParser};
+/// Whether the requests to the RPCs of a chain are recorded to or replayed
+/// from an RPC trace file. The file is only opened once the providers of the
+/// chain are built, which all share it.
+fn parse_rpc_trace(
+    chain: &ValueParser,
+    err: &mut ConfigParsingError,
+) -> Option<h_eth::RpcTraceConf> {
+    let trace = chain
+        .get_opt_key("rpcTrace")
+        .take_err(err, || &chain.cwp + "rpc_trace")
+        .flatten()?;
+    let path = PathBuf::from(trace.chain(err).get_key("path").parse_string().end()?);
+    match trace.chain(err).get_key("mode").parse_string().end()? {
+        "record" => Some(h_eth::RpcTraceConf::Record(Arc::new(
+            h_eth::RpcTraceRecorder::new(path),
+        ))),
+        "replay" => Some(h_eth::RpcTraceConf::Replay(Arc::new(
+            h_eth::RpcTraceReplayer::new(path),
+        ))),
+        mode => Err(eyre!("unknown rpc trace mode `{mode}`")).take_err(err, || &trace.cwp + "mode"),
+    }
+}
 
 #[allow(clippy::question_mark)] // TODO: `rustc` 1.80.1 clippy issue
 pub fn build_ethereum_connection_conf(
@@ -18,6 +144,7 @@ pub fn build_ethereum_connection_conf(
     err: &mut ConfigParsingError,
     default_rpc_consensus_type: &str,
     operation_batch: OperationBatchConfig,
+    proxy: Option<ProxyConf>,
 ) -> Option<ChainConnectionConf> {
     let Some(first_url) = rpcs.to_owned().clone().into_iter().next() else {
         return None;
@@ -36,6 +163,32 @@ pub fn build_ethereum_connection_conf(
         "quorum" => Some(h_eth::RpcConnectionConf::HttpQuorum {
             urls: rpcs.to_owned().clone(),
         }),
+        "hybrid" => {
+            // Uses the first websocket url of the RPCs, falling back to the
+            // HTTP urls while it is down
+            let ws_url = chain
+                .chain(err)
+                .get_key("rpcUrls")
+                .into_array_iter()
+                .and_then(|mut urls| {
+                    urls.find_map(|url| {
+                        url.chain(err)
+                            .get_opt_key("webSocket")
+                            .parse_from_str("Invalid websocket url")
+                            .end()
+                    })
+                });
+            match ws_url {
+                Some(ws_url) => Some(h_eth::RpcConnectionConf::WsHttpHybrid {
+                    ws_url,
+                    http_urls: rpcs.to_owned().clone(),
+                }),
+                None => Err(eyre!(
+                    "hybrid rpc consensus type requires a webSocket rpc url"
+                ))
+                .take_err(err, || &chain.cwp + "rpc_urls"),
+            }
+        }
         ty => Err(eyre!("unknown rpc consensus type `{ty}`"))
             .take_err(err, || &chain.cwp + "rpc_consensus_type"),
     };
@@ -68,10 +221,139 @@ pub fn build_ethereum_connection_conf(
         })
         .unwrap_or_default();
 
+    let revert_tracing = match chain
+        .chain(err)
+        .get_opt_key("revertTracing")
+        .parse_string()
+        .unwrap_or("none")
+    {
+        "none" => None,
+        "debugTraceCall" => Some(h_eth::RevertTraceMethod::DebugTraceCall),
+        "traceCall" => Some(h_eth::RevertTraceMethod::TraceCall),
+        method => Err(eyre!("unknown revert tracing method `{method}`"))
+            .take_err(err, || &chain.cwp + "revert_tracing"),
+    };
+
+    let finality_source = match chain
+        .chain(err)
+        .get_opt_key("finalitySource")
+        .parse_string()
+        .unwrap_or("blockDepth")
+    {
+        "blockDepth" => Some(h_eth::FinalitySource::BlockDepth),
+        "finalized" => Some(h_eth::FinalitySource::FinalizedTag),
+        "safe" => Some(h_eth::FinalitySource::SafeTag),
+        source => Err(eyre!("unknown finality source `{source}`"))
+            .take_err(err, || &chain.cwp + "finality_source"),
+    }
+    .unwrap_or_default();
+
+    // Request budgets are set per rpc url, keyed by its http url
+    let requests_per_second = chain
+        .chain(err)
+        .get_opt_key("rpcUrls")
+        .into_array_iter()
+        .map(|urls| {
+            urls.filter_map(|url| {
+                let budget = url
+                    .chain(err)
+                    .get_opt_key("requestsPerSecond")
+                    .parse_u32()
+                    .end()?;
+                let Some(budget) = NonZeroU32::new(budget) else {
+                    err.push(
+                        &url.cwp + "requests_per_second",
+                        eyre!("requestsPerSecond must be greater than zero"),
+                    );
+                    return None;
+                };
+                let http_url = url
+                    .chain(err)
+                    .get_key("http")
+                    .parse_from_str("Invalid url")
+                    .end()?;
+                Some((http_url, budget))
+            })
+            .collect::<HashMap<Url, _>>()
+        })
+        .unwrap_or_default();
+
+    let broadcast_transactions = chain
+        .chain(err)
+        .get_opt_key("broadcastTransactions")
+        .parse_bool()
+        .unwrap_or(false);
+
+    let legacy_mailbox = chain
+        .chain(err)
+        .get_opt_key("legacyMailbox")
+        .parse_bool()
+        .unwrap_or(false);
+    if legacy_mailbox && !cfg!(feature = "legacy") {
+        err.push(
+            &chain.cwp + "legacy_mailbox",
+            eyre!("Legacy mailboxes require the agent to be built with the `legacy` feature"),
+        );
+    }
+
+    let processor = chain
+        .get_opt_key("processor")
+        .take_err(err, || &chain.cwp + "processor")
+        .flatten()
+        .and_then(|processor| {
+            let address = processor
+                .chain(err)
+                .get_key("address")
+                .parse_address_hash()
+                .end()?;
+            let function = processor
+                .chain(err)
+                .get_opt_key("function")
+                .parse_string()
+                .unwrap_or("process")
+                .to_owned();
+            let arguments = processor
+                .chain(err)
+                .get_opt_key("arguments")
+                .into_array_iter()
+                .map(|arguments| {
+                    arguments
+                        .filter_map(|argument| match argument.chain(err).parse_string().end()? {
+                            "metadata" => Some(h_eth::ProcessArgument::Metadata),
+                            "message" => Some(h_eth::ProcessArgument::Message),
+                            "mailbox" => Some(h_eth::ProcessArgument::Mailbox),
+                            other => Err(eyre!("unknown processor argument `{other}`"))
+                                .take_err(err, || argument.cwp.clone()),
+                        })
+                        .collect()
+                })
+                .unwrap_or_else(|| {
+                    vec![
+                        h_eth::ProcessArgument::Metadata,
+                        h_eth::ProcessArgument::Message,
+                    ]
+                });
+            Some(h_eth::ProcessorContract {
+                address,
+                function,
+                arguments,
+            })
+        });
+
     Some(ChainConnectionConf::Ethereum(h_eth::ConnectionConf {
         rpc_connection: rpc_connection_conf?,
         transaction_overrides,
         operation_batch,
+        revert_tracing,
+        finality_source,
+        requests_per_second,
+        broadcast_transactions,
+        processor,
+        rpc_headers: parse_rpc_headers(chain, err),
+        proxy,
+        rpc_tls: parse_rpc_tls(chain, err),
+        legacy_mailbox,
+        rpc_trace: parse_rpc_trace(chain, err),
     }))
 }
 
@@ -80,6 +362,7 @@ pub fn build_cosmos_connection_conf(
     chain: &ValueParser,
     err: &mut ConfigParsingError,
     operation_batch: OperationBatchConfig,
+    proxy: Option<ProxyConf>,
 ) -> Option<ChainConnectionConf> {
     let mut local_err = ConfigParsingError::default();
     let grpcs =
@@ -156,6 +439,48 @@ pub fn build_cosmos_connection_conf(
         denom: native_token_denom.to_owned(),
     };
 
+    let fee_granter = chain
+        .chain(err)
+        .get_opt_key("feeGranter")
+        .parse_string()
+        .end()
+        .map(str::to_owned);
+
+    let fee_payer = chain
+        .chain(&mut local_err)
+        .get_opt_key("feePayer")
+        .and_then(parse_signer)
+        .end()
+        .and_then(|fee_payer| match fee_payer {
+            SignerConf::CosmosKey {
+                key,
+                prefix,
+                account_address_type,
+                public_key_type_url,
+            } => h_cosmos::Signer::new(
+                key.as_bytes().to_vec(),
+                prefix,
+                &account_address_type,
+                public_key_type_url,
+            )
+            .take_err(&mut local_err, || &chain.cwp + "fee_payer"),
+            _ => {
+                local_err.push(
+                    &chain.cwp + "fee_payer",
+                    eyre!("Fee payer must be a cosmosKey signer"),
+                );
+                None
+            }
+        });
+
+    // The tendermint RPC client only tunnels through HTTP proxies
+    if proxy.as_ref().map(ProxyConf::kind) == Some(ProxyKind::Socks5) {
+        local_err.push(
+            &chain.cwp + "proxy",
+            eyre!("Cosmos chains only support http proxies"),
+        );
+    }
+
     if !local_err.is_ok() {
         err.merge(local_err);
         None
@@ -170,6 +495,11 @@ pub fn build_cosmos_connection_conf(
             contract_address_bytes.unwrap().try_into().unwrap(),
             operation_batch,
             native_token,
+            fee_granter,
+            fee_payer,
+            parse_rpc_headers(chain, err),
+            proxy,
+            parse_rpc_tls(chain, err),
         )))
     }
 }
@@ -181,6 +511,7 @@ pub fn build_connection_conf(
     err: &mut ConfigParsingError,
     default_rpc_consensus_type: &str,
     operation_batch: OperationBatchConfig,
+    proxy: Option<ProxyConf>,
 ) -> Option<ChainConnectionConf> {
     match domain_protocol {
         HyperlaneDomainProtocol::Ethereum => build_ethereum_connection_conf(
@@ -189,6 +520,7 @@ pub fn build_connection_conf(
             err,
             default_rpc_consensus_type,
             operation_batch,
+            proxy,
         ),
         HyperlaneDomainProtocol::Fuel => rpcs
             .iter()
@@ -198,10 +530,29 @@ pub fn build_connection_conf(
             ChainConnectionConf::Sealevel(h_sealevel::ConnectionConf {
                 url: url.clone(),
                 operation_batch,
+                rpc_headers: parse_rpc_headers(chain, err),
+                proxy,
+                rpc_tls: parse_rpc_tls(chain, err),
+                rpc_usage: None,
             })
         }),
         HyperlaneDomainProtocol::Cosmos => {
-            build_cosmos_connection_conf(rpcs, chain, err, operation_batch)
+            build_cosmos_connection_conf(rpcs, chain, err, operation_batch, proxy)
+        }
+        protocol => {
+            if chain_backends().get(protocol).is_none() {
+                err.push(
+                    &chain.cwp + "protocol",
+                    eyre!("{protocol:?} support is not enabled in this build"),
+                );
+                return None;
+            }
+            rpcs.iter().next().map(|url| {
+                ChainConnectionConf::Backend(BackendConnectionConf {
+                    protocol,
+                    url: url.clone(),
+                })
+            })
         }
     }
 }
@@ -7,6 +7,7 @@
 use std::{
     collections::{HashMap, HashSet},
     default::Default,
+    time::Duration,
 };
 
 use convert_case::{Case, Casing};
@@ -18,21 +19,29 @@ use url::Url;
 
 use h_cosmos::RawCosmosAmount;
 use hyperlane_core::{
-    cfg_unwrap_all, config::*, HyperlaneDomain, HyperlaneDomainProtocol,
-    HyperlaneDomainTechnicalStack, IndexMode,
+    cfg_unwrap_all, config::*, register_domains, DomainMetadata, HyperlaneDomain,
+    HyperlaneDomainProtocol, HyperlaneDomainTechnicalStack, HyperlaneDomainType, IndexMode,
+    MessageVersion, MessageVersions,
 };
 
 use crate::settings::{
     chains::IndexSettings, parser::connection_parser::build_connection_conf, trace::TracingConfig,
-    ChainConf, CoreContractAddresses, Settings, SignerConf,
+    BackfillConf, ChainConf, ChainConnectionConf, CodeHashesConf, CoreContractAddresses,
+    ExpectedCodeHash, NotificationSinkConf, NotificationSinkKind, NotificationsConf, Settings,
+    SignerConf, DEFAULT_INDEXING_STALL_THRESHOLD, DEFAULT_PAGERDUTY_URL,
 };
 
 pub use super::envs::*;
 
 pub use self::json_value_parser::ValueParser;
+use self::validation::validate_chain_conf;
 
 mod connection_parser;
 mod json_value_parser;
+mod validation;
+
+/// Number of backfill chunks indexed at the same time, if not configured
+const DEFAULT_BACKFILL_CONCURRENCY: u32 = 4;
 
 /// The base agent config
 #[derive(Debug, Deserialize)]
@@ -69,6 +78,40 @@ impl FromRawConf<RawAgentConf, Option<&HashSet<&str>>> for Settings {
             .parse_value("Invalid log level")
             .unwrap_or_default();
 
+        let runtime_instrumentation = p
+            .chain(&mut err)
+            .get_opt_key("log")
+            .get_opt_key("runtimeInstrumentation")
+            .parse_bool()
+            .unwrap_or(false);
+
+        let rpc_usage_summary_interval = p
+            .chain(&mut err)
+            .get_opt_key("log")
+            .get_opt_key("rpcUsageSummaryInterval")
+            .parse_u64()
+            .end();
+        if rpc_usage_summary_interval == Some(0) {
+            err.push(
+                cwp + "log" + "rpcUsageSummaryInterval",
+                eyre!("RPC usage summary interval must be greater than 0"),
+            );
+        }
+        let rpc_usage_summary_interval = rpc_usage_summary_interval.map(Duration::from_secs);
+
+        // Domains of the config extend the registry used to resolve the chains
+        let domains: Vec<DomainMetadata> = p
+            .chain(&mut err)
+            .get_opt_key("domains")
+            .into_array_iter()
+            .map(|domains| {
+                domains
+                    .filter_map(|domain| parse_domain_metadata(domain).take_config_err(&mut err))
+                    .collect()
+            })
+            .unwrap_or_default();
+        register_domains(domains);
+
         let raw_chains: Vec<(String, ValueParser)> = if let Some(filter) = filter {
             p.chain(&mut err)
                 .get_opt_key("chains")
@@ -82,11 +125,18 @@ impl FromRawConf<RawAgentConf, Option<&HashSet<&str>>> for Settings {
         }
         .unwrap_or_default();
 
+        let proxy = p
+            .chain(&mut err)
+            .get_opt_key("proxy")
+            .and_then(parse_proxy)
+            .end();
+
         let default_signer = p
             .chain(&mut err)
             .get_opt_key("defaultSigner")
             .and_then(parse_signer)
-            .end();
+            .end()
+            .map(|signer| signer.with_proxy(proxy.as_ref()));
 
         let default_rpc_consensus_type = p
             .chain(&mut err)
@@ -94,10 +144,16 @@ impl FromRawConf<RawAgentConf, Option<&HashSet<&str>>> for Settings {
             .parse_string()
             .unwrap_or("fallback");
 
+        let notifications = p
+            .chain(&mut err)
+            .get_opt_key("notifications")
+            .and_then(parse_notifications)
+            .unwrap_or_default();
+
         let chains: HashMap<String, ChainConf> = raw_chains
             .into_iter()
             .filter_map(|(name, chain)| {
-                parse_chain(chain, &name, default_rpc_consensus_type)
+                parse_chain(chain, &name, default_rpc_consensus_type, proxy.as_ref())
                     .take_config_err(&mut err)
                     .map(|v| (name, v))
             })
@@ -112,7 +168,14 @@ impl FromRawConf<RawAgentConf, Option<&HashSet<&str>>> for Settings {
         err.into_result(Self {
             chains,
             metrics_port,
-            tracing: TracingConfig { fmt, level },
+            tracing: TracingConfig {
+                fmt,
+                level,
+                runtime_instrumentation,
+                rpc_usage_summary_interval,
+            },
+            notifications,
+            proxy,
         })
     }
 }
@@ -122,15 +185,19 @@ fn parse_chain(
     chain: ValueParser,
     name: &str,
     default_rpc_consensus_type: &str,
+    default_proxy: Option<&ProxyConf>,
 ) -> ConfigResult<ChainConf> {
     let mut err = ConfigParsingError::default();
 
     let domain = parse_domain(chain.clone(), name).take_config_err(&mut err);
+    // Signers connect to the services backing them through the agent's
+    // proxy, rather than the one of the chain
     let signer = chain
         .chain(&mut err)
         .get_opt_key("signer")
         .and_then(parse_signer)
-        .end();
+        .end()
+        .map(|signer| signer.with_proxy(default_proxy));
 
     let reorg_period = chain
         .chain(&mut err)
@@ -153,6 +220,13 @@ fn parse_chain(
         .get_opt_key("chunk")
         .parse_u32()
         .unwrap_or(1999);
+    let concurrency = chain
+        .chain(&mut err)
+        .get_opt_key("index")
+        .get_opt_key("concurrency")
+        .parse_u32()
+        .unwrap_or(1)
+        .max(1) as usize;
     let mode = chain
         .chain(&mut err)
         .get_opt_key("index")
@@ -164,11 +238,20 @@ fn parse_chain(
                 .and_then(|d| match d.domain_protocol() {
                     HyperlaneDomainProtocol::Ethereum => Some(IndexMode::Block),
                     HyperlaneDomainProtocol::Sealevel => Some(IndexMode::Sequence),
+                    HyperlaneDomainProtocol::Aptos => Some(IndexMode::Sequence),
+                    HyperlaneDomainProtocol::Ton => Some(IndexMode::Sequence),
                     _ => None,
                 })
                 .unwrap_or_default()
         });
 
+    let backfill = chain
+        .chain(&mut err)
+        .get_opt_key("index")
+        .get_opt_key("backfill")
+        .and_then(|backfill| parse_backfill(backfill, chunk_size))
+        .end();
+
     let mailbox = chain
         .chain(&mut err)
         .get_key("mailbox")
@@ -202,6 +285,80 @@ fn parse_chain(
         .parse_u32()
         .unwrap_or(1);
 
+    let data_cost_model = match chain
+        .chain(&mut err)
+        .get_opt_key("batchCostModel")
+        .parse_string()
+        .end()
+    {
+        None => None,
+        Some("calldata") => Some(DataCostModel::Calldata),
+        Some("l1Data") => Some(DataCostModel::L1Data),
+        Some(model) => Err(eyre!("unknown batch cost model `{model}`"))
+            .take_err(&mut err, || &chain.cwp + "batch_cost_model"),
+    };
+
+    // Pinned code hashes are keyed like the contract addresses they check
+    let expected_code_hashes = chain
+        .chain(&mut err)
+        .get_opt_key("codeHashes")
+        .into_obj_iter()
+        .map(|itr| {
+            itr.filter_map(|(contract, code_hash)| {
+                let code_hash = code_hash.chain(&mut err).parse_address_hash().end()?;
+                let address = chain
+                    .chain(&mut err)
+                    .get_key(&contract)
+                    .parse_address_hash()
+                    .end()?;
+                Some(ExpectedCodeHash {
+                    contract,
+                    address,
+                    code_hash,
+                })
+            })
+            .collect()
+        })
+        .unwrap_or_default();
+    let enforce_code_hashes = match chain
+        .chain(&mut err)
+        .get_opt_key("codeHashCheck")
+        .parse_string()
+        .unwrap_or("enforce")
+    {
+        "enforce" => true,
+        "warn" => false,
+        check => Err(eyre!("unknown code hash check `{check}`"))
+            .take_err(&mut err, || &chain.cwp + "code_hash_check")
+            .unwrap_or(true),
+    };
+
+    // Both the old and the new version are listed while the mailbox is
+    // upgraded
+    let message_versions = chain
+        .chain(&mut err)
+        .get_opt_key("messageVersions")
+        .into_array_iter()
+        .map(|versions| {
+            versions
+                .filter_map(|version| {
+                    let number = version.chain(&mut err).parse_u32().end()?;
+                    u8::try_from(number)
+                        .ok()
+                        .and_then(|number| MessageVersion::try_from(number).ok())
+                        .ok_or_else(|| eyre!("unsupported message version {number}"))
+                        .take_err(&mut err, || version.cwp.clone())
+                })
+                .collect::<Vec<_>>()
+        });
+
+    let proxy = chain
+        .chain(&mut err)
+        .get_opt_key("proxy")
+        .and_then(parse_proxy)
+        .end()
+        .or_else(|| default_proxy.cloned());
+
     cfg_unwrap_all!(&chain.cwp, err: [domain]);
     let connection = build_connection_conf(
         domain.domain_protocol(),
@@ -212,11 +369,21 @@ fn parse_chain(
         OperationBatchConfig {
             batch_contract_address,
             max_batch_size,
+            data_cost_model,
         },
+        proxy,
     );
 
     cfg_unwrap_all!(&chain.cwp, err: [connection, mailbox, interchain_gas_paymaster, validator_announce, merkle_tree_hook]);
-    err.into_result(ChainConf {
+    let legacy_mailbox =
+        matches!(&connection, ChainConnectionConf::Ethereum(conf) if conf.legacy_mailbox);
+    let message_versions = match message_versions {
+        Some(versions) => MessageVersions::new(versions),
+        // Legacy mailboxes dispatch legacy messages
+        None if legacy_mailbox => MessageVersions::new(vec![MessageVersion::V2]),
+        None => MessageVersions::default(),
+    };
+    let conf = ChainConf {
         domain,
         signer,
         reorg_period,
@@ -232,8 +399,17 @@ fn parse_chain(
             from,
             chunk_size,
             mode,
+            concurrency,
+            backfill,
         },
-    })
+        code_hashes: CodeHashesConf {
+            expected: expected_code_hashes,
+            enforce: enforce_code_hashes,
+        },
+        message_versions,
+    };
+    validate_chain_conf(&conf, &chain.cwp, &mut err);
+    err.into_result(conf)
 }
 
 /// Expects ChainMetadata
@@ -284,6 +460,49 @@ fn parse_domain(chain: ValueParser, name: &str) -> ConfigResult<HyperlaneDomain>
     err.into_result(domain)
 }
 
+/// Expects DomainMetadata
+fn parse_domain_metadata(domain: ValueParser) -> ConfigResult<DomainMetadata> {
+    let mut err = ConfigParsingError::default();
+
+    let id = domain.chain(&mut err).get_key("domainId").parse_u32().end();
+
+    let name = domain
+        .chain(&mut err)
+        .get_key("name")
+        .parse_string()
+        .end()
+        .map(str::to_ascii_lowercase);
+
+    let domain_type = domain
+        .chain(&mut err)
+        .get_opt_key("domainType")
+        .parse_from_str::<HyperlaneDomainType>("Invalid Hyperlane domain type")
+        .end()
+        .or(Some(HyperlaneDomainType::Unknown));
+
+    let protocol = domain
+        .chain(&mut err)
+        .get_key("protocol")
+        .parse_from_str::<HyperlaneDomainProtocol>("Invalid Hyperlane domain protocol")
+        .end();
+
+    let technical_stack = domain
+        .chain(&mut err)
+        .get_opt_key("technicalStack")
+        .parse_from_str::<HyperlaneDomainTechnicalStack>("Invalid chain technical stack")
+        .end()
+        .or_else(|| Some(HyperlaneDomainTechnicalStack::default()));
+
+    cfg_unwrap_all!(&domain.cwp, err: [id, name, domain_type, protocol, technical_stack]);
+    err.into_result(DomainMetadata {
+        id,
+        name,
+        domain_type,
+        protocol,
+        technical_stack,
+    })
+}
+
 /// Expects AgentSigner.
 fn parse_signer(signer: ValueParser) -> ConfigResult<SignerConf> {
     let mut err = ConfigParsingError::default();
@@ -319,7 +538,11 @@ fn parse_signer(signer: ValueParser) -> ConfigResult<SignerConf> {
                 .get_key("region")
                 .parse_from_str("Expected AWS region")
                 .unwrap_or_default();
-            err.into_result(SignerConf::Aws { id, region })
+            err.into_result(SignerConf::Aws {
+                id,
+                region,
+                proxy: None,
+            })
         }};
         (cosmosKey) => {{
             let key = signer
@@ -338,18 +561,39 @@ fn parse_signer(signer: ValueParser) -> ConfigResult<SignerConf> {
                 .parse_from_str("Expected Account Address Type")
                 .end()
                 .unwrap_or_default();
+            let public_key_type_url = signer
+                .chain(&mut err)
+                .get_opt_key("publicKeyTypeUrl")
+                .parse_string()
+                .end()
+                .map(str::to_owned);
             err.into_result(SignerConf::CosmosKey {
                 key,
                 prefix: prefix.to_string(),
                 account_address_type,
+                public_key_type_url,
             })
         }};
+        (starknetKey) => {{
+            let key = signer
+                .chain(&mut err)
+                .get_key("key")
+                .parse_private_key()
+                .unwrap_or_default();
+            let address = signer
+                .chain(&mut err)
+                .get_key("address")
+                .parse_address_hash()
+                .unwrap_or_default();
+            err.into_result(SignerConf::StarknetKey { key, address })
+        }};
     }
 
     match signer_type {
         Some("hexKey") => parse_signer!(hexKey),
         Some("aws") => parse_signer!(aws),
         Some("cosmosKey") => parse_signer!(cosmosKey),
+        Some("starknetKey") => parse_signer!(starknetKey),
         Some(t) => {
             Err(eyre!("Unknown signer type `{t}`")).into_config_result(|| &signer.cwp + "type")
         }
@@ -359,6 +603,120 @@ fn parse_signer(signer: ValueParser) -> ConfigResult<SignerConf> {
     }
 }
 
+/// Expects AgentIndexBackfill.
+fn parse_backfill(backfill: ValueParser, default_chunk_size: u32) -> ConfigResult<BackfillConf> {
+    let mut err = ConfigParsingError::default();
+
+    let from = backfill.chain(&mut err).get_key("from").parse_u32().end();
+    let to = backfill.chain(&mut err).get_key("to").parse_u32().end();
+    let chunk_size = backfill
+        .chain(&mut err)
+        .get_opt_key("chunk")
+        .parse_u32()
+        .unwrap_or(default_chunk_size);
+    let concurrency = backfill
+        .chain(&mut err)
+        .get_opt_key("concurrency")
+        .parse_u32()
+        .unwrap_or(DEFAULT_BACKFILL_CONCURRENCY);
+
+    cfg_unwrap_all!(&backfill.cwp, err: [from, to]);
+    if from > to {
+        err.push(
+            &backfill.cwp + "from",
+            eyre!("Backfill must start at or before the block it ends at"),
+        );
+    }
+    err.into_result(BackfillConf {
+        from,
+        to,
+        chunk_size: chunk_size.max(1),
+        concurrency: concurrency.max(1) as usize,
+    })
+}
+
+/// Expects the url of a proxy.
+fn parse_proxy(proxy: ValueParser) -> ConfigResult<ProxyConf> {
+    let url: Url = proxy.parse_from_str("Invalid proxy url")?;
+    ProxyConf::new(url).into_config_result(|| proxy.cwp.clone())
+}
+
+/// Expects NotificationsConfig.
+fn parse_notifications(notifications: ValueParser) -> ConfigResult<NotificationsConf> {
+    let mut err = ConfigParsingError::default();
+
+    let sinks = notifications
+        .chain(&mut err)
+        .get_opt_key("sinks")
+        .into_array_iter()
+        .map(|sinks| {
+            sinks
+                .filter_map(|sink| parse_notification_sink(sink).take_config_err(&mut err))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let indexing_stall_threshold = notifications
+        .chain(&mut err)
+        .get_opt_key("indexingStallThreshold")
+        .parse_u64()
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_INDEXING_STALL_THRESHOLD);
+
+    err.into_result(NotificationsConf {
+        sinks,
+        indexing_stall_threshold,
+    })
+}
+
+/// Expects NotificationSink.
+fn parse_notification_sink(sink: ValueParser) -> ConfigResult<NotificationSinkConf> {
+    let mut err = ConfigParsingError::default();
+
+    let sink_type = sink.chain(&mut err).get_key("type").parse_string().end();
+
+    let template = sink
+        .chain(&mut err)
+        .get_opt_key("template")
+        .parse_string()
+        .end()
+        .map(str::to_owned);
+
+    let url = |err: &mut ConfigParsingError, default: Option<&str>| -> Option<Url> {
+        sink.chain(err)
+            .get_opt_key("url")
+            .parse_from_str("Invalid notification sink url")
+            .end()
+            .or_else(|| default.and_then(|url| url.parse().ok()))
+            .ok_or_else(|| eyre!("Missing notification sink url"))
+            .into_config_result(|| &sink.cwp + "url")
+            .take_config_err(err)
+    };
+
+    let kind = match sink_type {
+        Some("webhook") => url(&mut err, None).map(|url| NotificationSinkKind::Webhook { url }),
+        Some("slack") => url(&mut err, None).map(|url| NotificationSinkKind::Slack { url }),
+        Some("pagerDuty") => {
+            let routing_key = sink
+                .chain(&mut err)
+                .get_key("routingKey")
+                .parse_string()
+                .end()
+                .map(str::to_owned);
+            url(&mut err, Some(DEFAULT_PAGERDUTY_URL))
+                .zip(routing_key)
+                .map(|(url, routing_key)| NotificationSinkKind::PagerDuty { url, routing_key })
+        }
+        Some(t) => Err(eyre!("Unknown notification sink type `{t}`"))
+            .into_config_result(|| &sink.cwp + "type")
+            .take_config_err(&mut err),
+        None => None,
+    };
+
+    cfg_unwrap_all!(&sink.cwp, err: [kind]);
+    err.into_result(NotificationSinkConf { kind, template })
+}
+
 /// Parser for agent signers.
 #[derive(Debug, Deserialize)]
 #[serde(transparent)]
@@ -0,0 +1,221 @@
+//! Checks of a parsed chain configuration which would otherwise only fail
+//! when its provider or signer is built, with a less helpful error.
+
+use eyre::{eyre, Result};
+use url::Url;
+
+use hyperlane_core::{
+    config::{ConfigErrResultExt, ConfigParsingError, ConfigPath},
+    HyperlaneDomainProtocol, H256,
+};
+
+use crate::settings::{ChainConf, ChainConnectionConf, SignerConf};
+
+const HTTP_SCHEMES: &[&str] = &["http", "https"];
+const WS_SCHEMES: &[&str] = &["ws", "wss"];
+
+/// Number of bytes of an EVM address
+const EVM_ADDRESS_BYTES: usize = 20;
+
+/// Validate the urls, contract addresses and signer of a chain, pushing every
+/// problem found to `err` with the path of the field at fault.
+pub(super) fn validate_chain_conf(
+    chain: &ChainConf,
+    cwp: &ConfigPath,
+    err: &mut ConfigParsingError,
+) {
+    let protocol = chain.connection.protocol();
+    validate_connection(&chain.connection, cwp, err);
+
+    let address_bytes = address_bytes(&chain.connection);
+    let addresses = [
+        ("mailbox", Some(chain.addresses.mailbox)),
+        (
+            "interchain_gas_paymaster",
+            Some(chain.addresses.interchain_gas_paymaster),
+        ),
+        (
+            "validator_announce",
+            Some(chain.addresses.validator_announce),
+        ),
+        ("merkle_tree_hook", Some(chain.addresses.merkle_tree_hook)),
+        (
+            "batch_contract_address",
+            chain
+                .connection
+                .operation_batch_config()
+                .and_then(|batch| batch.batch_contract_address),
+        ),
+    ];
+    for (key, address) in addresses {
+        if let Some(address) = address {
+            check_address(&address, address_bytes).take_err(err, || cwp + key);
+        }
+    }
+    if chain.addresses.mailbox.is_zero() {
+        err.push(cwp + "mailbox", eyre!("mailbox address must not be zero"));
+    }
+
+    if let Some(signer) = &chain.signer {
+        check_signer(protocol, signer).take_err(err, || cwp + "signer");
+        if let (ChainConnectionConf::Cosmos(conf), SignerConf::CosmosKey { prefix, .. }) =
+            (&chain.connection, signer)
+        {
+            if *prefix != conf.get_bech32_prefix() {
+                err.push(
+                    cwp + "signer" + "prefix",
+                    eyre!(
+                        "signer prefix `{prefix}` doesn't match the chain's bech32 prefix `{}`",
+                        conf.get_bech32_prefix()
+                    ),
+                );
+            }
+        }
+    }
+}
+
+fn validate_connection(
+    connection: &ChainConnectionConf,
+    cwp: &ConfigPath,
+    err: &mut ConfigParsingError,
+) {
+    let rpc_urls = || cwp + "rpc_urls";
+    match connection {
+        ChainConnectionConf::Ethereum(conf) => {
+            use hyperlane_ethereum::RpcConnectionConf::*;
+            match &conf.rpc_connection {
+                HttpQuorum { urls } | HttpFallback { urls } => {
+                    for url in urls {
+                        check_url(url, HTTP_SCHEMES).take_err(err, rpc_urls);
+                    }
+                }
+                Http { url } => {
+                    check_url(url, HTTP_SCHEMES).take_err(err, rpc_urls);
+                }
+                Ws { url } => {
+                    check_url(url, WS_SCHEMES).take_err(err, rpc_urls);
+                }
+                WsHttpHybrid { ws_url, http_urls } => {
+                    check_url(ws_url, WS_SCHEMES).take_err(err, rpc_urls);
+                    for url in http_urls {
+                        check_url(url, HTTP_SCHEMES).take_err(err, rpc_urls);
+                    }
+                }
+            }
+        }
+        ChainConnectionConf::Fuel(conf) => {
+            check_url(&conf.url, HTTP_SCHEMES).take_err(err, rpc_urls);
+        }
+        ChainConnectionConf::Sealevel(conf) => {
+            check_url(&conf.url, HTTP_SCHEMES).take_err(err, rpc_urls);
+        }
+        ChainConnectionConf::Cosmos(conf) => {
+            for url in conf.get_grpc_urls() {
+                check_url(&url, HTTP_SCHEMES).take_err(err, || cwp + "grpc_urls");
+            }
+            Url::parse(&conf.get_rpc_url())
+                .map_err(|e| eyre!("invalid url `{}`: {e}", conf.get_rpc_url()))
+                .and_then(|url| check_url(&url, HTTP_SCHEMES))
+                .take_err(err, rpc_urls);
+        }
+        // Backends accept urls of their own schemes
+        ChainConnectionConf::Backend(_) => {}
+    }
+}
+
+/// Number of bytes of the chain's addresses, which are left padded with
+/// zeros in a `H256`
+fn address_bytes(connection: &ChainConnectionConf) -> usize {
+    match connection {
+        ChainConnectionConf::Ethereum(_) => EVM_ADDRESS_BYTES,
+        ChainConnectionConf::Cosmos(conf) => conf.get_contract_address_bytes(),
+        _ => H256::len_bytes(),
+    }
+}
+
+fn check_url(url: &Url, schemes: &[&str]) -> Result<()> {
+    if !schemes.contains(&url.scheme()) {
+        return Err(eyre!(
+            "url `{url}` has scheme `{}`, expected one of {schemes:?}",
+            url.scheme()
+        ));
+    }
+    if url.host().is_none() {
+        return Err(eyre!("url `{url}` has no host"));
+    }
+    Ok(())
+}
+
+fn check_address(address: &H256, bytes: usize) -> Result<()> {
+    let padding = H256::len_bytes().saturating_sub(bytes);
+    if address.as_bytes()[..padding].iter().any(|b| *b != 0) {
+        return Err(eyre!(
+            "address {address:?} is longer than the {bytes} bytes of the chain's addresses"
+        ));
+    }
+    Ok(())
+}
+
+fn check_signer(protocol: HyperlaneDomainProtocol, signer: &SignerConf) -> Result<()> {
+    use HyperlaneDomainProtocol::*;
+    let supported = match signer {
+        // Signing is left to the node, or there is no signer at all
+        SignerConf::Node => true,
+        SignerConf::HexKey { .. } => !matches!(protocol, Cosmos | Starknet),
+        SignerConf::Aws { .. } => protocol == Ethereum,
+        SignerConf::CosmosKey { .. } => protocol == Cosmos,
+        SignerConf::StarknetKey { .. } => protocol == Starknet,
+    };
+    if !supported {
+        return Err(eyre!(
+            "{} signer is not supported by {protocol:?} chains",
+            signer_type(signer)
+        ));
+    }
+    Ok(())
+}
+
+/// The `type` the signer is configured with
+fn signer_type(signer: &SignerConf) -> &'static str {
+    match signer {
+        SignerConf::HexKey { .. } => "hexKey",
+        SignerConf::Aws { .. } => "aws",
+        SignerConf::CosmosKey { .. } => "cosmosKey",
+        SignerConf::StarknetKey { .. } => "starknetKey",
+        SignerConf::Node => "node",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check_url() {
+        let url = |s: &str| Url::parse(s).unwrap();
+        assert!(check_url(&url("https://rpc.example.com"), HTTP_SCHEMES).is_ok());
+        assert!(check_url(&url("wss://rpc.example.com"), WS_SCHEMES).is_ok());
+        assert!(check_url(&url("wss://rpc.example.com"), HTTP_SCHEMES).is_err());
+        assert!(check_url(&url("file:///tmp/rpc"), HTTP_SCHEMES).is_err());
+    }
+
+    #[test]
+    fn test_check_address() {
+        let evm = H256::from(hyperlane_core::H160::repeat_byte(0xff));
+        assert!(check_address(&evm, EVM_ADDRESS_BYTES).is_ok());
+        assert!(check_address(&evm, H256::len_bytes()).is_ok());
+        assert!(check_address(&H256::repeat_byte(0xff), EVM_ADDRESS_BYTES).is_err());
+        assert!(check_address(&H256::repeat_byte(0xff), H256::len_bytes()).is_ok());
+    }
+
+    #[test]
+    fn test_check_signer() {
+        let hex_key = SignerConf::HexKey {
+            key: H256::repeat_byte(1),
+        };
+        assert!(check_signer(HyperlaneDomainProtocol::Ethereum, &hex_key).is_ok());
+        assert!(check_signer(HyperlaneDomainProtocol::Sealevel, &hex_key).is_ok());
+        assert!(check_signer(HyperlaneDomainProtocol::Cosmos, &hex_key).is_err());
+        assert!(check_signer(HyperlaneDomainProtocol::Cosmos, &SignerConf::Node).is_ok());
+    }
+}
@@ -3,7 +3,7 @@ use ed25519_dalek::SecretKey;
 use ethers::prelude::{AwsSigner, LocalWallet};
 use ethers::utils::hex::ToHex;
 use eyre::{bail, Context, Report};
-use hyperlane_core::{AccountAddressType, H256};
+use hyperlane_core::{config::ProxyConf, AccountAddressType, H256};
 use hyperlane_sealevel::Keypair;
 use rusoto_core::Region;
 use rusoto_kms::KmsClient;
@@ -27,6 +27,8 @@ pub enum SignerConf {
         id: String,
         /// The AWS region
         region: Region,
+        /// The proxy requests to AWS KMS are routed through, if any
+        proxy: Option<ProxyConf>,
     },
     /// Cosmos Specific key
     CosmosKey {
@@ -36,6 +38,16 @@ pub enum SignerConf {
         prefix: String,
         /// Account address type for cosmos address
         account_address_type: AccountAddressType,
+        /// Type URL of the public key of Ethereum style accounts, whose keys
+        /// are ethsecp256k1 keys
+        public_key_type_url: Option<String>,
+    },
+    /// Starknet specific key
+    StarknetKey {
+        /// Private key value
+        key: H256,
+        /// Address of the account contract the key controls
+        address: H256,
     },
     /// Assume node will sign on RPC calls
     #[default]
@@ -43,6 +55,19 @@ pub enum SignerConf {
 }
 
 impl SignerConf {
+    /// Route the requests of signers backed by a remote service through
+    /// `proxy`, if any
+    pub fn with_proxy(self, proxy: Option<&ProxyConf>) -> Self {
+        match self {
+            SignerConf::Aws { id, region, .. } => SignerConf::Aws {
+                id,
+                region,
+                proxy: proxy.cloned(),
+            },
+            signer => signer,
+        }
+    }
+
     /// Try to convert the ethereum signer to a local wallet
     #[instrument(err)]
     pub async fn build<S: BuildableWithSignerConf>(&self) -> Result<S, Report> {
@@ -73,11 +98,11 @@ impl BuildableWithSignerConf for hyperlane_ethereum::Signers {
                         .context("Invalid ethereum signer key")?,
                 ),
             )),
-            SignerConf::Aws { id, region } => {
+            SignerConf::Aws { id, region, proxy } => {
                 let client = KmsClient::new_with_client(
                     rusoto_core::Client::new_with(
                         AwsChainCredentialsProvider::new(),
-                        utils::http_client_with_timeout().unwrap(),
+                        utils::http_client_with_timeout(proxy.as_ref()).unwrap(),
                     ),
                     region.clone(),
                 );
@@ -88,6 +113,9 @@ impl BuildableWithSignerConf for hyperlane_ethereum::Signers {
             SignerConf::CosmosKey { .. } => {
                 bail!("cosmosKey signer is not supported by Ethereum")
             }
+            SignerConf::StarknetKey { .. } => {
+                bail!("starknetKey signer is not supported by Ethereum")
+            }
             SignerConf::Node => bail!("Node signer"),
         })
     }
@@ -149,12 +177,14 @@ impl BuildableWithSignerConf for hyperlane_cosmos::Signer {
             key,
             prefix,
             account_address_type,
+            public_key_type_url,
         } = conf
         {
             Ok(hyperlane_cosmos::Signer::new(
                 key.as_bytes().to_vec(),
                 prefix.clone(),
                 account_address_type,
+                public_key_type_url.clone(),
             )?)
         } else {
             bail!(format!("{conf:?} key is not supported by cosmos"));
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use eyre::Result;
 pub use span_metrics::TimeSpanLifetime;
 use tracing_subscriber::{
@@ -55,12 +57,23 @@ pub struct TracingConfig {
     pub(crate) fmt: Style,
     #[serde(default)]
     pub(crate) level: Level,
+    /// Whether to serve tokio-console and export tokio runtime and task
+    /// metrics. This has a runtime cost, so it is off by default.
+    #[serde(default)]
+    pub(crate) runtime_instrumentation: bool,
+    /// How often a summary of the RPC requests made is logged, if at all
+    #[serde(default)]
+    pub(crate) rpc_usage_summary_interval: Option<Duration>,
 }
 
 impl TracingConfig {
     /// Attempt to instantiate and register a tracing subscriber setup from
-    /// settings.
-    pub fn start_tracing(&self, metrics: &CoreMetrics) -> Result<console_subscriber::Server> {
+    /// settings. Returns the tokio-console server to run if runtime
+    /// instrumentation is enabled.
+    pub fn start_tracing(
+        &self,
+        metrics: &CoreMetrics,
+    ) -> Result<Option<console_subscriber::Server>> {
         let mut target_layer = Targets::new().with_default(self.level);
 
         if self.level < Level::DependencyTrace {
@@ -88,7 +101,12 @@ impl TracingConfig {
         let fmt_layer: LogOutputLayer<_> = self.fmt.into();
         let err_layer = tracing_error::ErrorLayer::default();
 
-        let (tokio_layer, tokio_server) = console_subscriber::ConsoleLayer::new();
+        let (tokio_layer, tokio_server) = if self.runtime_instrumentation {
+            let (layer, server) = console_subscriber::ConsoleLayer::new();
+            (Some(layer), Some(server))
+        } else {
+            (None, None)
+        };
         let subscriber = tracing_subscriber::Registry::default()
             .with(tokio_layer)
             .with(target_layer)
@@ -99,4 +117,14 @@ impl TracingConfig {
         subscriber.try_init()?;
         Ok(tokio_server)
     }
+
+    /// Whether tokio runtime and task instrumentation is enabled
+    pub fn runtime_instrumentation(&self) -> bool {
+        self.runtime_instrumentation
+    }
+
+    /// How often a summary of the RPC requests made is logged, if at all
+    pub fn rpc_usage_summary_interval(&self) -> Option<Duration> {
+        self.rpc_usage_summary_interval
+    }
 }
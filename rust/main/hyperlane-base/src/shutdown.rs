@@ -0,0 +1,94 @@
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+/// Requests the tasks of an agent to shut down
+#[derive(Debug)]
+pub struct ShutdownTrigger(watch::Sender<bool>);
+
+impl ShutdownTrigger {
+    /// Create a trigger, and the signal its tasks wait on
+    pub fn new() -> (Self, ShutdownSignal) {
+        let (tx, rx) = watch::channel(false);
+        (Self(tx), ShutdownSignal(Some(rx)))
+    }
+
+    /// Signal the tasks to shut down
+    pub fn trigger(&self) {
+        self.0.send_replace(true);
+    }
+}
+
+/// Whether the agent is shutting down. Tasks should stop taking new work
+/// once it is triggered, and finish the work they started.
+///
+/// The default signal is never triggered.
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownSignal(Option<watch::Receiver<bool>>);
+
+impl ShutdownSignal {
+    /// Whether shutting down was requested
+    pub fn is_triggered(&self) -> bool {
+        self.0.as_ref().map_or(false, |rx| *rx.borrow())
+    }
+
+    /// Wait until shutting down is requested
+    pub async fn triggered(&self) {
+        match self.0.clone() {
+            Some(mut rx) => {
+                // If the trigger was dropped without being triggered, the
+                // agent isn't going to shut down gracefully
+                if rx.wait_for(|triggered| *triggered).await.is_err() {
+                    std::future::pending::<()>().await;
+                }
+            }
+            None => std::future::pending().await,
+        }
+    }
+}
+
+/// Wait for the process to be asked to terminate, with SIGTERM or ctrl-c
+pub async fn shutdown_requested() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = sigterm.recv() => info!("Received SIGTERM"),
+                    _ = tokio::signal::ctrl_c() => info!("Received ctrl-c"),
+                }
+                return;
+            }
+            Err(err) => warn!(?err, "Failed to listen for SIGTERM"),
+        }
+    }
+    if let Err(err) = tokio::signal::ctrl_c().await {
+        warn!(?err, "Failed to listen for ctrl-c");
+        std::future::pending::<()>().await;
+    }
+    info!("Received ctrl-c");
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn signal_is_triggered_by_trigger() {
+        let (trigger, signal) = ShutdownTrigger::new();
+        assert!(!signal.is_triggered());
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), signal.triggered())
+                .await
+                .is_err()
+        );
+
+        trigger.trigger();
+        assert!(signal.is_triggered());
+        signal.triggered().await;
+        assert!(!ShutdownSignal::default().is_triggered());
+    }
+}
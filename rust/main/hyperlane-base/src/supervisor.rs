@@ -0,0 +1,125 @@
+use std::{future::Future, time::Duration};
+
+use hyperlane_core::HyperlaneDomain;
+use prometheus::IntCounterVec;
+use tokio::{
+    task::JoinHandle,
+    time::{sleep, Instant},
+};
+use tracing::{error, warn};
+
+use crate::{spawn_named, CoreMetrics};
+
+/// How long to wait before the first restart of a task
+const INITIAL_BACKOFF: Duration = if cfg!(test) {
+    Duration::from_millis(10)
+} else {
+    Duration::from_secs(1)
+};
+/// The longest to wait before restarting a task
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+/// How long a task has to run for its backoff to be reset
+const HEALTHY_RUN: Duration = Duration::from_secs(600);
+
+/// Restarts tasks that panic or exit, so that a failure is isolated to the
+/// task of a single chain instead of leaving it dead or bringing down the
+/// agent. Only for tasks that can be rebuilt from scratch, like indexing
+/// tasks resuming from their cursor in the db; tasks owning state that would
+/// be lost, like queues or the receivers of channels, can't be supervised.
+#[derive(Debug, Clone)]
+pub struct TaskSupervisor {
+    restarts: IntCounterVec,
+}
+
+impl TaskSupervisor {
+    /// Create a supervisor counting restarts in `metrics`
+    pub fn new(metrics: &CoreMetrics) -> Self {
+        Self {
+            restarts: metrics.task_restarts_count(),
+        }
+    }
+
+    /// Spawn the task built by `task`, and build and spawn it again whenever
+    /// it panics or exits, waiting exponentially longer between restarts
+    /// unless it ran for a while. The returned handle never completes.
+    pub fn spawn<F, Fut>(
+        &self,
+        role: &'static str,
+        chain: Option<&HyperlaneDomain>,
+        mut task: F,
+    ) -> JoinHandle<()>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let chain = chain.cloned();
+        let restarts = self.restarts.with_label_values(&[
+            role,
+            chain
+                .as_ref()
+                .map(|domain| domain.name())
+                .unwrap_or_default(),
+        ]);
+        let supervisor = async move {
+            let chain = chain.as_ref();
+            let mut backoff = INITIAL_BACKOFF;
+            loop {
+                let started = Instant::now();
+                match spawn_named(role, chain, task()).await {
+                    Ok(()) => warn!(role, ?chain, "Supervised task exited, restarting it"),
+                    Err(err) => error!(role, ?chain, ?err, "Supervised task failed, restarting it"),
+                }
+                if started.elapsed() >= HEALTHY_RUN {
+                    backoff = INITIAL_BACKOFF;
+                }
+                sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+                restarts.inc();
+            }
+        };
+        spawn_named("supervisor", None, supervisor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+
+    use prometheus::Registry;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn restarts_panicking_task() {
+        let metrics = CoreMetrics::new("test", 0, Registry::new()).unwrap();
+        let supervisor = TaskSupervisor::new(&metrics);
+        let domain = HyperlaneDomain::new_test_domain("test");
+        let runs = Arc::new(AtomicU32::new(0));
+
+        let task_runs = runs.clone();
+        let handle = supervisor.spawn("flaky", Some(&domain), move || {
+            let runs = task_runs.clone();
+            async move {
+                if runs.fetch_add(1, Ordering::SeqCst) < 2 {
+                    panic!("flaky task failed");
+                }
+                std::future::pending::<()>().await;
+            }
+        });
+
+        // Backoffs of 10ms and 20ms before the second and third runs
+        sleep(Duration::from_millis(200)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            metrics
+                .task_restarts_count()
+                .with_label_values(&["flaky", "test"])
+                .get(),
+            2
+        );
+        handle.abort();
+    }
+}
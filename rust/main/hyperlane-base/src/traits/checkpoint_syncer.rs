@@ -4,6 +4,8 @@ use async_trait::async_trait;
 use eyre::Result;
 
 use crate::AgentMetadata;
+#[cfg(feature = "legacy")]
+use hyperlane_core::SignedLegacyCheckpoint;
 use hyperlane_core::{ReorgEvent, SignedAnnouncement, SignedCheckpointWithMessageId};
 
 /// A generic trait to read/write Checkpoints offchain
@@ -23,6 +25,13 @@ pub trait CheckpointSyncer: Debug + Send + Sync {
     }
     /// Attempt to fetch the signed (checkpoint, messageId) tuple at this index
     async fn fetch_checkpoint(&self, index: u32) -> Result<Option<SignedCheckpointWithMessageId>>;
+    /// Attempt to fetch the signed legacy checkpoint at this index, which
+    /// validators of legacy (v2) deployments write next to the (checkpoint,
+    /// messageId) tuples
+    #[cfg(feature = "legacy")]
+    async fn fetch_legacy_checkpoint(&self, _index: u32) -> Result<Option<SignedLegacyCheckpoint>> {
+        Ok(None)
+    }
     /// Write the signed (checkpoint, messageId) tuple to this syncer
     async fn write_checkpoint(
         &self,
@@ -0,0 +1,225 @@
+use std::{
+    fmt::{Debug, Formatter},
+    sync::{Arc, Mutex, OnceLock, Weak},
+    time::Duration,
+};
+
+use eyre::{eyre, Result};
+use hyperlane_core::config::ProxyConf;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::debug;
+use url::Url;
+
+use crate::utils::reqwest_client;
+
+/// Path of the validator route serving its latest signed checkpoint index
+pub const LATEST_CHECKPOINT_PATH: &str = "checkpoints/latest";
+
+/// How long the validator has to answer a request which isn't long polling
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long the validator holds a long polling request at most, plus some
+/// leeway for it to answer
+const LONG_POLL_TIMEOUT: Duration = Duration::from_secs(35);
+
+/// How long to wait before polling again after a failed request
+const POLL_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Body of the validator's latest checkpoint endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatestCheckpointResponse {
+    /// Index of the latest checkpoint the validator signed and submitted to
+    /// its storage, if any since it started
+    pub index: Option<u32>,
+}
+
+/// Client of the HTTP endpoint a validator serves the index of its latest
+/// signed checkpoint on. It is updated as soon as the checkpoint is written
+/// to the validator's storage, so polling it learns about new checkpoints
+/// sooner and more cheaply than polling the storage.
+///
+/// Once used, the endpoint is long polled in the background with the last
+/// known index, so that the latest index is known as soon as the validator
+/// signs a checkpoint, until every clone of the client is dropped.
+#[derive(Clone)]
+pub struct LatestCheckpointEndpoint {
+    url: Url,
+    client: Client,
+    poll: Arc<PollState>,
+}
+
+#[derive(Default)]
+struct PollState {
+    /// The latest index learned by long polling, cleared when polling fails
+    latest_index: Mutex<Option<u32>>,
+    poller: OnceLock<JoinHandle<()>>,
+}
+
+impl Drop for PollState {
+    fn drop(&mut self) {
+        if let Some(poller) = self.poller.get() {
+            poller.abort();
+        }
+    }
+}
+
+impl Debug for LatestCheckpointEndpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LatestCheckpointEndpoint {{ url: {} }}", self.url)
+    }
+}
+
+impl LatestCheckpointEndpoint {
+    /// Parses an announced storage location as the base url of a validator's
+    /// endpoint, requested through `proxy` if any. Returns None if the location
+    /// isn't an http(s) url.
+    pub fn from_location(location: &str, proxy: Option<&ProxyConf>) -> Option<Self> {
+        let mut url = Url::parse(location).ok()?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return None;
+        }
+        // Without a trailing slash, joining would replace the last segment
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        Some(Self {
+            url: url.join(LATEST_CHECKPOINT_PATH).ok()?,
+            client: reqwest_client(proxy),
+            poll: Default::default(),
+        })
+    }
+
+    /// The url the latest checkpoint index is requested from
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Index of the latest checkpoint the validator signed, if any. It is
+    /// requested from the validator until long polling learned it.
+    pub async fn latest_index(&self) -> Result<Option<u32>> {
+        self.poll.poller.get_or_init(|| {
+            tokio::spawn(Self::long_poll(
+                self.url.clone(),
+                self.client.clone(),
+                Arc::downgrade(&self.poll),
+            ))
+        });
+        if let Some(index) = *self.poll.latest_index.lock().unwrap() {
+            return Ok(Some(index));
+        }
+        fetch_latest_index(&self.client, &self.url, None).await
+    }
+
+    /// Long polls the endpoint with the last known index, for as long as a
+    /// client of the endpoint is alive
+    async fn long_poll(url: Url, client: Client, poll: Weak<PollState>) {
+        loop {
+            let Some(after) = poll
+                .upgrade()
+                .map(|poll| *poll.latest_index.lock().unwrap())
+            else {
+                return;
+            };
+            let result = fetch_latest_index(&client, &url, after).await;
+            let Some(state) = poll.upgrade() else {
+                return;
+            };
+            match result {
+                Ok(Some(index)) => *state.latest_index.lock().unwrap() = Some(index),
+                // Without a checkpoint to wait for a newer one of, the
+                // validator answers right away
+                Ok(None) => {
+                    drop(state);
+                    tokio::time::sleep(POLL_RETRY_DELAY).await;
+                }
+                Err(err) => {
+                    debug!(%url, ?err, "Failed to long poll validator endpoint");
+                    // Fall back to requesting the index until polling works again
+                    *state.latest_index.lock().unwrap() = None;
+                    drop(state);
+                    tokio::time::sleep(POLL_RETRY_DELAY).await;
+                }
+            }
+        }
+    }
+}
+
+/// Requests the latest index from the endpoint at `url`. With `after`, the
+/// validator holds the request until it signs a checkpoint newer than it.
+async fn fetch_latest_index(client: &Client, url: &Url, after: Option<u32>) -> Result<Option<u32>> {
+    let mut request = client.get(url.clone());
+    request = match after {
+        Some(after) => request
+            .query(&[("after", after)])
+            .timeout(LONG_POLL_TIMEOUT),
+        None => request.timeout(REQUEST_TIMEOUT),
+    };
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(eyre!(
+            "Latest checkpoint endpoint {} answered with status {}",
+            url,
+            response.status()
+        ));
+    }
+    Ok(response.json::<LatestCheckpointResponse>().await?.index)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_from_location() {
+        let endpoint =
+            LatestCheckpointEndpoint::from_location("https://validator.example.com", None).unwrap();
+        assert_eq!(
+            endpoint.url().as_str(),
+            "https://validator.example.com/checkpoints/latest"
+        );
+
+        let endpoint =
+            LatestCheckpointEndpoint::from_location("http://10.0.0.1:9090/hyperlane", None)
+                .unwrap();
+        assert_eq!(
+            endpoint.url().as_str(),
+            "http://10.0.0.1:9090/hyperlane/checkpoints/latest"
+        );
+
+        assert!(LatestCheckpointEndpoint::from_location("s3://bucket/us-east-1", None).is_none());
+        assert!(LatestCheckpointEndpoint::from_location("file:///tmp/checkpoints", None).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_long_polls_with_last_known_index() {
+        use std::collections::HashMap;
+
+        use axum::{extract::Query, routing::get, Json, Router};
+
+        // Answers long polls with the next index, and other requests with 1
+        let app = Router::new().route(
+            "/checkpoints/latest",
+            get(|Query(query): Query<HashMap<String, u32>>| async move {
+                let index = query.get("after").map_or(1, |after| after + 1);
+                if index > 3 {
+                    // Hold the long poll, as the validator does
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+                }
+                Json(LatestCheckpointResponse { index: Some(index) })
+            }),
+        );
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        let endpoint =
+            LatestCheckpointEndpoint::from_location(&format!("http://{addr}"), None).unwrap();
+        assert_eq!(endpoint.latest_index().await.unwrap(), Some(1));
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(endpoint.latest_index().await.unwrap(), Some(3));
+    }
+}
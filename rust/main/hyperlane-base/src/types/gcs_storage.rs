@@ -2,6 +2,10 @@ use crate::{AgentMetadata, CheckpointSyncer};
 use async_trait::async_trait;
 use derive_new::new;
 use eyre::{bail, Result};
+use hyper_tls::HttpsConnector;
+#[cfg(feature = "legacy")]
+use hyperlane_core::SignedLegacyCheckpoint;
+use hyperlane_core::{config::ProxyConf, rpc_clients::ProxyConnector};
 use hyperlane_core::{ReorgEvent, SignedAnnouncement, SignedCheckpointWithMessageId};
 use std::fmt;
 use ya_gcp::{
@@ -69,6 +73,9 @@ pub const GCS_SERVICE_ACCOUNT_KEY: &str = "GCS_SERVICE_ACCOUNT_KEY";
 #[derive(Debug, new)]
 pub struct GcsStorageClientBuilder {
     auth: AuthFlow,
+    /// The proxy requests to the bucket are routed through, if any
+    #[new(default)]
+    proxy: Option<ProxyConf>,
 }
 
 /// Google Cloud Storage client
@@ -77,12 +84,18 @@ pub struct GcsStorageClientBuilder {
 pub struct GcsStorageClient {
     // GCS storage client
     // # Details: <https://docs.rs/ya-gcp/latest/ya_gcp/storage/struct.StorageClient.html>
-    inner: StorageClient,
+    inner: StorageClient<HttpsConnector<ProxyConnector>>,
     // bucket name of this client's storage
     bucket: String,
 }
 
 impl GcsStorageClientBuilder {
+    /// Route the requests of the client through `proxy`, if any
+    pub fn with_proxy(mut self, proxy: Option<ProxyConf>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
     /// Instantiates `ya_gcp:StorageClient` based on provided auth method
     /// # Param
     /// * `baucket_name` - String name of target bucket to work with, will be used by all store and get ops
@@ -91,9 +104,14 @@ impl GcsStorageClientBuilder {
         bucket_name: impl Into<String>,
         folder: Option<String>,
     ) -> Result<GcsStorageClient> {
-        let inner = ClientBuilder::new(ClientBuilderConfig::new().auth_flow(self.auth))
-            .await?
-            .build_storage_client();
+        // Authentication requests go through the connector too
+        let connector = HttpsConnector::new_with_connector(ProxyConnector::new(self.proxy));
+        let inner = ClientBuilder::with_connector(
+            ClientBuilderConfig::new().auth_flow(self.auth),
+            connector,
+        )
+        .await?
+        .build_storage_client();
         let bucket = if let Some(folder) = folder {
             format! {"{}/{}", bucket_name.into(), folder}
         } else {
@@ -109,6 +127,10 @@ impl GcsStorageClient {
     fn get_checkpoint_key(index: u32) -> String {
         format!("checkpoint_{index}_with_id.json")
     }
+    #[cfg(feature = "legacy")]
+    fn get_legacy_checkpoint_key(index: u32) -> String {
+        format!("checkpoint_{index}.json")
+    }
     // #test only method[s]
     #[cfg(test)]
     pub(crate) async fn get_by_path(&self, path: impl AsRef<str>) -> Result<()> {
@@ -178,6 +200,27 @@ impl CheckpointSyncer for GcsStorageClient {
         }
     }
 
+    /// Attempt to fetch the signed legacy checkpoint at this index
+    #[cfg(feature = "legacy")]
+    async fn fetch_legacy_checkpoint(&self, index: u32) -> Result<Option<SignedLegacyCheckpoint>> {
+        match self
+            .inner
+            .get_object(
+                &self.bucket,
+                GcsStorageClient::get_legacy_checkpoint_key(index),
+            )
+            .await
+        {
+            Ok(data) => Ok(Some(serde_json::from_slice(data.as_ref())?)),
+            Err(e) => match e {
+                ObjectError::Failure(Error::HttpStatus(HttpStatusError(StatusCode::NOT_FOUND))) => {
+                    Ok(None)
+                }
+                _ => bail!(e),
+            },
+        }
+    }
+
     /// Write the signed (checkpoint, messageId) tuple to this syncer
     async fn write_checkpoint(
         &self,
@@ -4,6 +4,8 @@ use crate::traits::CheckpointSyncer;
 use crate::AgentMetadata;
 use async_trait::async_trait;
 use eyre::{Context, Result};
+#[cfg(feature = "legacy")]
+use hyperlane_core::SignedLegacyCheckpoint;
 use hyperlane_core::{ReorgEvent, SignedAnnouncement, SignedCheckpointWithMessageId};
 use prometheus::IntGauge;
 
@@ -33,6 +35,11 @@ impl LocalStorage {
         self.path.join(format!("{}_with_id.json", index))
     }
 
+    #[cfg(feature = "legacy")]
+    fn legacy_checkpoint_file_path(&self, index: u32) -> PathBuf {
+        self.path.join(format!("{}.json", index))
+    }
+
     fn latest_index_file_path(&self) -> PathBuf {
         self.path.join("index.json")
     }
@@ -86,6 +93,15 @@ impl CheckpointSyncer for LocalStorage {
         Ok(Some(checkpoint))
     }
 
+    #[cfg(feature = "legacy")]
+    async fn fetch_legacy_checkpoint(&self, index: u32) -> Result<Option<SignedLegacyCheckpoint>> {
+        let Ok(data) = tokio::fs::read(self.legacy_checkpoint_file_path(index)).await else {
+            return Ok(None);
+        };
+        let checkpoint = serde_json::from_slice(&data)?;
+        Ok(Some(checkpoint))
+    }
+
     async fn write_checkpoint(
         &self,
         signed_checkpoint: &SignedCheckpointWithMessageId,
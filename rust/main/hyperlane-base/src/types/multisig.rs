@@ -1,15 +1,34 @@
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use derive_new::new;
-use eyre::Result;
+use eyre::{eyre, Result};
+use futures_util::stream::{FuturesUnordered, StreamExt};
+use tokio::time::{sleep, timeout};
 use tracing::{debug, instrument};
 
 use hyperlane_core::{
     HyperlaneDomain, MultisigSignedCheckpoint, SignedCheckpointWithMessageId, H160, H256,
 };
+#[cfg(feature = "legacy")]
+use hyperlane_core::{MultisigSignedLegacyCheckpoint, SignedLegacyCheckpoint};
 
-use crate::{CheckpointSyncer, CoreMetrics};
+use crate::{CheckpointSyncer, CoreMetrics, LatestCheckpointEndpoint};
+
+/// How long a validator's checkpoint storage has to answer a request before
+/// the validator is skipped
+const VALIDATOR_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long to wait for a validator's checkpoint storage to answer before
+/// sending the same request again, in case the first one landed on a slow
+/// replica
+const HEDGE_DELAY: Duration = if cfg!(test) {
+    Duration::from_millis(100)
+} else {
+    Duration::from_secs(2)
+};
 
 /// For a particular validator set, fetches signed checkpoints from multiple
 /// validators to create MultisigSignedCheckpoints.
@@ -19,9 +38,23 @@ pub struct MultisigCheckpointSyncer {
     checkpoint_syncers: HashMap<H160, Arc<dyn CheckpointSyncer>>,
     metrics: Arc<CoreMetrics>,
     app_context: Option<String>,
+    /// The endpoint serving the latest checkpoint index of the validators
+    /// that announced one
+    #[new(default)]
+    latest_checkpoint_endpoints: HashMap<H160, LatestCheckpointEndpoint>,
 }
 
 impl MultisigCheckpointSyncer {
+    /// Learn the latest checkpoint index of validators from their endpoints
+    /// rather than their checkpoint storage
+    pub fn with_latest_checkpoint_endpoints(
+        mut self,
+        endpoints: HashMap<H160, LatestCheckpointEndpoint>,
+    ) -> Self {
+        self.latest_checkpoint_endpoints = endpoints;
+        self
+    }
+
     /// Gets the latest checkpoint index from each validator's checkpoint syncer.
     /// Returns a vector of the latest indices, in an unspecified order, and does
     /// not contain indices for validators that did not provide a latest index.
@@ -35,29 +68,34 @@ impl MultisigCheckpointSyncer {
         // Get the latest_index from each validator's checkpoint syncer.
         // If a validator does not return a latest index, None is recorded so
         // this can be surfaced in the metrics.
-        let mut latest_indices: HashMap<H160, Option<u32>> =
-            HashMap::with_capacity(validators.len());
-
-        for validator in validators {
-            let address = H160::from(*validator);
-            if let Some(checkpoint_syncer) = self.checkpoint_syncers.get(&address) {
-                // Gracefully handle errors getting the latest_index
-                match checkpoint_syncer.latest_index().await {
-                    Ok(Some(index)) => {
-                        debug!(?address, ?index, "Validator returned latest index");
-                        latest_indices.insert(H160::from(*validator), Some(index));
-                    }
-                    result => {
-                        debug!(
-                            ?address,
-                            ?result,
-                            "Failed to get latest index from validator"
-                        );
-                        latest_indices.insert(H160::from(*validator), None);
+        // The validators are queried in parallel so that a slow one doesn't
+        // hold up the others.
+        let latest_indices: HashMap<H160, Option<u32>> = validators
+            .iter()
+            .filter_map(|validator| {
+                let address = H160::from(*validator);
+                let syncer = self.checkpoint_syncers.get(&address)?;
+                Some(async move {
+                    // Gracefully handle errors getting the latest_index
+                    match self.latest_index(address, syncer).await {
+                        Ok(Some(index)) => {
+                            debug!(?address, ?index, "Validator returned latest index");
+                            (address, Some(index))
+                        }
+                        result => {
+                            debug!(
+                                ?address,
+                                ?result,
+                                "Failed to get latest index from validator"
+                            );
+                            (address, None)
+                        }
                     }
-                }
-            }
-        }
+                })
+            })
+            .collect::<FuturesUnordered<_>>()
+            .collect()
+            .await;
 
         if let Some(app_context) = &self.app_context {
             self.metrics
@@ -138,6 +176,9 @@ impl MultisigCheckpointSyncer {
     /// Fetches a MultisigSignedCheckpointWithMessageId if there is a quorum.
     /// Validators must reflect the onchain ordering of the set
     /// Returns Ok(None) if there is no quorum.
+    ///
+    /// Checkpoints are fetched from all validators in parallel, and the
+    /// remaining fetches are abandoned as soon as a quorum is found.
     #[instrument(err, skip(self))]
     pub async fn fetch_checkpoint(
         &self,
@@ -145,77 +186,348 @@ impl MultisigCheckpointSyncer {
         threshold: usize,
         index: u32,
     ) -> Result<Option<MultisigSignedCheckpoint>> {
-        // Keeps track of signed validator checkpoints for a particular root.
+        // Keeps track of signed validator checkpoints for a particular root, with
+        // the position of their validator in the set.
         // In practice, it's likely that validators will all sign the same root for a
         // particular index, but we'd like to be robust to this not being the case
-        let mut signed_checkpoints_per_root: HashMap<H256, Vec<SignedCheckpointWithMessageId>> =
-            HashMap::new();
+        let mut signed_checkpoints_per_root: HashMap<
+            H256,
+            Vec<(usize, SignedCheckpointWithMessageId)>,
+        > = HashMap::new();
 
-        for validator in validators.iter() {
-            let addr = H160::from(*validator);
-            if let Some(checkpoint_syncer) = self.checkpoint_syncers.get(&addr) {
-                // Gracefully ignore an error fetching the checkpoint from a validator's
-                // checkpoint syncer, which can happen if the validator has not
-                // signed the checkpoint at `index`.
-                if let Ok(Some(signed_checkpoint)) = checkpoint_syncer.fetch_checkpoint(index).await
-                {
-                    // If the signed checkpoint is for a different index, ignore it
-                    if signed_checkpoint.value.index != index {
-                        debug!(
-                            validator = format!("{:#x}", validator),
-                            index = index,
-                            checkpoint_index = signed_checkpoint.value.index,
-                            "Checkpoint index mismatch"
-                        );
-                        continue;
-                    }
+        let mut fetches = validators
+            .iter()
+            .enumerate()
+            .filter_map(|(position, validator)| {
+                let addr = H160::from(*validator);
+                let Some(checkpoint_syncer) = self.checkpoint_syncers.get(&addr) else {
+                    debug!(%validator, "Unable to find checkpoint syncer");
+                    return None;
+                };
+                Some(async move {
+                    let result = self
+                        .timed_request(addr, "checkpoint", || {
+                            checkpoint_syncer.fetch_checkpoint(index)
+                        })
+                        .await;
+                    (position, validator, result)
+                })
+            })
+            .collect::<FuturesUnordered<_>>();
 
-                    // Ensure that the signature is actually by the validator
-                    let signer = signed_checkpoint.recover()?;
+        while let Some((position, validator, result)) = fetches.next().await {
+            // Gracefully ignore an error fetching the checkpoint from a validator's
+            // checkpoint syncer, which can happen if the validator has not
+            // signed the checkpoint at `index`.
+            let Ok(Some(signed_checkpoint)) = result else {
+                debug!(
+                    validator = format!("{:#x}", validator),
+                    index = index,
+                    "Unable to find signed checkpoint"
+                );
+                continue;
+            };
 
-                    if H256::from(signer) != *validator {
-                        debug!(
-                            validator = format!("{:#x}", validator),
-                            index = index,
-                            "Checkpoint signature mismatch"
-                        );
-                        continue;
-                    }
+            // If the signed checkpoint is for a different index, ignore it
+            if signed_checkpoint.value.index != index {
+                debug!(
+                    validator = format!("{:#x}", validator),
+                    index = index,
+                    checkpoint_index = signed_checkpoint.value.index,
+                    "Checkpoint index mismatch"
+                );
+                continue;
+            }
 
-                    // Push the signed checkpoint into the hashmap
-                    let root = signed_checkpoint.value.root;
-                    let signed_checkpoints = signed_checkpoints_per_root.entry(root).or_default();
-                    signed_checkpoints.push(signed_checkpoint);
-
-                    // Count the number of signatures for this signed checkpoint
-                    let signature_count = signed_checkpoints.len();
-                    debug!(
-                        validator = format!("{:#x}", validator),
-                        index = index,
-                        root = format!("{:#x}", root),
-                        signature_count = signature_count,
-                        "Found signed checkpoint"
-                    );
-
-                    // If we've hit a quorum, create a MultisigSignedCheckpoint
-                    if signature_count >= threshold {
-                        let checkpoint: MultisigSignedCheckpoint = signed_checkpoints.try_into()?;
-                        debug!(checkpoint=?checkpoint, "Fetched multisig checkpoint");
-                        return Ok(Some(checkpoint));
-                    }
-                } else {
-                    debug!(
-                        validator = format!("{:#x}", validator),
-                        index = index,
-                        "Unable to find signed checkpoint"
-                    );
-                }
-            } else {
-                debug!(%validator, "Unable to find checkpoint syncer");
+            // Ensure that the signature is actually by the validator
+            let signer = signed_checkpoint.recover()?;
+
+            if H256::from(signer) != *validator {
+                debug!(
+                    validator = format!("{:#x}", validator),
+                    index = index,
+                    "Checkpoint signature mismatch"
+                );
                 continue;
             }
+
+            // Push the signed checkpoint into the hashmap
+            let root = signed_checkpoint.value.root;
+            let signed_checkpoints = signed_checkpoints_per_root.entry(root).or_default();
+            signed_checkpoints.push((position, signed_checkpoint));
+
+            // Count the number of signatures for this signed checkpoint
+            let signature_count = signed_checkpoints.len();
+            debug!(
+                validator = format!("{:#x}", validator),
+                index = index,
+                root = format!("{:#x}", root),
+                signature_count = signature_count,
+                "Found signed checkpoint"
+            );
+
+            // If we've hit a quorum, create a MultisigSignedCheckpoint
+            if signature_count >= threshold {
+                let mut signed_checkpoints = in_validator_order(std::mem::take(signed_checkpoints));
+                let checkpoint: MultisigSignedCheckpoint = (&mut signed_checkpoints).try_into()?;
+                debug!(checkpoint=?checkpoint, "Fetched multisig checkpoint");
+                return Ok(Some(checkpoint));
+            }
         }
         debug!("No quorum checkpoint found for message");
         Ok(None)
     }
+
+    /// Like [`Self::fetch_checkpoint_in_range`], but for the legacy
+    /// checkpoints of legacy (v2) deployments, which don't commit to a
+    /// message id
+    #[cfg(feature = "legacy")]
+    #[instrument(err, skip(self))]
+    pub async fn fetch_legacy_checkpoint_in_range(
+        &self,
+        validators: &[H256],
+        threshold: usize,
+        minimum_index: u32,
+        maximum_index: u32,
+        origin: &HyperlaneDomain,
+        destination: &HyperlaneDomain,
+    ) -> Result<Option<MultisigSignedLegacyCheckpoint>> {
+        let mut latest_indices = self
+            .get_validator_latest_checkpoints_and_update_metrics(validators, origin, destination)
+            .await;
+        latest_indices.sort_by(|a, b| b.cmp(a));
+        let Some(&highest_quorum_index) = latest_indices.get(threshold - 1) else {
+            debug!(
+                ?latest_indices,
+                "Not enough validators returned a latest index"
+            );
+            return Ok(None);
+        };
+        let start_index = highest_quorum_index.min(maximum_index);
+        for index in (minimum_index..=start_index).rev() {
+            if let Ok(Some(checkpoint)) = self
+                .fetch_legacy_checkpoint(validators, threshold, index)
+                .await
+            {
+                return Ok(Some(checkpoint));
+            }
+        }
+        debug!("No legacy checkpoint found in range");
+        Ok(None)
+    }
+
+    /// Fetches a quorum of signed legacy checkpoints at `index`, if there is
+    /// one. Validators must reflect the onchain ordering of the set.
+    #[cfg(feature = "legacy")]
+    #[instrument(err, skip(self))]
+    pub async fn fetch_legacy_checkpoint(
+        &self,
+        validators: &[H256],
+        threshold: usize,
+        index: u32,
+    ) -> Result<Option<MultisigSignedLegacyCheckpoint>> {
+        let mut signed_checkpoints_per_root: HashMap<H256, Vec<(usize, SignedLegacyCheckpoint)>> =
+            HashMap::new();
+
+        let mut fetches = validators
+            .iter()
+            .enumerate()
+            .filter_map(|(position, validator)| {
+                let addr = H160::from(*validator);
+                let checkpoint_syncer = self.checkpoint_syncers.get(&addr)?;
+                Some(async move {
+                    let result = self
+                        .timed_request(addr, "legacy_checkpoint", || {
+                            checkpoint_syncer.fetch_legacy_checkpoint(index)
+                        })
+                        .await;
+                    (position, validator, result)
+                })
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        while let Some((position, validator, result)) = fetches.next().await {
+            let Ok(Some(signed_checkpoint)) = result else {
+                continue;
+            };
+            // Ignore checkpoints for another index or not signed by the
+            // validator
+            if signed_checkpoint.value.index != index
+                || signed_checkpoint.recover().map(H256::from).ok() != Some(*validator)
+            {
+                debug!(
+                    validator = format!("{:#x}", validator),
+                    index, "Invalid legacy checkpoint"
+                );
+                continue;
+            }
+
+            let signed_checkpoints = signed_checkpoints_per_root
+                .entry(signed_checkpoint.value.root)
+                .or_default();
+            signed_checkpoints.push((position, signed_checkpoint));
+            if signed_checkpoints.len() >= threshold {
+                let mut signed_checkpoints = in_validator_order(std::mem::take(signed_checkpoints));
+                return Ok(Some((&mut signed_checkpoints).try_into()?));
+            }
+        }
+        debug!("No quorum legacy checkpoint found");
+        Ok(None)
+    }
+
+    /// Gets the latest checkpoint index of a validator from its endpoint if it
+    /// has one, falling back to its checkpoint storage
+    async fn latest_index(
+        &self,
+        validator: H160,
+        checkpoint_syncer: &Arc<dyn CheckpointSyncer>,
+    ) -> Result<Option<u32>> {
+        if let Some(endpoint) = self.latest_checkpoint_endpoints.get(&validator) {
+            match endpoint.latest_index().await {
+                Ok(Some(index)) => return Ok(Some(index)),
+                result => debug!(
+                    ?validator,
+                    url = %endpoint.url(),
+                    ?result,
+                    "No latest index from validator endpoint, falling back to its storage"
+                ),
+            }
+        }
+        self.timed_request(validator, "latest_index", || {
+            checkpoint_syncer.latest_index()
+        })
+        .await
+    }
+
+    /// Sends a request to a validator's checkpoint storage, hedging it if it is
+    /// slow to answer, and records how long it took.
+    async fn timed_request<T, Fut>(
+        &self,
+        validator: H160,
+        request_name: &str,
+        request: impl Fn() -> Fut,
+    ) -> Result<T>
+    where
+        Fut: Future<Output = Result<T>>,
+    {
+        let start = Instant::now();
+        let result = timeout(VALIDATOR_REQUEST_TIMEOUT, hedged(request)).await;
+        let result_label = match &result {
+            Ok(Ok(_)) => "success",
+            Ok(Err(_)) => "failure",
+            Err(_) => "timeout",
+        };
+        self.metrics
+            .checkpoint_fetch_duration_seconds()
+            .with_label_values(&[&format!("{:#x}", validator), request_name, result_label])
+            .observe(start.elapsed().as_secs_f64());
+        result.map_err(|_| {
+            eyre!("Validator storage did not answer within {VALIDATOR_REQUEST_TIMEOUT:?}")
+        })?
+    }
+}
+
+/// Runs `request`, sending it again if it hasn't answered within
+/// `HEDGE_DELAY`, and returns whichever answer comes first.
+async fn hedged<T, Fut>(request: impl Fn() -> Fut) -> Result<T>
+where
+    Fut: Future<Output = Result<T>>,
+{
+    let first = request();
+    tokio::pin!(first);
+    tokio::select! {
+        result = &mut first => return result,
+        _ = sleep(HEDGE_DELAY) => {}
+    }
+    let second = request();
+    tokio::pin!(second);
+    // Take the first successful answer, the other request may still succeed
+    // if one fails
+    tokio::select! {
+        result = &mut first => match result {
+            Ok(value) => Ok(value),
+            Err(_) => second.await,
+        },
+        result = &mut second => match result {
+            Ok(value) => Ok(value),
+            Err(_) => first.await,
+        },
+    }
+}
+
+/// Orders signed checkpoints by the position of their validator in the set,
+/// which is the order the ISM expects their signatures in.
+fn in_validator_order<T>(mut signed_checkpoints: Vec<(usize, T)>) -> Vec<T> {
+    signed_checkpoints.sort_by_key(|(position, _)| *position);
+    signed_checkpoints
+        .into_iter()
+        .map(|(_, signed_checkpoint)| signed_checkpoint)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    /// A request whose `n`th attempt answers after `delays[n]` with the
+    /// attempt number, or fails if the delay is `None`
+    fn request(
+        attempts: &AtomicU32,
+        delays: &[Option<Duration>],
+    ) -> impl Future<Output = Result<u32>> {
+        let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+        let delay = delays[attempt as usize];
+        async move {
+            sleep(delay.unwrap_or(HEDGE_DELAY * 2)).await;
+            delay
+                .map(|_| attempt)
+                .ok_or_else(|| eyre!("attempt {attempt} failed"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hedged_only_resends_slow_requests() {
+        let attempts = AtomicU32::new(0);
+        let delays = [Some(HEDGE_DELAY / 2)];
+        let start = Instant::now();
+        assert_eq!(hedged(|| request(&attempts, &delays)).await.unwrap(), 0);
+        assert!(start.elapsed() < HEDGE_DELAY);
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        // The second attempt is sent after the hedge delay and answers first
+        let attempts = AtomicU32::new(0);
+        let delays = [Some(HEDGE_DELAY * 10), Some(HEDGE_DELAY / 2)];
+        let start = Instant::now();
+        assert_eq!(hedged(|| request(&attempts, &delays)).await.unwrap(), 1);
+        let elapsed = start.elapsed();
+        assert!(elapsed >= HEDGE_DELAY + HEDGE_DELAY / 2);
+        assert!(elapsed < HEDGE_DELAY * 10);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_hedged_falls_back_to_other_attempt_on_failure() {
+        // The hedged attempt fails first, so the answer of the first is awaited
+        let attempts = AtomicU32::new(0);
+        let delays = [Some(HEDGE_DELAY * 4), None];
+        assert_eq!(hedged(|| request(&attempts, &delays)).await.unwrap(), 0);
+
+        // Both attempts fail
+        let attempts = AtomicU32::new(0);
+        let delays = [None, None];
+        assert!(hedged(|| request(&attempts, &delays)).await.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_in_validator_order() {
+        let signed_checkpoints = vec![(2, "c"), (0, "a"), (3, "d"), (1, "b")];
+        assert_eq!(
+            in_validator_order(signed_checkpoints),
+            vec!["a", "b", "c", "d"]
+        );
+        assert!(in_validator_order::<&str>(vec![]).is_empty());
+    }
 }
@@ -4,7 +4,11 @@ use async_trait::async_trait;
 use derive_new::new;
 use eyre::{bail, Result};
 use futures_util::TryStreamExt;
-use hyperlane_core::{ReorgEvent, SignedAnnouncement, SignedCheckpointWithMessageId};
+#[cfg(feature = "legacy")]
+use hyperlane_core::SignedLegacyCheckpoint;
+use hyperlane_core::{
+    config::ProxyConf, ReorgEvent, SignedAnnouncement, SignedCheckpointWithMessageId,
+};
 use prometheus::IntGauge;
 use rusoto_core::{
     credential::{Anonymous, AwsCredentials, StaticProvider},
@@ -40,6 +44,8 @@ pub struct S3Storage {
     anonymous_client: OnceLock<S3Client>,
     /// The latest seen signed checkpoint index.
     latest_index: Option<IntGauge>,
+    /// The proxy requests to the bucket are routed through, if any
+    proxy: Option<ProxyConf>,
 }
 
 impl fmt::Debug for S3Storage {
@@ -96,7 +102,7 @@ impl S3Storage {
     fn authenticated_client(&self) -> &S3Client {
         self.authenticated_client.get_or_init(|| {
             S3Client::new_with(
-                utils::http_client_with_timeout().unwrap(),
+                utils::http_client_with_timeout(self.proxy.as_ref()).unwrap(),
                 AwsChainCredentialsProvider::new(),
                 self.region.clone(),
             )
@@ -116,7 +122,7 @@ impl S3Storage {
             assert!(credentials.is_anonymous(), "AWS credentials not anonymous");
 
             S3Client::new_with(
-                utils::http_client_with_timeout().unwrap(),
+                utils::http_client_with_timeout(self.proxy.as_ref()).unwrap(),
                 StaticProvider::from(credentials),
                 self.region.clone(),
             )
@@ -134,6 +140,11 @@ impl S3Storage {
         format!("checkpoint_{index}_with_id.json")
     }
 
+    #[cfg(feature = "legacy")]
+    fn legacy_checkpoint_key(index: u32) -> String {
+        format!("checkpoint_{index}.json")
+    }
+
     fn latest_index_key() -> String {
         "checkpoint_latest_index.json".to_owned()
     }
@@ -185,6 +196,15 @@ impl CheckpointSyncer for S3Storage {
             .map_err(Into::into)
     }
 
+    #[cfg(feature = "legacy")]
+    async fn fetch_legacy_checkpoint(&self, index: u32) -> Result<Option<SignedLegacyCheckpoint>> {
+        self.anonymously_read_from_bucket(S3Storage::legacy_checkpoint_key(index))
+            .await?
+            .map(|data| serde_json::from_slice(&data))
+            .transpose()
+            .map_err(Into::into)
+    }
+
     async fn write_checkpoint(
         &self,
         signed_checkpoint: &SignedCheckpointWithMessageId,
@@ -1,15 +1,39 @@
 use std::time::Duration;
 
 use eyre::Result;
+use hyper_tls::HttpsConnector;
+use hyperlane_core::{
+    config::ProxyConf,
+    rpc_clients::{with_dns_failover, ProxyConnector},
+};
 use rusoto_core::{HttpClient, HttpConfig};
 
 /// See https://github.com/hyperium/hyper/issues/2136#issuecomment-589488526
 pub const HYPER_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(15);
 
-/// Create a new HTTP client with a timeout for the connection pool.
+/// A reqwest client builder routing requests through `proxy`, if any, and
+/// failing over between the addresses of hosts
+pub fn reqwest_client_builder(proxy: Option<&ProxyConf>) -> reqwest::ClientBuilder {
+    let builder = with_dns_failover(reqwest::Client::builder());
+    match proxy.and_then(|proxy| reqwest::Proxy::all(proxy.url().as_str()).ok()) {
+        Some(proxy) => builder.proxy(proxy),
+        None => builder,
+    }
+}
+
+/// A reqwest client routing requests through `proxy`, if any
+pub fn reqwest_client(proxy: Option<&ProxyConf>) -> reqwest::Client {
+    reqwest_client_builder(proxy).build().unwrap_or_default()
+}
+
+/// Create a new HTTP client with a timeout for the connection pool, routing
+/// requests through `proxy`, if any.
 /// This is a workaround for https://github.com/hyperium/hyper/issues/2136#issuecomment-589345238
-pub fn http_client_with_timeout() -> Result<HttpClient> {
+pub fn http_client_with_timeout(
+    proxy: Option<&ProxyConf>,
+) -> Result<HttpClient<HttpsConnector<ProxyConnector>>> {
     let mut config = HttpConfig::new();
     config.pool_idle_timeout(HYPER_POOL_IDLE_TIMEOUT);
-    Ok(HttpClient::new_with_config(config)?)
+    let connector = HttpsConnector::new_with_connector(ProxyConnector::new(proxy.cloned()));
+    Ok(HttpClient::from_connector_with_config(connector, config))
 }
@@ -0,0 +1,45 @@
+//! Benchmarks of decoding, encoding and hashing messages, which the relayer
+//! does for every indexed and processed message.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hyperlane_core::{HyperlaneMessage, HyperlaneMessageRef, RawHyperlaneMessage, H256};
+
+fn message(body_len: usize) -> HyperlaneMessage {
+    HyperlaneMessage {
+        version: 3,
+        nonce: 42,
+        origin: 1,
+        sender: H256::repeat_byte(1),
+        destination: 2,
+        recipient: H256::repeat_byte(2),
+        body: vec![0xab; body_len],
+    }
+}
+
+fn bench_message(c: &mut Criterion) {
+    let mut group = c.benchmark_group("message");
+    for body_len in [0, 256, 4096] {
+        let message = message(body_len);
+        let raw = RawHyperlaneMessage::from(&message);
+        group.throughput(Throughput::Bytes(raw.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("decode", body_len), &raw, |b, raw| {
+            b.iter(|| HyperlaneMessage::try_from(black_box(raw)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("decode_ref", body_len), &raw, |b, raw| {
+            b.iter(|| HyperlaneMessageRef::new(black_box(raw)).unwrap().id())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("encode", body_len),
+            &message,
+            |b, message| b.iter(|| RawHyperlaneMessage::from(black_box(message))),
+        );
+        group.bench_with_input(BenchmarkId::new("id", body_len), &message, |b, message| {
+            b.iter(|| black_box(message).id())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_message);
+criterion_main!(benches);
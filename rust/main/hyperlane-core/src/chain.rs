@@ -12,7 +12,7 @@ use serde::Serialize;
 #[cfg(feature = "strum")]
 use strum::{EnumIter, EnumString, IntoStaticStr};
 
-use crate::{utils::many_to_one, HyperlaneProtocolError, IndexMode, H160, H256};
+use crate::{utils::many_to_one, AddressFormatter, HyperlaneProtocolError, IndexMode, H256};
 
 #[derive(Debug, Clone)]
 pub struct Address(pub bytes::Bytes);
@@ -192,17 +192,19 @@ pub enum HyperlaneDomainProtocol {
     Sealevel,
     /// A Cosmos-based chain type which uses hyperlane-cosmos.
     Cosmos,
+    /// A Starknet-based chain type which uses hyperlane-starknet.
+    Starknet,
+    /// A Substrate-based chain type which uses hyperlane-substrate.
+    Substrate,
+    /// An Aptos-based chain type which uses hyperlane-aptos.
+    Aptos,
+    /// A TON-based chain type which uses hyperlane-ton.
+    Ton,
 }
 
 impl HyperlaneDomainProtocol {
     pub fn fmt_address(&self, addr: H256) -> String {
-        use HyperlaneDomainProtocol::*;
-        match self {
-            Ethereum => format!("{:?}", H160::from(addr)),
-            Fuel => format!("{:?}", addr),
-            Sealevel => format!("{:?}", addr),
-            Cosmos => format!("{:?}", addr),
-        }
+        AddressFormatter::for_protocol(*self).format(addr)
     }
 }
 
@@ -403,7 +405,10 @@ pub enum HyperlaneDomainConfigError {
 }
 
 impl HyperlaneDomain {
-    #[cfg(feature = "strum")]
+    /// Resolve a domain from its config, looking up its metadata in the
+    /// [`domain_registry`](crate::domain_registry). Domains missing from the
+    /// registry take the protocol and technical stack of the config.
+    #[cfg(feature = "agent")]
     pub fn from_config(
         domain_id: u32,
         name: &str,
@@ -411,13 +416,31 @@ impl HyperlaneDomain {
         domain_technical_stack: HyperlaneDomainTechnicalStack,
     ) -> Result<Self, HyperlaneDomainConfigError> {
         let name = name.to_ascii_lowercase();
-        if let Ok(domain) = KnownHyperlaneDomain::try_from(domain_id) {
-            if name == domain.as_str().to_ascii_lowercase() {
-                Ok(HyperlaneDomain::Known(domain))
-            } else {
-                Err(HyperlaneDomainConfigError::UnknownDomainName(name))
+        let registry = crate::domain_registry();
+        if let Some(domain) = registry.by_id(domain_id) {
+            if name != domain.name {
+                return Err(HyperlaneDomainConfigError::UnknownDomainName(name));
+            }
+            // Domains whose registry metadata matches the build time metadata
+            // keep their known variant
+            match KnownHyperlaneDomain::try_from(domain_id) {
+                Ok(known)
+                    if known.as_str() == name
+                        && known.domain_type() == domain.domain_type
+                        && known.domain_protocol() == domain.protocol
+                        && known.domain_technical_stack() == domain.technical_stack =>
+                {
+                    Ok(HyperlaneDomain::Known(known))
+                }
+                _ => Ok(HyperlaneDomain::Unknown {
+                    domain_id,
+                    domain_name: name,
+                    domain_type: domain.domain_type,
+                    domain_protocol: domain.protocol,
+                    domain_technical_stack: domain.technical_stack,
+                }),
             }
-        } else if name.as_str().parse::<KnownHyperlaneDomain>().is_ok() {
+        } else if registry.by_name(&name).is_some() {
             Err(HyperlaneDomainConfigError::DomainNameMismatch(
                 name, domain_id,
             ))
@@ -488,16 +511,29 @@ impl HyperlaneDomain {
         )
     }
 
+    pub const fn is_op_stack(&self) -> bool {
+        matches!(
+            self.domain_technical_stack(),
+            HyperlaneDomainTechnicalStack::OpStack
+        )
+    }
+
+    /// Whether this is the domain `known`, which may have been resolved from
+    /// registry metadata differing from the build time metadata
+    pub const fn is(&self, known: KnownHyperlaneDomain) -> bool {
+        self.id() == known as u32
+    }
+
     pub const fn is_injective(&self) -> bool {
-        matches!(self, Self::Known(KnownHyperlaneDomain::Injective))
+        self.is(KnownHyperlaneDomain::Injective)
     }
 
     pub const fn index_mode(&self) -> IndexMode {
         use HyperlaneDomainProtocol::*;
         let protocol = self.domain_protocol();
         many_to_one!(match protocol {
-            IndexMode::Block: [Ethereum, Cosmos],
-            IndexMode::Sequence : [Sealevel, Fuel],
+            IndexMode::Block: [Ethereum, Cosmos, Starknet, Substrate],
+            IndexMode::Sequence : [Sealevel, Fuel, Aptos, Ton],
         })
     }
 }
@@ -7,12 +7,18 @@ use std::fmt::{Debug, Display, Formatter};
 
 pub use config_path::ConfigPath;
 use eyre::Report;
+pub use proxy::{ProxyConf, ProxyConfError, ProxyKind};
+pub use rpc_headers::{RpcAuth, RpcHeadersConf};
+pub use rpc_tls::RpcTlsConf;
 pub use str_or_int::{StrOrInt, StrOrIntParseError};
 pub use trait_ext::*;
 
 use crate::H256;
 
 mod config_path;
+mod proxy;
+mod rpc_headers;
+mod rpc_tls;
 mod str_or_int;
 mod trait_ext;
 
@@ -29,6 +35,20 @@ pub struct OperationBatchConfig {
     pub batch_contract_address: Option<H256>,
     /// Batch size
     pub max_batch_size: u32,
+    /// How the data of transactions is priced on the chain. If set, messages
+    /// are only batched if the batch is estimated to be cheaper than
+    /// delivering them one by one.
+    pub data_cost_model: Option<DataCostModel>,
+}
+
+/// How the data of a transaction is priced, relative to its execution gas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataCostModel {
+    /// Calldata priced per EIP-2028
+    Calldata,
+    /// Calldata posted to an L1 compressed, as OP stack chains do since
+    /// Fjord, priced at the L1 data fee the chain currently charges
+    L1Data,
 }
 
 /// A trait that allows for constructing `Self` from a raw config type.
@@ -113,6 +133,11 @@ impl ConfigParsingError {
     pub fn is_ok(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// The errors and the config paths they were found at.
+    pub fn iter(&self) -> impl Iterator<Item = (&ConfigPath, &Report)> {
+        self.0.iter().map(|(path, report)| (path, report))
+    }
 }
 
 impl FromIterator<ConfigParsingError> for ConfigParsingError {
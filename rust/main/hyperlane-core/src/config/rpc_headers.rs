@@ -0,0 +1,129 @@
+use std::fmt::{Debug, Formatter};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+/// Credentials an RPC provider requires with every request
+#[derive(Clone, PartialEq, Eq)]
+pub enum RpcAuth {
+    /// HTTP basic authentication
+    Basic {
+        /// The username
+        username: String,
+        /// The password
+        password: String,
+    },
+    /// A bearer token, e.g. an API key
+    Bearer {
+        /// The token
+        token: String,
+    },
+}
+
+impl RpcAuth {
+    /// The value of the `Authorization` header
+    pub fn header_value(&self) -> String {
+        match self {
+            Self::Basic { username, password } => {
+                format!("Basic {}", BASE64.encode(format!("{username}:{password}")))
+            }
+            Self::Bearer { token } => format!("Bearer {token}"),
+        }
+    }
+}
+
+// Credentials are kept out of logged configs
+impl Debug for RpcAuth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .finish_non_exhaustive(),
+            Self::Bearer { .. } => f.debug_struct("Bearer").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Headers sent with every request to the RPCs of a chain, for providers
+/// which take API keys in headers or through authentication rather than in
+/// the url
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct RpcHeadersConf {
+    /// Custom headers, by name
+    pub headers: Vec<(String, String)>,
+    /// Authentication, sent as the `Authorization` header
+    pub auth: Option<RpcAuth>,
+}
+
+impl RpcHeadersConf {
+    /// Whether no headers are sent
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty() && self.auth.is_none()
+    }
+
+    /// All headers sent, including the `Authorization` header of the
+    /// authentication, which overrides a custom one
+    pub fn all(&self) -> Vec<(String, String)> {
+        let mut headers: Vec<_> = self
+            .headers
+            .iter()
+            .filter(|(name, _)| self.auth.is_none() || !name.eq_ignore_ascii_case("authorization"))
+            .cloned()
+            .collect();
+        if let Some(auth) = &self.auth {
+            headers.push(("authorization".to_owned(), auth.header_value()));
+        }
+        headers
+    }
+}
+
+// Header values often hold API keys, so only their names are logged
+impl Debug for RpcHeadersConf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<_> = self.headers.iter().map(|(name, _)| name).collect();
+        f.debug_struct("RpcHeadersConf")
+            .field("headers", &names)
+            .field("auth", &self.auth)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rpc_headers() {
+        let conf = RpcHeadersConf {
+            headers: vec![
+                ("x-api-key".to_owned(), "secret".to_owned()),
+                ("Authorization".to_owned(), "overridden".to_owned()),
+            ],
+            auth: Some(RpcAuth::Basic {
+                username: "Aladdin".to_owned(),
+                password: "open sesame".to_owned(),
+            }),
+        };
+        assert_eq!(
+            conf.all(),
+            vec![
+                ("x-api-key".to_owned(), "secret".to_owned()),
+                (
+                    "authorization".to_owned(),
+                    "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==".to_owned()
+                ),
+            ]
+        );
+        let debug = format!("{conf:?}");
+        assert!(
+            !debug.contains("secret") && !debug.contains("sesame"),
+            "{debug}"
+        );
+
+        let bearer = RpcAuth::Bearer {
+            token: "token".to_owned(),
+        };
+        assert_eq!(bearer.header_value(), "Bearer token");
+        assert!(RpcHeadersConf::default().is_empty());
+    }
+}
@@ -0,0 +1,24 @@
+use std::fmt::{Debug, Formatter};
+
+/// A client certificate presented to RPCs requiring mutual TLS, e.g. nodes
+/// hosted behind an enterprise gateway
+#[derive(Clone, PartialEq, Eq)]
+pub struct RpcTlsConf {
+    /// The PEM encoded client certificate, followed by its intermediates
+    pub client_cert: Vec<u8>,
+    /// The PEM encoded PKCS#8 private key of the client certificate
+    pub client_key: Vec<u8>,
+    /// A PEM encoded CA certificate the certificates of the RPCs are
+    /// verified against, on top of the system roots
+    pub ca_cert: Option<Vec<u8>>,
+}
+
+// The private key is kept out of logged configs, and the certificates are
+// too long to be useful in them
+impl Debug for RpcTlsConf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcTlsConf")
+            .field("has_ca_cert", &self.ca_cert.is_some())
+            .finish_non_exhaustive()
+    }
+}
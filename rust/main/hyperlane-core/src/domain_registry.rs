@@ -0,0 +1,210 @@
+//! Runtime registry of the metadata of Hyperlane domains.
+//!
+//! The registry starts from an embedded file listing the domains known at
+//! build time, which agents extend or override with the domains of their
+//! config. Operators can therefore add domains without recompiling.
+
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{OnceLock, RwLock, RwLockReadGuard},
+};
+
+use serde::Deserialize;
+
+use crate::{HyperlaneDomainProtocol, HyperlaneDomainTechnicalStack, HyperlaneDomainType};
+
+/// The domains known at build time
+const EMBEDDED_DOMAINS: &str = include_str!("domains.json");
+
+/// Metadata of a Hyperlane domain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DomainMetadata {
+    /// The domain id
+    pub id: u32,
+    /// The chain name, in lowercase
+    pub name: String,
+    /// Type of the domain
+    pub domain_type: HyperlaneDomainType,
+    /// Protocol of the domain
+    pub protocol: HyperlaneDomainProtocol,
+    /// Technical stack of the domain
+    pub technical_stack: HyperlaneDomainTechnicalStack,
+}
+
+/// An error loading domain metadata
+#[derive(thiserror::Error, Debug)]
+pub enum DomainRegistryError {
+    /// The registry is not valid JSON
+    #[error("Invalid domain registry: {0}")]
+    Json(#[from] serde_json::Error),
+    /// A field of a domain has an unknown value
+    #[error("Invalid {field} `{value}` for domain {domain_id}")]
+    InvalidField {
+        /// The domain id
+        domain_id: u32,
+        /// Name of the field
+        field: &'static str,
+        /// Value of the field
+        value: String,
+    },
+}
+
+/// A domain as written in registry files
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawDomainMetadata {
+    domain_id: u32,
+    name: String,
+    domain_type: Option<String>,
+    protocol: String,
+    technical_stack: Option<String>,
+}
+
+impl TryFrom<RawDomainMetadata> for DomainMetadata {
+    type Error = DomainRegistryError;
+
+    fn try_from(raw: RawDomainMetadata) -> Result<Self, Self::Error> {
+        let parse = |field: &'static str, value: &str| DomainRegistryError::InvalidField {
+            domain_id: raw.domain_id,
+            field,
+            value: value.to_owned(),
+        };
+        let domain_type = match &raw.domain_type {
+            Some(value) => {
+                HyperlaneDomainType::from_str(value).map_err(|_| parse("domain type", value))?
+            }
+            None => HyperlaneDomainType::Unknown,
+        };
+        let protocol = HyperlaneDomainProtocol::from_str(&raw.protocol)
+            .map_err(|_| parse("protocol", &raw.protocol))?;
+        let technical_stack = match &raw.technical_stack {
+            Some(value) => HyperlaneDomainTechnicalStack::from_str(value)
+                .map_err(|_| parse("technical stack", value))?,
+            None => HyperlaneDomainTechnicalStack::default(),
+        };
+        Ok(Self {
+            id: raw.domain_id,
+            name: raw.name.to_ascii_lowercase(),
+            domain_type,
+            protocol,
+            technical_stack,
+        })
+    }
+}
+
+/// Metadata of Hyperlane domains, by domain id
+#[derive(Debug, Clone, Default)]
+pub struct DomainRegistry {
+    domains: HashMap<u32, DomainMetadata>,
+}
+
+impl DomainRegistry {
+    /// The registry of the domains known at build time
+    pub fn embedded() -> Self {
+        Self::from_json(EMBEDDED_DOMAINS).expect("embedded domain registry is valid")
+    }
+
+    /// Parse a registry from a JSON array of domains
+    pub fn from_json(json: &str) -> Result<Self, DomainRegistryError> {
+        let raw: Vec<RawDomainMetadata> = serde_json::from_str(json)?;
+        let mut registry = Self::default();
+        for domain in raw {
+            registry.insert(domain.try_into()?);
+        }
+        Ok(registry)
+    }
+
+    /// Add a domain, replacing any domain with the same id or name
+    pub fn insert(&mut self, domain: DomainMetadata) {
+        self.domains
+            .retain(|id, existing| *id != domain.id && existing.name != domain.name);
+        self.domains.insert(domain.id, domain);
+    }
+
+    /// The domain with id `domain_id`
+    pub fn by_id(&self, domain_id: u32) -> Option<&DomainMetadata> {
+        self.domains.get(&domain_id)
+    }
+
+    /// The domain named `name`, ignoring case
+    pub fn by_name(&self, name: &str) -> Option<&DomainMetadata> {
+        self.domains
+            .values()
+            .find(|domain| domain.name.eq_ignore_ascii_case(name))
+    }
+}
+
+fn global() -> &'static RwLock<DomainRegistry> {
+    static REGISTRY: OnceLock<RwLock<DomainRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(DomainRegistry::embedded()))
+}
+
+/// The registry of the domains known to this process
+pub fn domain_registry() -> RwLockReadGuard<'static, DomainRegistry> {
+    global().read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Add domains to the registry of this process, replacing the domains with
+/// the same ids or names.
+pub fn register_domains(domains: impl IntoIterator<Item = DomainMetadata>) {
+    let mut registry = global().write().unwrap_or_else(|e| e.into_inner());
+    for domain in domains {
+        registry.insert(domain);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+    use crate::KnownHyperlaneDomain;
+
+    #[test]
+    fn test_embedded_registry_matches_known_domains() {
+        let registry = DomainRegistry::embedded();
+        for known in KnownHyperlaneDomain::iter() {
+            let domain = registry.by_id(known as u32).unwrap();
+            assert_eq!(domain.name, known.as_str());
+            assert_eq!(domain.domain_type, known.domain_type());
+            assert_eq!(domain.protocol, known.domain_protocol());
+            assert_eq!(domain.technical_stack, known.domain_technical_stack());
+        }
+    }
+
+    #[test]
+    fn test_insert_replaces_id_and_name() {
+        let mut registry = DomainRegistry::from_json(
+            r#"[{"domainId": 1, "name": "Foo", "protocol": "ethereum"}]"#,
+        )
+        .unwrap();
+        let foo = registry.by_name("foo").unwrap();
+        assert_eq!(foo.domain_type, HyperlaneDomainType::Unknown);
+        assert_eq!(foo.technical_stack, HyperlaneDomainTechnicalStack::Other);
+
+        registry.insert(DomainMetadata {
+            id: 2,
+            name: "foo".to_owned(),
+            domain_type: HyperlaneDomainType::Mainnet,
+            protocol: HyperlaneDomainProtocol::Ton,
+            technical_stack: HyperlaneDomainTechnicalStack::Other,
+        });
+        assert!(registry.by_id(1).is_none());
+        assert_eq!(registry.by_name("FOO").unwrap().id, 2);
+    }
+
+    #[test]
+    fn test_invalid_protocol() {
+        let err = DomainRegistry::from_json(r#"[{"domainId": 1, "name": "foo", "protocol": "x"}]"#)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DomainRegistryError::InvalidField {
+                domain_id: 1,
+                field: "protocol",
+                ..
+            }
+        ));
+    }
+}
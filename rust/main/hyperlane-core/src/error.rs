@@ -17,9 +17,16 @@ use crate::{Error as PrimitiveTypeError, HyperlaneSignerError, H256, U256};
 pub type ChainResult<T> = Result<T, ChainCommunicationError>;
 
 /// An "Any"-typed error.
-pub trait HyperlaneCustomError: StdError + Send + Sync + Any {}
+pub trait HyperlaneCustomError: StdError + Send + Sync + Any {
+    /// The error as `Any`, for it to be downcast to its concrete type
+    fn as_any(&self) -> &dyn Any;
+}
 
-impl<E: StdError + Send + Sync + Any> HyperlaneCustomError for E {}
+impl<E: StdError + Send + Sync + Any> HyperlaneCustomError for E {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
 
 /// Thin wrapper around a boxed HyperlaneCustomError; required to satisfy
 /// AsDynError implementations. Basically a trait-object adaptor.
@@ -27,6 +34,13 @@ impl<E: StdError + Send + Sync + Any> HyperlaneCustomError for E {}
 #[derive(new)]
 pub struct HyperlaneCustomErrorWrapper(Box<dyn HyperlaneCustomError>);
 
+impl HyperlaneCustomErrorWrapper {
+    /// The wrapped error, if it is of type `E`
+    pub fn downcast_ref<E: HyperlaneCustomError>(&self) -> Option<&E> {
+        self.0.as_ref().as_any().downcast_ref()
+    }
+}
+
 impl Debug for HyperlaneCustomErrorWrapper {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", AsRef::<dyn HyperlaneCustomError>::as_ref(&self))
@@ -157,6 +171,9 @@ pub enum ChainCommunicationError {
     /// Hyperlane signer error
     #[error("{0}")]
     HyperlaneSignerError(#[from] HyperlaneSignerError),
+    /// Error indexing logs
+    #[error(transparent)]
+    IndexError(#[from] IndexError),
 }
 
 impl ChainCommunicationError {
@@ -241,6 +258,53 @@ impl From<ethers_providers::ProviderError> for ChainCommunicationError {
     }
 }
 
+/// Errors indexing the logs of a contract. They tell apart logs which aren't
+/// available yet, which are worth querying again, from malformed logs, which
+/// no retry will fix.
+///
+/// Only the indexers which can tell these apart return them, i.e. the EVM
+/// dispatch indexer and the Sealevel mailbox and IGP indexers. Other indexing
+/// failures are reported as untyped `ChainCommunicationError`s.
+#[derive(Debug, thiserror::Error)]
+pub enum IndexError {
+    /// An indexed log is missing the sequence that sequence aware cursors
+    /// need
+    #[error("Indexed log is missing its sequence")]
+    MissingSequence,
+    /// No log was found for a sequence, e.g. because the RPC lags behind the
+    /// one the sequence count was read from
+    #[error("No {log} log found for sequence {sequence}")]
+    LogNotFound {
+        /// The kind of log
+        log: &'static str,
+        /// The sequence queried
+        sequence: u64,
+    },
+    /// A log is missing one of its fields
+    #[error("{log} log is missing its {field}")]
+    MissingLogField {
+        /// The kind of log
+        log: &'static str,
+        /// The missing field
+        field: &'static str,
+    },
+    /// A log couldn't be decoded
+    #[error("Malformed {log} log: {reason}")]
+    MalformedLog {
+        /// The kind of log
+        log: &'static str,
+        /// Why it couldn't be decoded
+        reason: String,
+    },
+}
+
+impl IndexError {
+    /// Whether querying the log again may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, IndexError::LogNotFound { .. })
+    }
+}
+
 /// Error types for the Hyperlane protocol
 #[derive(Debug, thiserror::Error)]
 pub enum HyperlaneProtocolError {
@@ -257,4 +321,13 @@ pub enum HyperlaneProtocolError {
     /// Expected a gas limit and none was provided
     #[error("A gas limit was expected for `process` contract call")]
     ProcessGasLimitRequired,
+    /// Hook metadata of a variant other than the standard one was encountered
+    #[error("Unsupported hook metadata variant ({0})")]
+    UnsupportedHookMetadataVariant(u16),
+    /// An encoded message was shorter than the fixed size fields of a message
+    #[error("Encoded message is too short ({0} bytes)")]
+    MessageTooShort(usize),
+    /// A message of a version the domain isn't configured for was encountered
+    #[error("Unsupported message version ({0})")]
+    UnsupportedMessageVersion(u8),
 }
@@ -8,6 +8,8 @@
 extern crate core;
 
 pub use chain::*;
+#[cfg(feature = "agent")]
+pub use domain_registry::*;
 pub use error::*;
 pub use error::{ChainCommunicationError, ChainResult, HyperlaneProtocolError};
 pub use identifiers::HyperlaneIdentifier;
@@ -34,6 +36,10 @@ pub mod metrics;
 mod types;
 
 mod chain;
+/// Runtime registry of domain metadata. Only agents use it: programs built
+/// from this crate cannot have writable statics.
+#[cfg(feature = "agent")]
+mod domain_registry;
 mod error;
 
 /// Implementations of custom rpc client logic (e.g. fallback)
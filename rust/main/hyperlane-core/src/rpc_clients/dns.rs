@@ -0,0 +1,305 @@
+//! Resolution of RPC hosts with failover between their addresses. Hosts often
+//! have several A and AAAA records, some of which may be unreachable: each
+//! address gets a share of the connect timeout, addresses which failed are
+//! tried last, and hosts are resolved again once their addresses keep failing
+//! rather than being connected to at the first address resolved.
+//!
+//! Clients taking a connector, such as hyper ones, connect through
+//! [`DnsFailover::connect`] and get all of the above. reqwest clients can't
+//! take a connector, so they only resolve hosts through [`FailoverResolver`]:
+//! they try the addresses in the same order and share the connect timeout
+//! between them, but which address failed isn't reported back. Their failures
+//! don't move an address back nor get its host resolved again early, which
+//! only happens once the resolution expires.
+
+use std::{
+    collections::HashMap,
+    io,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, LazyLock, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use tokio::{net::TcpStream, time::timeout};
+
+/// How long connecting to a host may take, shared between its addresses
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long the addresses of a host are used before resolving it again
+const RESOLUTION_TTL: Duration = Duration::from_secs(60);
+/// How long an address which failed to connect is tried after the others
+const FAILED_ADDRESS_BACKOFF: Duration = Duration::from_secs(60);
+/// How many connections to a host can fail in a row before it is resolved
+/// again
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+/// The addresses of the hosts connected to by the agent
+pub static DNS_FAILOVER: LazyLock<DnsFailover> = LazyLock::new(Default::default);
+
+/// Resolved addresses of hosts, and the connection failures to them
+#[derive(Debug, Default)]
+pub struct DnsFailover {
+    hosts: Mutex<HashMap<String, HostAddresses>>,
+}
+
+#[derive(Debug)]
+struct HostAddresses {
+    /// In the order they are tried when none failed
+    addresses: Vec<IpAddr>,
+    resolved_at: Instant,
+    failed_at: HashMap<IpAddr, Instant>,
+    consecutive_failures: u32,
+}
+
+impl HostAddresses {
+    fn new(addresses: Vec<IpAddr>) -> Self {
+        Self {
+            addresses,
+            resolved_at: Instant::now(),
+            failed_at: HashMap::new(),
+            consecutive_failures: 0,
+        }
+    }
+
+    /// Whether the host should be resolved again
+    fn is_stale(&self) -> bool {
+        self.resolved_at.elapsed() > RESOLUTION_TTL
+            || self.consecutive_failures >= MAX_CONSECUTIVE_FAILURES
+    }
+
+    /// The addresses in the order to try them: those which haven't failed
+    /// recently first, then those which failed, least recently first
+    fn ordered(&self) -> Vec<IpAddr> {
+        let recent_failure = |ip: &IpAddr| {
+            self.failed_at
+                .get(ip)
+                .filter(|failed_at| failed_at.elapsed() < FAILED_ADDRESS_BACKOFF)
+                .copied()
+        };
+        let mut addresses = self.addresses.clone();
+        // The sort is stable, so healthy addresses keep their order
+        addresses.sort_by_key(recent_failure);
+        addresses
+    }
+}
+
+impl DnsFailover {
+    fn hosts(&self) -> MutexGuard<'_, HashMap<String, HostAddresses>> {
+        self.hosts.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// The addresses of `host` in the order to try them, resolving it if it
+    /// wasn't recently
+    pub async fn resolve(&self, host: &str) -> io::Result<Vec<IpAddr>> {
+        let cached = self
+            .hosts()
+            .get(host)
+            .filter(|addresses| !addresses.is_stale())
+            .map(HostAddresses::ordered);
+        if let Some(addresses) = cached {
+            return Ok(addresses);
+        }
+
+        let resolved: Vec<_> = tokio::net::lookup_host((host, 0))
+            .await?
+            .map(|address| address.ip())
+            .collect();
+        if resolved.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{host} has no addresses"),
+            ));
+        }
+        let addresses = HostAddresses::new(interleave_families(resolved));
+        let ordered = addresses.ordered();
+        self.hosts().insert(host.to_owned(), addresses);
+        Ok(ordered)
+    }
+
+    /// Record that connecting to `host` at `ip` failed
+    pub fn report_failure(&self, host: &str, ip: IpAddr) {
+        if let Some(addresses) = self.hosts().get_mut(host) {
+            addresses.failed_at.insert(ip, Instant::now());
+            addresses.consecutive_failures += 1;
+        }
+    }
+
+    /// Record that connecting to `host` at `ip` succeeded
+    pub fn report_success(&self, host: &str, ip: IpAddr) {
+        if let Some(addresses) = self.hosts().get_mut(host) {
+            addresses.failed_at.remove(&ip);
+            addresses.consecutive_failures = 0;
+        }
+    }
+
+    /// Connect to `host:port`, trying each of its addresses in turn
+    pub async fn connect(&self, host: &str, port: u16) -> io::Result<TcpStream> {
+        // IPv6 hosts of urls are bracketed
+        let literal = host.trim_start_matches('[').trim_end_matches(']');
+        if let Ok(ip) = literal.parse::<IpAddr>() {
+            return connect_with_timeout(SocketAddr::new(ip, port), CONNECT_TIMEOUT).await;
+        }
+
+        let addresses = self.resolve(host).await?;
+        let mut last_err = None;
+        for (i, ip) in addresses.iter().enumerate() {
+            // Like hyper, the timeout is shared between the remaining addresses
+            let address_timeout = CONNECT_TIMEOUT / (addresses.len() - i) as u32;
+            match connect_with_timeout(SocketAddr::new(*ip, port), address_timeout).await {
+                Ok(stream) => {
+                    self.report_success(host, *ip);
+                    return Ok(stream);
+                }
+                Err(err) => {
+                    self.report_failure(host, *ip);
+                    last_err = Some(err);
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| io::Error::other(format!("{host} has no addresses"))))
+    }
+}
+
+async fn connect_with_timeout(address: SocketAddr, limit: Duration) -> io::Result<TcpStream> {
+    let stream = timeout(limit, TcpStream::connect(address))
+        .await
+        .map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("connecting to {address} timed out"),
+            )
+        })??;
+    stream.set_nodelay(true)?;
+    Ok(stream)
+}
+
+/// Alternate between IPv6 and IPv4 addresses, starting with the family of the
+/// first, so that an unreachable family doesn't hold up connecting
+fn interleave_families(addresses: Vec<IpAddr>) -> Vec<IpAddr> {
+    let Some(first) = addresses.first().copied() else {
+        return addresses;
+    };
+    let (mut preferred, mut other): (Vec<_>, Vec<_>) = addresses
+        .into_iter()
+        .partition(|ip| ip.is_ipv6() == first.is_ipv6());
+    let mut interleaved = Vec::with_capacity(preferred.len() + other.len());
+    preferred.reverse();
+    other.reverse();
+    loop {
+        match (preferred.pop(), other.pop()) {
+            (None, None) => break,
+            (preferred, other) => interleaved.extend(preferred.into_iter().chain(other)),
+        }
+    }
+    interleaved
+}
+
+/// Resolves the hosts of reqwest clients through [`DNS_FAILOVER`]. The
+/// failures of the connections made with the addresses it resolves aren't
+/// recorded, but the ones recorded by other clients are taken into account.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FailoverResolver;
+
+impl Resolve for FailoverResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addresses = DNS_FAILOVER.resolve(name.as_str()).await?;
+            // reqwest sets the port of the url
+            let addrs: Addrs = Box::new(addresses.into_iter().map(|ip| SocketAddr::new(ip, 0)));
+            Ok(addrs)
+        })
+    }
+}
+
+/// Resolve hosts through [`DNS_FAILOVER`], giving up on an address once its
+/// share of the connect timeout is over rather than waiting on the OS
+pub fn with_dns_failover(builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    builder
+        .dns_resolver(Arc::new(FailoverResolver))
+        .connect_timeout(CONNECT_TIMEOUT)
+}
+
+#[cfg(test)]
+mod test {
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    #[test]
+    fn test_interleave_families() {
+        let v4: IpAddr = "10.0.0.1".parse().unwrap();
+        let v4_2: IpAddr = "10.0.0.2".parse().unwrap();
+        let v6: IpAddr = "fd00::1".parse().unwrap();
+        assert_eq!(interleave_families(vec![v6, v4, v4_2]), vec![v6, v4, v4_2]);
+        assert_eq!(interleave_families(vec![v4, v4_2, v6]), vec![v4, v6, v4_2]);
+        assert_eq!(interleave_families(vec![]), vec![]);
+    }
+
+    async fn test_failover() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let dead: IpAddr = "127.0.0.2".parse().unwrap();
+        let alive: IpAddr = "127.0.0.1".parse().unwrap();
+        let failover = DnsFailover::default();
+        failover
+            .hosts()
+            .insert("rpc.test".into(), HostAddresses::new(vec![dead, alive]));
+
+        failover.connect("rpc.test", port).await.unwrap();
+        assert_eq!(
+            failover.resolve("rpc.test").await.unwrap(),
+            vec![alive, dead]
+        );
+        assert_eq!(failover.hosts()["rpc.test"].consecutive_failures, 0);
+
+        for _ in 0..MAX_CONSECUTIVE_FAILURES {
+            failover.report_failure("rpc.test", alive);
+        }
+        assert!(failover.hosts()["rpc.test"].is_stale());
+    }
+
+    #[test]
+    fn test_connect_failover() {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(test_failover());
+    }
+
+    async fn test_resolver() {
+        let first: IpAddr = "10.0.0.1".parse().unwrap();
+        let second: IpAddr = "10.0.0.2".parse().unwrap();
+        DNS_FAILOVER.hosts().insert(
+            "resolver.test".into(),
+            HostAddresses::new(vec![first, second]),
+        );
+        let resolve = || async {
+            FailoverResolver
+                .resolve("resolver.test".parse().unwrap())
+                .await
+                .unwrap()
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            resolve().await,
+            vec![SocketAddr::new(first, 0), SocketAddr::new(second, 0)]
+        );
+        // Failures recorded by connectors are taken into account
+        DNS_FAILOVER.report_failure("resolver.test", first);
+        assert_eq!(
+            resolve().await,
+            vec![SocketAddr::new(second, 0), SocketAddr::new(first, 0)]
+        );
+    }
+
+    #[test]
+    fn test_resolver_orders_addresses() {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(test_resolver());
+    }
+}
@@ -1,14 +1,30 @@
 pub use self::error::*;
 
+#[cfg(feature = "async")]
+pub use self::dns::*;
+
 #[cfg(feature = "async")]
 pub use self::fallback::*;
 
+#[cfg(feature = "async")]
+pub use self::proxy::*;
+
 #[cfg(feature = "async")]
 pub use self::retry::*;
 
+pub use self::usage::*;
+
+#[cfg(feature = "async")]
+mod dns;
+
 mod error;
 #[cfg(feature = "async")]
 mod fallback;
 
+#[cfg(feature = "async")]
+mod proxy;
+
 #[cfg(feature = "async")]
 mod retry;
+
+mod usage;
@@ -0,0 +1,172 @@
+//! Connections routed through an HTTP or SOCKS5 proxy, for clients taking a
+//! connector such as hyper and tonic ones. The handshakes with the proxy are
+//! left to `async-http-proxy` and `tokio-socks`. Clients with their own proxy
+//! support, such as reqwest, are configured with the proxy url instead.
+
+use std::{
+    future::Future,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use async_http_proxy::{http_connect_tokio, http_connect_tokio_with_basic_auth};
+use hyper::{service::Service, Uri};
+use tokio::net::TcpStream;
+use tokio_socks::tcp::Socks5Stream;
+
+use crate::config::{ProxyConf, ProxyKind};
+
+use super::DNS_FAILOVER;
+
+/// Opens TCP connections to the targets of requests, through a proxy if one
+/// is configured, failing over between the addresses of hosts. TLS is layered
+/// on top of the connection by the client.
+#[derive(Clone, Debug)]
+pub struct ProxyConnector {
+    proxy: Option<ProxyConf>,
+}
+
+impl ProxyConnector {
+    /// Create a connector, connecting directly if `proxy` is None
+    pub fn new(proxy: Option<ProxyConf>) -> Self {
+        Self { proxy }
+    }
+}
+
+impl Service<Uri> for ProxyConnector {
+    type Response = TcpStream;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = io::Result<TcpStream>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let proxy = self.proxy.clone();
+        Box::pin(async move {
+            let host = uri
+                .host()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "url has no host"))?;
+            let default_port = match uri.scheme_str() {
+                Some("https") => 443,
+                _ => 80,
+            };
+            let port = uri.port_u16().unwrap_or(default_port);
+            match proxy {
+                Some(proxy) => connect(&proxy, host, port).await,
+                None => DNS_FAILOVER.connect(host, port).await,
+            }
+        })
+    }
+}
+
+/// Open a connection to `host:port` through a proxy
+pub async fn connect(proxy: &ProxyConf, host: &str, port: u16) -> io::Result<TcpStream> {
+    let (proxy_host, proxy_port) = proxy.address();
+    let mut stream = DNS_FAILOVER.connect(proxy_host, proxy_port).await?;
+    // IPv6 hosts of urls are bracketed
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    match proxy.kind() {
+        ProxyKind::Http => {
+            let connected = match proxy.credentials() {
+                Some((username, password)) => {
+                    http_connect_tokio_with_basic_auth(&mut stream, host, port, username, password)
+                        .await
+                }
+                None => http_connect_tokio(&mut stream, host, port).await,
+            };
+            connected.map_err(io::Error::other)?;
+            Ok(stream)
+        }
+        // The host is sent to the proxy as is, for it to resolve
+        ProxyKind::Socks5 => {
+            let connected = match proxy.credentials() {
+                Some((username, password)) => {
+                    Socks5Stream::connect_with_password_and_socket(
+                        stream,
+                        (host, port),
+                        username,
+                        password,
+                    )
+                    .await
+                }
+                None => Socks5Stream::connect_with_socket(stream, (host, port)).await,
+            };
+            Ok(connected.map_err(io::Error::other)?.into_inner())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    /// Accepts a connection and answers each step of the handshake, given as
+    /// the length of the request and the reply, then answers `ping` with
+    /// `hello`, as the client speaks first over TLS. Returns the requests.
+    async fn serve_proxy(listener: TcpListener, steps: Vec<(usize, Vec<u8>)>) -> Vec<u8> {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut requests = vec![];
+        for (request_len, reply) in steps {
+            let mut request = vec![0u8; request_len];
+            stream.read_exact(&mut request).await.unwrap();
+            requests.extend(request);
+            stream.write_all(&reply).await.unwrap();
+        }
+        let mut ping = [0u8; 4];
+        stream.read_exact(&mut ping).await.unwrap();
+        assert_eq!(&ping, b"ping");
+        stream.write_all(b"hello").await.unwrap();
+        requests
+    }
+
+    async fn connect_and_read(proxy: ProxyConf) -> String {
+        let mut stream = connect(&proxy, "example.com", 443).await.unwrap();
+        stream.write_all(b"ping").await.unwrap();
+        let mut hello = String::new();
+        stream.read_to_string(&mut hello).await.unwrap();
+        hello
+    }
+
+    async fn test_proxies() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let request = "CONNECT example.com:443 HTTP/1.1\r\nHost: example.com:443\r\n\
+                       Proxy-Connection: Keep-Alive\r\n\r\n";
+        let reply = b"HTTP/1.1 200 Connection established\r\n\r\n".to_vec();
+        let server = tokio::spawn(serve_proxy(listener, vec![(request.len(), reply)]));
+        let proxy = ProxyConf::new(format!("http://{address}").parse().unwrap()).unwrap();
+        assert_eq!(connect_and_read(proxy).await, "hello");
+        assert_eq!(server.await.unwrap(), request.as_bytes());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let mut handshake = vec![5, 1, 0, 5, 1, 0, 3, 11];
+        handshake.extend(b"example.com");
+        handshake.extend(443u16.to_be_bytes());
+        let steps = vec![
+            (3, vec![5, 0]),
+            (handshake.len() - 3, vec![5, 0, 0, 1, 127, 0, 0, 1, 0, 80]),
+        ];
+        let server = tokio::spawn(serve_proxy(listener, steps));
+        let proxy = ProxyConf::new(format!("socks5h://{address}").parse().unwrap()).unwrap();
+        assert_eq!(connect_and_read(proxy).await, "hello");
+        assert_eq!(server.await.unwrap(), handshake);
+    }
+
+    #[test]
+    fn test_connect_through_proxy() {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(test_proxies());
+    }
+}
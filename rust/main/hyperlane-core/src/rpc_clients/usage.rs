@@ -0,0 +1,49 @@
+use prometheus::IntCounterVec;
+use url::Url;
+
+/// Records the RPC requests made on a chain whose RPC clients aren't
+/// instrumented by the ethers middleware, in the same metrics, so that they
+/// show up in the RPC usage summaries too.
+#[derive(Clone, Debug)]
+pub struct RpcUsageMetrics {
+    chain: String,
+    /// Labelled by `provider_node`, `chain`, `method` and `status`
+    request_count: IntCounterVec,
+    /// Labelled by `provider_node`, `chain` and `method`
+    request_payload_bytes: IntCounterVec,
+}
+
+impl RpcUsageMetrics {
+    /// Record the requests made on `chain` in the given metrics
+    pub fn new(
+        chain: &str,
+        request_count: IntCounterVec,
+        request_payload_bytes: IntCounterVec,
+    ) -> Self {
+        Self {
+            chain: chain.to_owned(),
+            request_count,
+            request_payload_bytes,
+        }
+    }
+
+    /// Record a request to the node at `url`
+    pub fn record_request(&self, url: &Url, method: &str, success: bool) {
+        let status = if success { "success" } else { "failure" };
+        self.request_count
+            .with_label_values(&[provider_node(url), &self.chain, method, status])
+            .inc();
+    }
+
+    /// Record the bytes of the payload of a request to the node at `url`
+    pub fn record_payload_bytes(&self, url: &Url, method: &str, bytes: usize) {
+        self.request_payload_bytes
+            .with_label_values(&[provider_node(url), &self.chain, method])
+            .inc_by(bytes as u64);
+    }
+}
+
+/// The host of the node, as labelled by the ethers middleware
+fn provider_node(url: &Url) -> &str {
+    url.host_str().unwrap_or("unknown")
+}
@@ -8,7 +8,7 @@ use async_trait::async_trait;
 use auto_impl::auto_impl;
 use eyre::Result;
 
-use crate::{Indexed, LogMeta};
+use crate::{Indexed, LogMeta, SkippedLog};
 
 /// A cursor governs event indexing for a contract.
 #[async_trait]
@@ -33,6 +33,30 @@ pub trait ContractSyncCursor<T>: Debug + Send + Sync + 'static {
         logs: Vec<(Indexed<T>, LogMeta)>,
         range: RangeInclusive<u32>,
     ) -> Result<()>;
+
+    /// Ingests the logs like `update`, along with the logs of the range that
+    /// were skipped because they were malformed. Cursors which require every
+    /// sequence to be indexed count the skipped ones as indexed, so that they
+    /// don't query them again forever.
+    async fn update_with_skipped(
+        &mut self,
+        logs: Vec<(Indexed<T>, LogMeta)>,
+        _skipped: &[SkippedLog],
+        range: RangeInclusive<u32>,
+    ) -> Result<()> {
+        self.update(logs, range).await
+    }
+
+    /// Moves the cursor back to query again from `from_block`, the earliest
+    /// block that may have been reorged, after the `orphaned_logs` it ingested
+    /// were reorged out.
+    async fn rewind_orphaned(
+        &mut self,
+        _orphaned_logs: &[(Indexed<T>, LogMeta)],
+        _from_block: u32,
+    ) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// The action that should be taken by the contract sync loop
@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::RangeInclusive};
 
 use async_trait::async_trait;
 use auto_impl::auto_impl;
@@ -13,6 +13,12 @@ pub trait HyperlaneLogStore<T>: Send + Sync + Debug {
     /// Store a list of logs and their associated metadata
     /// Returns the number of elements that were stored.
     async fn store_logs(&self, logs: &[(Indexed<T>, LogMeta)]) -> Result<u32>;
+
+    /// Remove previously stored logs whose blocks were orphaned by a reorg.
+    /// Returns the number of elements that were removed.
+    async fn invalidate_logs(&self, _logs: &[(Indexed<T>, LogMeta)]) -> Result<u32> {
+        Ok(0)
+    }
 }
 
 /// A sequence is a monotonically increasing number that is incremented every time a message ID is indexed.
@@ -57,3 +63,23 @@ pub trait HyperlaneWatermarkedLogStore<T>: HyperlaneLogStore<T> {
     /// Stores the block number high watermark
     async fn store_high_watermark(&self, block_number: u32) -> Result<()>;
 }
+
+/// Records the block ranges a backfill has indexed, so that it resumes where it
+/// left off after a restart.
+#[async_trait]
+#[auto_impl(&, Box, Arc)]
+pub trait HyperlaneBackfillStore: Send + Sync + Debug {
+    /// Whether the backfill named `label` has indexed `range`
+    async fn is_backfill_range_complete(
+        &self,
+        label: &str,
+        range: &RangeInclusive<u32>,
+    ) -> Result<bool>;
+
+    /// Record that the backfill named `label` has indexed `range`
+    async fn store_backfill_range_complete(
+        &self,
+        label: &str,
+        range: &RangeInclusive<u32>,
+    ) -> Result<()>;
+}
@@ -11,7 +11,7 @@ use async_trait::async_trait;
 use auto_impl::auto_impl;
 use serde::Deserialize;
 
-use crate::{ChainResult, Indexed, LogMeta, H512};
+use crate::{ChainResult, Indexed, LogMeta, SkippedLog, H256, H512};
 
 /// Indexing mode.
 #[derive(Copy, Debug, Default, Deserialize, Clone)]
@@ -34,6 +34,16 @@ pub trait Indexer<T: Sized>: Send + Sync + Debug {
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<T>, LogMeta)>>;
 
+    /// Fetch list of logs between blocks `from` and `to`, inclusive, skipping
+    /// the malformed ones instead of failing the whole range. Returns the
+    /// logs along with the ones that were skipped.
+    async fn fetch_logs_in_range_skipping_malformed(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<(Vec<(Indexed<T>, LogMeta)>, Vec<SkippedLog>)> {
+        Ok((self.fetch_logs_in_range(range).await?, vec![]))
+    }
+
     /// Get the chain's latest block number that has reached finality
     async fn get_finalized_block_number(&self) -> ChainResult<u32>;
 
@@ -44,6 +54,13 @@ pub trait Indexer<T: Sized>: Send + Sync + Debug {
     ) -> ChainResult<Vec<(Indexed<T>, LogMeta)>> {
         Ok(vec![])
     }
+
+    /// Get the hash of the canonical block at `height`, used to detect reorgs
+    /// of the blocks logs were fetched from. Returns `None` if the indexer
+    /// can't tell.
+    async fn get_block_hash(&self, _height: u32) -> ChainResult<Option<H256>> {
+        Ok(None)
+    }
 }
 
 /// Interface for indexing data in sequence.
@@ -55,4 +72,10 @@ pub trait Indexer<T: Sized>: Send + Sync + Debug {
 pub trait SequenceAwareIndexer<T>: Indexer<T> {
     /// Return the latest finalized sequence (if any) and block number
     async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)>;
+
+    /// Return the sequence count as of `block`, or `None` if it can't be queried at
+    /// past blocks. Lets cursors skip ranges of blocks without new logs.
+    async fn sequence_count_at_block(&self, _block: u32) -> ChainResult<Option<u32>> {
+        Ok(None)
+    }
 }
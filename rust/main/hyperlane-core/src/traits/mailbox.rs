@@ -27,6 +27,18 @@ pub trait Mailbox: HyperlaneContract + Send + Sync + Debug {
     /// Fetch the status of a message
     async fn delivered(&self, id: H256) -> ChainResult<bool>;
 
+    /// Fetch how far the delivery of a message has progressed. Chains which
+    /// can see a delivery before it is finalized report it as
+    /// `DeliveryStatus::Unfinalized`; by default a message is only reported
+    /// delivered once `delivered` is.
+    async fn delivery_status(&self, id: H256) -> ChainResult<DeliveryStatus> {
+        Ok(if self.delivered(id).await? {
+            DeliveryStatus::Finalized
+        } else {
+            DeliveryStatus::NotDelivered
+        })
+    }
+
     /// Fetch the current default interchain security module value
     async fn default_ism(&self) -> ChainResult<H256>;
 
@@ -72,6 +84,19 @@ pub trait Mailbox: HyperlaneContract + Send + Sync + Debug {
     fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8>;
 }
 
+/// How far the delivery of a message has progressed on its destination chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    /// The message hasn't been delivered, or the transaction delivering it
+    /// was dropped
+    NotDelivered,
+    /// The message was delivered by a transaction which isn't finalized yet
+    /// and may still be dropped
+    Unfinalized,
+    /// The message was delivered by a finalized transaction
+    Finalized,
+}
+
 /// The result of processing a batch of messages
 #[derive(new, Debug)]
 pub struct BatchResult {
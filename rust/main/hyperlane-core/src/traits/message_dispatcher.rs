@@ -0,0 +1,40 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+
+use crate::{ChainResult, HyperlaneChain, TxOutcome, H256, U256};
+
+/// The result of dispatching a message
+#[derive(Debug, Clone)]
+pub struct DispatchOutcome {
+    /// Id of the dispatched message, if the dispatch transaction executed
+    pub message_id: Option<H256>,
+    /// The dispatch transaction
+    pub tx_outcome: TxOutcome,
+}
+
+/// Interface for dispatching messages through the mailbox from the
+/// configured signer, and paying for their delivery. Allows abstraction over
+/// different chains.
+#[async_trait]
+#[auto_impl(&, Box, Arc)]
+pub trait MessageDispatcher: HyperlaneChain + Send + Sync + Debug {
+    /// Dispatch a message with `body` to `recipient` on the `destination`
+    /// domain, paying the fee quoted by the mailbox's hooks.
+    async fn dispatch(
+        &self,
+        destination: u32,
+        recipient: H256,
+        body: Vec<u8>,
+    ) -> ChainResult<DispatchOutcome>;
+
+    /// Pay the interchain gas paymaster for `gas_amount` of gas to deliver
+    /// the message with id `message_id` to the `destination` domain.
+    async fn pay_for_gas(
+        &self,
+        message_id: H256,
+        destination: u32,
+        gas_amount: U256,
+    ) -> ChainResult<TxOutcome>;
+}
@@ -9,11 +9,16 @@ pub use interchain_gas::*;
 pub use interchain_security_module::*;
 pub use mailbox::*;
 pub use merkle_tree_hook::*;
+pub use message_dispatcher::*;
 pub use multisig_ism::*;
+pub use native_token_transfer::*;
 pub use pending_operation::*;
 pub use provider::*;
 pub use routing_ism::*;
 pub use signing::*;
+pub use token_router::*;
+#[cfg(feature = "async")]
+pub use tx_submitter::*;
 pub use validator_announce::*;
 
 use crate::{FixedPointNumber, H512, U256};
@@ -29,11 +34,16 @@ mod interchain_gas;
 mod interchain_security_module;
 mod mailbox;
 mod merkle_tree_hook;
+mod message_dispatcher;
 mod multisig_ism;
+mod native_token_transfer;
 mod pending_operation;
 mod provider;
 mod routing_ism;
 mod signing;
+mod token_router;
+#[cfg(feature = "async")]
+mod tx_submitter;
 mod validator_announce;
 
 /// The result of a transaction
@@ -0,0 +1,20 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+
+use crate::{ChainResult, HyperlaneChain, TxOutcome, U256};
+
+/// Interface for sending the native token of a chain from the configured
+/// signer to another account. Allows abstraction over different chains.
+#[async_trait]
+#[auto_impl(&, Box, Arc)]
+pub trait NativeTokenTransfer: HyperlaneChain + Send + Sync + Debug {
+    /// The address of the account funds are sent from, formatted in the
+    /// chain's own address format.
+    fn sender_address(&self) -> String;
+
+    /// Send `amount` of the native token, in its lowest denomination, to
+    /// `recipient`, formatted in the chain's own address format.
+    async fn transfer_native(&self, recipient: String, amount: U256) -> ChainResult<TxOutcome>;
+}
@@ -52,6 +52,15 @@ pub trait PendingOperation: Send + Sync + Debug + TryBatchAs<HyperlaneMessage> {
     /// operations when neither of them have a `next_attempt_after`
     fn priority(&self) -> u32;
 
+    /// A higher value means a higher priority, such as the gas payment per
+    /// unit of gas of a message. Operations ready to be attempted are ordered
+    /// by it, so that when queues are backed up the best paying operations go
+    /// first. Operations with the same payment priority are ordered by origin,
+    /// then keep their `priority` (nonce) order.
+    fn payment_priority(&self) -> u64 {
+        0
+    }
+
     /// The domain this originates from.
     fn origin_domain_id(&self) -> u32;
 
@@ -244,6 +253,24 @@ pub enum ReprepareReason {
     #[strum(to_string = "Delivery transaction reverted or reorged")]
     /// Delivery transaction reverted or reorged
     RevertedOrReorged,
+    #[strum(to_string = "Error decorating message delivery")]
+    /// Error decorating message delivery
+    ErrorDecoratingDelivery,
+    #[strum(to_string = "Submission is leased by another relayer")]
+    /// Submission is leased by another relayer
+    LeasedByOtherRelayer,
+    #[strum(to_string = "Error taking the submission lease")]
+    /// Error taking the submission lease
+    ErrorTakingSubmissionLease,
+    #[strum(to_string = "Error dry running ISM verification")]
+    /// Error dry running the ISM's verification of the metadata
+    ErrorVerifyingMetadata,
+    #[strum(to_string = "ISM rejected the metadata")]
+    /// Dry running the ISM's verification of the metadata failed
+    IsmRejectedMetadata,
+    #[strum(to_string = "Message delivery estimated fee exceeds max fee")]
+    /// Message delivery estimated fee exceeds the max fee of a delivery
+    ExceedsMaxDeliveryFee,
 }
 
 #[derive(Display, Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -261,6 +288,9 @@ pub enum ConfirmReason {
     ErrorConfirmingDelivery,
     /// Error storing delivery outcome
     ErrorRecordingProcessSuccess,
+    #[strum(to_string = "Delivered, awaiting finality")]
+    /// Operation was delivered by a transaction which isn't finalized yet
+    AwaitingFinality,
 }
 
 /// Utility fn to calculate the total estimated cost of an operation batch
@@ -328,15 +358,15 @@ impl Ord for QueueOperation {
             // No time means it should come before
             (None, Some(_)) => Less,
             (Some(_), None) => Greater,
-            (None, None) => {
-                if self.origin_domain_id() == other.origin_domain_id() {
-                    // Should execute in order of nonce for the same origin
-                    self.priority().cmp(&other.priority())
-                } else {
-                    // There is no priority between these messages, so arbitrarily use the id
-                    self.id().cmp(&other.id())
-                }
-            }
+            // A higher payment priority should come before, then operations
+            // execute in order of nonce for the same origin. There is no
+            // priority between the rest, so arbitrarily use the id
+            (None, None) => other
+                .payment_priority()
+                .cmp(&self.payment_priority())
+                .then_with(|| self.origin_domain_id().cmp(&other.origin_domain_id()))
+                .then_with(|| self.priority().cmp(&other.priority()))
+                .then_with(|| self.id().cmp(&other.id())),
         }
     }
 }
@@ -4,7 +4,9 @@ use async_trait::async_trait;
 use auto_impl::auto_impl;
 use thiserror::Error;
 
-use crate::{BlockInfo, ChainInfo, ChainResult, HyperlaneChain, TxnInfo, H256, U256};
+use crate::{
+    BlockInfo, ChainInfo, ChainResult, HyperlaneChain, HyperlaneMessage, TxnInfo, H256, H512, U256,
+};
 
 /// Interface for a provider. Allows abstraction over different provider types
 /// for different chains.
@@ -25,11 +27,46 @@ pub trait HyperlaneProvider: HyperlaneChain + Send + Sync + Debug {
     /// Returns whether a contract exists at the provided address
     async fn is_contract(&self, address: &H256) -> ChainResult<bool>;
 
+    /// Returns the hash of the code at the provided address, or None if the
+    /// chain can't cheaply tell when the code at an address changes
+    async fn code_hash(&self, _address: &H256) -> ChainResult<Option<H256>> {
+        Ok(None)
+    }
+
     /// Fetch the balance of the wallet address associated with the chain provider.
     async fn get_balance(&self, address: String) -> ChainResult<U256>;
 
     /// Fetch metrics related to this chain
     async fn get_chain_metrics(&self) -> ChainResult<Option<ChainInfo>>;
+
+    /// Fetch the fee the chain currently charges for posting transaction data
+    /// to its L1, or None if it isn't a rollup exposing it
+    async fn l1_data_fee(&self) -> ChainResult<Option<L1DataFee>> {
+        Ok(None)
+    }
+
+    /// Fetch the metadata the transaction with `hash` passed to the hooks of
+    /// `message` when dispatching it, or None if it can't be read from the
+    /// transaction, e.g. because the transaction didn't call `mailbox`
+    /// directly
+    async fn get_dispatch_hook_metadata(
+        &self,
+        _hash: &H512,
+        _mailbox: &H256,
+        _message: &HyperlaneMessage,
+    ) -> ChainResult<Option<Vec<u8>>> {
+        Ok(None)
+    }
+}
+
+/// The fee a rollup charges for posting transaction data to its L1, along
+/// with its own gas price to compare it to the execution gas of transactions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L1DataFee {
+    /// Fee of a byte of compressed transaction data, in wei
+    pub fee_per_compressed_byte: U256,
+    /// Gas price of the chain, in wei
+    pub gas_price: U256,
 }
 
 /// Errors when querying for provider information.
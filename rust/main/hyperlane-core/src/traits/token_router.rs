@@ -0,0 +1,19 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+
+use crate::{ChainResult, HyperlaneContract, U256};
+
+/// Interface for reading the token balances backing a warp route router.
+/// Allows abstraction over different chains
+#[async_trait]
+#[auto_impl(&, Box, Arc)]
+pub trait TokenRouter: HyperlaneContract + Send + Sync + Debug {
+    /// Balance of the wrapped token locked as collateral by a collateral
+    /// router.
+    async fn collateral_balance(&self) -> ChainResult<U256>;
+
+    /// Total supply of the token minted by a synthetic router.
+    async fn synthetic_supply(&self) -> ChainResult<U256>;
+}
@@ -0,0 +1,269 @@
+//! Transactions are submitted the same way on every chain: their gas is
+//! estimated, they are signed and sent, their inclusion is awaited and the
+//! errors of each step are classified. Chains implement the steps in a
+//! [`TxAdapter`], while retries, resubmissions and fee escalation are
+//! implemented once by [`TxSubmitter`].
+
+use std::{fmt::Debug, time::Duration};
+
+use async_trait::async_trait;
+use tokio::time::sleep;
+use tracing::{debug, warn};
+
+use crate::{ChainCommunicationError, ChainResult, TxOutcome};
+
+/// How an error of a submission step is handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxErrorClass {
+    /// A transient error, e.g. of the connection to the chain. The step is
+    /// retried.
+    Retryable,
+    /// The fees of the transaction are too low for it to be included. It is
+    /// resubmitted with escalated fees.
+    Underpriced,
+    /// The transaction won't be included however often it is submitted, e.g.
+    /// because it reverts.
+    Fatal,
+}
+
+/// The chain specific steps of submitting a transaction
+#[async_trait]
+pub trait TxAdapter: Send + Sync {
+    /// A transaction to be submitted
+    type Tx: Send + Sync;
+    /// A sent transaction, whose inclusion is awaited
+    type Pending: Send + Sync + Debug;
+
+    /// Estimate the gas limit and fees of a transaction, unless already set
+    async fn estimate(&self, tx: &mut Self::Tx) -> ChainResult<()>;
+
+    /// Sign and send a transaction. Adapters of chains with nonces pin the
+    /// nonce of the transaction the first time it is sent, so that it is
+    /// replaced when resubmitted.
+    async fn send(&self, tx: &mut Self::Tx) -> ChainResult<Self::Pending>;
+
+    /// Wait for a sent transaction to be included. None if it wasn't included
+    /// before the adapter stopped waiting.
+    async fn confirm(&self, pending: &Self::Pending) -> ChainResult<Option<TxOutcome>>;
+
+    /// Multiply the fees of a transaction by `multiplier` for it to replace
+    /// the sent one. Returns false if the fees can't be escalated, in which
+    /// case the transaction isn't resubmitted.
+    fn escalate_fee(&self, tx: &mut Self::Tx, multiplier: f64) -> bool;
+
+    /// Classify an error of a submission step
+    fn classify(&self, err: &ChainCommunicationError) -> TxErrorClass;
+}
+
+/// How transactions are retried and resubmitted
+#[derive(Debug, Clone, PartialEq)]
+pub struct TxSubmitterConf {
+    /// How many times a step failing with a retryable error is retried
+    pub max_retries: u32,
+    /// How long to wait before retrying a step
+    pub retry_interval: Duration,
+    /// How many times a transaction is sent at most, including resubmissions
+    pub max_submissions: u32,
+    /// What the fees of a resubmitted transaction are multiplied by. Most
+    /// EVM nodes require at least 1.1 to replace a pending transaction.
+    pub fee_escalation: f64,
+}
+
+impl Default for TxSubmitterConf {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            retry_interval: Duration::from_secs(2),
+            max_submissions: 3,
+            fee_escalation: 1.125,
+        }
+    }
+}
+
+/// Submits transactions through the steps of a [`TxAdapter`]
+#[async_trait]
+pub trait TxSubmitter: TxAdapter {
+    /// Submit a transaction and wait for its inclusion. A transaction that
+    /// isn't included in time or is underpriced is resubmitted with escalated
+    /// fees, until `conf.max_submissions` is reached.
+    async fn submit(&self, mut tx: Self::Tx, conf: &TxSubmitterConf) -> ChainResult<TxOutcome> {
+        let mut retries = 0;
+        while let Err(err) = self.estimate(&mut tx).await {
+            retry(self, err, &mut retries, conf).await?;
+        }
+
+        let max_submissions = conf.max_submissions.max(1);
+        let mut sent = vec![];
+        for submission in 1..=max_submissions {
+            let mut retries = 0;
+            let pending = loop {
+                match self.send(&mut tx).await {
+                    Ok(pending) => break Some(pending),
+                    Err(err) if self.classify(&err) == TxErrorClass::Underpriced => {
+                        debug!(submission, error=?err, "Transaction underpriced");
+                        break None;
+                    }
+                    Err(err) => {
+                        if let Err(err) = retry(self, err, &mut retries, conf).await {
+                            // Resending fails once a previous submission is included
+                            return match confirm_any(self, &sent).await {
+                                Some(outcome) => Ok(outcome),
+                                None => Err(err),
+                            };
+                        }
+                    }
+                }
+            };
+
+            if let Some(pending) = pending {
+                let mut retries = 0;
+                loop {
+                    match self.confirm(&pending).await {
+                        Ok(Some(outcome)) => return Ok(outcome),
+                        Ok(None) => break,
+                        Err(err) => retry(self, err, &mut retries, conf).await?,
+                    }
+                }
+                warn!(submission, ?pending, "Transaction not included in time");
+                sent.push(pending);
+            }
+
+            if submission == max_submissions || !self.escalate_fee(&mut tx, conf.fee_escalation) {
+                break;
+            }
+            debug!(submission, "Resubmitting transaction with escalated fees");
+        }
+        Err(ChainCommunicationError::TransactionTimeout())
+    }
+}
+
+impl<A: TxAdapter> TxSubmitter for A {}
+
+/// Returns the error if it isn't retryable or the step was retried too many
+/// times, otherwise waits before the step is retried
+async fn retry<A: TxAdapter + ?Sized>(
+    adapter: &A,
+    err: ChainCommunicationError,
+    retries: &mut u32,
+    conf: &TxSubmitterConf,
+) -> ChainResult<()> {
+    if adapter.classify(&err) != TxErrorClass::Retryable || *retries >= conf.max_retries {
+        return Err(err);
+    }
+    *retries += 1;
+    warn!(retries = *retries, error=?err, "Retrying transaction submission step");
+    sleep(conf.retry_interval).await;
+    Ok(())
+}
+
+/// The outcome of the first of the sent transactions found to be included
+async fn confirm_any<A: TxAdapter + ?Sized>(adapter: &A, sent: &[A::Pending]) -> Option<TxOutcome> {
+    for pending in sent {
+        if let Ok(Some(outcome)) = adapter.confirm(pending).await {
+            return Some(outcome);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use crate::{FixedPointNumber, H512, U256};
+
+    use super::*;
+
+    /// Includes transactions paying at least `min_fee`
+    #[derive(Default)]
+    struct TestAdapter {
+        min_fee: u64,
+        connection_errors: Mutex<u32>,
+        sent: Mutex<Vec<u64>>,
+    }
+
+    #[async_trait]
+    impl TxAdapter for TestAdapter {
+        type Tx = u64;
+        type Pending = u64;
+
+        async fn estimate(&self, _tx: &mut u64) -> ChainResult<()> {
+            Ok(())
+        }
+
+        async fn send(&self, tx: &mut u64) -> ChainResult<u64> {
+            let mut connection_errors = self.connection_errors.lock().unwrap();
+            if *connection_errors > 0 {
+                *connection_errors -= 1;
+                return Err(ChainCommunicationError::from_other_str("connection"));
+            }
+            self.sent.lock().unwrap().push(*tx);
+            Ok(*tx)
+        }
+
+        async fn confirm(&self, fee: &u64) -> ChainResult<Option<TxOutcome>> {
+            Ok((*fee >= self.min_fee).then(|| TxOutcome {
+                transaction_id: H512::from_low_u64_be(*fee),
+                executed: true,
+                gas_used: U256::zero(),
+                gas_price: FixedPointNumber::zero(),
+            }))
+        }
+
+        fn escalate_fee(&self, tx: &mut u64, multiplier: f64) -> bool {
+            *tx = (*tx as f64 * multiplier) as u64;
+            true
+        }
+
+        fn classify(&self, err: &ChainCommunicationError) -> TxErrorClass {
+            match err {
+                ChainCommunicationError::ContractError(_) => TxErrorClass::Retryable,
+                _ => TxErrorClass::Fatal,
+            }
+        }
+    }
+
+    fn submit(adapter: &TestAdapter, fee: u64) -> ChainResult<TxOutcome> {
+        let conf = TxSubmitterConf {
+            retry_interval: Duration::ZERO,
+            fee_escalation: 2.,
+            ..Default::default()
+        };
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(adapter.submit(fee, &conf))
+    }
+
+    #[test]
+    fn test_submit_escalates_fees() {
+        let adapter = TestAdapter {
+            min_fee: 30,
+            connection_errors: Mutex::new(2),
+            ..Default::default()
+        };
+        let outcome = submit(&adapter, 10).unwrap();
+        assert_eq!(outcome.transaction_id, H512::from_low_u64_be(40));
+        assert_eq!(*adapter.sent.lock().unwrap(), vec![10, 20, 40]);
+
+        // Not included after the maximum number of submissions
+        let adapter = TestAdapter {
+            min_fee: 100,
+            ..Default::default()
+        };
+        assert!(matches!(
+            submit(&adapter, 10),
+            Err(ChainCommunicationError::TransactionTimeout())
+        ));
+        assert_eq!(adapter.sent.lock().unwrap().len(), 3);
+
+        // Connection errors are only retried so many times
+        let adapter = TestAdapter {
+            connection_errors: Mutex::new(4),
+            ..Default::default()
+        };
+        assert!(submit(&adapter, 10).is_err());
+        assert!(adapter.sent.lock().unwrap().is_empty());
+    }
+}
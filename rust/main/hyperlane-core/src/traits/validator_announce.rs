@@ -3,7 +3,10 @@ use std::fmt::Debug;
 use async_trait::async_trait;
 use auto_impl::auto_impl;
 
-use crate::{Announcement, ChainResult, HyperlaneContract, SignedType, TxOutcome, H256, U256};
+use crate::{
+    Announcement, ChainCommunicationError, ChainResult, HyperlaneContract, SignedType, TxOutcome,
+    H256, U256,
+};
 
 /// Interface for the ValidatorAnnounce chain contract. Allows abstraction over
 /// different chains
@@ -16,6 +19,13 @@ pub trait ValidatorAnnounce: HyperlaneContract + Send + Sync + Debug {
         validators: &[H256],
     ) -> ChainResult<Vec<Vec<String>>>;
 
+    /// Returns the validators that have announced a storage location
+    async fn get_announced_validators(&self) -> ChainResult<Vec<H256>> {
+        Err(ChainCommunicationError::from_other_str(
+            "Listing announced validators is not supported on this chain",
+        ))
+    }
+
     /// Announce a storage location for a validator
     async fn announce(&self, announcement: SignedType<Announcement>) -> ChainResult<TxOutcome>;
 
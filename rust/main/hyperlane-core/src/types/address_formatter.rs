@@ -0,0 +1,102 @@
+use bech32::{ToBase32, Variant};
+
+use crate::{HyperlaneDomainProtocol, H256};
+
+/// Formats 32 byte addresses the way the chains they are on display them
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AddressFormatter {
+    /// 0x prefixed hex of the trailing `bytes` bytes of the address
+    Hex {
+        /// How many bytes long native addresses are
+        bytes: usize,
+    },
+    /// Bech32 with a human readable prefix of the trailing `bytes` bytes of
+    /// the address
+    Bech32 {
+        /// The human readable prefix
+        prefix: String,
+        /// How many bytes long native addresses are
+        bytes: usize,
+    },
+    /// Base58 of the address
+    Base58,
+}
+
+impl AddressFormatter {
+    /// The formatter of the chains of a protocol. The bech32 prefix of Cosmos
+    /// chains is per chain, so their addresses are formatted as hex unless
+    /// built with [`AddressFormatter::Bech32`].
+    pub fn for_protocol(protocol: HyperlaneDomainProtocol) -> Self {
+        use HyperlaneDomainProtocol::*;
+        match protocol {
+            Ethereum => Self::Hex { bytes: 20 },
+            Sealevel => Self::Base58,
+            Fuel | Cosmos | Starknet | Substrate | Aptos | Ton => Self::Hex { bytes: 32 },
+        }
+    }
+
+    fn trailing_bytes(address: &H256, bytes: usize) -> &[u8] {
+        let address = address.as_bytes();
+        &address[address.len().saturating_sub(bytes)..]
+    }
+
+    /// Format an address. Addresses which can't be bech32 encoded are
+    /// formatted as hex.
+    pub fn format(&self, address: H256) -> String {
+        match self {
+            Self::Hex { bytes } => {
+                format!("0x{}", hex::encode(Self::trailing_bytes(&address, *bytes)))
+            }
+            Self::Bech32 { prefix, bytes } => {
+                let data = Self::trailing_bytes(&address, *bytes).to_base32();
+                bech32::encode(prefix, data, Variant::Bech32)
+                    .unwrap_or_else(|_| format!("{address:?}"))
+            }
+            Self::Base58 => bs58::encode(address.as_bytes()).into_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_format_address() {
+        let address =
+            H256::from_str("0x000000000000000000000000b5a0b6ed8e2a4a1d3ed4a4e0b3a6c0a4d8d8c7e1")
+                .unwrap();
+        assert_eq!(
+            AddressFormatter::for_protocol(HyperlaneDomainProtocol::Ethereum).format(address),
+            "0xb5a0b6ed8e2a4a1d3ed4a4e0b3a6c0a4d8d8c7e1"
+        );
+        assert_eq!(
+            AddressFormatter::for_protocol(HyperlaneDomainProtocol::Fuel).format(address),
+            format!("{address:?}")
+        );
+        assert_eq!(
+            AddressFormatter::Base58.format(H256::zero()),
+            "11111111111111111111111111111111"
+        );
+
+        // Example from the hyperlane-cosmos address tests
+        let digest =
+            H256::from_str("0x0d8a53233a016a05f234d1cd105c3a3884c7ecde176b85bde7b423a05cd45b21")
+                .unwrap();
+        let bech32 = AddressFormatter::Bech32 {
+            prefix: "dual".to_owned(),
+            bytes: 32,
+        };
+        assert_eq!(
+            bech32.format(digest),
+            "dual1pk99xge6q94qtu3568x3qhp68zzv0mx7za4ct008ks36qhx5tvss3qawfh"
+        );
+        let invalid_prefix = AddressFormatter::Bech32 {
+            prefix: "".to_owned(),
+            bytes: 32,
+        };
+        assert_eq!(invalid_prefix.format(digest), format!("{digest:?}"));
+    }
+}
@@ -0,0 +1,224 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::{HyperlaneDomainProtocol, U256};
+
+/// The denomination of an amount of a native token, by how many decimals its
+/// smallest unit is of a whole token
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Denomination {
+    decimals: u8,
+}
+
+impl Denomination {
+    /// Wei, the smallest unit of ether and of most EVM native tokens
+    pub const WEI: Self = Self::new(18);
+    /// Micro units of cosmos native tokens, e.g. uatom
+    pub const MICRO: Self = Self::new(6);
+    /// Lamports, the smallest unit of SOL
+    pub const LAMPORTS: Self = Self::new(9);
+    /// Octas, the smallest unit of APT
+    pub const OCTAS: Self = Self::new(8);
+    /// Nanotons, the smallest unit of TON
+    pub const NANOTONS: Self = Self::new(9);
+
+    /// A denomination whose smallest unit is `10^-decimals` of a whole token
+    pub const fn new(decimals: u8) -> Self {
+        Self { decimals }
+    }
+
+    /// The denomination each protocol typically uses for the smallest unit of
+    /// its native token
+    pub const fn of_protocol(protocol: HyperlaneDomainProtocol) -> Self {
+        match protocol {
+            HyperlaneDomainProtocol::Cosmos => Self::MICRO,
+            HyperlaneDomainProtocol::Sealevel => Self::LAMPORTS,
+            HyperlaneDomainProtocol::Aptos => Self::OCTAS,
+            HyperlaneDomainProtocol::Ton => Self::NANOTONS,
+            _ => Self::WEI,
+        }
+    }
+
+    /// How many decimals the smallest unit is of a whole token
+    pub const fn decimals(&self) -> u8 {
+        self.decimals
+    }
+}
+
+/// Errors of arithmetic on native amounts
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AmountError {
+    /// The amounts are in different denominations and must be converted first
+    #[error("Amounts have different denominations: {0:?} and {1:?}")]
+    DenominationMismatch(Denomination, Denomination),
+    /// The result does not fit a U256
+    #[error("Amount overflowed")]
+    Overflow,
+    /// The result is negative
+    #[error("Amount underflowed")]
+    Underflow,
+}
+
+/// An amount of a native token in the smallest unit of its denomination.
+/// Arithmetic is checked and only allowed between amounts of the same
+/// denomination, so that amounts of chains with different decimals can't be
+/// mixed up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NativeAmount {
+    value: U256,
+    denomination: Denomination,
+}
+
+impl NativeAmount {
+    /// An amount of `value` smallest units of `denomination`
+    pub const fn new(value: U256, denomination: Denomination) -> Self {
+        Self {
+            value,
+            denomination,
+        }
+    }
+
+    /// An amount of the native token of `protocol`, in its smallest unit
+    pub const fn of_protocol(value: U256, protocol: HyperlaneDomainProtocol) -> Self {
+        Self::new(value, Denomination::of_protocol(protocol))
+    }
+
+    /// The amount in the smallest unit of its denomination
+    pub const fn value(&self) -> U256 {
+        self.value
+    }
+
+    /// The denomination of the amount
+    pub const fn denomination(&self) -> Denomination {
+        self.denomination
+    }
+
+    /// Add `rhs`, which must be of the same denomination
+    pub fn checked_add(self, rhs: Self) -> Result<Self, AmountError> {
+        self.ensure_same_denomination(&rhs)?;
+        let value = self
+            .value
+            .checked_add(rhs.value)
+            .ok_or(AmountError::Overflow)?;
+        Ok(Self::new(value, self.denomination))
+    }
+
+    /// Subtract `rhs`, which must be of the same denomination
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, AmountError> {
+        self.ensure_same_denomination(&rhs)?;
+        let value = self
+            .value
+            .checked_sub(rhs.value)
+            .ok_or(AmountError::Underflow)?;
+        Ok(Self::new(value, self.denomination))
+    }
+
+    /// Multiply by a unitless `factor`, e.g. the gas a price is paid for
+    pub fn checked_mul(self, factor: U256) -> Result<Self, AmountError> {
+        let value = self
+            .value
+            .checked_mul(factor)
+            .ok_or(AmountError::Overflow)?;
+        Ok(Self::new(value, self.denomination))
+    }
+
+    /// Compare with `rhs`, which must be of the same denomination
+    pub fn checked_cmp(&self, rhs: &Self) -> Result<Ordering, AmountError> {
+        self.ensure_same_denomination(rhs)?;
+        Ok(self.value.cmp(&rhs.value))
+    }
+
+    /// The same amount in `denomination`. Converting to fewer decimals rounds
+    /// down.
+    pub fn convert(self, denomination: Denomination) -> Result<Self, AmountError> {
+        let (from, to) = (self.denomination.decimals, denomination.decimals);
+        let value = match from.cmp(&to) {
+            Ordering::Equal => self.value,
+            Ordering::Less => self
+                .value
+                .checked_mul(U256::exp10((to - from).into()))
+                .ok_or(AmountError::Overflow)?,
+            Ordering::Greater => self.value / U256::exp10((from - to).into()),
+        };
+        Ok(Self::new(value, denomination))
+    }
+
+    /// The amount in whole tokens, which may lose precision
+    #[cfg(feature = "float")]
+    pub fn to_whole_f64(&self) -> f64 {
+        self.value.to_f64_lossy() / 10f64.powi(self.denomination.decimals.into())
+    }
+
+    fn ensure_same_denomination(&self, rhs: &Self) -> Result<(), AmountError> {
+        if self.denomination == rhs.denomination {
+            Ok(())
+        } else {
+            Err(AmountError::DenominationMismatch(
+                self.denomination,
+                rhs.denomination,
+            ))
+        }
+    }
+}
+
+impl fmt::Display for NativeAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (decimals: {})",
+            self.value, self.denomination.decimals
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic_requires_same_denomination() {
+        let wei = NativeAmount::new(1_000.into(), Denomination::WEI);
+        let uatom = NativeAmount::new(1_000.into(), Denomination::MICRO);
+
+        assert_eq!(
+            wei.checked_add(wei).unwrap(),
+            NativeAmount::new(2_000.into(), Denomination::WEI)
+        );
+        assert_eq!(
+            wei.checked_add(uatom),
+            Err(AmountError::DenominationMismatch(
+                Denomination::WEI,
+                Denomination::MICRO
+            ))
+        );
+        assert!(wei.checked_cmp(&uatom).is_err());
+        assert_eq!(
+            uatom.checked_sub(uatom.checked_mul(2.into()).unwrap()),
+            Err(AmountError::Underflow)
+        );
+        assert_eq!(
+            NativeAmount::new(U256::MAX, Denomination::WEI).checked_add(wei),
+            Err(AmountError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_convert() {
+        let uatom = NativeAmount::new(1_500_000.into(), Denomination::MICRO);
+        let wei = uatom.convert(Denomination::WEI).unwrap();
+        assert_eq!(wei.value(), U256::exp10(18) * 3 / 2);
+        assert_eq!(wei.convert(Denomination::MICRO).unwrap(), uatom);
+        // Converting to fewer decimals rounds down
+        assert_eq!(
+            NativeAmount::new(999.into(), Denomination::LAMPORTS)
+                .convert(Denomination::MICRO)
+                .unwrap()
+                .value(),
+            U256::zero()
+        );
+        assert_eq!(
+            NativeAmount::new(U256::MAX, Denomination::MICRO).convert(Denomination::WEI),
+            Err(AmountError::Overflow)
+        );
+    }
+}
@@ -50,3 +50,13 @@ impl Signable for Announcement {
 
 /// An announcement that has been signed.
 pub type SignedAnnouncement = SignedType<Announcement>;
+
+/// An announcement recorded by a validator announce contract, as indexed from
+/// its events
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ValidatorAnnouncement {
+    /// The validator address
+    pub validator: H256,
+    /// The location of signed checkpoints
+    pub storage_location: String,
+}
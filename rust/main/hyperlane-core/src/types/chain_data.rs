@@ -21,6 +21,11 @@ pub struct ChainInfo {
     /// The current gas price, in the lowest denomination (e.g. wei)
     /// Unless the chain implements an EIP-1559 style tx fee mechanism, this field will be `None`
     pub min_gas_price: Option<U256>,
+    /// The latest finalized block number, if the chain is configured to read it
+    /// from the node. Otherwise the finalized block is the latest block minus
+    /// the reorg period.
+    #[new(default)]
+    pub finalized_block_number: Option<u64>,
 }
 
 /// Information about a given transaction in the chain.
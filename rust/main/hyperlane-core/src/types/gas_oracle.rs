@@ -0,0 +1,87 @@
+//! Gas oracle configuration of interchain gas paymasters, as indexed from the
+//! events of the paymasters and of their storage gas oracles.
+
+use crate::{H256, U256};
+
+/// The scale of the token exchange rates of gas oracles, i.e. the rate of
+/// remote tokens worth as much as local ones
+pub const TOKEN_EXCHANGE_RATE_SCALE: u64 = 10_000_000_000;
+
+/// An update of how an interchain gas paymaster quotes the gas of a remote
+/// domain
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum GasOracleConfigUpdate {
+    /// The paymaster set the gas oracle and gas overhead of a remote domain
+    DestinationGasConfig {
+        /// The remote domain
+        remote_domain: u32,
+        /// The gas oracle quoting the gas of the remote domain
+        gas_oracle: H256,
+        /// The destination gas added to the gas limit of messages
+        gas_overhead: U256,
+    },
+    /// A storage gas oracle set the gas data of a remote domain
+    RemoteGasData {
+        /// The gas oracle which set the data
+        gas_oracle: H256,
+        /// The remote domain
+        remote_domain: u32,
+        /// The exchange rate of the remote token quoted in the local token,
+        /// scaled by `TOKEN_EXCHANGE_RATE_SCALE`
+        token_exchange_rate: U256,
+        /// The gas price on the remote domain
+        gas_price: U256,
+    },
+}
+
+impl GasOracleConfigUpdate {
+    /// The remote domain the update is for
+    pub fn remote_domain(&self) -> u32 {
+        match self {
+            Self::DestinationGasConfig { remote_domain, .. }
+            | Self::RemoteGasData { remote_domain, .. } => *remote_domain,
+        }
+    }
+}
+
+/// The latest gas oracle configuration of an interchain gas paymaster for a
+/// remote domain
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct GasOracleConfig {
+    /// The gas oracle quoting the gas of the remote domain
+    pub gas_oracle: H256,
+    /// The destination gas added to the gas limit of messages
+    pub gas_overhead: U256,
+    /// The exchange rate of the remote token quoted in the local token,
+    /// scaled by `TOKEN_EXCHANGE_RATE_SCALE`
+    pub token_exchange_rate: U256,
+    /// The gas price on the remote domain
+    pub gas_price: U256,
+}
+
+impl GasOracleConfig {
+    /// The payment in local tokens the paymaster requires for `gas_limit`
+    /// remote gas, as `quoteGasPayment` computes it on chain
+    pub fn quote_gas_payment(&self, gas_limit: U256) -> U256 {
+        gas_limit
+            .saturating_mul(self.gas_price)
+            .saturating_mul(self.token_exchange_rate)
+            / U256::from(TOKEN_EXCHANGE_RATE_SCALE)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_quote_gas_payment() {
+        let config = GasOracleConfig {
+            gas_price: 10.into(),
+            // Remote tokens are worth half as much as local ones
+            token_exchange_rate: (TOKEN_EXCHANGE_RATE_SCALE / 2).into(),
+            ..Default::default()
+        };
+        assert_eq!(config.quote_gas_payment(1000.into()), 5000.into());
+    }
+}
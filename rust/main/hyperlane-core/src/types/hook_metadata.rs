@@ -0,0 +1,163 @@
+use crate::{Decode, Encode, HyperlaneProtocolError, H160, U256};
+
+/// Length of standard hook metadata without custom metadata: a 2 byte
+/// variant, a 32 byte msg value, a 32 byte gas limit and a 20 byte refund
+/// address.
+const STANDARD_HOOK_METADATA_PREFIX_LEN: usize = 2 + 32 + 32 + 20;
+
+/// Metadata passed to the hooks of a dispatch, in the format of the
+/// `StandardHookMetadata` library of the Solidity contracts.
+///
+/// Built with the `with_*` methods, e.g.
+/// `StandardHookMetadata::default().with_gas_limit(gas_limit)`.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct StandardHookMetadata {
+    /// Value sent along with the dispatch to pay the hooks
+    pub msg_value: U256,
+    /// Gas limit of the delivery on the destination, e.g. for the IGP to
+    /// quote
+    pub gas_limit: U256,
+    /// Address to refund overpayments of the hooks to
+    pub refund_address: H160,
+    /// Metadata for custom hooks
+    pub custom_metadata: Vec<u8>,
+}
+
+impl StandardHookMetadata {
+    /// The only variant of standard hook metadata
+    pub const VARIANT: u16 = 1;
+
+    /// Set the value sent along with the dispatch
+    pub fn with_msg_value(self, msg_value: U256) -> Self {
+        Self { msg_value, ..self }
+    }
+
+    /// Set the gas limit of the delivery on the destination
+    pub fn with_gas_limit(self, gas_limit: U256) -> Self {
+        Self { gas_limit, ..self }
+    }
+
+    /// Set the address overpayments are refunded to
+    pub fn with_refund_address(self, refund_address: H160) -> Self {
+        Self {
+            refund_address,
+            ..self
+        }
+    }
+
+    /// Set the metadata for custom hooks
+    pub fn with_custom_metadata(self, custom_metadata: Vec<u8>) -> Self {
+        Self {
+            custom_metadata,
+            ..self
+        }
+    }
+}
+
+impl Encode for StandardHookMetadata {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: std::io::Write,
+    {
+        let mut buf = [0u8; 32];
+        writer.write_all(&Self::VARIANT.to_be_bytes())?;
+        self.msg_value.to_big_endian(&mut buf);
+        writer.write_all(&buf)?;
+        self.gas_limit.to_big_endian(&mut buf);
+        writer.write_all(&buf)?;
+        writer.write_all(self.refund_address.as_ref())?;
+        writer.write_all(&self.custom_metadata)?;
+        Ok(STANDARD_HOOK_METADATA_PREFIX_LEN + self.custom_metadata.len())
+    }
+}
+
+impl Decode for StandardHookMetadata {
+    /// Hooks fall back to defaults for metadata too short to contain a field,
+    /// but this only reads metadata containing all fields.
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: std::io::Read,
+    {
+        let mut variant = [0u8; 2];
+        reader.read_exact(&mut variant)?;
+        let variant = u16::from_be_bytes(variant);
+        if variant != Self::VARIANT {
+            return Err(HyperlaneProtocolError::UnsupportedHookMetadataVariant(
+                variant,
+            ));
+        }
+
+        let mut msg_value = [0u8; 32];
+        reader.read_exact(&mut msg_value)?;
+
+        let mut gas_limit = [0u8; 32];
+        reader.read_exact(&mut gas_limit)?;
+
+        let mut refund_address = H160::zero();
+        reader.read_exact(refund_address.as_mut())?;
+
+        let mut custom_metadata = vec![];
+        reader.read_to_end(&mut custom_metadata)?;
+
+        Ok(Self {
+            msg_value: U256::from_big_endian(&msg_value),
+            gas_limit: U256::from_big_endian(&gas_limit),
+            refund_address,
+            custom_metadata,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let metadata = StandardHookMetadata::default()
+            .with_msg_value(U256::exp10(15))
+            .with_gas_limit(300_000.into())
+            .with_refund_address(H160::repeat_byte(0xab))
+            .with_custom_metadata(b"custom".to_vec());
+
+        let encoded = metadata.to_vec();
+        assert_eq!(encoded.len(), STANDARD_HOOK_METADATA_PREFIX_LEN + 6);
+        assert_eq!(&encoded[..2], &[0, 1]);
+        assert_eq!(
+            StandardHookMetadata::read_from(&mut encoded.as_slice()).unwrap(),
+            metadata
+        );
+
+        let empty = StandardHookMetadata::default();
+        assert_eq!(
+            StandardHookMetadata::read_from(&mut empty.to_vec().as_slice()).unwrap(),
+            empty
+        );
+    }
+
+    #[test]
+    fn test_matches_solidity_encoding() {
+        // StandardHookMetadata.formatMetadata(0, 50_000, 0x00..01, "")
+        let encoded = hex::decode(
+            "0001\
+            0000000000000000000000000000000000000000000000000000000000000000\
+            000000000000000000000000000000000000000000000000000000000000c350\
+            0000000000000000000000000000000000000001",
+        )
+        .unwrap();
+        let metadata = StandardHookMetadata::read_from(&mut encoded.as_slice()).unwrap();
+        assert_eq!(metadata.gas_limit, 50_000.into());
+        assert_eq!(metadata.refund_address, H160::from_low_u64_be(1));
+        assert_eq!(metadata.to_vec(), encoded);
+    }
+
+    #[test]
+    fn test_read_invalid_metadata() {
+        assert!(matches!(
+            StandardHookMetadata::read_from(&mut [0u8, 2].as_slice()),
+            Err(HyperlaneProtocolError::UnsupportedHookMetadataVariant(2))
+        ));
+        // Too short to contain the gas limit
+        assert!(StandardHookMetadata::read_from(&mut [0u8, 1, 0, 0].as_slice()).is_err());
+    }
+}
@@ -1,6 +1,9 @@
 use derive_new::new;
 
-use crate::{HyperlaneMessage, InterchainGasPayment, MerkleTreeInsertion, Sequenced, H256};
+use crate::{
+    HyperlaneMessage, IndexError, InterchainGasPayment, LogMeta, MerkleTreeInsertion, Sequenced,
+    TransferRemote, ValidatorAnnouncement, H256,
+};
 
 /// Wrapper struct that adds indexing information to a type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, new)]
@@ -20,21 +23,31 @@ pub struct SequenceIndexed<T> {
 }
 
 impl<T> TryFrom<Indexed<T>> for SequenceIndexed<T> {
-    type Error = eyre::Report;
+    type Error = IndexError;
 
     fn try_from(value: Indexed<T>) -> Result<Self, Self::Error> {
         match value.sequence {
             Some(sequence) => Ok(SequenceIndexed::new(value.inner, sequence)),
-            None => eyre::bail!("Missing indexing sequence"),
+            None => Err(IndexError::MissingSequence),
         }
     }
 }
 
+/// A log that was found while indexing but skipped because it's malformed,
+/// which querying it again won't fix
+#[derive(Debug, Clone, PartialEq, Eq, new)]
+pub struct SkippedLog {
+    /// The sequence of the log, if it could still be read
+    pub sequence: Option<u32>,
+    /// The metadata of the log
+    pub meta: LogMeta,
+}
+
 /// Convert a vector of `Indexed` values to a vector of `SequenceIndexed` values
 /// so that if any `Option` is `None`, the conversion will fail
 pub fn indexed_to_sequence_indexed_array<T, U>(
     indexed_array: Vec<(Indexed<T>, U)>,
-) -> Result<Vec<(SequenceIndexed<T>, U)>, eyre::Report> {
+) -> Result<Vec<(SequenceIndexed<T>, U)>, IndexError> {
     indexed_array
         .into_iter()
         .map(|(item, meta)| SequenceIndexed::<T>::try_from(item).map(|si| (si, meta)))
@@ -85,3 +98,17 @@ impl From<InterchainGasPayment> for Indexed<InterchainGasPayment> {
         Indexed::new(value)
     }
 }
+
+// The transfers of a router aren't sequenced, since other senders dispatch
+// through the same mailbox
+impl From<TransferRemote> for Indexed<TransferRemote> {
+    fn from(value: TransferRemote) -> Self {
+        Indexed::new(value)
+    }
+}
+
+impl From<ValidatorAnnouncement> for Indexed<ValidatorAnnouncement> {
+    fn from(value: ValidatorAnnouncement) -> Self {
+        Indexed::new(value)
+    }
+}
@@ -0,0 +1,118 @@
+//! Types of legacy (v2) deployments, from before merkle tree hooks. Their
+//! mailbox holds the merkle tree, and their validators sign checkpoints of it
+//! without a message id, which legacy multisig ISMs verify.
+
+use serde::{Deserialize, Serialize};
+use sha3::{digest::Update, Digest, Keccak256};
+
+use crate::{
+    utils::domain_hash, Checkpoint, MultisigSignedCheckpointError, Signable, Signature, SignedType,
+    H256,
+};
+
+/// A checkpoint of the merkle tree of a legacy mailbox
+#[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+pub struct LegacyCheckpoint {
+    /// The mailbox address
+    pub mailbox_address: H256,
+    /// The mailbox domain
+    pub mailbox_domain: u32,
+    /// The checkpointed root
+    pub root: H256,
+    /// The index of the checkpoint
+    pub index: u32,
+}
+
+impl From<LegacyCheckpoint> for Checkpoint {
+    fn from(checkpoint: LegacyCheckpoint) -> Self {
+        Checkpoint {
+            // The mailbox is the merkle tree of legacy deployments
+            merkle_tree_hook_address: checkpoint.mailbox_address,
+            mailbox_domain: checkpoint.mailbox_domain,
+            root: checkpoint.root,
+            index: checkpoint.index,
+        }
+    }
+}
+
+impl Signable for LegacyCheckpoint {
+    /// A hash of the checkpoint contents.
+    /// The EIP-191 compliant version of this hash is signed by validators.
+    fn signing_hash(&self) -> H256 {
+        // sign:
+        // domain_hash(mailbox_address, mailbox_domain) || root || index (as u32)
+        H256::from_slice(
+            Keccak256::new()
+                .chain(domain_hash(self.mailbox_address, self.mailbox_domain))
+                .chain(self.root)
+                .chain(self.index.to_be_bytes())
+                .finalize()
+                .as_slice(),
+        )
+    }
+}
+
+/// Signed legacy checkpoint
+pub type SignedLegacyCheckpoint = SignedType<LegacyCheckpoint>;
+
+/// A legacy checkpoint and multiple signatures
+#[derive(Clone, Debug)]
+pub struct MultisigSignedLegacyCheckpoint {
+    /// The checkpoint
+    pub checkpoint: LegacyCheckpoint,
+    /// Signatures over the checkpoint ordered by validator index, length == threshold
+    pub signatures: Vec<Signature>,
+}
+
+impl TryFrom<&mut Vec<SignedLegacyCheckpoint>> for MultisigSignedLegacyCheckpoint {
+    type Error = MultisigSignedCheckpointError;
+
+    /// Given multiple signed legacy checkpoints, create a
+    /// MultisigSignedLegacyCheckpoint
+    fn try_from(signed_checkpoints: &mut Vec<SignedLegacyCheckpoint>) -> Result<Self, Self::Error> {
+        let Some(first) = signed_checkpoints.first() else {
+            return Err(MultisigSignedCheckpointError::EmptySignatures());
+        };
+        let checkpoint = first.value;
+        if !signed_checkpoints.iter().all(|c| checkpoint == c.value) {
+            return Err(MultisigSignedCheckpointError::InconsistentCheckpoints());
+        }
+
+        let signatures = signed_checkpoints.iter().map(|c| c.signature).collect();
+
+        Ok(MultisigSignedLegacyCheckpoint {
+            checkpoint,
+            signatures,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CheckpointWithMessageId;
+
+    #[test]
+    fn test_legacy_checkpoint_signs_without_message_id() {
+        let legacy = LegacyCheckpoint {
+            mailbox_address: H256::from_low_u64_be(1),
+            mailbox_domain: 1,
+            root: H256::from_low_u64_be(2),
+            index: 3,
+        };
+        let with_id = CheckpointWithMessageId {
+            checkpoint: legacy.into(),
+            message_id: H256::zero(),
+        };
+        let expected = H256::from_slice(
+            Keccak256::new()
+                .chain(domain_hash(legacy.mailbox_address, legacy.mailbox_domain))
+                .chain(legacy.root)
+                .chain(3u32.to_be_bytes())
+                .finalize()
+                .as_slice(),
+        );
+        assert_eq!(legacy.signing_hash(), expected);
+        assert_ne!(legacy.signing_hash(), with_id.signing_hash());
+    }
+}
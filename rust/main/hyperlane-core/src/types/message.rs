@@ -3,9 +3,9 @@ use sha3::{digest::Update, Digest, Keccak256};
 use std::fmt::{Debug, Display, Formatter};
 
 use crate::utils::{fmt_address_for_domain, fmt_domain};
-use crate::{Decode, Encode, HyperlaneProtocolError, H256};
+use crate::{Decode, Encode, HyperlaneProtocolError, MessageVersion, H256};
 
-const HYPERLANE_MESSAGE_PREFIX_LEN: usize = 77;
+pub(super) const HYPERLANE_MESSAGE_PREFIX_LEN: usize = 77;
 
 /// A message ID that has been delivered to the destination
 pub type Delivery = H256;
@@ -44,7 +44,7 @@ impl Default for HyperlaneMessage {
     fn default() -> Self {
         Self {
             // Use version 3 now that Hyperlane V3 is the default
-            version: 3,
+            version: MessageVersion::CURRENT.into(),
             nonce: 0,
             origin: 0,
             sender: H256::zero(),
@@ -83,30 +83,125 @@ impl Display for HyperlaneMessage {
     }
 }
 
-impl From<RawHyperlaneMessage> for HyperlaneMessage {
-    fn from(m: RawHyperlaneMessage) -> Self {
-        HyperlaneMessage::from(&m)
+impl TryFrom<RawHyperlaneMessage> for HyperlaneMessage {
+    type Error = HyperlaneProtocolError;
+
+    fn try_from(m: RawHyperlaneMessage) -> Result<Self, Self::Error> {
+        HyperlaneMessage::try_from(&m)
     }
 }
 
-impl From<&RawHyperlaneMessage> for HyperlaneMessage {
-    fn from(m: &RawHyperlaneMessage) -> Self {
-        let version = m[0];
-        let nonce: [u8; 4] = m[1..5].try_into().unwrap();
-        let origin: [u8; 4] = m[5..9].try_into().unwrap();
-        let sender: [u8; 32] = m[9..41].try_into().unwrap();
-        let destination: [u8; 4] = m[41..45].try_into().unwrap();
-        let recipient: [u8; 32] = m[45..77].try_into().unwrap();
-        let body = m[77..].into();
-        Self {
-            version,
-            nonce: u32::from_be_bytes(nonce),
-            origin: u32::from_be_bytes(origin),
-            sender: H256::from(sender),
-            destination: u32::from_be_bytes(destination),
-            recipient: H256::from(recipient),
-            body,
+impl TryFrom<&RawHyperlaneMessage> for HyperlaneMessage {
+    type Error = HyperlaneProtocolError;
+
+    fn try_from(m: &RawHyperlaneMessage) -> Result<Self, Self::Error> {
+        Ok(HyperlaneMessageRef::new(m)?.into_owned())
+    }
+}
+
+/// A Hyperlane message borrowed from its encoding. Fields are read in place,
+/// so messages can be inspected and filtered without copying their body.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct HyperlaneMessageRef<'a> {
+    raw: &'a [u8],
+}
+
+impl<'a> HyperlaneMessageRef<'a> {
+    /// Borrow a message from its encoding
+    pub fn new(raw: &'a [u8]) -> Result<Self, HyperlaneProtocolError> {
+        if raw.len() < HYPERLANE_MESSAGE_PREFIX_LEN {
+            return Err(HyperlaneProtocolError::MessageTooShort(raw.len()));
         }
+        Ok(Self { raw })
+    }
+
+    /// Hyperlane version number
+    pub fn version(&self) -> u8 {
+        self.raw[0]
+    }
+
+    /// Message nonce
+    pub fn nonce(&self) -> u32 {
+        self.read_u32(1)
+    }
+
+    /// Origin domain ID
+    pub fn origin(&self) -> u32 {
+        self.read_u32(5)
+    }
+
+    /// Address in origin convention
+    pub fn sender(&self) -> H256 {
+        H256::from_slice(&self.raw[9..41])
+    }
+
+    /// Destination domain ID
+    pub fn destination(&self) -> u32 {
+        self.read_u32(41)
+    }
+
+    /// Address in destination convention
+    pub fn recipient(&self) -> H256 {
+        H256::from_slice(&self.raw[45..77])
+    }
+
+    /// Message contents
+    pub fn body(&self) -> &'a [u8] {
+        &self.raw[HYPERLANE_MESSAGE_PREFIX_LEN..]
+    }
+
+    /// The encoded message
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.raw
+    }
+
+    /// Convert the message to a message id, hashing the encoding in place
+    pub fn id(&self) -> H256 {
+        H256::from_slice(Keccak256::new().chain(self.raw).finalize().as_slice())
+    }
+
+    /// Copy the message, e.g. to store it
+    pub fn into_owned(self) -> HyperlaneMessage {
+        HyperlaneMessage {
+            version: self.version(),
+            nonce: self.nonce(),
+            origin: self.origin(),
+            sender: self.sender(),
+            destination: self.destination(),
+            recipient: self.recipient(),
+            body: self.body().to_vec(),
+        }
+    }
+
+    fn read_u32(&self, offset: usize) -> u32 {
+        u32::from_be_bytes(self.raw[offset..offset + 4].try_into().unwrap())
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for HyperlaneMessageRef<'a> {
+    type Error = HyperlaneProtocolError;
+
+    fn try_from(raw: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::new(raw)
+    }
+}
+
+impl From<HyperlaneMessageRef<'_>> for HyperlaneMessage {
+    fn from(m: HyperlaneMessageRef<'_>) -> Self {
+        m.into_owned()
+    }
+}
+
+impl Debug for HyperlaneMessageRef<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "HyperlaneMessageRef {{ id: {:?}, nonce: {}, origin: {}, destination: {}, .. }}",
+            self.id(),
+            self.nonce(),
+            fmt_domain(self.origin()),
+            fmt_domain(self.destination()),
+        )
     }
 }
 
@@ -170,3 +265,47 @@ impl HyperlaneMessage {
         H256::from_slice(Keccak256::new().chain(self.to_vec()).finalize().as_slice())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn message() -> HyperlaneMessage {
+        HyperlaneMessage {
+            version: 3,
+            nonce: 7,
+            origin: 1,
+            sender: H256::repeat_byte(1),
+            destination: 2,
+            recipient: H256::repeat_byte(2),
+            body: b"hello".to_vec(),
+        }
+    }
+
+    #[test]
+    fn borrowed_message_matches_owned() {
+        let message = message();
+        let raw = RawHyperlaneMessage::from(&message);
+        let borrowed = HyperlaneMessageRef::new(&raw).unwrap();
+
+        assert_eq!(borrowed.version(), message.version);
+        assert_eq!(borrowed.nonce(), message.nonce);
+        assert_eq!(borrowed.origin(), message.origin);
+        assert_eq!(borrowed.sender(), message.sender);
+        assert_eq!(borrowed.destination(), message.destination);
+        assert_eq!(borrowed.recipient(), message.recipient);
+        assert_eq!(borrowed.body(), message.body.as_slice());
+        assert_eq!(borrowed.id(), message.id());
+        assert_eq!(borrowed.into_owned(), message);
+    }
+
+    #[test]
+    fn borrowed_message_rejects_truncated_encoding() {
+        let raw = RawHyperlaneMessage::from(&message());
+        assert!(matches!(
+            HyperlaneMessageRef::new(&raw[..HYPERLANE_MESSAGE_PREFIX_LEN - 1]),
+            Err(HyperlaneProtocolError::MessageTooShort(76))
+        ));
+        assert!(HyperlaneMessageRef::new(&raw[..HYPERLANE_MESSAGE_PREFIX_LEN]).is_ok());
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{HyperlaneMessage, HyperlaneMessageRef, HyperlaneProtocolError, RawHyperlaneMessage};
+
+/// A version of the Hyperlane message format, i.e. of the first byte of
+/// encoded messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "u8", try_from = "u8")]
+#[repr(u8)]
+pub enum MessageVersion {
+    /// Messages of legacy (v2) mailboxes
+    V2 = 0,
+    /// Messages of current (v3) mailboxes
+    V3 = 3,
+}
+
+impl MessageVersion {
+    /// The version of the messages dispatched by current mailboxes
+    pub const CURRENT: Self = Self::V3;
+
+    /// Length of the fixed size fields preceding the body
+    pub fn header_len(&self) -> usize {
+        match self {
+            // v3 kept the layout of v2, only bumping the version
+            Self::V2 | Self::V3 => super::message::HYPERLANE_MESSAGE_PREFIX_LEN,
+        }
+    }
+
+    /// Decode a message of this version
+    pub fn decode(&self, raw: &[u8]) -> Result<HyperlaneMessage, HyperlaneProtocolError> {
+        let Some(&version) = raw.first() else {
+            return Err(HyperlaneProtocolError::MessageTooShort(0));
+        };
+        if version != u8::from(*self) {
+            return Err(HyperlaneProtocolError::UnsupportedMessageVersion(version));
+        }
+        if raw.len() < self.header_len() {
+            return Err(HyperlaneProtocolError::MessageTooShort(raw.len()));
+        }
+        match self {
+            Self::V2 | Self::V3 => Ok(HyperlaneMessageRef::new(raw)?.into_owned()),
+        }
+    }
+
+    /// Encode a message of this version
+    pub fn encode(
+        &self,
+        message: &HyperlaneMessage,
+    ) -> Result<RawHyperlaneMessage, HyperlaneProtocolError> {
+        if message.version != u8::from(*self) {
+            return Err(HyperlaneProtocolError::UnsupportedMessageVersion(
+                message.version,
+            ));
+        }
+        match self {
+            Self::V2 | Self::V3 => Ok(RawHyperlaneMessage::from(message)),
+        }
+    }
+}
+
+impl From<MessageVersion> for u8 {
+    fn from(version: MessageVersion) -> Self {
+        version as u8
+    }
+}
+
+impl TryFrom<u8> for MessageVersion {
+    type Error = HyperlaneProtocolError;
+
+    fn try_from(version: u8) -> Result<Self, Self::Error> {
+        match version {
+            0 => Ok(Self::V2),
+            3 => Ok(Self::V3),
+            _ => Err(HyperlaneProtocolError::UnsupportedMessageVersion(version)),
+        }
+    }
+}
+
+impl Display for MessageVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", u8::from(*self))
+    }
+}
+
+/// The message versions the mailbox of a domain dispatches and processes.
+/// While a domain is upgraded, it accepts both the old and the new version,
+/// so that agents handle messages of either until the upgrade is over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageVersions(Vec<MessageVersion>);
+
+impl Default for MessageVersions {
+    fn default() -> Self {
+        Self(vec![MessageVersion::CURRENT])
+    }
+}
+
+impl MessageVersions {
+    /// The given versions, or the default ones if there are none
+    pub fn new(versions: Vec<MessageVersion>) -> Self {
+        if versions.is_empty() {
+            return Self::default();
+        }
+        Self(versions)
+    }
+
+    /// The versions, in the order they were configured
+    pub fn versions(&self) -> &[MessageVersion] {
+        &self.0
+    }
+
+    /// Whether messages with the version byte `version` are supported
+    pub fn supports(&self, version: u8) -> bool {
+        MessageVersion::try_from(version).is_ok_and(|version| self.0.contains(&version))
+    }
+
+    /// Decode a message of one of the supported versions
+    pub fn decode(&self, raw: &[u8]) -> Result<HyperlaneMessage, HyperlaneProtocolError> {
+        let Some(&version) = raw.first() else {
+            return Err(HyperlaneProtocolError::MessageTooShort(0));
+        };
+        self.supported(version)?.decode(raw)
+    }
+
+    /// Encode a message of one of the supported versions
+    pub fn encode(
+        &self,
+        message: &HyperlaneMessage,
+    ) -> Result<RawHyperlaneMessage, HyperlaneProtocolError> {
+        self.supported(message.version)?.encode(message)
+    }
+
+    fn supported(&self, version: u8) -> Result<MessageVersion, HyperlaneProtocolError> {
+        MessageVersion::try_from(version)
+            .ok()
+            .filter(|version| self.0.contains(version))
+            .ok_or(HyperlaneProtocolError::UnsupportedMessageVersion(version))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::H256;
+
+    #[test]
+    fn test_versions_straddle_upgrade() {
+        let legacy = HyperlaneMessage {
+            version: MessageVersion::V2.into(),
+            ..Default::default()
+        };
+        let current = HyperlaneMessage::default();
+        let upgrading = MessageVersions::new(vec![MessageVersion::V2, MessageVersion::V3]);
+        let upgraded = MessageVersions::default();
+
+        for message in [&legacy, &current] {
+            let raw = upgrading.encode(message).unwrap();
+            assert_eq!(&upgrading.decode(&raw).unwrap(), message);
+        }
+        assert!(upgraded.supports(current.version));
+        assert!(!upgraded.supports(legacy.version));
+        assert!(matches!(
+            upgraded.decode(&RawHyperlaneMessage::from(&legacy)),
+            Err(HyperlaneProtocolError::UnsupportedMessageVersion(0))
+        ));
+        assert!(matches!(
+            upgrading.encode(&HyperlaneMessage {
+                version: 1,
+                ..Default::default()
+            }),
+            Err(HyperlaneProtocolError::UnsupportedMessageVersion(1))
+        ));
+        assert_eq!(MessageVersions::new(vec![]), upgraded);
+    }
+
+    #[test]
+    fn test_round_trip_per_version() {
+        for version in [MessageVersion::V2, MessageVersion::V3] {
+            let message = HyperlaneMessage {
+                version: version.into(),
+                nonce: 42,
+                origin: 1,
+                sender: H256::repeat_byte(1),
+                destination: 2,
+                recipient: H256::repeat_byte(2),
+                body: b"hello".to_vec(),
+            };
+            let raw = version.encode(&message).unwrap();
+            assert_eq!(raw.len(), version.header_len() + message.body.len());
+            assert_eq!(version.decode(&raw).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn test_rejects_other_versions() {
+        let legacy = HyperlaneMessage {
+            version: MessageVersion::V2.into(),
+            ..Default::default()
+        };
+        let raw = RawHyperlaneMessage::from(&legacy);
+
+        assert!(matches!(
+            MessageVersion::V3.encode(&legacy),
+            Err(HyperlaneProtocolError::UnsupportedMessageVersion(0))
+        ));
+        assert!(matches!(
+            MessageVersion::V3.decode(&raw),
+            Err(HyperlaneProtocolError::UnsupportedMessageVersion(0))
+        ));
+        assert!(matches!(
+            MessageVersion::V2.decode(&raw[..10]),
+            Err(HyperlaneProtocolError::MessageTooShort(10))
+        ));
+        assert!(matches!(
+            MessageVersion::V2.decode(&[]),
+            Err(HyperlaneProtocolError::MessageTooShort(0))
+        ));
+    }
+}
@@ -1,37 +1,54 @@
 use std::fmt;
 use std::io::{Read, Write};
-use std::ops::Add;
+use std::ops::{Add, Sub};
 
+use num::CheckedDiv;
 use serde::{Deserialize, Serialize};
 
 pub use self::primitive_types::*;
 #[cfg(feature = "ethers")]
 pub use ::primitive_types as ethers_core_types;
 pub use account_address_type::AccountAddressType;
+pub use address_formatter::*;
+pub use amount::*;
 pub use announcement::*;
 pub use chain_data::*;
 pub use checkpoint::*;
+pub use gas_oracle::*;
+pub use hook_metadata::*;
 pub use indexing::*;
+#[cfg(feature = "legacy")]
+pub use legacy::*;
 pub use log_metadata::*;
 pub use merkle_tree::*;
 pub use message::*;
+pub use message_version::*;
 pub use reorg::*;
 pub use transaction::*;
+pub use transfer_remote::*;
 
 use crate::{Decode, Encode, HyperlaneProtocolError};
 
 /// This module contains enum for account address type
 mod account_address_type;
+mod address_formatter;
+mod amount;
 mod announcement;
 mod chain_data;
 mod checkpoint;
+mod gas_oracle;
+mod hook_metadata;
 mod indexing;
+#[cfg(feature = "legacy")]
+mod legacy;
 mod log_metadata;
 mod merkle_tree;
 mod message;
+mod message_version;
 mod reorg;
 mod serialize;
 mod transaction;
+mod transfer_remote;
 
 /// Unified 32-byte identifier with convenience tooling for handling
 /// 20-byte ids (e.g ethereum addresses)
@@ -190,6 +207,28 @@ impl Add for InterchainGasPayment {
     }
 }
 
+impl Sub for InterchainGasPayment {
+    type Output = Self;
+
+    /// Remove a payment from a total, saturating at zero
+    fn sub(self, rhs: Self) -> Self {
+        assert_eq!(
+            self.message_id, rhs.message_id,
+            "Cannot subtract interchain gas payments for different messages"
+        );
+        assert_eq!(
+            self.destination, rhs.destination,
+            "Cannot subtract interchain gas payments for different destinations"
+        );
+        Self {
+            message_id: self.message_id,
+            destination: self.destination,
+            payment: self.payment.saturating_sub(rhs.payment),
+            gas_amount: self.gas_amount.saturating_sub(rhs.gas_amount),
+        }
+    }
+}
+
 impl Add for InterchainGasExpenditure {
     type Output = Self;
 
@@ -263,11 +302,39 @@ pub struct TxCostEstimate {
     /// is used to cover L1 and L2 costs. For details:
     /// `<https://medium.com/offchainlabs/understanding-arbitrum-2-dimensional-fees-fd1d582596c9>`
     pub l2_gas_limit: Option<U256>,
+    /// The fee for posting the transaction data to L1, in the native token.
+    /// Only present for rollups, where it is charged on top of the L2 gas.
+    pub l1_data_fee: Option<U256>,
 }
 
 impl TxCostEstimate {
-    /// The gas limit to be used by gas enforcement policies.
+    /// The gas limit to be used by gas enforcement policies, including the
+    /// gas the L1 data fee is worth at the gas price.
     pub fn enforceable_gas_limit(&self) -> U256 {
-        self.l2_gas_limit.unwrap_or(self.gas_limit)
+        self.l2_gas_limit
+            .unwrap_or(self.gas_limit)
+            .saturating_add(self.l1_data_fee_gas())
+    }
+
+    /// The total cost of the transaction in the native token, covering the L2
+    /// gas and the L1 data fee.
+    pub fn total_cost(&self) -> FixedPointNumber {
+        let l2_gas = FixedPointNumber::try_from(self.l2_gas_limit.unwrap_or(self.gas_limit))
+            .unwrap_or_default();
+        let l1_data_fee =
+            FixedPointNumber::try_from(self.l1_data_fee.unwrap_or_default()).unwrap_or_default();
+        l2_gas * self.gas_price.clone() + l1_data_fee
+    }
+
+    /// The L1 data fee divided by the gas price, rounded up
+    fn l1_data_fee_gas(&self) -> U256 {
+        let Some(l1_data_fee) = self.l1_data_fee else {
+            return U256::zero();
+        };
+        FixedPointNumber::try_from(l1_data_fee)
+            .ok()
+            .and_then(|fee| fee.checked_div(&self.gas_price))
+            .and_then(|gas| gas.ceil_to_integer().try_into().ok())
+            .unwrap_or_default()
     }
 }
@@ -0,0 +1,76 @@
+use crate::{HyperlaneMessage, H256, U256};
+
+/// Length of a warp route token message body without metadata: a 32 byte
+/// recipient followed by a 32 byte big endian amount or token id.
+const TOKEN_MESSAGE_LEN: usize = 32 + 32;
+
+/// A warp route transfer to a remote chain, as dispatched by the origin router.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TransferRemote {
+    /// Id of the message carrying the transfer
+    pub message_id: H256,
+    /// Nonce of the message carrying the transfer
+    pub nonce: u32,
+    /// The router the transfer was sent from
+    pub router: H256,
+    /// Destination domain of the transfer
+    pub destination: u32,
+    /// The recipient on the destination chain
+    pub recipient: H256,
+    /// The amount, in the remote token's decimals, or the token id
+    pub amount_or_id: U256,
+}
+
+impl TransferRemote {
+    /// Decode the transfer carried by a message dispatched by a warp route
+    /// router. Returns `None` if the body is not a token message.
+    pub fn from_message(message: &HyperlaneMessage) -> Option<Self> {
+        if message.body.len() < TOKEN_MESSAGE_LEN {
+            return None;
+        }
+        Some(Self {
+            message_id: message.id(),
+            nonce: message.nonce,
+            router: message.sender,
+            destination: message.destination,
+            recipient: H256::from_slice(&message.body[..32]),
+            amount_or_id: U256::from_big_endian(&message.body[32..TOKEN_MESSAGE_LEN]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_message() {
+        let recipient = H256::repeat_byte(7);
+        let mut amount = [0u8; 32];
+        U256::from(1_000_000u64).to_big_endian(&mut amount);
+        let message = HyperlaneMessage {
+            nonce: 3,
+            sender: H256::repeat_byte(1),
+            destination: 10,
+            body: [recipient.as_bytes(), &amount, b"metadata"].concat(),
+            ..Default::default()
+        };
+
+        let transfer = TransferRemote::from_message(&message).unwrap();
+        assert_eq!(transfer.message_id, message.id());
+        assert_eq!(transfer.nonce, 3);
+        assert_eq!(transfer.router, message.sender);
+        assert_eq!(transfer.destination, 10);
+        assert_eq!(transfer.recipient, recipient);
+        assert_eq!(transfer.amount_or_id, U256::from(1_000_000u64));
+    }
+
+    #[test]
+    fn test_from_non_token_message() {
+        let message = HyperlaneMessage {
+            body: vec![0; 40],
+            ..Default::default()
+        };
+        assert_eq!(TransferRemote::from_message(&message), None);
+    }
+}
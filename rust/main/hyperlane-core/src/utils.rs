@@ -5,7 +5,9 @@ use std::str::FromStr;
 #[cfg(feature = "float")]
 use std::time::Duration;
 
-use crate::{KnownHyperlaneDomain, H160, H256};
+#[cfg(not(feature = "agent"))]
+use crate::KnownHyperlaneDomain;
+use crate::{H160, H256};
 
 /// Converts a hex or base58 string to an H256.
 pub fn hex_or_base58_to_h256(string: &str) -> Result<H256> {
@@ -52,9 +54,19 @@ pub fn announcement_domain_hash(address: H256, domain: impl Into<u32>) -> H256 {
 
 /// Pretty print an address based on the domain it is for.
 pub fn fmt_address_for_domain(domain: u32, addr: H256) -> String {
-    KnownHyperlaneDomain::try_from(domain)
-        .map(|d| d.domain_protocol().fmt_address(addr))
-        .unwrap_or_else(|_| format!("{addr:?}"))
+    #[cfg(feature = "agent")]
+    {
+        crate::domain_registry()
+            .by_id(domain)
+            .map(|d| d.protocol.fmt_address(addr))
+            .unwrap_or_else(|| format!("{addr:?}"))
+    }
+    #[cfg(not(feature = "agent"))]
+    {
+        KnownHyperlaneDomain::try_from(domain)
+            .map(|d| d.domain_protocol().fmt_address(addr))
+            .unwrap_or_else(|_| format!("{addr:?}"))
+    }
 }
 
 /// Pretty print a byte slice, including a hex prefix
@@ -64,7 +76,14 @@ pub fn bytes_to_hex(bytes: &[u8]) -> String {
 
 /// Format a domain id as a name if it is known or just the number if not.
 pub fn fmt_domain(domain: u32) -> String {
-    #[cfg(feature = "strum")]
+    #[cfg(feature = "agent")]
+    {
+        crate::domain_registry()
+            .by_id(domain)
+            .map(|d| d.name.clone())
+            .unwrap_or_else(|| domain.to_string())
+    }
+    #[cfg(all(feature = "strum", not(feature = "agent")))]
     {
         KnownHyperlaneDomain::try_from(domain)
             .map(|d| d.to_string())
@@ -0,0 +1,85 @@
+#![allow(non_snake_case)]
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+
+use async_trait::async_trait;
+use mockall::*;
+
+use hyperlane_core::*;
+
+mock! {
+    pub Indexer<T: 'static + Send + Sync + Debug> {}
+
+    impl<T: 'static + Send + Sync + Debug> Debug for Indexer<T> {
+        fn fmt<'a>(&self, f: &mut std::fmt::Formatter<'a>) -> std::fmt::Result;
+    }
+
+    #[async_trait]
+    impl<T: 'static + Send + Sync + Debug> Indexer<T> for Indexer<T> {
+        async fn fetch_logs_in_range(
+            &self,
+            range: RangeInclusive<u32>,
+        ) -> ChainResult<Vec<(Indexed<T>, LogMeta)>>;
+        async fn get_finalized_block_number(&self) -> ChainResult<u32>;
+        async fn fetch_logs_by_tx_hash(
+            &self,
+            tx_hash: H512,
+        ) -> ChainResult<Vec<(Indexed<T>, LogMeta)>>;
+        async fn get_block_hash(&self, height: u32) -> ChainResult<Option<H256>>;
+    }
+
+    #[async_trait]
+    impl<T: 'static + Send + Sync + Debug> SequenceAwareIndexer<T> for Indexer<T> {
+        async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)>;
+    }
+}
+
+/// Mock indexer of dispatched messages
+pub type MockMessageIndexer = MockIndexer<HyperlaneMessage>;
+
+/// Mock indexer of delivered message ids
+pub type MockDeliveryIndexer = MockIndexer<H256>;
+
+/// Mock indexer of interchain gas payments
+pub type MockInterchainGasPaymentIndexer = MockIndexer<InterchainGasPayment>;
+
+/// Mock indexer of merkle tree insertions
+pub type MockMerkleTreeInsertionIndexer = MockIndexer<MerkleTreeInsertion>;
+
+/// A sequence aware indexer with a fixed sequence count and tip, which never
+/// finds any log. For tests of cursors which only query the latest sequence.
+#[derive(Debug, Clone)]
+pub struct MockLatestSequenceQuerier {
+    /// The sequence count returned by `latest_sequence_count_and_tip`
+    pub latest_sequence_count: Option<u32>,
+    /// The tip returned by `latest_sequence_count_and_tip` and
+    /// `get_finalized_block_number`
+    pub tip: u32,
+}
+
+#[async_trait]
+impl<T> SequenceAwareIndexer<T> for MockLatestSequenceQuerier
+where
+    T: Sequenced + Debug,
+{
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        Ok((self.latest_sequence_count, self.tip))
+    }
+}
+
+#[async_trait]
+impl<T> Indexer<T> for MockLatestSequenceQuerier
+where
+    T: Sequenced + Debug,
+{
+    async fn fetch_logs_in_range(
+        &self,
+        _range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<T>, LogMeta)>> {
+        Ok(vec![])
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        Ok(self.tip)
+    }
+}
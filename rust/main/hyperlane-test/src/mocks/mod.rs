@@ -1,6 +1,9 @@
+/// Mock indexers
+pub mod indexer;
 /// Mock mailbox contract
 pub mod mailbox;
 pub mod validator_announce;
 
+pub use indexer::*;
 pub use mailbox::MockMailboxContract;
 pub use validator_announce::MockValidatorAnnounceContract;
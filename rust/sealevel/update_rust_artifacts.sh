@@ -0,0 +1,17 @@
+#!/bin/sh
+
+# Must be ran from the `rust/sealevel` directory
+# Builds and exports the programs the rust localnet test utils deploy
+
+ARTIFACTS_DIR=../main/chains/hyperlane-sealevel/artifacts
+mkdir -p "$ARTIFACTS_DIR"
+
+build() {
+    cargo build-sbf --manifest-path programs/"$1"/Cargo.toml --sbf-out-dir target/deploy
+}
+
+build mailbox && \
+build hyperlane-sealevel-igp && \
+cp target/deploy/hyperlane_sealevel_mailbox.so \
+    target/deploy/hyperlane_sealevel_igp.so \
+    "$ARTIFACTS_DIR"
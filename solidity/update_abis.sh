@@ -11,6 +11,8 @@ copy() {
 
 copy interfaces/IMailbox && \
 copy interfaces/IInterchainGasPaymaster && \
+copy hooks/igp/InterchainGasPaymaster && \
+copy hooks/igp/StorageGasOracle && \
 copy interfaces/IValidatorAnnounce && \
 copy interfaces/IInterchainSecurityModule && \
 copy interfaces/isms/IMultisigIsm && \
@@ -0,0 +1,20 @@
+#!/bin/sh
+
+# Must be ran from the `solidity` directory, after `yarn build`
+# Exports the artifacts the rust localnet fixtures deploy contracts from
+
+ARTIFACTS_DIR=../rust/main/chains/hyperlane-ethereum/artifacts
+mkdir -p "$ARTIFACTS_DIR"
+
+copy() {
+    # Optionally allow path to be passed in, and extract the contract name
+    # as the string following the last instance of `/`
+    CONTRACT_NAME="${1##*/}"
+    jq '{abi, bytecode}' < artifacts/contracts/"$1".sol/"$CONTRACT_NAME".json > "$ARTIFACTS_DIR"/"$CONTRACT_NAME".json
+}
+
+copy Mailbox && \
+copy hooks/MerkleTreeHook && \
+copy isms/NoopIsm && \
+copy isms/multisig/ValidatorAnnounce && \
+copy test/TestRecipient
@@ -1,9 +1,18 @@
 import { expect } from 'chai';
 
+import { objMap } from '@hyperlane-xyz/utils';
+
 import { TestChainName, testChainMetadata } from '../consts/testChains.js';
 import { MockCoinGecko } from '../test/MockCoinGecko.js';
+import { ChainMap, ChainName } from '../types.js';
 
-import { CoinGeckoTokenPriceGetter } from './token-prices.js';
+import {
+  CoinGeckoTokenPriceGetter,
+  MultiSourceTokenPriceGetter,
+  StaticPriceSource,
+  TokenPriceQuote,
+  TokenPriceSource,
+} from './token-prices.js';
 
 describe('TokenPriceGetter', () => {
   let tokenPriceGetter: CoinGeckoTokenPriceGetter;
@@ -49,3 +58,64 @@ describe('TokenPriceGetter', () => {
     });
   });
 });
+
+describe('MultiSourceTokenPriceGetter', () => {
+  const chain = TestChainName.test1;
+  const mainnetMetadata = objMap(testChainMetadata, (_, metadata) => ({
+    ...metadata,
+    isTestnet: false,
+  }));
+
+  class StalePriceSource implements TokenPriceSource {
+    readonly name = 'stale';
+
+    constructor(protected price: number) {}
+
+    async getTokenPrices(
+      chains: ChainName[],
+    ): Promise<ChainMap<TokenPriceQuote>> {
+      const timestamp = new Date(Date.now() - 2 * 60 * 60 * 1000);
+      return Object.fromEntries(
+        chains.map((c) => [c, { price: this.price, timestamp }]),
+      );
+    }
+  }
+
+  const getter = (sources: TokenPriceSource[]) =>
+    new MultiSourceTokenPriceGetter(sources, mainnetMetadata);
+
+  it('uses the most preferred source agreeing with the others', async () => {
+    const price = await getter([
+      new StaticPriceSource({ [chain]: 105 }),
+      new StaticPriceSource({ [chain]: 100 }),
+    ]).getTokenPrice(chain);
+    expect(price).to.equal(105);
+  });
+
+  it('ignores outliers', async () => {
+    const price = await getter([
+      new StaticPriceSource({ [chain]: 1000 }),
+      new StaticPriceSource({ [chain]: 100 }),
+      new StaticPriceSource({ [chain]: 101 }),
+    ]).getTokenPrice(chain);
+    expect(price).to.equal(100);
+  });
+
+  it('ignores stale quotes', async () => {
+    const price = await getter([
+      new StalePriceSource(1000),
+      new StaticPriceSource({ [chain]: 100 }),
+    ]).getTokenPrice(chain);
+    expect(price).to.equal(100);
+  });
+
+  it('fails when the sources disagree', async () => {
+    const disagreeing = getter([
+      new StaticPriceSource({ [chain]: 100 }),
+      new StaticPriceSource({ [chain]: 200 }),
+    ]);
+    let error: Error | undefined;
+    await disagreeing.getTokenPrice(chain).catch((e) => (error = e));
+    expect(error).to.not.be.undefined;
+  });
+});
@@ -1,8 +1,10 @@
 import CoinGecko from 'coingecko-api';
+import { ethers } from 'ethers';
 
-import { rootLogger, sleep } from '@hyperlane-xyz/utils';
+import { Address, rootLogger, sleep } from '@hyperlane-xyz/utils';
 
 import { ChainMetadata } from '../metadata/chainMetadataTypes.js';
+import { MultiProvider } from '../providers/MultiProvider.js';
 import { ChainMap, ChainName } from '../types.js';
 
 export interface TokenPriceGetter {
@@ -65,36 +67,17 @@ class TokenPriceCache {
   }
 }
 
-export class CoinGeckoTokenPriceGetter implements TokenPriceGetter {
-  protected coinGecko: CoinGeckoInterface;
+/**
+ * Prices the native tokens of mainnets from cached quotes, refreshed once
+ * they are no longer fresh. Testnet tokens are all priced at 1.0 USD.
+ */
+abstract class CachingTokenPriceGetter implements TokenPriceGetter {
   protected cache: TokenPriceCache;
-  protected sleepMsBetweenRequests: number;
   protected metadata: ChainMap<ChainMetadata>;
 
-  constructor(
-    coinGecko: CoinGeckoInterface,
-    chainMetadata: ChainMap<ChainMetadata>,
-    expirySeconds?: number,
-    sleepMsBetweenRequests = 5000,
-  ) {
-    this.coinGecko = coinGecko;
+  constructor(chainMetadata: ChainMap<ChainMetadata>, expirySeconds?: number) {
     this.cache = new TokenPriceCache(expirySeconds);
     this.metadata = chainMetadata;
-    this.sleepMsBetweenRequests = sleepMsBetweenRequests;
-  }
-
-  static withDefaultCoinGecko(
-    chainMetadata: ChainMap<ChainMetadata>,
-    expirySeconds?: number,
-    sleepMsBetweenRequests = 5000,
-  ): CoinGeckoTokenPriceGetter {
-    const coinGecko = new CoinGecko();
-    return new CoinGeckoTokenPriceGetter(
-      coinGecko,
-      chainMetadata,
-      expirySeconds,
-      sleepMsBetweenRequests,
-    );
   }
 
   async getTokenPrice(chain: ChainName): Promise<number> {
@@ -136,7 +119,40 @@ export class CoinGeckoTokenPriceGetter implements TokenPriceGetter {
     return chains.map((chain) => this.cache.fetch(chain));
   }
 
-  private async queryTokenPrices(chains: ChainName[]): Promise<void> {
+  // Updates the cache with the prices of the chains
+  protected abstract queryTokenPrices(chains: ChainName[]): Promise<void>;
+}
+
+export class CoinGeckoTokenPriceGetter extends CachingTokenPriceGetter {
+  protected coinGecko: CoinGeckoInterface;
+  protected sleepMsBetweenRequests: number;
+
+  constructor(
+    coinGecko: CoinGeckoInterface,
+    chainMetadata: ChainMap<ChainMetadata>,
+    expirySeconds?: number,
+    sleepMsBetweenRequests = 5000,
+  ) {
+    super(chainMetadata, expirySeconds);
+    this.coinGecko = coinGecko;
+    this.sleepMsBetweenRequests = sleepMsBetweenRequests;
+  }
+
+  static withDefaultCoinGecko(
+    chainMetadata: ChainMap<ChainMetadata>,
+    expirySeconds?: number,
+    sleepMsBetweenRequests = 5000,
+  ): CoinGeckoTokenPriceGetter {
+    const coinGecko = new CoinGecko();
+    return new CoinGeckoTokenPriceGetter(
+      coinGecko,
+      chainMetadata,
+      expirySeconds,
+      sleepMsBetweenRequests,
+    );
+  }
+
+  protected async queryTokenPrices(chains: ChainName[]): Promise<void> {
     const currency = 'usd';
     // The CoinGecko API expects, in some cases, IDs that do not match
     // ChainNames.
@@ -154,3 +170,264 @@ export class CoinGeckoTokenPriceGetter implements TokenPriceGetter {
     chains.map((chain, i) => this.cache.put(chain, prices[i]));
   }
 }
+
+/** The USD price of a native token, as last updated by a price source */
+export type TokenPriceQuote = {
+  price: number;
+  timestamp: Date;
+};
+
+/** A source of the USD prices of the native tokens of chains */
+export interface TokenPriceSource {
+  readonly name: string;
+  /** Quotes of the chains the source prices, which may not be all of them */
+  getTokenPrices(chains: ChainName[]): Promise<ChainMap<TokenPriceQuote>>;
+}
+
+export class CoinGeckoPriceSource implements TokenPriceSource {
+  readonly name = 'coingecko';
+
+  constructor(
+    protected coinGecko: CoinGeckoInterface,
+    protected metadata: ChainMap<ChainMetadata>,
+  ) {}
+
+  async getTokenPrices(
+    chains: ChainName[],
+  ): Promise<ChainMap<TokenPriceQuote>> {
+    const ids = chains.map(
+      (chain) => this.metadata[chain].gasCurrencyCoinGeckoId || chain,
+    );
+    const response = await this.coinGecko.simple.price({
+      ids,
+      vs_currencies: ['usd'],
+      include_last_updated_at: true,
+    });
+    const quotes: ChainMap<TokenPriceQuote> = {};
+    chains.forEach((chain, i) => {
+      const data = response.data[ids[i]];
+      if (data?.usd === undefined) return;
+      quotes[chain] = {
+        price: data.usd,
+        timestamp: data.last_updated_at
+          ? new Date(data.last_updated_at * 1000)
+          : new Date(),
+      };
+    });
+    return quotes;
+  }
+}
+
+/** Prices tokens by the symbol of the native token of chains */
+export class CoinMarketCapPriceSource implements TokenPriceSource {
+  readonly name = 'coinmarketcap';
+
+  constructor(
+    protected apiKey: string,
+    protected metadata: ChainMap<ChainMetadata>,
+    protected baseUrl = 'https://pro-api.coinmarketcap.com',
+  ) {}
+
+  async getTokenPrices(
+    chains: ChainName[],
+  ): Promise<ChainMap<TokenPriceQuote>> {
+    const symbols = chains.map(
+      (chain) => this.metadata[chain].nativeToken?.symbol,
+    );
+    const url = new URL('/v2/cryptocurrency/quotes/latest', this.baseUrl);
+    url.searchParams.set(
+      'symbol',
+      [...new Set(symbols.filter((s) => !!s))].join(','),
+    );
+    url.searchParams.set('convert', 'USD');
+    const response = await fetch(url.toString(), {
+      headers: { 'X-CMC_PRO_API_KEY': this.apiKey },
+    });
+    if (!response.ok) {
+      throw new Error(
+        `CoinMarketCap request failed with status ${response.status}`,
+      );
+    }
+    const { data } = await response.json();
+    const quotes: ChainMap<TokenPriceQuote> = {};
+    chains.forEach((chain, i) => {
+      const symbol = symbols[i];
+      // Symbols aren't unique, and the most relevant token comes first
+      const usd = symbol ? data[symbol]?.[0]?.quote?.USD : undefined;
+      if (usd?.price === undefined) return;
+      quotes[chain] = {
+        price: usd.price,
+        timestamp: new Date(usd.last_updated),
+      };
+    });
+    return quotes;
+  }
+}
+
+/** A Chainlink USD price feed of the native token of a chain */
+export type ChainlinkFeed = {
+  /** The chain the feed is deployed on */
+  chain: ChainName;
+  address: Address;
+};
+
+const CHAINLINK_AGGREGATOR_ABI = [
+  'function decimals() view returns (uint8)',
+  'function latestRoundData() view returns (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound)',
+];
+
+export class ChainlinkPriceSource implements TokenPriceSource {
+  readonly name = 'chainlink';
+
+  constructor(
+    protected multiProvider: MultiProvider,
+    protected feeds: ChainMap<ChainlinkFeed>,
+  ) {}
+
+  async getTokenPrices(
+    chains: ChainName[],
+  ): Promise<ChainMap<TokenPriceQuote>> {
+    const quotes: ChainMap<TokenPriceQuote> = {};
+    await Promise.all(
+      chains
+        .filter((chain) => !!this.feeds[chain])
+        .map(async (chain) => {
+          const feed = this.feeds[chain];
+          const aggregator = new ethers.Contract(
+            feed.address,
+            CHAINLINK_AGGREGATOR_ABI,
+            this.multiProvider.getProvider(feed.chain),
+          );
+          const [decimals, round] = await Promise.all([
+            aggregator.decimals(),
+            aggregator.latestRoundData(),
+          ]);
+          quotes[chain] = {
+            price: parseFloat(ethers.utils.formatUnits(round.answer, decimals)),
+            timestamp: new Date(round.updatedAt.toNumber() * 1000),
+          };
+        }),
+    );
+    return quotes;
+  }
+}
+
+/** Fixed prices, e.g. of tokens no other source prices */
+export class StaticPriceSource implements TokenPriceSource {
+  readonly name = 'static';
+
+  constructor(protected prices: ChainMap<number>) {}
+
+  async getTokenPrices(
+    chains: ChainName[],
+  ): Promise<ChainMap<TokenPriceQuote>> {
+    const quotes: ChainMap<TokenPriceQuote> = {};
+    for (const chain of chains) {
+      if (this.prices[chain] === undefined) continue;
+      quotes[chain] = { price: this.prices[chain], timestamp: new Date() };
+    }
+    return quotes;
+  }
+}
+
+export type MultiSourceTokenPriceGetterOptions = {
+  /** How long fetched prices are used before being refreshed */
+  expirySeconds?: number;
+  /** How old a quote may be before it is ignored. Defaults to 1 hour. */
+  maxQuoteAgeSeconds?: number;
+  /**
+   * How far a quote may deviate from the median quote of a token before it
+   * is ignored as an outlier. Defaults to 0.1, i.e. 10%.
+   */
+  maxDeviation?: number;
+};
+
+/**
+ * Prices tokens from several sources, in order of preference. Stale quotes
+ * and quotes that deviate from the other sources are ignored, and the price
+ * of a token isn't updated if its sources don't mostly agree.
+ */
+export class MultiSourceTokenPriceGetter extends CachingTokenPriceGetter {
+  protected maxQuoteAgeSeconds: number;
+  protected maxDeviation: number;
+
+  constructor(
+    protected sources: TokenPriceSource[],
+    chainMetadata: ChainMap<ChainMetadata>,
+    options: MultiSourceTokenPriceGetterOptions = {},
+  ) {
+    super(chainMetadata, options.expirySeconds);
+    this.maxQuoteAgeSeconds = options.maxQuoteAgeSeconds ?? 60 * 60;
+    this.maxDeviation = options.maxDeviation ?? 0.1;
+  }
+
+  protected async queryTokenPrices(chains: ChainName[]): Promise<void> {
+    const results = await Promise.allSettled(
+      this.sources.map((source) => source.getTokenPrices(chains)),
+    );
+    results.forEach((result, i) => {
+      if (result.status === 'rejected') {
+        rootLogger.warn(
+          `Failed to query token prices from ${this.sources[i].name}`,
+          result.reason,
+        );
+      }
+    });
+
+    const oldestTimestamp = Date.now() - 1000 * this.maxQuoteAgeSeconds;
+    for (const chain of chains) {
+      const prices: Array<{ source: string; price: number }> = [];
+      results.forEach((result, i) => {
+        if (result.status === 'rejected') return;
+        const source = this.sources[i].name;
+        const quote = result.value[chain];
+        if (!quote) return;
+        if (quote.timestamp.getTime() < oldestTimestamp) {
+          rootLogger.warn(
+            `Ignoring stale ${chain} token price from ${source}, last updated at ${quote.timestamp.toISOString()}`,
+          );
+          return;
+        }
+        prices.push({ source, price: quote.price });
+      });
+
+      const price = this.checkedPrice(chain, prices);
+      if (price !== undefined) this.cache.put(chain, price);
+    }
+  }
+
+  // The price of the most preferred source agreeing with most other sources
+  protected checkedPrice(
+    chain: ChainName,
+    prices: Array<{ source: string; price: number }>,
+  ): number | undefined {
+    if (prices.length === 0) {
+      rootLogger.warn(`No fresh token price found for ${chain}`);
+      return undefined;
+    }
+    const median = medianOf(prices.map(({ price }) => price));
+    const agreeing = prices.filter(({ source, price }) => {
+      const deviation = Math.abs(price / median - 1);
+      if (deviation > this.maxDeviation) {
+        rootLogger.warn(
+          `Ignoring ${chain} token price of ${price} from ${source}, deviating by ${deviation} from the median price of ${median}`,
+        );
+        return false;
+      }
+      return true;
+    });
+    if (agreeing.length * 2 <= prices.length) {
+      rootLogger.warn(`Token price sources disagree on ${chain}`, prices);
+      return undefined;
+    }
+    return agreeing[0].price;
+  }
+}
+
+function medianOf(values: number[]): number {
+  const sorted = values.slice().sort((a, b) => a - b);
+  const mid = Math.floor(sorted.length / 2);
+  return sorted.length % 2 === 0
+    ? (sorted[mid - 1] + sorted[mid]) / 2
+    : sorted[mid];
+}
@@ -123,7 +123,19 @@ export { HyperlaneIgp } from './gas/HyperlaneIgp.js';
 export { HyperlaneIgpChecker } from './gas/HyperlaneIgpChecker.js';
 export { HyperlaneIgpDeployer } from './gas/HyperlaneIgpDeployer.js';
 export { StorageGasOracleConfig } from './gas/oracle/types.js';
-export { CoinGeckoTokenPriceGetter } from './gas/token-prices.js';
+export {
+  ChainlinkFeed,
+  ChainlinkPriceSource,
+  CoinGeckoPriceSource,
+  CoinGeckoTokenPriceGetter,
+  CoinMarketCapPriceSource,
+  MultiSourceTokenPriceGetter,
+  MultiSourceTokenPriceGetterOptions,
+  StaticPriceSource,
+  TokenPriceGetter,
+  TokenPriceQuote,
+  TokenPriceSource,
+} from './gas/token-prices.js';
 export {
   IgpBeneficiaryViolation,
   IgpConfig,
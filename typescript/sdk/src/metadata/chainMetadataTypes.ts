@@ -40,6 +40,14 @@ export const RpcUrlSchema = z.object({
     .positive()
     .optional()
     .describe('Maximum number of concurrent RPC requests.'),
+  requestsPerSecond: z
+    .number()
+    .int()
+    .positive()
+    .optional()
+    .describe(
+      'Maximum number of RPC requests per second. Agents queue the requests over it.',
+    ),
   webSocket: z
     .string()
     .optional()